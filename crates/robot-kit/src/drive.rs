@@ -17,7 +17,7 @@ use tokio::sync::Mutex;
 
 /// Drive backend abstraction
 #[async_trait]
-trait DriveBackend: Send + Sync {
+pub trait DriveBackend: Send + Sync {
     async fn move_robot(
         &self,
         linear_x: f64,
@@ -30,8 +30,44 @@ trait DriveBackend: Send + Sync {
     async fn get_odometry(&self) -> Result<(f64, f64, f64)>; // x, y, theta - reserved for future odometry integration
 }
 
-/// Mock backend for testing
-struct MockDrive;
+/// Command received by [`MockDrive`]
+#[derive(Debug, Clone, PartialEq)]
+pub enum MotorCommand {
+    Move {
+        linear_x: f64,
+        linear_y: f64,
+        angular_z: f64,
+        duration_ms: u64,
+    },
+    Stop,
+}
+
+/// Mock backend for testing - logs and records every command
+#[derive(Default)]
+pub struct MockDrive {
+    commands: std::sync::Mutex<Vec<MotorCommand>>,
+}
+
+impl MockDrive {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Commands received so far, oldest first
+    pub fn commands(&self) -> Vec<MotorCommand> {
+        self.commands
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .clone()
+    }
+
+    fn record(&self, command: MotorCommand) {
+        self.commands
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .push(command);
+    }
+}
 
 #[async_trait]
 impl DriveBackend for MockDrive {
@@ -49,12 +85,19 @@ impl DriveBackend for MockDrive {
             angular_z,
             duration_ms
         );
+        self.record(MotorCommand::Move {
+            linear_x,
+            linear_y,
+            angular_z,
+            duration_ms,
+        });
         tokio::time::sleep(Duration::from_millis(duration_ms.min(100))).await;
         Ok(())
     }
 
     async fn stop(&self) -> Result<()> {
         tracing::info!("MOCK DRIVE: STOP");
+        self.record(MotorCommand::Stop);
         Ok(())
     }
 
@@ -194,9 +237,14 @@ impl DriveTool {
                 port: config.drive.serial_port.clone(),
             }),
             // "gpio" => Arc::new(GpioDrive::new(&config)), // Would use rppal
-            _ => Arc::new(MockDrive),
+            _ => Arc::new(MockDrive::new()),
         };
 
+        Self::with_backend(config, backend)
+    }
+
+    /// Create with an explicit backend (e.g. a shared [`MockDrive`] in tests)
+    pub fn with_backend(config: RobotConfig, backend: Arc<dyn DriveBackend>) -> Self {
         Self {
             config,
             backend,
@@ -419,6 +467,22 @@ mod tests {
         assert!(result.output.contains("stopped"));
     }
 
+    #[tokio::test]
+    async fn mock_drive_records_commands() {
+        let mock = Arc::new(MockDrive::new());
+        let tool = DriveTool::with_backend(RobotConfig::default(), mock.clone());
+        tool.execute(json!({"action": "forward", "distance": 0.1}))
+            .await
+            .unwrap();
+        *tool.last_command.lock().await = None;
+        tool.execute(json!({"action": "stop"})).await.unwrap();
+
+        let commands = mock.commands();
+        assert_eq!(commands.len(), 2);
+        assert!(matches!(commands[0], MotorCommand::Move { linear_x, .. } if linear_x > 0.0));
+        assert_eq!(commands[1], MotorCommand::Stop);
+    }
+
     #[tokio::test]
     async fn drive_unknown_action() {
         let tool = DriveTool::new(RobotConfig::default());
@@ -108,7 +108,7 @@ mod tests;
 pub use config::RobotConfig;
 pub use traits::{Tool, ToolResult, ToolSpec};
 
-pub use drive::DriveTool;
+pub use drive::{DriveBackend, DriveTool, MockDrive, MotorCommand};
 pub use emote::EmoteTool;
 pub use listen::ListenTool;
 pub use look::LookTool;
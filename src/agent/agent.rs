@@ -5,7 +5,7 @@ use crate::agent::memory_loader::{DefaultMemoryLoader, MemoryLoader};
 use crate::agent::prompt::{PromptContext, SystemPromptBuilder};
 use crate::config::Config;
 use crate::memory::{self, Memory, MemoryCategory};
-use crate::observability::{self, Observer, ObserverEvent};
+use crate::observability::{self, Observer, ObserverEvent, TraceSpan};
 use crate::providers::{self, ChatMessage, ChatRequest, ConversationMessage, Provider};
 use crate::runtime;
 use crate::security::SecurityPolicy;
@@ -26,6 +26,7 @@ pub struct Agent {
     tool_dispatcher: Box<dyn ToolDispatcher>,
     memory_loader: Box<dyn MemoryLoader>,
     config: crate::config::AgentConfig,
+    provider_name: String,
     model_name: String,
     temperature: f64,
     workspace_dir: std::path::PathBuf,
@@ -48,6 +49,7 @@ pub struct AgentBuilder {
     tool_dispatcher: Option<Box<dyn ToolDispatcher>>,
     memory_loader: Option<Box<dyn MemoryLoader>>,
     config: Option<crate::config::AgentConfig>,
+    provider_name: Option<String>,
     model_name: Option<String>,
     temperature: Option<f64>,
     workspace_dir: Option<std::path::PathBuf>,
@@ -71,6 +73,7 @@ impl AgentBuilder {
             tool_dispatcher: None,
             memory_loader: None,
             config: None,
+            provider_name: None,
             model_name: None,
             temperature: None,
             workspace_dir: None,
@@ -124,6 +127,11 @@ impl AgentBuilder {
         self
     }
 
+    pub fn provider_name(mut self, provider_name: String) -> Self {
+        self.provider_name = Some(provider_name);
+        self
+    }
+
     pub fn model_name(mut self, model_name: String) -> Self {
         self.model_name = Some(model_name);
         self
@@ -208,6 +216,7 @@ impl AgentBuilder {
                 .memory_loader
                 .unwrap_or_else(|| Box::new(DefaultMemoryLoader::default())),
             config: self.config.unwrap_or_default(),
+            provider_name: self.provider_name.unwrap_or_else(|| "unknown".into()),
             model_name: self
                 .model_name
                 .unwrap_or_else(|| "anthropic/claude-sonnet-4-20250514".into()),
@@ -269,11 +278,12 @@ impl Agent {
             None
         };
 
-        let tools = tools::all_tools_with_runtime(
+        let mut tools = tools::all_tools_with_runtime(
             Arc::new(config.clone()),
             &security,
-            runtime,
+            runtime.clone(),
             memory.clone(),
+            observer.clone(),
             composio_key,
             composio_entity_id,
             &config.browser,
@@ -285,6 +295,11 @@ impl Agent {
             config,
         );
 
+        let skills = crate::skills::load_skills_with_config(&config.workspace_dir, config);
+        let skill_tools =
+            crate::skills::create_skill_tools(&skills, &security, runtime, config, &tools);
+        tools.extend(skill_tools);
+
         let provider_name = config.default_provider.as_deref().unwrap_or("openrouter");
 
         let model_name = config
@@ -302,11 +317,15 @@ impl Agent {
             &model_name,
         )?;
 
+        let tool_modes =
+            providers::ToolModes::load(&config.workspace_dir, config.agent.tool_modes.clone());
         let dispatcher_choice = config.agent.tool_dispatcher.as_str();
         let tool_dispatcher: Box<dyn ToolDispatcher> = match dispatcher_choice {
             "native" => Box::new(NativeToolDispatcher),
             "xml" => Box::new(XmlToolDispatcher),
-            _ if provider.supports_native_tools() => Box::new(NativeToolDispatcher),
+            _ if tool_modes.native_tools(provider.as_ref(), provider_name, &model_name) => {
+                Box::new(NativeToolDispatcher)
+            }
             _ => Box::new(XmlToolDispatcher),
         };
 
@@ -329,6 +348,7 @@ impl Agent {
             )))
             .prompt_builder(SystemPromptBuilder::with_defaults())
             .config(config.agent.clone())
+            .provider_name(provider_name.to_string())
             .model_name(model_name)
             .temperature(config.default_temperature)
             .workspace_dir(config.workspace_dir.clone())
@@ -336,10 +356,7 @@ impl Agent {
             .available_hints(available_hints)
             .route_model_by_hint(route_model_by_hint)
             .identity_config(config.identity.clone())
-            .skills(crate::skills::load_skills_with_config(
-                &config.workspace_dir,
-                config,
-            ))
+            .skills(skills)
             .skills_prompt_mode(config.skills.prompt_injection_mode)
             .auto_save(config.memory.auto_save)
             .build()
@@ -414,8 +431,9 @@ impl Agent {
             call.arguments.clone()
         };
 
+        let span = TraceSpan::tool_call(tool_name);
         let result = if let Some(tool) = self.tools.iter().find(|t| t.name() == tool_name) {
-            match tool.execute(arguments).await {
+            match span.instrument(tool.execute(arguments)).await {
                 Ok(r) => {
                     self.observer.record_event(&ObserverEvent::ToolCall {
                         tool: tool_name.to_string(),
@@ -443,6 +461,9 @@ impl Agent {
         };
 
         let success = !result.starts_with("Error") && !result.starts_with("The tool");
+        if !success {
+            span.set_error(&result);
+        }
         ToolExecutionResult {
             name: tool_name.to_string(),
             output: result,
@@ -524,12 +545,23 @@ impl Agent {
             .push(ConversationMessage::Chat(ChatMessage::user(enriched)));
 
         let effective_model = self.classify_model(user_message);
+        let turn_span = TraceSpan::agent_turn(&self.provider_name, &effective_model, "cli");
+        let result = turn_span
+            .instrument(self.run_turn_loop(&effective_model))
+            .await;
+        if let Err(e) = &result {
+            turn_span.set_error(&e.to_string());
+        }
+        result
+    }
 
+    async fn run_turn_loop(&mut self, effective_model: &str) -> Result<String> {
         for _ in 0..self.config.max_tool_iterations {
             let messages = self.tool_dispatcher.to_provider_messages(&self.history);
-            let response = match self
-                .provider
-                .chat(
+            let llm_span =
+                TraceSpan::llm_call(&self.provider_name, effective_model, self.temperature);
+            let response = match llm_span
+                .instrument(self.provider.chat(
                     ChatRequest {
                         messages: &messages,
                         tools: if self.tool_dispatcher.should_send_tool_specs() {
@@ -537,15 +569,26 @@ impl Agent {
                         } else {
                             None
                         },
+                        response_format: None,
+                        params: self.config.generation.non_empty(),
                     },
-                    &effective_model,
+                    effective_model,
                     self.temperature,
-                )
+                ))
                 .await
             {
-                Ok(resp) => resp,
-                Err(err) => return Err(err),
+                Ok(resp) => {
+                    if let Some(usage) = &resp.usage {
+                        llm_span.record_usage(usage.input_tokens, usage.output_tokens);
+                    }
+                    resp
+                }
+                Err(err) => {
+                    llm_span.fail(&crate::providers::sanitize_api_error(&err.to_string()));
+                    return Err(err);
+                }
             };
+            drop(llm_span);
 
             let (text, calls) = self.tool_dispatcher.parse_response(&response);
             if calls.is_empty() {
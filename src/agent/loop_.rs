@@ -1,16 +1,21 @@
 use crate::approval::{ApprovalManager, ApprovalRequest, ApprovalResponse};
 use crate::config::Config;
-use crate::memory::{self, Memory, MemoryCategory};
+use crate::cost::UsageRecorder;
+use crate::memory::{self, Memory, MemoryCategory, ResponseCache};
 use crate::multimodal;
-use crate::observability::{self, runtime_trace, Observer, ObserverEvent};
+use crate::observability::{self, runtime_trace, Observer, ObserverEvent, TraceSpan};
+use crate::providers::streaming::StreamAccumulator;
 use crate::providers::{
-    self, ChatMessage, ChatRequest, Provider, ProviderCapabilityError, ToolCall,
+    self, ChatMessage, ChatRequest, ChatResponse, GenerationParams, Provider,
+    ProviderCapabilityError, StreamEvent, ToolCall, ToolMode, ToolModes, COMPACTION_SUMMARY_PREFIX,
+    VOLATILE_PROMPT_HEADING,
 };
 use crate::runtime;
 use crate::security::SecurityPolicy;
 use crate::tools::{self, Tool};
 use crate::util::truncate_with_ellipsis;
 use anyhow::Result;
+use futures_util::StreamExt;
 use regex::{Regex, RegexSet};
 use std::collections::HashSet;
 use std::fmt::Write;
@@ -99,6 +104,10 @@ const COMPACTION_MAX_SOURCE_CHARS: usize = 12_000;
 /// Max characters retained in stored compaction summary.
 const COMPACTION_MAX_SUMMARY_CHARS: usize = 2_000;
 
+/// Compact once history is estimated to fill this percentage of the model's
+/// context window (from the model catalog), even below the message-count cap.
+const COMPACTION_CONTEXT_WINDOW_PERCENT: u64 = 75;
+
 /// Minimum interval between progress sends to avoid flooding the draft channel.
 pub(crate) const PROGRESS_MIN_INTERVAL_MS: u64 = 500;
 
@@ -145,7 +154,7 @@ fn autosave_memory_key(prefix: &str) -> String {
 
 /// Trim conversation history to prevent unbounded growth.
 /// Preserves the system prompt (first message if role=system) and the most recent messages.
-fn trim_history(history: &mut Vec<ChatMessage>, max_history: usize) {
+pub(crate) fn trim_history(history: &mut Vec<ChatMessage>, max_history: usize) {
     // Nothing to trim if within limit
     let has_system = history.first().map_or(false, |m| m.role == "system");
     let non_system_count = if has_system {
@@ -163,6 +172,32 @@ fn trim_history(history: &mut Vec<ChatMessage>, max_history: usize) {
     history.drain(start..start + to_remove);
 }
 
+/// Rough token count for context-window checks (~4 characters per token).
+fn estimate_history_tokens(history: &[ChatMessage]) -> u64 {
+    history
+        .iter()
+        .map(|msg| msg.content.len() as u64 / 4 + 4)
+        .sum()
+}
+
+/// Context window the model catalog lists for `provider`/`model`, if any.
+pub(crate) fn catalog_context_window(
+    workspace_dir: &std::path::Path,
+    provider: &str,
+    model: &str,
+) -> Option<u64> {
+    providers::ModelCatalog::load(workspace_dir)
+        .find(provider, model)
+        .and_then(|model| model.context_window)
+}
+
+/// Whether `history` fills enough of `context_window` to need compaction.
+pub(crate) fn exceeds_context_window(history: &[ChatMessage], context_window: Option<u64>) -> bool {
+    context_window.is_some_and(|window| {
+        estimate_history_tokens(history) > window * COMPACTION_CONTEXT_WINDOW_PERCENT / 100
+    })
+}
+
 fn build_compaction_transcript(messages: &[ChatMessage]) -> String {
     let mut transcript = String::new();
     for msg in messages {
@@ -183,15 +218,17 @@ fn apply_compaction_summary(
     compact_end: usize,
     summary: &str,
 ) {
-    let summary_msg = ChatMessage::assistant(format!("[Compaction summary]\n{}", summary.trim()));
+    let summary_msg =
+        ChatMessage::assistant(format!("{COMPACTION_SUMMARY_PREFIX}\n{}", summary.trim()));
     history.splice(start..compact_end, std::iter::once(summary_msg));
 }
 
-async fn auto_compact_history(
+pub(crate) async fn auto_compact_history(
     history: &mut Vec<ChatMessage>,
     provider: &dyn Provider,
     model: &str,
     max_history: usize,
+    context_window: Option<u64>,
 ) -> Result<bool> {
     let has_system = history.first().map_or(false, |m| m.role == "system");
     let non_system_count = if has_system {
@@ -200,12 +237,17 @@ async fn auto_compact_history(
         history.len()
     };
 
-    if non_system_count <= max_history {
+    let over_window = exceeds_context_window(history, context_window);
+    if non_system_count <= max_history && !over_window {
         return Ok(false);
     }
 
     let start = if has_system { 1 } else { 0 };
-    let keep_recent = COMPACTION_KEEP_RECENT_MESSAGES.min(non_system_count);
+    let mut keep_recent = COMPACTION_KEEP_RECENT_MESSAGES.min(non_system_count);
+    if over_window {
+        // Few but large messages: keep only the newer half verbatim.
+        keep_recent = keep_recent.min(non_system_count / 2);
+    }
     let compact_count = non_system_count.saturating_sub(keep_recent);
     if compact_count == 0 {
         return Ok(false);
@@ -1732,6 +1774,45 @@ fn detect_tool_call_parse_issue(response: &str, parsed_calls: &[ParsedToolCall])
     }
 }
 
+/// Why a response to a native tool-calling request is unusable, if it is:
+/// a native call with malformed arguments, or tool-call markup in the text
+/// that no parser understands.
+fn native_tool_call_issue(resp: &ChatResponse) -> Option<String> {
+    if let Some(call) = resp.tool_calls.iter().find(|call| {
+        !call.arguments.trim().is_empty()
+            && serde_json::from_str::<serde_json::Value>(&call.arguments).is_err()
+    }) {
+        return Some(format!(
+            "native tool call `{}` had unparseable arguments",
+            call.name
+        ));
+    }
+    if !resp.tool_calls.is_empty() {
+        return None;
+    }
+    let text = resp.text_or_empty();
+    let (_, calls) = parse_tool_calls(text);
+    detect_tool_call_parse_issue(text, &calls)
+}
+
+/// Messages for a prompt-guided request to a native-tools provider, which
+/// will not inject tool instructions itself. `None` when the system prompt
+/// already carries them.
+fn prompt_guided_messages(
+    messages: &[ChatMessage],
+    tool_specs: &[crate::tools::ToolSpec],
+) -> Option<Vec<ChatMessage>> {
+    let has_protocol = messages
+        .iter()
+        .any(|m| m.role == "system" && m.content.contains("## Tool Use Protocol"));
+    (!has_protocol).then(|| {
+        providers::traits::with_system_instructions(
+            messages,
+            &providers::traits::build_tool_instructions_text(tool_specs),
+        )
+    })
+}
+
 fn parse_structured_tool_calls(tool_calls: &[ToolCall]) -> Vec<ParsedToolCall> {
     tool_calls
         .iter()
@@ -1864,6 +1945,42 @@ pub(crate) fn is_tool_loop_cancelled(err: &anyhow::Error) -> bool {
     err.chain().any(|source| source.is::<ToolLoopCancelled>())
 }
 
+/// Run `before_model_resolve` hooks over the configured provider and model.
+pub(crate) async fn resolve_model_with_hooks(
+    hooks: Option<&crate::hooks::HookRunner>,
+    provider: &str,
+    model: &str,
+) -> Result<(String, String)> {
+    let Some(hooks) = hooks else {
+        return Ok((provider.to_string(), model.to_string()));
+    };
+    match hooks
+        .run_before_model_resolve(provider.to_string(), model.to_string())
+        .await
+    {
+        crate::hooks::HookResult::Continue(resolved) => Ok(resolved),
+        crate::hooks::HookResult::Cancel(reason) => {
+            anyhow::bail!("Model resolution cancelled by hook: {reason}")
+        }
+    }
+}
+
+/// Run `before_prompt_build` hooks over a freshly built system prompt.
+pub(crate) async fn build_prompt_with_hooks(
+    hooks: Option<&crate::hooks::HookRunner>,
+    prompt: String,
+) -> Result<String> {
+    let Some(hooks) = hooks else {
+        return Ok(prompt);
+    };
+    match hooks.run_before_prompt_build(prompt).await {
+        crate::hooks::HookResult::Continue(prompt) => Ok(prompt),
+        crate::hooks::HookResult::Cancel(reason) => {
+            anyhow::bail!("System prompt build cancelled by hook: {reason}")
+        }
+    }
+}
+
 /// Execute a single turn of the agent loop: send messages, parse tool calls,
 /// execute tools, and loop until the LLM produces a final text response.
 /// When `silent` is true, suppresses stdout (for channel use).
@@ -1879,6 +1996,10 @@ pub(crate) async fn agent_turn(
     silent: bool,
     multimodal_config: &crate::config::MultimodalConfig,
     max_tool_iterations: usize,
+    hooks: Option<&crate::hooks::HookRunner>,
+    generation: Option<&GenerationParams>,
+    tool_modes: Option<&ToolModes>,
+    costs: Option<&UsageRecorder>,
 ) -> Result<String> {
     run_tool_call_loop(
         provider,
@@ -1895,8 +2016,11 @@ pub(crate) async fn agent_turn(
         max_tool_iterations,
         None,
         None,
-        None,
+        hooks,
         &[],
+        generation,
+        tool_modes,
+        costs,
     )
     .await
 }
@@ -1929,7 +2053,8 @@ async fn execute_one_tool(
         });
     };
 
-    let tool_future = tool.execute(call_arguments);
+    let span = TraceSpan::tool_call(call_name);
+    let tool_future = span.instrument(tool.execute(call_arguments));
     let tool_result = if let Some(token) = cancellation_token {
         tokio::select! {
             () = token.cancelled() => return Err(ToolLoopCancelled.into()),
@@ -1947,6 +2072,9 @@ async fn execute_one_tool(
                 duration,
                 success: r.success,
             });
+            if !r.success {
+                span.set_error(&scrub_credentials(r.error.as_deref().unwrap_or(&r.output)));
+            }
             if r.success {
                 Ok(ToolExecutionOutcome {
                     output: scrub_credentials(&r.output),
@@ -1972,6 +2100,7 @@ async fn execute_one_tool(
                 success: false,
             });
             let reason = format!("Error executing {call_name}: {e}");
+            span.set_error(&scrub_credentials(&reason));
             Ok(ToolExecutionOutcome {
                 output: reason.clone(),
                 success: false,
@@ -2067,8 +2196,80 @@ async fn execute_tools_sequential(
 //   • max_iterations is reached (runaway safety), or
 //   • the cancellation token fires (external abort).
 
+/// Stream one model response, relaying answer text and tool-call starts to the
+/// draft channel as they arrive.
+///
+/// Returns the assembled response and whether answer text reached the draft;
+/// on error the flag tells the caller the draft needs clearing.
+async fn stream_chat_to_draft(
+    provider: &dyn Provider,
+    request: ChatRequest<'_>,
+    model: &str,
+    temperature: f64,
+    tx: &tokio::sync::mpsc::Sender<String>,
+) -> (Result<ChatResponse>, bool) {
+    let mut events = provider.stream_chat(request, model, temperature);
+    let mut accumulator = StreamAccumulator::default();
+    let mut pending = String::new();
+    let mut relayed_text = false;
+    let mut line_open = false;
+    let mut reasoning_noted = false;
+
+    while let Some(event) = events.next().await {
+        let event = match event {
+            Ok(event) => event,
+            Err(e) => return (Err(e.into()), relayed_text),
+        };
+        accumulator.push(&event);
+
+        match event {
+            StreamEvent::TextDelta(delta) => {
+                if !relayed_text {
+                    // The answer replaces progress lines as it streams in.
+                    let _ = tx.send(DRAFT_CLEAR_SENTINEL.to_string()).await;
+                    relayed_text = true;
+                }
+                line_open = !delta.ends_with('\n');
+                pending.push_str(&delta);
+                if pending.len() >= STREAM_CHUNK_MIN_CHARS {
+                    let _ = tx.send(std::mem::take(&mut pending)).await;
+                }
+            }
+            StreamEvent::ReasoningDelta(_) if !reasoning_noted && !relayed_text => {
+                reasoning_noted = true;
+                let _ = tx.send("\u{1f9e0} Reasoning...\n".to_string()).await;
+            }
+            StreamEvent::ToolCallStart { name, .. } => {
+                let mut progress = std::mem::take(&mut pending);
+                if line_open {
+                    progress.push('\n');
+                    line_open = false;
+                }
+                let _ = writeln!(progress, "\u{1f527} {name}");
+                let _ = tx.send(progress).await;
+            }
+            _ => {}
+        }
+    }
+
+    if !pending.is_empty() {
+        let _ = tx.send(pending).await;
+    }
+    (Ok(accumulator.finish()), relayed_text)
+}
+
 /// Execute a single turn of the agent loop: send messages, parse tool calls,
 /// execute tools, and loop until the LLM produces a final text response.
+///
+/// The turn runs inside an `invoke_agent` trace span, so provider requests
+/// and tool executions (including delegated sub-agent turns) nest under it.
+///
+/// With `tool_modes`, models in `auto` mode whose native tool calls are
+/// rejected or unparseable are retried with prompt-guided tools, and the
+/// switch is remembered for later turns.
+///
+/// With `costs`, the usage each provider response reports, including prompt
+/// cache reads and writes, is priced and recorded.
 #[allow(clippy::too_many_arguments)]
 pub(crate) async fn run_tool_call_loop(
     provider: &dyn Provider,
@@ -2087,6 +2288,61 @@ pub(crate) async fn run_tool_call_loop(
     on_delta: Option<tokio::sync::mpsc::Sender<String>>,
     hooks: Option<&crate::hooks::HookRunner>,
     excluded_tools: &[String],
+    generation: Option<&GenerationParams>,
+    tool_modes: Option<&ToolModes>,
+    costs: Option<&UsageRecorder>,
+) -> Result<String> {
+    let span = TraceSpan::agent_turn(provider_name, model, channel_name);
+    let result = span
+        .instrument(run_tool_call_loop_inner(
+            provider,
+            history,
+            tools_registry,
+            observer,
+            provider_name,
+            model,
+            temperature,
+            silent,
+            approval,
+            channel_name,
+            multimodal_config,
+            max_tool_iterations,
+            cancellation_token,
+            on_delta,
+            hooks,
+            excluded_tools,
+            generation,
+            tool_modes,
+            costs,
+        ))
+        .await;
+    if let Err(e) = &result {
+        span.set_error(&crate::providers::sanitize_api_error(&e.to_string()));
+    }
+    result
+}
+
+#[allow(clippy::too_many_arguments)]
+async fn run_tool_call_loop_inner(
+    provider: &dyn Provider,
+    history: &mut Vec<ChatMessage>,
+    tools_registry: &[Box<dyn Tool>],
+    observer: &dyn Observer,
+    provider_name: &str,
+    model: &str,
+    temperature: f64,
+    silent: bool,
+    approval: Option<&ApprovalManager>,
+    channel_name: &str,
+    multimodal_config: &crate::config::MultimodalConfig,
+    max_tool_iterations: usize,
+    cancellation_token: Option<CancellationToken>,
+    on_delta: Option<tokio::sync::mpsc::Sender<String>>,
+    hooks: Option<&crate::hooks::HookRunner>,
+    excluded_tools: &[String],
+    generation: Option<&GenerationParams>,
+    tool_modes: Option<&ToolModes>,
+    costs: Option<&UsageRecorder>,
 ) -> Result<String> {
     let max_iterations = if max_tool_iterations == 0 {
         DEFAULT_MAX_TOOL_ITERATIONS
@@ -2099,9 +2355,33 @@ pub(crate) async fn run_tool_call_loop(
         .filter(|tool| !excluded_tools.iter().any(|ex| ex == tool.name()))
         .map(|tool| tool.spec())
         .collect();
-    let use_native_tools = provider.supports_native_tools() && !tool_specs.is_empty();
+    let tool_mode = tool_modes.map_or(ToolMode::Auto, |modes| modes.mode_for(provider_name, model));
+    let mut use_native_tools =
+        provider.supports_native_tools() && tool_mode != ToolMode::Xml && !tool_specs.is_empty();
+    let can_fall_back = tool_modes.is_some() && tool_mode == ToolMode::Auto;
     let turn_id = Uuid::new_v4().to_string();
     let mut seen_tool_signatures: HashSet<(String, String)> = HashSet::new();
+    // Set once a tool (e.g. `sop_execute`) asks for its own generation parameters.
+    let mut tool_generation: Option<GenerationParams> = None;
+
+    runtime_trace::record_event(
+        "turn_start",
+        Some(channel_name),
+        Some(provider_name),
+        Some(model),
+        Some(&turn_id),
+        None,
+        None,
+        serde_json::json!({
+            "user_message": history
+                .iter()
+                .rev()
+                .find(|message| message.role == "user")
+                .map(|message| scrub_credentials(&message.content)),
+            "native_tools": use_native_tools,
+            "tools": tool_specs.iter().map(|spec| spec.name.as_str()).collect::<Vec<_>>(),
+        }),
+    );
 
     for iteration in 0..max_iterations {
         if cancellation_token
@@ -2125,6 +2405,38 @@ pub(crate) async fn run_tool_call_loop(
 
         let prepared_messages =
             multimodal::prepare_messages_for_provider(history, multimodal_config).await?;
+        let guided_messages =
+            if !use_native_tools && provider.supports_native_tools() && !tool_specs.is_empty() {
+                prompt_guided_messages(&prepared_messages.messages, &tool_specs)
+            } else {
+                None
+            };
+        let request_messages = guided_messages
+            .as_deref()
+            .unwrap_or(&prepared_messages.messages);
+
+        // ── Hook: before_llm_call (modifying) ─────────────────
+        let hooked_call = match hooks {
+            Some(hooks) => match hooks
+                .run_before_llm_call(request_messages.to_vec(), model.to_string())
+                .await
+            {
+                crate::hooks::HookResult::Continue(call) => Some(call),
+                crate::hooks::HookResult::Cancel(reason) => {
+                    anyhow::bail!("LLM call cancelled by hook: {reason}")
+                }
+            },
+            None => None,
+        };
+        let (request_messages, model) = match &hooked_call {
+            Some((messages, model)) => (messages.as_slice(), model.as_str()),
+            None => (request_messages, model),
+        };
+        // Prompt-guided tool calls arrive as markup inside the text, which must
+        // not reach the draft; only stream when tools are native or absent.
+        let stream_to_draft = on_delta.is_some()
+            && provider.supports_stream_events()
+            && (use_native_tools || tool_specs.is_empty());
 
         // ── Progress: LLM thinking ────────────────────────────
         if let Some(ref tx) = on_delta {
@@ -2170,14 +2482,74 @@ pub(crate) async fn run_tool_call_loop(
             None
         };
 
-        let chat_future = provider.chat(
-            ChatRequest {
-                messages: &prepared_messages.messages,
-                tools: request_tools,
-            },
-            model,
-            temperature,
-        );
+        let request_generation = tool_generation.as_ref().or(generation);
+        let llm_span = TraceSpan::llm_call(provider_name, model, temperature);
+        let request = ChatRequest {
+            messages: request_messages,
+            tools: request_tools,
+            response_format: None,
+            params: request_generation,
+        };
+        let chat_future = llm_span.instrument(async {
+            let first = 'native: {
+                if let Some(tx) = on_delta.as_ref().filter(|_| stream_to_draft) {
+                    let (result, relayed_text) =
+                        stream_chat_to_draft(provider, request, model, temperature, tx).await;
+                    match result {
+                        Ok(resp) => break 'native Ok((resp, relayed_text)),
+                        Err(e) => {
+                            tracing::warn!(
+                                provider = provider_name,
+                                "Streaming failed, retrying without streaming: {}",
+                                crate::providers::sanitize_api_error(&e.to_string())
+                            );
+                            if relayed_text {
+                                let _ = tx.send(DRAFT_CLEAR_SENTINEL.to_string()).await;
+                            }
+                        }
+                    }
+                }
+                provider
+                    .chat(request, model, temperature)
+                    .await
+                    .map(|resp| (resp, false))
+            };
+            if !(can_fall_back && use_native_tools) {
+                return first.map(|(resp, streamed)| (resp, streamed, None));
+            }
+
+            let reason = match &first {
+                Err(e) if providers::tool_mode::is_tool_rejection(e) => {
+                    crate::providers::sanitize_api_error(&e.to_string())
+                }
+                Ok((resp, streamed)) => match native_tool_call_issue(resp) {
+                    Some(issue) => {
+                        if let Some(tx) = on_delta.as_ref().filter(|_| *streamed) {
+                            let _ = tx.send(DRAFT_CLEAR_SENTINEL.to_string()).await;
+                        }
+                        issue
+                    }
+                    None => return first.map(|(resp, streamed)| (resp, streamed, None)),
+                },
+                Err(_) => return first.map(|(resp, streamed)| (resp, streamed, None)),
+            };
+            tracing::warn!(
+                provider = provider_name,
+                model,
+                "Native tool calling failed, retrying with prompt-guided tools: {reason}"
+            );
+            let guided = prompt_guided_messages(request_messages, &tool_specs);
+            let retry = ChatRequest {
+                messages: guided.as_deref().unwrap_or(request_messages),
+                tools: None,
+                response_format: None,
+                params: request_generation,
+            };
+            provider
+                .chat(retry, model, temperature)
+                .await
+                .map(|resp| (resp, false, Some(reason)))
+        });
 
         let chat_result = if let Some(token) = cancellation_token.as_ref() {
             tokio::select! {
@@ -2188,14 +2560,40 @@ pub(crate) async fn run_tool_call_loop(
             chat_future.await
         };
 
+        let text_streamed;
         let (response_text, parsed_text, tool_calls, assistant_history_content, native_tool_calls) =
             match chat_result {
-                Ok(resp) => {
+                Ok((resp, streamed, fallback_reason)) => {
+                    text_streamed = streamed;
+                    if let Some(reason) = fallback_reason {
+                        use_native_tools = false;
+                        if let Some(modes) = tool_modes {
+                            modes.record_xml_fallback(provider_name, model, &reason);
+                        }
+                        runtime_trace::record_event(
+                            "tool_mode_fallback",
+                            Some(channel_name),
+                            Some(provider_name),
+                            Some(model),
+                            Some(&turn_id),
+                            Some(true),
+                            Some(&reason),
+                            serde_json::json!({
+                                "iteration": iteration + 1,
+                                "mode": ToolMode::Xml.as_str(),
+                            }),
+                        );
+                    }
                     let (resp_input_tokens, resp_output_tokens) = resp
                         .usage
                         .as_ref()
                         .map(|u| (u.input_tokens, u.output_tokens))
                         .unwrap_or((None, None));
+                    llm_span.record_usage(resp_input_tokens, resp_output_tokens);
+                    drop(llm_span);
+                    if let (Some(costs), Some(usage)) = (costs, resp.usage.as_ref()) {
+                        costs.record(provider_name, model, usage);
+                    }
 
                     observer.record_event(&ObserverEvent::LlmResponse {
                         provider: provider_name.to_string(),
@@ -2258,6 +2656,15 @@ pub(crate) async fn run_tool_call_loop(
                             "output_tokens": resp_output_tokens,
                             "raw_response": scrub_credentials(&response_text),
                             "native_tool_calls": resp.tool_calls.len(),
+                            "tool_calls": resp
+                                .tool_calls
+                                .iter()
+                                .map(|call| serde_json::json!({
+                                    "id": call.id,
+                                    "name": call.name,
+                                    "arguments": scrub_credentials(&call.arguments),
+                                }))
+                                .collect::<Vec<_>>(),
                             "parsed_tool_calls": calls.len(),
                         }),
                     );
@@ -2295,6 +2702,7 @@ pub(crate) async fn run_tool_call_loop(
                 }
                 Err(e) => {
                     let safe_error = crate::providers::sanitize_api_error(&e.to_string());
+                    llm_span.fail(&safe_error);
                     observer.record_event(&ObserverEvent::LlmResponse {
                         provider: provider_name.to_string(),
                         model: model.to_string(),
@@ -2356,8 +2764,10 @@ pub(crate) async fn run_tool_call_loop(
             );
             // No tool calls — this is the final response.
             // If a streaming sender is provided, relay the text in small chunks
-            // so the channel can progressively update the draft message.
-            if let Some(ref tx) = on_delta {
+            // so the channel can progressively update the draft message, unless
+            // the provider stream already delivered it.
+            let already_streamed = text_streamed && display_text == response_text;
+            if let Some(tx) = on_delta.as_ref().filter(|_| !already_streamed) {
                 // Clear accumulated progress lines before streaming the final answer.
                 let _ = tx.send(DRAFT_CLEAR_SENTINEL.to_string()).await;
                 // Split on whitespace boundaries, accumulating chunks of at least
@@ -2458,6 +2868,7 @@ pub(crate) async fn run_tool_call_loop(
                     };
 
                     // Only prompt interactively on CLI; auto-approve on other channels.
+                    let approval_started = Instant::now();
                     let decision = if channel_name == "cli" {
                         mgr.prompt_cli(&request)
                     } else {
@@ -2465,6 +2876,12 @@ pub(crate) async fn run_tool_call_loop(
                     };
 
                     mgr.record_decision(&tool_name, &tool_args, decision, channel_name);
+                    observer.record_event(&ObserverEvent::ApprovalResolved {
+                        channel: channel_name.to_string(),
+                        tool: tool_name.clone(),
+                        approved: decision != ApprovalResponse::No,
+                        wait: approval_started.elapsed(),
+                    });
 
                     if decision == ApprovalResponse::No {
                         let denied = "Denied by user.".to_string();
@@ -2628,6 +3045,21 @@ pub(crate) async fn run_tool_call_loop(
                 let _ = tx.send(format!("{icon} {} ({secs}s)\n", call.name)).await;
             }
 
+            if outcome.success {
+                match find_tool(tools_registry, &call.name)
+                    .and_then(|tool| tool.generation_override(&call.arguments))
+                {
+                    Some(tools::GenerationOverride::Apply(params)) => {
+                        tool_generation = Some(match generation {
+                            Some(base) => params.with_defaults(base),
+                            None => params,
+                        });
+                    }
+                    Some(tools::GenerationOverride::Reset) => tool_generation = None,
+                    None => {}
+                }
+            }
+
             ordered_results[*idx] = Some((call.name.clone(), call.tool_call_id.clone(), outcome));
         }
 
@@ -2777,8 +3209,9 @@ pub async fn run(
     let mut tools_registry = tools::all_tools_with_runtime(
         Arc::new(config.clone()),
         &security,
-        runtime,
+        runtime.clone(),
         mem.clone(),
+        observer.clone(),
         composio_key,
         composio_entity_id,
         &config.browser,
@@ -2796,17 +3229,36 @@ pub async fn run(
         tracing::info!(count = peripheral_tools.len(), "Peripheral tools added");
         tools_registry.extend(peripheral_tools);
     }
+    tools_registry.extend(crate::robot::create_robot_tools(&config, observer.clone()).await?);
 
-    // ── Resolve provider ─────────────────────────────────────────
-    let provider_name = provider_override
-        .as_deref()
-        .or(config.default_provider.as_deref())
-        .unwrap_or("openrouter");
+    let skills = crate::skills::load_skills_with_config(&config.workspace_dir, &config);
+    let skill_tools =
+        crate::skills::create_skill_tools(&skills, &security, runtime, &config, &tools_registry);
+    if !skill_tools.is_empty() {
+        tracing::info!(count = skill_tools.len(), "Skill tools added");
+        tools_registry.extend(skill_tools);
+    }
+
+    let hooks = config
+        .hooks
+        .enabled
+        .then(|| crate::hooks::HookRunner::from_config(&config));
 
-    let model_name = model_override
-        .as_deref()
-        .or(config.default_model.as_deref())
-        .unwrap_or("anthropic/claude-sonnet-4");
+    // ── Resolve provider ─────────────────────────────────────────
+    let (provider_name, model_name) = resolve_model_with_hooks(
+        hooks.as_ref(),
+        provider_override
+            .as_deref()
+            .or(config.default_provider.as_deref())
+            .unwrap_or("openrouter"),
+        model_override
+            .as_deref()
+            .or(config.default_model.as_deref())
+            .unwrap_or("anthropic/claude-sonnet-4"),
+    )
+    .await?;
+    let (provider_name, model_name) = (provider_name.as_str(), model_name.as_str());
+    let context_window = catalog_context_window(&config.workspace_dir, provider_name, model_name);
 
     let provider_runtime_options = providers::ProviderRuntimeOptions {
         auth_profile_override: None,
@@ -2853,7 +3305,6 @@ pub async fn run(
         .collect();
 
     // ── Build system prompt from workspace MD files (OpenClaw framework) ──
-    let skills = crate::skills::load_skills_with_config(&config.workspace_dir, &config);
     let mut tool_descs: Vec<(&str, &str)> = vec![
         (
             "shell",
@@ -2967,7 +3418,9 @@ pub async fn run(
     } else {
         None
     };
-    let native_tools = provider.supports_native_tools();
+    let tool_modes = ToolModes::load(&config.workspace_dir, config.agent.tool_modes.clone());
+    let usage_recorder = UsageRecorder::from_config(&config);
+    let native_tools = tool_modes.native_tools(provider.as_ref(), provider_name, model_name);
     let mut system_prompt = crate::channels::build_system_prompt_with_mode(
         &config.workspace_dir,
         model_name,
@@ -2983,6 +3436,7 @@ pub async fn run(
     if !native_tools {
         system_prompt.push_str(&build_tool_instructions(&tools_registry));
     }
+    let system_prompt = build_prompt_with_hooks(hooks.as_ref(), system_prompt).await?;
 
     // ── Approval manager (supervised mode) ───────────────────────
     let approval_manager = if interactive {
@@ -3022,30 +3476,55 @@ pub async fn run(
             format!("{context}[{now}] {msg}")
         };
 
-        let mut history = vec![
-            ChatMessage::system(&system_prompt),
-            ChatMessage::user(&enriched),
-        ];
-
-        let response = run_tool_call_loop(
-            provider.as_ref(),
-            &mut history,
-            &tools_registry,
-            observer.as_ref(),
-            provider_name,
+        let response_cache =
+            memory::create_response_cache(&config.memory, &config.workspace_dir, observer.clone());
+        let cache_key = one_shot_cache_key(
+            response_cache.as_ref(),
             model_name,
+            &system_prompt,
+            &format!("{context}{msg}"),
             temperature,
-            false,
-            approval_manager.as_ref(),
-            channel_name,
-            &config.multimodal,
-            config.agent.max_tool_iterations,
-            None,
-            None,
-            None,
-            &[],
-        )
-        .await?;
+        );
+        let cached = match (&response_cache, &cache_key) {
+            (Some(cache), Some(key)) => cache.get(key).ok().flatten(),
+            _ => None,
+        };
+
+        let response = if let Some(cached) = cached {
+            cached
+        } else {
+            let mut history = vec![
+                ChatMessage::system(&system_prompt),
+                ChatMessage::user(&enriched),
+            ];
+
+            let response = run_tool_call_loop(
+                provider.as_ref(),
+                &mut history,
+                &tools_registry,
+                observer.as_ref(),
+                provider_name,
+                model_name,
+                temperature,
+                false,
+                approval_manager.as_ref(),
+                channel_name,
+                &config.multimodal,
+                config.agent.max_tool_iterations,
+                None,
+                None,
+                hooks.as_ref(),
+                &[],
+                config.agent.generation.non_empty(),
+                Some(&tool_modes),
+                usage_recorder.as_ref(),
+            )
+            .await?;
+            if let (Some(cache), Some(key)) = (&response_cache, &cache_key) {
+                cache_one_shot_response(cache, key, model_name, &history, &response);
+            }
+            response
+        };
         final_output = response.clone();
         println!("{response}");
         observer.record_event(&ObserverEvent::TurnComplete);
@@ -3164,8 +3643,11 @@ pub async fn run(
                 config.agent.max_tool_iterations,
                 None,
                 None,
-                None,
+                hooks.as_ref(),
                 &[],
+                config.agent.generation.non_empty(),
+                Some(&tool_modes),
+                usage_recorder.as_ref(),
             )
             .await
             {
@@ -3192,6 +3674,7 @@ pub async fn run(
                 provider.as_ref(),
                 model_name,
                 config.agent.max_history_messages,
+                context_window,
             )
             .await
             {
@@ -3219,6 +3702,47 @@ pub async fn run(
 
 /// Process a single message through the full agent (with tools, peripherals, memory).
 /// Used by channels (Telegram, Discord, etc.) to enable hardware and tool use.
+/// Response-cache key for a one-shot turn, or `None` when it must not be cached.
+///
+/// Only deterministic turns (`temperature == 0`) are cached. The key covers the
+/// system prompt up to its date/time section and the message with its memory
+/// context, so per-turn timestamps do not defeat it.
+fn one_shot_cache_key(
+    cache: Option<&ResponseCache>,
+    model: &str,
+    system_prompt: &str,
+    prompt: &str,
+    temperature: f64,
+) -> Option<String> {
+    if cache.is_none() || temperature.abs() > f64::EPSILON {
+        return None;
+    }
+    let stable = system_prompt
+        .find(VOLATILE_PROMPT_HEADING)
+        .map_or(system_prompt, |at| &system_prompt[..at]);
+    Some(ResponseCache::cache_key(model, Some(stable), prompt))
+}
+
+/// Cache a one-shot reply unless the turn called tools, whose results may
+/// differ on the next run.
+fn cache_one_shot_response(
+    cache: &ResponseCache,
+    key: &str,
+    model: &str,
+    history: &[ChatMessage],
+    response: &str,
+) {
+    // system + user + final assistant reply
+    if history.len() != 3 {
+        return;
+    }
+    // Rough estimate: the tool loop does not surface provider usage.
+    let tokens = u32::try_from(response.len() / 4).unwrap_or(u32::MAX);
+    if let Err(e) = cache.put(key, model, response, tokens) {
+        tracing::warn!("Failed to store cached response: {e}");
+    }
+}
+
 pub async fn process_message(config: Config, message: &str) -> Result<String> {
     let observer: Arc<dyn Observer> =
         Arc::from(observability::create_observer(&config.observability));
@@ -3246,8 +3770,9 @@ pub async fn process_message(config: Config, message: &str) -> Result<String> {
     let mut tools_registry = tools::all_tools_with_runtime(
         Arc::new(config.clone()),
         &security,
-        runtime,
+        runtime.clone(),
         mem.clone(),
+        observer.clone(),
         composio_key,
         composio_entity_id,
         &config.browser,
@@ -3261,12 +3786,26 @@ pub async fn process_message(config: Config, message: &str) -> Result<String> {
     let peripheral_tools: Vec<Box<dyn Tool>> =
         crate::peripherals::create_peripheral_tools(&config.peripherals).await?;
     tools_registry.extend(peripheral_tools);
-
-    let provider_name = config.default_provider.as_deref().unwrap_or("openrouter");
-    let model_name = config
-        .default_model
-        .clone()
-        .unwrap_or_else(|| "anthropic/claude-sonnet-4-20250514".into());
+    tools_registry.extend(crate::robot::create_robot_tools(&config, observer.clone()).await?);
+    let skills = crate::skills::load_skills_with_config(&config.workspace_dir, &config);
+    let skill_tools =
+        crate::skills::create_skill_tools(&skills, &security, runtime, &config, &tools_registry);
+    tools_registry.extend(skill_tools);
+
+    let hooks = config
+        .hooks
+        .enabled
+        .then(|| crate::hooks::HookRunner::from_config(&config));
+    let (provider_name, model_name) = resolve_model_with_hooks(
+        hooks.as_ref(),
+        config.default_provider.as_deref().unwrap_or("openrouter"),
+        config
+            .default_model
+            .as_deref()
+            .unwrap_or("anthropic/claude-sonnet-4-20250514"),
+    )
+    .await?;
+    let provider_name = provider_name.as_str();
     let provider_runtime_options = providers::ProviderRuntimeOptions {
         auth_profile_override: None,
         provider_api_url: config.api_url.clone(),
@@ -3300,7 +3839,6 @@ pub async fn process_message(config: Config, message: &str) -> Result<String> {
         .map(|b| b.board.clone())
         .collect();
 
-    let skills = crate::skills::load_skills_with_config(&config.workspace_dir, &config);
     let mut tool_descs: Vec<(&str, &str)> = vec![
         ("shell", "Execute terminal commands."),
         ("file_read", "Read file contents."),
@@ -3354,7 +3892,9 @@ pub async fn process_message(config: Config, message: &str) -> Result<String> {
     } else {
         None
     };
-    let native_tools = provider.supports_native_tools();
+    let tool_modes = ToolModes::load(&config.workspace_dir, config.agent.tool_modes.clone());
+    let usage_recorder = UsageRecorder::from_config(&config);
+    let native_tools = tool_modes.native_tools(provider.as_ref(), provider_name, &model_name);
     let mut system_prompt = crate::channels::build_system_prompt_with_mode(
         &config.workspace_dir,
         &model_name,
@@ -3368,6 +3908,7 @@ pub async fn process_message(config: Config, message: &str) -> Result<String> {
     if !native_tools {
         system_prompt.push_str(&build_tool_instructions(&tools_registry));
     }
+    let system_prompt = build_prompt_with_hooks(hooks.as_ref(), system_prompt).await?;
 
     let mem_context = build_context(mem.as_ref(), message, config.memory.min_relevance_score).await;
     let rag_limit = if config.agent.compact_context { 2 } else { 5 };
@@ -3383,12 +3924,27 @@ pub async fn process_message(config: Config, message: &str) -> Result<String> {
         format!("{context}[{now}] {message}")
     };
 
+    let response_cache =
+        memory::create_response_cache(&config.memory, &config.workspace_dir, observer.clone());
+    let cache_key = one_shot_cache_key(
+        response_cache.as_ref(),
+        &model_name,
+        &system_prompt,
+        &format!("{context}{message}"),
+        config.default_temperature,
+    );
+    if let (Some(cache), Some(key)) = (&response_cache, &cache_key) {
+        if let Ok(Some(cached)) = cache.get(key) {
+            return Ok(cached);
+        }
+    }
+
     let mut history = vec![
         ChatMessage::system(&system_prompt),
         ChatMessage::user(&enriched),
     ];
 
-    agent_turn(
+    let response = agent_turn(
         provider.as_ref(),
         &mut history,
         &tools_registry,
@@ -3399,8 +3955,16 @@ pub async fn process_message(config: Config, message: &str) -> Result<String> {
         true,
         &config.multimodal,
         config.agent.max_tool_iterations,
+        hooks.as_ref(),
+        config.agent.generation.non_empty(),
+        Some(&tool_modes),
+        usage_recorder.as_ref(),
     )
-    .await
+    .await?;
+    if let (Some(cache), Some(key)) = (&response_cache, &cache_key) {
+        cache_one_shot_response(cache, key, &model_name, &history, &response);
+    }
+    Ok(response)
 }
 
 #[cfg(test)]
@@ -3712,6 +4276,9 @@ mod tests {
             None,
             None,
             &[],
+            None,
+            None,
+            None,
         )
         .await
         .expect_err("provider without vision support should fail");
@@ -3758,6 +4325,9 @@ mod tests {
             None,
             None,
             &[],
+            None,
+            None,
+            None,
         )
         .await
         .expect_err("oversized payload must fail");
@@ -3798,6 +4368,9 @@ mod tests {
             None,
             None,
             &[],
+            None,
+            None,
+            None,
         )
         .await
         .expect("valid multimodal payload should pass");
@@ -3924,6 +4497,9 @@ mod tests {
             None,
             None,
             &[],
+            None,
+            None,
+            None,
         )
         .await
         .expect("parallel execution should complete");
@@ -3993,6 +4569,9 @@ mod tests {
             None,
             None,
             &[],
+            None,
+            None,
+            None,
         )
         .await
         .expect("loop should finish after deduplicating repeated calls");
@@ -4049,6 +4628,9 @@ mod tests {
             None,
             None,
             &[],
+            None,
+            None,
+            None,
         )
         .await
         .expect("native fallback id flow should complete");
@@ -4069,30 +4651,443 @@ mod tests {
         );
     }
 
-    #[test]
-    fn parse_tool_calls_extracts_single_call() {
-        let response = r#"Let me check that.
-<tool_call>
-{"name": "shell", "arguments": {"command": "ls -la"}}
-</tool_call>"#;
-
-        let (text, calls) = parse_tool_calls(response);
-        assert_eq!(text, "Let me check that.");
-        assert_eq!(calls.len(), 1);
-        assert_eq!(calls[0].name, "shell");
-        assert_eq!(
-            calls[0].arguments.get("command").unwrap().as_str().unwrap(),
-            "ls -la"
-        );
+    /// Replays scripted event streams; `chat` serves the non-streaming fallback.
+    struct StreamingProvider {
+        streams: Mutex<VecDeque<Vec<crate::providers::traits::StreamResult<StreamEvent>>>>,
+        chat_responses: Mutex<VecDeque<ChatResponse>>,
     }
 
-    #[test]
-    fn parse_tool_calls_extracts_multiple_calls() {
-        let response = r#"<tool_call>
-{"name": "file_read", "arguments": {"path": "a.txt"}}
-</tool_call>
-<tool_call>
-{"name": "file_read", "arguments": {"path": "b.txt"}}
+    #[async_trait]
+    impl Provider for StreamingProvider {
+        fn capabilities(&self) -> ProviderCapabilities {
+            ProviderCapabilities {
+                native_tool_calling: true,
+                vision: false,
+            }
+        }
+
+        async fn chat_with_system(
+            &self,
+            _system_prompt: Option<&str>,
+            _message: &str,
+            _model: &str,
+            _temperature: f64,
+        ) -> anyhow::Result<String> {
+            anyhow::bail!("chat_with_system should not be used in streaming provider tests");
+        }
+
+        async fn chat(
+            &self,
+            _request: ChatRequest<'_>,
+            _model: &str,
+            _temperature: f64,
+        ) -> anyhow::Result<ChatResponse> {
+            self.chat_responses
+                .lock()
+                .unwrap()
+                .pop_front()
+                .ok_or_else(|| anyhow::anyhow!("streaming provider has no chat responses"))
+        }
+
+        fn supports_stream_events(&self) -> bool {
+            true
+        }
+
+        fn stream_chat(
+            &self,
+            _request: ChatRequest<'_>,
+            _model: &str,
+            _temperature: f64,
+        ) -> futures_util::stream::BoxStream<
+            'static,
+            crate::providers::traits::StreamResult<StreamEvent>,
+        > {
+            let events = self.streams.lock().unwrap().pop_front().unwrap_or_default();
+            futures_util::stream::iter(events).boxed()
+        }
+    }
+
+    async fn run_streaming_loop(
+        provider: &StreamingProvider,
+        tool_modes: Option<&ToolModes>,
+    ) -> (String, Vec<String>, usize) {
+        let invocations = Arc::new(AtomicUsize::new(0));
+        let tools_registry: Vec<Box<dyn Tool>> = vec![Box::new(CountingTool::new(
+            "count_tool",
+            Arc::clone(&invocations),
+        ))];
+        let mut history = vec![
+            ChatMessage::system("test-system"),
+            ChatMessage::user("run tool calls"),
+        ];
+        let (tx, mut rx) = tokio::sync::mpsc::channel::<String>(64);
+
+        let result = run_tool_call_loop(
+            provider,
+            &mut history,
+            &tools_registry,
+            &NoopObserver,
+            "mock-provider",
+            "mock-model",
+            0.0,
+            true,
+            None,
+            "cli",
+            &crate::config::MultimodalConfig::default(),
+            4,
+            None,
+            Some(tx),
+            None,
+            &[],
+            None,
+            tool_modes,
+            None,
+        )
+        .await
+        .expect("streaming loop should complete");
+
+        let mut drafts = Vec::new();
+        while let Ok(delta) = rx.try_recv() {
+            drafts.push(delta);
+        }
+        (result, drafts, invocations.load(Ordering::SeqCst))
+    }
+
+    #[tokio::test]
+    async fn run_tool_call_loop_streams_tool_calls_and_answer_to_draft() {
+        let provider = StreamingProvider {
+            streams: Mutex::new(VecDeque::from([
+                vec![
+                    Ok(StreamEvent::ReasoningDelta("count first".into())),
+                    Ok(StreamEvent::TextDelta("Counting.".into())),
+                    Ok(StreamEvent::ToolCallStart {
+                        index: 0,
+                        id: "call_1".into(),
+                        name: "count_tool".into(),
+                    }),
+                    Ok(StreamEvent::ToolCallDelta {
+                        index: 0,
+                        arguments: r#"{"value":"#.into(),
+                    }),
+                    Ok(StreamEvent::ToolCallDelta {
+                        index: 0,
+                        arguments: r#""A"}"#.into(),
+                    }),
+                    Ok(StreamEvent::ToolCallEnd { index: 0 }),
+                    Ok(StreamEvent::Done),
+                ],
+                vec![
+                    Ok(StreamEvent::TextDelta("All ".into())),
+                    Ok(StreamEvent::TextDelta("done.".into())),
+                    Ok(StreamEvent::Done),
+                ],
+            ])),
+            chat_responses: Mutex::new(VecDeque::new()),
+        };
+
+        let (result, drafts, invocations) = run_streaming_loop(&provider, None).await;
+
+        assert_eq!(result, "All done.");
+        assert_eq!(invocations, 1);
+        assert!(drafts.contains(&"\u{1f9e0} Reasoning...\n".to_string()));
+        assert!(drafts.contains(&"Counting.\n\u{1f527} count_tool\n".to_string()));
+        let last_clear = drafts
+            .iter()
+            .rposition(|d| d == DRAFT_CLEAR_SENTINEL)
+            .expect("final answer should clear progress lines");
+        assert_eq!(drafts[last_clear + 1..].concat(), "All done.");
+    }
+
+    #[tokio::test]
+    async fn run_tool_call_loop_falls_back_to_chat_when_stream_fails() {
+        let provider = StreamingProvider {
+            streams: Mutex::new(VecDeque::from([vec![
+                Ok(StreamEvent::TextDelta("Partial".into())),
+                Err(crate::providers::traits::StreamError::Provider(
+                    "connection reset".into(),
+                )),
+            ]])),
+            chat_responses: Mutex::new(VecDeque::from([ChatResponse {
+                text: Some("Recovered answer".into()),
+                tool_calls: Vec::new(),
+                usage: None,
+                reasoning_content: None,
+            }])),
+        };
+
+        let (result, drafts, _) = run_streaming_loop(&provider, None).await;
+
+        assert_eq!(result, "Recovered answer");
+        let last_clear = drafts
+            .iter()
+            .rposition(|d| d == DRAFT_CLEAR_SENTINEL)
+            .unwrap();
+        assert_eq!(drafts[last_clear + 1..].concat(), "Recovered answer");
+    }
+
+    #[tokio::test]
+    async fn run_tool_call_loop_retries_truncated_streamed_native_calls_with_xml() {
+        let tmp = TempDir::new().unwrap();
+        let tool_modes = ToolModes::load(tmp.path(), std::collections::HashMap::new());
+        let provider = StreamingProvider {
+            streams: Mutex::new(VecDeque::from([vec![
+                Ok(StreamEvent::ToolCallStart {
+                    index: 0,
+                    id: "call_1".into(),
+                    name: "count_tool".into(),
+                }),
+                Ok(StreamEvent::ToolCallDelta {
+                    index: 0,
+                    arguments: r#"{"value": "A"#.into(),
+                }),
+                Ok(StreamEvent::Done),
+            ]])),
+            chat_responses: Mutex::new(VecDeque::from([
+                ChatResponse {
+                    text: Some(
+                        "<tool_call>\n{\"name\": \"count_tool\", \"arguments\": {\"value\": \"A\"}}\n</tool_call>"
+                            .into(),
+                    ),
+                    tool_calls: Vec::new(),
+                    usage: None,
+                    reasoning_content: None,
+                },
+                ChatResponse {
+                    text: Some("Counted A.".into()),
+                    tool_calls: Vec::new(),
+                    usage: None,
+                    reasoning_content: None,
+                },
+            ])),
+        };
+
+        let (result, drafts, invocations) = run_streaming_loop(&provider, Some(&tool_modes)).await;
+
+        assert_eq!(result, "Counted A.");
+        assert_eq!(invocations, 1);
+        assert!(drafts.contains(&DRAFT_CLEAR_SENTINEL.to_string()));
+        assert_eq!(
+            tool_modes.mode_for("mock-provider", "mock-model"),
+            ToolMode::Xml
+        );
+    }
+
+    /// Native-tools provider whose model mishandles native tool definitions:
+    /// requests carrying `tools` get `native_reply`, prompt-guided requests
+    /// get the scripted text responses.
+    struct NativeToolTroubleProvider {
+        native_reply: fn() -> anyhow::Result<ChatResponse>,
+        guided_responses: Mutex<VecDeque<&'static str>>,
+        native_requests: AtomicUsize,
+        guided_prompts: Mutex<Vec<String>>,
+    }
+
+    impl NativeToolTroubleProvider {
+        fn new(
+            native_reply: fn() -> anyhow::Result<ChatResponse>,
+            guided: Vec<&'static str>,
+        ) -> Self {
+            Self {
+                native_reply,
+                guided_responses: Mutex::new(guided.into()),
+                native_requests: AtomicUsize::new(0),
+                guided_prompts: Mutex::new(Vec::new()),
+            }
+        }
+    }
+
+    #[async_trait]
+    impl Provider for NativeToolTroubleProvider {
+        fn capabilities(&self) -> ProviderCapabilities {
+            ProviderCapabilities {
+                native_tool_calling: true,
+                vision: false,
+            }
+        }
+
+        async fn chat_with_system(
+            &self,
+            _system_prompt: Option<&str>,
+            _message: &str,
+            _model: &str,
+            _temperature: f64,
+        ) -> anyhow::Result<String> {
+            anyhow::bail!("chat_with_system should not be used in tool mode tests");
+        }
+
+        async fn chat(
+            &self,
+            request: ChatRequest<'_>,
+            _model: &str,
+            _temperature: f64,
+        ) -> anyhow::Result<ChatResponse> {
+            if request.tools.is_some() {
+                self.native_requests.fetch_add(1, Ordering::SeqCst);
+                return (self.native_reply)();
+            }
+            let system = request
+                .messages
+                .iter()
+                .find(|m| m.role == "system")
+                .map(|m| m.content.clone())
+                .unwrap_or_default();
+            self.guided_prompts.lock().unwrap().push(system);
+            let text = self
+                .guided_responses
+                .lock()
+                .unwrap()
+                .pop_front()
+                .ok_or_else(|| anyhow::anyhow!("no guided responses left"))?;
+            Ok(ChatResponse {
+                text: Some(text.to_string()),
+                tool_calls: Vec::new(),
+                usage: None,
+                reasoning_content: None,
+            })
+        }
+    }
+
+    fn reject_native_tools() -> anyhow::Result<ChatResponse> {
+        anyhow::bail!("Ollama API error (400 Bad Request): gemma2 does not support tools")
+    }
+
+    fn malformed_native_call() -> anyhow::Result<ChatResponse> {
+        Ok(ChatResponse {
+            text: None,
+            tool_calls: vec![ToolCall {
+                id: "call_1".into(),
+                name: "count_tool".into(),
+                arguments: r#"{"value": "A""#.into(),
+            }],
+            usage: None,
+            reasoning_content: None,
+        })
+    }
+
+    async fn run_tool_mode_loop(
+        provider: &NativeToolTroubleProvider,
+        tool_modes: &ToolModes,
+    ) -> (Result<String>, usize) {
+        let invocations = Arc::new(AtomicUsize::new(0));
+        let tools_registry: Vec<Box<dyn Tool>> = vec![Box::new(CountingTool::new(
+            "count_tool",
+            Arc::clone(&invocations),
+        ))];
+        let mut history = vec![
+            ChatMessage::system("test-system"),
+            ChatMessage::user("count A"),
+        ];
+        let result = run_tool_call_loop(
+            provider,
+            &mut history,
+            &tools_registry,
+            &NoopObserver,
+            "ollama",
+            "gemma2",
+            0.0,
+            true,
+            None,
+            "cli",
+            &crate::config::MultimodalConfig::default(),
+            4,
+            None,
+            None,
+            None,
+            &[],
+            None,
+            Some(tool_modes),
+            None,
+        )
+        .await;
+        (result, invocations.load(Ordering::SeqCst))
+    }
+
+    #[tokio::test]
+    async fn run_tool_call_loop_falls_back_to_xml_when_native_tools_rejected() {
+        let tmp = TempDir::new().unwrap();
+        let tool_modes = ToolModes::load(tmp.path(), std::collections::HashMap::new());
+        let provider = NativeToolTroubleProvider::new(
+            reject_native_tools,
+            vec![
+                "<tool_call>\n{\"name\": \"count_tool\", \"arguments\": {\"value\": \"A\"}}\n</tool_call>",
+                "Counted A.",
+                "Still prompt-guided.",
+            ],
+        );
+
+        let (result, invocations) = run_tool_mode_loop(&provider, &tool_modes).await;
+
+        assert_eq!(result.unwrap(), "Counted A.");
+        assert_eq!(invocations, 1);
+        assert_eq!(provider.native_requests.load(Ordering::SeqCst), 1);
+        assert!(provider.guided_prompts.lock().unwrap()[0].contains("## Tool Use Protocol"));
+
+        // The fallback is remembered: later turns skip native tools entirely.
+        let reloaded = ToolModes::load(tmp.path(), std::collections::HashMap::new());
+        assert_eq!(reloaded.mode_for("ollama", "gemma2"), ToolMode::Xml);
+        let (result, _) = run_tool_mode_loop(&provider, &reloaded).await;
+        assert_eq!(result.unwrap(), "Still prompt-guided.");
+        assert_eq!(provider.native_requests.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn run_tool_call_loop_retries_unparseable_native_calls_with_xml() {
+        let tmp = TempDir::new().unwrap();
+        let tool_modes = ToolModes::load(tmp.path(), std::collections::HashMap::new());
+        let provider = NativeToolTroubleProvider::new(
+            malformed_native_call,
+            vec![
+                "<tool_call>\n{\"name\": \"count_tool\", \"arguments\": {\"value\": \"A\"}}\n</tool_call>",
+                "Counted A.",
+            ],
+        );
+
+        let (result, invocations) = run_tool_mode_loop(&provider, &tool_modes).await;
+
+        assert_eq!(result.unwrap(), "Counted A.");
+        assert_eq!(invocations, 1);
+        assert_eq!(tool_modes.mode_for("ollama", "gemma2"), ToolMode::Xml);
+    }
+
+    #[tokio::test]
+    async fn run_tool_call_loop_keeps_native_tools_when_pinned() {
+        let tmp = TempDir::new().unwrap();
+        let overrides = std::collections::HashMap::from([("gemma2".to_string(), ToolMode::Native)]);
+        let tool_modes = ToolModes::load(tmp.path(), overrides);
+        let provider = NativeToolTroubleProvider::new(reject_native_tools, vec!["unused"]);
+
+        let (result, _) = run_tool_mode_loop(&provider, &tool_modes).await;
+
+        let err = result.unwrap_err().to_string();
+        assert!(err.contains("does not support tools"));
+        assert!(provider.guided_prompts.lock().unwrap().is_empty());
+        assert!(crate::providers::tool_mode::learned_tool_modes(tmp.path()).is_empty());
+    }
+
+    #[test]
+    fn parse_tool_calls_extracts_single_call() {
+        let response = r#"Let me check that.
+<tool_call>
+{"name": "shell", "arguments": {"command": "ls -la"}}
+</tool_call>"#;
+
+        let (text, calls) = parse_tool_calls(response);
+        assert_eq!(text, "Let me check that.");
+        assert_eq!(calls.len(), 1);
+        assert_eq!(calls[0].name, "shell");
+        assert_eq!(
+            calls[0].arguments.get("command").unwrap().as_str().unwrap(),
+            "ls -la"
+        );
+    }
+
+    #[test]
+    fn parse_tool_calls_extracts_multiple_calls() {
+        let response = r#"<tool_call>
+{"name": "file_read", "arguments": {"path": "a.txt"}}
+</tool_call>
+<tool_call>
+{"name": "file_read", "arguments": {"path": "b.txt"}}
 </tool_call>"#;
 
         let (_, calls) = parse_tool_calls(response);
@@ -4711,6 +5706,36 @@ Tail"#;
         assert!(history[3].content.contains("recent 2"));
     }
 
+    #[tokio::test]
+    async fn auto_compact_history_triggers_on_context_window_below_message_cap() {
+        let provider = NonVisionProvider {
+            calls: Arc::new(AtomicUsize::new(0)),
+        };
+        let big = "x".repeat(4_000);
+        let mut history = vec![ChatMessage::system("sys")];
+        for _ in 0..4 {
+            history.push(ChatMessage::user(big.clone()));
+            history.push(ChatMessage::assistant(big.clone()));
+        }
+
+        // 8 messages of ~1k tokens each: far below the cap of 50 messages
+        // and a 128k window, but over 75% of an 8k window.
+        let mut roomy = history.clone();
+        let compacted = auto_compact_history(&mut roomy, &provider, "m", 50, Some(128_000))
+            .await
+            .unwrap();
+        assert!(!compacted);
+        assert_eq!(roomy.len(), 9);
+
+        let compacted = auto_compact_history(&mut history, &provider, "m", 50, Some(8_000))
+            .await
+            .unwrap();
+        assert!(compacted);
+        assert_eq!(history.len(), 6);
+        assert!(history[1].content.starts_with(COMPACTION_SUMMARY_PREFIX));
+        assert_eq!(provider.calls.load(Ordering::SeqCst), 1);
+    }
+
     #[test]
     fn autosave_memory_key_has_prefix_and_uniqueness() {
         let key1 = autosave_memory_key("user_msg");
@@ -5605,4 +6630,390 @@ Let me check the result."#;
         assert_eq!(parsed["content"].as_str(), Some("answer"));
         assert!(parsed.get("reasoning_content").is_none());
     }
+
+    #[test]
+    fn one_shot_cache_key_ignores_volatile_prompt_section() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let cache = ResponseCache::new(tmp.path(), 60, 10).unwrap();
+        let prompt_at = |time: &str| format!("Rules\n\n{VOLATILE_PROMPT_HEADING}\nTime: {time}\n");
+
+        let first = one_shot_cache_key(Some(&cache), "m", &prompt_at("10:00"), "hi", 0.0);
+        let second = one_shot_cache_key(Some(&cache), "m", &prompt_at("10:05"), "hi", 0.0);
+        assert!(first.is_some());
+        assert_eq!(first, second);
+
+        assert!(one_shot_cache_key(Some(&cache), "m", &prompt_at("10:00"), "hi", 0.7).is_none());
+        assert!(one_shot_cache_key(None, "m", &prompt_at("10:00"), "hi", 0.0).is_none());
+    }
+
+    struct ParamsRecordingProvider {
+        responses: Mutex<VecDeque<&'static str>>,
+        params: Mutex<Vec<Option<GenerationParams>>>,
+    }
+
+    #[async_trait]
+    impl Provider for ParamsRecordingProvider {
+        async fn chat_with_system(
+            &self,
+            _system_prompt: Option<&str>,
+            _message: &str,
+            _model: &str,
+            _temperature: f64,
+        ) -> anyhow::Result<String> {
+            anyhow::bail!("chat_with_system should not be used in params recording tests");
+        }
+
+        async fn chat(
+            &self,
+            request: ChatRequest<'_>,
+            _model: &str,
+            _temperature: f64,
+        ) -> anyhow::Result<ChatResponse> {
+            self.params.lock().unwrap().push(request.params.cloned());
+            let text = self.responses.lock().unwrap().pop_front().unwrap_or("done");
+            Ok(ChatResponse {
+                text: Some(text.to_string()),
+                tool_calls: Vec::new(),
+                usage: None,
+                reasoning_content: None,
+            })
+        }
+    }
+
+    /// Tool whose successful calls switch the turn's generation parameters.
+    struct GenerationOverrideTool {
+        name: &'static str,
+        change: tools::GenerationOverride,
+    }
+
+    #[async_trait]
+    impl Tool for GenerationOverrideTool {
+        fn name(&self) -> &str {
+            self.name
+        }
+
+        fn description(&self) -> &str {
+            "Starts or finishes a procedure with its own generation limits"
+        }
+
+        fn parameters_schema(&self) -> serde_json::Value {
+            serde_json::json!({ "type": "object" })
+        }
+
+        async fn execute(
+            &self,
+            _args: serde_json::Value,
+        ) -> anyhow::Result<crate::tools::ToolResult> {
+            Ok(crate::tools::ToolResult {
+                success: true,
+                output: "started".into(),
+                error: None,
+            })
+        }
+
+        fn generation_override(
+            &self,
+            _args: &serde_json::Value,
+        ) -> Option<tools::GenerationOverride> {
+            Some(self.change.clone())
+        }
+    }
+
+    #[tokio::test]
+    async fn run_tool_call_loop_scopes_tool_generation_override_to_the_procedure() {
+        let provider = ParamsRecordingProvider {
+            responses: Mutex::new(VecDeque::from([
+                "<tool_call>\n{\"name\":\"start_procedure\",\"arguments\":{}}\n</tool_call>",
+                "<tool_call>\n{\"name\":\"finish_procedure\",\"arguments\":{}}\n</tool_call>",
+                "done",
+            ])),
+            params: Mutex::new(Vec::new()),
+        };
+        let tools_registry: Vec<Box<dyn Tool>> = vec![
+            Box::new(GenerationOverrideTool {
+                name: "start_procedure",
+                change: tools::GenerationOverride::Apply(GenerationParams {
+                    max_tokens: Some(64),
+                    ..GenerationParams::default()
+                }),
+            }),
+            Box::new(GenerationOverrideTool {
+                name: "finish_procedure",
+                change: tools::GenerationOverride::Reset,
+            }),
+        ];
+        let base = GenerationParams {
+            seed: Some(7),
+            ..GenerationParams::default()
+        };
+        let mut history = vec![
+            ChatMessage::system("test-system"),
+            ChatMessage::user("run the procedure"),
+        ];
+
+        let result = run_tool_call_loop(
+            &provider,
+            &mut history,
+            &tools_registry,
+            &NoopObserver,
+            "mock-provider",
+            "mock-model",
+            0.0,
+            true,
+            None,
+            "cli",
+            &crate::config::MultimodalConfig::default(),
+            4,
+            None,
+            None,
+            None,
+            &[],
+            Some(&base),
+            None,
+            None,
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(result, "done");
+        let params = provider.params.lock().unwrap();
+        assert_eq!(params[0].as_ref(), Some(&base));
+        assert_eq!(
+            params[1],
+            Some(GenerationParams {
+                max_tokens: Some(64),
+                seed: Some(7),
+                ..GenerationParams::default()
+            })
+        );
+        // The final answer is back on the turn's own parameters.
+        assert_eq!(params[2].as_ref(), Some(&base));
+    }
+
+    #[tokio::test]
+    async fn run_tool_call_loop_records_usage_with_cache_tokens() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let cost_config = crate::config::CostConfig {
+            enabled: true,
+            ..crate::config::CostConfig::default()
+        };
+        let tracker = Arc::new(crate::cost::CostTracker::new(cost_config, tmp.path()).unwrap());
+        let recorder = UsageRecorder::new(
+            Arc::clone(&tracker),
+            std::collections::HashMap::new(),
+            tmp.path(),
+        );
+        let provider = ScriptedProvider {
+            responses: Arc::new(Mutex::new(VecDeque::from([ChatResponse {
+                text: Some("done".into()),
+                tool_calls: Vec::new(),
+                usage: Some(crate::providers::traits::TokenUsage {
+                    input_tokens: Some(100),
+                    output_tokens: Some(20),
+                    cache_read_tokens: Some(4000),
+                    cache_write_tokens: Some(500),
+                }),
+                reasoning_content: None,
+            }]))),
+            capabilities: ProviderCapabilities::default(),
+        };
+        let mut history = vec![ChatMessage::system("test-system"), ChatMessage::user("hi")];
+
+        run_tool_call_loop(
+            &provider,
+            &mut history,
+            &[],
+            &NoopObserver,
+            "anthropic",
+            "claude-sonnet-4-20250514",
+            0.0,
+            true,
+            None,
+            "cli",
+            &crate::config::MultimodalConfig::default(),
+            4,
+            None,
+            None,
+            None,
+            &[],
+            None,
+            None,
+            Some(&recorder),
+        )
+        .await
+        .unwrap();
+
+        let summary = tracker.get_summary().unwrap();
+        assert_eq!(summary.request_count, 1);
+        assert_eq!(summary.cache_read_tokens, 4000);
+        assert_eq!(summary.cache_write_tokens, 500);
+        assert!(summary.cache_savings_usd > 0.0);
+        assert!(summary
+            .by_model
+            .contains_key("anthropic/claude-sonnet-4-20250514"));
+    }
+
+    /// Records the model and messages of every request.
+    #[derive(Default)]
+    struct RequestRecordingProvider {
+        requests: Mutex<Vec<(String, Vec<ChatMessage>)>>,
+    }
+
+    #[async_trait]
+    impl Provider for RequestRecordingProvider {
+        async fn chat_with_system(
+            &self,
+            _system_prompt: Option<&str>,
+            _message: &str,
+            _model: &str,
+            _temperature: f64,
+        ) -> anyhow::Result<String> {
+            anyhow::bail!("chat_with_system should not be used in request recording tests");
+        }
+
+        async fn chat(
+            &self,
+            request: ChatRequest<'_>,
+            model: &str,
+            _temperature: f64,
+        ) -> anyhow::Result<ChatResponse> {
+            self.requests
+                .lock()
+                .unwrap()
+                .push((model.to_string(), request.messages.to_vec()));
+            Ok(ChatResponse {
+                text: Some("done".into()),
+                tool_calls: Vec::new(),
+                usage: None,
+                reasoning_content: None,
+            })
+        }
+    }
+
+    struct RewritingHook {
+        cancel_llm_call: bool,
+    }
+
+    #[async_trait]
+    impl crate::hooks::HookHandler for RewritingHook {
+        fn name(&self) -> &str {
+            "rewriting"
+        }
+
+        async fn before_model_resolve(
+            &self,
+            provider: String,
+            _model: String,
+        ) -> crate::hooks::HookResult<(String, String)> {
+            crate::hooks::HookResult::Continue((provider, "resolved-model".into()))
+        }
+
+        async fn before_prompt_build(&self, prompt: String) -> crate::hooks::HookResult<String> {
+            crate::hooks::HookResult::Continue(format!("{prompt}\nhooked prompt"))
+        }
+
+        async fn before_llm_call(
+            &self,
+            mut messages: Vec<ChatMessage>,
+            model: String,
+        ) -> crate::hooks::HookResult<(Vec<ChatMessage>, String)> {
+            if self.cancel_llm_call {
+                return crate::hooks::HookResult::Cancel("budget exhausted".into());
+            }
+            messages.push(ChatMessage::user("hooked input"));
+            crate::hooks::HookResult::Continue((messages, format!("{model}-hooked")))
+        }
+    }
+
+    fn rewriting_hooks(cancel_llm_call: bool) -> crate::hooks::HookRunner {
+        let mut hooks = crate::hooks::HookRunner::new();
+        hooks.register(Box::new(RewritingHook { cancel_llm_call }));
+        hooks
+    }
+
+    #[tokio::test]
+    async fn modifying_hooks_rewrite_model_prompt_and_llm_request() {
+        let hooks = rewriting_hooks(false);
+        let (provider_name, model) =
+            resolve_model_with_hooks(Some(&hooks), "openrouter", "configured-model")
+                .await
+                .unwrap();
+        assert_eq!(
+            (provider_name.as_str(), model.as_str()),
+            ("openrouter", "resolved-model")
+        );
+        let prompt = build_prompt_with_hooks(Some(&hooks), "base prompt".into())
+            .await
+            .unwrap();
+        assert_eq!(prompt, "base prompt\nhooked prompt");
+
+        let provider = RequestRecordingProvider::default();
+        let mut history = vec![ChatMessage::system(&prompt), ChatMessage::user("hi")];
+        let result = run_tool_call_loop(
+            &provider,
+            &mut history,
+            &[],
+            &NoopObserver,
+            &provider_name,
+            &model,
+            0.0,
+            true,
+            None,
+            "cli",
+            &crate::config::MultimodalConfig::default(),
+            4,
+            None,
+            None,
+            Some(&hooks),
+            &[],
+            None,
+            None,
+            None,
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(result, "done");
+        let requests = provider.requests.lock().unwrap();
+        assert_eq!(requests[0].0, "resolved-model-hooked");
+        assert_eq!(requests[0].1.last().unwrap().content, "hooked input");
+        // The rewrite applies to the request only, not the stored history.
+        assert!(!history.iter().any(|m| m.content == "hooked input"));
+    }
+
+    #[tokio::test]
+    async fn before_llm_call_hook_cancel_stops_the_turn() {
+        let hooks = rewriting_hooks(true);
+        let provider = RequestRecordingProvider::default();
+        let mut history = vec![ChatMessage::system("system"), ChatMessage::user("hi")];
+
+        let err = run_tool_call_loop(
+            &provider,
+            &mut history,
+            &[],
+            &NoopObserver,
+            "mock-provider",
+            "mock-model",
+            0.0,
+            true,
+            None,
+            "cli",
+            &crate::config::MultimodalConfig::default(),
+            4,
+            None,
+            None,
+            Some(&hooks),
+            &[],
+            None,
+            None,
+            None,
+        )
+        .await
+        .unwrap_err();
+
+        assert!(err
+            .to_string()
+            .contains("cancelled by hook: budget exhausted"));
+        assert!(provider.requests.lock().unwrap().is_empty());
+    }
 }
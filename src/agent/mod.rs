@@ -4,7 +4,9 @@ pub mod classifier;
 pub mod dispatcher;
 pub mod loop_;
 pub mod memory_loader;
+pub mod outcome;
 pub mod prompt;
+pub mod replay;
 pub mod research;
 
 #[cfg(test)]
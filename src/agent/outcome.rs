@@ -0,0 +1,171 @@
+//! Structured verdicts on finished agent runs.
+//!
+//! Background runners (goal loop, cron agent jobs) hand the task and the
+//! agent's final reply to the model once more and get back a
+//! schema-constrained [`TaskOutcome`], instead of guessing success from
+//! keywords in free text.
+
+use crate::config::Config;
+use crate::providers::structured::{chat_structured, DEFAULT_MAX_REPAIRS};
+use crate::providers::{self, ChatMessage, Provider};
+use crate::util::truncate_with_ellipsis;
+use anyhow::Result;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// Maximum characters of agent output sent for assessment.
+const MAX_ASSESSED_OUTPUT_CHARS: usize = 8_000;
+
+const ASSESSMENT_SYSTEM_PROMPT: &str = "You review the result of an autonomous agent run. \
+Decide whether the agent accomplished the task, based only on the task and the agent's final \
+reply. Use \"failed\" when the reply reports errors, refusals or missing results, \"skipped\" \
+when the agent determined the task needed no action, and \"completed\" otherwise.";
+
+/// Verdict on a task.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum OutcomeStatus {
+    Completed,
+    Failed,
+    Skipped,
+}
+
+/// Structured assessment of one agent run.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct TaskOutcome {
+    /// Whether the task was accomplished.
+    pub status: OutcomeStatus,
+    /// One or two sentences on what was done or what went wrong.
+    pub summary: String,
+}
+
+impl TaskOutcome {
+    /// `true` unless the run failed; skipped tasks count as done.
+    pub fn succeeded(&self) -> bool {
+        self.status != OutcomeStatus::Failed
+    }
+}
+
+fn assessment_messages(task: &str, output: &str) -> Vec<ChatMessage> {
+    vec![
+        ChatMessage::system(ASSESSMENT_SYSTEM_PROMPT),
+        ChatMessage::user(format!(
+            "Task:\n{}\n\nAgent reply:\n{}",
+            task.trim(),
+            truncate_with_ellipsis(output.trim(), MAX_ASSESSED_OUTPUT_CHARS)
+        )),
+    ]
+}
+
+/// Assess an agent run with an existing provider.
+pub async fn assess_with_provider(
+    provider: &dyn Provider,
+    model: &str,
+    task: &str,
+    output: &str,
+) -> Result<TaskOutcome> {
+    chat_structured(
+        provider,
+        &assessment_messages(task, output),
+        model,
+        0.0,
+        DEFAULT_MAX_REPAIRS,
+    )
+    .await
+}
+
+/// Assess an agent run using the configured default provider.
+pub async fn assess(
+    config: &Config,
+    model_override: Option<&str>,
+    task: &str,
+    output: &str,
+) -> Result<TaskOutcome> {
+    let provider_name = config.default_provider.as_deref().unwrap_or("openrouter");
+    let model = model_override
+        .or(config.default_model.as_deref())
+        .unwrap_or("anthropic/claude-sonnet-4");
+
+    let provider_runtime_options = providers::ProviderRuntimeOptions {
+        auth_profile_override: None,
+        provider_api_url: config.api_url.clone(),
+        zeroclaw_dir: config.config_path.parent().map(std::path::PathBuf::from),
+        secrets_encrypt: config.secrets.encrypt,
+        reasoning_enabled: config.runtime.reasoning_enabled,
+        extra_headers: std::collections::HashMap::new(),
+    };
+    let provider = providers::create_routed_provider_with_options(
+        provider_name,
+        config.api_key.as_deref(),
+        config.api_url.as_deref(),
+        &config.reliability,
+        &config.model_routes,
+        model,
+        &provider_runtime_options,
+    )?;
+
+    assess_with_provider(provider.as_ref(), model, task, output).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::providers::{ChatRequest, ChatResponse};
+    use async_trait::async_trait;
+
+    struct FixedProvider(&'static str);
+
+    #[async_trait]
+    impl Provider for FixedProvider {
+        async fn chat_with_system(
+            &self,
+            _system_prompt: Option<&str>,
+            _message: &str,
+            _model: &str,
+            _temperature: f64,
+        ) -> Result<String> {
+            Ok(self.0.to_string())
+        }
+
+        async fn chat(
+            &self,
+            request: ChatRequest<'_>,
+            _model: &str,
+            _temperature: f64,
+        ) -> Result<ChatResponse> {
+            let format = request
+                .response_format
+                .expect("assessment requests a schema");
+            assert_eq!(format.name, "TaskOutcome");
+            assert!(request.messages[1].content.contains("Task:\nDeploy"));
+            Ok(ChatResponse {
+                text: Some(self.0.to_string()),
+                tool_calls: Vec::new(),
+                usage: None,
+                reasoning_content: None,
+            })
+        }
+    }
+
+    #[tokio::test]
+    async fn assess_parses_structured_verdict() {
+        let provider =
+            FixedProvider(r#"{"status": "failed", "summary": "Deploy script exited 1"}"#);
+        let outcome = assess_with_provider(&provider, "m", "Deploy", "Error: exit code 1")
+            .await
+            .unwrap();
+
+        assert_eq!(outcome.status, OutcomeStatus::Failed);
+        assert_eq!(outcome.summary, "Deploy script exited 1");
+        assert!(!outcome.succeeded());
+    }
+
+    #[test]
+    fn skipped_counts_as_success() {
+        let outcome = TaskOutcome {
+            status: OutcomeStatus::Skipped,
+            summary: "Nothing to do".into(),
+        };
+        assert!(outcome.succeeded());
+    }
+}
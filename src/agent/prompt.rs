@@ -1,5 +1,6 @@
 use crate::config::IdentityConfig;
 use crate::identity;
+use crate::providers::VOLATILE_PROMPT_HEADING;
 use crate::skills::Skill;
 use crate::tools::Tool;
 use anyhow::Result;
@@ -38,9 +39,10 @@ impl SystemPromptBuilder {
                 Box::new(SafetySection),
                 Box::new(SkillsSection),
                 Box::new(WorkspaceSection),
-                Box::new(DateTimeSection),
                 Box::new(RuntimeSection),
                 Box::new(ChannelMediaSection),
+                // Changes every turn; last so the prefix stays cacheable.
+                Box::new(DateTimeSection),
             ],
         }
     }
@@ -209,7 +211,7 @@ impl PromptSection for DateTimeSection {
     fn build(&self, _ctx: &PromptContext<'_>) -> Result<String> {
         let now = Local::now();
         Ok(format!(
-            "## Current Date & Time\n\n{} ({})",
+            "{VOLATILE_PROMPT_HEADING}\n\n{} ({})",
             now.format("%Y-%m-%d %H:%M:%S"),
             now.format("%Z")
         ))
@@ -358,6 +360,11 @@ mod tests {
         assert!(prompt.contains("## Tools"));
         assert!(prompt.contains("test_tool"));
         assert!(prompt.contains("instr"));
+
+        // Everything before the per-turn date/time section stays cacheable.
+        let volatile_at = prompt.find(VOLATILE_PROMPT_HEADING).unwrap();
+        assert!(prompt.find("## Runtime").unwrap() < volatile_at);
+        assert!(prompt.find("## Channel Media Markers").unwrap() < volatile_at);
     }
 
     #[test]
@@ -375,6 +382,9 @@ mod tests {
                 kind: "shell".into(),
                 command: "echo ok".into(),
                 args: std::collections::HashMap::new(),
+                parameters: None,
+                input: crate::skills::SkillToolInput::Env,
+                timeout_secs: None,
             }],
             prompts: vec!["Run smoke tests before deploy.".into()],
             location: None,
@@ -413,6 +423,9 @@ mod tests {
                 kind: "shell".into(),
                 command: "echo ok".into(),
                 args: std::collections::HashMap::new(),
+                parameters: None,
+                input: crate::skills::SkillToolInput::Env,
+                timeout_secs: None,
             }],
             prompts: vec!["Run smoke tests before deploy.".into()],
             location: Some(Path::new("/tmp/workspace/skills/deploy/SKILL.md").to_path_buf()),
@@ -473,6 +486,9 @@ mod tests {
                 kind: "shell&exec".into(),
                 command: "cargo clippy".into(),
                 args: std::collections::HashMap::new(),
+                parameters: None,
+                input: crate::skills::SkillToolInput::Env,
+                timeout_secs: None,
             }],
             prompts: vec!["Use <tool_call> and & keep output \"safe\"".into()],
             location: None,
@@ -0,0 +1,768 @@
+//! Deterministic replay of recorded agent turns.
+//!
+//! `zeroclaw replay <trace-id>` loads one turn from the runtime trace and
+//! re-runs it through [`run_tool_call_loop`] with a [`ReplayProvider`] that
+//! returns the recorded model responses in order and stub tools that return
+//! the recorded tool outputs. The replayed tool calls and final text are then
+//! compared against the recording, so a production bug report captured with
+//! `runtime_trace_mode = "full"` becomes an offline regression check for
+//! prompt and parser changes.
+
+use crate::agent::loop_::{run_tool_call_loop, scrub_credentials};
+use crate::config::Config;
+use crate::observability::runtime_trace::{self, RuntimeTraceEvent};
+use crate::observability::NoopObserver;
+use crate::providers::traits::{ProviderCapabilities, TokenUsage, ToolCall};
+use crate::providers::{ChatMessage, ChatRequest, ChatResponse, Provider};
+use crate::tools::{Tool, ToolResult};
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use serde::Serialize;
+use serde_json::Value;
+use std::collections::{HashMap, VecDeque};
+use std::fmt;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+
+/// A tool invocation, as recorded or as replayed.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ToolInvocation {
+    pub name: String,
+    pub arguments: Value,
+}
+
+impl fmt::Display for ToolInvocation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}({})", self.name, self.arguments)
+    }
+}
+
+/// One agent turn reconstructed from runtime trace events.
+#[derive(Debug, Clone)]
+pub struct RecordedTurn {
+    pub turn_id: String,
+    pub channel: String,
+    pub provider: String,
+    pub model: String,
+    pub user_message: String,
+    pub native_tools: bool,
+    /// Tools that were offered to the model.
+    pub tools: Vec<String>,
+    /// Model responses in request order; `Err` holds a recorded provider error.
+    pub responses: Vec<Result<ChatResponse, String>>,
+    /// Tool calls the loop executed, in order.
+    pub tool_calls: Vec<ToolInvocation>,
+    /// Recorded tool outputs, per tool name, in execution order.
+    pub tool_outputs: HashMap<String, VecDeque<ToolResult>>,
+    /// Final reply text, when the turn completed.
+    pub final_text: Option<String>,
+    /// Gaps in the recording that make the replay less faithful.
+    pub warnings: Vec<String>,
+}
+
+impl RecordedTurn {
+    /// Rebuild a turn from its events (oldest first).
+    pub fn from_events(events: &[RuntimeTraceEvent]) -> Result<Self> {
+        let first = events
+            .first()
+            .context("no runtime trace events recorded for this turn")?;
+
+        let mut turn = Self {
+            turn_id: first.turn_id.clone().unwrap_or_default(),
+            channel: first.channel.clone().unwrap_or_else(|| "cli".into()),
+            provider: first.provider.clone().unwrap_or_else(|| "unknown".into()),
+            model: first.model.clone().unwrap_or_default(),
+            user_message: String::new(),
+            native_tools: false,
+            tools: Vec::new(),
+            responses: Vec::new(),
+            tool_calls: Vec::new(),
+            tool_outputs: HashMap::new(),
+            final_text: None,
+            warnings: Vec::new(),
+        };
+        let mut saw_turn_start = false;
+
+        for event in events {
+            let payload = &event.payload;
+            match event.event_type.as_str() {
+                "turn_start" => {
+                    saw_turn_start = true;
+                    turn.user_message = payload_str(payload, "user_message").to_string();
+                    turn.native_tools = payload
+                        .get("native_tools")
+                        .and_then(Value::as_bool)
+                        .unwrap_or(false);
+                    turn.tools = payload
+                        .get("tools")
+                        .and_then(Value::as_array)
+                        .map(|tools| {
+                            tools
+                                .iter()
+                                .filter_map(Value::as_str)
+                                .map(str::to_string)
+                                .collect()
+                        })
+                        .unwrap_or_default();
+                }
+                "llm_response" if event.success == Some(true) => {
+                    turn.responses
+                        .push(Ok(recorded_response(payload, &mut turn.warnings)));
+                }
+                "llm_response" => {
+                    let message = event
+                        .message
+                        .clone()
+                        .unwrap_or_else(|| "recorded provider error".into());
+                    turn.responses.push(Err(message));
+                }
+                "tool_call_start" => {
+                    turn.tool_calls.push(ToolInvocation {
+                        name: payload_str(payload, "tool").to_string(),
+                        arguments: parse_arguments(payload_str(payload, "arguments")),
+                    });
+                }
+                "tool_call_result" if payload.get("output").is_some() => {
+                    let output = payload_str(payload, "output").to_string();
+                    let result = if event.success == Some(true) {
+                        ToolResult {
+                            success: true,
+                            output,
+                            error: None,
+                        }
+                    } else {
+                        let reason = event.message.clone().unwrap_or_else(|| {
+                            output
+                                .strip_prefix("Error: ")
+                                .unwrap_or(&output)
+                                .to_string()
+                        });
+                        ToolResult {
+                            success: false,
+                            output: String::new(),
+                            error: Some(reason),
+                        }
+                    };
+                    turn.tool_outputs
+                        .entry(payload_str(payload, "tool").to_string())
+                        .or_default()
+                        .push_back(result);
+                }
+                "turn_final_response" => {
+                    turn.final_text = Some(payload_str(payload, "text").to_string());
+                }
+                _ => {}
+            }
+        }
+
+        if turn.responses.is_empty() {
+            anyhow::bail!("turn {} has no recorded model responses", turn.turn_id);
+        }
+
+        if !saw_turn_start {
+            turn.warnings.push(
+                "trace has no turn_start event; user message and tool list are inferred".into(),
+            );
+            turn.native_tools = turn
+                .responses
+                .iter()
+                .any(|response| response.as_ref().is_ok_and(|r| !r.tool_calls.is_empty()));
+            for call in &turn.tool_calls {
+                if !turn.tools.contains(&call.name) {
+                    turn.tools.push(call.name.clone());
+                }
+            }
+        }
+
+        Ok(turn)
+    }
+}
+
+fn payload_str<'a>(payload: &'a Value, key: &str) -> &'a str {
+    payload.get(key).and_then(Value::as_str).unwrap_or_default()
+}
+
+fn parse_arguments(raw: &str) -> Value {
+    serde_json::from_str(raw).unwrap_or_else(|_| Value::String(raw.to_string()))
+}
+
+fn recorded_response(payload: &Value, warnings: &mut Vec<String>) -> ChatResponse {
+    let tool_calls: Vec<ToolCall> = payload
+        .get("tool_calls")
+        .and_then(Value::as_array)
+        .map(|calls| {
+            calls
+                .iter()
+                .map(|call| ToolCall {
+                    id: payload_str(call, "id").to_string(),
+                    name: payload_str(call, "name").to_string(),
+                    arguments: payload_str(call, "arguments").to_string(),
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+
+    let native_count = payload
+        .get("native_tool_calls")
+        .and_then(Value::as_u64)
+        .unwrap_or(0);
+    if tool_calls.is_empty() && native_count > 0 {
+        warnings.push(format!(
+            "a response carried {native_count} native tool call(s) that the trace did not record"
+        ));
+    }
+
+    ChatResponse {
+        text: Some(payload_str(payload, "raw_response").to_string()),
+        tool_calls,
+        usage: Some(TokenUsage {
+            input_tokens: payload.get("input_tokens").and_then(Value::as_u64),
+            output_tokens: payload.get("output_tokens").and_then(Value::as_u64),
+            cache_read_tokens: None,
+            cache_write_tokens: None,
+        }),
+        reasoning_content: None,
+    }
+}
+
+/// Provider that answers with recorded responses, in order.
+pub struct ReplayProvider {
+    responses: Mutex<VecDeque<Result<ChatResponse, String>>>,
+    native_tools: bool,
+    requests: AtomicUsize,
+}
+
+impl ReplayProvider {
+    pub fn new(responses: Vec<Result<ChatResponse, String>>, native_tools: bool) -> Self {
+        Self {
+            responses: Mutex::new(responses.into()),
+            native_tools,
+            requests: AtomicUsize::new(0),
+        }
+    }
+
+    /// Number of model requests made so far, including ones past the recording.
+    pub fn requests(&self) -> usize {
+        self.requests.load(Ordering::SeqCst)
+    }
+
+    fn next_response(&self) -> Result<ChatResponse> {
+        self.requests.fetch_add(1, Ordering::SeqCst);
+        match self
+            .responses
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .pop_front()
+        {
+            Some(Ok(response)) => Ok(response),
+            Some(Err(message)) => Err(anyhow::anyhow!(message)),
+            None => anyhow::bail!("replay: the recording has no more model responses"),
+        }
+    }
+}
+
+#[async_trait]
+impl Provider for ReplayProvider {
+    fn capabilities(&self) -> ProviderCapabilities {
+        ProviderCapabilities {
+            native_tool_calling: self.native_tools,
+            vision: true,
+        }
+    }
+
+    async fn chat_with_system(
+        &self,
+        _system_prompt: Option<&str>,
+        _message: &str,
+        _model: &str,
+        _temperature: f64,
+    ) -> Result<String> {
+        Ok(self.next_response()?.text.unwrap_or_default())
+    }
+
+    async fn chat(
+        &self,
+        _request: ChatRequest<'_>,
+        _model: &str,
+        _temperature: f64,
+    ) -> Result<ChatResponse> {
+        self.next_response()
+    }
+}
+
+type SharedOutputs = Arc<Mutex<HashMap<String, VecDeque<ToolResult>>>>;
+
+/// Stand-in for a real tool that returns recorded outputs and logs its calls.
+struct ReplayTool {
+    name: String,
+    outputs: SharedOutputs,
+    calls: Arc<Mutex<Vec<ToolInvocation>>>,
+}
+
+#[async_trait]
+impl Tool for ReplayTool {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn description(&self) -> &str {
+        "回放录制的工具输出，用于确定性回归测试。"
+    }
+
+    fn parameters_schema(&self) -> Value {
+        serde_json::json!({ "type": "object" })
+    }
+
+    async fn execute(&self, args: Value) -> Result<ToolResult> {
+        self.calls
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .push(ToolInvocation {
+                name: self.name.clone(),
+                arguments: args,
+            });
+        let recorded = self
+            .outputs
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .get_mut(&self.name)
+            .and_then(VecDeque::pop_front);
+        Ok(recorded.unwrap_or_else(|| ToolResult {
+            success: false,
+            output: String::new(),
+            error: Some(format!("replay: no recorded output left for {}", self.name)),
+        }))
+    }
+}
+
+/// A point where the replayed turn departs from the recording.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum Divergence {
+    /// The n-th tool call differs in tool, arguments or presence.
+    ToolCall {
+        index: usize,
+        expected: Option<ToolInvocation>,
+        actual: Option<ToolInvocation>,
+    },
+    /// The final reply text differs.
+    FinalText {
+        expected: Option<String>,
+        actual: Option<String>,
+    },
+    /// The loop made a different number of model requests.
+    ModelRequests { expected: usize, actual: usize },
+}
+
+impl fmt::Display for Divergence {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::ToolCall {
+                index,
+                expected: Some(expected),
+                actual: Some(actual),
+            } if expected.name == actual.name => write!(
+                f,
+                "tool call #{}: different arguments for {}: expected {}, got {}",
+                index + 1,
+                expected.name,
+                expected.arguments,
+                actual.arguments
+            ),
+            Self::ToolCall {
+                index,
+                expected: Some(expected),
+                actual: Some(actual),
+            } => write!(
+                f,
+                "tool call #{}: different tool: expected {expected}, got {actual}",
+                index + 1
+            ),
+            Self::ToolCall {
+                index,
+                expected: Some(expected),
+                actual: None,
+            } => write!(f, "tool call #{}: missing {expected}", index + 1),
+            Self::ToolCall {
+                index,
+                actual: Some(actual),
+                ..
+            } => write!(f, "tool call #{}: unexpected {actual}", index + 1),
+            Self::ToolCall { index, .. } => write!(f, "tool call #{}", index + 1),
+            Self::FinalText { expected, actual } => write!(
+                f,
+                "final text differs: expected {:?}, got {:?}",
+                expected.as_deref().unwrap_or("<none>"),
+                actual.as_deref().unwrap_or("<none>")
+            ),
+            Self::ModelRequests { expected, actual } => write!(
+                f,
+                "model requests: recorded {expected}, replay made {actual}"
+            ),
+        }
+    }
+}
+
+/// Outcome of replaying one turn.
+#[derive(Debug, Clone, Serialize)]
+pub struct ReplayReport {
+    pub turn_id: String,
+    pub tool_calls: Vec<ToolInvocation>,
+    pub final_text: Option<String>,
+    /// Error the replayed loop returned, if any.
+    pub error: Option<String>,
+    pub divergences: Vec<Divergence>,
+    pub warnings: Vec<String>,
+}
+
+impl ReplayReport {
+    /// True when the replay reproduced the recording exactly.
+    pub fn is_match(&self) -> bool {
+        self.divergences.is_empty()
+    }
+}
+
+/// Re-run a recorded turn against the current agent loop and compare.
+pub async fn replay_turn(turn: &RecordedTurn, config: &Config) -> ReplayReport {
+    let provider = ReplayProvider::new(turn.responses.clone(), turn.native_tools);
+    let outputs: SharedOutputs = Arc::new(Mutex::new(turn.tool_outputs.clone()));
+    let calls = Arc::new(Mutex::new(Vec::new()));
+    let tools: Vec<Box<dyn Tool>> = turn
+        .tools
+        .iter()
+        .map(|name| {
+            Box::new(ReplayTool {
+                name: name.clone(),
+                outputs: Arc::clone(&outputs),
+                calls: Arc::clone(&calls),
+            }) as Box<dyn Tool>
+        })
+        .collect();
+
+    let mut history = vec![ChatMessage::user(turn.user_message.clone())];
+    let result = run_tool_call_loop(
+        &provider,
+        &mut history,
+        &tools,
+        &NoopObserver,
+        &turn.provider,
+        &turn.model,
+        config.default_temperature,
+        true,
+        None,
+        &turn.channel,
+        &config.multimodal,
+        config.agent.max_tool_iterations,
+        None,
+        None,
+        None,
+        &[],
+        None,
+        None,
+        None,
+    )
+    .await;
+
+    let (final_text, error) = match result {
+        Ok(text) => (Some(scrub_credentials(&text)), None),
+        Err(e) => (None, Some(e.to_string())),
+    };
+    let tool_calls = calls.lock().unwrap_or_else(|e| e.into_inner()).clone();
+
+    let mut divergences = Vec::new();
+    for index in 0..turn.tool_calls.len().max(tool_calls.len()) {
+        let expected = turn.tool_calls.get(index);
+        let actual = tool_calls.get(index);
+        if !same_invocation(expected, actual) {
+            divergences.push(Divergence::ToolCall {
+                index,
+                expected: expected.cloned(),
+                actual: actual.cloned(),
+            });
+        }
+    }
+    if final_text != turn.final_text {
+        divergences.push(Divergence::FinalText {
+            expected: turn.final_text.clone(),
+            actual: final_text.clone(),
+        });
+    }
+    if provider.requests() != turn.responses.len() {
+        divergences.push(Divergence::ModelRequests {
+            expected: turn.responses.len(),
+            actual: provider.requests(),
+        });
+    }
+
+    ReplayReport {
+        turn_id: turn.turn_id.clone(),
+        tool_calls,
+        final_text,
+        error,
+        divergences,
+        warnings: turn.warnings.clone(),
+    }
+}
+
+/// Recorded arguments went through credential scrubbing, so compare the
+/// replayed arguments in the same scrubbed form.
+fn same_invocation(expected: Option<&ToolInvocation>, actual: Option<&ToolInvocation>) -> bool {
+    match (expected, actual) {
+        (Some(expected), Some(actual)) => {
+            expected.name == actual.name
+                && expected.arguments
+                    == parse_arguments(&scrub_credentials(&actual.arguments.to_string()))
+        }
+        (None, None) => true,
+        _ => false,
+    }
+}
+
+/// Entry point for `zeroclaw replay`. Accepts a runtime trace event id or a
+/// turn id and fails when the replay diverges from the recording.
+pub async fn run(config: &Config, trace_id: &str, json: bool) -> Result<()> {
+    let path = runtime_trace::resolve_trace_path(&config.observability, &config.workspace_dir);
+    let trace_id = trace_id.trim();
+
+    let turn_id = match runtime_trace::find_event_by_id(&path, trace_id)? {
+        Some(event) => event
+            .turn_id
+            .with_context(|| format!("trace event {trace_id} is not part of an agent turn"))?,
+        None => trace_id.to_string(),
+    };
+    let events = runtime_trace::load_turn_events(&path, &turn_id)?;
+    if events.is_empty() {
+        anyhow::bail!(
+            "No runtime trace events found for '{trace_id}' (path: {}).",
+            path.display()
+        );
+    }
+    let turn = RecordedTurn::from_events(&events)?;
+
+    // Keep the replayed loop from appending to the trace it is reading.
+    runtime_trace::disable();
+    let report = replay_turn(&turn, config).await;
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&report)?);
+    } else {
+        println!(
+            "Replayed turn {} ({} / {}, channel {})",
+            turn.turn_id, turn.provider, turn.model, turn.channel
+        );
+        println!(
+            "Model responses: {} recorded | Tool calls: {} recorded, {} replayed",
+            turn.responses.len(),
+            turn.tool_calls.len(),
+            report.tool_calls.len()
+        );
+        for warning in &report.warnings {
+            println!("warning: {warning}");
+        }
+        if let Some(error) = &report.error {
+            println!("Replay error: {error}");
+        }
+        if report.is_match() {
+            println!("Replay matches the recorded turn.");
+        } else {
+            println!("Divergences:");
+            for divergence in &report.divergences {
+                println!("  - {divergence}");
+            }
+        }
+    }
+
+    if !report.is_match() {
+        anyhow::bail!(
+            "Replay diverged from the recorded turn ({} difference(s))",
+            report.divergences.len()
+        );
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn event(event_type: &str, success: Option<bool>, payload: Value) -> RuntimeTraceEvent {
+        RuntimeTraceEvent {
+            id: uuid::Uuid::new_v4().to_string(),
+            timestamp: chrono::Utc::now().to_rfc3339(),
+            event_type: event_type.into(),
+            channel: Some("telegram".into()),
+            provider: Some("openrouter".into()),
+            model: Some("test-model".into()),
+            turn_id: Some("turn-1".into()),
+            success,
+            message: None,
+            payload,
+        }
+    }
+
+    /// A prompt-guided turn: one XML tool call, then a final answer.
+    fn recorded_events() -> Vec<RuntimeTraceEvent> {
+        vec![
+            event(
+                "turn_start",
+                None,
+                serde_json::json!({
+                    "user_message": "what is in notes.txt?",
+                    "native_tools": false,
+                    "tools": ["file_read", "shell"],
+                }),
+            ),
+            event(
+                "llm_response",
+                Some(true),
+                serde_json::json!({
+                    "raw_response": "<tool_call>\n{\"name\": \"file_read\", \"arguments\": {\"path\": \"notes.txt\"}}\n</tool_call>",
+                    "native_tool_calls": 0,
+                    "tool_calls": [],
+                }),
+            ),
+            event(
+                "tool_call_start",
+                None,
+                serde_json::json!({ "tool": "file_read", "arguments": "{\"path\":\"notes.txt\"}" }),
+            ),
+            event(
+                "tool_call_result",
+                Some(true),
+                serde_json::json!({ "tool": "file_read", "output": "buy milk" }),
+            ),
+            event(
+                "llm_response",
+                Some(true),
+                serde_json::json!({ "raw_response": "The note says: buy milk", "tool_calls": [] }),
+            ),
+            event(
+                "turn_final_response",
+                Some(true),
+                serde_json::json!({ "text": "The note says: buy milk" }),
+            ),
+        ]
+    }
+
+    #[test]
+    fn recorded_turn_is_rebuilt_from_events() {
+        let turn = RecordedTurn::from_events(&recorded_events()).unwrap();
+        assert_eq!(turn.turn_id, "turn-1");
+        assert_eq!(turn.channel, "telegram");
+        assert_eq!(turn.user_message, "what is in notes.txt?");
+        assert_eq!(turn.tools, ["file_read", "shell"]);
+        assert_eq!(turn.responses.len(), 2);
+        assert_eq!(
+            turn.tool_calls,
+            [ToolInvocation {
+                name: "file_read".into(),
+                arguments: serde_json::json!({ "path": "notes.txt" }),
+            }]
+        );
+        assert_eq!(turn.tool_outputs["file_read"][0].output, "buy milk");
+        assert_eq!(turn.final_text.as_deref(), Some("The note says: buy milk"));
+        assert!(turn.warnings.is_empty());
+    }
+
+    #[test]
+    fn turn_without_model_responses_is_rejected() {
+        let events = vec![event("turn_start", None, serde_json::json!({}))];
+        assert!(RecordedTurn::from_events(&events).is_err());
+    }
+
+    #[tokio::test]
+    async fn replay_of_unchanged_turn_matches() {
+        let turn = RecordedTurn::from_events(&recorded_events()).unwrap();
+        let report = replay_turn(&turn, &Config::default()).await;
+        assert!(report.is_match(), "{:?}", report.divergences);
+        assert_eq!(
+            report.final_text.as_deref(),
+            Some("The note says: buy milk")
+        );
+    }
+
+    #[tokio::test]
+    async fn replay_flags_changed_arguments_and_final_text() {
+        let mut events = recorded_events();
+        events[2].payload["arguments"] = "{\"path\":\"todo.txt\"}".into();
+        events[5].payload["text"] = "Nothing to do".into();
+
+        let turn = RecordedTurn::from_events(&events).unwrap();
+        let report = replay_turn(&turn, &Config::default()).await;
+
+        assert_eq!(report.divergences.len(), 2, "{:?}", report.divergences);
+        assert!(matches!(
+            &report.divergences[0],
+            Divergence::ToolCall { index: 0, expected: Some(e), actual: Some(a) }
+                if e.name == a.name && e.arguments != a.arguments
+        ));
+        assert!(matches!(
+            report.divergences[1],
+            Divergence::FinalText { .. }
+        ));
+        assert!(report.divergences[0]
+            .to_string()
+            .contains("different arguments for file_read"));
+    }
+
+    #[tokio::test]
+    async fn replay_flags_extra_model_requests() {
+        let mut events = recorded_events();
+        events.truncate(4);
+
+        let turn = RecordedTurn::from_events(&events).unwrap();
+        let report = replay_turn(&turn, &Config::default()).await;
+
+        assert!(report.error.is_some());
+        assert!(report.divergences.contains(&Divergence::ModelRequests {
+            expected: 1,
+            actual: 2,
+        }));
+    }
+
+    #[tokio::test]
+    async fn native_tool_calls_are_replayed() {
+        let events = vec![
+            event(
+                "turn_start",
+                None,
+                serde_json::json!({ "user_message": "list files", "native_tools": true, "tools": ["shell"] }),
+            ),
+            event(
+                "llm_response",
+                Some(true),
+                serde_json::json!({
+                    "raw_response": "",
+                    "native_tool_calls": 1,
+                    "tool_calls": [{ "id": "call_1", "name": "shell", "arguments": "{\"command\":\"ls\"}" }],
+                }),
+            ),
+            event(
+                "tool_call_start",
+                None,
+                serde_json::json!({ "tool": "shell", "arguments": "{\"command\":\"ls\"}" }),
+            ),
+            event(
+                "tool_call_result",
+                Some(true),
+                serde_json::json!({ "tool": "shell", "output": "a.txt" }),
+            ),
+            event(
+                "llm_response",
+                Some(true),
+                serde_json::json!({ "raw_response": "a.txt", "native_tool_calls": 0, "tool_calls": [] }),
+            ),
+            event(
+                "turn_final_response",
+                Some(true),
+                serde_json::json!({ "text": "a.txt" }),
+            ),
+        ];
+
+        let turn = RecordedTurn::from_events(&events).unwrap();
+        assert!(turn.native_tools);
+        let report = replay_turn(&turn, &Config::default()).await;
+        assert!(report.is_match(), "{:?}", report.divergences);
+        assert_eq!(report.tool_calls[0].name, "shell");
+    }
+}
@@ -163,6 +163,8 @@ pub async fn run_research_phase(
             } else {
                 None // Prompt-guided: tools are in system prompt
             },
+            response_format: None,
+            params: None,
         };
 
         let response: ChatResponse = provider.chat(request, model, temperature).await?;
@@ -71,7 +71,7 @@ use crate::agent::loop_::{build_tool_instructions, run_tool_call_loop, scrub_cre
 use crate::config::Config;
 use crate::identity;
 use crate::memory::{self, Memory};
-use crate::observability::{self, runtime_trace, Observer};
+use crate::observability::{self, runtime_trace, Observer, SpanKind, TraceSpan};
 use crate::providers::{self, ChatMessage, Provider};
 use crate::runtime;
 use crate::security::SecurityPolicy;
@@ -173,10 +173,11 @@ struct ChannelRuntimeDefaults {
     api_key: Option<String>,
     api_url: Option<String>,
     reliability: crate::config::ReliabilityConfig,
+    generation: crate::providers::GenerationParams,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
-struct ConfigFileStamp {
+pub(crate) struct ConfigFileStamp {
     modified: SystemTime,
     len: u64,
 }
@@ -224,6 +225,9 @@ struct ChannelRuntimeContext {
     multimodal: crate::config::MultimodalConfig,
     hooks: Option<Arc<crate::hooks::HookRunner>>,
     non_cli_excluded_tools: Arc<Vec<String>>,
+    race_context: Arc<providers::RaceContext>,
+    tool_modes: Arc<providers::ToolModes>,
+    usage_recorder: Option<Arc<crate::cost::UsageRecorder>>,
 }
 
 #[derive(Clone)]
@@ -565,6 +569,7 @@ fn runtime_defaults_from_config(config: &Config) -> ChannelRuntimeDefaults {
         api_key: config.api_key.clone(),
         api_url: config.api_url.clone(),
         reliability: config.reliability.clone(),
+        generation: config.agent.generation.clone(),
     }
 }
 
@@ -592,10 +597,11 @@ fn runtime_defaults_snapshot(ctx: &ChannelRuntimeContext) -> ChannelRuntimeDefau
         api_key: ctx.api_key.clone(),
         api_url: ctx.api_url.clone(),
         reliability: (*ctx.reliability).clone(),
+        generation: crate::providers::GenerationParams::default(),
     }
 }
 
-async fn config_file_stamp(path: &Path) -> Option<ConfigFileStamp> {
+pub(crate) async fn config_file_stamp(path: &Path) -> Option<ConfigFileStamp> {
     let metadata = tokio::fs::metadata(path).await.ok()?;
     let modified = metadata.modified().ok()?;
     Some(ConfigFileStamp {
@@ -621,12 +627,14 @@ fn decrypt_optional_secret_for_runtime_reload(
     Ok(())
 }
 
-async fn load_runtime_defaults_from_config_file(path: &Path) -> Result<ChannelRuntimeDefaults> {
-    let contents = tokio::fs::read_to_string(path)
-        .await
-        .with_context(|| format!("Failed to read {}", path.display()))?;
-    let mut parsed: Config =
-        toml::from_str(&contents).with_context(|| format!("Failed to parse {}", path.display()))?;
+fn load_runtime_defaults_from_config_file(path: &Path) -> Result<ChannelRuntimeDefaults> {
+    let (merged, _) = crate::config::layers::load_merged(
+        path,
+        crate::config::layers::active_profile().as_deref(),
+    )?;
+    let mut parsed: Config = toml::Value::Table(merged)
+        .try_into()
+        .with_context(|| format!("Failed to parse {}", path.display()))?;
     parsed.config_path = path.to_path_buf();
 
     if let Some(zeroclaw_dir) = path.parent() {
@@ -658,7 +666,7 @@ async fn maybe_apply_runtime_config_update(ctx: &ChannelRuntimeContext) -> Resul
         }
     }
 
-    let next_defaults = load_runtime_defaults_from_config_file(&config_path).await?;
+    let next_defaults = load_runtime_defaults_from_config_file(&config_path)?;
     let next_default_provider = providers::create_resilient_provider_with_options(
         &next_defaults.default_provider,
         next_defaults.api_key.as_deref(),
@@ -916,6 +924,44 @@ async fn get_or_create_provider(
     Ok(Arc::clone(cached))
 }
 
+/// Race the route's provider against the configured racers.
+///
+/// Racers that fail to initialize are skipped; with none left the route's
+/// provider is used alone.
+async fn build_racing_provider(
+    ctx: &ChannelRuntimeContext,
+    route: &ChannelRouteSelection,
+    lead: Arc<dyn Provider>,
+    racing: &crate::config::RacingConfig,
+) -> Arc<dyn Provider> {
+    let lead_racer = providers::Racer::new(route.provider.clone(), Arc::clone(&lead));
+    let mut racers = vec![lead_racer];
+    for racer in &racing.providers {
+        if racer.provider == route.provider && racer.model == route.model {
+            continue;
+        }
+        match get_or_create_provider(ctx, &racer.provider).await {
+            Ok(provider) => racers.push(
+                providers::Racer::new(racer.provider.clone(), provider)
+                    .with_model(racer.model.clone()),
+            ),
+            Err(err) => tracing::warn!(
+                provider = racer.provider.as_str(),
+                "Skipping racer that failed to initialize: {err}"
+            ),
+        }
+    }
+    if racers.len() == 1 {
+        return lead;
+    }
+
+    Arc::new(providers::RacingProvider::new(
+        racers,
+        racing.hedge_delay_ms,
+        Arc::clone(&ctx.race_context),
+    ))
+}
+
 async fn create_resilient_provider_nonblocking(
     provider_name: &str,
     api_key: Option<String>,
@@ -959,8 +1005,16 @@ fn build_models_help_response(current: &ChannelRouteSelection, workspace_dir: &P
             "\nCached model IDs (top {}):",
             cached_models.len()
         );
+        let catalog = providers::ModelCatalog::load(workspace_dir);
         for model in cached_models {
-            let _ = writeln!(response, "- `{model}`");
+            match catalog.find(&current.provider, &model) {
+                Some(info) => {
+                    let _ = writeln!(response, "- `{model}` ({})", info.summary());
+                }
+                None => {
+                    let _ = writeln!(response, "- `{model}`");
+                }
+            }
         }
     }
 
@@ -1387,12 +1441,14 @@ fn strip_isolated_tool_json_artifacts(message: &str, known_tool_names: &HashSet<
 fn spawn_supervised_listener(
     ch: Arc<dyn Channel>,
     tx: tokio::sync::mpsc::Sender<traits::ChannelMessage>,
+    observer: Arc<dyn Observer>,
     initial_backoff_secs: u64,
     max_backoff_secs: u64,
 ) -> tokio::task::JoinHandle<()> {
     spawn_supervised_listener_with_health_interval(
         ch,
         tx,
+        observer,
         initial_backoff_secs,
         max_backoff_secs,
         Duration::from_secs(CHANNEL_HEALTH_HEARTBEAT_SECS),
@@ -1402,6 +1458,7 @@ fn spawn_supervised_listener(
 fn spawn_supervised_listener_with_health_interval(
     ch: Arc<dyn Channel>,
     tx: tokio::sync::mpsc::Sender<traits::ChannelMessage>,
+    observer: Arc<dyn Observer>,
     initial_backoff_secs: u64,
     max_backoff_secs: u64,
     health_interval: Duration,
@@ -1453,6 +1510,9 @@ fn spawn_supervised_listener_with_health_interval(
             }
 
             crate::health::bump_component_restart(&component);
+            observer.record_event(&observability::ObserverEvent::ChannelRestart {
+                channel: ch.name().to_string(),
+            });
             tokio::time::sleep(Duration::from_secs(backoff)).await;
             // Double backoff AFTER sleeping so first error uses initial_backoff
             backoff = backoff.saturating_mul(2).min(max_backoff);
@@ -1558,7 +1618,34 @@ async fn process_channel_message(
     }
 
     let history_key = conversation_history_key(&msg);
-    let route = get_route_selection(ctx.as_ref(), &history_key);
+    let mut route = get_route_selection(ctx.as_ref(), &history_key);
+
+    // ── Hooks: before_model_resolve / before_prompt_build (modifying) ──
+    let hooks = ctx.hooks.as_deref();
+    match crate::agent::loop_::resolve_model_with_hooks(hooks, &route.provider, &route.model).await
+    {
+        Ok((provider, model)) => {
+            route.provider = provider;
+            route.model = model;
+        }
+        Err(err) => {
+            tracing::info!(%err, "incoming message dropped by hook");
+            return;
+        }
+    }
+    let system_prompt = match crate::agent::loop_::build_prompt_with_hooks(
+        hooks,
+        build_channel_system_prompt(ctx.system_prompt.as_str(), &msg.channel, &msg.reply_target),
+    )
+    .await
+    {
+        Ok(prompt) => prompt,
+        Err(err) => {
+            tracing::info!(%err, "incoming message dropped by hook");
+            return;
+        }
+    };
+
     let runtime_defaults = runtime_defaults_snapshot(ctx.as_ref());
     let active_provider = match get_or_create_provider(ctx.as_ref(), &route.provider).await {
         Ok(provider) => provider,
@@ -1579,6 +1666,23 @@ async fn process_channel_message(
             return;
         }
     };
+    let lead_provider = Arc::clone(&active_provider);
+    let active_provider = if runtime_defaults.reliability.racing.applies_to(&msg.channel) {
+        build_racing_provider(
+            ctx.as_ref(),
+            &route,
+            active_provider,
+            &runtime_defaults.reliability.racing,
+        )
+        .await
+    } else {
+        active_provider
+    };
+    // A race bills each of its attempts itself.
+    let turn_costs = ctx
+        .usage_recorder
+        .as_deref()
+        .filter(|_| Arc::ptr_eq(&lead_provider, &active_provider));
     if ctx.auto_save_memory && msg.content.chars().count() >= AUTOSAVE_MIN_MESSAGE_CHARS {
         let autosave_key = conversation_memory_key(&msg);
         let _ = ctx
@@ -1627,10 +1731,30 @@ async fn process_channel_message(
         }
     }
 
-    let system_prompt =
-        build_channel_system_prompt(ctx.system_prompt.as_str(), &msg.channel, &msg.reply_target);
     let mut history = vec![ChatMessage::system(system_prompt)];
     history.extend(prior_turns);
+
+    // Compact ahead of the call once the conversation fills most of the
+    // model's catalog context window, rather than waiting for an overflow.
+    let context_window = crate::agent::loop_::catalog_context_window(
+        ctx.workspace_dir.as_path(),
+        &route.provider,
+        &route.model,
+    );
+    if crate::agent::loop_::exceeds_context_window(&history, context_window)
+        && compact_sender_history(ctx.as_ref(), &history_key)
+    {
+        let compacted_turns = ctx
+            .conversation_histories
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .get(&history_key)
+            .cloned()
+            .unwrap_or_default();
+        history.truncate(1);
+        history.extend(normalize_cached_channel_turns(compacted_turns));
+    }
+
     let use_streaming = target_channel
         .as_ref()
         .is_some_and(|ch| ch.supports_draft_updates());
@@ -1754,6 +1878,9 @@ async fn process_channel_message(
                 } else {
                     ctx.non_cli_excluded_tools.as_ref()
                 },
+                runtime_defaults.generation.non_empty(),
+                Some(ctx.tool_modes.as_ref()),
+                turn_costs,
             ),
         ) => LlmExecutionResult::Completed(result),
     };
@@ -2188,7 +2315,11 @@ async fn run_message_dispatch_loop(
                 }
             }
 
-            process_channel_message(worker_ctx, msg, cancellation_token).await;
+            let span = TraceSpan::start(format!("process {}", msg.channel), SpanKind::Consumer)
+                .with_attr("messaging.system", msg.channel.as_str())
+                .with_attr("messaging.message.id", msg.id.as_str());
+            span.instrument(process_channel_message(worker_ctx, msg, cancellation_token))
+                .await;
 
             if interrupt_enabled {
                 let mut active = in_flight.lock().await;
@@ -2417,16 +2548,7 @@ pub fn build_system_prompt_with_mode(
         load_openclaw_bootstrap_files(&mut prompt, workspace_dir, max_chars);
     }
 
-    // ── 6. Date & Time ──────────────────────────────────────────
-    let now = chrono::Local::now();
-    let _ = writeln!(
-        prompt,
-        "## Current Date & Time\n\n{} ({})\n",
-        now.format("%Y-%m-%d %H:%M:%S"),
-        now.format("%Z")
-    );
-
-    // ── 7. Runtime ──────────────────────────────────────────────
+    // ── 6. Runtime ──────────────────────────────────────────────
     let host =
         hostname::get().map_or_else(|_| "unknown".into(), |h| h.to_string_lossy().to_string());
     let _ = writeln!(
@@ -2435,13 +2557,23 @@ pub fn build_system_prompt_with_mode(
         std::env::consts::OS,
     );
 
-    // ── 8. Channel Capabilities ─────────────────────────────────────
+    // ── 7. Channel Capabilities ─────────────────────────────────────
     prompt.push_str("## Channel Capabilities\n\n");
     prompt.push_str("- You are running as a messaging bot. Your response is automatically sent back to the user's channel.\n");
     prompt.push_str("- You do NOT need to ask permission to respond — just respond directly.\n");
     prompt.push_str("- NEVER repeat, describe, or echo credentials, tokens, API keys, or secrets in your responses.\n");
     prompt.push_str("- If a tool output contains credentials, they have already been redacted — do not mention them.\n\n");
 
+    // ── 8. Date & Time (last: changes every turn, keeps the prefix cacheable)
+    let now = chrono::Local::now();
+    let _ = writeln!(
+        prompt,
+        "{}\n\n{} ({})\n",
+        crate::providers::VOLATILE_PROMPT_HEADING,
+        now.format("%Y-%m-%d %H:%M:%S"),
+        now.format("%Z")
+    );
+
     if prompt.is_empty() {
         "You are ZeroClaw, a fast and efficient AI assistant built in Rust. Be helpful, concise, and direct."
             .to_string()
@@ -3134,11 +3266,12 @@ pub async fn start_channels(config: Config) -> Result<()> {
     };
     // Build system prompt from workspace identity files + skills
     let workspace = config.workspace_dir.clone();
-    let tools_registry = Arc::new(tools::all_tools_with_runtime(
+    let mut tools_registry = tools::all_tools_with_runtime(
         Arc::new(config.clone()),
         &security,
-        runtime,
+        runtime.clone(),
         Arc::clone(&mem),
+        Arc::clone(&observer),
         composio_key,
         composio_entity_id,
         &config.browser,
@@ -3148,9 +3281,13 @@ pub async fn start_channels(config: Config) -> Result<()> {
         &config.agents,
         config.api_key.as_deref(),
         &config,
-    ));
+    );
 
     let skills = crate::skills::load_skills_with_config(&workspace, &config);
+    let skill_tools =
+        crate::skills::create_skill_tools(&skills, &security, runtime, &config, &tools_registry);
+    tools_registry.extend(skill_tools);
+    let tools_registry = Arc::new(tools_registry);
 
     // Collect tool descriptions for the prompt
     let mut tool_descs: Vec<(&str, &str)> = vec![
@@ -3219,7 +3356,9 @@ pub async fn start_channels(config: Config) -> Result<()> {
     } else {
         None
     };
-    let native_tools = provider.supports_native_tools();
+    let tool_modes =
+        providers::ToolModes::load(&config.workspace_dir, config.agent.tool_modes.clone());
+    let native_tools = tool_modes.native_tools(provider.as_ref(), &provider_name, &model);
     let mut system_prompt = build_system_prompt_with_mode(
         &workspace,
         &model,
@@ -3300,11 +3439,12 @@ pub async fn start_channels(config: Config) -> Result<()> {
     let (tx, rx) = tokio::sync::mpsc::channel::<traits::ChannelMessage>(100);
 
     // Spawn a listener for each channel
-    let mut handles = Vec::new();
+    let mut handles = ListenerHandles::default();
     for ch in &channels {
-        handles.push(spawn_supervised_listener(
+        handles.0.push(spawn_supervised_listener(
             ch.clone(),
             tx.clone(),
+            Arc::clone(&observer),
             initial_backoff_secs,
             max_backoff_secs,
         ));
@@ -3331,6 +3471,13 @@ pub async fn start_channels(config: Config) -> Result<()> {
         .as_ref()
         .is_some_and(|tg| tg.interrupt_on_new_message);
 
+    // Raced replies bill every attempt, including the cancelled ones.
+    let usage_recorder = crate::cost::UsageRecorder::from_config(&config).map(Arc::new);
+    let mut race_context = providers::RaceContext::new();
+    if let Some(recorder) = &usage_recorder {
+        race_context = race_context.with_usage_recorder(Arc::clone(recorder));
+    }
+
     let runtime_ctx = Arc::new(ChannelRuntimeContext {
         channels_by_name,
         provider: Arc::clone(&provider),
@@ -3356,27 +3503,42 @@ pub async fn start_channels(config: Config) -> Result<()> {
         interrupt_on_new_message,
         multimodal: config.multimodal.clone(),
         hooks: if config.hooks.enabled {
-            let mut runner = crate::hooks::HookRunner::new();
-            if config.hooks.builtin.command_logger {
-                runner.register(Box::new(crate::hooks::builtin::CommandLoggerHook::new()));
-            }
-            Some(Arc::new(runner))
+            Some(Arc::new(crate::hooks::HookRunner::from_config(&config)))
         } else {
             None
         },
         non_cli_excluded_tools: Arc::new(config.autonomy.non_cli_excluded_tools.clone()),
+        race_context: Arc::new(race_context),
+        tool_modes: Arc::new(tool_modes),
+        usage_recorder,
     });
 
     run_message_dispatch_loop(rx, runtime_ctx, max_in_flight_messages).await;
 
     // Wait for all channel tasks
-    for h in handles {
+    for h in std::mem::take(&mut handles.0) {
         let _ = h.await;
     }
 
     Ok(())
 }
 
+/// Listener tasks owned by one `start_channels` run.
+///
+/// Aborted on drop so that cancelling the channels component (daemon
+/// config reload, shutdown) does not leave orphaned listeners polling
+/// the old channel set.
+#[derive(Default)]
+struct ListenerHandles(Vec<tokio::task::JoinHandle<()>>);
+
+impl Drop for ListenerHandles {
+    fn drop(&mut self) {
+        for handle in &self.0 {
+            handle.abort();
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -3453,6 +3615,25 @@ mod tests {
         assert!(!is_context_window_overflow_error(&other_err));
     }
 
+    #[test]
+    fn models_help_annotates_cached_models_from_catalog() {
+        let tmp = TempDir::new().unwrap();
+        std::fs::create_dir_all(tmp.path().join("state")).unwrap();
+        std::fs::write(
+            tmp.path().join("state").join(MODEL_CACHE_FILE),
+            r#"{"entries": [{"provider": "openai", "models": ["gpt-4o-mini", "ft:custom"]}]}"#,
+        )
+        .unwrap();
+        let current = ChannelRouteSelection {
+            provider: "openai".into(),
+            model: "gpt-4o-mini".into(),
+        };
+
+        let response = build_models_help_response(&current, tmp.path());
+        assert!(response.contains("- `gpt-4o-mini` (128k ctx, 16k out, $0.15/$0.60 per 1M"));
+        assert!(response.contains("- `ft:custom`\n"));
+    }
+
     #[test]
     fn memory_context_skip_rules_exclude_history_blobs() {
         assert!(should_skip_memory_context_entry(
@@ -3578,6 +3759,9 @@ mod tests {
             workspace_dir: Arc::new(std::env::temp_dir()),
             message_timeout_secs: CHANNEL_MESSAGE_TIMEOUT_SECS,
             non_cli_excluded_tools: Arc::new(Vec::new()),
+            race_context: Arc::new(providers::RaceContext::new()),
+            tool_modes: Arc::new(providers::ToolModes::default()),
+            usage_recorder: None,
         };
 
         assert!(compact_sender_history(&ctx, &sender));
@@ -3627,6 +3811,9 @@ mod tests {
             workspace_dir: Arc::new(std::env::temp_dir()),
             message_timeout_secs: CHANNEL_MESSAGE_TIMEOUT_SECS,
             non_cli_excluded_tools: Arc::new(Vec::new()),
+            race_context: Arc::new(providers::RaceContext::new()),
+            tool_modes: Arc::new(providers::ToolModes::default()),
+            usage_recorder: None,
         };
 
         append_sender_turn(&ctx, &sender, ChatMessage::user("hello"));
@@ -3679,6 +3866,9 @@ mod tests {
             workspace_dir: Arc::new(std::env::temp_dir()),
             message_timeout_secs: CHANNEL_MESSAGE_TIMEOUT_SECS,
             non_cli_excluded_tools: Arc::new(Vec::new()),
+            race_context: Arc::new(providers::RaceContext::new()),
+            tool_modes: Arc::new(providers::ToolModes::default()),
+            usage_recorder: None,
         };
 
         assert!(rollback_orphan_user_turn(&ctx, &sender, "pending"));
@@ -4152,6 +4342,9 @@ BTC is currently around $65,000 based on latest tool output."#
             message_timeout_secs: CHANNEL_MESSAGE_TIMEOUT_SECS,
             interrupt_on_new_message: false,
             non_cli_excluded_tools: Arc::new(Vec::new()),
+            race_context: Arc::new(providers::RaceContext::new()),
+            tool_modes: Arc::new(providers::ToolModes::default()),
+            usage_recorder: None,
             multimodal: crate::config::MultimodalConfig::default(),
             hooks: None,
         });
@@ -4211,6 +4404,9 @@ BTC is currently around $65,000 based on latest tool output."#
             message_timeout_secs: CHANNEL_MESSAGE_TIMEOUT_SECS,
             interrupt_on_new_message: false,
             non_cli_excluded_tools: Arc::new(Vec::new()),
+            race_context: Arc::new(providers::RaceContext::new()),
+            tool_modes: Arc::new(providers::ToolModes::default()),
+            usage_recorder: None,
             multimodal: crate::config::MultimodalConfig::default(),
             hooks: None,
         });
@@ -4286,6 +4482,9 @@ BTC is currently around $65,000 based on latest tool output."#
             multimodal: crate::config::MultimodalConfig::default(),
             hooks: None,
             non_cli_excluded_tools: Arc::new(Vec::new()),
+            race_context: Arc::new(providers::RaceContext::new()),
+            tool_modes: Arc::new(providers::ToolModes::default()),
+            usage_recorder: None,
         });
 
         process_channel_message(
@@ -4345,6 +4544,9 @@ BTC is currently around $65,000 based on latest tool output."#
             multimodal: crate::config::MultimodalConfig::default(),
             hooks: None,
             non_cli_excluded_tools: Arc::new(Vec::new()),
+            race_context: Arc::new(providers::RaceContext::new()),
+            tool_modes: Arc::new(providers::ToolModes::default()),
+            usage_recorder: None,
         });
 
         process_channel_message(
@@ -4413,6 +4615,9 @@ BTC is currently around $65,000 based on latest tool output."#
             multimodal: crate::config::MultimodalConfig::default(),
             hooks: None,
             non_cli_excluded_tools: Arc::new(Vec::new()),
+            race_context: Arc::new(providers::RaceContext::new()),
+            tool_modes: Arc::new(providers::ToolModes::default()),
+            usage_recorder: None,
         });
 
         process_channel_message(
@@ -4502,6 +4707,9 @@ BTC is currently around $65,000 based on latest tool output."#
             multimodal: crate::config::MultimodalConfig::default(),
             hooks: None,
             non_cli_excluded_tools: Arc::new(Vec::new()),
+            race_context: Arc::new(providers::RaceContext::new()),
+            tool_modes: Arc::new(providers::ToolModes::default()),
+            usage_recorder: None,
         });
 
         process_channel_message(
@@ -4531,6 +4739,278 @@ BTC is currently around $65,000 based on latest tool output."#
         );
     }
 
+    struct RerouteHook;
+
+    #[async_trait::async_trait]
+    impl crate::hooks::HookHandler for RerouteHook {
+        fn name(&self) -> &str {
+            "reroute"
+        }
+
+        async fn before_model_resolve(
+            &self,
+            _provider: String,
+            _model: String,
+        ) -> crate::hooks::HookResult<(String, String)> {
+            crate::hooks::HookResult::Continue(("openrouter".into(), "hooked-model".into()))
+        }
+    }
+
+    #[tokio::test]
+    async fn process_channel_message_applies_model_resolve_hook() {
+        let channel: Arc<dyn Channel> = Arc::new(TelegramRecordingChannel::default());
+
+        let mut channels_by_name = HashMap::new();
+        channels_by_name.insert(channel.name().to_string(), channel);
+
+        let default_provider_impl = Arc::new(ModelCaptureProvider::default());
+        let default_provider: Arc<dyn Provider> = default_provider_impl.clone();
+        let hooked_provider_impl = Arc::new(ModelCaptureProvider::default());
+        let hooked_provider: Arc<dyn Provider> = hooked_provider_impl.clone();
+
+        let mut provider_cache_seed: HashMap<String, Arc<dyn Provider>> = HashMap::new();
+        provider_cache_seed.insert("test-provider".to_string(), Arc::clone(&default_provider));
+        provider_cache_seed.insert("openrouter".to_string(), hooked_provider);
+
+        let mut hooks = crate::hooks::HookRunner::new();
+        hooks.register(Box::new(RerouteHook));
+
+        let runtime_ctx = Arc::new(ChannelRuntimeContext {
+            channels_by_name: Arc::new(channels_by_name),
+            provider: Arc::clone(&default_provider),
+            default_provider: Arc::new("test-provider".to_string()),
+            memory: Arc::new(NoopMemory),
+            tools_registry: Arc::new(vec![]),
+            observer: Arc::new(NoopObserver),
+            system_prompt: Arc::new("test-system-prompt".to_string()),
+            model: Arc::new("default-model".to_string()),
+            temperature: 0.0,
+            auto_save_memory: false,
+            max_tool_iterations: 5,
+            min_relevance_score: 0.0,
+            conversation_histories: Arc::new(Mutex::new(HashMap::new())),
+            provider_cache: Arc::new(Mutex::new(provider_cache_seed)),
+            route_overrides: Arc::new(Mutex::new(HashMap::new())),
+            api_key: None,
+            api_url: None,
+            reliability: Arc::new(crate::config::ReliabilityConfig::default()),
+            provider_runtime_options: providers::ProviderRuntimeOptions::default(),
+            workspace_dir: Arc::new(std::env::temp_dir()),
+            message_timeout_secs: CHANNEL_MESSAGE_TIMEOUT_SECS,
+            interrupt_on_new_message: false,
+            multimodal: crate::config::MultimodalConfig::default(),
+            hooks: Some(Arc::new(hooks)),
+            non_cli_excluded_tools: Arc::new(Vec::new()),
+            race_context: Arc::new(providers::RaceContext::new()),
+            tool_modes: Arc::new(providers::ToolModes::default()),
+            usage_recorder: None,
+        });
+
+        process_channel_message(
+            runtime_ctx,
+            traits::ChannelMessage {
+                id: "msg-hooked-1".to_string(),
+                sender: "alice".to_string(),
+                reply_target: "chat-1".to_string(),
+                content: "hello hooked provider".to_string(),
+                channel: "telegram".to_string(),
+                timestamp: 2,
+                thread_ts: None,
+            },
+            CancellationToken::new(),
+        )
+        .await;
+
+        assert_eq!(default_provider_impl.call_count.load(Ordering::SeqCst), 0);
+        assert_eq!(
+            hooked_provider_impl
+                .models
+                .lock()
+                .unwrap_or_else(|e| e.into_inner())
+                .as_slice(),
+            &["hooked-model".to_string()]
+        );
+    }
+
+    #[tokio::test]
+    async fn process_channel_message_compacts_history_near_catalog_context_window() {
+        let workspace = TempDir::new().unwrap();
+        std::fs::write(
+            workspace.path().join(providers::catalog::CATALOG_OVERRIDE_FILE),
+            r#"{"models": [{"provider": "test-provider", "id": "small-model", "context_window": 4000}]}"#,
+        )
+        .unwrap();
+
+        let channel: Arc<dyn Channel> = Arc::new(TelegramRecordingChannel::default());
+        let mut channels_by_name = HashMap::new();
+        channels_by_name.insert(channel.name().to_string(), channel);
+
+        let provider_impl = Arc::new(ModelCaptureProvider::default());
+        let provider: Arc<dyn Provider> = provider_impl.clone();
+        let mut provider_cache_seed: HashMap<String, Arc<dyn Provider>> = HashMap::new();
+        provider_cache_seed.insert("test-provider".to_string(), Arc::clone(&provider));
+
+        // ~20 x 700 chars is well past 75% of a 4000-token window.
+        let sender_key = "telegram_alice".to_string();
+        let mut histories = HashMap::new();
+        histories.insert(
+            sender_key.clone(),
+            (0..20)
+                .map(|idx| {
+                    let content = format!("msg-{idx}-{}", "x".repeat(700));
+                    if idx % 2 == 0 {
+                        ChatMessage::user(content)
+                    } else {
+                        ChatMessage::assistant(content)
+                    }
+                })
+                .collect::<Vec<_>>(),
+        );
+
+        let runtime_ctx = Arc::new(ChannelRuntimeContext {
+            channels_by_name: Arc::new(channels_by_name),
+            provider,
+            default_provider: Arc::new("test-provider".to_string()),
+            memory: Arc::new(NoopMemory),
+            tools_registry: Arc::new(vec![]),
+            observer: Arc::new(NoopObserver),
+            system_prompt: Arc::new("test-system-prompt".to_string()),
+            model: Arc::new("small-model".to_string()),
+            temperature: 0.0,
+            auto_save_memory: false,
+            max_tool_iterations: 5,
+            min_relevance_score: 0.0,
+            conversation_histories: Arc::new(Mutex::new(histories)),
+            provider_cache: Arc::new(Mutex::new(provider_cache_seed)),
+            route_overrides: Arc::new(Mutex::new(HashMap::new())),
+            api_key: None,
+            api_url: None,
+            reliability: Arc::new(crate::config::ReliabilityConfig::default()),
+            provider_runtime_options: providers::ProviderRuntimeOptions::default(),
+            workspace_dir: Arc::new(workspace.path().to_path_buf()),
+            message_timeout_secs: CHANNEL_MESSAGE_TIMEOUT_SECS,
+            interrupt_on_new_message: false,
+            multimodal: crate::config::MultimodalConfig::default(),
+            hooks: None,
+            non_cli_excluded_tools: Arc::new(Vec::new()),
+            race_context: Arc::new(providers::RaceContext::new()),
+            tool_modes: Arc::new(providers::ToolModes::default()),
+            usage_recorder: None,
+        });
+
+        process_channel_message(
+            Arc::clone(&runtime_ctx),
+            traits::ChannelMessage {
+                id: "msg-window-1".to_string(),
+                sender: "alice".to_string(),
+                reply_target: "chat-1".to_string(),
+                content: "next question".to_string(),
+                channel: "telegram".to_string(),
+                timestamp: 3,
+                thread_ts: None,
+            },
+            CancellationToken::new(),
+        )
+        .await;
+
+        assert_eq!(provider_impl.call_count.load(Ordering::SeqCst), 1);
+        let histories = runtime_ctx
+            .conversation_histories
+            .lock()
+            .unwrap_or_else(|e| e.into_inner());
+        let turns = &histories[&sender_key];
+        assert!(turns.len() <= CHANNEL_HISTORY_COMPACT_KEEP_MESSAGES + 1);
+        assert!(turns
+            .iter()
+            .all(|turn| turn.content.chars().count() <= CHANNEL_HISTORY_COMPACT_CONTENT_CHARS + 3));
+        assert_eq!(turns.last().unwrap().content, "ok");
+    }
+
+    #[tokio::test]
+    async fn process_channel_message_races_providers_on_configured_channels() {
+        let channel: Arc<dyn Channel> = Arc::new(TelegramRecordingChannel::default());
+
+        let mut channels_by_name = HashMap::new();
+        channels_by_name.insert(channel.name().to_string(), channel);
+
+        let default_provider: Arc<dyn Provider> = Arc::new(SlowProvider {
+            delay: Duration::from_secs(5),
+        });
+        let racer_impl = Arc::new(ModelCaptureProvider::default());
+        let racer: Arc<dyn Provider> = racer_impl.clone();
+
+        let mut provider_cache_seed: HashMap<String, Arc<dyn Provider>> = HashMap::new();
+        provider_cache_seed.insert("test-provider".to_string(), Arc::clone(&default_provider));
+        provider_cache_seed.insert("groq".to_string(), racer);
+
+        let mut reliability = crate::config::ReliabilityConfig::default();
+        reliability.racing = crate::config::RacingConfig {
+            channels: vec!["telegram".to_string()],
+            providers: vec![crate::config::RacerConfig {
+                provider: "groq".to_string(),
+                model: "racer-model".to_string(),
+            }],
+            hedge_delay_ms: 0,
+        };
+
+        let runtime_ctx = Arc::new(ChannelRuntimeContext {
+            channels_by_name: Arc::new(channels_by_name),
+            provider: Arc::clone(&default_provider),
+            default_provider: Arc::new("test-provider".to_string()),
+            memory: Arc::new(NoopMemory),
+            tools_registry: Arc::new(vec![]),
+            observer: Arc::new(NoopObserver),
+            system_prompt: Arc::new("test-system-prompt".to_string()),
+            model: Arc::new("default-model".to_string()),
+            temperature: 0.0,
+            auto_save_memory: false,
+            max_tool_iterations: 5,
+            min_relevance_score: 0.0,
+            conversation_histories: Arc::new(Mutex::new(HashMap::new())),
+            provider_cache: Arc::new(Mutex::new(provider_cache_seed)),
+            route_overrides: Arc::new(Mutex::new(HashMap::new())),
+            api_key: None,
+            api_url: None,
+            reliability: Arc::new(reliability),
+            provider_runtime_options: providers::ProviderRuntimeOptions::default(),
+            workspace_dir: Arc::new(std::env::temp_dir()),
+            message_timeout_secs: CHANNEL_MESSAGE_TIMEOUT_SECS,
+            interrupt_on_new_message: false,
+            multimodal: crate::config::MultimodalConfig::default(),
+            hooks: None,
+            non_cli_excluded_tools: Arc::new(Vec::new()),
+            race_context: Arc::new(providers::RaceContext::new()),
+            tool_modes: Arc::new(providers::ToolModes::default()),
+            usage_recorder: None,
+        });
+
+        let started = Instant::now();
+        process_channel_message(
+            runtime_ctx,
+            traits::ChannelMessage {
+                id: "msg-race-1".to_string(),
+                sender: "alice".to_string(),
+                reply_target: "chat-1".to_string(),
+                content: "hello racers".to_string(),
+                channel: "telegram".to_string(),
+                timestamp: 1,
+                thread_ts: None,
+            },
+            CancellationToken::new(),
+        )
+        .await;
+
+        assert!(started.elapsed() < Duration::from_secs(5));
+        assert_eq!(
+            racer_impl
+                .models
+                .lock()
+                .unwrap_or_else(|e| e.into_inner())
+                .as_slice(),
+            &["racer-model".to_string()]
+        );
+    }
+
     #[tokio::test]
     async fn process_channel_message_prefers_cached_default_provider_instance() {
         let channel_impl = Arc::new(TelegramRecordingChannel::default());
@@ -4573,6 +5053,9 @@ BTC is currently around $65,000 based on latest tool output."#
             multimodal: crate::config::MultimodalConfig::default(),
             hooks: None,
             non_cli_excluded_tools: Arc::new(Vec::new()),
+            race_context: Arc::new(providers::RaceContext::new()),
+            tool_modes: Arc::new(providers::ToolModes::default()),
+            usage_recorder: None,
         });
 
         process_channel_message(
@@ -4624,6 +5107,7 @@ BTC is currently around $65,000 based on latest tool output."#
                         api_key: None,
                         api_url: None,
                         reliability: crate::config::ReliabilityConfig::default(),
+                        generation: crate::providers::GenerationParams::default(),
                     },
                     last_applied_stamp: None,
                 },
@@ -4659,6 +5143,9 @@ BTC is currently around $65,000 based on latest tool output."#
             multimodal: crate::config::MultimodalConfig::default(),
             hooks: None,
             non_cli_excluded_tools: Arc::new(Vec::new()),
+            race_context: Arc::new(providers::RaceContext::new()),
+            tool_modes: Arc::new(providers::ToolModes::default()),
+            usage_recorder: None,
         });
 
         process_channel_message(
@@ -4730,6 +5217,9 @@ BTC is currently around $65,000 based on latest tool output."#
             multimodal: crate::config::MultimodalConfig::default(),
             hooks: None,
             non_cli_excluded_tools: Arc::new(Vec::new()),
+            race_context: Arc::new(providers::RaceContext::new()),
+            tool_modes: Arc::new(providers::ToolModes::default()),
+            usage_recorder: None,
         });
 
         process_channel_message(
@@ -4790,6 +5280,9 @@ BTC is currently around $65,000 based on latest tool output."#
             multimodal: crate::config::MultimodalConfig::default(),
             hooks: None,
             non_cli_excluded_tools: Arc::new(Vec::new()),
+            race_context: Arc::new(providers::RaceContext::new()),
+            tool_modes: Arc::new(providers::ToolModes::default()),
+            usage_recorder: None,
         });
 
         process_channel_message(
@@ -4961,6 +5454,9 @@ BTC is currently around $65,000 based on latest tool output."#
             multimodal: crate::config::MultimodalConfig::default(),
             hooks: None,
             non_cli_excluded_tools: Arc::new(Vec::new()),
+            race_context: Arc::new(providers::RaceContext::new()),
+            tool_modes: Arc::new(providers::ToolModes::default()),
+            usage_recorder: None,
         });
 
         let (tx, rx) = tokio::sync::mpsc::channel::<traits::ChannelMessage>(4);
@@ -5041,6 +5537,9 @@ BTC is currently around $65,000 based on latest tool output."#
             multimodal: crate::config::MultimodalConfig::default(),
             hooks: None,
             non_cli_excluded_tools: Arc::new(Vec::new()),
+            race_context: Arc::new(providers::RaceContext::new()),
+            tool_modes: Arc::new(providers::ToolModes::default()),
+            usage_recorder: None,
         });
 
         let (tx, rx) = tokio::sync::mpsc::channel::<traits::ChannelMessage>(8);
@@ -5133,6 +5632,9 @@ BTC is currently around $65,000 based on latest tool output."#
             multimodal: crate::config::MultimodalConfig::default(),
             hooks: None,
             non_cli_excluded_tools: Arc::new(Vec::new()),
+            race_context: Arc::new(providers::RaceContext::new()),
+            tool_modes: Arc::new(providers::ToolModes::default()),
+            usage_recorder: None,
         });
 
         let (tx, rx) = tokio::sync::mpsc::channel::<traits::ChannelMessage>(8);
@@ -5207,6 +5709,9 @@ BTC is currently around $65,000 based on latest tool output."#
             multimodal: crate::config::MultimodalConfig::default(),
             hooks: None,
             non_cli_excluded_tools: Arc::new(Vec::new()),
+            race_context: Arc::new(providers::RaceContext::new()),
+            tool_modes: Arc::new(providers::ToolModes::default()),
+            usage_recorder: None,
         });
 
         process_channel_message(
@@ -5266,6 +5771,9 @@ BTC is currently around $65,000 based on latest tool output."#
             multimodal: crate::config::MultimodalConfig::default(),
             hooks: None,
             non_cli_excluded_tools: Arc::new(Vec::new()),
+            race_context: Arc::new(providers::RaceContext::new()),
+            tool_modes: Arc::new(providers::ToolModes::default()),
+            usage_recorder: None,
         });
 
         process_channel_message(
@@ -5322,6 +5830,18 @@ BTC is currently around $65,000 based on latest tool output."#
         assert!(prompt.contains("## Runtime"), "missing Runtime section");
     }
 
+    #[test]
+    fn prompt_puts_date_time_section_last() {
+        let ws = make_workspace();
+        let prompt = build_system_prompt(ws.path(), "test-model", &[], &[], None, None);
+
+        let volatile_at = prompt
+            .find(crate::providers::VOLATILE_PROMPT_HEADING)
+            .expect("missing Date/Time");
+        assert!(prompt.find("## Runtime").unwrap() < volatile_at);
+        assert!(prompt.find("## Channel Capabilities").unwrap() < volatile_at);
+    }
+
     #[test]
     fn prompt_injects_tools() {
         let ws = make_workspace();
@@ -5473,6 +5993,9 @@ BTC is currently around $65,000 based on latest tool output."#
                 kind: "shell".into(),
                 command: "cargo clippy".into(),
                 args: HashMap::new(),
+                parameters: None,
+                input: crate::skills::SkillToolInput::Env,
+                timeout_secs: None,
             }],
             prompts: vec!["Always run cargo test before final response.".into()],
             location: None,
@@ -5508,6 +6031,9 @@ BTC is currently around $65,000 based on latest tool output."#
                 kind: "shell".into(),
                 command: "cargo clippy".into(),
                 args: HashMap::new(),
+                parameters: None,
+                input: crate::skills::SkillToolInput::Env,
+                timeout_secs: None,
             }],
             prompts: vec!["Always run cargo test before final response.".into()],
             location: None,
@@ -5549,6 +6075,9 @@ BTC is currently around $65,000 based on latest tool output."#
                 kind: "shell&exec".into(),
                 command: "cargo clippy".into(),
                 args: HashMap::new(),
+                parameters: None,
+                input: crate::skills::SkillToolInput::Env,
+                timeout_secs: None,
             }],
             prompts: vec!["Use <tool_call> and & keep output \"safe\"".into()],
             location: None,
@@ -5782,6 +6311,9 @@ BTC is currently around $65,000 based on latest tool output."#
             multimodal: crate::config::MultimodalConfig::default(),
             hooks: None,
             non_cli_excluded_tools: Arc::new(Vec::new()),
+            race_context: Arc::new(providers::RaceContext::new()),
+            tool_modes: Arc::new(providers::ToolModes::default()),
+            usage_recorder: None,
         });
 
         process_channel_message(
@@ -5867,6 +6399,9 @@ BTC is currently around $65,000 based on latest tool output."#
             multimodal: crate::config::MultimodalConfig::default(),
             hooks: None,
             non_cli_excluded_tools: Arc::new(Vec::new()),
+            race_context: Arc::new(providers::RaceContext::new()),
+            tool_modes: Arc::new(providers::ToolModes::default()),
+            usage_recorder: None,
         });
 
         process_channel_message(
@@ -5952,6 +6487,9 @@ BTC is currently around $65,000 based on latest tool output."#
             multimodal: crate::config::MultimodalConfig::default(),
             hooks: None,
             non_cli_excluded_tools: Arc::new(Vec::new()),
+            race_context: Arc::new(providers::RaceContext::new()),
+            tool_modes: Arc::new(providers::ToolModes::default()),
+            usage_recorder: None,
         });
 
         process_channel_message(
@@ -6336,8 +6874,9 @@ This is an example JSON object for profile settings."#;
             calls: Arc::clone(&calls),
         });
 
+        let prom = crate::observability::PrometheusObserver::new();
         let (tx, rx) = tokio::sync::mpsc::channel::<traits::ChannelMessage>(1);
-        let handle = spawn_supervised_listener(channel, tx, 1, 1);
+        let handle = spawn_supervised_listener(channel, tx, Arc::new(prom.clone()), 1, 1);
 
         tokio::time::sleep(Duration::from_millis(80)).await;
         drop(rx);
@@ -6353,6 +6892,9 @@ This is an example JSON object for profile settings."#;
             .unwrap_or("")
             .contains("listen boom"));
         assert!(calls.load(Ordering::SeqCst) >= 1);
+        assert!(prom
+            .encode()
+            .contains(r#"zeroclaw_channel_restarts_total{channel="test-supervised-fail"} 1"#));
     }
 
     #[tokio::test]
@@ -6369,6 +6911,7 @@ This is an example JSON object for profile settings."#;
         let handle = spawn_supervised_listener_with_health_interval(
             channel,
             tx,
+            Arc::new(crate::observability::NoopObserver),
             1,
             1,
             Duration::from_millis(20),
@@ -6501,6 +7044,9 @@ This is an example JSON object for profile settings."#;
             multimodal: crate::config::MultimodalConfig::default(),
             hooks: None,
             non_cli_excluded_tools: Arc::new(Vec::new()),
+            race_context: Arc::new(providers::RaceContext::new()),
+            tool_modes: Arc::new(providers::ToolModes::default()),
+            usage_recorder: None,
         });
 
         // Simulate a photo attachment message with [IMAGE:] marker.
@@ -6567,6 +7113,9 @@ This is an example JSON object for profile settings."#;
             multimodal: crate::config::MultimodalConfig::default(),
             hooks: None,
             non_cli_excluded_tools: Arc::new(Vec::new()),
+            race_context: Arc::new(providers::RaceContext::new()),
+            tool_modes: Arc::new(providers::ToolModes::default()),
+            usage_recorder: None,
         });
 
         process_channel_message(
@@ -0,0 +1,629 @@
+//! Layered `config.toml` loading.
+//!
+//! The effective config is built from, lowest precedence first:
+//!
+//! 1. `config.toml` (and any files it `extends`)
+//! 2. `config.<profile>.toml` when a profile is selected (`--profile` / `ZEROCLAW_PROFILE`)
+//! 3. `config.local.toml` when present
+//!
+//! Layers are deep-merged: tables merge key by key, arrays append the
+//! entries they do not already contain, and scalars are replaced. A file can
+//! list dotted key paths under a top-level `replace = [...]` to replace those
+//! tables/arrays instead of merging into them. Every leaf remembers the files
+//! it came from so `zeroclaw config show --resolved --origin` can report it.
+
+use super::Config;
+use anyhow::{bail, Context, Result};
+use serde_json::Value as JsonValue;
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt::Write as _;
+use std::path::{Path, PathBuf};
+use toml::{Table, Value};
+
+/// Environment variable selecting the config profile (set by `--profile`).
+pub const PROFILE_ENV: &str = "ZEROCLAW_PROFILE";
+
+const EXTENDS_KEY: &str = "extends";
+const REPLACE_KEY: &str = "replace";
+const LOCAL_OVERLAY: &str = "local";
+const MAX_EXTENDS_DEPTH: usize = 8;
+
+/// The profile selected for this process, if any.
+pub fn active_profile() -> Option<String> {
+    std::env::var(PROFILE_ENV)
+        .ok()
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty())
+}
+
+/// Provenance of a loaded config.
+#[derive(Debug, Clone, Default)]
+pub struct ConfigLayers {
+    pub profile: Option<String>,
+    /// Every file that contributed, lowest precedence first.
+    pub files: Vec<PathBuf>,
+    /// Dotted key path → files that set it (the last one wins for scalars).
+    pub origins: BTreeMap<String, Vec<PathBuf>>,
+    /// Key paths whose value came from environment overrides.
+    pub env_overrides: Vec<String>,
+    /// The config as loaded (decrypted), used by `Config::save` to write back
+    /// only the keys that changed since.
+    pub(crate) loaded: JsonValue,
+}
+
+impl ConfigLayers {
+    /// More than one file contributed, so saving must not flatten the layers.
+    pub fn is_layered(&self) -> bool {
+        self.files.len() > 1
+    }
+
+    /// Files that set `path`, falling back to the nearest ancestor key.
+    pub fn origin(&self, path: &str) -> Option<&[PathBuf]> {
+        let mut key = path;
+        loop {
+            if let Some(files) = self.origins.get(key) {
+                return Some(files);
+            }
+            key = &key[..key.rfind('.')?];
+        }
+    }
+
+    /// A file with higher precedence than `base` that sets `path`, a key
+    /// below it or a key above it.
+    fn overriding_file(&self, path: &str, base: &Path) -> Option<&Path> {
+        let base_rank = self.files.iter().position(|file| file == base)?;
+        self.origins
+            .iter()
+            .filter(|(key, _)| {
+                *key == path
+                    || key.starts_with(&format!("{path}."))
+                    || path.starts_with(&format!("{key}."))
+            })
+            .flat_map(|(_, files)| files)
+            .find(|file| {
+                self.files
+                    .iter()
+                    .position(|candidate| candidate == *file)
+                    .is_some_and(|rank| rank > base_rank)
+            })
+            .map(PathBuf::as_path)
+    }
+}
+
+fn overlay_path(config_path: &Path, suffix: &str) -> PathBuf {
+    let stem = config_path
+        .file_stem()
+        .and_then(|stem| stem.to_str())
+        .unwrap_or("config");
+    config_path.with_file_name(format!("{stem}.{suffix}.toml"))
+}
+
+/// Top-level layer files for `config_path`, lowest precedence first, paired
+/// with whether the file must exist.
+fn layer_candidates(config_path: &Path, profile: Option<&str>) -> Vec<(PathBuf, bool)> {
+    let mut paths = vec![(config_path.to_path_buf(), true)];
+    if let Some(profile) = profile {
+        paths.push((overlay_path(config_path, profile), true));
+    }
+    paths.push((overlay_path(config_path, LOCAL_OVERLAY), false));
+    paths
+}
+
+/// Files whose changes should trigger a reload of `config`, including
+/// optional overlays that do not exist yet.
+pub fn watch_paths(config: &Config) -> Vec<PathBuf> {
+    let profile = config
+        .layers
+        .as_ref()
+        .and_then(|layers| layers.profile.clone());
+    let mut paths: Vec<PathBuf> = layer_candidates(&config.config_path, profile.as_deref())
+        .into_iter()
+        .map(|(path, _)| path)
+        .collect();
+    if let Some(layers) = &config.layers {
+        for file in &layers.files {
+            if !paths.contains(file) {
+                paths.push(file.clone());
+            }
+        }
+    }
+    paths
+}
+
+/// Read and deep-merge every layer of `config_path`.
+pub fn load_merged(config_path: &Path, profile: Option<&str>) -> Result<(Table, ConfigLayers)> {
+    let mut merged = Table::new();
+    let mut layers = ConfigLayers {
+        profile: profile.map(str::to_string),
+        ..ConfigLayers::default()
+    };
+
+    for (path, required) in layer_candidates(config_path, profile) {
+        if !path.exists() {
+            if required {
+                bail!("Config file not found: {}", path.display());
+            }
+            continue;
+        }
+        merge_file(&path, &mut merged, &mut layers, &mut Vec::new())?;
+    }
+
+    Ok((merged, layers))
+}
+
+fn merge_file(
+    path: &Path,
+    merged: &mut Table,
+    layers: &mut ConfigLayers,
+    stack: &mut Vec<PathBuf>,
+) -> Result<()> {
+    let canonical = path.canonicalize().unwrap_or_else(|_| path.to_path_buf());
+    if stack.contains(&canonical) {
+        bail!("Config `extends` cycle through {}", path.display());
+    }
+    if stack.len() >= MAX_EXTENDS_DEPTH {
+        bail!(
+            "Config `extends` chain is deeper than {MAX_EXTENDS_DEPTH} files at {}",
+            path.display()
+        );
+    }
+
+    let contents = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read config file {}", path.display()))?;
+    let mut table: Table = toml::from_str(&contents)
+        .with_context(|| format!("Failed to parse config file {}", path.display()))?;
+    let extends = take_string_list(&mut table, EXTENDS_KEY, path)?;
+    let replace: BTreeSet<String> = take_string_list(&mut table, REPLACE_KEY, path)?
+        .into_iter()
+        .collect();
+
+    let dir = path.parent().unwrap_or_else(|| Path::new("."));
+    stack.push(canonical);
+    for include in extends {
+        let include = PathBuf::from(include);
+        let include = if include.is_absolute() {
+            include
+        } else {
+            dir.join(include)
+        };
+        merge_file(&include, merged, layers, stack)?;
+    }
+    stack.pop();
+
+    merge_table(merged, table, "", path, &replace, &mut layers.origins);
+    if !layers.files.iter().any(|file| file == path) {
+        layers.files.push(path.to_path_buf());
+    }
+    Ok(())
+}
+
+/// Remove a layering directive (`extends` / `replace`) from a parsed file.
+fn take_string_list(table: &mut Table, key: &str, path: &Path) -> Result<Vec<String>> {
+    match table.remove(key) {
+        None => Ok(Vec::new()),
+        Some(Value::String(value)) => Ok(vec![value]),
+        Some(Value::Array(values)) => values
+            .into_iter()
+            .map(|value| match value {
+                Value::String(value) => Ok(value),
+                other => bail!(
+                    "`{key}` in {} must contain strings, found {}",
+                    path.display(),
+                    other.type_str()
+                ),
+            })
+            .collect(),
+        Some(other) => bail!(
+            "`{key}` in {} must be a string or an array of strings, found {}",
+            path.display(),
+            other.type_str()
+        ),
+    }
+}
+
+fn join_path(prefix: &str, key: &str) -> String {
+    if prefix.is_empty() {
+        key.to_string()
+    } else {
+        format!("{prefix}.{key}")
+    }
+}
+
+fn merge_table(
+    base: &mut Table,
+    overlay: Table,
+    prefix: &str,
+    origin: &Path,
+    replace: &BTreeSet<String>,
+    origins: &mut BTreeMap<String, Vec<PathBuf>>,
+) {
+    for (key, value) in overlay {
+        let path = join_path(prefix, &key);
+        let merge = !replace.contains(&path);
+        match (base.get_mut(&key), value) {
+            (Some(Value::Table(existing)), Value::Table(incoming)) if merge => {
+                merge_table(existing, incoming, &path, origin, replace, origins);
+            }
+            (Some(Value::Array(existing)), Value::Array(incoming)) if merge => {
+                for item in incoming {
+                    if !existing.contains(&item) {
+                        existing.push(item);
+                    }
+                }
+                let files = origins.entry(path).or_default();
+                if !files.iter().any(|file| file == origin) {
+                    files.push(origin.to_path_buf());
+                }
+            }
+            (_, value) => {
+                origins.retain(|existing, _| {
+                    existing != &path && !existing.starts_with(&format!("{path}."))
+                });
+                record_origins(&path, &value, origin, origins);
+                base.insert(key, value);
+            }
+        }
+    }
+}
+
+fn record_origins(
+    path: &str,
+    value: &Value,
+    origin: &Path,
+    origins: &mut BTreeMap<String, Vec<PathBuf>>,
+) {
+    match value {
+        Value::Table(table) if !table.is_empty() => {
+            for (key, value) in table {
+                record_origins(&join_path(path, key), value, origin, origins);
+            }
+        }
+        _ => {
+            origins.insert(path.to_string(), vec![origin.to_path_buf()]);
+        }
+    }
+}
+
+/// Dotted key paths whose values differ between two serialized configs.
+/// Arrays are compared as a whole.
+pub fn changed_paths(old: &JsonValue, new: &JsonValue) -> Vec<String> {
+    let mut out = Vec::new();
+    collect_changed_paths("", old, new, &mut out);
+    out.sort();
+    out
+}
+
+fn collect_changed_paths(prefix: &str, old: &JsonValue, new: &JsonValue, out: &mut Vec<String>) {
+    match (old, new) {
+        (JsonValue::Object(old), JsonValue::Object(new)) => {
+            let keys: BTreeSet<&String> = old.keys().chain(new.keys()).collect();
+            for key in keys {
+                let path = join_path(prefix, key);
+                match (old.get(key), new.get(key)) {
+                    (Some(a), Some(b)) => collect_changed_paths(&path, a, b, out),
+                    _ => out.push(path),
+                }
+            }
+        }
+        _ if old != new => out.push(prefix.to_string()),
+        _ => {}
+    }
+}
+
+/// Render the base file for a layered config: the on-disk base file with
+/// only the keys changed since load patched in, so values from overlays are
+/// never flattened into it.
+///
+/// `current` is the plaintext config and `encrypted` the same config with
+/// secrets already sealed by `SecretStore`; values are copied from the latter.
+pub(crate) fn render_patched_base(
+    config_path: &Path,
+    layers: &ConfigLayers,
+    current: &JsonValue,
+    encrypted: &Table,
+) -> Result<String> {
+    let mut base: Table = if config_path.exists() {
+        let contents = std::fs::read_to_string(config_path)
+            .with_context(|| format!("Failed to read config file {}", config_path.display()))?;
+        toml::from_str(&contents)
+            .with_context(|| format!("Failed to parse config file {}", config_path.display()))?
+    } else {
+        Table::new()
+    };
+
+    for path in changed_paths(&layers.loaded, current) {
+        if let Some(file) = layers.overriding_file(&path, config_path) {
+            tracing::warn!(
+                "Config key `{path}` is also set by {}; the change is saved to {} but the overlay still takes precedence",
+                file.display(),
+                config_path.display()
+            );
+        }
+        let segments: Vec<&str> = path.split('.').collect();
+        match lookup(encrypted, &segments) {
+            Some(value) => insert_path(&mut base, &segments, value.clone()),
+            None => remove_path(&mut base, &segments),
+        }
+    }
+
+    toml::to_string_pretty(&base).context("Failed to serialize config")
+}
+
+fn lookup<'a>(table: &'a Table, segments: &[&str]) -> Option<&'a Value> {
+    let (first, rest) = segments.split_first()?;
+    let value = table.get(*first)?;
+    if rest.is_empty() {
+        return Some(value);
+    }
+    lookup(value.as_table()?, rest)
+}
+
+fn insert_path(table: &mut Table, segments: &[&str], value: Value) {
+    let Some((first, rest)) = segments.split_first() else {
+        return;
+    };
+    if rest.is_empty() {
+        table.insert((*first).to_string(), value);
+        return;
+    }
+    let entry = table
+        .entry((*first).to_string())
+        .or_insert_with(|| Value::Table(Table::new()));
+    if !entry.is_table() {
+        *entry = Value::Table(Table::new());
+    }
+    if let Value::Table(child) = entry {
+        insert_path(child, rest, value);
+    }
+}
+
+fn remove_path(table: &mut Table, segments: &[&str]) {
+    match segments {
+        [] => {}
+        [last] => {
+            table.remove(*last);
+        }
+        [first, rest @ ..] => {
+            if let Some(Value::Table(child)) = table.get_mut(*first) {
+                remove_path(child, rest);
+            }
+        }
+    }
+}
+
+fn display_file(path: &Path, config_dir: Option<&Path>) -> String {
+    config_dir
+        .and_then(|dir| path.strip_prefix(dir).ok())
+        .unwrap_or(path)
+        .display()
+        .to_string()
+}
+
+/// Human-readable list of the active profile and layer files.
+pub fn describe_layers(config: &Config) -> String {
+    let mut out = String::new();
+    let Some(layers) = &config.layers else {
+        let _ = writeln!(out, "Config: {}", config.config_path.display());
+        return out;
+    };
+    let _ = writeln!(
+        out,
+        "Profile: {}",
+        layers.profile.as_deref().unwrap_or("(none)")
+    );
+    let _ = writeln!(out, "Layers (lowest precedence first):");
+    for (idx, file) in layers.files.iter().enumerate() {
+        let _ = writeln!(out, "  {}. {}", idx + 1, file.display());
+    }
+    if !layers.env_overrides.is_empty() {
+        let _ = writeln!(
+            out,
+            "Environment overrides: {}",
+            layers.env_overrides.join(", ")
+        );
+    }
+    out
+}
+
+/// Render every effective leaf value as `key = value  # origin`.
+///
+/// Pass a config with secrets already masked; origins are `env`, the files
+/// that set the key (relative to the config directory), or `default`.
+pub fn render_with_origins(config: &Config) -> Result<String> {
+    let json = serde_json::to_value(config).context("Failed to serialize config")?;
+    let mut leaves = Vec::new();
+    collect_leaves("", &json, &mut leaves);
+
+    let config_dir = config.config_path.parent();
+    let mut out = String::new();
+    for (path, value) in leaves {
+        let rendered = match Value::try_from(value) {
+            Ok(value) => value.to_string(),
+            Err(_) => value.to_string(),
+        };
+        let origin = match &config.layers {
+            Some(layers) if layers.env_overrides.iter().any(|key| key == &path) => "env".into(),
+            Some(layers) => layers.origin(&path).map_or_else(
+                || "default".to_string(),
+                |files| {
+                    files
+                        .iter()
+                        .map(|file| display_file(file, config_dir))
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                },
+            ),
+            None => "default".into(),
+        };
+        let _ = writeln!(out, "{path} = {rendered}  # {origin}");
+    }
+    Ok(out)
+}
+
+fn collect_leaves<'a>(prefix: &str, value: &'a JsonValue, out: &mut Vec<(String, &'a JsonValue)>) {
+    match value {
+        JsonValue::Object(map) => {
+            for (key, value) in map {
+                collect_leaves(&join_path(prefix, key), value, out);
+            }
+        }
+        // TOML has no null; unset optional keys are omitted.
+        JsonValue::Null => {}
+        _ => out.push((prefix.to_string(), value)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn write(dir: &Path, name: &str, contents: &str) -> PathBuf {
+        let path = dir.join(name);
+        std::fs::write(&path, contents).unwrap();
+        path
+    }
+
+    #[test]
+    fn profile_and_local_overlays_deep_merge_with_origins() {
+        let tmp = TempDir::new().unwrap();
+        let base = write(
+            tmp.path(),
+            "config.toml",
+            "default_model = \"base-model\"\n[autonomy]\nallowed_commands = [\"git\"]\nmax_actions_per_hour = 10\n",
+        );
+        let prod = write(
+            tmp.path(),
+            "config.prod.toml",
+            "default_model = \"prod-model\"\n[autonomy]\nallowed_commands = [\"cargo\", \"git\"]\n",
+        );
+        let local = write(
+            tmp.path(),
+            "config.local.toml",
+            "[autonomy]\nmax_actions_per_hour = 99\n",
+        );
+
+        let (merged, layers) = load_merged(&base, Some("prod")).unwrap();
+        assert_eq!(merged["default_model"].as_str(), Some("prod-model"));
+        let autonomy = merged["autonomy"].as_table().unwrap();
+        assert_eq!(
+            autonomy["allowed_commands"],
+            Value::Array(vec!["git".into(), "cargo".into()])
+        );
+        assert_eq!(autonomy["max_actions_per_hour"].as_integer(), Some(99));
+
+        assert_eq!(
+            layers.files,
+            vec![base.clone(), prod.clone(), local.clone()]
+        );
+        assert_eq!(layers.origin("default_model"), Some(&[prod.clone()][..]));
+        assert_eq!(
+            layers.origin("autonomy.allowed_commands"),
+            Some(&[base.clone(), prod][..])
+        );
+        assert_eq!(
+            layers.origin("autonomy.max_actions_per_hour"),
+            Some(&[local][..])
+        );
+        assert!(layers.is_layered());
+    }
+
+    #[test]
+    fn replace_directive_and_extends_are_honoured() {
+        let tmp = TempDir::new().unwrap();
+        let shared = write(
+            tmp.path(),
+            "shared.toml",
+            "[autonomy]\nallowed_commands = [\"git\", \"ls\"]\n",
+        );
+        let base = write(
+            tmp.path(),
+            "config.toml",
+            "extends = \"shared.toml\"\nreplace = [\"autonomy.allowed_commands\"]\n[autonomy]\nallowed_commands = [\"cargo\"]\n",
+        );
+
+        let (merged, layers) = load_merged(&base, None).unwrap();
+        assert!(!merged.contains_key("extends"));
+        assert!(!merged.contains_key("replace"));
+        assert_eq!(
+            merged["autonomy"]["allowed_commands"],
+            Value::Array(vec!["cargo".into()])
+        );
+        assert_eq!(layers.files, vec![shared, base.clone()]);
+        assert_eq!(
+            layers.origin("autonomy.allowed_commands"),
+            Some(&[base][..])
+        );
+    }
+
+    #[test]
+    fn missing_profile_and_extends_cycles_are_errors() {
+        let tmp = TempDir::new().unwrap();
+        let base = write(tmp.path(), "config.toml", "extends = \"other.toml\"\n");
+        write(tmp.path(), "other.toml", "extends = \"config.toml\"\n");
+
+        let err = load_merged(&base, None).unwrap_err();
+        assert!(err.to_string().contains("cycle"), "{err}");
+
+        write(tmp.path(), "config.toml", "default_model = \"m\"\n");
+        let err = load_merged(&base, Some("staging")).unwrap_err();
+        assert!(err.to_string().contains("config.staging.toml"), "{err}");
+    }
+
+    #[tokio::test]
+    async fn save_patches_base_file_without_flattening_overlays() {
+        let tmp = TempDir::new().unwrap();
+        let running = Config {
+            config_path: tmp.path().join("config.toml"),
+            workspace_dir: tmp.path().join("workspace"),
+            ..Config::default()
+        };
+        running.save().await.unwrap();
+        write(
+            tmp.path(),
+            "config.local.toml",
+            "default_model = \"local-model\"\n[gateway]\nport = 4242\n",
+        );
+
+        let mut config = running.reload_from_disk().unwrap();
+        assert_eq!(config.default_model.as_deref(), Some("local-model"));
+        assert_eq!(config.gateway.port, 4242);
+
+        config.api_key = Some("sk-live-secret".into());
+        config.autonomy.allowed_commands = vec!["cargo".into()];
+        config.save().await.unwrap();
+
+        let base = std::fs::read_to_string(tmp.path().join("config.toml")).unwrap();
+        assert!(!base.contains("local-model"));
+        assert!(!base.contains("4242"));
+        assert!(!base.contains("sk-live-secret"), "secrets stay encrypted");
+        assert!(base.contains("allowed_commands = [\"cargo\"]"));
+
+        let reloaded = running.reload_from_disk().unwrap();
+        assert_eq!(reloaded.api_key.as_deref(), Some("sk-live-secret"));
+        assert_eq!(
+            reloaded.autonomy.allowed_commands,
+            vec!["cargo".to_string()]
+        );
+        assert_eq!(reloaded.default_model.as_deref(), Some("local-model"));
+    }
+
+    #[tokio::test]
+    async fn origins_report_file_and_default() {
+        let tmp = TempDir::new().unwrap();
+        let running = Config {
+            config_path: tmp.path().join("config.toml"),
+            workspace_dir: tmp.path().join("workspace"),
+            ..Config::default()
+        };
+        write(tmp.path(), "config.toml", "default_temperature = 0.3\n");
+        write(tmp.path(), "config.local.toml", "[gateway]\nport = 4242\n");
+
+        let config = running.reload_from_disk().unwrap();
+        let rendered = render_with_origins(&config).unwrap();
+        assert!(rendered.contains("default_temperature = 0.3  # config.toml"));
+        assert!(rendered.contains("gateway.port = 4242  # config.local.toml"));
+        assert!(rendered.contains("gateway.host = \"127.0.0.1\"  # default"));
+        assert!(describe_layers(&config).contains("config.local.toml"));
+    }
+}
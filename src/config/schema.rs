@@ -1,5 +1,6 @@
+use crate::config::layers::{self, ConfigLayers};
 use crate::config::traits::ChannelConfig;
-use crate::providers::{is_glm_alias, is_zai_alias};
+use crate::providers::{is_glm_alias, is_zai_alias, GenerationParams, ToolMode};
 use crate::security::{AutonomyLevel, DomainMatcher};
 use anyhow::{Context, Result};
 use directories::UserDirs;
@@ -7,7 +8,7 @@ use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::path::{Path, PathBuf};
-use std::sync::{OnceLock, RwLock};
+use std::sync::{Arc, OnceLock, RwLock};
 #[cfg(unix)]
 use tokio::fs::File;
 use tokio::fs::{self, OpenOptions};
@@ -39,6 +40,9 @@ const SUPPORTED_PROXY_SERVICE_KEYS: &[&str] = &[
     "tool.composio",
     "tool.http_request",
     "tool.pushover",
+    "tool.skill",
+    "tool.web_search",
+    "hook.http",
     "memory.embeddings",
     "tunnel.custom",
     "transcription.groq",
@@ -48,6 +52,7 @@ const SUPPORTED_PROXY_SERVICE_SELECTORS: &[&str] = &[
     "provider.*",
     "channel.*",
     "tool.*",
+    "hook.*",
     "memory.*",
     "tunnel.*",
     "transcription.*",
@@ -134,10 +139,18 @@ pub struct Config {
     #[serde(default)]
     pub heartbeat: HeartbeatConfig,
 
+    /// Autonomous goal executor configuration (`[goal_loop]`).
+    #[serde(default)]
+    pub goal_loop: GoalLoopConfig,
+
     /// Cron job configuration (`[cron]`).
     #[serde(default)]
     pub cron: CronConfig,
 
+    /// Standard operating procedure engine configuration (`[sop]`).
+    #[serde(default)]
+    pub sop: SopConfig,
+
     /// Channel configurations: Telegram, Discord, Slack, etc. (`[channels_config]`).
     #[serde(default)]
     pub channels_config: ChannelsConfig,
@@ -202,6 +215,10 @@ pub struct Config {
     #[serde(default)]
     pub peripherals: PeripheralsConfig,
 
+    /// Robot kit tools (drive, look, speak, listen, sense, emote) (`[robot]`).
+    #[serde(default)]
+    pub robot: RobotConfig,
+
     /// Delegate agent configurations for multi-agent workflows.
     #[serde(default)]
     pub agents: HashMap<String, DelegateAgentConfig>,
@@ -217,6 +234,10 @@ pub struct Config {
     /// Voice transcription configuration (Whisper API via Groq).
     #[serde(default)]
     pub transcription: TranscriptionConfig,
+
+    /// Files and profile this config was loaded from - computed, not serialized
+    #[serde(skip)]
+    pub layers: Option<Arc<ConfigLayers>>,
 }
 
 /// Named provider profile definition compatible with Codex app-server style config.
@@ -411,6 +432,15 @@ pub struct AgentConfig {
     /// Tool dispatch strategy (e.g. `"auto"`). Default: `"auto"`.
     #[serde(default = "default_agent_tool_dispatcher")]
     pub tool_dispatcher: String,
+    /// Generation parameters for agent turns (`max_tokens`, `top_p`, `stop`,
+    /// `seed`, penalties, `reasoning_effort`, `thinking_budget`). Route and
+    /// cron job parameters take precedence. Default: provider defaults.
+    #[serde(default, skip_serializing_if = "GenerationParams::is_empty")]
+    pub generation: GenerationParams,
+    /// Per-model tool calling mode (`auto`, `native`, `xml`), keyed by
+    /// `provider/model` or model id. Default: `auto` for every model.
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub tool_modes: HashMap<String, ToolMode>,
 }
 
 fn default_agent_max_tool_iterations() -> usize {
@@ -433,6 +463,8 @@ impl Default for AgentConfig {
             max_history_messages: default_agent_max_history_messages(),
             parallel_tools: false,
             tool_dispatcher: default_agent_tool_dispatcher(),
+            generation: GenerationParams::default(),
+            tool_modes: HashMap::new(),
         }
     }
 }
@@ -732,6 +764,9 @@ pub struct PeripheralBoardConfig {
     /// Baud rate for serial (default: 115200)
     #[serde(default = "default_peripheral_baud")]
     pub baud: u32,
+    /// Simulated board settings, used when `transport = "simulated"`
+    #[serde(default)]
+    pub simulation: Option<SimulatedBoardConfig>,
 }
 
 fn default_peripheral_transport() -> String {
@@ -749,6 +784,108 @@ impl Default for PeripheralBoardConfig {
             transport: default_peripheral_transport(),
             path: None,
             baud: default_peripheral_baud(),
+            simulation: None,
+        }
+    }
+}
+
+/// Simulated peripheral board (`[peripherals.boards.simulation]`).
+///
+/// Speaks the same newline-delimited JSON protocol as serial firmware, so
+/// GPIO, capabilities and memory-read tools can run without real hardware.
+#[derive(Debug, Clone, Serialize, Deserialize, Default, JsonSchema)]
+pub struct SimulatedBoardConfig {
+    /// Serve the board on a pseudo-terminal and connect through the serial
+    /// transport instead of in-process (default: false)
+    #[serde(default)]
+    pub pty: bool,
+    /// Initial pin levels (unlisted pins start low)
+    #[serde(default)]
+    pub pins: Vec<SimulatedPinConfig>,
+    /// Timed pin changes applied after connect, each emitted as a peripheral signal
+    #[serde(default)]
+    pub script: Vec<SimulatedPinStep>,
+    /// Memory contents served by `memory_read` (unmapped bytes read as zero)
+    #[serde(default)]
+    pub memory: Vec<SimulatedMemoryConfig>,
+    /// Fault injection
+    #[serde(default)]
+    pub faults: SimulatedFaultConfig,
+}
+
+/// Initial level of one simulated pin.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct SimulatedPinConfig {
+    pub pin: u32,
+    pub value: u8,
+}
+
+/// One scripted pin change.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct SimulatedPinStep {
+    /// Delay after connect, in milliseconds
+    pub after_ms: u64,
+    pub pin: u32,
+    pub value: u8,
+}
+
+/// A block of simulated memory.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct SimulatedMemoryConfig {
+    /// Start address (e.g. 0x2000_0000)
+    pub address: u64,
+    /// Contents as hex bytes (e.g. "DEADBEEF")
+    pub hex: String,
+}
+
+/// Faults injected by a simulated board.
+#[derive(Debug, Clone, Serialize, Deserialize, Default, JsonSchema)]
+pub struct SimulatedFaultConfig {
+    /// Commands answered with `ok: false`
+    #[serde(default)]
+    pub fail_commands: Vec<String>,
+    /// Commands that never get a response, to exercise request timeouts
+    #[serde(default)]
+    pub drop_commands: Vec<String>,
+    /// Delay before every response, in milliseconds
+    #[serde(default)]
+    pub latency_ms: u64,
+    /// Pins that ignore writes and keep their level
+    #[serde(default)]
+    pub stuck_pins: Vec<u32>,
+}
+
+// ── Robot kit ───────────────────────────────────────────────────
+
+/// Robot kit integration (`[robot]` section).
+///
+/// Registers `zeroclaw-robot-kit` tools as agent tools behind its safety
+/// monitor. Requires the `robot` build feature.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct RobotConfig {
+    /// Enable robot tools (default: false)
+    #[serde(default)]
+    pub enabled: bool,
+    /// Robot kit TOML file (drive backend, camera, audio, sensors, safety).
+    /// Relative paths resolve against the config directory. Unset uses the
+    /// built-in defaults (mock drive and sensors).
+    #[serde(default)]
+    pub config_file: Option<String>,
+    /// How often the e-stop state is checked, in milliseconds (default: 500)
+    #[serde(default = "default_robot_estop_poll_ms")]
+    pub estop_poll_ms: u64,
+}
+
+fn default_robot_estop_poll_ms() -> u64 {
+    500
+}
+
+impl Default for RobotConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            config_file: None,
+            estop_poll_ms: default_robot_estop_poll_ms(),
         }
     }
 }
@@ -1109,7 +1246,9 @@ pub struct WebSearchConfig {
     /// Enable `web_search_tool` for web searches
     #[serde(default)]
     pub enabled: bool,
-    /// Search provider: "duckduckgo" (free, no API key) or "brave" (requires API key)
+    /// Search provider: "duckduckgo" (free, no API key), "brave" (requires API key),
+    /// "firecrawl", "searxng" (requires `searxng_url`), or the `name` of a
+    /// `[[web_search.custom]]` backend
     #[serde(default = "default_web_search_provider")]
     pub provider: String,
     /// Brave Search API key (required if provider is "brave")
@@ -1121,12 +1260,68 @@ pub struct WebSearchConfig {
     /// Request timeout in seconds
     #[serde(default = "default_web_search_timeout_secs")]
     pub timeout_secs: u64,
+    /// Base URL of a SearXNG instance (required if provider is "searxng").
+    /// The instance must have the `json` output format enabled.
+    #[serde(default)]
+    pub searxng_url: Option<String>,
+    /// Custom JSON search endpoints (`[[web_search.custom]]`), selectable by name
+    #[serde(default)]
+    pub custom: Vec<CustomSearchBackendConfig>,
+    /// Query these providers in parallel and merge their results, dropping
+    /// duplicate URLs. Overrides `provider` when non-empty.
+    #[serde(default)]
+    pub fan_out: Vec<String>,
+    /// Cache results per query for this many minutes (0 = disabled).
+    /// Stored in `memory/search_cache.db` next to the response cache.
+    #[serde(default)]
+    pub cache_ttl_minutes: u32,
+    /// Maximum number of cached queries
+    #[serde(default = "default_web_search_cache_max_entries")]
+    pub cache_max_entries: usize,
+}
+
+/// Generic JSON search endpoint (`[[web_search.custom]]`).
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct CustomSearchBackendConfig {
+    /// Backend name, used as `provider` or in `fan_out`
+    pub name: String,
+    /// Request URL; `{query}` (URL-encoded) and `{max_results}` are substituted
+    pub url_template: String,
+    /// Extra request headers; `{api_key}` in a value is replaced by `api_key`
+    #[serde(default)]
+    pub headers: HashMap<String, String>,
+    /// API key substituted into headers (encrypted at rest)
+    #[serde(default)]
+    pub api_key: Option<String>,
+    /// JSONPath selecting the result list, e.g. `$.data.items[*]`
+    pub results_path: String,
+    /// JSONPath of the title within one result
+    #[serde(default = "default_custom_search_title_path")]
+    pub title_path: String,
+    /// JSONPath of the URL within one result
+    #[serde(default = "default_custom_search_url_path")]
+    pub url_path: String,
+    /// JSONPath of the snippet within one result
+    #[serde(default)]
+    pub snippet_path: Option<String>,
 }
 
 fn default_web_search_provider() -> String {
     "duckduckgo".into()
 }
 
+fn default_web_search_cache_max_entries() -> usize {
+    1000
+}
+
+fn default_custom_search_title_path() -> String {
+    "$.title".into()
+}
+
+fn default_custom_search_url_path() -> String {
+    "$.url".into()
+}
+
 fn default_web_search_max_results() -> usize {
     5
 }
@@ -1143,6 +1338,11 @@ impl Default for WebSearchConfig {
             brave_api_key: None,
             max_results: default_web_search_max_results(),
             timeout_secs: default_web_search_timeout_secs(),
+            searxng_url: None,
+            custom: Vec::new(),
+            fan_out: Vec::new(),
+            cache_ttl_minutes: 0,
+            cache_max_entries: default_web_search_cache_max_entries(),
         }
     }
 }
@@ -1737,7 +1937,7 @@ pub struct MemoryConfig {
     /// For sqlite backend: prune conversation rows older than this many days
     #[serde(default = "default_conversation_retention_days")]
     pub conversation_retention_days: u32,
-    /// Embedding provider: "none" | "openai" | "custom:URL"
+    /// Embedding provider: "none" | "openai" | "custom:URL" | "local:DIR" (feature `embeddings-local`)
     #[serde(default = "default_embedding_provider")]
     pub embedding_provider: String,
     /// Embedding model name (e.g. "text-embedding-3-small")
@@ -1937,6 +2137,10 @@ pub struct HooksConfig {
     pub enabled: bool,
     #[serde(default)]
     pub builtin: BuiltinHooksConfig,
+    /// User-defined hooks (`[[hooks.custom]]`) backed by an executable,
+    /// an HTTP endpoint or a WASM module.
+    #[serde(default)]
+    pub custom: Vec<CustomHookConfig>,
 }
 
 impl Default for HooksConfig {
@@ -1944,6 +2148,7 @@ impl Default for HooksConfig {
         Self {
             enabled: true,
             builtin: BuiltinHooksConfig::default(),
+            custom: Vec::new(),
         }
     }
 }
@@ -1962,6 +2167,68 @@ impl Default for BuiltinHooksConfig {
     }
 }
 
+/// How a custom hook is executed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum CustomHookKind {
+    /// Spawn `command` with `args`; the event is written to stdin, the verdict read from stdout.
+    Command,
+    /// POST the event as JSON to `url`; the response body is the verdict.
+    Http,
+    /// Run `module` from the WASM tools directory with the event as input payload.
+    Wasm,
+}
+
+/// What happens when a custom hook errors, times out or returns an invalid verdict.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum HookFailurePolicy {
+    /// Continue with the unmodified data.
+    #[default]
+    Open,
+    /// Cancel the operation.
+    Closed,
+}
+
+/// User-defined lifecycle hook (`[[hooks.custom]]`).
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct CustomHookConfig {
+    /// Hook name, used in logs and cancellation reasons
+    pub name: String,
+    /// Lifecycle events this hook subscribes to (e.g. `before_tool_call`)
+    pub events: Vec<String>,
+    /// Execution backend: `command`, `http` or `wasm`
+    pub kind: CustomHookKind,
+    /// Executable path (`kind = "command"`)
+    #[serde(default)]
+    pub command: Option<String>,
+    /// Arguments passed to `command`
+    #[serde(default)]
+    pub args: Vec<String>,
+    /// Endpoint receiving the event (`kind = "http"`)
+    #[serde(default)]
+    pub url: Option<String>,
+    /// Extra request headers (`kind = "http"`)
+    #[serde(default)]
+    pub headers: HashMap<String, String>,
+    /// Module name inside the WASM tools directory, without `.wasm` (`kind = "wasm"`)
+    #[serde(default)]
+    pub module: Option<String>,
+    /// Maximum time a single invocation may take
+    #[serde(default = "default_custom_hook_timeout_ms")]
+    pub timeout_ms: u64,
+    /// Behaviour on error, timeout or malformed verdict: `open` or `closed`
+    #[serde(default)]
+    pub on_failure: HookFailurePolicy,
+    /// Ordering among hooks; higher runs first
+    #[serde(default)]
+    pub priority: i32,
+}
+
+fn default_custom_hook_timeout_ms() -> u64 {
+    5_000
+}
+
 // ── Autonomy / Security ──────────────────────────────────────────
 
 /// Autonomy and security policy configuration (`[autonomy]` section).
@@ -2516,6 +2783,9 @@ pub struct ReliabilityConfig {
     /// Max retries for cron job execution attempts.
     #[serde(default = "default_scheduler_retries")]
     pub scheduler_retries: u32,
+    /// Speculative provider racing for latency-critical channels.
+    #[serde(default)]
+    pub racing: RacingConfig,
 }
 
 fn default_provider_retries() -> u32 {
@@ -2554,10 +2824,74 @@ impl Default for ReliabilityConfig {
             channel_max_backoff_secs: default_channel_backoff_max_secs(),
             scheduler_poll_secs: default_scheduler_poll_secs(),
             scheduler_retries: default_scheduler_retries(),
+            racing: RacingConfig::default(),
         }
     }
 }
 
+/// Speculative provider racing (`[reliability.racing]` section).
+///
+/// Replies on the listed channels are requested from the channel's active
+/// provider and from each racer; the first successful response wins and the
+/// other requests are cancelled.
+///
+/// ```toml
+/// [reliability.racing]
+/// channels = ["clawdtalk", "telnyx"]
+/// hedge_delay_ms = 400
+///
+/// [[reliability.racing.providers]]
+/// provider = "groq"
+/// model = "llama-3.3-70b-versatile"
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct RacingConfig {
+    /// Channels whose replies are raced (e.g. `["clawdtalk", "telnyx"]`).
+    #[serde(default)]
+    pub channels: Vec<String>,
+    /// Providers raced against the channel's active provider, in launch order.
+    #[serde(default)]
+    pub providers: Vec<RacerConfig>,
+    /// Delay (ms) before launching the next racer until enough latencies have
+    /// been observed to use the previous racer's p95. `0` launches all at once.
+    #[serde(default = "default_racing_hedge_delay_ms")]
+    pub hedge_delay_ms: u64,
+}
+
+/// A provider and model raced against the active provider.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct RacerConfig {
+    /// Provider name (e.g. `"groq"`, `"openai"`)
+    pub provider: String,
+    /// Model to request from that provider
+    pub model: String,
+}
+
+fn default_racing_hedge_delay_ms() -> u64 {
+    400
+}
+
+impl Default for RacingConfig {
+    fn default() -> Self {
+        Self {
+            channels: Vec::new(),
+            providers: Vec::new(),
+            hedge_delay_ms: default_racing_hedge_delay_ms(),
+        }
+    }
+}
+
+impl RacingConfig {
+    /// Whether replies on `channel` should be raced.
+    pub fn applies_to(&self, channel: &str) -> bool {
+        !self.providers.is_empty()
+            && self
+                .channels
+                .iter()
+                .any(|name| name.trim().eq_ignore_ascii_case(channel))
+    }
+}
+
 // ── Scheduler ────────────────────────────────────────────────────
 
 /// Scheduler configuration for periodic task execution (`[scheduler]` section).
@@ -2610,9 +2944,13 @@ impl Default for SchedulerConfig {
 /// hint = "fast"
 /// provider = "groq"
 /// model = "llama-3.3-70b-versatile"
+/// max_tokens = 256
 /// ```
 ///
 /// Usage: pass `hint:reasoning` as the model parameter to route the request.
+/// Generation parameters (`max_tokens`, `top_p`, `stop`, `seed`,
+/// `presence_penalty`, `frequency_penalty`, `reasoning_effort`,
+/// `thinking_budget`) set on a route apply to every request sent through it.
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct ModelRouteConfig {
     /// Task hint name (e.g. "reasoning", "fast", "code", "summarize")
@@ -2624,9 +2962,9 @@ pub struct ModelRouteConfig {
     /// Optional API key override for this route's provider
     #[serde(default)]
     pub api_key: Option<String>,
-    /// Maximum tokens for this model
-    #[serde(default)]
-    pub max_tokens: Option<u32>,
+    /// Generation defaults for requests sent through this route
+    #[serde(flatten)]
+    pub generation: GenerationParams,
 }
 
 // ── Embedding routing ───────────────────────────────────────────
@@ -2727,6 +3065,59 @@ impl Default for HeartbeatConfig {
     }
 }
 
+// ── Goal loop ───────────────────────────────────────────────────
+
+/// Autonomous goal executor (`[goal_loop]` section).
+///
+/// When enabled, the daemon periodically works through actionable steps in
+/// `state/goals.json` and runs a reflection pass on stalled goals.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct GoalLoopConfig {
+    /// Enable the goal loop daemon component. Default: `false`.
+    #[serde(default)]
+    pub enabled: bool,
+    /// Interval in minutes between goal cycles. Default: `10`.
+    #[serde(default = "default_goal_loop_interval_minutes")]
+    pub interval_minutes: u32,
+    /// Maximum wall-clock time for a single step, in seconds. Default: `120`.
+    #[serde(default = "default_goal_loop_step_timeout_secs")]
+    pub step_timeout_secs: u64,
+    /// Maximum number of steps executed per cycle. Default: `3`.
+    #[serde(default = "default_goal_loop_max_steps_per_cycle")]
+    pub max_steps_per_cycle: u32,
+    /// Optional delivery channel for progress reports (for example: `telegram`).
+    #[serde(default)]
+    pub channel: Option<String>,
+    /// Delivery recipient/chat identifier (required when `channel` is set).
+    #[serde(default)]
+    pub target: Option<String>,
+}
+
+fn default_goal_loop_interval_minutes() -> u32 {
+    10
+}
+
+fn default_goal_loop_step_timeout_secs() -> u64 {
+    120
+}
+
+fn default_goal_loop_max_steps_per_cycle() -> u32 {
+    3
+}
+
+impl Default for GoalLoopConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            interval_minutes: default_goal_loop_interval_minutes(),
+            step_timeout_secs: default_goal_loop_step_timeout_secs(),
+            max_steps_per_cycle: default_goal_loop_max_steps_per_cycle(),
+            channel: None,
+            target: None,
+        }
+    }
+}
+
 // ── Cron ────────────────────────────────────────────────────────
 
 /// Cron job configuration (`[cron]` section).
@@ -2753,6 +3144,53 @@ impl Default for CronConfig {
     }
 }
 
+// ── SOP ─────────────────────────────────────────────────────────
+
+/// Standard operating procedure engine (`[sop]` section).
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct SopConfig {
+    /// Directory containing SOP definitions. Default: `<workspace>/sops`.
+    #[serde(default)]
+    pub sops_dir: Option<String>,
+    /// Execution mode for SOPs whose `SOP.toml` does not set one. Default: `supervised`.
+    #[serde(default)]
+    pub default_execution_mode: crate::sop::types::SopExecutionMode,
+    /// Maximum number of SOP runs active at once across all SOPs. Default: `4`.
+    #[serde(default = "default_sop_max_concurrent_total")]
+    pub max_concurrent_total: usize,
+    /// Seconds before a critical/high-priority run waiting for approval is
+    /// auto-approved; `0` disables the timeout. Default: `300`.
+    #[serde(default = "default_sop_approval_timeout_secs")]
+    pub approval_timeout_secs: u64,
+    /// Finished runs kept in memory for status queries; `0` keeps all. Default: `100`.
+    #[serde(default = "default_sop_max_finished_runs")]
+    pub max_finished_runs: usize,
+}
+
+fn default_sop_max_concurrent_total() -> usize {
+    4
+}
+
+fn default_sop_approval_timeout_secs() -> u64 {
+    300
+}
+
+fn default_sop_max_finished_runs() -> usize {
+    100
+}
+
+impl Default for SopConfig {
+    fn default() -> Self {
+        Self {
+            sops_dir: None,
+            default_execution_mode: crate::sop::types::SopExecutionMode::default(),
+            max_concurrent_total: default_sop_max_concurrent_total(),
+            approval_timeout_secs: default_sop_approval_timeout_secs(),
+            max_finished_runs: default_sop_max_finished_runs(),
+        }
+    }
+}
+
 // ── Tunnel ──────────────────────────────────────────────────────
 
 /// Tunnel configuration for exposing the gateway publicly (`[tunnel]` section).
@@ -3914,6 +4352,8 @@ impl Default for Config {
             model_routes: Vec::new(),
             embedding_routes: Vec::new(),
             heartbeat: HeartbeatConfig::default(),
+            goal_loop: GoalLoopConfig::default(),
+            sop: SopConfig::default(),
             cron: CronConfig::default(),
             channels_config: ChannelsConfig::default(),
             memory: MemoryConfig::default(),
@@ -3931,11 +4371,13 @@ impl Default for Config {
             identity: IdentityConfig::default(),
             cost: CostConfig::default(),
             peripherals: PeripheralsConfig::default(),
+            robot: RobotConfig::default(),
             agents: HashMap::new(),
             hooks: HooksConfig::default(),
             hardware: HardwareConfig::default(),
             query_classification: QueryClassificationConfig::default(),
             transcription: TranscriptionConfig::default(),
+            layers: None,
         }
     }
 }
@@ -4346,71 +4788,7 @@ impl Config {
                 }
             }
 
-            let contents = fs::read_to_string(&config_path)
-                .await
-                .context("Failed to read config file")?;
-
-            // Track ignored/unknown config keys to warn users about silent misconfigurations
-            // (e.g., using [providers.ollama] which doesn't exist instead of top-level api_url)
-            let mut ignored_paths: Vec<String> = Vec::new();
-            let mut config: Config = serde_ignored::deserialize(
-                toml::de::Deserializer::parse(&contents).context("Failed to parse config file")?,
-                |path| {
-                    ignored_paths.push(path.to_string());
-                },
-            )
-            .context("Failed to deserialize config file")?;
-
-            // Warn about each unknown config key
-            for path in ignored_paths {
-                tracing::warn!(
-                    "Unknown config key ignored: \"{}\". Check config.toml for typos or deprecated options.",
-                    path
-                );
-            }
-            // Set computed paths that are skipped during serialization
-            config.config_path = config_path.clone();
-            config.workspace_dir = workspace_dir;
-            let store = crate::security::SecretStore::new(&zeroclaw_dir, config.secrets.encrypt);
-            decrypt_optional_secret(&store, &mut config.api_key, "config.api_key")?;
-            decrypt_optional_secret(
-                &store,
-                &mut config.composio.api_key,
-                "config.composio.api_key",
-            )?;
-
-            decrypt_optional_secret(
-                &store,
-                &mut config.browser.computer_use.api_key,
-                "config.browser.computer_use.api_key",
-            )?;
-
-            decrypt_optional_secret(
-                &store,
-                &mut config.web_search.brave_api_key,
-                "config.web_search.brave_api_key",
-            )?;
-
-            decrypt_optional_secret(
-                &store,
-                &mut config.storage.provider.config.db_url,
-                "config.storage.provider.config.db_url",
-            )?;
-
-            for agent in config.agents.values_mut() {
-                decrypt_optional_secret(&store, &mut agent.api_key, "config.agents.*.api_key")?;
-            }
-
-            if let Some(ref mut ns) = config.channels_config.nostr {
-                decrypt_secret(
-                    &store,
-                    &mut ns.private_key,
-                    "config.channels_config.nostr.private_key",
-                )?;
-            }
-
-            config.apply_env_overrides();
-            config.validate()?;
+            let config = Self::load_existing_file(&config_path, &zeroclaw_dir, workspace_dir)?;
             tracing::info!(
                 path = %config.config_path.display(),
                 workspace = %config.workspace_dir.display(),
@@ -4445,6 +4823,101 @@ impl Config {
         }
     }
 
+    /// Re-read `config.toml` from [`Config::config_path`], keeping the resolved
+    /// workspace directory. Runs the same decryption, env overrides and
+    /// validation as [`Config::load_or_init`]; used by the daemon hot-reload path.
+    pub fn reload_from_disk(&self) -> Result<Self> {
+        let zeroclaw_dir = self
+            .config_path
+            .parent()
+            .context("Config path must have a parent directory")?;
+        Self::load_existing_file(&self.config_path, zeroclaw_dir, self.workspace_dir.clone())
+    }
+
+    fn load_existing_file(
+        config_path: &Path,
+        zeroclaw_dir: &Path,
+        workspace_dir: PathBuf,
+    ) -> Result<Self> {
+        let (merged, mut config_layers) =
+            layers::load_merged(config_path, layers::active_profile().as_deref())?;
+
+        // Track ignored/unknown config keys to warn users about silent misconfigurations
+        // (e.g., using [providers.ollama] which doesn't exist instead of top-level api_url)
+        let mut ignored_paths: Vec<String> = Vec::new();
+        let mut config: Config = serde_ignored::deserialize(toml::Value::Table(merged), |path| {
+            ignored_paths.push(path.to_string());
+        })
+        .context("Failed to deserialize config file")?;
+
+        // Warn about each unknown config key
+        for path in ignored_paths {
+            tracing::warn!(
+                "Unknown config key ignored: \"{}\". Check config.toml for typos or deprecated options.",
+                path
+            );
+        }
+        // Set computed paths that are skipped during serialization
+        config.config_path = config_path.to_path_buf();
+        config.workspace_dir = workspace_dir;
+        let store = crate::security::SecretStore::new(zeroclaw_dir, config.secrets.encrypt);
+        decrypt_optional_secret(&store, &mut config.api_key, "config.api_key")?;
+        decrypt_optional_secret(
+            &store,
+            &mut config.composio.api_key,
+            "config.composio.api_key",
+        )?;
+
+        decrypt_optional_secret(
+            &store,
+            &mut config.browser.computer_use.api_key,
+            "config.browser.computer_use.api_key",
+        )?;
+
+        decrypt_optional_secret(
+            &store,
+            &mut config.web_search.brave_api_key,
+            "config.web_search.brave_api_key",
+        )?;
+
+        decrypt_optional_secret(
+            &store,
+            &mut config.storage.provider.config.db_url,
+            "config.storage.provider.config.db_url",
+        )?;
+
+        for agent in config.agents.values_mut() {
+            decrypt_optional_secret(&store, &mut agent.api_key, "config.agents.*.api_key")?;
+        }
+
+        for backend in &mut config.web_search.custom {
+            decrypt_optional_secret(
+                &store,
+                &mut backend.api_key,
+                "config.web_search.custom.*.api_key",
+            )?;
+        }
+
+        if let Some(ref mut ns) = config.channels_config.nostr {
+            decrypt_secret(
+                &store,
+                &mut ns.private_key,
+                "config.channels_config.nostr.private_key",
+            )?;
+        }
+
+        let before_env =
+            serde_json::to_value(&config).context("Failed to serialize loaded config")?;
+        config.apply_env_overrides();
+        config.validate()?;
+
+        let loaded = serde_json::to_value(&config).context("Failed to serialize loaded config")?;
+        config_layers.env_overrides = layers::changed_paths(&before_env, &loaded);
+        config_layers.loaded = loaded;
+        config.layers = Some(Arc::new(config_layers));
+        Ok(config)
+    }
+
     fn lookup_model_provider_profile(
         &self,
         provider_name: &str,
@@ -4607,6 +5080,38 @@ impl Config {
             if route.model.trim().is_empty() {
                 anyhow::bail!("model_routes[{i}].model must not be empty");
             }
+            if let Err(e) = route.generation.validate() {
+                anyhow::bail!("model_routes[{i}].{e}");
+            }
+        }
+        if !self.model_routes.is_empty() {
+            let catalog = crate::providers::ModelCatalog::load(&self.workspace_dir);
+            let today = chrono::Utc::now().date_naive();
+            for (i, route) in self.model_routes.iter().enumerate() {
+                let Some(model) = catalog.find(&route.provider, &route.model) else {
+                    continue;
+                };
+                if let (Some(requested), Some(limit)) =
+                    (route.generation.max_tokens, model.max_output_tokens)
+                {
+                    if u64::from(requested) > limit {
+                        anyhow::bail!(
+                            "model_routes[{i}].max_tokens ({requested}) exceeds the {limit}-token output limit of {}",
+                            route.model
+                        );
+                    }
+                }
+                if model.is_deprecated(today) {
+                    tracing::warn!(
+                        "model_routes[{i}] uses {}, which was retired on {}",
+                        route.model,
+                        model.deprecated_on.unwrap_or(today)
+                    );
+                }
+            }
+        }
+        if let Err(e) = self.agent.generation.validate() {
+            anyhow::bail!("agent.generation.{e}");
         }
 
         // Embedding routes
@@ -4622,6 +5127,16 @@ impl Config {
             }
         }
 
+        // Provider racing
+        for (i, racer) in self.reliability.racing.providers.iter().enumerate() {
+            if racer.provider.trim().is_empty() {
+                anyhow::bail!("reliability.racing.providers[{i}].provider must not be empty");
+            }
+            if racer.model.trim().is_empty() {
+                anyhow::bail!("reliability.racing.providers[{i}].model must not be empty");
+            }
+        }
+
         for (profile_key, profile) in &self.model_providers {
             let profile_name = profile_key.trim();
             if profile_name.is_empty() {
@@ -4690,6 +5205,124 @@ impl Config {
             }
         }
 
+        // Web search backends
+        for (i, backend) in self.web_search.custom.iter().enumerate() {
+            let name = backend.name.trim();
+            if name.is_empty() {
+                anyhow::bail!("web_search.custom[{i}].name must not be empty");
+            }
+            if crate::tools::search_backends::BUILTIN_SEARCH_BACKENDS.contains(&name) {
+                anyhow::bail!("web_search.custom[{i}].name '{name}' shadows a built-in provider");
+            }
+            if self.web_search.custom[..i]
+                .iter()
+                .any(|other| other.name.trim() == name)
+            {
+                anyhow::bail!("web_search.custom[{i}].name '{name}' is defined more than once");
+            }
+            let url = backend.url_template.trim();
+            if !url.starts_with("http://") && !url.starts_with("https://") {
+                anyhow::bail!("web_search.custom[{i}].url_template must be an http(s) URL");
+            }
+            for (field, path) in [
+                ("results_path", Some(backend.results_path.as_str())),
+                ("title_path", Some(backend.title_path.as_str())),
+                ("url_path", Some(backend.url_path.as_str())),
+                ("snippet_path", backend.snippet_path.as_deref()),
+            ] {
+                if let Some(path) = path {
+                    if let Err(e) = crate::tools::search_backends::JsonPath::parse(path) {
+                        anyhow::bail!("web_search.custom[{i}].{field} is invalid: {e}");
+                    }
+                }
+            }
+        }
+
+        // Custom hooks
+        for (i, hook) in self.hooks.custom.iter().enumerate() {
+            let name = hook.name.trim();
+            if name.is_empty() {
+                anyhow::bail!("hooks.custom[{i}].name must not be empty");
+            }
+            if self.hooks.custom[..i]
+                .iter()
+                .any(|other| other.name.trim() == name)
+            {
+                anyhow::bail!("hooks.custom[{i}].name '{name}' is defined more than once");
+            }
+            if hook.events.is_empty() {
+                anyhow::bail!("hooks.custom[{i}].events must not be empty");
+            }
+            for event in &hook.events {
+                if !crate::hooks::HOOK_EVENTS.contains(&event.trim()) {
+                    anyhow::bail!(
+                        "hooks.custom[{i}].events contains unknown event '{event}'; expected one of: {}",
+                        crate::hooks::HOOK_EVENTS.join(", ")
+                    );
+                }
+            }
+            if hook.timeout_ms == 0 {
+                anyhow::bail!("hooks.custom[{i}].timeout_ms must be greater than 0");
+            }
+            let has =
+                |value: &Option<String>| value.as_deref().is_some_and(|v| !v.trim().is_empty());
+            match hook.kind {
+                CustomHookKind::Command if !has(&hook.command) => {
+                    anyhow::bail!("hooks.custom[{i}].command is required when kind = \"command\"");
+                }
+                CustomHookKind::Http => {
+                    let url = hook.url.as_deref().map(str::trim).unwrap_or_default();
+                    if !url.starts_with("http://") && !url.starts_with("https://") {
+                        anyhow::bail!("hooks.custom[{i}].url must be an http(s) URL");
+                    }
+                }
+                CustomHookKind::Wasm if !has(&hook.module) => {
+                    anyhow::bail!("hooks.custom[{i}].module is required when kind = \"wasm\"");
+                }
+                _ => {}
+            }
+        }
+
+        // Simulated peripherals
+        for (i, board) in self.peripherals.boards.iter().enumerate() {
+            let Some(sim) = &board.simulation else {
+                continue;
+            };
+            if board.transport != "simulated" {
+                anyhow::bail!(
+                    "peripherals.boards[{i}].simulation requires transport = \"simulated\""
+                );
+            }
+            let levels = sim
+                .pins
+                .iter()
+                .map(|pin| pin.value)
+                .chain(sim.script.iter().map(|step| step.value));
+            for value in levels {
+                if value > 1 {
+                    anyhow::bail!(
+                        "peripherals.boards[{i}].simulation pin values must be 0 or 1, got {value}"
+                    );
+                }
+            }
+            for (j, block) in sim.memory.iter().enumerate() {
+                let digits: String = block.hex.split_whitespace().collect();
+                if digits.is_empty()
+                    || !digits.len().is_multiple_of(2)
+                    || !digits.chars().all(|c| c.is_ascii_hexdigit())
+                {
+                    anyhow::bail!(
+                        "peripherals.boards[{i}].simulation.memory[{j}].hex must be an even number of hex digits"
+                    );
+                }
+            }
+        }
+
+        // Robot kit
+        if self.robot.enabled && self.robot.estop_poll_ms == 0 {
+            anyhow::bail!("robot.estop_poll_ms must be greater than 0");
+        }
+
         // Proxy (delegate to existing validation)
         self.proxy.validate()?;
 
@@ -5023,6 +5656,14 @@ impl Config {
             encrypt_optional_secret(&store, &mut agent.api_key, "config.agents.*.api_key")?;
         }
 
+        for backend in &mut config_to_save.web_search.custom {
+            encrypt_optional_secret(
+                &store,
+                &mut backend.api_key,
+                "config.web_search.custom.*.api_key",
+            )?;
+        }
+
         if let Some(ref mut ns) = config_to_save.channels_config.nostr {
             encrypt_secret(
                 &store,
@@ -5031,8 +5672,18 @@ impl Config {
             )?;
         }
 
-        let toml_str =
-            toml::to_string_pretty(&config_to_save).context("Failed to serialize config")?;
+        let toml_str = match self.layers.as_deref().filter(|l| l.is_layered()) {
+            // Only write back what changed so overlay values stay in their own files.
+            Some(config_layers) => {
+                let current = serde_json::to_value(self).context("Failed to serialize config")?;
+                let encrypted =
+                    toml::Table::try_from(&config_to_save).context("Failed to serialize config")?;
+                layers::render_patched_base(&self.config_path, config_layers, &current, &encrypted)?
+            }
+            None => {
+                toml::to_string_pretty(&config_to_save).context("Failed to serialize config")?
+            }
+        };
 
         let parent_dir = self
             .config_path
@@ -5416,6 +6067,8 @@ default_temperature = 0.7
                 target: Some("telegram".into()),
                 to: Some("123456".into()),
             },
+            goal_loop: GoalLoopConfig::default(),
+            sop: SopConfig::default(),
             cron: CronConfig::default(),
             channels_config: ChannelsConfig {
                 cli: true,
@@ -5464,10 +6117,12 @@ default_temperature = 0.7
             identity: IdentityConfig::default(),
             cost: CostConfig::default(),
             peripherals: PeripheralsConfig::default(),
+            robot: RobotConfig::default(),
             agents: HashMap::new(),
             hooks: HooksConfig::default(),
             hardware: HardwareConfig::default(),
             transcription: TranscriptionConfig::default(),
+            layers: None,
         };
 
         let toml_str = toml::to_string_pretty(&config).unwrap();
@@ -5579,6 +6234,10 @@ max_tool_iterations = 20
 max_history_messages = 80
 parallel_tools = true
 tool_dispatcher = "xml"
+
+[agent.tool_modes]
+"ollama/gemma2" = "xml"
+"gpt-4o" = "native"
 "#;
         let parsed: Config = toml::from_str(raw).unwrap();
         assert!(parsed.agent.compact_context);
@@ -5586,6 +6245,14 @@ tool_dispatcher = "xml"
         assert_eq!(parsed.agent.max_history_messages, 80);
         assert!(parsed.agent.parallel_tools);
         assert_eq!(parsed.agent.tool_dispatcher, "xml");
+        assert_eq!(
+            parsed.agent.tool_modes.get("ollama/gemma2"),
+            Some(&ToolMode::Xml)
+        );
+        assert_eq!(
+            parsed.agent.tool_modes.get("gpt-4o"),
+            Some(&ToolMode::Native)
+        );
     }
 
     #[tokio::test]
@@ -5628,6 +6295,8 @@ tool_dispatcher = "xml"
             embedding_routes: Vec::new(),
             query_classification: QueryClassificationConfig::default(),
             heartbeat: HeartbeatConfig::default(),
+            goal_loop: GoalLoopConfig::default(),
+            sop: SopConfig::default(),
             cron: CronConfig::default(),
             channels_config: ChannelsConfig::default(),
             memory: MemoryConfig::default(),
@@ -5646,10 +6315,12 @@ tool_dispatcher = "xml"
             identity: IdentityConfig::default(),
             cost: CostConfig::default(),
             peripherals: PeripheralsConfig::default(),
+            robot: RobotConfig::default(),
             agents: HashMap::new(),
             hooks: HooksConfig::default(),
             hardware: HardwareConfig::default(),
             transcription: TranscriptionConfig::default(),
+            layers: None,
         };
 
         config.save().await.unwrap();
@@ -6875,6 +7546,61 @@ requires_openai_auth = true
         assert!(result.is_ok(), "expected validation to pass: {result:?}");
     }
 
+    #[test]
+    async fn validate_rejects_route_max_tokens_above_catalog_output_limit() {
+        let tmp = TempDir::new().unwrap();
+        let route = |model: &str, max_tokens: u32| ModelRouteConfig {
+            hint: "fast".into(),
+            provider: "openai".into(),
+            model: model.into(),
+            api_key: None,
+            generation: GenerationParams {
+                max_tokens: Some(max_tokens),
+                ..GenerationParams::default()
+            },
+        };
+        let mut config = Config {
+            workspace_dir: tmp.path().to_path_buf(),
+            model_routes: vec![route("gpt-4o-mini", 100_000)],
+            ..Config::default()
+        };
+
+        let error = config.validate().expect_err("expected validation failure");
+        assert!(error
+            .to_string()
+            .contains("model_routes[0].max_tokens (100000) exceeds the 16384-token output limit"));
+
+        // Models missing from the catalog are not second-guessed.
+        config.model_routes = vec![route("gpt-4o-mini", 4_096), route("my-local", 100_000)];
+        assert!(config.validate().is_ok());
+    }
+
+    #[test]
+    async fn reliability_racing_parses_and_validates_racers() {
+        let raw = r#"
+[racing]
+channels = ["clawdtalk", "telnyx"]
+
+[[racing.providers]]
+provider = "groq"
+model = "llama-3.3-70b-versatile"
+"#;
+        let reliability: ReliabilityConfig = toml::from_str(raw).unwrap();
+        assert_eq!(reliability.racing.hedge_delay_ms, 400);
+        assert!(reliability.racing.applies_to("telnyx"));
+        assert!(!reliability.racing.applies_to("telegram"));
+
+        let mut config = Config {
+            reliability,
+            ..Config::default()
+        };
+        config.reliability.racing.providers[0].model = " ".into();
+        let error = config.validate().expect_err("expected validation failure");
+        assert!(error
+            .to_string()
+            .contains("reliability.racing.providers[0].model must not be empty"));
+    }
+
     #[test]
     async fn validate_rejects_unknown_model_provider_wire_api() {
         let _env_guard = env_override_lock().await;
@@ -6898,6 +7624,123 @@ requires_openai_auth = true
             .contains("wire_api must be one of: responses, chat_completions"));
     }
 
+    #[test]
+    async fn custom_hooks_parse_from_toml() {
+        let raw = r#"
+enabled = true
+
+[[custom]]
+name = "redact"
+events = ["on_message_sending", "before_tool_call"]
+kind = "command"
+command = "/usr/local/bin/redact"
+on_failure = "closed"
+priority = 20
+
+[[custom]]
+name = "audit"
+events = ["on_after_tool_call"]
+kind = "http"
+url = "https://policy.example.com/hook"
+timeout_ms = 500
+"#;
+        let hooks: HooksConfig = toml::from_str(raw).unwrap();
+        assert_eq!(hooks.custom.len(), 2);
+        assert_eq!(hooks.custom[0].kind, CustomHookKind::Command);
+        assert_eq!(hooks.custom[0].on_failure, HookFailurePolicy::Closed);
+        assert_eq!(hooks.custom[0].priority, 20);
+        assert_eq!(hooks.custom[0].timeout_ms, 5_000);
+        assert_eq!(hooks.custom[1].on_failure, HookFailurePolicy::Open);
+        assert_eq!(hooks.custom[1].timeout_ms, 500);
+    }
+
+    #[test]
+    async fn validate_rejects_invalid_custom_hooks() {
+        let _env_guard = env_override_lock().await;
+        let hook = CustomHookConfig {
+            name: "policy".into(),
+            events: vec!["before_tool_call".into()],
+            kind: CustomHookKind::Command,
+            command: Some("/bin/policy".into()),
+            args: Vec::new(),
+            url: None,
+            headers: HashMap::new(),
+            module: None,
+            timeout_ms: 1_000,
+            on_failure: HookFailurePolicy::Closed,
+            priority: 0,
+        };
+        let with_hook = |hook: CustomHookConfig| {
+            let mut config = Config::default();
+            config.hooks.custom = vec![hook];
+            config.validate()
+        };
+
+        assert!(with_hook(hook.clone()).is_ok());
+
+        let mut unknown_event = hook.clone();
+        unknown_event.events = vec!["before_lunch".into()];
+        let err = with_hook(unknown_event).unwrap_err().to_string();
+        assert!(err.contains("unknown event 'before_lunch'"), "{err}");
+
+        let mut missing_command = hook.clone();
+        missing_command.command = None;
+        let err = with_hook(missing_command).unwrap_err().to_string();
+        assert!(err.contains("hooks.custom[0].command is required"), "{err}");
+
+        let mut bad_url = hook.clone();
+        bad_url.kind = CustomHookKind::Http;
+        bad_url.url = Some("ftp://example.com".into());
+        let err = with_hook(bad_url).unwrap_err().to_string();
+        assert!(err.contains("hooks.custom[0].url"), "{err}");
+
+        let mut zero_timeout = hook;
+        zero_timeout.timeout_ms = 0;
+        let err = with_hook(zero_timeout).unwrap_err().to_string();
+        assert!(err.contains("timeout_ms"), "{err}");
+    }
+
+    #[test]
+    async fn simulated_peripheral_parses_and_validates() {
+        let _env_guard = env_override_lock().await;
+        let raw = r#"
+enabled = true
+
+[[boards]]
+board = "simulated"
+transport = "simulated"
+
+[boards.simulation]
+pins = [{ pin = 13, value = 1 }]
+script = [{ after_ms = 250, pin = 5, value = 1 }]
+memory = [{ address = 0x2000_0000, hex = "DEADBEEF" }]
+faults = { fail_commands = ["gpio_write"], latency_ms = 10 }
+"#;
+        let peripherals: PeripheralsConfig = toml::from_str(raw).unwrap();
+        let sim = peripherals.boards[0].simulation.clone().unwrap();
+        assert!(!sim.pty);
+        assert_eq!(sim.memory[0].address, 0x2000_0000);
+        assert_eq!(sim.script[0].after_ms, 250);
+        assert_eq!(sim.faults.fail_commands, ["gpio_write"]);
+
+        let mut config = Config::default();
+        config.peripherals = peripherals;
+        assert!(config.validate().is_ok());
+
+        config.peripherals.boards[0]
+            .simulation
+            .as_mut()
+            .unwrap()
+            .memory[0]
+            .hex = "ABC".into();
+        let err = config.validate().unwrap_err().to_string();
+        assert!(err.contains("simulation.memory[0].hex"), "{err}");
+
+        config.peripherals.boards[0].transport = "serial".into();
+        let err = config.validate().unwrap_err().to_string();
+        assert!(err.contains("requires transport = \"simulated\""), "{err}");
+    }
+
     #[test]
     async fn env_override_model_fallback() {
         let _env_guard = env_override_lock().await;
@@ -7602,6 +8445,7 @@ default_model = "legacy-model"
                 transport: "serial".into(),
                 path: Some("/dev/ttyACM0".into()),
                 baud: 115_200,
+                simulation: None,
             }],
             datasheet_dir: None,
         };
@@ -1,8 +1,12 @@
+pub mod recorder;
 pub mod tracker;
 pub mod types;
 
 // Re-exported for potential external use (public API)
+pub use recorder::UsageRecorder;
 #[allow(unused_imports)]
 pub use tracker::CostTracker;
 #[allow(unused_imports)]
-pub use types::{BudgetCheck, CostRecord, CostSummary, ModelStats, TokenUsage, UsagePeriod};
+pub use types::{
+    BudgetCheck, CostRecord, CostSummary, ModelPrices, ModelStats, TokenUsage, UsagePeriod,
+};
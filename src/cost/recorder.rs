@@ -0,0 +1,178 @@
+use super::tracker::CostTracker;
+use super::types::{ModelPrices, TokenUsage};
+use crate::config::schema::{Config, ModelPricing};
+use crate::providers::catalog::ModelCatalog;
+use crate::providers::traits::TokenUsage as ReportedUsage;
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::Arc;
+
+/// Prices provider-reported usage and records it with a cost tracker.
+///
+/// Prices come from `[cost.prices]` and then the model catalog. Cache
+/// reads and writes use the catalog's cache prices; models without them
+/// bill cached tokens at the input price.
+pub struct UsageRecorder {
+    tracker: Arc<CostTracker>,
+    prices: HashMap<String, ModelPricing>,
+    catalog: ModelCatalog,
+}
+
+impl UsageRecorder {
+    pub fn new(
+        tracker: Arc<CostTracker>,
+        prices: HashMap<String, ModelPricing>,
+        workspace_dir: &Path,
+    ) -> Self {
+        Self {
+            tracker,
+            prices,
+            catalog: ModelCatalog::load(workspace_dir),
+        }
+    }
+
+    /// Recorder for `config`, or `None` when cost tracking is disabled or
+    /// the cost store cannot be opened.
+    pub fn from_config(config: &Config) -> Option<Self> {
+        if !config.cost.enabled {
+            return None;
+        }
+        match CostTracker::new(config.cost.clone(), &config.workspace_dir) {
+            Ok(tracker) => Some(Self::new(
+                Arc::new(tracker),
+                config.cost.prices.clone(),
+                &config.workspace_dir,
+            )),
+            Err(e) => {
+                tracing::warn!("Failed to initialize cost tracker: {e}");
+                None
+            }
+        }
+    }
+
+    pub fn tracker(&self) -> &CostTracker {
+        &self.tracker
+    }
+
+    /// Prices for `model` served by `provider`.
+    pub fn prices(&self, provider: &str, model: &str) -> ModelPrices {
+        let info = self.catalog.find(provider, model);
+        let (input, output) = self
+            .prices
+            .get(&format!("{provider}/{model}"))
+            .or_else(|| self.prices.get(model))
+            .map(|p| (p.input, p.output))
+            .or_else(|| info?.pricing())
+            .unwrap_or((0.0, 0.0));
+        ModelPrices {
+            input,
+            output,
+            cache_read: info.and_then(|i| i.cache_read_price).unwrap_or(input),
+            cache_write: info.and_then(|i| i.cache_write_price).unwrap_or(input),
+        }
+    }
+
+    /// Cost of one call, keyed `provider/model`.
+    pub fn price(&self, provider: &str, model: &str, usage: &ReportedUsage) -> TokenUsage {
+        let prices = self.prices(provider, model);
+        TokenUsage::new(
+            format!("{provider}/{model}"),
+            usage.input_tokens.unwrap_or(0),
+            usage.output_tokens.unwrap_or(0),
+            prices.input,
+            prices.output,
+        )
+        .with_cache_tokens(
+            usage.cache_read_tokens.unwrap_or(0),
+            usage.cache_write_tokens.unwrap_or(0),
+            &prices,
+        )
+    }
+
+    /// Price and record one call. Failures are logged, not returned, so
+    /// cost tracking never fails a turn.
+    pub fn record(&self, provider: &str, model: &str, usage: &ReportedUsage) {
+        if let Err(e) = self
+            .tracker
+            .record_usage(self.price(provider, model, usage))
+        {
+            tracing::warn!(provider, model, "Failed to record usage cost: {e}");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::schema::CostConfig;
+    use tempfile::TempDir;
+
+    fn recorder(tmp: &TempDir, prices: HashMap<String, ModelPricing>) -> UsageRecorder {
+        let config = CostConfig {
+            enabled: true,
+            ..CostConfig::default()
+        };
+        let tracker = Arc::new(CostTracker::new(config, tmp.path()).unwrap());
+        UsageRecorder::new(tracker, prices, tmp.path())
+    }
+
+    fn usage(input: u64, cache_read: u64, cache_write: u64) -> ReportedUsage {
+        ReportedUsage {
+            input_tokens: Some(input),
+            output_tokens: Some(0),
+            cache_read_tokens: Some(cache_read),
+            cache_write_tokens: Some(cache_write),
+        }
+    }
+
+    #[test]
+    fn anthropic_cache_tokens_use_catalog_cache_prices() {
+        let tmp = TempDir::new().unwrap();
+        let recorder = recorder(&tmp, HashMap::new());
+
+        let cost = recorder.price(
+            "anthropic",
+            "claude-sonnet-4-20250514",
+            &usage(0, 1_000_000, 1_000_000),
+        );
+
+        // Catalog: reads $0.30, writes $3.75 per 1M; reads save $2.70 over input.
+        assert!((cost.cost_usd - 4.05).abs() < 1e-9);
+        assert!((cost.cache_savings_usd - 2.7).abs() < 1e-9);
+    }
+
+    #[test]
+    fn configured_price_without_cache_prices_bills_cache_at_input_price() {
+        let tmp = TempDir::new().unwrap();
+        let prices = HashMap::from([(
+            "custom/model".to_string(),
+            ModelPricing {
+                input: 2.0,
+                output: 4.0,
+            },
+        )]);
+        let recorder = recorder(&tmp, prices);
+
+        let cost = recorder.price("custom", "model", &usage(1_000_000, 1_000_000, 0));
+
+        assert!((cost.cost_usd - 4.0).abs() < 1e-9);
+        assert!(cost.cache_savings_usd.abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn record_adds_to_session_summary() {
+        let tmp = TempDir::new().unwrap();
+        let recorder = recorder(&tmp, HashMap::new());
+
+        recorder.record(
+            "anthropic",
+            "claude-sonnet-4-20250514",
+            &usage(100, 2000, 0),
+        );
+
+        let summary = recorder.tracker().get_summary().unwrap();
+        assert_eq!(summary.request_count, 1);
+        assert_eq!(summary.cache_read_tokens, 2000);
+        assert!(summary.cache_savings_usd > 0.0);
+    }
+}
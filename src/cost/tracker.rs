@@ -149,6 +149,18 @@ impl CostTracker {
             .iter()
             .map(|record| record.usage.total_tokens)
             .sum();
+        let cache_read_tokens: u64 = session_costs
+            .iter()
+            .map(|record| record.usage.cache_read_tokens)
+            .sum();
+        let cache_write_tokens: u64 = session_costs
+            .iter()
+            .map(|record| record.usage.cache_write_tokens)
+            .sum();
+        let cache_savings_usd: f64 = session_costs
+            .iter()
+            .map(|record| record.usage.cache_savings_usd)
+            .sum();
         let request_count = session_costs.len();
         let by_model = build_session_model_stats(&session_costs);
 
@@ -157,6 +169,9 @@ impl CostTracker {
             daily_cost_usd: daily_cost,
             monthly_cost_usd: monthly_cost,
             total_tokens,
+            cache_read_tokens,
+            cache_write_tokens,
+            cache_savings_usd,
             request_count,
             by_model,
         })
@@ -214,11 +229,15 @@ fn build_session_model_stats(session_costs: &[CostRecord]) -> HashMap<String, Mo
                 model: record.usage.model.clone(),
                 cost_usd: 0.0,
                 total_tokens: 0,
+                cache_read_tokens: 0,
+                cache_write_tokens: 0,
                 request_count: 0,
             });
 
         entry.cost_usd += record.usage.cost_usd;
         entry.total_tokens += record.usage.total_tokens;
+        entry.cache_read_tokens += record.usage.cache_read_tokens;
+        entry.cache_write_tokens += record.usage.cache_write_tokens;
         entry.request_count += 1;
     }
 
@@ -404,6 +423,7 @@ impl CostStorage {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::cost::types::ModelPrices;
     use tempfile::TempDir;
 
     fn enabled_config() -> CostConfig {
@@ -447,6 +467,32 @@ mod tests {
         assert_eq!(summary.by_model.len(), 1);
     }
 
+    #[test]
+    fn summary_reports_prompt_cache_tokens_and_savings() {
+        let tmp = TempDir::new().unwrap();
+        let tracker = CostTracker::new(enabled_config(), tmp.path()).unwrap();
+
+        let prices = ModelPrices {
+            input: 3.0,
+            output: 15.0,
+            cache_read: 0.3,
+            cache_write: 3.75,
+        };
+        for _ in 0..2 {
+            let usage = TokenUsage::new("cached/model", 100, 50, 3.0, 15.0)
+                .with_cache_tokens(1000, 200, &prices);
+            tracker.record_usage(usage).unwrap();
+        }
+
+        let summary = tracker.get_summary().unwrap();
+        assert_eq!(summary.cache_read_tokens, 2000);
+        assert_eq!(summary.cache_write_tokens, 400);
+        assert!(summary.cache_savings_usd > 0.0);
+        let stats = &summary.by_model["cached/model"];
+        assert_eq!(stats.cache_read_tokens, 2000);
+        assert_eq!(stats.cache_write_tokens, 400);
+    }
+
     #[test]
     fn budget_exceeded_daily_limit() {
         let tmp = TempDir::new().unwrap();
@@ -11,12 +11,38 @@ pub struct TokenUsage {
     pub output_tokens: u64,
     /// Total tokens
     pub total_tokens: u64,
+    /// Input tokens served from the prompt cache
+    #[serde(default)]
+    pub cache_read_tokens: u64,
+    /// Input tokens written to the prompt cache
+    #[serde(default)]
+    pub cache_write_tokens: u64,
     /// Calculated cost in USD
     pub cost_usd: f64,
+    /// Cost avoided by reading from the prompt cache, in USD
+    #[serde(default)]
+    pub cache_savings_usd: f64,
+    /// Billed through a provider batch API at the batch discount
+    #[serde(default)]
+    pub batch: bool,
     /// Timestamp of the request
     pub timestamp: chrono::DateTime<chrono::Utc>,
 }
 
+/// Prices for one model (USD per 1M tokens).
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct ModelPrices {
+    pub input: f64,
+    pub output: f64,
+    /// Input tokens read from the prompt cache
+    pub cache_read: f64,
+    /// Input tokens written to the prompt cache
+    pub cache_write: f64,
+}
+
+/// Price of batch API tokens relative to interactive requests.
+const BATCH_PRICE_FACTOR: f64 = 0.5;
+
 impl TokenUsage {
     fn sanitize_price(value: f64) -> f64 {
         if value.is_finite() && value > 0.0 {
@@ -49,11 +75,51 @@ impl TokenUsage {
             input_tokens,
             output_tokens,
             total_tokens,
+            cache_read_tokens: 0,
+            cache_write_tokens: 0,
             cost_usd,
+            cache_savings_usd: 0.0,
+            batch: false,
             timestamp: chrono::Utc::now(),
         }
     }
 
+    /// Add prompt-cache tokens reported separately from `input_tokens`.
+    ///
+    /// Reads and writes are billed at the model's cache prices; what the
+    /// reads would have cost at the input price beyond that is recorded as
+    /// savings.
+    pub fn with_cache_tokens(
+        mut self,
+        cache_read_tokens: u64,
+        cache_write_tokens: u64,
+        prices: &ModelPrices,
+    ) -> Self {
+        let cost = |tokens: u64, price_per_million: f64| {
+            (tokens as f64 / 1_000_000.0) * Self::sanitize_price(price_per_million)
+        };
+        let read_cost = cost(cache_read_tokens, prices.cache_read);
+        let write_cost = cost(cache_write_tokens, prices.cache_write);
+
+        self.cache_read_tokens = cache_read_tokens;
+        self.cache_write_tokens = cache_write_tokens;
+        self.total_tokens = self
+            .total_tokens
+            .saturating_add(cache_read_tokens)
+            .saturating_add(cache_write_tokens);
+        self.cost_usd += read_cost + write_cost;
+        self.cache_savings_usd = (cost(cache_read_tokens, prices.input) - read_cost).max(0.0);
+        self
+    }
+
+    /// Apply the batch API discount to the cost computed so far.
+    pub fn with_batch_discount(mut self) -> Self {
+        self.cost_usd *= BATCH_PRICE_FACTOR;
+        self.cache_savings_usd *= BATCH_PRICE_FACTOR;
+        self.batch = true;
+        self
+    }
+
     /// Get the total cost.
     pub fn cost(&self) -> f64 {
         self.cost_usd
@@ -120,6 +186,15 @@ pub struct CostSummary {
     pub monthly_cost_usd: f64,
     /// Total tokens used
     pub total_tokens: u64,
+    /// Session input tokens served from the prompt cache
+    #[serde(default)]
+    pub cache_read_tokens: u64,
+    /// Session input tokens written to the prompt cache
+    #[serde(default)]
+    pub cache_write_tokens: u64,
+    /// Session cost avoided by prompt-cache reads
+    #[serde(default)]
+    pub cache_savings_usd: f64,
     /// Number of requests
     pub request_count: usize,
     /// Breakdown by model
@@ -135,6 +210,12 @@ pub struct ModelStats {
     pub cost_usd: f64,
     /// Total tokens for this model
     pub total_tokens: u64,
+    /// Prompt-cache reads for this model
+    #[serde(default)]
+    pub cache_read_tokens: u64,
+    /// Prompt-cache writes for this model
+    #[serde(default)]
+    pub cache_write_tokens: u64,
     /// Number of requests for this model
     pub request_count: usize,
 }
@@ -146,6 +227,9 @@ impl Default for CostSummary {
             daily_cost_usd: 0.0,
             monthly_cost_usd: 0.0,
             total_tokens: 0,
+            cache_read_tokens: 0,
+            cache_write_tokens: 0,
+            cache_savings_usd: 0.0,
             request_count: 0,
             by_model: std::collections::HashMap::new(),
         }
@@ -181,6 +265,59 @@ mod tests {
         assert_eq!(usage.total_tokens, 2000);
     }
 
+    #[test]
+    fn token_usage_prices_cache_reads_and_writes() {
+        let prices = ModelPrices {
+            input: 3.0,
+            output: 15.0,
+            cache_read: 0.3,
+            cache_write: 3.75,
+        };
+        let usage = TokenUsage::new("test/model", 1000, 500, 3.0, 15.0)
+            .with_cache_tokens(100_000, 10_000, &prices);
+
+        // Base 0.0105 + reads (100k/1M)*0.3 = 0.03 + writes (10k/1M)*3.75 = 0.0375
+        assert!((usage.cost_usd - 0.078).abs() < 0.0001);
+        // Reads would have cost 0.3 at full price
+        assert!((usage.cache_savings_usd - 0.27).abs() < 0.0001);
+        assert_eq!(usage.cache_read_tokens, 100_000);
+        assert_eq!(usage.cache_write_tokens, 10_000);
+        assert_eq!(usage.total_tokens, 111_500);
+    }
+
+    #[test]
+    fn token_usage_cache_tokens_at_input_price_save_nothing() {
+        let prices = ModelPrices {
+            input: 2.0,
+            output: 8.0,
+            cache_read: 2.0,
+            cache_write: 2.0,
+        };
+        let usage = TokenUsage::new("test/model", 0, 0, 2.0, 8.0)
+            .with_cache_tokens(500_000, 500_000, &prices);
+
+        assert!((usage.cost_usd - 2.0).abs() < 0.0001);
+        assert!(usage.cache_savings_usd.abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn token_usage_batch_discount_halves_cost() {
+        let usage = TokenUsage::new("test/model", 1000, 500, 3.0, 15.0).with_batch_discount();
+
+        assert!((usage.cost_usd - 0.00525).abs() < 0.00001);
+        assert!(usage.batch);
+        assert_eq!(usage.total_tokens, 1500);
+    }
+
+    #[test]
+    fn token_usage_without_cache_fields_deserializes() {
+        let json = r#"{"model":"m","input_tokens":1,"output_tokens":2,"total_tokens":3,"cost_usd":0.5,"timestamp":"2026-01-01T00:00:00Z"}"#;
+        let usage: TokenUsage = serde_json::from_str(json).unwrap();
+        assert_eq!(usage.cache_read_tokens, 0);
+        assert!(usage.cache_savings_usd.abs() < f64::EPSILON);
+        assert!(!usage.batch);
+    }
+
     #[test]
     fn cost_record_creation() {
         let usage = TokenUsage::new("test/model", 100, 50, 1.0, 2.0);
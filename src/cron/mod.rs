@@ -15,10 +15,13 @@ pub use schedule::{
 };
 #[allow(unused_imports)]
 pub use store::{
-    add_agent_job, add_job, add_shell_job, due_jobs, get_job, list_jobs, list_runs,
-    record_last_run, record_run, remove_job, reschedule_after_run, update_job,
+    add_agent_job, add_job, add_shell_job, clear_pending_batch, due_jobs, get_job, list_jobs,
+    list_runs, pending_batches, record_last_run, record_pending_batch, record_run, remove_job,
+    reschedule_after_run, update_job,
+};
+pub use types::{
+    CronJob, CronJobPatch, CronRun, DeliveryConfig, JobType, PendingBatch, Schedule, SessionTarget,
 };
-pub use types::{CronJob, CronJobPatch, CronRun, DeliveryConfig, JobType, Schedule, SessionTarget};
 
 #[allow(clippy::needless_pass_by_value)]
 pub fn handle_command(command: crate::CronCommands, config: &Config) -> Result<()> {
@@ -3,9 +3,13 @@ use crate::channels::{
 };
 use crate::config::Config;
 use crate::cron::{
-    due_jobs, next_run_for_schedule, record_last_run, record_run, remove_job, reschedule_after_run,
-    update_job, CronJob, CronJobPatch, DeliveryConfig, JobType, Schedule, SessionTarget,
+    clear_pending_batch, due_jobs, get_job, next_run_for_schedule, pending_batches,
+    record_last_run, record_pending_batch, record_run, remove_job, reschedule_after_run,
+    update_job, CronJob, CronJobPatch, DeliveryConfig, JobType, PendingBatch, Schedule,
+    SessionTarget,
 };
+use crate::observability::{Observer, ObserverEvent};
+use crate::providers::{self, BatchRequest, BatchStatus, ChatMessage, ChatRequest, ChatResponse};
 use crate::security::SecurityPolicy;
 use anyhow::Result;
 use chrono::{DateTime, Utc};
@@ -27,6 +31,8 @@ pub async fn run(config: Config) -> Result<()> {
         &config.autonomy,
         &config.workspace_dir,
     ));
+    let observer: Arc<dyn Observer> =
+        Arc::from(crate::observability::create_observer(&config.observability));
 
     crate::health::mark_component_ok(SCHEDULER_COMPONENT);
 
@@ -35,6 +41,10 @@ pub async fn run(config: Config) -> Result<()> {
         // Keep scheduler liveness fresh even when there are no due jobs.
         crate::health::mark_component_ok(SCHEDULER_COMPONENT);
 
+        // Collect batches submitted on earlier ticks, including ones left
+        // pending by a previous daemon run.
+        poll_pending_batches(&config, observer.as_ref()).await;
+
         let jobs = match due_jobs(&config, Utc::now()) {
             Ok(jobs) => jobs,
             Err(e) => {
@@ -44,7 +54,7 @@ pub async fn run(config: Config) -> Result<()> {
             }
         };
 
-        process_due_jobs(&config, &security, jobs, SCHEDULER_COMPONENT).await;
+        process_due_jobs(&config, &security, &observer, jobs, SCHEDULER_COMPONENT).await;
     }
 }
 
@@ -65,7 +75,7 @@ async fn execute_job_with_retry(
     for attempt in 0..=retries {
         let (success, output) = match job.job_type {
             JobType::Shell => run_job_command(config, security, job).await,
-            JobType::Agent => run_agent_job(config, security, job).await,
+            JobType::Agent => Box::pin(run_agent_job(config, security, job)).await,
         };
         last_output = output;
 
@@ -91,6 +101,7 @@ async fn execute_job_with_retry(
 async fn process_due_jobs(
     config: &Config,
     security: &Arc<SecurityPolicy>,
+    observer: &Arc<dyn Observer>,
     jobs: Vec<CronJob>,
     component: &str,
 ) {
@@ -98,18 +109,23 @@ async fn process_due_jobs(
     crate::health::mark_component_ok(component);
 
     let max_concurrent = config.scheduler.max_concurrent.max(1);
-    let mut in_flight =
-        stream::iter(
-            jobs.into_iter().map(|job| {
-                let config = config.clone();
-                let security = Arc::clone(security);
-                let component = component.to_owned();
-                async move {
-                    execute_and_persist_job(&config, security.as_ref(), &job, &component).await
-                }
-            }),
-        )
-        .buffer_unordered(max_concurrent);
+    let mut in_flight = stream::iter(jobs.into_iter().map(|job| {
+        let config = config.clone();
+        let security = Arc::clone(security);
+        let observer = Arc::clone(observer);
+        let component = component.to_owned();
+        async move {
+            Box::pin(execute_and_persist_job(
+                &config,
+                security.as_ref(),
+                observer.as_ref(),
+                &job,
+                &component,
+            ))
+            .await
+        }
+    }))
+    .buffer_unordered(max_concurrent);
 
     while let Some((job_id, success, output)) = in_flight.next().await {
         if !success {
@@ -121,75 +137,282 @@ async fn process_due_jobs(
 async fn execute_and_persist_job(
     config: &Config,
     security: &SecurityPolicy,
+    observer: &dyn Observer,
     job: &CronJob,
     component: &str,
 ) -> (String, bool, String) {
     crate::health::mark_component_ok(component);
     warn_if_high_frequency_agent_job(job);
 
+    if is_batch_job(job) {
+        return submit_batch_job(config, security, job).await;
+    }
+
     let started_at = Utc::now();
     let (success, output) = execute_job_with_retry(config, security, job).await;
     let finished_at = Utc::now();
     let success = persist_job_result(config, job, success, &output, started_at, finished_at).await;
+    observer.record_event(&ObserverEvent::CronJobRun {
+        job_id: job.id.clone(),
+        success,
+        duration: (finished_at - started_at).to_std().unwrap_or_default(),
+    });
 
     (job.id.clone(), success, output)
 }
 
+/// Config for an agent job run, with the job's generation overrides layered
+/// over `[agent] generation`.
+fn agent_job_config(config: &Config, job: &CronJob) -> Config {
+    let mut run_config = config.clone();
+    if let Some(generation) = &job.generation {
+        run_config.agent.generation = generation.with_defaults(&config.agent.generation);
+    }
+    run_config
+}
+
 async fn run_agent_job(
     config: &Config,
     security: &SecurityPolicy,
     job: &CronJob,
 ) -> (bool, String) {
-    if !security.can_act() {
-        return (
-            false,
-            "blocked by security policy: autonomy is read-only".to_string(),
-        );
-    }
-
-    if security.is_rate_limited() {
-        return (
-            false,
-            "blocked by security policy: rate limit exceeded".to_string(),
-        );
+    if let Err(blocked) = check_agent_policy(security) {
+        return (false, blocked);
     }
-
-    if !security.record_action() {
-        return (
-            false,
-            "blocked by security policy: action budget exhausted".to_string(),
-        );
-    }
-    let name = job.name.clone().unwrap_or_else(|| "cron-job".to_string());
     let prompt = job.prompt.clone().unwrap_or_default();
-    let prefixed_prompt = format!("[cron:{} {name}] {prompt}", job.id);
+    let prefixed_prompt = agent_job_prompt(job);
     let model_override = job.model.clone();
 
     let run_result = match job.session_target {
         SessionTarget::Main | SessionTarget::Isolated => {
-            crate::agent::run(
-                config.clone(),
+            Box::pin(crate::agent::run(
+                agent_job_config(config, job),
                 Some(prefixed_prompt),
                 None,
                 model_override,
                 config.default_temperature,
                 vec![],
                 false,
-            )
+            ))
             .await
         }
     };
 
-    match run_result {
-        Ok(response) => (
-            true,
-            if response.trim().is_empty() {
-                "agent job executed".to_string()
-            } else {
-                response
-            },
-        ),
-        Err(e) => (false, format!("agent job failed: {e}")),
+    let response = match run_result {
+        Ok(response) if response.trim().is_empty() => {
+            return (true, "agent job executed".to_string());
+        }
+        Ok(response) => response,
+        Err(e) => return (false, format!("agent job failed: {e}")),
+    };
+
+    // The agent finished; ask for a structured verdict instead of assuming
+    // that any reply means success.
+    match crate::agent::outcome::assess(config, job.model.as_deref(), &prompt, &response).await {
+        Ok(outcome) if !outcome.succeeded() => {
+            tracing::warn!(job_id = %job.id, "Cron agent job assessed as failed: {}", outcome.summary);
+            (false, response)
+        }
+        Ok(_) => (true, response),
+        Err(e) => {
+            tracing::warn!(job_id = %job.id, "Cron agent job assessment unavailable: {e}");
+            (true, response)
+        }
+    }
+}
+
+/// Gate an agent run on the autonomy level and action budget.
+fn check_agent_policy(security: &SecurityPolicy) -> std::result::Result<(), String> {
+    if !security.can_act() {
+        return Err("blocked by security policy: autonomy is read-only".to_string());
+    }
+    if security.is_rate_limited() {
+        return Err("blocked by security policy: rate limit exceeded".to_string());
+    }
+    if !security.record_action() {
+        return Err("blocked by security policy: action budget exhausted".to_string());
+    }
+    Ok(())
+}
+
+fn agent_job_prompt(job: &CronJob) -> String {
+    let name = job.name.as_deref().unwrap_or("cron-job");
+    let prompt = job.prompt.as_deref().unwrap_or_default();
+    format!("[cron:{} {name}] {prompt}", job.id)
+}
+
+fn is_batch_job(job: &CronJob) -> bool {
+    job.batch && matches!(job.job_type, JobType::Agent)
+}
+
+/// Submit a batch job's prompt as a single tool-free request. The run is
+/// recorded once a later tick finds the batch ended.
+async fn submit_batch_job(
+    config: &Config,
+    security: &SecurityPolicy,
+    job: &CronJob,
+) -> (String, bool, String) {
+    match pending_batches(config) {
+        Ok(pending) if pending.iter().any(|batch| batch.job_id == job.id) => {
+            tracing::debug!(job_id = %job.id, "Cron batch still pending; skipping submission");
+            return (job.id.clone(), true, "batch pending".to_string());
+        }
+        Ok(_) => {}
+        Err(e) => return (job.id.clone(), false, format!("batch lookup failed: {e}")),
+    }
+
+    let started_at = Utc::now();
+    match send_batch(config, security, job).await {
+        Ok(pending) => {
+            let output = format!("batch {} submitted", pending.handle.id);
+            if let Err(e) = record_pending_batch(config, &pending) {
+                let output = format!(
+                    "batch {} submitted but not recorded: {e}",
+                    pending.handle.id
+                );
+                let success =
+                    persist_job_result(config, job, false, &output, started_at, Utc::now()).await;
+                return (job.id.clone(), success, output);
+            }
+            if let Err(e) = reschedule_after_run(config, job, true, &output) {
+                tracing::warn!("Failed to persist scheduler run result: {e}");
+            }
+            (job.id.clone(), true, output)
+        }
+        Err(output) => {
+            let success =
+                persist_job_result(config, job, false, &output, started_at, Utc::now()).await;
+            (job.id.clone(), success, output)
+        }
+    }
+}
+
+async fn send_batch(
+    config: &Config,
+    security: &SecurityPolicy,
+    job: &CronJob,
+) -> std::result::Result<PendingBatch, String> {
+    check_agent_policy(security)?;
+    let provider_name = config.default_provider.as_deref().unwrap_or("openrouter");
+    let model = job
+        .model
+        .as_deref()
+        .or(config.default_model.as_deref())
+        .ok_or_else(|| "batch job failed: no model configured".to_string())?;
+    let provider = providers::create_batch_provider(
+        provider_name,
+        config.api_key.as_deref(),
+        config.api_url.as_deref(),
+    )
+    .map_err(|e| format!("batch job failed: {e}"))?;
+
+    let generation = job.generation.as_ref().map_or_else(
+        || config.agent.generation.clone(),
+        |generation| generation.with_defaults(&config.agent.generation),
+    );
+    let messages = [ChatMessage::user(agent_job_prompt(job))];
+    let requests = [BatchRequest {
+        custom_id: &job.id,
+        request: ChatRequest {
+            messages: &messages,
+            tools: None,
+            response_format: None,
+            params: generation.non_empty(),
+        },
+    }];
+    let handle = provider
+        .submit_batch(&requests, model, config.default_temperature)
+        .await
+        .map_err(|e| format!("batch job failed: {e}"))?;
+
+    Ok(PendingBatch {
+        job_id: job.id.clone(),
+        provider: provider_name.to_string(),
+        handle,
+    })
+}
+
+/// Poll submitted batches and record the runs of those that have ended.
+async fn poll_pending_batches(config: &Config, observer: &dyn Observer) {
+    let batches = match pending_batches(config) {
+        Ok(batches) => batches,
+        Err(e) => {
+            tracing::warn!("Cron batch query failed: {e}");
+            return;
+        }
+    };
+
+    for pending in batches {
+        let Ok(job) = get_job(config, &pending.job_id) else {
+            let _ = clear_pending_batch(config, &pending.job_id);
+            continue;
+        };
+        let status = match providers::create_batch_provider(
+            &pending.provider,
+            config.api_key.as_deref(),
+            config.api_url.as_deref(),
+        ) {
+            Ok(provider) => provider.poll_batch(&pending.handle).await,
+            Err(e) => Err(e),
+        };
+        let (success, output) = match status {
+            Ok(BatchStatus::InProgress) => continue,
+            Ok(BatchStatus::Completed(results)) => {
+                let outcome = results
+                    .into_iter()
+                    .find(|result| result.custom_id == job.id)
+                    .map(|result| result.outcome);
+                match outcome {
+                    Some(Ok(response)) => {
+                        record_batch_cost(config, &pending, &response);
+                        match response.text {
+                            Some(text) if !text.trim().is_empty() => (true, text),
+                            _ => (true, "agent job executed".to_string()),
+                        }
+                    }
+                    Some(Err(e)) => (false, format!("batch job failed: {e}")),
+                    None => (false, "batch job failed: no result for job".to_string()),
+                }
+            }
+            Ok(BatchStatus::Failed(reason)) => (false, format!("batch job failed: {reason}")),
+            Err(e) => {
+                tracing::warn!("Polling cron batch {} failed: {e}", pending.handle.id);
+                continue;
+            }
+        };
+
+        if let Err(e) = clear_pending_batch(config, &job.id) {
+            tracing::warn!("Failed to clear cron batch {}: {e}", pending.handle.id);
+        }
+        let finished_at = Utc::now();
+        let started_at = pending.handle.submitted_at;
+        let success =
+            persist_job_result(config, &job, success, &output, started_at, finished_at).await;
+        observer.record_event(&ObserverEvent::CronJobRun {
+            job_id: job.id.clone(),
+            success,
+            duration: (finished_at - started_at).to_std().unwrap_or_default(),
+        });
+        if !success {
+            tracing::warn!("Scheduler job '{}' failed: {output}", job.id);
+        }
+    }
+}
+
+/// Record a batch response's usage at the discounted batch price.
+fn record_batch_cost(config: &Config, pending: &PendingBatch, response: &ChatResponse) {
+    let Some(usage) = &response.usage else {
+        return;
+    };
+    let Some(recorder) = crate::cost::UsageRecorder::from_config(config) else {
+        return;
+    };
+    let handle = &pending.handle;
+    let cost = recorder
+        .price(&handle.provider, &handle.model, usage)
+        .with_batch_discount();
+    if let Err(e) = recorder.tracker().record_usage(cost) {
+        tracing::warn!("Failed to record cron batch cost: {e}");
     }
 }
 
@@ -504,6 +727,8 @@ mod tests {
             last_run: None,
             last_status: None,
             last_output: None,
+            generation: None,
+            batch: false,
         }
     }
 
@@ -761,7 +986,8 @@ mod tests {
         let component = unique_component("scheduler-idle");
 
         crate::health::mark_component_error(&component, "pre-existing error");
-        process_due_jobs(&config, &security, Vec::new(), &component).await;
+        let observer: Arc<dyn Observer> = Arc::new(crate::observability::NoopObserver);
+        process_due_jobs(&config, &security, &observer, Vec::new(), &component).await;
 
         let snapshot = crate::health::snapshot_json();
         let entry = &snapshot["components"][component.as_str()];
@@ -781,12 +1007,183 @@ mod tests {
         ));
         let component = unique_component("scheduler-fail");
 
+        let prom = crate::observability::PrometheusObserver::new();
+        let observer: Arc<dyn Observer> = Arc::new(prom.clone());
+        let job_id = job.id.clone();
+
         crate::health::mark_component_ok(&component);
-        process_due_jobs(&config, &security, vec![job], &component).await;
+        process_due_jobs(&config, &security, &observer, vec![job], &component).await;
 
         let snapshot = crate::health::snapshot_json();
         let entry = &snapshot["components"][component.as_str()];
         assert_eq!(entry["status"], "ok");
+        assert!(prom.encode().contains(&format!(
+            r#"zeroclaw_cron_job_runs_total{{job_id="{job_id}",status="error"}} 1"#
+        )));
+    }
+
+    #[tokio::test]
+    async fn batch_job_submits_once_and_records_run_when_batch_ends() {
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        let tmp = TempDir::new().unwrap();
+        let mut config = test_config(&tmp).await;
+        config.default_provider = Some("openai".into());
+        config.default_model = Some("gpt-4o-mini".into());
+        config.api_key = Some("test-key".into());
+        config.api_url = Some(server.uri());
+        config.cost.enabled = true;
+        config.cost.prices.insert(
+            "openai/gpt-4o-mini".into(),
+            crate::config::schema::ModelPricing {
+                input: 2.0,
+                output: 8.0,
+            },
+        );
+        let job = cron::add_agent_job(
+            &config,
+            None,
+            crate::cron::Schedule::Cron {
+                expr: "0 3 * * *".into(),
+                tz: None,
+            },
+            "summarize the day",
+            SessionTarget::Isolated,
+            None,
+            None,
+            false,
+        )
+        .unwrap();
+        let job = cron::update_job(
+            &config,
+            &job.id,
+            CronJobPatch {
+                batch: Some(true),
+                ..CronJobPatch::default()
+            },
+        )
+        .unwrap();
+
+        Mock::given(method("POST"))
+            .and(path("/files"))
+            .respond_with(
+                ResponseTemplate::new(200).set_body_json(serde_json::json!({"id": "file-in"})),
+            )
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/batches"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_json(serde_json::json!({"id": "batch_1", "status": "validating"})),
+            )
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/batches/batch_1"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_json(serde_json::json!({"id": "batch_1", "status": "in_progress"})),
+            )
+            .up_to_n_times(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/batches/batch_1"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "id": "batch_1",
+                "status": "completed",
+                "output_file_id": "file-out"
+            })))
+            .mount(&server)
+            .await;
+        let output_line = serde_json::json!({
+            "custom_id": job.id,
+            "response": {
+                "status_code": 200,
+                "body": {
+                    "choices": [{"message": {"content": "Nightly summary"}}],
+                    "usage": {"prompt_tokens": 1_000_000, "completion_tokens": 0}
+                }
+            }
+        });
+        Mock::given(method("GET"))
+            .and(path("/files/file-out/content"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(output_line.to_string()))
+            .mount(&server)
+            .await;
+
+        let security = SecurityPolicy::from_config(&config.autonomy, &config.workspace_dir);
+        let observer = crate::observability::NoopObserver;
+        let component = unique_component("scheduler-batch");
+
+        let (_, success, output) =
+            execute_and_persist_job(&config, &security, &observer, &job, &component).await;
+        assert!(success, "{output}");
+        assert_eq!(output, "batch batch_1 submitted");
+        let (_, _, output) =
+            execute_and_persist_job(&config, &security, &observer, &job, &component).await;
+        assert_eq!(output, "batch pending");
+
+        // Pending batches live in the cron DB, so a fresh poll loop (as
+        // after a daemon restart) picks them up.
+        poll_pending_batches(&config, &observer).await;
+        assert!(cron::list_runs(&config, &job.id, 10).unwrap().is_empty());
+        assert_eq!(cron::pending_batches(&config).unwrap().len(), 1);
+
+        poll_pending_batches(&config, &observer).await;
+        let runs = cron::list_runs(&config, &job.id, 10).unwrap();
+        assert_eq!(runs.len(), 1);
+        assert_eq!(runs[0].status, "ok");
+        assert_eq!(runs[0].output.as_deref(), Some("Nightly summary"));
+        assert!(cron::pending_batches(&config).unwrap().is_empty());
+
+        // 1M input tokens at $2/M, halved by the batch discount.
+        let tracker =
+            crate::cost::CostTracker::new(config.cost.clone(), &config.workspace_dir).unwrap();
+        let daily = tracker.get_summary().unwrap().daily_cost_usd;
+        assert!((daily - 1.0).abs() < 1e-9, "daily cost {daily}");
+    }
+
+    #[tokio::test]
+    async fn batch_cost_falls_back_to_model_catalog_pricing() {
+        let tmp = TempDir::new().unwrap();
+        let mut config = test_config(&tmp).await;
+        config.cost.enabled = true;
+        config.cost.prices.clear();
+        let pending = PendingBatch {
+            job_id: "job-1".into(),
+            provider: "openai".into(),
+            handle: crate::providers::BatchHandle {
+                provider: "openai".into(),
+                id: "batch_1".into(),
+                model: "gpt-4.1".into(),
+                submitted_at: Utc::now(),
+            },
+        };
+        let response = ChatResponse {
+            text: Some("done".into()),
+            tool_calls: Vec::new(),
+            usage: Some(crate::providers::traits::TokenUsage {
+                input_tokens: Some(1_000_000),
+                output_tokens: Some(0),
+                cache_read_tokens: None,
+                cache_write_tokens: None,
+            }),
+            reasoning_content: None,
+        };
+
+        record_batch_cost(&config, &pending, &response);
+
+        // Bundled catalog lists gpt-4.1 input at $2/M; batch halves it.
+        let tracker =
+            crate::cost::CostTracker::new(config.cost.clone(), &config.workspace_dir).unwrap();
+        let daily = tracker.get_summary().unwrap().daily_cost_usd;
+        assert!((daily - 1.0).abs() < 1e-9, "daily cost {daily}");
     }
 
     #[tokio::test]
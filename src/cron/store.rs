@@ -1,8 +1,9 @@
 use crate::config::Config;
 use crate::cron::{
     next_run_for_schedule, schedule_cron_expression, validate_schedule, CronJob, CronJobPatch,
-    CronRun, DeliveryConfig, JobType, Schedule, SessionTarget,
+    CronRun, DeliveryConfig, JobType, PendingBatch, Schedule, SessionTarget,
 };
+use crate::providers::GenerationParams;
 use anyhow::{Context, Result};
 use chrono::{DateTime, Utc};
 use rusqlite::types::{FromSqlResult, ValueRef};
@@ -117,7 +118,8 @@ pub fn list_jobs(config: &Config) -> Result<Vec<CronJob>> {
     with_connection(config, |conn| {
         let mut stmt = conn.prepare(
             "SELECT id, expression, command, schedule, job_type, prompt, name, session_target, model,
-                    enabled, delivery, delete_after_run, created_at, next_run, last_run, last_status, last_output
+                    enabled, delivery, delete_after_run, created_at, next_run, last_run, last_status, last_output,
+                    generation, batch
              FROM cron_jobs ORDER BY next_run ASC",
         )?;
 
@@ -135,7 +137,8 @@ pub fn get_job(config: &Config, job_id: &str) -> Result<CronJob> {
     with_connection(config, |conn| {
         let mut stmt = conn.prepare(
             "SELECT id, expression, command, schedule, job_type, prompt, name, session_target, model,
-                    enabled, delivery, delete_after_run, created_at, next_run, last_run, last_status, last_output
+                    enabled, delivery, delete_after_run, created_at, next_run, last_run, last_status, last_output,
+                    generation, batch
              FROM cron_jobs WHERE id = ?1",
         )?;
 
@@ -168,7 +171,8 @@ pub fn due_jobs(config: &Config, now: DateTime<Utc>) -> Result<Vec<CronJob>> {
     with_connection(config, |conn| {
         let mut stmt = conn.prepare(
             "SELECT id, expression, command, schedule, job_type, prompt, name, session_target, model,
-                    enabled, delivery, delete_after_run, created_at, next_run, last_run, last_status, last_output
+                    enabled, delivery, delete_after_run, created_at, next_run, last_run, last_status, last_output,
+                    generation, batch
              FROM cron_jobs
              WHERE enabled = 1 AND next_run <= ?1
              ORDER BY next_run ASC
@@ -219,6 +223,13 @@ pub fn update_job(config: &Config, job_id: &str, patch: CronJobPatch) -> Result<
     if let Some(delete_after_run) = patch.delete_after_run {
         job.delete_after_run = delete_after_run;
     }
+    if let Some(generation) = patch.generation {
+        generation.validate()?;
+        job.generation = generation.non_empty().cloned();
+    }
+    if let Some(batch) = patch.batch {
+        job.batch = batch;
+    }
 
     if schedule_changed {
         job.next_run = next_run_for_schedule(&job.schedule, Utc::now())?;
@@ -229,8 +240,8 @@ pub fn update_job(config: &Config, job_id: &str, patch: CronJobPatch) -> Result<
             "UPDATE cron_jobs
              SET expression = ?1, command = ?2, schedule = ?3, job_type = ?4, prompt = ?5, name = ?6,
                  session_target = ?7, model = ?8, enabled = ?9, delivery = ?10, delete_after_run = ?11,
-                 next_run = ?12
-             WHERE id = ?13",
+                 next_run = ?12, generation = ?13, batch = ?14
+             WHERE id = ?15",
             params![
                 job.expression,
                 job.command,
@@ -244,6 +255,8 @@ pub fn update_job(config: &Config, job_id: &str, patch: CronJobPatch) -> Result<
                 serde_json::to_string(&job.delivery)?,
                 if job.delete_after_run { 1 } else { 0 },
                 job.next_run.to_rfc3339(),
+                job.generation.as_ref().map(serde_json::to_string).transpose()?,
+                if job.batch { 1 } else { 0 },
                 job.id,
             ],
         )
@@ -406,6 +419,61 @@ pub fn list_runs(config: &Config, job_id: &str, limit: usize) -> Result<Vec<Cron
     })
 }
 
+/// Remember the batch submitted for a job's current run.
+pub fn record_pending_batch(config: &Config, batch: &PendingBatch) -> Result<()> {
+    with_connection(config, |conn| {
+        conn.execute(
+            "INSERT OR REPLACE INTO cron_batches (job_id, provider, handle, submitted_at)
+             VALUES (?1, ?2, ?3, ?4)",
+            params![
+                batch.job_id,
+                batch.provider,
+                serde_json::to_string(&batch.handle)?,
+                batch.handle.submitted_at.to_rfc3339()
+            ],
+        )
+        .context("Failed to record pending cron batch")?;
+        Ok(())
+    })
+}
+
+/// Batches still awaiting results, oldest first.
+pub fn pending_batches(config: &Config) -> Result<Vec<PendingBatch>> {
+    with_connection(config, |conn| {
+        let mut stmt = conn.prepare(
+            "SELECT job_id, provider, handle FROM cron_batches ORDER BY submitted_at ASC",
+        )?;
+        let rows = stmt.query_map([], |row| {
+            let handle_raw: String = row.get(2)?;
+            let handle = serde_json::from_str(&handle_raw)
+                .with_context(|| format!("Failed to parse cron batch handle: {handle_raw}"))
+                .map_err(sql_conversion_error)?;
+            Ok(PendingBatch {
+                job_id: row.get(0)?,
+                provider: row.get(1)?,
+                handle,
+            })
+        })?;
+
+        let mut batches = Vec::new();
+        for row in rows {
+            batches.push(row?);
+        }
+        Ok(batches)
+    })
+}
+
+pub fn clear_pending_batch(config: &Config, job_id: &str) -> Result<()> {
+    with_connection(config, |conn| {
+        conn.execute(
+            "DELETE FROM cron_batches WHERE job_id = ?1",
+            params![job_id],
+        )
+        .context("Failed to clear pending cron batch")?;
+        Ok(())
+    })
+}
+
 fn parse_rfc3339(raw: &str) -> Result<DateTime<Utc>> {
     let parsed = DateTime::parse_from_rfc3339(raw)
         .with_context(|| format!("Invalid RFC3339 timestamp in cron DB: {raw}"))?;
@@ -425,6 +493,9 @@ fn map_cron_job_row(row: &rusqlite::Row<'_>) -> rusqlite::Result<CronJob> {
     let delivery_raw: Option<String> = row.get(10)?;
     let delivery = decode_delivery(delivery_raw.as_deref()).map_err(sql_conversion_error)?;
 
+    let generation_raw: Option<String> = row.get(17)?;
+    let generation = decode_generation(generation_raw.as_deref()).map_err(sql_conversion_error)?;
+
     let next_run_raw: String = row.get(13)?;
     let last_run_raw: Option<String> = row.get(14)?;
     let created_at_raw: String = row.get(12)?;
@@ -450,6 +521,8 @@ fn map_cron_job_row(row: &rusqlite::Row<'_>) -> rusqlite::Result<CronJob> {
         },
         last_status: row.get(15)?,
         last_output: row.get(16)?,
+        generation,
+        batch: row.get::<_, i64>(18)? != 0,
     })
 }
 
@@ -483,6 +556,15 @@ fn decode_delivery(delivery_raw: Option<&str>) -> Result<DeliveryConfig> {
     Ok(DeliveryConfig::default())
 }
 
+fn decode_generation(generation_raw: Option<&str>) -> Result<Option<GenerationParams>> {
+    match generation_raw.map(str::trim) {
+        Some(raw) if !raw.is_empty() => serde_json::from_str(raw)
+            .map(Some)
+            .with_context(|| format!("Failed to parse cron generation JSON: {raw}")),
+        _ => Ok(None),
+    }
+}
+
 fn add_column_if_missing(conn: &Connection, name: &str, sql_type: &str) -> Result<()> {
     let mut stmt = conn.prepare("PRAGMA table_info(cron_jobs)")?;
     let mut rows = stmt.query([])?;
@@ -542,7 +624,9 @@ fn with_connection<T>(config: &Config, f: impl FnOnce(&Connection) -> Result<T>)
             next_run         TEXT NOT NULL,
             last_run         TEXT,
             last_status      TEXT,
-            last_output      TEXT
+            last_output      TEXT,
+            generation       TEXT,
+            batch            INTEGER NOT NULL DEFAULT 0
         );
         CREATE INDEX IF NOT EXISTS idx_cron_jobs_next_run ON cron_jobs(next_run);
 
@@ -558,7 +642,15 @@ fn with_connection<T>(config: &Config, f: impl FnOnce(&Connection) -> Result<T>)
         );
         CREATE INDEX IF NOT EXISTS idx_cron_runs_job_id ON cron_runs(job_id);
         CREATE INDEX IF NOT EXISTS idx_cron_runs_started_at ON cron_runs(started_at);
-        CREATE INDEX IF NOT EXISTS idx_cron_runs_job_started ON cron_runs(job_id, started_at);",
+        CREATE INDEX IF NOT EXISTS idx_cron_runs_job_started ON cron_runs(job_id, started_at);
+
+        CREATE TABLE IF NOT EXISTS cron_batches (
+            job_id       TEXT PRIMARY KEY,
+            provider     TEXT NOT NULL,
+            handle       TEXT NOT NULL,
+            submitted_at TEXT NOT NULL,
+            FOREIGN KEY (job_id) REFERENCES cron_jobs(id) ON DELETE CASCADE
+        );",
     )
     .context("Failed to initialize cron schema")?;
 
@@ -571,6 +663,8 @@ fn with_connection<T>(config: &Config, f: impl FnOnce(&Connection) -> Result<T>)
     add_column_if_missing(&conn, "enabled", "INTEGER NOT NULL DEFAULT 1")?;
     add_column_if_missing(&conn, "delivery", "TEXT")?;
     add_column_if_missing(&conn, "delete_after_run", "INTEGER NOT NULL DEFAULT 0")?;
+    add_column_if_missing(&conn, "generation", "TEXT")?;
+    add_column_if_missing(&conn, "batch", "INTEGER NOT NULL DEFAULT 0")?;
 
     f(&conn)
 }
@@ -579,6 +673,7 @@ fn with_connection<T>(config: &Config, f: impl FnOnce(&Connection) -> Result<T>)
 mod tests {
     use super::*;
     use crate::config::Config;
+    use crate::providers::BatchHandle;
     use chrono::Duration as ChronoDuration;
     use tempfile::TempDir;
 
@@ -643,6 +738,78 @@ mod tests {
         assert!(list_jobs(&config).unwrap().is_empty());
     }
 
+    #[test]
+    fn update_job_sets_and_clears_generation() {
+        let tmp = TempDir::new().unwrap();
+        let config = test_config(&tmp);
+        let job = add_job(&config, "*/10 * * * *", "echo generation").unwrap();
+        assert!(job.generation.is_none());
+
+        let params = GenerationParams {
+            max_tokens: Some(128),
+            seed: Some(7),
+            ..GenerationParams::default()
+        };
+        let updated = update_job(
+            &config,
+            &job.id,
+            CronJobPatch {
+                generation: Some(params.clone()),
+                ..CronJobPatch::default()
+            },
+        )
+        .unwrap();
+        assert_eq!(updated.generation, Some(params));
+
+        let cleared = update_job(
+            &config,
+            &job.id,
+            CronJobPatch {
+                generation: Some(GenerationParams::default()),
+                ..CronJobPatch::default()
+            },
+        )
+        .unwrap();
+        assert!(cleared.generation.is_none());
+    }
+
+    #[test]
+    fn pending_batches_roundtrip_and_cascade_on_job_removal() {
+        let tmp = TempDir::new().unwrap();
+        let config = test_config(&tmp);
+        let job = add_job(&config, "*/10 * * * *", "echo batch").unwrap();
+        let updated = update_job(
+            &config,
+            &job.id,
+            CronJobPatch {
+                batch: Some(true),
+                ..CronJobPatch::default()
+            },
+        )
+        .unwrap();
+        assert!(updated.batch);
+
+        let pending = PendingBatch {
+            job_id: job.id.clone(),
+            provider: "openai".into(),
+            handle: BatchHandle {
+                provider: "openai".into(),
+                id: "batch_1".into(),
+                model: "gpt-4o-mini".into(),
+                submitted_at: Utc::now(),
+            },
+        };
+        record_pending_batch(&config, &pending).unwrap();
+        assert_eq!(pending_batches(&config).unwrap(), vec![pending.clone()]);
+
+        clear_pending_batch(&config, &job.id).unwrap();
+        assert!(pending_batches(&config).unwrap().is_empty());
+
+        record_pending_batch(&config, &pending).unwrap();
+        remove_job(&config, &job.id).unwrap();
+        assert!(pending_batches(&config).unwrap().is_empty());
+    }
+
     #[test]
     fn due_jobs_filters_by_timestamp_and_enabled() {
         let tmp = TempDir::new().unwrap();
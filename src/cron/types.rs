@@ -1,3 +1,4 @@
+use crate::providers::{BatchHandle, GenerationParams};
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
@@ -120,6 +121,21 @@ pub struct CronJob {
     pub last_run: Option<DateTime<Utc>>,
     pub last_status: Option<String>,
     pub last_output: Option<String>,
+    /// Sampling overrides for agent jobs, layered over `[agent] generation`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub generation: Option<GenerationParams>,
+    /// Run agent jobs through the provider's discounted batch API.
+    #[serde(default)]
+    pub batch: bool,
+}
+
+/// A batch submitted for an agent job whose results are not yet collected.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PendingBatch {
+    pub job_id: String,
+    /// Configured provider entry to poll through (e.g. `anthropic-custom:<url>`).
+    pub provider: String,
+    pub handle: BatchHandle,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -144,6 +160,9 @@ pub struct CronJobPatch {
     pub model: Option<String>,
     pub session_target: Option<SessionTarget>,
     pub delete_after_run: Option<bool>,
+    /// Replaces the job's generation overrides; an empty object clears them.
+    pub generation: Option<GenerationParams>,
+    pub batch: Option<bool>,
 }
 
 #[cfg(test)]
@@ -1,12 +1,16 @@
+pub mod reload;
+
 use crate::config::Config;
 use anyhow::Result;
 use chrono::Utc;
+use std::collections::BTreeMap;
 use std::future::Future;
 use std::path::PathBuf;
 use tokio::task::JoinHandle;
 use tokio::time::Duration;
 
 const STATUS_FLUSH_SECONDS: u64 = 5;
+const CONFIG_WATCH_SECONDS: u64 = 2;
 
 pub async fn run(config: Config, host: String, port: u16) -> Result<()> {
     let initial_backoff = config.reliability.channel_initial_backoff_secs.max(1);
@@ -23,86 +27,203 @@ pub async fn run(config: Config, host: String, port: u16) -> Result<()> {
                 .await;
     }
 
-    let mut handles: Vec<JoinHandle<()>> = vec![spawn_state_writer(config.clone())];
-
-    {
-        let gateway_cfg = config.clone();
-        let gateway_host = host.clone();
-        handles.push(spawn_component_supervisor(
-            "gateway",
-            initial_backoff,
-            max_backoff,
-            move || {
-                let cfg = gateway_cfg.clone();
-                let host = gateway_host.clone();
-                async move { crate::gateway::run_gateway(&host, port, cfg).await }
-            },
-        ));
+    let state_writer = spawn_state_writer(config.clone());
+    let mut components = DaemonComponents {
+        host: host.clone(),
+        port,
+        initial_backoff,
+        max_backoff,
+        handles: BTreeMap::new(),
+    };
+    for &name in reload::COMPONENTS {
+        components.start(name, &config);
     }
 
-    {
-        if has_supervised_channels(&config) {
-            let channels_cfg = config.clone();
-            handles.push(spawn_component_supervisor(
-                "channels",
-                initial_backoff,
-                max_backoff,
-                move || {
-                    let cfg = channels_cfg.clone();
-                    async move { crate::channels::start_channels(cfg).await }
-                },
-            ));
-        } else {
-            crate::health::mark_component_ok("channels");
-            tracing::info!("No real-time channels configured; channel supervisor disabled");
+    println!("🧠 ZeroClaw daemon started");
+    println!("   Gateway:  http://{host}:{port}");
+    println!("   Components: gateway, channels, heartbeat, goal_loop, scheduler");
+    println!(
+        "   Config:   watching {} for changes",
+        config.config_path.display()
+    );
+    println!("   Ctrl+C to stop");
+
+    let mut running = config;
+    let mut watcher =
+        reload::ConfigWatcher::new(crate::config::layers::watch_paths(&running)).await;
+    let mut watch_interval = tokio::time::interval(Duration::from_secs(CONFIG_WATCH_SECONDS));
+    let ctrl_c = tokio::signal::ctrl_c();
+    tokio::pin!(ctrl_c);
+
+    loop {
+        tokio::select! {
+            result = &mut ctrl_c => {
+                result?;
+                break;
+            }
+            _ = watch_interval.tick() => {
+                if watcher.changed().await {
+                    apply_config_reload(&mut running, &mut components).await;
+                    watcher
+                        .set_paths(crate::config::layers::watch_paths(&running))
+                        .await;
+                }
+            }
         }
     }
 
-    if config.heartbeat.enabled {
-        let heartbeat_cfg = config.clone();
-        handles.push(spawn_component_supervisor(
-            "heartbeat",
-            initial_backoff,
-            max_backoff,
-            move || {
-                let cfg = heartbeat_cfg.clone();
-                async move { Box::pin(run_heartbeat_worker(cfg)).await }
-            },
-        ));
+    crate::health::mark_component_error("daemon", "shutdown requested");
+
+    state_writer.abort();
+    let _ = state_writer.await;
+    components.shutdown().await;
+
+    Ok(())
+}
+
+/// Re-load `config.toml` and hot-apply what can be applied in place.
+///
+/// Invalid configs are rejected and the daemon keeps running the previous one.
+async fn apply_config_reload(running: &mut Config, components: &mut DaemonComponents) {
+    let (next, plan) = match reload::load_and_plan(running) {
+        Ok(result) => result,
+        Err(e) => {
+            tracing::error!("Config reload rejected; keeping the running config: {e:#}");
+            reload::audit_reload(running, Err(&format!("{e:#}")));
+            return;
+        }
+    };
+
+    if plan.diff.is_empty() {
+        tracing::debug!("Config file touched without effective changes");
+        return;
     }
 
-    if config.cron.enabled {
-        let scheduler_cfg = config.clone();
-        handles.push(spawn_component_supervisor(
-            "scheduler",
-            initial_backoff,
-            max_backoff,
-            move || {
-                let cfg = scheduler_cfg.clone();
-                async move { crate::cron::scheduler::run(cfg).await }
-            },
-        ));
-    } else {
-        crate::health::mark_component_ok("scheduler");
-        tracing::info!("Cron disabled; scheduler supervisor not started");
+    if plan.reinit_observability {
+        crate::observability::runtime_trace::init_from_config(
+            &next.observability,
+            &next.workspace_dir,
+        );
+    }
+    for &name in &plan.components {
+        components.restart(name, &next).await;
     }
 
-    println!("🧠 ZeroClaw daemon started");
-    println!("   Gateway:  http://{host}:{port}");
-    println!("   Components: gateway, channels, heartbeat, scheduler");
-    println!("   Ctrl+C to stop");
+    tracing::info!(
+        changed = ?plan.diff.changed_paths,
+        hot_applied = ?plan.hot_sections,
+        restarted = ?plan.components,
+        "Config reloaded"
+    );
+    if !plan.restart_required.is_empty() {
+        tracing::warn!(
+            sections = ?plan.restart_required,
+            "Config changes need a daemon restart to take effect"
+        );
+    }
 
-    tokio::signal::ctrl_c().await?;
-    crate::health::mark_component_error("daemon", "shutdown requested");
+    reload::audit_reload(&next, Ok(&plan));
+    *running = next;
+}
 
-    for handle in &handles {
-        handle.abort();
+/// Supervised daemon components, keyed by name.
+struct DaemonComponents {
+    host: String,
+    port: u16,
+    initial_backoff: u64,
+    max_backoff: u64,
+    handles: BTreeMap<&'static str, JoinHandle<()>>,
+}
+
+impl DaemonComponents {
+    /// Start `name` under a supervisor if `config` enables it.
+    fn start(&mut self, name: &'static str, config: &Config) {
+        let (initial_backoff, max_backoff) = (self.initial_backoff, self.max_backoff);
+        let handle = match name {
+            "gateway" => {
+                let gateway_cfg = config.clone();
+                let gateway_host = self.host.clone();
+                let port = self.port;
+                spawn_component_supervisor(name, initial_backoff, max_backoff, move || {
+                    let cfg = gateway_cfg.clone();
+                    let host = gateway_host.clone();
+                    async move { crate::gateway::run_gateway(&host, port, cfg).await }
+                })
+            }
+            "channels" => {
+                if !has_supervised_channels(config) {
+                    crate::health::mark_component_ok("channels");
+                    tracing::info!("No real-time channels configured; channel supervisor disabled");
+                    return;
+                }
+                let channels_cfg = config.clone();
+                spawn_component_supervisor(name, initial_backoff, max_backoff, move || {
+                    let cfg = channels_cfg.clone();
+                    async move { crate::channels::start_channels(cfg).await }
+                })
+            }
+            "heartbeat" => {
+                if !config.heartbeat.enabled {
+                    return;
+                }
+                let heartbeat_cfg = config.clone();
+                spawn_component_supervisor(name, initial_backoff, max_backoff, move || {
+                    let cfg = heartbeat_cfg.clone();
+                    async move { Box::pin(run_heartbeat_worker(cfg)).await }
+                })
+            }
+            "goal_loop" => {
+                if !config.goal_loop.enabled {
+                    return;
+                }
+                let goal_loop_cfg = config.clone();
+                spawn_component_supervisor(name, initial_backoff, max_backoff, move || {
+                    let cfg = goal_loop_cfg.clone();
+                    async move { Box::pin(run_goal_loop_worker(cfg)).await }
+                })
+            }
+            "scheduler" => {
+                if !config.cron.enabled {
+                    crate::health::mark_component_ok("scheduler");
+                    tracing::info!("Cron disabled; scheduler supervisor not started");
+                    return;
+                }
+                let scheduler_cfg = config.clone();
+                spawn_component_supervisor(name, initial_backoff, max_backoff, move || {
+                    let cfg = scheduler_cfg.clone();
+                    async move { crate::cron::scheduler::run(cfg).await }
+                })
+            }
+            other => {
+                tracing::warn!("Unknown daemon component '{other}' not started");
+                return;
+            }
+        };
+        self.handles.insert(name, handle);
     }
-    for handle in handles {
-        let _ = handle.await;
+
+    async fn stop(&mut self, name: &str) {
+        if let Some(handle) = self.handles.remove(name) {
+            handle.abort();
+            let _ = handle.await;
+        }
     }
 
-    Ok(())
+    /// Stop `name` and start it again with `config` (or leave it stopped if
+    /// the new config disables it).
+    async fn restart(&mut self, name: &'static str, config: &Config) {
+        self.stop(name).await;
+        self.start(name, config);
+    }
+
+    async fn shutdown(mut self) {
+        for handle in self.handles.values() {
+            handle.abort();
+        }
+        for (_, handle) in std::mem::take(&mut self.handles) {
+            let _ = handle.await;
+        }
+    }
 }
 
 pub fn state_file_path(config: &Config) -> PathBuf {
@@ -198,7 +319,7 @@ async fn run_heartbeat_worker(config: Config) -> Result<()> {
         for task in tasks {
             let prompt = format!("[Heartbeat Task] {task}");
             let temp = config.default_temperature;
-            match crate::agent::run(
+            match Box::pin(crate::agent::run(
                 config.clone(),
                 Some(prompt),
                 None,
@@ -206,7 +327,7 @@ async fn run_heartbeat_worker(config: Config) -> Result<()> {
                 temp,
                 vec![],
                 false,
-            )
+            ))
             .await
             {
                 Ok(output) => {
@@ -242,6 +363,130 @@ async fn run_heartbeat_worker(config: Config) -> Result<()> {
     }
 }
 
+async fn run_goal_loop_worker(config: Config) -> Result<()> {
+    let engine = crate::goals::engine::GoalEngine::new(&config.workspace_dir);
+    let delivery = goal_loop_delivery_target(&config)?;
+    let step_timeout = Duration::from_secs(config.goal_loop.step_timeout_secs.max(1));
+    let max_steps = config.goal_loop.max_steps_per_cycle.max(1);
+    let mut reflected = std::collections::HashMap::new();
+
+    let interval_mins = config.goal_loop.interval_minutes.max(1);
+    let mut interval = tokio::time::interval(Duration::from_secs(u64::from(interval_mins) * 60));
+
+    loop {
+        interval.tick().await;
+
+        if let Some(reason) = goal_loop_budget_exceeded(&config) {
+            crate::health::mark_component_error("goal_loop", reason.clone());
+            tracing::warn!("Goal loop cycle skipped: {reason}");
+            continue;
+        }
+
+        let events = engine
+            .run_cycle(
+                max_steps,
+                &mut reflected,
+                |prompt| {
+                    let cfg = config.clone();
+                    async move {
+                        let temp = cfg.default_temperature;
+                        let run = Box::pin(crate::agent::run(
+                            cfg,
+                            Some(prompt),
+                            None,
+                            None,
+                            temp,
+                            vec![],
+                            false,
+                        ));
+                        match tokio::time::timeout(step_timeout, run).await {
+                            Ok(result) => result,
+                            Err(_) => {
+                                anyhow::bail!("step timed out after {}s", step_timeout.as_secs())
+                            }
+                        }
+                    }
+                },
+                |prompt, output| {
+                    let cfg = &config;
+                    async move { crate::agent::outcome::assess(cfg, None, &prompt, &output).await }
+                },
+            )
+            .await?;
+        crate::health::mark_component_ok("goal_loop");
+
+        let Some((channel, target)) = &delivery else {
+            continue;
+        };
+        for event in events {
+            if let Err(e) = crate::cron::scheduler::deliver_announcement(
+                &config,
+                channel,
+                target,
+                &event.to_string(),
+            )
+            .await
+            {
+                crate::health::mark_component_error("goal_loop", format!("delivery failed: {e}"));
+                tracing::warn!("Goal loop delivery failed: {e}");
+            }
+        }
+    }
+}
+
+/// Returns a reason when the configured cost limits leave no budget for a
+/// goal cycle. Tracker errors are logged and do not block the cycle.
+fn goal_loop_budget_exceeded(config: &Config) -> Option<String> {
+    if !config.cost.enabled {
+        return None;
+    }
+    let check = crate::cost::CostTracker::new(config.cost.clone(), &config.workspace_dir)
+        .and_then(|tracker| tracker.check_budget(0.0));
+    match check {
+        Ok(crate::cost::BudgetCheck::Exceeded {
+            current_usd,
+            limit_usd,
+            period,
+        }) => Some(format!(
+            "{period:?} cost limit reached (${current_usd:.2} of ${limit_usd:.2})"
+        )),
+        Ok(_) => None,
+        Err(e) => {
+            tracing::warn!("Goal loop budget check failed: {e}");
+            None
+        }
+    }
+}
+
+fn goal_loop_delivery_target(config: &Config) -> Result<Option<(String, String)>> {
+    let channel = config
+        .goal_loop
+        .channel
+        .as_deref()
+        .map(str::trim)
+        .filter(|value| !value.is_empty());
+    let target = config
+        .goal_loop
+        .target
+        .as_deref()
+        .map(str::trim)
+        .filter(|value| !value.is_empty());
+
+    match (channel, target) {
+        (None, None) => Ok(None),
+        (Some(_), None) => {
+            anyhow::bail!("goal_loop.target is required when goal_loop.channel is set")
+        }
+        (None, Some(_)) => {
+            anyhow::bail!("goal_loop.channel is required when goal_loop.target is set")
+        }
+        (Some(channel), Some(target)) => {
+            validate_delivery_channel_config(config, channel, "goal_loop.channel")?;
+            Ok(Some((channel.to_string(), target.to_string())))
+        }
+    }
+}
+
 fn heartbeat_tasks_for_tick(
     file_tasks: Vec<String>,
     fallback_message: Option<&str>,
@@ -276,43 +521,25 @@ fn heartbeat_delivery_target(config: &Config) -> Result<Option<(String, String)>
         (Some(_), None) => anyhow::bail!("heartbeat.to is required when heartbeat.target is set"),
         (None, Some(_)) => anyhow::bail!("heartbeat.target is required when heartbeat.to is set"),
         (Some(channel), Some(target)) => {
-            validate_heartbeat_channel_config(config, channel)?;
+            validate_delivery_channel_config(config, channel, "heartbeat.target")?;
             Ok(Some((channel.to_string(), target.to_string())))
         }
     }
 }
 
-fn validate_heartbeat_channel_config(config: &Config, channel: &str) -> Result<()> {
-    match channel.to_ascii_lowercase().as_str() {
-        "telegram" => {
-            if config.channels_config.telegram.is_none() {
-                anyhow::bail!(
-                    "heartbeat.target is set to telegram but channels_config.telegram is not configured"
-                );
-            }
-        }
-        "discord" => {
-            if config.channels_config.discord.is_none() {
-                anyhow::bail!(
-                    "heartbeat.target is set to discord but channels_config.discord is not configured"
-                );
-            }
-        }
-        "slack" => {
-            if config.channels_config.slack.is_none() {
-                anyhow::bail!(
-                    "heartbeat.target is set to slack but channels_config.slack is not configured"
-                );
-            }
-        }
-        "mattermost" => {
-            if config.channels_config.mattermost.is_none() {
-                anyhow::bail!(
-                    "heartbeat.target is set to mattermost but channels_config.mattermost is not configured"
-                );
-            }
-        }
-        other => anyhow::bail!("unsupported heartbeat.target channel: {other}"),
+fn validate_delivery_channel_config(config: &Config, channel: &str, field: &str) -> Result<()> {
+    let configured = match channel.to_ascii_lowercase().as_str() {
+        "telegram" => config.channels_config.telegram.is_some(),
+        "discord" => config.channels_config.discord.is_some(),
+        "slack" => config.channels_config.slack.is_some(),
+        "mattermost" => config.channels_config.mattermost.is_some(),
+        other => anyhow::bail!("unsupported {field} channel: {other}"),
+    };
+    if !configured {
+        let channel = channel.to_ascii_lowercase();
+        anyhow::bail!(
+            "{field} is set to {channel} but channels_config.{channel} is not configured"
+        );
     }
 
     Ok(())
@@ -541,4 +768,47 @@ mod tests {
         let target = heartbeat_delivery_target(&config).unwrap();
         assert_eq!(target, Some(("telegram".to_string(), "123456".to_string())));
     }
+
+    #[test]
+    fn goal_loop_delivery_target_requires_both_fields() {
+        let mut config = Config::default();
+        assert!(goal_loop_delivery_target(&config).unwrap().is_none());
+
+        config.goal_loop.channel = Some("telegram".into());
+        let err = goal_loop_delivery_target(&config).unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("goal_loop.target is required when goal_loop.channel is set"));
+
+        config.goal_loop.channel = None;
+        config.goal_loop.target = Some("123456".into());
+        let err = goal_loop_delivery_target(&config).unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("goal_loop.channel is required when goal_loop.target is set"));
+    }
+
+    #[test]
+    fn goal_loop_delivery_target_validates_channel() {
+        let mut config = Config::default();
+        config.goal_loop.channel = Some("slack".into());
+        config.goal_loop.target = Some("C123".into());
+        let err = goal_loop_delivery_target(&config).unwrap_err();
+        assert!(err.to_string().contains(
+            "goal_loop.channel is set to slack but channels_config.slack is not configured"
+        ));
+
+        config.goal_loop.channel = Some("email".into());
+        let err = goal_loop_delivery_target(&config).unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("unsupported goal_loop.channel channel"));
+    }
+
+    #[test]
+    fn goal_loop_budget_not_checked_when_cost_tracking_disabled() {
+        let tmp = TempDir::new().unwrap();
+        let config = test_config(&tmp);
+        assert!(goal_loop_budget_exceeded(&config).is_none());
+    }
 }
@@ -0,0 +1,396 @@
+//! Daemon-wide config hot-reload.
+//!
+//! The daemon polls `config.toml` for changes. When the file changes it is
+//! re-loaded through the normal decrypt → env-override → validate path, diffed
+//! against the running config, and each changed top-level section is either
+//! hot-applied (by restarting the daemon components that read it) or reported
+//! as needing a full daemon restart. Sections that need a restart keep their
+//! running values until then, so restarted components never see a
+//! half-applied memory backend or secrets store.
+
+use crate::channels::{config_file_stamp, ConfigFileStamp};
+use crate::config::{layers, Config};
+use crate::security::{AuditEvent, AuditEventType, AuditLogger};
+use anyhow::{Context, Result};
+use serde_json::{Map, Value};
+use std::collections::BTreeSet;
+use std::path::PathBuf;
+
+/// Every component the daemon supervises, in start-up order.
+pub const COMPONENTS: &[&str] = &["gateway", "channels", "heartbeat", "goal_loop", "scheduler"];
+
+/// Components that build an agent (tools, `SecurityPolicy`, observer) from config.
+const AGENT_COMPONENTS: &[&str] = COMPONENTS;
+
+/// Agent components other than channels. Channels refresh provider/model
+/// defaults in place on every message, so they are not restarted for those.
+const NON_CHANNEL_AGENT_COMPONENTS: &[&str] = &["gateway", "heartbeat", "goal_loop", "scheduler"];
+
+/// How a change to one top-level config section is applied.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SectionImpact {
+    /// Applied by restarting the listed daemon components with the new config.
+    /// An empty list means loading the config already applied it process-wide.
+    Hot(&'static [&'static str]),
+    /// Only takes effect after the daemon process restarts.
+    RestartRequired,
+}
+
+/// Classify a top-level `config.toml` section.
+///
+/// Unknown sections are treated as restart-required so that new config
+/// surface is never hot-applied by accident.
+pub fn section_impact(section: &str) -> SectionImpact {
+    match section {
+        "autonomy"
+        | "security"
+        | "agent"
+        | "agents"
+        | "skills"
+        | "hooks"
+        | "browser"
+        | "http_request"
+        | "web_fetch"
+        | "web_search"
+        | "composio"
+        | "multimodal"
+        | "query_classification"
+        | "transcription"
+        | "identity"
+        | "cost"
+        | "observability" => SectionImpact::Hot(AGENT_COMPONENTS),
+        "api_key"
+        | "api_url"
+        | "default_provider"
+        | "default_model"
+        | "default_temperature"
+        | "model_providers"
+        | "model_routes"
+        | "reliability" => SectionImpact::Hot(NON_CHANNEL_AGENT_COMPONENTS),
+        "channels_config" => SectionImpact::Hot(&["channels"]),
+        "gateway" => SectionImpact::Hot(&["gateway"]),
+        "heartbeat" => SectionImpact::Hot(&["heartbeat"]),
+        "goal_loop" => SectionImpact::Hot(&["goal_loop"]),
+        "cron" | "scheduler" => SectionImpact::Hot(&["scheduler"]),
+        // `apply_env_overrides` installs the new proxy settings while loading.
+        "proxy" => SectionImpact::Hot(&[]),
+        _ => SectionImpact::RestartRequired,
+    }
+}
+
+/// Dotted key paths that differ between two configs (`autonomy.allowed_commands`).
+///
+/// Only paths are recorded, never values, so the diff is safe to log and audit
+/// even when it touches API keys.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ConfigDiff {
+    pub changed_paths: Vec<String>,
+}
+
+impl ConfigDiff {
+    pub fn between(old: &Config, new: &Config) -> Result<Self> {
+        let old = serde_json::to_value(old).context("Failed to serialize running config")?;
+        let new = serde_json::to_value(new).context("Failed to serialize reloaded config")?;
+        Ok(Self {
+            changed_paths: layers::changed_paths(&old, &new),
+        })
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.changed_paths.is_empty()
+    }
+
+    /// Top-level sections touched by this diff.
+    pub fn sections(&self) -> BTreeSet<String> {
+        self.changed_paths
+            .iter()
+            .map(|path| path.split('.').next().unwrap_or(path).to_string())
+            .collect()
+    }
+}
+
+/// What a reload will do with a given diff.
+#[derive(Debug, Clone, Default)]
+pub struct ReloadPlan {
+    pub diff: ConfigDiff,
+    /// Changed sections applied without restarting the daemon.
+    pub hot_sections: Vec<String>,
+    /// Changed sections that keep their running values until the daemon restarts.
+    pub restart_required: Vec<String>,
+    /// Daemon components to restart with the new config.
+    pub components: BTreeSet<&'static str>,
+    /// Re-initialize process-wide observability (runtime trace).
+    pub reinit_observability: bool,
+}
+
+impl ReloadPlan {
+    pub fn from_diff(diff: ConfigDiff) -> Self {
+        let mut plan = Self::default();
+        for section in diff.sections() {
+            match section_impact(&section) {
+                SectionImpact::Hot(components) => {
+                    plan.components.extend(components.iter().copied());
+                    plan.reinit_observability |= section == "observability";
+                    plan.hot_sections.push(section);
+                }
+                SectionImpact::RestartRequired => plan.restart_required.push(section),
+            }
+        }
+        plan.diff = diff;
+        plan
+    }
+
+    /// JSON payload recorded in the audit log.
+    pub fn audit_details(&self) -> Value {
+        serde_json::json!({
+            "changed_paths": self.diff.changed_paths,
+            "hot_applied": self.hot_sections,
+            "restart_required": self.restart_required,
+            "restarted_components": self.components,
+        })
+    }
+}
+
+/// Build the config components should run with: the reloaded config, except
+/// that restart-required sections keep their running values.
+pub fn effective_config(running: &Config, reloaded: &Config, plan: &ReloadPlan) -> Result<Config> {
+    if plan.restart_required.is_empty() {
+        return Ok(reloaded.clone());
+    }
+
+    let running_json =
+        serde_json::to_value(running).context("Failed to serialize running config")?;
+    let mut merged =
+        serde_json::to_value(reloaded).context("Failed to serialize reloaded config")?;
+    if let (Some(merged), Some(running)) = (merged.as_object_mut(), running_json.as_object()) {
+        for section in &plan.restart_required {
+            keep_running_section(merged, running, section);
+        }
+    }
+
+    let mut config: Config =
+        serde_json::from_value(merged).context("Failed to rebuild merged config")?;
+    config.config_path = reloaded.config_path.clone();
+    config.workspace_dir = reloaded.workspace_dir.clone();
+    config.layers = reloaded.layers.clone();
+    Ok(config)
+}
+
+fn keep_running_section(merged: &mut Map<String, Value>, running: &Map<String, Value>, key: &str) {
+    match running.get(key) {
+        Some(value) => {
+            merged.insert(key.to_string(), value.clone());
+        }
+        None => {
+            merged.remove(key);
+        }
+    }
+}
+
+/// Polls the modification stamps of the config file and its overlays.
+pub struct ConfigWatcher {
+    paths: Vec<PathBuf>,
+    last_stamps: Vec<Option<ConfigFileStamp>>,
+}
+
+impl ConfigWatcher {
+    pub async fn new(paths: Vec<PathBuf>) -> Self {
+        let last_stamps = Self::stamps(&paths).await;
+        Self { paths, last_stamps }
+    }
+
+    async fn stamps(paths: &[PathBuf]) -> Vec<Option<ConfigFileStamp>> {
+        let mut stamps = Vec::with_capacity(paths.len());
+        for path in paths {
+            stamps.push(config_file_stamp(path).await);
+        }
+        stamps
+    }
+
+    /// Switch to a new set of files (e.g. after `extends` changed), keeping
+    /// the current stamps when the set is unchanged.
+    pub async fn set_paths(&mut self, paths: Vec<PathBuf>) {
+        if paths != self.paths {
+            self.last_stamps = Self::stamps(&paths).await;
+            self.paths = paths;
+        }
+    }
+
+    /// Returns `true` once per observed change to any watched file
+    /// (including an overlay being created or removed).
+    pub async fn changed(&mut self) -> bool {
+        let stamps = Self::stamps(&self.paths).await;
+        if stamps == self.last_stamps {
+            return false;
+        }
+        self.last_stamps = stamps;
+        true
+    }
+}
+
+/// Re-load and validate `config.toml`, then plan how to apply it.
+///
+/// Returns the config components should run with alongside the plan.
+pub fn load_and_plan(running: &Config) -> Result<(Config, ReloadPlan)> {
+    let reloaded = running.reload_from_disk()?;
+    let plan = ReloadPlan::from_diff(ConfigDiff::between(running, &reloaded)?);
+    let effective = effective_config(running, &reloaded, &plan)?;
+    Ok((effective, plan))
+}
+
+/// Record a config reload (applied or rejected) in the security audit log.
+pub fn audit_reload(config: &Config, outcome: std::result::Result<&ReloadPlan, &str>) {
+    let Some(zeroclaw_dir) = config.config_path.parent() else {
+        return;
+    };
+    let logger = match AuditLogger::new(config.security.audit.clone(), zeroclaw_dir.to_path_buf()) {
+        Ok(logger) => logger,
+        Err(e) => {
+            tracing::warn!("Config reload audit logger unavailable: {e}");
+            return;
+        }
+    };
+
+    let event = AuditEvent::new(AuditEventType::ConfigChange)
+        .with_actor("daemon".to_string(), None, None)
+        .with_action(
+            "config reload".to_string(),
+            "medium".to_string(),
+            false,
+            outcome.is_ok(),
+        );
+    let event = match outcome {
+        Ok(plan) => event
+            .with_result(true, None, 0, None)
+            .with_details(plan.audit_details()),
+        Err(error) => event.with_result(false, None, 0, Some(error.to_string())),
+    };
+
+    if let Err(e) = logger.log(&event) {
+        tracing::warn!("Failed to write config reload audit event: {e}");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn base_config(tmp: &TempDir) -> Config {
+        let mut config = Config::default();
+        config.config_path = tmp.path().join("config.toml");
+        config.workspace_dir = tmp.path().join("workspace");
+        config
+    }
+
+    #[test]
+    fn diff_reports_nested_paths_without_values() {
+        let tmp = TempDir::new().unwrap();
+        let old = base_config(&tmp);
+        let mut new = old.clone();
+        new.autonomy.allowed_commands.push("make".into());
+        new.cron.enabled = !old.cron.enabled;
+        new.api_key = Some("sk-secret".into());
+
+        let diff = ConfigDiff::between(&old, &new).unwrap();
+        assert_eq!(
+            diff.changed_paths,
+            vec!["api_key", "autonomy.allowed_commands", "cron.enabled"]
+        );
+        assert!(!format!("{diff:?}").contains("sk-secret"));
+        assert!(ConfigDiff::between(&old, &old.clone()).unwrap().is_empty());
+    }
+
+    #[test]
+    fn plan_splits_hot_and_restart_required_sections() {
+        let tmp = TempDir::new().unwrap();
+        let old = base_config(&tmp);
+        let mut new = old.clone();
+        new.autonomy.max_actions_per_hour += 1;
+        new.memory.backend = "markdown".into();
+        new.observability.runtime_trace_mode = "rolling".into();
+
+        let plan = ReloadPlan::from_diff(ConfigDiff::between(&old, &new).unwrap());
+        assert_eq!(plan.hot_sections, vec!["autonomy", "observability"]);
+        assert_eq!(plan.restart_required, vec!["memory"]);
+        assert!(plan.reinit_observability);
+        assert_eq!(plan.components.len(), COMPONENTS.len());
+
+        let details = plan.audit_details();
+        assert_eq!(details["restart_required"], serde_json::json!(["memory"]));
+    }
+
+    #[test]
+    fn channel_and_provider_changes_restart_only_affected_components() {
+        assert_eq!(
+            section_impact("channels_config"),
+            SectionImpact::Hot(&["channels"])
+        );
+        let SectionImpact::Hot(components) = section_impact("default_model") else {
+            panic!("default_model should be hot-applied");
+        };
+        assert!(!components.contains(&"channels"));
+        assert_eq!(section_impact("secrets"), SectionImpact::RestartRequired);
+        assert_eq!(
+            section_impact("unknown_future_section"),
+            SectionImpact::RestartRequired
+        );
+    }
+
+    #[test]
+    fn effective_config_keeps_running_restart_required_sections() {
+        let tmp = TempDir::new().unwrap();
+        let old = base_config(&tmp);
+        let mut new = old.clone();
+        new.memory.backend = "markdown".into();
+        new.autonomy.allowed_commands = vec!["cargo".into()];
+
+        let plan = ReloadPlan::from_diff(ConfigDiff::between(&old, &new).unwrap());
+        let effective = effective_config(&old, &new, &plan).unwrap();
+        assert_eq!(effective.memory.backend, old.memory.backend);
+        assert_eq!(
+            effective.autonomy.allowed_commands,
+            vec!["cargo".to_string()]
+        );
+        assert_eq!(effective.config_path, new.config_path);
+        assert_eq!(effective.workspace_dir, new.workspace_dir);
+    }
+
+    #[tokio::test]
+    async fn load_and_plan_rejects_invalid_config() {
+        let tmp = TempDir::new().unwrap();
+        let running = base_config(&tmp);
+        let mut broken = running.clone();
+        broken.gateway.host = String::new();
+        broken.save().await.unwrap();
+
+        let err = load_and_plan(&running).unwrap_err();
+        assert!(format!("{err:#}").contains("gateway.host"));
+    }
+
+    #[tokio::test]
+    async fn watcher_fires_once_per_change_and_audit_records_diff() {
+        let tmp = TempDir::new().unwrap();
+        let mut running = base_config(&tmp);
+        running.save().await.unwrap();
+        let mut watcher = ConfigWatcher::new(layers::watch_paths(&running)).await;
+        assert!(!watcher.changed().await);
+
+        let mut edited = running.clone();
+        edited.autonomy.allowed_commands = vec!["cargo".into(), "git".into(), "ls".into()];
+        edited.save().await.unwrap();
+        assert!(watcher.changed().await);
+        assert!(!watcher.changed().await);
+
+        running.security.audit.enabled = true;
+        let (effective, plan) = load_and_plan(&running).unwrap();
+        assert_eq!(plan.hot_sections, vec!["autonomy"]);
+        assert_eq!(effective.autonomy.allowed_commands.len(), 3);
+
+        audit_reload(&running, Ok(&plan));
+        let log =
+            std::fs::read_to_string(tmp.path().join(&running.security.audit.log_path)).unwrap();
+        assert!(log.contains("\"event_type\":\"config_change\""));
+        assert!(log.contains("autonomy.allowed_commands"));
+    }
+}
@@ -1,8 +1,10 @@
 use crate::config::Config;
-use anyhow::Result;
+use crate::observability::runtime_trace::RuntimeTraceEvent;
+use crate::observability::trace_query::{self, TraceFilter};
+use anyhow::{Context, Result};
 use chrono::{DateTime, Utc};
 use std::io::Write;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 const DAEMON_STALE_SECONDS: i64 = 30;
 const SCHEDULER_STALE_SECONDS: i64 = 120;
@@ -81,6 +83,7 @@ pub fn diagnose(config: &Config) -> Vec<DiagResult> {
 
     check_config_semantics(config, &mut items);
     check_workspace(config, &mut items);
+    check_tool_modes(config, &mut items);
     check_daemon_state(config, &mut items);
     check_environment(&mut items);
     check_cli_tools(&mut items);
@@ -332,19 +335,53 @@ pub async fn run_models(
     Ok(())
 }
 
-pub fn run_traces(
-    config: &Config,
-    id: Option<&str>,
-    event_filter: Option<&str>,
-    contains: Option<&str>,
-    limit: usize,
-) -> Result<()> {
+/// Aggregate views for `zeroclaw doctor traces --view`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TraceView {
+    SlowestTools,
+    FailingTools,
+    TokensPerTurn,
+    ParseFailures,
+}
+
+/// Export formats for `zeroclaw doctor traces --export`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TraceExportFormat {
+    Csv,
+    OtlpJson,
+}
+
+/// Options for `zeroclaw doctor traces`.
+#[derive(Debug, Clone, Default)]
+pub struct TracesOptions {
+    /// Show one event by id (ignores every other option).
+    pub id: Option<String>,
+    pub filter: TraceFilter,
+    /// Row limit; listing defaults to 20, views and exports cover all matches when unset.
+    pub limit: Option<usize>,
+    pub view: Option<TraceView>,
+    pub export: Option<TraceExportFormat>,
+    /// Export destination; stdout when unset.
+    pub output: Option<PathBuf>,
+    /// Keep printing matching events as they are recorded.
+    pub follow: bool,
+}
+
+const DEFAULT_TRACE_LIST_LIMIT: usize = 20;
+const TRACE_FOLLOW_POLL: std::time::Duration = std::time::Duration::from_secs(1);
+
+pub async fn run_traces(config: &Config, options: &TracesOptions) -> Result<()> {
     let path = crate::observability::runtime_trace::resolve_trace_path(
         &config.observability,
         &config.workspace_dir,
     );
 
-    if let Some(target_id) = id.map(str::trim).filter(|value| !value.is_empty()) {
+    if let Some(target_id) = options
+        .id
+        .as_deref()
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+    {
         match crate::observability::runtime_trace::find_event_by_id(&path, target_id)? {
             Some(event) => {
                 println!("{}", serde_json::to_string_pretty(&event)?);
@@ -360,7 +397,7 @@ pub fn run_traces(
         return Ok(());
     }
 
-    if !path.exists() {
+    if !path.exists() && !options.follow {
         println!(
             "Runtime trace file not found: {}.\n\
              Enable [observability] runtime_trace_mode = \"rolling\" or \"full\", then reproduce the issue.",
@@ -369,15 +406,49 @@ pub fn run_traces(
         return Ok(());
     }
 
-    let safe_limit = limit.max(1);
-    let events = crate::observability::runtime_trace::load_events(
-        &path,
-        safe_limit,
-        event_filter,
-        contains,
-    )?;
+    let all_events = crate::observability::runtime_trace::read_events(&path)?;
+    let mut events: Vec<RuntimeTraceEvent> = all_events
+        .iter()
+        .filter(|event| options.filter.matches(event))
+        .cloned()
+        .collect();
+
+    if let Some(format) = options.export {
+        if let Some(limit) = options.limit {
+            let keep_from = events.len().saturating_sub(limit.max(1));
+            events.drain(..keep_from);
+        }
+        return export_traces(config, &events, format, options.output.as_deref());
+    }
+
+    if let Some(view) = options.view {
+        print_trace_view(&path, &options.filter, &events, view, options.limit);
+        return Ok(());
+    }
+
+    let limit = options.limit.unwrap_or(DEFAULT_TRACE_LIST_LIMIT).max(1);
+    let keep_from = events.len().saturating_sub(limit);
+    let recent = &events[keep_from..];
 
-    if events.is_empty() {
+    if options.follow {
+        println!(
+            "Following runtime traces (Ctrl+C to stop). Path: {}",
+            path.display()
+        );
+        println!("Filters: {}", options.filter.summary());
+        println!();
+        for event in recent {
+            print_trace_line(event);
+        }
+        return follow_traces(
+            &path,
+            &options.filter,
+            all_events.last().map(|event| event.id.clone()),
+        )
+        .await;
+    }
+
+    if recent.is_empty() {
         println!(
             "No runtime trace events matched query (path: {}).",
             path.display()
@@ -387,26 +458,11 @@ pub fn run_traces(
 
     println!("Runtime traces (newest first)");
     println!("Path: {}", path.display());
-    println!(
-        "Filters: event={} contains={} limit={}",
-        event_filter.unwrap_or("*"),
-        contains.unwrap_or("*"),
-        safe_limit
-    );
+    println!("Filters: {} limit={}", options.filter.summary(), limit);
     println!();
 
-    for event in events {
-        let success = match event.success {
-            Some(true) => "ok",
-            Some(false) => "fail",
-            None => "-",
-        };
-        let message = event.message.unwrap_or_default();
-        let preview = truncate_for_display(&message, 80);
-        println!(
-            "- {} | {} | {} | {} | {}",
-            event.timestamp, event.id, event.event_type, success, preview
-        );
+    for event in recent.iter().rev() {
+        print_trace_line(event);
     }
 
     println!();
@@ -414,6 +470,175 @@ pub fn run_traces(
     Ok(())
 }
 
+fn print_trace_line(event: &RuntimeTraceEvent) {
+    let success = match event.success {
+        Some(true) => "ok",
+        Some(false) => "fail",
+        None => "-",
+    };
+    let preview = truncate_for_display(event.message.as_deref().unwrap_or_default(), 80);
+    println!(
+        "- {} | {} | {} | {} | {}",
+        event.timestamp, event.id, event.event_type, success, preview
+    );
+}
+
+async fn follow_traces(
+    path: &Path,
+    filter: &TraceFilter,
+    mut last_seen_id: Option<String>,
+) -> Result<()> {
+    loop {
+        tokio::time::sleep(TRACE_FOLLOW_POLL).await;
+        let events = match crate::observability::runtime_trace::read_events(path) {
+            Ok(events) => events,
+            Err(err) => {
+                tracing::debug!("Runtime trace file not readable yet: {err}");
+                continue;
+            }
+        };
+        for event in trace_query::events_after(&events, last_seen_id.as_deref()) {
+            if filter.matches(event) {
+                print_trace_line(event);
+            }
+        }
+        if let Some(last) = events.last() {
+            last_seen_id = Some(last.id.clone());
+        }
+        std::io::stdout().flush()?;
+    }
+}
+
+fn export_traces(
+    config: &Config,
+    events: &[RuntimeTraceEvent],
+    format: TraceExportFormat,
+    output: Option<&Path>,
+) -> Result<()> {
+    let rendered = match format {
+        TraceExportFormat::Csv => trace_query::to_csv(events),
+        TraceExportFormat::OtlpJson => {
+            let service_name = config
+                .observability
+                .otel_service_name
+                .as_deref()
+                .unwrap_or("zeroclaw");
+            let mut json =
+                serde_json::to_string_pretty(&trace_query::to_otlp_json(events, service_name))?;
+            json.push('\n');
+            json
+        }
+    };
+
+    match output {
+        Some(path) => {
+            std::fs::write(path, rendered)
+                .with_context(|| format!("Failed to write trace export to {}", path.display()))?;
+            println!("Exported {} events to {}", events.len(), path.display());
+        }
+        None => print!("{rendered}"),
+    }
+    Ok(())
+}
+
+fn print_trace_view(
+    path: &Path,
+    filter: &TraceFilter,
+    events: &[RuntimeTraceEvent],
+    view: TraceView,
+    limit: Option<usize>,
+) {
+    let rows = limit.unwrap_or(DEFAULT_TRACE_LIST_LIMIT).max(1);
+    let title = match view {
+        TraceView::SlowestTools => "Slowest tools (by p95 duration)",
+        TraceView::FailingTools => "Most failing tools",
+        TraceView::TokensPerTurn => "Tokens per turn (heaviest first)",
+        TraceView::ParseFailures => "Tool-call parse failures per model",
+    };
+    println!("{title}");
+    println!("Path: {}", path.display());
+    println!("Filters: {} events={}", filter.summary(), events.len());
+    println!();
+
+    let printed = match view {
+        TraceView::SlowestTools | TraceView::FailingTools => {
+            let stats = if view == TraceView::SlowestTools {
+                trace_query::slowest_tools(events)
+            } else {
+                trace_query::failing_tools(events)
+            };
+            if !stats.is_empty() {
+                println!(
+                    "  {:<24} {:>7} {:>8} {:>7} {:>9} {:>9} {:>9}",
+                    "tool", "calls", "failures", "fail%", "avg_ms", "p95_ms", "max_ms"
+                );
+            }
+            for stat in stats.iter().take(rows) {
+                println!(
+                    "  {:<24} {:>7} {:>8} {:>6.1}% {:>9} {:>9} {:>9}",
+                    truncate_for_display(&stat.tool, 24),
+                    stat.calls,
+                    stat.failures,
+                    stat.failure_rate() * 100.0,
+                    stat.avg_ms,
+                    stat.p95_ms,
+                    stat.max_ms
+                );
+            }
+            stats.len()
+        }
+        TraceView::TokensPerTurn => {
+            let turns = trace_query::tokens_per_turn(events);
+            if !turns.is_empty() {
+                println!(
+                    "  {:<36} {:<25} {:<12} {:>5} {:>9} {:>9} {:>9}",
+                    "turn", "started", "channel", "calls", "input", "output", "total"
+                );
+            }
+            for turn in turns.iter().take(rows) {
+                println!(
+                    "  {:<36} {:<25} {:<12} {:>5} {:>9} {:>9} {:>9}",
+                    turn.turn_id,
+                    truncate_for_display(&turn.started, 25),
+                    truncate_for_display(turn.channel.as_deref().unwrap_or("-"), 12),
+                    turn.llm_calls,
+                    turn.input_tokens,
+                    turn.output_tokens,
+                    turn.total_tokens()
+                );
+            }
+            turns.len()
+        }
+        TraceView::ParseFailures => {
+            let stats = trace_query::parse_failures(events);
+            if !stats.is_empty() {
+                println!(
+                    "  {:<16} {:<36} {:>9} {:>12} {:>7}",
+                    "provider", "model", "responses", "parse_issues", "rate"
+                );
+            }
+            for stat in stats.iter().take(rows) {
+                println!(
+                    "  {:<16} {:<36} {:>9} {:>12} {:>6.1}%",
+                    truncate_for_display(&stat.provider, 16),
+                    truncate_for_display(&stat.model, 36),
+                    stat.responses,
+                    stat.parse_issues,
+                    stat.rate() * 100.0
+                );
+            }
+            stats.len()
+        }
+    };
+
+    if printed == 0 {
+        println!("  (no matching events)");
+    } else if printed > rows {
+        println!();
+        println!("  … {} more (raise --limit to see them)", printed - rows);
+    }
+}
+
 // ── Config semantic validation ───────────────────────────────────
 
 fn check_config_semantics(config: &Config, items: &mut Vec<DiagItem>) {
@@ -634,8 +859,24 @@ fn embedding_provider_validation_error(name: &str) -> Option<String> {
         return None;
     }
 
+    if let Some(model_dir) = normalized.strip_prefix("local:") {
+        if model_dir.trim().is_empty() {
+            return Some("local provider requires a model directory after 'local:'".into());
+        }
+        if !cfg!(feature = "embeddings-local") {
+            return Some("this build was compiled without `embeddings-local`".into());
+        }
+        if !std::path::Path::new(model_dir.trim()).is_dir() {
+            return Some(format!(
+                "local model directory not found: {}",
+                model_dir.trim()
+            ));
+        }
+        return None;
+    }
+
     let Some(url) = normalized.strip_prefix("custom:") else {
-        return Some("supported values: none, openai, custom:<url>".into());
+        return Some("supported values: none, openai, custom:<url>, local:<dir>".into());
     };
 
     let url = url.trim();
@@ -766,6 +1007,39 @@ fn workspace_probe_path(workspace_dir: &Path) -> std::path::PathBuf {
     ))
 }
 
+// ── Tool calling modes ───────────────────────────────────────────
+
+fn check_tool_modes(config: &Config, items: &mut Vec<DiagItem>) {
+    let cat = "tool modes";
+
+    let mut overrides: Vec<_> = config.agent.tool_modes.iter().collect();
+    overrides.sort_by(|a, b| a.0.cmp(b.0));
+    for (model, mode) in overrides {
+        items.push(DiagItem::ok(
+            cat,
+            format!("{model}: {} (configured)", mode.as_str()),
+        ));
+    }
+
+    for learned in crate::providers::tool_mode::learned_tool_modes(&config.workspace_dir) {
+        let key = format!("{}/{}", learned.provider, learned.model);
+        if config.agent.tool_modes.contains_key(&key)
+            || config.agent.tool_modes.contains_key(&learned.model)
+        {
+            continue;
+        }
+        items.push(DiagItem::warn(
+            cat,
+            format!(
+                "{key}: switched to {} on {} ({}); set agent.tool_modes.\"{key}\" = \"native\" to override",
+                learned.mode.as_str(),
+                learned.learned_at.format("%Y-%m-%d"),
+                learned.reason
+            ),
+        ));
+    }
+}
+
 // ── Daemon state (original logic, preserved) ─────────────────────
 
 fn check_daemon_state(config: &Config, items: &mut Vec<DiagItem>) {
@@ -1059,6 +1333,39 @@ mod tests {
         assert_eq!(plan_outcome, ModelProbeOutcome::AuthOrAccess);
     }
 
+    #[test]
+    fn tool_modes_report_learned_fallbacks_and_overrides() {
+        let tmp = TempDir::new().unwrap();
+        let mut config = Config {
+            workspace_dir: tmp.path().to_path_buf(),
+            ..Config::default()
+        };
+        config
+            .agent
+            .tool_modes
+            .insert("gpt-4o".into(), crate::providers::ToolMode::Native);
+        let modes = crate::providers::ToolModes::load(tmp.path(), std::collections::HashMap::new());
+        modes.record_xml_fallback(
+            "ollama",
+            "gemma2",
+            "ollama API error (400): does not support tools",
+        );
+        modes.record_xml_fallback("openai", "gpt-4o", "malformed arguments");
+
+        let mut items = Vec::new();
+        check_tool_modes(&config, &mut items);
+
+        assert_eq!(items.len(), 2);
+        assert_eq!(items[0].severity, Severity::Ok);
+        assert!(items[0].message.starts_with("gpt-4o: native"));
+        let learned = &items[1];
+        assert_eq!(learned.severity, Severity::Warn);
+        assert!(learned
+            .message
+            .starts_with("ollama/gemma2: switched to xml"));
+        assert!(learned.message.contains("does not support tools"));
+    }
+
     #[test]
     fn config_validation_catches_bad_temperature() {
         let mut config = Config::default();
@@ -1166,6 +1473,7 @@ mod tests {
             provider: "groq".into(),
             model: String::new(),
             api_key: None,
+            generation: Default::default(),
         }];
         let mut items = Vec::new();
         check_config_semantics(&config, &mut items);
@@ -1311,4 +1619,51 @@ mod tests {
         assert!(agent_messages[0].contains("agent \"alpha\""));
         assert!(agent_messages[1].contains("agent \"zeta\""));
     }
+
+    #[tokio::test]
+    async fn run_traces_exports_filtered_events_to_csv() {
+        let tmp = TempDir::new().unwrap();
+        let mut config = Config::default();
+        config.workspace_dir = tmp.path().to_path_buf();
+        config.observability.runtime_trace_path = "trace.jsonl".into();
+
+        let lines: Vec<String> = [("a", "shell", false), ("b", "file_read", true)]
+            .iter()
+            .map(|(id, tool, success)| {
+                serde_json::to_string(&RuntimeTraceEvent {
+                    id: (*id).into(),
+                    timestamp: Utc::now().to_rfc3339(),
+                    event_type: "tool_call_result".into(),
+                    channel: Some("cli".into()),
+                    provider: None,
+                    model: None,
+                    turn_id: Some("turn-1".into()),
+                    success: Some(*success),
+                    message: None,
+                    payload: serde_json::json!({ "tool": tool, "duration_ms": 7 }),
+                })
+                .unwrap()
+            })
+            .collect();
+        std::fs::write(tmp.path().join("trace.jsonl"), lines.join("\n")).unwrap();
+
+        let output = tmp.path().join("out.csv");
+        let options = TracesOptions {
+            filter: TraceFilter {
+                success: Some(false),
+                since: Some(Utc::now() - chrono::Duration::hours(1)),
+                ..TraceFilter::default()
+            },
+            export: Some(TraceExportFormat::Csv),
+            output: Some(output.clone()),
+            ..TracesOptions::default()
+        };
+        run_traces(&config, &options).await.unwrap();
+
+        let csv = std::fs::read_to_string(output).unwrap();
+        let rows: Vec<_> = csv.lines().skip(1).collect();
+        assert_eq!(rows.len(), 1);
+        assert!(rows[0].starts_with("a,"));
+        assert!(rows[0].contains(",false,shell,7,"));
+    }
 }
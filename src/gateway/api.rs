@@ -483,6 +483,9 @@ pub async fn handle_api_cost(
                 "daily_cost_usd": 0.0,
                 "monthly_cost_usd": 0.0,
                 "total_tokens": 0,
+                "cache_read_tokens": 0,
+                "cache_write_tokens": 0,
+                "cache_savings_usd": 0.0,
                 "request_count": 0,
                 "by_model": {},
             }
@@ -505,6 +508,94 @@ pub async fn handle_api_cli_tools(
     Json(serde_json::json!({"cli_tools": tools})).into_response()
 }
 
+/// GET /api/goals — goals tracked by the goal loop
+pub async fn handle_api_goals(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+) -> impl IntoResponse {
+    if let Err(e) = require_auth(&state, &headers) {
+        return e.into_response();
+    }
+
+    let config = state.config.lock().clone();
+    let engine = crate::goals::engine::GoalEngine::new(&config.workspace_dir);
+    match engine.load_state().await {
+        Ok(goal_state) => Json(serde_json::json!({
+            "enabled": config.goal_loop.enabled,
+            "goals": goal_state.goals,
+        }))
+        .into_response(),
+        Err(e) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(serde_json::json!({"error": format!("Failed to load goals: {e}")})),
+        )
+            .into_response(),
+    }
+}
+
+/// GET /api/task-plans — saved named task plans
+pub async fn handle_api_task_plans_list(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+) -> impl IntoResponse {
+    if let Err(e) = require_auth(&state, &headers) {
+        return e.into_response();
+    }
+
+    let workspace_dir = state.config.lock().workspace_dir.clone();
+    let store = crate::tools::task_plan::TaskPlanStore::new(&workspace_dir);
+    match store.list() {
+        Ok(plans) => {
+            let plans_json: Vec<serde_json::Value> = plans
+                .iter()
+                .map(|plan| {
+                    serde_json::json!({
+                        "name": plan.name,
+                        "goal_id": plan.goal_id,
+                        "created_at": plan.created_at,
+                        "updated_at": plan.updated_at,
+                        "completed": plan.completed(),
+                        "total": plan.tasks.len(),
+                    })
+                })
+                .collect();
+            Json(serde_json::json!({"plans": plans_json})).into_response()
+        }
+        Err(e) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(serde_json::json!({"error": format!("Failed to list task plans: {e}")})),
+        )
+            .into_response(),
+    }
+}
+
+/// GET /api/task-plans/:name — one saved task plan with its tasks
+pub async fn handle_api_task_plan_get(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Path(name): Path<String>,
+) -> impl IntoResponse {
+    if let Err(e) = require_auth(&state, &headers) {
+        return e.into_response();
+    }
+
+    let workspace_dir = state.config.lock().workspace_dir.clone();
+    let store = crate::tools::task_plan::TaskPlanStore::new(&workspace_dir);
+    match store.get(&name) {
+        Ok(Some(plan)) => Json(serde_json::json!({"plan": plan})).into_response(),
+        Ok(None) => (
+            StatusCode::NOT_FOUND,
+            Json(serde_json::json!({"error": format!("Task plan '{name}' not found")})),
+        )
+            .into_response(),
+        Err(e) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(serde_json::json!({"error": format!("Failed to load task plans: {e}")})),
+        )
+            .into_response(),
+    }
+}
+
 /// GET /api/health — component health snapshot
 pub async fn handle_api_health(
     State(state): State<AppState>,
@@ -692,7 +783,7 @@ fn restore_embedding_route_api_keys(
     }
 }
 
-fn mask_sensitive_fields(config: &crate::config::Config) -> crate::config::Config {
+pub(crate) fn mask_sensitive_fields(config: &crate::config::Config) -> crate::config::Config {
     let mut masked = config.clone();
 
     mask_optional_secret(&mut masked.api_key);
@@ -982,6 +1073,7 @@ fn hydrate_config_for_save(
     // These are runtime-computed fields skipped from TOML serialization.
     incoming.config_path = current.config_path.clone();
     incoming.workspace_dir = current.workspace_dir.clone();
+    incoming.layers = current.layers.clone();
     incoming
 }
 
@@ -1032,6 +1124,7 @@ mod tests {
             provider: "openrouter".to_string(),
             model: "anthropic/claude-sonnet-4.6".to_string(),
             api_key: Some("route-model-key".to_string()),
+            generation: Default::default(),
         }];
         cfg.embedding_routes = vec![crate::config::schema::EmbeddingRouteConfig {
             hint: "semantic".to_string(),
@@ -1166,12 +1259,14 @@ mod tests {
                 provider: "openrouter".to_string(),
                 model: "anthropic/claude-sonnet-4.6".to_string(),
                 api_key: Some("route-model-key-1".to_string()),
+                generation: Default::default(),
             },
             crate::config::schema::ModelRouteConfig {
                 hint: "fast".to_string(),
                 provider: "openrouter".to_string(),
                 model: "openai/gpt-4.1-mini".to_string(),
                 api_key: Some("route-model-key-2".to_string()),
+                generation: Default::default(),
             },
         ];
         current.embedding_routes = vec![
@@ -1318,12 +1413,14 @@ mod tests {
                 provider: "openrouter".to_string(),
                 model: "anthropic/claude-sonnet-4.6".to_string(),
                 api_key: Some("route-model-key-1".to_string()),
+                generation: Default::default(),
             },
             crate::config::schema::ModelRouteConfig {
                 hint: "fast".to_string(),
                 provider: "openrouter".to_string(),
                 model: "openai/gpt-4.1-mini".to_string(),
                 api_key: Some("route-model-key-2".to_string()),
+                generation: Default::default(),
             },
         ];
         current.embedding_routes = vec![
@@ -1353,6 +1450,7 @@ mod tests {
                 provider: "openai".to_string(),
                 model: "gpt-4.1".to_string(),
                 api_key: Some(MASKED_SECRET.to_string()),
+                generation: Default::default(),
             });
         incoming
             .embedding_routes
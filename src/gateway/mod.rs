@@ -18,6 +18,7 @@ use crate::channels::{
 use crate::config::Config;
 use crate::cost::CostTracker;
 use crate::memory::{self, Memory, MemoryCategory};
+use crate::observability::{SpanKind, TraceSpan};
 use crate::providers::{self, ChatMessage, Provider};
 use crate::runtime;
 use crate::security::pairing::{constant_time_eq, is_public_bind, PairingGuard};
@@ -334,7 +335,9 @@ pub async fn run_gateway(host: &str, port: u16, config: Config) -> Result<()> {
 
     // ── Hooks ──────────────────────────────────────────────────────
     let hooks: Option<std::sync::Arc<crate::hooks::HookRunner>> = if config.hooks.enabled {
-        Some(std::sync::Arc::new(crate::hooks::HookRunner::new()))
+        Some(std::sync::Arc::new(crate::hooks::HookRunner::from_config(
+            &config,
+        )))
     } else {
         None
     };
@@ -385,11 +388,21 @@ pub async fn run_gateway(host: &str, port: u16, config: Config) -> Result<()> {
         (None, None)
     };
 
+    // SSE broadcast channel for real-time events
+    let (event_tx, _event_rx) = tokio::sync::broadcast::channel::<serde_json::Value>(256);
+    // Wrap observer with broadcast capability for SSE
+    let broadcast_observer: Arc<dyn crate::observability::Observer> =
+        Arc::new(sse::BroadcastObserver::new(
+            crate::observability::create_observer(&config.observability),
+            event_tx.clone(),
+        ));
+
     let tools_registry_raw = tools::all_tools_with_runtime(
         Arc::new(config.clone()),
         &security,
         runtime,
         Arc::clone(&mem),
+        Arc::clone(&broadcast_observer),
         composio_key,
         composio_entity_id,
         &config.browser,
@@ -418,8 +431,6 @@ pub async fn run_gateway(host: &str, port: u16, config: Config) -> Result<()> {
         None
     };
 
-    // SSE broadcast channel for real-time events
-    let (event_tx, _event_rx) = tokio::sync::broadcast::channel::<serde_json::Value>(256);
     // Extract webhook secret for authentication
     let webhook_secret_hash: Option<Arc<str>> =
         config.channels_config.webhook.as_ref().and_then(|webhook| {
@@ -624,13 +635,6 @@ pub async fn run_gateway(host: &str, port: u16, config: Config) -> Result<()> {
         hooks.fire_gateway_start(host, actual_port).await;
     }
 
-    // Wrap observer with broadcast capability for SSE
-    let broadcast_observer: Arc<dyn crate::observability::Observer> =
-        Arc::new(sse::BroadcastObserver::new(
-            crate::observability::create_observer(&config.observability),
-            event_tx.clone(),
-        ));
-
     let state = AppState {
         config: config_state,
         provider,
@@ -696,6 +700,9 @@ pub async fn run_gateway(host: &str, port: u16, config: Config) -> Result<()> {
         .route("/api/cost", get(api::handle_api_cost))
         .route("/api/cli-tools", get(api::handle_api_cli_tools))
         .route("/api/health", get(api::handle_api_health))
+        .route("/api/goals", get(api::handle_api_goals))
+        .route("/api/task-plans", get(api::handle_api_task_plans_list))
+        .route("/api/task-plans/{name}", get(api::handle_api_task_plan_get))
         // ── SSE event stream ──
         .route("/api/events", get(sse::handle_sse_events))
         // ── WebSocket agent chat ──
@@ -710,6 +717,7 @@ pub async fn run_gateway(host: &str, port: u16, config: Config) -> Result<()> {
             StatusCode::REQUEST_TIMEOUT,
             Duration::from_secs(REQUEST_TIMEOUT_SECS),
         ))
+        .layer(axum::middleware::from_fn(trace_request))
         // ── SPA fallback: non-API GET requests serve index.html ──
         .fallback(get(static_files::handle_spa_fallback));
 
@@ -727,6 +735,35 @@ pub async fn run_gateway(host: &str, port: u16, config: Config) -> Result<()> {
 // AXUM HANDLERS
 // ══════════════════════════════════════════════════════════════════════════════
 
+/// Run each request inside a server span that continues the caller's trace
+/// when the request carries a W3C `traceparent` header.
+async fn trace_request(
+    request: axum::extract::Request,
+    next: axum::middleware::Next,
+) -> axum::response::Response {
+    let method = request.method().as_str().to_string();
+    let route = request
+        .extensions()
+        .get::<axum::extract::MatchedPath>()
+        .map_or("unmatched", axum::extract::MatchedPath::as_str)
+        .to_string();
+    let span = TraceSpan::start_from_headers(
+        format!("{method} {route}"),
+        SpanKind::Server,
+        request.headers(),
+    )
+    .with_attr("http.request.method", method)
+    .with_attr("http.route", route);
+
+    let response = span.instrument(next.run(request)).await;
+    let status = response.status();
+    span.set_attr("http.response.status_code", u32::from(status.as_u16()));
+    if status.is_server_error() {
+        span.set_error(status.canonical_reason().unwrap_or("server error"));
+    }
+    response
+}
+
 /// GET /health — always public (no secrets leaked)
 async fn handle_health(State(state): State<AppState>) -> impl IntoResponse {
     let body = serde_json::json!({
@@ -743,6 +780,8 @@ const PROMETHEUS_CONTENT_TYPE: &str = "text/plain; version=0.0.4; charset=utf-8"
 
 /// GET /metrics — Prometheus text exposition format
 async fn handle_metrics(State(state): State<AppState>) -> impl IntoResponse {
+    refresh_scrape_gauges(&state).await;
+
     let body = if let Some(prom) = state
         .observer
         .as_ref()
@@ -761,6 +800,43 @@ async fn handle_metrics(State(state): State<AppState>) -> impl IntoResponse {
     )
 }
 
+/// Sample point-in-time gauges (spend vs. budget, memory size) right before a scrape.
+async fn refresh_scrape_gauges(state: &AppState) {
+    use crate::observability::traits::ObserverMetric;
+
+    if let Some(tracker) = state.cost_tracker.as_ref() {
+        let (daily_limit, monthly_limit) = {
+            let config = state.config.lock();
+            (config.cost.daily_limit_usd, config.cost.monthly_limit_usd)
+        };
+        match tracker.get_summary() {
+            Ok(summary) => {
+                for (period, spent_usd, limit_usd) in [
+                    ("daily", summary.daily_cost_usd, daily_limit),
+                    ("monthly", summary.monthly_cost_usd, monthly_limit),
+                ] {
+                    state.observer.record_metric(&ObserverMetric::CostBudget {
+                        period: period.into(),
+                        spent_usd,
+                        limit_usd,
+                    });
+                }
+            }
+            Err(e) => tracing::debug!("Cost summary unavailable for /metrics: {e}"),
+        }
+    }
+
+    match state.mem.count().await {
+        Ok(count) => state
+            .observer
+            .record_metric(&ObserverMetric::MemoryEntries {
+                backend: state.mem.name().to_string(),
+                count: count as u64,
+            }),
+        Err(e) => tracing::debug!("Memory count unavailable for /metrics: {e}"),
+    }
+}
+
 /// POST /pair — exchange one-time code for bearer token
 #[axum::debug_handler]
 async fn handle_pair(
@@ -1821,6 +1897,53 @@ mod tests {
         let body = response.into_body().collect().await.unwrap().to_bytes();
         let text = String::from_utf8(body.to_vec()).unwrap();
         assert!(text.contains("zeroclaw_heartbeat_ticks_total 1"));
+        assert!(text.contains(r#"zeroclaw_memory_entries{backend="mock"} 0"#));
+    }
+
+    #[tokio::test]
+    async fn metrics_endpoint_sees_prometheus_through_broadcast_wrapper() {
+        let prom = crate::observability::PrometheusObserver::new();
+        let observer: Arc<dyn crate::observability::Observer> =
+            Arc::new(sse::BroadcastObserver::new(
+                Box::new(prom.clone()),
+                tokio::sync::broadcast::channel(16).0,
+            ));
+        observer.record_event(&crate::observability::ObserverEvent::ChannelRestart {
+            channel: "telegram".into(),
+        });
+
+        let state = AppState {
+            config: Arc::new(Mutex::new(Config::default())),
+            provider: Arc::new(MockProvider::default()),
+            model: "test-model".into(),
+            temperature: 0.0,
+            mem: Arc::new(MockMemory),
+            auto_save: false,
+            webhook_secret_hash: None,
+            pairing: Arc::new(PairingGuard::new(false, &[])),
+            trust_forwarded_headers: false,
+            rate_limiter: Arc::new(GatewayRateLimiter::new(100, 100, 100)),
+            idempotency_store: Arc::new(IdempotencyStore::new(Duration::from_secs(300), 1000)),
+            whatsapp: None,
+            whatsapp_app_secret: None,
+            linq: None,
+            linq_signing_secret: None,
+            nextcloud_talk: None,
+            nextcloud_talk_webhook_secret: None,
+            wati: None,
+            observer,
+            tools_registry: Arc::new(Vec::new()),
+            tools_registry_exec: None,
+            multimodal: crate::config::MultimodalConfig::default(),
+            max_tool_iterations: 5,
+            cost_tracker: None,
+            event_tx: tokio::sync::broadcast::channel(16).0,
+        };
+
+        let response = handle_metrics(State(state)).await.into_response();
+        let body = response.into_body().collect().await.unwrap().to_bytes();
+        let text = String::from_utf8(body.to_vec()).unwrap();
+        assert!(text.contains(r#"zeroclaw_channel_restarts_total{channel="telegram"} 1"#));
     }
 
     #[test]
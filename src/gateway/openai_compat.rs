@@ -5,7 +5,9 @@
 //! library, `curl`, Aura) to send chat requests through the gateway.
 
 use super::AppState;
-use crate::providers::traits::{ChatMessage, StreamOptions};
+use crate::providers::traits::{
+    ChatMessage, ChatRequest, GenerationParams, StreamChunk, StreamEvent, StreamOptions,
+};
 use axum::{
     body::Body,
     extract::{ConnectInfo, State},
@@ -39,6 +41,10 @@ pub struct ChatCompletionsRequest {
     /// Whether to stream the response as SSE events.
     #[serde(default)]
     pub stream: Option<bool>,
+    /// Sampling limits and reasoning controls (`max_tokens`, `top_p`, `stop`,
+    /// `seed`, penalties, `reasoning_effort`, `thinking_budget`).
+    #[serde(flatten)]
+    pub generation: GenerationParams,
 }
 
 #[derive(Debug, Deserialize)]
@@ -201,6 +207,17 @@ pub async fn handle_v1_chat_completions(
         return (StatusCode::BAD_REQUEST, Json(err)).into_response();
     }
 
+    if let Err(e) = request.generation.validate() {
+        let err = serde_json::json!({
+            "error": {
+                "message": format!("Invalid generation parameters: {e}"),
+                "type": "invalid_request_error",
+                "code": "invalid_generation_params"
+            }
+        });
+        return (StatusCode::BAD_REQUEST, Json(err)).into_response();
+    }
+
     let model = request
         .model
         .as_deref()
@@ -209,6 +226,7 @@ pub async fn handle_v1_chat_completions(
         .to_string();
     let temperature = request.temperature.unwrap_or(state.temperature);
     let stream = request.stream.unwrap_or(false);
+    let generation = request.generation.non_empty().cloned();
 
     // Convert messages to provider format
     let messages: Vec<ChatMessage> = request
@@ -242,6 +260,7 @@ pub async fn handle_v1_chat_completions(
             messages,
             model,
             temperature,
+            generation,
             provider_label,
             started_at,
         )
@@ -252,6 +271,7 @@ pub async fn handle_v1_chat_completions(
             messages,
             model,
             temperature,
+            generation,
             provider_label,
             started_at,
         )
@@ -266,14 +286,11 @@ async fn handle_non_streaming(
     messages: Vec<ChatMessage>,
     model: String,
     temperature: f64,
+    generation: Option<GenerationParams>,
     provider_label: String,
     started_at: Instant,
 ) -> impl IntoResponse {
-    match state
-        .provider
-        .chat_with_history(&messages, &model, temperature)
-        .await
-    {
+    match complete(&state, &messages, &model, temperature, generation.as_ref()).await {
         Ok(response_text) => {
             let duration = started_at.elapsed();
             record_success(&state, &provider_label, &model, duration);
@@ -327,28 +344,67 @@ async fn handle_non_streaming(
     }
 }
 
+/// Complete a conversation, sending generation params through a typed
+/// request when the client supplied any.
+async fn complete(
+    state: &AppState,
+    messages: &[ChatMessage],
+    model: &str,
+    temperature: f64,
+    generation: Option<&GenerationParams>,
+) -> anyhow::Result<String> {
+    if generation.is_none() {
+        return state
+            .provider
+            .chat_with_history(messages, model, temperature)
+            .await;
+    }
+    let response = state
+        .provider
+        .chat(
+            ChatRequest {
+                messages,
+                tools: None,
+                response_format: None,
+                params: generation,
+            },
+            model,
+            temperature,
+        )
+        .await?;
+    Ok(response.text.unwrap_or_default())
+}
+
 /// Streaming chat completions via SSE.
 fn handle_streaming(
     state: AppState,
     messages: Vec<ChatMessage>,
     model: String,
     temperature: f64,
+    generation: Option<GenerationParams>,
     provider_label: String,
     started_at: Instant,
 ) -> impl IntoResponse {
     let request_id = format!("chatcmpl-{}", Uuid::new_v4());
     let created = unix_timestamp();
 
-    if !state.provider.supports_streaming() {
+    // Generation params only reach providers through typed requests, so
+    // providers without typed streaming answer those in a single chunk too.
+    let typed_stream = generation.is_some() && state.provider.supports_stream_events();
+    if !state.provider.supports_streaming() || (generation.is_some() && !typed_stream) {
         // Provider doesn't support streaming — fall back to a single-chunk response
         let model_clone = model.clone();
         let id = request_id.clone();
 
         let stream = futures_util::stream::once(async move {
-            match state
-                .provider
-                .chat_with_history(&messages, &model_clone, temperature)
-                .await
+            match complete(
+                &state,
+                &messages,
+                &model_clone,
+                temperature,
+                generation.as_ref(),
+            )
+            .await
             {
                 Ok(text) => {
                     let duration = started_at.elapsed();
@@ -396,12 +452,36 @@ fn handle_streaming(
     }
 
     // Provider supports native streaming
-    let provider_stream = state.provider.stream_chat_with_history(
-        &messages,
-        &model,
-        temperature,
-        StreamOptions::new(true),
-    );
+    let provider_stream = if typed_stream {
+        state
+            .provider
+            .stream_chat(
+                ChatRequest {
+                    messages: &messages,
+                    tools: None,
+                    response_format: None,
+                    params: generation.as_ref(),
+                },
+                &model,
+                temperature,
+            )
+            .filter_map(|event| async move {
+                match event {
+                    Ok(StreamEvent::TextDelta(text)) => Some(Ok(StreamChunk::delta(text))),
+                    Ok(StreamEvent::Done) => Some(Ok(StreamChunk::final_chunk())),
+                    Ok(_) => None,
+                    Err(e) => Some(Err(e)),
+                }
+            })
+            .boxed()
+    } else {
+        state.provider.stream_chat_with_history(
+            &messages,
+            &model,
+            temperature,
+            StreamOptions::new(true),
+        )
+    };
 
     let model_for_stream = model.clone();
     let state_for_stream = state.clone();
@@ -615,6 +695,29 @@ mod tests {
         assert_eq!(req.temperature, Some(0.5));
         assert_eq!(req.stream, Some(true));
         assert_eq!(req.messages.len(), 2);
+        assert!(req.generation.is_empty());
+    }
+
+    #[test]
+    fn chat_completions_request_collects_generation_params() {
+        let json = r#"{
+            "messages": [{"role": "user", "content": "Hi"}],
+            "max_tokens": 64,
+            "top_p": 0.8,
+            "stop": "###",
+            "seed": 42,
+            "reasoning_effort": "high"
+        }"#;
+        let req: ChatCompletionsRequest = serde_json::from_str(json).unwrap();
+        assert_eq!(req.generation.max_tokens, Some(64));
+        assert_eq!(req.generation.top_p, Some(0.8));
+        assert_eq!(req.generation.stop, vec!["###".to_string()]);
+        assert_eq!(req.generation.seed, Some(42));
+        assert_eq!(
+            req.generation.reasoning_effort,
+            Some(crate::providers::ReasoningEffort::High)
+        );
+        assert!(req.generation.validate().is_ok());
     }
 
     #[test]
@@ -152,7 +152,8 @@ impl crate::observability::Observer for BroadcastObserver {
         "broadcast"
     }
 
+    /// Delegates to the wrapped observer so `/metrics` can find the Prometheus backend.
     fn as_any(&self) -> &dyn std::any::Any {
-        self
+        self.inner.as_any()
     }
 }
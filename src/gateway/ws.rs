@@ -4,15 +4,20 @@
 //! ```text
 //! Client -> Server: {"type":"message","content":"Hello"}
 //! Server -> Client: {"type":"chunk","content":"Hi! "}
+//! Server -> Client: {"type":"clear"}   (discard chunks received so far)
 //! Server -> Client: {"type":"tool_call","name":"shell","args":{...}}
 //! Server -> Client: {"type":"tool_result","name":"shell","output":"..."}
 //! Server -> Client: {"type":"done","full_response":"..."}
 //! ```
 
 use super::AppState;
-use crate::agent::loop_::run_tool_call_loop;
+use crate::agent::loop_::{
+    auto_compact_history, catalog_context_window, run_tool_call_loop, trim_history,
+    DRAFT_CLEAR_SENTINEL,
+};
 use crate::approval::ApprovalManager;
-use crate::providers::ChatMessage;
+use crate::cost::UsageRecorder;
+use crate::providers::{ChatMessage, ToolModes};
 use std::sync::Arc;
 use axum::{
     extract::{
@@ -155,9 +160,30 @@ async fn handle_socket(mut socket: WebSocket, state: AppState) {
     // Add system message to history
     history.push(ChatMessage::system(&system_prompt));
 
-    let approval_manager = {
+    let (approval_manager, tool_modes, usage_recorder, context_window) = {
         let config_guard = state.config.lock();
-        ApprovalManager::from_config(&config_guard.autonomy)
+        (
+            ApprovalManager::from_config(&config_guard.autonomy),
+            ToolModes::load(
+                &config_guard.workspace_dir,
+                config_guard.agent.tool_modes.clone(),
+            ),
+            state.cost_tracker.clone().map(|tracker| {
+                UsageRecorder::new(
+                    tracker,
+                    config_guard.cost.prices.clone(),
+                    &config_guard.workspace_dir,
+                )
+            }),
+            catalog_context_window(
+                &config_guard.workspace_dir,
+                config_guard
+                    .default_provider
+                    .as_deref()
+                    .unwrap_or("unknown"),
+                &state.model,
+            ),
+        )
     };
 
     while let Some(msg) = socket.recv().await {
@@ -205,11 +231,16 @@ async fn handle_socket(mut socket: WebSocket, state: AppState) {
             "model": state.model,
         }));
 
-        // Run the agent loop with tool execution
-        let result = run_tool_call_loop(
+        // Run the agent loop with tool execution, relaying draft deltas
+        // (streamed text and tool progress) while it runs.
+        let generation = state.config.lock().agent.generation.clone();
+        let no_tools = Arc::new(Vec::new());
+        let tools_registry = state.tools_registry_exec.as_ref().unwrap_or(&no_tools);
+        let (delta_tx, mut delta_rx) = tokio::sync::mpsc::channel::<String>(64);
+        let agent_loop = run_tool_call_loop(
             state.provider.as_ref(),
             &mut history,
-            state.tools_registry_exec.as_ref().unwrap_or(&Arc::new(Vec::new())).as_ref(),
+            tools_registry.as_ref(),
             state.observer.as_ref(),
             &provider_label,
             &state.model,
@@ -220,11 +251,24 @@ async fn handle_socket(mut socket: WebSocket, state: AppState) {
             &state.multimodal,
             state.max_tool_iterations,
             None, // cancellation token
-            None, // delta streaming
+            Some(delta_tx),
             None, // hooks
             &[],  // excluded tools
-        )
-        .await;
+            generation.non_empty(),
+            Some(&tool_modes),
+            usage_recorder.as_ref(),
+        );
+        let relay = async {
+            while let Some(delta) = delta_rx.recv().await {
+                let frame = if delta == DRAFT_CLEAR_SENTINEL {
+                    serde_json::json!({"type": "clear"})
+                } else {
+                    serde_json::json!({"type": "chunk", "content": delta})
+                };
+                let _ = socket.send(Message::Text(frame.to_string().into())).await;
+            }
+        };
+        let (result, ()) = tokio::join!(agent_loop, relay);
 
         match result {
             Ok(response) => {
@@ -233,6 +277,21 @@ async fn handle_socket(mut socket: WebSocket, state: AppState) {
                 // Add assistant response to history
                 history.push(ChatMessage::assistant(&safe_response));
 
+                // Compact before the next message, like the CLI between turns.
+                let max_history = state.config.lock().agent.max_history_messages;
+                if let Err(e) = auto_compact_history(
+                    &mut history,
+                    state.provider.as_ref(),
+                    &state.model,
+                    max_history,
+                    context_window,
+                )
+                .await
+                {
+                    tracing::warn!("WebSocket history compaction failed: {e}");
+                }
+                trim_history(&mut history, max_history);
+
                 // Send the full response as a done message
                 let done = serde_json::json!({
                     "type": "done",
@@ -1,11 +1,21 @@
+use crate::agent::outcome::TaskOutcome;
+use crate::util::truncate_with_ellipsis;
 use anyhow::Result;
 use serde::{Deserialize, Serialize};
-use std::fmt::Write as _;
+use std::collections::HashMap;
+use std::fmt::{self, Write as _};
+use std::future::Future;
 use std::path::{Path, PathBuf};
 
 /// Maximum retry attempts per step before marking the goal as blocked.
 const MAX_STEP_ATTEMPTS: u32 = 3;
 
+/// Maximum characters of agent output kept as a step result.
+const MAX_RESULT_CHARS: usize = 500;
+
+/// Maximum characters of a step result appended to the goal context.
+const MAX_CONTEXT_ENTRY_CHARS: usize = 200;
+
 // ── Data Structures ─────────────────────────────────────────────
 
 /// Root state persisted to `{workspace}/state/goals.json`.
@@ -47,6 +57,8 @@ pub enum GoalStatus {
     Completed,
     Blocked,
     Cancelled,
+    /// Temporarily excluded from the goal loop until resumed.
+    Paused,
 }
 
 impl<'de> Deserialize<'de> for GoalStatus {
@@ -57,11 +69,25 @@ impl<'de> Deserialize<'de> for GoalStatus {
             "completed" => Self::Completed,
             "blocked" => Self::Blocked,
             "cancelled" => Self::Cancelled,
+            "paused" => Self::Paused,
             _ => Self::Pending,
         })
     }
 }
 
+impl fmt::Display for GoalStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Pending => "pending",
+            Self::InProgress => "in_progress",
+            Self::Completed => "completed",
+            Self::Blocked => "blocked",
+            Self::Cancelled => "cancelled",
+            Self::Paused => "paused",
+        })
+    }
+}
+
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
 #[serde(rename_all = "snake_case")]
 pub enum GoalPriority {
@@ -120,6 +146,54 @@ impl<'de> Deserialize<'de> for StepStatus {
     }
 }
 
+/// Progress produced by a goal cycle, reported to the delivery channel.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GoalEvent {
+    StepCompleted {
+        goal: String,
+        step: String,
+        summary: String,
+    },
+    StepFailed {
+        goal: String,
+        step: String,
+        attempts: u32,
+        error: String,
+    },
+    GoalCompleted {
+        goal: String,
+    },
+    Reflected {
+        goal: String,
+        summary: String,
+    },
+}
+
+impl fmt::Display for GoalEvent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::StepCompleted {
+                goal,
+                step,
+                summary,
+            } => write!(f, "[Goal] {goal}\n✅ {step}\n{summary}"),
+            Self::StepFailed {
+                goal,
+                step,
+                attempts,
+                error,
+            } => write!(
+                f,
+                "[Goal] {goal}\n❌ {step} (attempt {attempts}/{MAX_STEP_ATTEMPTS})\n{error}"
+            ),
+            Self::GoalCompleted { goal } => write!(f, "[Goal] {goal}\n🎯 All steps completed"),
+            Self::Reflected { goal, summary } => {
+                write!(f, "[Goal] {goal}\n🤔 Reflection\n{summary}")
+            }
+        }
+    }
+}
+
 // ── GoalEngine ──────────────────────────────────────────────────
 
 pub struct GoalEngine {
@@ -158,6 +232,222 @@ impl GoalEngine {
         Ok(())
     }
 
+    /// Append a new in-progress goal. Without explicit steps the goal gets a
+    /// single step mirroring its description.
+    pub async fn add_goal(
+        &self,
+        description: &str,
+        priority: GoalPriority,
+        steps: &[String],
+    ) -> Result<Goal> {
+        let mut state = self.load_state().await?;
+        let next = state
+            .goals
+            .iter()
+            .filter_map(|g| g.id.strip_prefix('g')?.parse::<u64>().ok())
+            .max()
+            .unwrap_or(0)
+            + 1;
+        let descriptions: Vec<&str> = if steps.is_empty() {
+            vec![description]
+        } else {
+            steps.iter().map(String::as_str).collect()
+        };
+        let now = chrono::Utc::now().to_rfc3339();
+        let goal = Goal {
+            id: format!("g{next}"),
+            description: description.to_string(),
+            status: GoalStatus::InProgress,
+            priority,
+            created_at: now.clone(),
+            updated_at: now,
+            steps: descriptions
+                .iter()
+                .enumerate()
+                .map(|(i, d)| Step {
+                    id: format!("s{}", i + 1),
+                    description: (*d).to_string(),
+                    status: StepStatus::Pending,
+                    result: None,
+                    attempts: 0,
+                })
+                .collect(),
+            context: String::new(),
+            last_error: None,
+        };
+        state.goals.push(goal.clone());
+        self.save_state(&state).await?;
+        Ok(goal)
+    }
+
+    /// Set a goal's status and persist the change. Returns `false` when the
+    /// goal does not exist.
+    pub async fn set_goal_status(&self, goal_id: &str, status: GoalStatus) -> Result<bool> {
+        let mut state = self.load_state().await?;
+        let Some(goal) = state.goals.iter_mut().find(|g| g.id == goal_id) else {
+            return Ok(false);
+        };
+        goal.status = status;
+        goal.updated_at = chrono::Utc::now().to_rfc3339();
+        self.save_state(&state).await?;
+        Ok(true)
+    }
+
+    /// Apply the outcome of one step execution and persist it.
+    ///
+    /// State is re-read first so edits the agent made to `goals.json` while
+    /// executing the step are preserved.
+    pub async fn record_step_outcome(
+        &self,
+        goal_id: &str,
+        step_id: &str,
+        success: bool,
+        output: &str,
+    ) -> Result<Vec<GoalEvent>> {
+        let mut state = self.load_state().await?;
+        let Some(goal) = state.goals.iter_mut().find(|g| g.id == goal_id) else {
+            return Ok(Vec::new());
+        };
+        let Some(si) = goal.steps.iter().position(|s| s.id == step_id) else {
+            return Ok(Vec::new());
+        };
+
+        let summary = truncate_with_ellipsis(output.trim(), MAX_RESULT_CHARS);
+        let mut events = Vec::new();
+        let step = &mut goal.steps[si];
+        step.attempts += 1;
+
+        if success {
+            step.status = StepStatus::Completed;
+            step.result = Some(summary.clone());
+            let entry = truncate_with_ellipsis(&summary, MAX_CONTEXT_ENTRY_CHARS);
+            let _ = writeln!(goal.context, "- {}: {entry}", step.description);
+            events.push(GoalEvent::StepCompleted {
+                goal: goal.description.clone(),
+                step: goal.steps[si].description.clone(),
+                summary,
+            });
+            goal.last_error = None;
+            if goal.steps.iter().all(|s| s.status == StepStatus::Completed) {
+                goal.status = GoalStatus::Completed;
+                events.push(GoalEvent::GoalCompleted {
+                    goal: goal.description.clone(),
+                });
+            }
+        } else {
+            events.push(GoalEvent::StepFailed {
+                goal: goal.description.clone(),
+                step: step.description.clone(),
+                attempts: step.attempts,
+                error: summary.clone(),
+            });
+            goal.last_error = Some(summary);
+        }
+
+        goal.updated_at = chrono::Utc::now().to_rfc3339();
+        self.save_state(&state).await?;
+        Ok(events)
+    }
+
+    /// Run one goal cycle: execute up to `max_steps` actionable steps, then
+    /// reflect on stalled goals.
+    ///
+    /// `execute` runs a prompt through the agent and returns its output.
+    /// `assess` judges a step's prompt and output with a structured verdict;
+    /// if it fails, [`Self::interpret_result`] decides instead.
+    /// `reflected` remembers the state each stalled goal was last reflected
+    /// on, so an unchanged goal is not reflected on again every cycle.
+    pub async fn run_cycle<F, Fut, A, AFut>(
+        &self,
+        max_steps: u32,
+        reflected: &mut HashMap<String, String>,
+        mut execute: F,
+        mut assess: A,
+    ) -> Result<Vec<GoalEvent>>
+    where
+        F: FnMut(String) -> Fut,
+        Fut: Future<Output = Result<String>>,
+        A: FnMut(String, String) -> AFut,
+        AFut: Future<Output = Result<TaskOutcome>>,
+    {
+        let mut events = Vec::new();
+
+        for _ in 0..max_steps {
+            let state = self.load_state().await?;
+            let Some((gi, si)) = Self::select_next_actionable(&state) else {
+                break;
+            };
+            let goal = &state.goals[gi];
+            let step = &goal.steps[si];
+            let prompt = Self::build_step_prompt(goal, step);
+
+            let (success, output) = match execute(prompt.clone()).await {
+                Ok(output) => match assess(prompt, output.clone()).await {
+                    Ok(outcome) => (outcome.succeeded(), output),
+                    Err(e) => {
+                        tracing::warn!("Goal step assessment failed, using heuristic: {e}");
+                        (Self::interpret_result(&output), output)
+                    }
+                },
+                Err(e) => (false, format!("Error: {e}")),
+            };
+            events.extend(
+                self.record_step_outcome(&goal.id, &step.id, success, &output)
+                    .await?,
+            );
+        }
+
+        let state = self.load_state().await?;
+        for gi in Self::find_stalled_goals(&state) {
+            let goal = &state.goals[gi];
+            if reflected.get(&goal.id) == Some(&fingerprint(goal)) {
+                continue;
+            }
+
+            let summary = match execute(Self::build_reflection_prompt(goal)).await {
+                Ok(output) => truncate_with_ellipsis(output.trim(), MAX_RESULT_CHARS),
+                Err(e) => format!("Reflection failed: {e}"),
+            };
+            events.push(GoalEvent::Reflected {
+                goal: goal.description.clone(),
+                summary,
+            });
+
+            let after = self.load_state().await?;
+            let current = after.goals.iter().find(|g| g.id == goal.id).unwrap_or(goal);
+            reflected.insert(goal.id.clone(), fingerprint(current));
+        }
+
+        Ok(events)
+    }
+
+    /// Set the status of one step and persist the change.
+    ///
+    /// A pending goal is moved to in-progress once any of its steps starts.
+    /// Returns `false` when the goal or step does not exist.
+    pub async fn set_step_status(
+        &self,
+        goal_id: &str,
+        step_id: &str,
+        status: StepStatus,
+    ) -> Result<bool> {
+        let mut state = self.load_state().await?;
+        let Some(goal) = state.goals.iter_mut().find(|g| g.id == goal_id) else {
+            return Ok(false);
+        };
+        let Some(step) = goal.steps.iter_mut().find(|s| s.id == step_id) else {
+            return Ok(false);
+        };
+
+        step.status = status;
+        if goal.status == GoalStatus::Pending && step.status != StepStatus::Pending {
+            goal.status = GoalStatus::InProgress;
+        }
+        goal.updated_at = chrono::Utc::now().to_rfc3339();
+        self.save_state(&state).await?;
+        Ok(true)
+    }
+
     /// Select the next actionable (goal_index, step_index) pair.
     ///
     /// Strategy: highest-priority in-progress goal, first pending step
@@ -241,7 +531,8 @@ impl GoalEngine {
         prompt
     }
 
-    /// Simple heuristic: output containing error indicators → failure.
+    /// Fallback heuristic when no structured assessment is available:
+    /// output containing error indicators → failure.
     pub fn interpret_result(output: &str) -> bool {
         let lower = output.to_ascii_lowercase();
         let failure_indicators = [
@@ -328,11 +619,25 @@ impl GoalEngine {
     }
 }
 
+/// Snapshot of a goal used to detect whether it changed since reflection.
+fn fingerprint(goal: &Goal) -> String {
+    serde_json::to_string(goal).unwrap_or_default()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::agent::outcome::OutcomeStatus;
     use tempfile::TempDir;
 
+    /// Assessment stub that approves every step.
+    async fn approve(_prompt: String, output: String) -> Result<TaskOutcome> {
+        Ok(TaskOutcome {
+            status: OutcomeStatus::Completed,
+            summary: output,
+        })
+    }
+
     fn sample_goal_state() -> GoalState {
         GoalState {
             goals: vec![
@@ -522,6 +827,28 @@ target = "oc_test"
         assert_eq!(loaded.goals[1].priority, GoalPriority::Medium);
     }
 
+    #[tokio::test]
+    async fn set_step_status_persists_and_starts_pending_goal() {
+        let tmp = TempDir::new().unwrap();
+        let engine = GoalEngine::new(tmp.path());
+        let mut state = sample_goal_state();
+        state.goals[1].status = GoalStatus::Pending;
+        engine.save_state(&state).await.unwrap();
+
+        assert!(engine
+            .set_step_status("g2", "s1", StepStatus::Completed)
+            .await
+            .unwrap());
+        assert!(!engine
+            .set_step_status("g2", "missing", StepStatus::Completed)
+            .await
+            .unwrap());
+
+        let loaded = engine.load_state().await.unwrap();
+        assert_eq!(loaded.goals[1].status, GoalStatus::InProgress);
+        assert_eq!(loaded.goals[1].steps[0].status, StepStatus::Completed);
+    }
+
     #[test]
     fn priority_ordering() {
         assert!(GoalPriority::Critical > GoalPriority::High);
@@ -928,4 +1255,207 @@ target = "oc_test"
             assert_eq!(*priority, parsed);
         }
     }
+
+    #[tokio::test]
+    async fn add_goal_assigns_ids_and_default_step() {
+        let tmp = TempDir::new().unwrap();
+        let engine = GoalEngine::new(tmp.path());
+        engine.save_state(&sample_goal_state()).await.unwrap();
+
+        let goal = engine
+            .add_goal("Tidy inbox", GoalPriority::Low, &[])
+            .await
+            .unwrap();
+        assert_eq!(goal.id, "g3");
+        assert_eq!(goal.status, GoalStatus::InProgress);
+        assert_eq!(goal.steps.len(), 1);
+        assert_eq!(goal.steps[0].id, "s1");
+        assert_eq!(goal.steps[0].description, "Tidy inbox");
+
+        let goal = engine
+            .add_goal(
+                "Ship",
+                GoalPriority::High,
+                &["Build".to_string(), "Release".to_string()],
+            )
+            .await
+            .unwrap();
+        assert_eq!(goal.id, "g4");
+        assert_eq!(goal.steps[1].id, "s2");
+        assert_eq!(engine.load_state().await.unwrap().goals.len(), 4);
+    }
+
+    #[tokio::test]
+    async fn paused_goal_is_not_actionable() {
+        let tmp = TempDir::new().unwrap();
+        let engine = GoalEngine::new(tmp.path());
+        engine.save_state(&sample_goal_state()).await.unwrap();
+
+        assert!(engine
+            .set_goal_status("g1", GoalStatus::Paused)
+            .await
+            .unwrap());
+        assert!(!engine
+            .set_goal_status("missing", GoalStatus::Paused)
+            .await
+            .unwrap());
+
+        let state = engine.load_state().await.unwrap();
+        assert_eq!(state.goals[0].status, GoalStatus::Paused);
+        // g2 is selected now that g1 is paused
+        assert_eq!(GoalEngine::select_next_actionable(&state), Some((1, 0)));
+
+        let json = serde_json::to_string(&state).unwrap();
+        let parsed: GoalState = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed.goals[0].status, GoalStatus::Paused);
+    }
+
+    #[tokio::test]
+    async fn run_cycle_executes_steps_and_completes_goal() {
+        let tmp = TempDir::new().unwrap();
+        let engine = GoalEngine::new(tmp.path());
+        engine.save_state(&sample_goal_state()).await.unwrap();
+
+        let mut prompts = Vec::new();
+        let mut reflected = HashMap::new();
+        let events = engine
+            .run_cycle(
+                3,
+                &mut reflected,
+                |prompt| {
+                    prompts.push(prompt);
+                    async { Ok("Done successfully".to_string()) }
+                },
+                approve,
+            )
+            .await
+            .unwrap();
+
+        // g1 s2, g1 s3 (goal completes), then g2 s1
+        assert_eq!(prompts.len(), 3);
+        assert!(prompts[0].contains("Current step: Setup environment"));
+        assert!(events.contains(&GoalEvent::GoalCompleted {
+            goal: "Build automation platform".into()
+        }));
+
+        let state = engine.load_state().await.unwrap();
+        assert_eq!(state.goals[0].status, GoalStatus::Completed);
+        assert!(state.goals[0]
+            .context
+            .contains("- Write code: Done successfully"));
+        assert_eq!(state.goals[1].steps[0].status, StepStatus::Completed);
+        assert_eq!(state.goals[1].steps[0].attempts, 1);
+    }
+
+    #[tokio::test]
+    async fn run_cycle_records_failures_and_reflects_once() {
+        let tmp = TempDir::new().unwrap();
+        let engine = GoalEngine::new(tmp.path());
+        let mut state = sample_goal_state();
+        state.goals.truncate(1);
+        state.goals[0].steps.truncate(2);
+        engine.save_state(&state).await.unwrap();
+
+        let mut reflected = HashMap::new();
+        let mut calls = 0;
+        let events = engine
+            .run_cycle(
+                5,
+                &mut reflected,
+                |_| {
+                    calls += 1;
+                    async { Err(anyhow::anyhow!("network down")) }
+                },
+                approve,
+            )
+            .await
+            .unwrap();
+
+        // Three failed attempts exhaust s2, then one reflection on the stalled goal.
+        assert_eq!(calls, MAX_STEP_ATTEMPTS as usize + 1);
+        assert!(matches!(
+            events[0],
+            GoalEvent::StepFailed { attempts: 1, .. }
+        ));
+        assert!(matches!(events.last(), Some(GoalEvent::Reflected { .. })));
+
+        let state = engine.load_state().await.unwrap();
+        assert_eq!(state.goals[0].steps[1].attempts, MAX_STEP_ATTEMPTS);
+        assert!(state.goals[0]
+            .last_error
+            .as_deref()
+            .unwrap()
+            .contains("network down"));
+
+        // Unchanged stalled goal is not reflected on again.
+        let events = engine
+            .run_cycle(
+                5,
+                &mut reflected,
+                |_| async { Ok("should not run".to_string()) },
+                approve,
+            )
+            .await
+            .unwrap();
+        assert!(events.is_empty());
+    }
+
+    #[tokio::test]
+    async fn run_cycle_trusts_structured_assessment_over_keywords() {
+        let tmp = TempDir::new().unwrap();
+        let engine = GoalEngine::new(tmp.path());
+        let mut state = sample_goal_state();
+        state.goals.truncate(1);
+        engine.save_state(&state).await.unwrap();
+
+        let mut reflected = HashMap::new();
+        let mut assessed = Vec::new();
+        let events = engine
+            .run_cycle(
+                1,
+                &mut reflected,
+                |_| async { Ok("Done. All tasks completed.".to_string()) },
+                |prompt, output| {
+                    assessed.push((prompt, output));
+                    async {
+                        Ok(TaskOutcome {
+                            status: OutcomeStatus::Failed,
+                            summary: "Tests were never run".into(),
+                        })
+                    }
+                },
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(assessed.len(), 1);
+        assert!(assessed[0].0.contains("Current step: Setup environment"));
+        assert_eq!(assessed[0].1, "Done. All tasks completed.");
+        assert!(matches!(
+            events[0],
+            GoalEvent::StepFailed { attempts: 1, .. }
+        ));
+    }
+
+    #[tokio::test]
+    async fn run_cycle_falls_back_to_heuristic_when_assessment_fails() {
+        let tmp = TempDir::new().unwrap();
+        let engine = GoalEngine::new(tmp.path());
+        let mut state = sample_goal_state();
+        state.goals.truncate(1);
+        engine.save_state(&state).await.unwrap();
+
+        let mut reflected = HashMap::new();
+        let events = engine
+            .run_cycle(
+                1,
+                &mut reflected,
+                |_| async { Ok("Failed to install package".to_string()) },
+                |_, _| async { Err(anyhow::anyhow!("provider unavailable")) },
+            )
+            .await
+            .unwrap();
+
+        assert!(matches!(events[0], GoalEvent::StepFailed { .. }));
+    }
 }
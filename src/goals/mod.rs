@@ -1 +1,84 @@
 pub mod engine;
+
+use crate::config::Config;
+use anyhow::Result;
+use engine::{GoalEngine, GoalPriority, GoalStatus, StepStatus};
+
+pub async fn handle_command(command: crate::GoalCommands, config: &Config) -> Result<()> {
+    let engine = GoalEngine::new(&config.workspace_dir);
+
+    match command {
+        crate::GoalCommands::List => {
+            let state = engine.load_state().await?;
+            if state.goals.is_empty() {
+                println!("No goals yet.");
+                println!("\nUsage:");
+                println!("  zeroclaw goals add 'Publish weekly report' --step 'Collect metrics'");
+                return Ok(());
+            }
+
+            println!("🎯 Goals ({}):", state.goals.len());
+            for goal in &state.goals {
+                let done = goal
+                    .steps
+                    .iter()
+                    .filter(|s| s.status == StepStatus::Completed)
+                    .count();
+                println!(
+                    "- {} | {} | {:?} | {}/{} steps | {}",
+                    goal.id,
+                    goal.status,
+                    goal.priority,
+                    done,
+                    goal.steps.len(),
+                    goal.description
+                );
+                if let Some(err) = &goal.last_error {
+                    println!("    last error: {err}");
+                }
+            }
+            if !config.goal_loop.enabled {
+                println!("\nNote: [goal_loop] is disabled; the daemon will not execute goals.");
+            }
+            Ok(())
+        }
+        crate::GoalCommands::Add {
+            description,
+            steps,
+            priority,
+        } => {
+            let priority = parse_priority(&priority)?;
+            let goal = engine.add_goal(&description, priority, &steps).await?;
+            println!("✅ Added goal {}", goal.id);
+            println!("  Goal : {}", goal.description);
+            for step in &goal.steps {
+                println!("  Step : {} {}", step.id, step.description);
+            }
+            Ok(())
+        }
+        crate::GoalCommands::Pause { id } => {
+            if !engine.set_goal_status(&id, GoalStatus::Paused).await? {
+                anyhow::bail!("Goal '{id}' not found");
+            }
+            println!("⏸️  Paused goal {id}");
+            Ok(())
+        }
+        crate::GoalCommands::Resume { id } => {
+            if !engine.set_goal_status(&id, GoalStatus::InProgress).await? {
+                anyhow::bail!("Goal '{id}' not found");
+            }
+            println!("▶️  Resumed goal {id}");
+            Ok(())
+        }
+    }
+}
+
+fn parse_priority(raw: &str) -> Result<GoalPriority> {
+    match raw.trim().to_ascii_lowercase().as_str() {
+        "low" => Ok(GoalPriority::Low),
+        "medium" => Ok(GoalPriority::Medium),
+        "high" => Ok(GoalPriority::High),
+        "critical" => Ok(GoalPriority::Critical),
+        other => anyhow::bail!("Invalid priority '{other}'. Use: low, medium, high, critical"),
+    }
+}
@@ -0,0 +1,764 @@
+//! User-defined hooks declared under `[[hooks.custom]]`.
+//!
+//! A [`CustomHook`] forwards the lifecycle events it subscribes to into an
+//! external executable, an HTTP endpoint or a WASM module. Every invocation
+//! receives one JSON document:
+//!
+//! ```json
+//! { "event": "before_tool_call", "hook": "policy", "data": { "name": "shell", "args": {} } }
+//! ```
+//!
+//! and answers with a verdict:
+//!
+//! ```json
+//! { "action": "continue", "data": { "args": { "command": "ls" } } }
+//! { "action": "cancel", "reason": "shell is blocked by policy" }
+//! ```
+//!
+//! Fields present in `data` replace the corresponding values; missing fields
+//! are left untouched, and an empty response means "continue unchanged".
+//! Errors, timeouts and malformed verdicts follow the hook's `on_failure`
+//! policy. Verdicts for void events are ignored.
+
+use anyhow::{bail, Context, Result};
+use async_trait::async_trait;
+use futures_util::StreamExt;
+use serde_json::{json, Map, Value};
+use std::collections::HashSet;
+use std::path::PathBuf;
+use std::process::Stdio;
+use std::time::Duration;
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWriteExt};
+
+use crate::channels::traits::ChannelMessage;
+use crate::config::{CustomHookConfig, CustomHookKind, HookFailurePolicy, WasmRuntimeConfig};
+use crate::providers::traits::{ChatMessage, ChatResponse};
+use crate::runtime::WasmRuntime;
+use crate::tools::traits::ToolResult;
+
+use super::traits::{HookHandler, HookResult};
+
+/// Lifecycle events a custom hook can subscribe to.
+pub const HOOK_EVENTS: &[&str] = &[
+    "on_gateway_start",
+    "on_gateway_stop",
+    "on_session_start",
+    "on_session_end",
+    "on_llm_input",
+    "on_llm_output",
+    "on_after_tool_call",
+    "on_message_sent",
+    "on_heartbeat_tick",
+    "before_model_resolve",
+    "before_prompt_build",
+    "before_llm_call",
+    "before_tool_call",
+    "on_message_received",
+    "on_message_sending",
+];
+
+/// Maximum verdict size accepted from a command or HTTP hook (1MB).
+const MAX_VERDICT_BYTES: usize = 1_048_576;
+
+/// Decision returned by an external hook.
+#[derive(Debug, Clone, PartialEq)]
+enum Verdict {
+    Continue(Map<String, Value>),
+    Cancel(String),
+}
+
+/// Hook handler backed by a `[[hooks.custom]]` entry.
+pub struct CustomHook {
+    config: CustomHookConfig,
+    events: HashSet<String>,
+    workspace_dir: PathBuf,
+    wasm_config: WasmRuntimeConfig,
+}
+
+impl CustomHook {
+    pub fn new(
+        config: CustomHookConfig,
+        workspace_dir: PathBuf,
+        wasm_config: WasmRuntimeConfig,
+    ) -> Self {
+        let events = config
+            .events
+            .iter()
+            .map(|event| event.trim().to_string())
+            .collect();
+        Self {
+            config,
+            events,
+            workspace_dir,
+            wasm_config,
+        }
+    }
+
+    fn subscribes(&self, event: &str) -> bool {
+        self.events.contains(event)
+    }
+
+    fn timeout(&self) -> Duration {
+        Duration::from_millis(self.config.timeout_ms.max(1))
+    }
+
+    async fn invoke(&self, event: &str, data: Value) -> Result<Verdict> {
+        let request = json!({
+            "event": event,
+            "hook": self.config.name,
+            "data": data,
+        });
+        let raw = tokio::time::timeout(self.timeout(), self.dispatch(&request))
+            .await
+            .map_err(|_| anyhow::anyhow!("timed out after {}ms", self.config.timeout_ms))??;
+        parse_verdict(&raw)
+    }
+
+    async fn dispatch(&self, request: &Value) -> Result<String> {
+        match self.config.kind {
+            CustomHookKind::Command => self.run_command(request).await,
+            CustomHookKind::Http => self.run_http(request).await,
+            CustomHookKind::Wasm => self.run_wasm(request).await,
+        }
+    }
+
+    async fn run_command(&self, request: &Value) -> Result<String> {
+        let program = self
+            .config
+            .command
+            .as_deref()
+            .map(str::trim)
+            .filter(|command| !command.is_empty())
+            .context("no command configured")?;
+        let mut cmd = tokio::process::Command::new(program);
+        cmd.args(&self.config.args)
+            .current_dir(&self.workspace_dir)
+            .env_clear()
+            .env("ZEROCLAW_HOOK_NAME", &self.config.name)
+            .env(
+                "ZEROCLAW_HOOK_EVENT",
+                request["event"].as_str().unwrap_or_default(),
+            )
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .kill_on_drop(true);
+        for var in ["PATH", "HOME", "LANG"] {
+            if let Ok(value) = std::env::var(var) {
+                cmd.env(var, value);
+            }
+        }
+
+        let mut child = cmd
+            .spawn()
+            .with_context(|| format!("failed to spawn '{program}'"))?;
+        if let Some(mut stdin) = child.stdin.take() {
+            // A hook that exits without reading its input is not an error.
+            let _ = stdin.write_all(request.to_string().as_bytes()).await;
+        }
+        // Drain stderr alongside stdout so a chatty hook cannot block on a
+        // full pipe; an oversized verdict drops (and kills) the child.
+        let stderr = child.stderr.take().map(|stderr| {
+            tokio::spawn(async move { read_bounded(stderr, MAX_VERDICT_BYTES).await })
+        });
+        let mut stdout = Vec::new();
+        if let Some(pipe) = child.stdout.take() {
+            pipe.take(MAX_VERDICT_BYTES as u64 + 1)
+                .read_to_end(&mut stdout)
+                .await?;
+        }
+        if stdout.len() > MAX_VERDICT_BYTES {
+            bail!("verdict exceeds {MAX_VERDICT_BYTES} bytes");
+        }
+        let status = child.wait().await?;
+        if !status.success() {
+            let stderr = match stderr {
+                Some(task) => task.await.ok().and_then(Result::ok).unwrap_or_default(),
+                None => Vec::new(),
+            };
+            bail!(
+                "'{program}' exited with {status}: {}",
+                String::from_utf8_lossy(&stderr).trim()
+            );
+        }
+        Ok(String::from_utf8_lossy(&stdout).into_owned())
+    }
+
+    async fn run_http(&self, request: &Value) -> Result<String> {
+        let url = self
+            .config
+            .url
+            .as_deref()
+            .map(str::trim)
+            .context("no url configured")?;
+        let client = crate::config::build_runtime_proxy_client("hook.http");
+        let mut builder = client.post(url).json(request);
+        for (name, value) in &self.config.headers {
+            builder = builder.header(name, value);
+        }
+        let response = builder.send().await?;
+        let status = response.status();
+        let mut chunks = response.bytes_stream();
+        let mut bytes = Vec::new();
+        while let Some(chunk) = chunks.next().await {
+            bytes.extend_from_slice(&chunk?);
+            if bytes.len() > MAX_VERDICT_BYTES {
+                bail!("verdict exceeds {MAX_VERDICT_BYTES} bytes");
+            }
+        }
+        let body = String::from_utf8_lossy(&bytes).into_owned();
+        if !status.is_success() {
+            bail!("HTTP {}: {}", status.as_u16(), body.trim());
+        }
+        Ok(body)
+    }
+
+    async fn run_wasm(&self, request: &Value) -> Result<String> {
+        let module = self
+            .config
+            .module
+            .as_deref()
+            .map(str::trim)
+            .context("no module configured")?
+            .to_string();
+        let runtime =
+            WasmRuntime::with_workspace(self.wasm_config.clone(), self.workspace_dir.clone());
+        let workspace_dir = self.workspace_dir.clone();
+        let payload = serde_json::to_vec(request)?;
+        let caps = runtime.default_capabilities();
+
+        let result = tokio::task::spawn_blocking(move || {
+            runtime.execute_module_with_input(&module, &workspace_dir, &caps, &payload)
+        })
+        .await
+        .context("WASM execution task failed")??;
+        if result.exit_code != 0 {
+            if result.stderr.is_empty() {
+                bail!("WASM module exited with code {}", result.exit_code);
+            }
+            bail!("{}", result.stderr);
+        }
+        Ok(result.stdout)
+    }
+
+    /// Run a modifying event and merge the verdict into `original`.
+    async fn run_modifying<T: Clone>(
+        &self,
+        event: &str,
+        data: Value,
+        original: T,
+        apply: impl FnOnce(T, &Map<String, Value>) -> Result<T>,
+    ) -> HookResult<T> {
+        let outcome = match self.invoke(event, data).await {
+            Ok(Verdict::Cancel(reason)) => return HookResult::Cancel(reason),
+            Ok(Verdict::Continue(changes)) if changes.is_empty() => {
+                return HookResult::Continue(original)
+            }
+            Ok(Verdict::Continue(changes)) => apply(original.clone(), &changes),
+            Err(e) => Err(e),
+        };
+        match outcome {
+            Ok(updated) => HookResult::Continue(updated),
+            Err(e) => {
+                tracing::warn!(
+                    hook = %self.config.name,
+                    event,
+                    policy = ?self.config.on_failure,
+                    "custom hook failed: {e:#}"
+                );
+                match self.config.on_failure {
+                    HookFailurePolicy::Open => HookResult::Continue(original),
+                    HookFailurePolicy::Closed => HookResult::Cancel(format!(
+                        "hook '{}' failed during {event}: {e}",
+                        self.config.name
+                    )),
+                }
+            }
+        }
+    }
+
+    /// Run a void event; the verdict is ignored and failures are only logged.
+    async fn notify(&self, event: &str, data: Value) {
+        if !self.subscribes(event) {
+            return;
+        }
+        if let Err(e) = self.invoke(event, data).await {
+            tracing::warn!(hook = %self.config.name, event, "custom hook failed: {e:#}");
+        }
+    }
+}
+
+/// Parse a hook response into a [`Verdict`]. Empty output means "continue".
+fn parse_verdict(raw: &str) -> Result<Verdict> {
+    let raw = raw.trim();
+    if raw.is_empty() {
+        return Ok(Verdict::Continue(Map::new()));
+    }
+    let value: Value = serde_json::from_str(raw).context("verdict is not valid JSON")?;
+    let Value::Object(mut verdict) = value else {
+        bail!("verdict must be a JSON object");
+    };
+    let action = verdict
+        .get("action")
+        .map(|action| action.as_str().context("verdict `action` must be a string"))
+        .transpose()?
+        .unwrap_or("continue")
+        .to_ascii_lowercase();
+    match action.as_str() {
+        "continue" => match verdict.remove("data") {
+            None | Some(Value::Null) => Ok(Verdict::Continue(Map::new())),
+            Some(Value::Object(changes)) => Ok(Verdict::Continue(changes)),
+            Some(_) => bail!("verdict `data` must be a JSON object"),
+        },
+        "cancel" => Ok(Verdict::Cancel(
+            verdict
+                .get("reason")
+                .and_then(Value::as_str)
+                .filter(|reason| !reason.trim().is_empty())
+                .unwrap_or("cancelled by custom hook")
+                .to_string(),
+        )),
+        other => bail!("unknown verdict action '{other}'; expected continue or cancel"),
+    }
+}
+
+/// Replace `current` with `changes[key]` when present; it must be a string.
+fn take_string(changes: &Map<String, Value>, key: &str, current: String) -> Result<String> {
+    match changes.get(key) {
+        None => Ok(current),
+        Some(Value::String(value)) => Ok(value.clone()),
+        Some(_) => bail!("verdict field `{key}` must be a string"),
+    }
+}
+
+#[async_trait]
+impl HookHandler for CustomHook {
+    fn name(&self) -> &str {
+        &self.config.name
+    }
+
+    fn priority(&self) -> i32 {
+        self.config.priority
+    }
+
+    async fn on_gateway_start(&self, host: &str, port: u16) {
+        self.notify("on_gateway_start", json!({ "host": host, "port": port }))
+            .await;
+    }
+
+    async fn on_gateway_stop(&self) {
+        self.notify("on_gateway_stop", json!({})).await;
+    }
+
+    async fn on_session_start(&self, session_id: &str, channel: &str) {
+        self.notify(
+            "on_session_start",
+            json!({ "session_id": session_id, "channel": channel }),
+        )
+        .await;
+    }
+
+    async fn on_session_end(&self, session_id: &str, channel: &str) {
+        self.notify(
+            "on_session_end",
+            json!({ "session_id": session_id, "channel": channel }),
+        )
+        .await;
+    }
+
+    async fn on_llm_input(&self, messages: &[ChatMessage], model: &str) {
+        self.notify(
+            "on_llm_input",
+            json!({ "messages": messages, "model": model }),
+        )
+        .await;
+    }
+
+    async fn on_llm_output(&self, response: &ChatResponse) {
+        let tool_calls: Vec<Value> = response
+            .tool_calls
+            .iter()
+            .map(|call| json!({ "id": call.id, "name": call.name, "arguments": call.arguments }))
+            .collect();
+        self.notify(
+            "on_llm_output",
+            json!({ "text": response.text, "tool_calls": tool_calls }),
+        )
+        .await;
+    }
+
+    async fn on_after_tool_call(&self, tool: &str, result: &ToolResult, duration: Duration) {
+        self.notify(
+            "on_after_tool_call",
+            json!({
+                "tool": tool,
+                "success": result.success,
+                "output": result.output,
+                "error": result.error,
+                "duration_ms": u64::try_from(duration.as_millis()).unwrap_or(u64::MAX),
+            }),
+        )
+        .await;
+    }
+
+    async fn on_message_sent(&self, channel: &str, recipient: &str, content: &str) {
+        self.notify(
+            "on_message_sent",
+            json!({ "channel": channel, "recipient": recipient, "content": content }),
+        )
+        .await;
+    }
+
+    async fn on_heartbeat_tick(&self) {
+        self.notify("on_heartbeat_tick", json!({})).await;
+    }
+
+    async fn before_model_resolve(
+        &self,
+        provider: String,
+        model: String,
+    ) -> HookResult<(String, String)> {
+        if !self.subscribes("before_model_resolve") {
+            return HookResult::Continue((provider, model));
+        }
+        let data = json!({ "provider": provider, "model": model });
+        self.run_modifying(
+            "before_model_resolve",
+            data,
+            (provider, model),
+            |(provider, model), changes| {
+                Ok((
+                    take_string(changes, "provider", provider)?,
+                    take_string(changes, "model", model)?,
+                ))
+            },
+        )
+        .await
+    }
+
+    async fn before_prompt_build(&self, prompt: String) -> HookResult<String> {
+        if !self.subscribes("before_prompt_build") {
+            return HookResult::Continue(prompt);
+        }
+        let data = json!({ "prompt": prompt });
+        self.run_modifying("before_prompt_build", data, prompt, |prompt, changes| {
+            take_string(changes, "prompt", prompt)
+        })
+        .await
+    }
+
+    async fn before_llm_call(
+        &self,
+        messages: Vec<ChatMessage>,
+        model: String,
+    ) -> HookResult<(Vec<ChatMessage>, String)> {
+        if !self.subscribes("before_llm_call") {
+            return HookResult::Continue((messages, model));
+        }
+        let data = json!({ "messages": messages, "model": model });
+        self.run_modifying(
+            "before_llm_call",
+            data,
+            (messages, model),
+            |(messages, model), changes| {
+                let messages = match changes.get("messages") {
+                    None => messages,
+                    Some(value) => serde_json::from_value(value.clone())
+                        .context("verdict field `messages` is not a valid message list")?,
+                };
+                Ok((messages, take_string(changes, "model", model)?))
+            },
+        )
+        .await
+    }
+
+    async fn before_tool_call(&self, name: String, args: Value) -> HookResult<(String, Value)> {
+        if !self.subscribes("before_tool_call") {
+            return HookResult::Continue((name, args));
+        }
+        let data = json!({ "name": name, "args": args });
+        self.run_modifying(
+            "before_tool_call",
+            data,
+            (name, args),
+            |(name, args), changes| {
+                let args = changes.get("args").cloned().unwrap_or(args);
+                Ok((take_string(changes, "name", name)?, args))
+            },
+        )
+        .await
+    }
+
+    async fn on_message_received(&self, message: ChannelMessage) -> HookResult<ChannelMessage> {
+        if !self.subscribes("on_message_received") {
+            return HookResult::Continue(message);
+        }
+        let data = json!({
+            "id": message.id,
+            "sender": message.sender,
+            "reply_target": message.reply_target,
+            "content": message.content,
+            "channel": message.channel,
+            "timestamp": message.timestamp,
+            "thread_ts": message.thread_ts,
+        });
+        self.run_modifying(
+            "on_message_received",
+            data,
+            message,
+            |mut message, changes| {
+                message.content = take_string(changes, "content", message.content)?;
+                message.reply_target = take_string(changes, "reply_target", message.reply_target)?;
+                match changes.get("thread_ts") {
+                    None => {}
+                    Some(Value::Null) => message.thread_ts = None,
+                    Some(Value::String(ts)) => message.thread_ts = Some(ts.clone()),
+                    Some(_) => bail!("verdict field `thread_ts` must be a string or null"),
+                }
+                Ok(message)
+            },
+        )
+        .await
+    }
+
+    async fn on_message_sending(
+        &self,
+        channel: String,
+        recipient: String,
+        content: String,
+    ) -> HookResult<(String, String, String)> {
+        if !self.subscribes("on_message_sending") {
+            return HookResult::Continue((channel, recipient, content));
+        }
+        let data = json!({ "channel": channel, "recipient": recipient, "content": content });
+        self.run_modifying(
+            "on_message_sending",
+            data,
+            (channel, recipient, content),
+            |(channel, recipient, content), changes| {
+                Ok((
+                    take_string(changes, "channel", channel)?,
+                    take_string(changes, "recipient", recipient)?,
+                    take_string(changes, "content", content)?,
+                ))
+            },
+        )
+        .await
+    }
+}
+
+/// Read at most `limit` bytes from `reader`, discarding the rest so the
+/// writer is never blocked on a full pipe.
+async fn read_bounded(reader: impl AsyncRead + Unpin, limit: usize) -> std::io::Result<Vec<u8>> {
+    let mut bytes = Vec::new();
+    let mut reader = reader.take(limit as u64);
+    reader.read_to_end(&mut bytes).await?;
+    tokio::io::copy(&mut reader.into_inner(), &mut tokio::io::sink()).await?;
+    Ok(bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hook_config(kind: CustomHookKind, events: &[&str]) -> CustomHookConfig {
+        CustomHookConfig {
+            name: "policy".into(),
+            events: events.iter().map(|event| (*event).to_string()).collect(),
+            kind,
+            command: None,
+            args: Vec::new(),
+            url: None,
+            headers: std::collections::HashMap::new(),
+            module: None,
+            timeout_ms: 5_000,
+            on_failure: HookFailurePolicy::Open,
+            priority: 0,
+        }
+    }
+
+    fn shell_hook(events: &[&str], script: &str) -> CustomHookConfig {
+        let mut config = hook_config(CustomHookKind::Command, events);
+        config.command = Some("sh".into());
+        config.args = vec!["-c".into(), script.into()];
+        config
+    }
+
+    fn build(config: CustomHookConfig) -> CustomHook {
+        CustomHook::new(config, std::env::temp_dir(), WasmRuntimeConfig::default())
+    }
+
+    #[test]
+    fn parse_verdict_defaults_to_continue() {
+        assert_eq!(parse_verdict("").unwrap(), Verdict::Continue(Map::new()));
+        assert_eq!(parse_verdict("{}").unwrap(), Verdict::Continue(Map::new()));
+        let Verdict::Continue(changes) =
+            parse_verdict(r#"{"action":"continue","data":{"prompt":"x"}}"#).unwrap()
+        else {
+            panic!("expected continue");
+        };
+        assert_eq!(changes["prompt"], "x");
+    }
+
+    #[test]
+    fn parse_verdict_cancel_uses_reason() {
+        assert_eq!(
+            parse_verdict(r#"{"action":"cancel","reason":"nope"}"#).unwrap(),
+            Verdict::Cancel("nope".into())
+        );
+        assert_eq!(
+            parse_verdict(r#"{"action":"CANCEL"}"#).unwrap(),
+            Verdict::Cancel("cancelled by custom hook".into())
+        );
+    }
+
+    #[test]
+    fn parse_verdict_rejects_malformed_input() {
+        assert!(parse_verdict("not json").is_err());
+        assert!(parse_verdict("[1]").is_err());
+        assert!(parse_verdict(r#"{"action":"explode"}"#).is_err());
+        assert!(parse_verdict(r#"{"data":"oops"}"#).is_err());
+    }
+
+    #[tokio::test]
+    async fn unsubscribed_events_pass_through_without_running() {
+        let mut config = hook_config(CustomHookKind::Command, &["before_tool_call"]);
+        config.command = Some("/nonexistent/hook".into());
+        config.on_failure = HookFailurePolicy::Closed;
+        let hook = build(config);
+
+        match hook.before_prompt_build("hello".into()).await {
+            HookResult::Continue(prompt) => assert_eq!(prompt, "hello"),
+            HookResult::Cancel(reason) => panic!("unexpected cancel: {reason}"),
+        }
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn command_hook_rewrites_tool_arguments() {
+        let hook = build(shell_hook(
+            &["before_tool_call"],
+            r#"cat >/dev/null; echo '{"data":{"args":{"command":"ls"}}}'"#,
+        ));
+
+        match hook
+            .before_tool_call("shell".into(), json!({"command": "rm -rf /"}))
+            .await
+        {
+            HookResult::Continue((name, args)) => {
+                assert_eq!(name, "shell");
+                assert_eq!(args, json!({"command": "ls"}));
+            }
+            HookResult::Cancel(reason) => panic!("unexpected cancel: {reason}"),
+        }
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn command_hook_receives_event_payload_and_can_cancel() {
+        let hook = build(shell_hook(
+            &["on_message_sending"],
+            r#"if grep -q '"content":"secret'; then echo '{"action":"cancel","reason":"redacted"}'; fi"#,
+        ));
+
+        let result = hook
+            .on_message_sending("telegram".into(), "alice".into(), "secret plan".into())
+            .await;
+        assert!(matches!(result, HookResult::Cancel(reason) if reason == "redacted"));
+
+        let result = hook
+            .on_message_sending("telegram".into(), "alice".into(), "hello".into())
+            .await;
+        assert!(matches!(result, HookResult::Continue((_, _, content)) if content == "hello"));
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn failure_policy_controls_errors() {
+        let mut config = shell_hook(&["before_prompt_build"], "exit 3");
+        let open = build(config.clone());
+        match open.before_prompt_build("hi".into()).await {
+            HookResult::Continue(prompt) => assert_eq!(prompt, "hi"),
+            HookResult::Cancel(reason) => panic!("fail-open hook cancelled: {reason}"),
+        }
+
+        config.on_failure = HookFailurePolicy::Closed;
+        let closed = build(config);
+        match closed.before_prompt_build("hi".into()).await {
+            HookResult::Cancel(reason) => assert!(reason.contains("policy"), "{reason}"),
+            HookResult::Continue(_) => panic!("fail-closed hook should cancel"),
+        }
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn timeout_is_a_failure() {
+        let mut config = shell_hook(&["before_prompt_build"], "sleep 5");
+        config.timeout_ms = 100;
+        config.on_failure = HookFailurePolicy::Closed;
+        let hook = build(config);
+
+        let started = std::time::Instant::now();
+        match hook.before_prompt_build("hi".into()).await {
+            HookResult::Cancel(reason) => assert!(reason.contains("timed out"), "{reason}"),
+            HookResult::Continue(_) => panic!("timed-out hook should cancel"),
+        }
+        assert!(started.elapsed() < Duration::from_secs(4));
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn oversized_command_verdict_is_rejected() {
+        let mut config = shell_hook(
+            &["before_prompt_build"],
+            "cat >/dev/null; head -c 2000000 /dev/zero | tr '\\0' ' '; sleep 5",
+        );
+        config.on_failure = HookFailurePolicy::Closed;
+        let hook = build(config);
+
+        let started = std::time::Instant::now();
+        match hook.before_prompt_build("hi".into()).await {
+            HookResult::Cancel(reason) => assert!(reason.contains("exceeds"), "{reason}"),
+            HookResult::Continue(_) => panic!("oversized verdict should cancel"),
+        }
+        assert!(started.elapsed() < Duration::from_secs(4));
+    }
+
+    #[tokio::test]
+    async fn oversized_http_verdict_is_rejected() {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            let (mut socket, _) = listener.accept().await.unwrap();
+            let mut request = [0u8; 4096];
+            let _ = socket.read(&mut request).await;
+            let body = vec![b' '; MAX_VERDICT_BYTES * 2];
+            let head = format!(
+                "HTTP/1.1 200 OK\r\ncontent-type: application/json\r\ncontent-length: {}\r\n\r\n",
+                body.len()
+            );
+            let _ = socket.write_all(head.as_bytes()).await;
+            let _ = socket.write_all(&body).await;
+        });
+
+        let mut config = hook_config(CustomHookKind::Http, &["before_prompt_build"]);
+        config.url = Some(format!("http://{addr}/hook"));
+        config.on_failure = HookFailurePolicy::Closed;
+        let hook = build(config);
+
+        match hook.before_prompt_build("hi".into()).await {
+            HookResult::Cancel(reason) => assert!(reason.contains("exceeds"), "{reason}"),
+            HookResult::Continue(_) => panic!("oversized verdict should cancel"),
+        }
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn invalid_verdict_fields_follow_failure_policy() {
+        let mut config = shell_hook(&["before_prompt_build"], r#"echo '{"data":{"prompt":42}}'"#);
+        config.on_failure = HookFailurePolicy::Closed;
+        let hook = build(config);
+
+        assert!(hook.before_prompt_build("hi".into()).await.is_cancel());
+    }
+}
@@ -28,6 +28,22 @@ impl HookRunner {
         }
     }
 
+    /// Build a runner with the built-in and `[[hooks.custom]]` handlers enabled in `config`.
+    pub fn from_config(config: &crate::config::Config) -> Self {
+        let mut runner = Self::new();
+        if config.hooks.builtin.command_logger {
+            runner.register(Box::new(super::builtin::CommandLoggerHook::new()));
+        }
+        for hook in &config.hooks.custom {
+            runner.register(Box::new(super::CustomHook::new(
+                hook.clone(),
+                config.workspace_dir.clone(),
+                config.runtime.wasm.clone(),
+            )));
+        }
+        runner
+    }
+
     /// Register a handler and re-sort by descending priority.
     pub fn register(&mut self, handler: Box<dyn HookHandler>) {
         self.handlers.push(handler);
@@ -65,10 +65,12 @@ pub(crate) mod onboard;
 pub mod peripherals;
 pub mod providers;
 pub mod rag;
+pub mod robot;
 pub mod runtime;
 pub(crate) mod security;
 pub(crate) mod service;
 pub(crate) mod skills;
+pub(crate) mod sop;
 pub mod tools;
 pub(crate) mod tunnel;
 pub mod update;
@@ -294,6 +296,61 @@ Examples:
     },
 }
 
+/// Goal subcommands
+#[derive(Subcommand, Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub enum GoalCommands {
+    /// List goals and their step progress
+    List,
+    /// Add a new goal for the daemon goal loop
+    #[command(long_about = "\
+Add a new goal to state/goals.json.
+
+The goal starts in progress and is picked up by the daemon goal loop \
+when [goal_loop] is enabled. Repeat --step to define ordered steps; \
+without steps the goal gets a single step mirroring its description.
+
+Examples:
+  zeroclaw goals add 'Publish weekly report' --step 'Collect metrics' --step 'Write summary'
+  zeroclaw goals add 'Clean up old branches' --priority low")]
+    Add {
+        /// Goal description
+        description: String,
+        /// Ordered step description (repeatable)
+        #[arg(long = "step")]
+        steps: Vec<String>,
+        /// Priority: low, medium, high, critical
+        #[arg(long, default_value = "medium")]
+        priority: String,
+    },
+    /// Pause a goal so the goal loop skips it
+    Pause {
+        /// Goal ID
+        id: String,
+    },
+    /// Resume a paused goal
+    Resume {
+        /// Goal ID
+        id: String,
+    },
+}
+
+/// SOP subcommands
+#[derive(Subcommand, Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub enum SopCommands {
+    /// List loaded SOPs with their triggers and execution mode
+    List,
+    /// Check SOP definitions for missing steps and triggers
+    Validate {
+        /// SOP name (validates all SOPs when omitted)
+        name: Option<String>,
+    },
+    /// Show one SOP's triggers and steps
+    Show {
+        /// SOP name
+        name: String,
+    },
+}
+
 /// Memory management subcommands
 #[derive(Subcommand, Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 pub enum MemoryCommands {
@@ -63,6 +63,7 @@ mod cron;
 mod daemon;
 mod doctor;
 mod gateway;
+mod goals;
 mod hardware;
 mod health;
 mod heartbeat;
@@ -76,11 +77,13 @@ mod observability;
 mod onboard;
 mod peripherals;
 mod providers;
+mod robot;
 mod runtime;
 mod security;
 mod service;
 mod skillforge;
 mod skills;
+mod sop;
 mod tools;
 mod tunnel;
 mod util;
@@ -89,8 +92,8 @@ use config::Config;
 
 // Re-export so binary modules can use crate::<CommandEnum> while keeping a single source of truth.
 pub use zeroclaw::{
-    ChannelCommands, CronCommands, HardwareCommands, IntegrationCommands, MigrateCommands,
-    PeripheralCommands, ServiceCommands, SkillCommands,
+    ChannelCommands, CronCommands, GoalCommands, HardwareCommands, IntegrationCommands,
+    MigrateCommands, PeripheralCommands, ServiceCommands, SkillCommands, SopCommands,
 };
 
 #[derive(Copy, Clone, Debug, Eq, PartialEq, ValueEnum)]
@@ -129,6 +132,10 @@ struct Cli {
     #[arg(long, global = true)]
     config_dir: Option<String>,
 
+    /// Config profile overlay (loads config.<PROFILE>.toml on top of config.toml)
+    #[arg(long, global = true)]
+    profile: Option<String>,
+
     #[command(subcommand)]
     command: Commands,
 }
@@ -263,6 +270,28 @@ Examples:
         doctor_command: Option<DoctorCommands>,
     },
 
+    /// Replay a recorded agent turn and report where it diverges
+    #[command(long_about = "\
+Replay a recorded agent turn and report where it diverges.
+
+Re-runs one turn from the runtime trace with the recorded model \
+responses and stubbed tools that return the recorded outputs, then \
+compares the tool calls and final reply against the recording. \
+Exits non-zero when the replay diverges. Requires \
+[observability] runtime_trace_mode = \"rolling\" or \"full\".
+
+Examples:
+  zeroclaw replay <trace-id>          # any event id from `zeroclaw doctor traces`
+  zeroclaw replay <turn-id> --json    # machine-readable report")]
+    Replay {
+        /// Runtime trace event id or turn id
+        trace_id: String,
+
+        /// Print the replay report as JSON
+        #[arg(long)]
+        json: bool,
+    },
+
     /// Show system status (full details)
     Status,
 
@@ -319,6 +348,41 @@ Examples:
         cron_command: CronCommands,
     },
 
+    /// Manage long-running goals (add, list, pause, resume)
+    #[command(long_about = "\
+Manage long-running goals executed by the daemon goal loop.
+
+Goals live in state/goals.json. When [goal_loop] is enabled, the \
+daemon works through actionable steps on each cycle and reflects \
+on goals whose steps are exhausted.
+
+Examples:
+  zeroclaw goals list
+  zeroclaw goals add 'Publish weekly report' --step 'Collect metrics' --step 'Write summary'
+  zeroclaw goals pause g1
+  zeroclaw goals resume g1")]
+    Goals {
+        #[command(subcommand)]
+        goal_command: GoalCommands,
+    },
+
+    /// Inspect standard operating procedures (list, validate, show)
+    #[command(long_about = "\
+Inspect standard operating procedures.
+
+SOPs live in <workspace>/sops/<name>/ as SOP.toml (metadata and \
+triggers) plus SOP.md (numbered steps). Set [sop] sops_dir to load \
+them from elsewhere.
+
+Examples:
+  zeroclaw sop list
+  zeroclaw sop validate
+  zeroclaw sop show restart-pump")]
+    Sop {
+        #[command(subcommand)]
+        sop_command: SopCommands,
+    },
+
     /// Manage provider model catalogs
     Models {
         #[command(subcommand)]
@@ -432,11 +496,15 @@ Manage ZeroClaw configuration.
 
 Inspect and export configuration settings. Use 'schema' to dump \
 the full JSON Schema for the config file, which documents every \
-available key, type, and default value.
+available key, type, and default value. Use 'show' to see which \
+files (base, --profile overlay, config.local.toml) make up the \
+effective config and where each value came from.
 
 Examples:
   zeroclaw config schema              # print JSON Schema to stdout
-  zeroclaw config schema > schema.json")]
+  zeroclaw config schema > schema.json
+  zeroclaw config show                # list active profile and layer files
+  zeroclaw --profile prod config show --resolved --origin")]
     Config {
         #[command(subcommand)]
         config_command: ConfigCommands,
@@ -463,6 +531,15 @@ Examples:
 enum ConfigCommands {
     /// Dump the full configuration JSON Schema to stdout
     Schema,
+    /// Show the config layers, or the resolved config with --resolved
+    Show {
+        /// Print the effective config after merging layers and env overrides (secrets masked)
+        #[arg(long)]
+        resolved: bool,
+        /// Annotate each resolved value with the file (or env/default) it came from
+        #[arg(long, requires = "resolved")]
+        origin: bool,
+    },
 }
 
 #[derive(Subcommand, Debug)]
@@ -613,22 +690,86 @@ enum DoctorCommands {
         use_cache: bool,
     },
     /// Query runtime trace events (tool diagnostics and model replies)
+    ///
+    /// Examples:
+    /// - `zeroclaw doctor traces --tool shell --success false --since 2h`
+    /// - `zeroclaw doctor traces --view slowest-tools --since 1d`
+    /// - `zeroclaw doctor traces --view tokens-per-turn --channel telegram`
+    /// - `zeroclaw doctor traces --follow --event tool_call_result`
+    /// - `zeroclaw doctor traces --export otlp-json --output traces.json`
     Traces {
         /// Show a specific trace event by id
         #[arg(long)]
         id: Option<String>,
-        /// Filter list output by event type
+        /// Filter by event type (e.g. `tool_call_result`, `llm_response`)
         #[arg(long)]
         event: Option<String>,
+        /// Filter by channel name
+        #[arg(long)]
+        channel: Option<String>,
+        /// Filter by provider name
+        #[arg(long)]
+        provider: Option<String>,
+        /// Filter by model name
+        #[arg(long)]
+        model: Option<String>,
+        /// Filter by tool name (tool call events)
+        #[arg(long)]
+        tool: Option<String>,
+        /// Filter by agent turn id
+        #[arg(long)]
+        turn: Option<String>,
+        /// Filter by outcome (`true` or `false`)
+        #[arg(long)]
+        success: Option<bool>,
+        /// Only events at or after this time (RFC 3339, YYYY-MM-DD, or an age like 15m, 2h, 7d)
+        #[arg(long)]
+        since: Option<String>,
+        /// Only events before this time (same formats as --since)
+        #[arg(long)]
+        until: Option<String>,
         /// Case-insensitive text match across message/payload
         #[arg(long)]
         contains: Option<String>,
-        /// Maximum number of events to display
-        #[arg(long, default_value = "20")]
-        limit: usize,
+        /// Maximum number of events or rows to display
+        /// (default 20 for listings and views; exports include every match)
+        #[arg(long)]
+        limit: Option<usize>,
+        /// Aggregate matching events instead of listing them
+        #[arg(long, value_enum, conflicts_with_all = ["export", "follow"])]
+        view: Option<TraceViewArg>,
+        /// Export matching events (oldest first) instead of listing them
+        #[arg(long, value_enum, conflicts_with = "follow")]
+        export: Option<TraceExportArg>,
+        /// Write the export to this file instead of stdout
+        #[arg(long, requires = "export")]
+        output: Option<std::path::PathBuf>,
+        /// Keep printing matching events as they are recorded
+        #[arg(long, short = 'f')]
+        follow: bool,
     },
 }
 
+#[derive(Copy, Clone, Debug, Eq, PartialEq, ValueEnum)]
+enum TraceViewArg {
+    #[value(name = "slowest-tools")]
+    SlowestTools,
+    #[value(name = "failing-tools")]
+    FailingTools,
+    #[value(name = "tokens-per-turn")]
+    TokensPerTurn,
+    #[value(name = "parse-failures")]
+    ParseFailures,
+}
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq, ValueEnum)]
+enum TraceExportArg {
+    #[value(name = "csv")]
+    Csv,
+    #[value(name = "otlp-json")]
+    OtlpJson,
+}
+
 #[derive(Subcommand, Debug)]
 enum MemoryCommands {
     /// List memory entries with optional filters
@@ -678,6 +819,13 @@ async fn main() -> Result<()> {
         std::env::set_var("ZEROCLAW_CONFIG_DIR", config_dir);
     }
 
+    if let Some(profile) = &cli.profile {
+        if profile.trim().is_empty() {
+            bail!("--profile cannot be empty");
+        }
+        std::env::set_var(config::layers::PROFILE_ENV, profile.trim());
+    }
+
     // Completions must remain stdout-only and should not load config or initialize logging.
     // This avoids warnings/log lines corrupting sourced completion scripts.
     if let Commands::Completions { shell } = &cli.command {
@@ -914,6 +1062,10 @@ async fn main() -> Result<()> {
 
         Commands::Cron { cron_command } => cron::handle_command(cron_command, &config),
 
+        Commands::Goals { goal_command } => goals::handle_command(goal_command, &config).await,
+
+        Commands::Sop { sop_command } => sop::handle_command(sop_command, &config),
+
         Commands::Models { model_command } => match model_command {
             ModelCommands::Refresh {
                 provider,
@@ -985,18 +1137,61 @@ async fn main() -> Result<()> {
             Some(DoctorCommands::Traces {
                 id,
                 event,
+                channel,
+                provider,
+                model,
+                tool,
+                turn,
+                success,
+                since,
+                until,
                 contains,
                 limit,
-            }) => doctor::run_traces(
-                &config,
-                id.as_deref(),
-                event.as_deref(),
-                contains.as_deref(),
-                limit,
-            ),
+                view,
+                export,
+                output,
+                follow,
+            }) => {
+                let now = chrono::Utc::now();
+                let parse_bound = |raw: Option<String>| {
+                    raw.map(|raw| observability::trace_query::parse_time_bound(&raw, now))
+                        .transpose()
+                };
+                let options = doctor::TracesOptions {
+                    id,
+                    filter: observability::trace_query::TraceFilter {
+                        event,
+                        channel,
+                        provider,
+                        model,
+                        tool,
+                        turn,
+                        success,
+                        since: parse_bound(since)?,
+                        until: parse_bound(until)?,
+                        contains,
+                    },
+                    limit,
+                    view: view.map(|view| match view {
+                        TraceViewArg::SlowestTools => doctor::TraceView::SlowestTools,
+                        TraceViewArg::FailingTools => doctor::TraceView::FailingTools,
+                        TraceViewArg::TokensPerTurn => doctor::TraceView::TokensPerTurn,
+                        TraceViewArg::ParseFailures => doctor::TraceView::ParseFailures,
+                    }),
+                    export: export.map(|format| match format {
+                        TraceExportArg::Csv => doctor::TraceExportFormat::Csv,
+                        TraceExportArg::OtlpJson => doctor::TraceExportFormat::OtlpJson,
+                    }),
+                    output,
+                    follow,
+                };
+                doctor::run_traces(&config, &options).await
+            }
             None => doctor::run(&config),
         },
 
+        Commands::Replay { trace_id, json } => agent::replay::run(&config, &trace_id, json).await,
+
         Commands::Channel { channel_command } => match channel_command {
             ChannelCommands::Start => channels::start_channels(config).await,
             ChannelCommands::Doctor => channels::doctor_channels(config).await,
@@ -1036,6 +1231,19 @@ async fn main() -> Result<()> {
                 );
                 Ok(())
             }
+            ConfigCommands::Show { resolved, origin } => {
+                if !resolved {
+                    print!("{}", config::layers::describe_layers(&config));
+                    return Ok(());
+                }
+                let masked = gateway::api::mask_sensitive_fields(&config);
+                if origin {
+                    print!("{}", config::layers::render_with_origins(&masked)?);
+                } else {
+                    print!("{}", toml::to_string_pretty(&masked)?);
+                }
+                Ok(())
+            }
         },
     }
 }
@@ -1908,6 +2116,35 @@ mod tests {
         }
     }
 
+    #[test]
+    fn config_show_cli_parses_profile_and_origin_flags() {
+        let cli = Cli::try_parse_from([
+            "zeroclaw",
+            "--profile",
+            "prod",
+            "config",
+            "show",
+            "--resolved",
+            "--origin",
+        ])
+        .expect("config show should parse");
+        assert_eq!(cli.profile.as_deref(), Some("prod"));
+        match cli.command {
+            Commands::Config {
+                config_command: ConfigCommands::Show { resolved, origin },
+            } => {
+                assert!(resolved);
+                assert!(origin);
+            }
+            other => panic!("expected config show command, got {other:?}"),
+        }
+
+        assert!(
+            Cli::try_parse_from(["zeroclaw", "config", "show", "--origin"]).is_err(),
+            "--origin requires --resolved"
+        );
+    }
+
     #[test]
     fn completions_cli_parses_supported_shells() {
         for shell in ["bash", "fish", "zsh", "powershell", "elvish"] {
@@ -1976,4 +2213,54 @@ mod tests {
             other => panic!("expected estop resume command, got {other:?}"),
         }
     }
+
+    #[test]
+    fn cli_parses_doctor_traces_view_and_filters() {
+        let cli = Cli::try_parse_from([
+            "zeroclaw",
+            "doctor",
+            "traces",
+            "--view",
+            "slowest-tools",
+            "--tool",
+            "shell",
+            "--success",
+            "false",
+            "--since",
+            "2h",
+        ])
+        .expect("doctor traces command should parse");
+
+        match cli.command {
+            Commands::Doctor {
+                doctor_command:
+                    Some(DoctorCommands::Traces {
+                        view,
+                        tool,
+                        success,
+                        since,
+                        limit,
+                        ..
+                    }),
+            } => {
+                assert_eq!(view, Some(TraceViewArg::SlowestTools));
+                assert_eq!(tool.as_deref(), Some("shell"));
+                assert_eq!(success, Some(false));
+                assert_eq!(since.as_deref(), Some("2h"));
+                assert!(limit.is_none());
+            }
+            other => panic!("expected doctor traces command, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn cli_rejects_doctor_traces_follow_with_export() {
+        assert!(Cli::try_parse_from([
+            "zeroclaw", "doctor", "traces", "--follow", "--export", "csv"
+        ])
+        .is_err());
+        assert!(
+            Cli::try_parse_from(["zeroclaw", "doctor", "traces", "--output", "x.csv"]).is_err()
+        );
+    }
 }
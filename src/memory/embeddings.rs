@@ -186,10 +186,43 @@ pub fn create_embedding_provider(
             let key = api_key.unwrap_or("");
             Box::new(OpenAiEmbedding::new(base_url, key, model, dims))
         }
+        name if name.starts_with("local:") => {
+            let model_dir = name.strip_prefix("local:").unwrap_or("").trim();
+            create_local_embedding(model_dir, dims)
+        }
         _ => Box::new(NoopEmbedding),
     }
 }
 
+#[cfg(feature = "embeddings-local")]
+fn create_local_embedding(model_dir: &str, dims: usize) -> Box<dyn EmbeddingProvider> {
+    match super::local_embeddings::LocalEmbedding::load(std::path::Path::new(model_dir)) {
+        Ok(provider) => {
+            if provider.dimensions() != dims {
+                tracing::warn!(
+                    "local embedding model in {model_dir} produces {} dimensions, not the configured {dims}; using the model's",
+                    provider.dimensions()
+                );
+            }
+            Box::new(provider)
+        }
+        Err(e) => {
+            tracing::warn!(
+                "failed to load local embedding model from {model_dir}: {e:#}; falling back to keyword-only search"
+            );
+            Box::new(NoopEmbedding)
+        }
+    }
+}
+
+#[cfg(not(feature = "embeddings-local"))]
+fn create_local_embedding(model_dir: &str, _dims: usize) -> Box<dyn EmbeddingProvider> {
+    tracing::warn!(
+        "embedding provider 'local:{model_dir}' requested but this build was compiled without `embeddings-local`; rebuild with `--features embeddings-local`. Falling back to keyword-only search"
+    );
+    Box::new(NoopEmbedding)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -270,6 +303,27 @@ mod tests {
         assert_eq!(p.name(), "none");
     }
 
+    #[cfg(not(feature = "embeddings-local"))]
+    #[test]
+    fn factory_local_without_feature_returns_noop() {
+        let p = create_embedding_provider("local:/models/all-MiniLM-L6-v2", None, "model", 384);
+        assert_eq!(p.name(), "none");
+        assert_eq!(p.dimensions(), 0);
+    }
+
+    #[cfg(feature = "embeddings-local")]
+    #[test]
+    fn factory_local_missing_model_dir_returns_noop() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let p = create_embedding_provider(
+            &format!("local:{}", tmp.path().display()),
+            None,
+            "model",
+            384,
+        );
+        assert_eq!(p.name(), "none");
+    }
+
     #[test]
     fn factory_unknown_provider_returns_noop() {
         let p = create_embedding_provider("cohere", None, "model", 1536);
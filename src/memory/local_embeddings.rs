@@ -0,0 +1,378 @@
+//! In-process sentence embeddings for offline memory search.
+//!
+//! With the `embeddings-local` feature, `embedding_provider = "local:<dir>"`
+//! loads a BERT-family sentence-transformer (e.g. `all-MiniLM-L6-v2`) from
+//! `<dir>/config.json`, `<dir>/tokenizer.json` and `<dir>/model.safetensors`
+//! and runs it on CPU with candle. No network access is needed.
+//!
+//! Inference runs on one worker thread. Requests from concurrent callers are
+//! queued, and the worker embeds everything waiting (up to
+//! [`MAX_BATCH_TEXTS`] texts) in a single forward pass.
+
+use super::embeddings::EmbeddingProvider;
+use async_trait::async_trait;
+use std::sync::mpsc;
+use tokio::sync::oneshot;
+
+/// Most texts embedded in one forward pass.
+pub const MAX_BATCH_TEXTS: usize = 32;
+
+/// A model that turns texts into fixed-size vectors, one per text.
+pub trait SentenceEncoder: Send + 'static {
+    fn dimensions(&self) -> usize;
+
+    fn encode(&mut self, texts: &[String]) -> anyhow::Result<Vec<Vec<f32>>>;
+}
+
+struct EmbedJob {
+    texts: Vec<String>,
+    reply: oneshot::Sender<anyhow::Result<Vec<Vec<f32>>>>,
+}
+
+/// Embedding provider backed by an in-process [`SentenceEncoder`].
+pub struct LocalEmbedding {
+    dims: usize,
+    jobs: mpsc::Sender<EmbedJob>,
+}
+
+impl LocalEmbedding {
+    /// Start a worker thread that owns `encoder`. The thread exits when the
+    /// provider is dropped.
+    pub fn spawn(encoder: impl SentenceEncoder) -> anyhow::Result<Self> {
+        let dims = encoder.dimensions();
+        let (jobs, queue) = mpsc::channel();
+        std::thread::Builder::new()
+            .name("zeroclaw-embeddings".into())
+            .spawn(move || run_worker(encoder, &queue))?;
+        Ok(Self { dims, jobs })
+    }
+
+    /// Load a sentence-transformer from `dir` and start its worker.
+    #[cfg(feature = "embeddings-local")]
+    pub fn load(dir: &std::path::Path) -> anyhow::Result<Self> {
+        Self::spawn(bert::BertEncoder::load(dir)?)
+    }
+}
+
+fn run_worker(mut encoder: impl SentenceEncoder, queue: &mpsc::Receiver<EmbedJob>) {
+    while let Ok(first) = queue.recv() {
+        let mut queued = first.texts.len();
+        let mut batch = vec![first];
+        while queued < MAX_BATCH_TEXTS {
+            match queue.try_recv() {
+                Ok(job) => {
+                    queued += job.texts.len();
+                    batch.push(job);
+                }
+                Err(_) => break,
+            }
+        }
+
+        let texts: Vec<String> = batch
+            .iter()
+            .flat_map(|job| job.texts.iter().cloned())
+            .collect();
+        match encode_chunked(&mut encoder, &texts) {
+            Ok(vectors) => {
+                let mut vectors = vectors.into_iter();
+                for job in batch {
+                    let count = job.texts.len();
+                    let _ = job.reply.send(Ok(vectors.by_ref().take(count).collect()));
+                }
+            }
+            Err(e) => {
+                let message = format!("{e:#}");
+                for job in batch {
+                    let _ = job
+                        .reply
+                        .send(Err(anyhow::anyhow!("Local embedding failed: {message}")));
+                }
+            }
+        }
+    }
+}
+
+fn encode_chunked(
+    encoder: &mut impl SentenceEncoder,
+    texts: &[String],
+) -> anyhow::Result<Vec<Vec<f32>>> {
+    let mut vectors = Vec::with_capacity(texts.len());
+    for chunk in texts.chunks(MAX_BATCH_TEXTS) {
+        let encoded = encoder.encode(chunk)?;
+        if encoded.len() != chunk.len() {
+            anyhow::bail!(
+                "encoder returned {} vectors for {} texts",
+                encoded.len(),
+                chunk.len()
+            );
+        }
+        vectors.extend(encoded);
+    }
+    Ok(vectors)
+}
+
+#[async_trait]
+impl EmbeddingProvider for LocalEmbedding {
+    fn name(&self) -> &str {
+        "local"
+    }
+
+    fn dimensions(&self) -> usize {
+        self.dims
+    }
+
+    async fn embed(&self, texts: &[&str]) -> anyhow::Result<Vec<Vec<f32>>> {
+        if texts.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let (reply, response) = oneshot::channel();
+        let job = EmbedJob {
+            texts: texts.iter().map(|text| (*text).to_string()).collect(),
+            reply,
+        };
+        self.jobs
+            .send(job)
+            .map_err(|_| anyhow::anyhow!("Local embedding worker has stopped"))?;
+        response
+            .await
+            .map_err(|_| anyhow::anyhow!("Local embedding worker has stopped"))?
+    }
+}
+
+#[cfg(feature = "embeddings-local")]
+mod bert {
+    use super::SentenceEncoder;
+    use anyhow::Context;
+    use candle_core::{Device, Tensor};
+    use candle_nn::VarBuilder;
+    use candle_transformers::models::bert::{BertModel, Config, DTYPE};
+    use std::path::Path;
+    use tokenizers::{PaddingParams, Tokenizer, TruncationParams};
+
+    /// Tokens kept per text; MiniLM-style models are trained on 256.
+    const MAX_SEQUENCE_TOKENS: usize = 256;
+
+    pub(super) struct BertEncoder {
+        model: BertModel,
+        tokenizer: Tokenizer,
+        device: Device,
+        dims: usize,
+    }
+
+    impl BertEncoder {
+        pub(super) fn load(dir: &Path) -> anyhow::Result<Self> {
+            let config_path = dir.join("config.json");
+            let raw_config = std::fs::read_to_string(&config_path)
+                .with_context(|| format!("failed to read {}", config_path.display()))?;
+            let config: Config =
+                serde_json::from_str(&raw_config).context("invalid model config.json")?;
+            let dims = serde_json::from_str::<serde_json::Value>(&raw_config)?
+                .get("hidden_size")
+                .and_then(serde_json::Value::as_u64)
+                .context("model config.json has no hidden_size")?;
+
+            let mut tokenizer = Tokenizer::from_file(dir.join("tokenizer.json"))
+                .map_err(|e| anyhow::anyhow!("failed to load tokenizer.json: {e}"))?;
+            tokenizer.with_padding(Some(PaddingParams::default()));
+            tokenizer
+                .with_truncation(Some(TruncationParams {
+                    max_length: MAX_SEQUENCE_TOKENS,
+                    ..TruncationParams::default()
+                }))
+                .map_err(|e| anyhow::anyhow!("invalid tokenizer truncation: {e}"))?;
+
+            // Read into memory rather than mmap: the crate forbids unsafe code,
+            // and sentence-embedding weights are small (~90 MB for MiniLM).
+            let device = Device::Cpu;
+            let weights_path = dir.join("model.safetensors");
+            let weights = std::fs::read(&weights_path)
+                .with_context(|| format!("failed to read {}", weights_path.display()))?;
+            let vars = VarBuilder::from_buffered_safetensors(weights, DTYPE, &device)?;
+            let model = BertModel::load(vars, &config)?;
+
+            Ok(Self {
+                model,
+                tokenizer,
+                device,
+                dims: usize::try_from(dims)?,
+            })
+        }
+    }
+
+    impl SentenceEncoder for BertEncoder {
+        fn dimensions(&self) -> usize {
+            self.dims
+        }
+
+        fn encode(&mut self, texts: &[String]) -> anyhow::Result<Vec<Vec<f32>>> {
+            let encodings = self
+                .tokenizer
+                .encode_batch(texts.to_vec(), true)
+                .map_err(|e| anyhow::anyhow!("tokenization failed: {e}"))?;
+            let ids = encodings
+                .iter()
+                .map(|encoding| Tensor::new(encoding.get_ids(), &self.device))
+                .collect::<candle_core::Result<Vec<_>>>()?;
+            let mask = encodings
+                .iter()
+                .map(|encoding| Tensor::new(encoding.get_attention_mask(), &self.device))
+                .collect::<candle_core::Result<Vec<_>>>()?;
+            let ids = Tensor::stack(&ids, 0)?;
+            let mask = Tensor::stack(&mask, 0)?;
+            let type_ids = ids.zeros_like()?;
+            let hidden = self.model.forward(&ids, &type_ids, Some(&mask))?;
+
+            // Mean-pool over real (unpadded) tokens, then L2-normalise.
+            let mask = mask.to_dtype(DTYPE)?.unsqueeze(2)?;
+            let pooled = hidden
+                .broadcast_mul(&mask)?
+                .sum(1)?
+                .broadcast_div(&mask.sum(1)?)?;
+            let norms = pooled.sqr()?.sum_keepdim(1)?.sqrt()?;
+            Ok(pooled.broadcast_div(&norms)?.to_vec2::<f32>()?)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::{Arc, Mutex};
+
+    /// Encodes each text as `[len, batch_index]` and records batch sizes.
+    /// Each call waits for a permit so tests can queue work behind it.
+    struct FakeEncoder {
+        batches: Arc<Mutex<Vec<usize>>>,
+        permits: mpsc::Receiver<()>,
+    }
+
+    impl SentenceEncoder for FakeEncoder {
+        fn dimensions(&self) -> usize {
+            2
+        }
+
+        fn encode(&mut self, texts: &[String]) -> anyhow::Result<Vec<Vec<f32>>> {
+            self.permits.recv()?;
+            let mut batches = self.batches.lock().unwrap();
+            batches.push(texts.len());
+            let batch = batches.len() as f32;
+            if texts.iter().any(|text| text == "boom") {
+                anyhow::bail!("model exploded");
+            }
+            Ok(texts
+                .iter()
+                .map(|text| vec![text.len() as f32, batch])
+                .collect())
+        }
+    }
+
+    fn fake() -> (LocalEmbedding, Arc<Mutex<Vec<usize>>>, mpsc::Sender<()>) {
+        let batches = Arc::new(Mutex::new(Vec::new()));
+        let (permit, permits) = mpsc::channel();
+        let provider = LocalEmbedding::spawn(FakeEncoder {
+            batches: Arc::clone(&batches),
+            permits,
+        })
+        .unwrap();
+        (provider, batches, permit)
+    }
+
+    #[tokio::test]
+    async fn embeds_in_order_with_encoder_dimensions() {
+        let (provider, _, permit) = fake();
+        permit.send(()).unwrap();
+        assert_eq!(provider.name(), "local");
+        assert_eq!(provider.dimensions(), 2);
+        let vectors = provider.embed(&["a", "abc"]).await.unwrap();
+        assert_eq!(vectors, vec![vec![1.0, 1.0], vec![3.0, 1.0]]);
+        assert!(provider.embed(&[]).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn concurrent_requests_share_a_forward_pass() {
+        let (provider, batches, permit) = fake();
+        let provider = Arc::new(provider);
+
+        // The first request occupies the worker until a permit arrives; the
+        // next three queue up behind it.
+        let first = tokio::spawn({
+            let provider = Arc::clone(&provider);
+            async move { provider.embed_one("first").await }
+        });
+        tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+        let queued: Vec<_> = ["bb", "ccc", "dddd"]
+            .into_iter()
+            .map(|text| {
+                let provider = Arc::clone(&provider);
+                tokio::spawn(async move { provider.embed_one(text).await })
+            })
+            .collect();
+        tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+        permit.send(()).unwrap();
+        permit.send(()).unwrap();
+
+        assert_eq!(first.await.unwrap().unwrap(), vec![5.0, 1.0]);
+        let mut lengths = Vec::new();
+        for handle in queued {
+            let vector = handle.await.unwrap().unwrap();
+            assert_eq!(vector[1], 2.0, "queued requests run in the second pass");
+            lengths.push(vector[0]);
+        }
+        assert_eq!(lengths, vec![2.0, 3.0, 4.0]);
+        assert_eq!(*batches.lock().unwrap(), vec![1, 3]);
+    }
+
+    #[tokio::test]
+    async fn large_requests_are_split_into_bounded_batches() {
+        let (provider, batches, permit) = fake();
+        for _ in 0..3 {
+            permit.send(()).unwrap();
+        }
+        let texts: Vec<String> = (0..MAX_BATCH_TEXTS * 2 + 1)
+            .map(|i| i.to_string())
+            .collect();
+        let refs: Vec<&str> = texts.iter().map(String::as_str).collect();
+
+        let vectors = provider.embed(&refs).await.unwrap();
+        assert_eq!(vectors.len(), texts.len());
+        assert_eq!(
+            *batches.lock().unwrap(),
+            vec![MAX_BATCH_TEXTS, MAX_BATCH_TEXTS, 1]
+        );
+    }
+
+    #[tokio::test]
+    async fn sqlite_memory_reuses_cached_local_embeddings() {
+        use crate::memory::{Memory, MemoryCategory, SqliteMemory};
+
+        let (provider, batches, permit) = fake();
+        for _ in 0..8 {
+            permit.send(()).unwrap();
+        }
+        let tmp = tempfile::TempDir::new().unwrap();
+        let mem = SqliteMemory::with_embedder(tmp.path(), Arc::new(provider), 0.7, 0.3, 100, None)
+            .unwrap();
+
+        mem.store("pref", "likes dark mode", MemoryCategory::Core, None)
+            .await
+            .unwrap();
+        let results = mem.recall("likes dark mode", 5, None).await.unwrap();
+        assert_eq!(results.len(), 1);
+        // The query text matches the stored content, so its vector comes
+        // from the embedding cache instead of a second forward pass.
+        assert_eq!(batches.lock().unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn encoder_errors_reach_every_caller_in_the_batch() {
+        let (provider, _, permit) = fake();
+        permit.send(()).unwrap();
+        let err = provider.embed(&["ok", "boom"]).await.unwrap_err();
+        assert!(err.to_string().contains("model exploded"), "{err}");
+
+        // The worker keeps serving after a failed batch.
+        permit.send(()).unwrap();
+        assert_eq!(provider.embed_one("ok").await.unwrap(), vec![2.0, 2.0]);
+    }
+}
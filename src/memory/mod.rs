@@ -3,6 +3,8 @@ pub mod chunker;
 pub mod cli;
 pub mod embeddings;
 pub mod hygiene;
+#[cfg(any(feature = "embeddings-local", test))]
+pub mod local_embeddings;
 pub mod lucid;
 pub mod markdown;
 pub mod none;
@@ -10,6 +12,7 @@ pub mod none;
 pub mod postgres;
 pub mod qdrant;
 pub mod response_cache;
+pub mod search_cache;
 pub mod snapshot;
 pub mod sqlite;
 pub mod traits;
@@ -27,12 +30,14 @@ pub use none::NoneMemory;
 pub use postgres::PostgresMemory;
 pub use qdrant::QdrantMemory;
 pub use response_cache::ResponseCache;
+pub use search_cache::SearchCache;
 pub use sqlite::SqliteMemory;
 pub use traits::Memory;
 #[allow(unused_imports)]
 pub use traits::{MemoryCategory, MemoryEntry};
 
 use crate::config::{EmbeddingRouteConfig, MemoryConfig, StorageProviderConfig};
+use crate::observability::Observer;
 use anyhow::Context;
 use std::path::Path;
 use std::sync::Arc;
@@ -376,8 +381,13 @@ pub fn create_memory_for_migration(
     )
 }
 
-/// Factory: create an optional response cache from config.
-pub fn create_response_cache(config: &MemoryConfig, workspace_dir: &Path) -> Option<ResponseCache> {
+/// Factory: create an optional response cache from config. Lookups are
+/// reported to `observer` (`zeroclaw_response_cache_lookups_total`).
+pub fn create_response_cache(
+    config: &MemoryConfig,
+    workspace_dir: &Path,
+    observer: Arc<dyn Observer>,
+) -> Option<ResponseCache> {
     if !config.response_cache_enabled {
         return None;
     }
@@ -393,7 +403,7 @@ pub fn create_response_cache(config: &MemoryConfig, workspace_dir: &Path) -> Opt
                 config.response_cache_ttl_minutes,
                 config.response_cache_max_entries
             );
-            Some(cache)
+            Some(cache.with_observer(observer))
         }
         Err(e) => {
             tracing::warn!("Response cache disabled due to error: {e}");
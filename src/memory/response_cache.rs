@@ -5,12 +5,14 @@
 //! configurable TTL (default: 1 hour). The cache is optional and disabled by
 //! default — users opt in via `[memory] response_cache_enabled = true`.
 
+use crate::observability::{Observer, ObserverEvent};
 use anyhow::Result;
 use chrono::{Duration, Local};
 use parking_lot::Mutex;
 use rusqlite::{params, Connection};
 use sha2::{Digest, Sha256};
 use std::path::{Path, PathBuf};
+use std::sync::Arc;
 
 /// Response cache backed by a dedicated SQLite database.
 ///
@@ -22,6 +24,7 @@ pub struct ResponseCache {
     db_path: PathBuf,
     ttl_minutes: i64,
     max_entries: usize,
+    observer: Option<Arc<dyn Observer>>,
 }
 
 impl ResponseCache {
@@ -58,9 +61,16 @@ impl ResponseCache {
             db_path,
             ttl_minutes: i64::from(ttl_minutes),
             max_entries,
+            observer: None,
         })
     }
 
+    /// Report every lookup as a hit or miss to `observer`.
+    pub fn with_observer(mut self, observer: Arc<dyn Observer>) -> Self {
+        self.observer = Some(observer);
+        self
+    }
+
     /// Build a deterministic cache key from model + system prompt + user prompt.
     pub fn cache_key(model: &str, system_prompt: Option<&str>, user_prompt: &str) -> String {
         let mut hasher = Sha256::new();
@@ -100,6 +110,12 @@ impl ResponseCache {
             )?;
         }
 
+        if let Some(observer) = &self.observer {
+            observer.record_event(&ObserverEvent::ResponseCacheLookup {
+                hit: result.is_some(),
+            });
+        }
+
         Ok(result)
     }
 
@@ -233,6 +249,25 @@ mod tests {
         assert!(result.is_none());
     }
 
+    #[test]
+    fn lookups_are_reported_to_observer() {
+        let tmp = TempDir::new().unwrap();
+        let prom = crate::observability::PrometheusObserver::new();
+        let cache = ResponseCache::new(tmp.path(), 60, 1000)
+            .unwrap()
+            .with_observer(Arc::new(prom.clone()));
+        let key = ResponseCache::cache_key("gpt-4", None, "hi");
+
+        cache.get(&key).unwrap();
+        cache.put(&key, "gpt-4", "hello", 5).unwrap();
+        cache.get(&key).unwrap();
+        cache.get(&key).unwrap();
+
+        let output = prom.encode();
+        assert!(output.contains(r#"zeroclaw_response_cache_lookups_total{result="hit"} 2"#));
+        assert!(output.contains(r#"zeroclaw_response_cache_lookups_total{result="miss"} 1"#));
+    }
+
     #[test]
     fn expired_entry_returns_none() {
         let (_tmp, cache) = temp_cache(0); // 0-minute TTL → everything is instantly expired
@@ -0,0 +1,223 @@
+//! Search cache — avoid repeating identical web searches.
+//!
+//! Stores serialized `web_search_tool` results in a dedicated SQLite database
+//! keyed by a SHA-256 hash of `(backend, max_results, normalized query)`.
+//! Entries expire after a configurable TTL. The cache is disabled by default —
+//! users opt in via `[web_search] cache_ttl_minutes = <minutes>`.
+
+use anyhow::Result;
+use chrono::{Duration, Local};
+use parking_lot::Mutex;
+use rusqlite::{params, Connection};
+use sha2::{Digest, Sha256};
+use std::path::Path;
+
+/// Search result cache backed by a dedicated SQLite database.
+///
+/// Lives alongside `response_cache.db` as `search_cache.db` so it can be
+/// independently wiped without touching memories or cached LLM responses.
+pub struct SearchCache {
+    conn: Mutex<Connection>,
+    ttl_minutes: i64,
+    max_entries: usize,
+}
+
+impl SearchCache {
+    /// Open (or create) the search cache database.
+    pub fn new(workspace_dir: &Path, ttl_minutes: u32, max_entries: usize) -> Result<Self> {
+        let db_dir = workspace_dir.join("memory");
+        std::fs::create_dir_all(&db_dir)?;
+        let db_path = db_dir.join("search_cache.db");
+
+        let conn = Connection::open(&db_path)?;
+
+        conn.execute_batch(
+            "PRAGMA journal_mode = WAL;
+             PRAGMA synchronous  = NORMAL;
+             PRAGMA temp_store   = MEMORY;",
+        )?;
+
+        conn.execute_batch(
+            "CREATE TABLE IF NOT EXISTS search_cache (
+                query_hash  TEXT PRIMARY KEY,
+                backend     TEXT NOT NULL,
+                query       TEXT NOT NULL,
+                results     TEXT NOT NULL,
+                created_at  TEXT NOT NULL,
+                accessed_at TEXT NOT NULL,
+                hit_count   INTEGER NOT NULL DEFAULT 0
+            );
+            CREATE INDEX IF NOT EXISTS idx_sc_accessed ON search_cache(accessed_at);
+            CREATE INDEX IF NOT EXISTS idx_sc_created ON search_cache(created_at);",
+        )?;
+
+        Ok(Self {
+            conn: Mutex::new(conn),
+            ttl_minutes: i64::from(ttl_minutes),
+            max_entries,
+        })
+    }
+
+    /// Build a deterministic cache key. Queries are compared case-insensitively
+    /// with whitespace collapsed, so trivially different phrasings share a slot.
+    pub fn cache_key(backend: &str, query: &str, max_results: usize) -> String {
+        let normalized = query
+            .split_whitespace()
+            .collect::<Vec<_>>()
+            .join(" ")
+            .to_lowercase();
+        let mut hasher = Sha256::new();
+        hasher.update(backend.as_bytes());
+        hasher.update(b"|");
+        hasher.update(max_results.to_string().as_bytes());
+        hasher.update(b"|");
+        hasher.update(normalized.as_bytes());
+        let hash = hasher.finalize();
+        format!("{:064x}", hash)
+    }
+
+    /// Look up cached results. Returns `None` on miss or expired entry.
+    pub fn get(&self, key: &str) -> Result<Option<String>> {
+        let conn = self.conn.lock();
+
+        let now = Local::now();
+        let cutoff = (now - Duration::minutes(self.ttl_minutes)).to_rfc3339();
+
+        let mut stmt = conn.prepare(
+            "SELECT results FROM search_cache
+             WHERE query_hash = ?1 AND created_at > ?2",
+        )?;
+
+        let result: Option<String> = stmt.query_row(params![key, cutoff], |row| row.get(0)).ok();
+
+        if result.is_some() {
+            conn.execute(
+                "UPDATE search_cache
+                 SET accessed_at = ?1, hit_count = hit_count + 1
+                 WHERE query_hash = ?2",
+                params![now.to_rfc3339(), key],
+            )?;
+        }
+
+        Ok(result)
+    }
+
+    /// Store serialized results in the cache.
+    pub fn put(&self, key: &str, backend: &str, query: &str, results: &str) -> Result<()> {
+        let conn = self.conn.lock();
+
+        let now = Local::now().to_rfc3339();
+
+        conn.execute(
+            "INSERT OR REPLACE INTO search_cache
+             (query_hash, backend, query, results, created_at, accessed_at, hit_count)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, 0)",
+            params![key, backend, query, results, now, now],
+        )?;
+
+        // Evict expired entries
+        let cutoff = (Local::now() - Duration::minutes(self.ttl_minutes)).to_rfc3339();
+        conn.execute(
+            "DELETE FROM search_cache WHERE created_at <= ?1",
+            params![cutoff],
+        )?;
+
+        // LRU eviction if over max_entries
+        #[allow(clippy::cast_possible_wrap)]
+        let max = self.max_entries as i64;
+        conn.execute(
+            "DELETE FROM search_cache WHERE query_hash IN (
+                SELECT query_hash FROM search_cache
+                ORDER BY accessed_at ASC
+                LIMIT MAX(0, (SELECT COUNT(*) FROM search_cache) - ?1)
+            )",
+            params![max],
+        )?;
+
+        Ok(())
+    }
+
+    /// Return cache statistics: (total_entries, total_hits).
+    pub fn stats(&self) -> Result<(usize, u64)> {
+        let conn = self.conn.lock();
+
+        let count: i64 =
+            conn.query_row("SELECT COUNT(*) FROM search_cache", [], |row| row.get(0))?;
+        let hits: i64 = conn.query_row(
+            "SELECT COALESCE(SUM(hit_count), 0) FROM search_cache",
+            [],
+            |row| row.get(0),
+        )?;
+
+        #[allow(clippy::cast_sign_loss, clippy::cast_possible_truncation)]
+        Ok((count as usize, hits as u64))
+    }
+
+    /// Wipe the entire cache.
+    pub fn clear(&self) -> Result<usize> {
+        let conn = self.conn.lock();
+
+        let affected = conn.execute("DELETE FROM search_cache", [])?;
+        Ok(affected)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn temp_cache(ttl_minutes: u32, max_entries: usize) -> (TempDir, SearchCache) {
+        let tmp = TempDir::new().unwrap();
+        let cache = SearchCache::new(tmp.path(), ttl_minutes, max_entries).unwrap();
+        (tmp, cache)
+    }
+
+    #[test]
+    fn cache_key_normalizes_query() {
+        let k1 = SearchCache::cache_key("searxng", "Rust  async", 5);
+        let k2 = SearchCache::cache_key("searxng", " rust async ", 5);
+        assert_eq!(k1, k2);
+        assert_eq!(k1.len(), 64);
+    }
+
+    #[test]
+    fn cache_key_varies_by_backend_and_limit() {
+        let base = SearchCache::cache_key("searxng", "rust", 5);
+        assert_ne!(base, SearchCache::cache_key("brave", "rust", 5));
+        assert_ne!(base, SearchCache::cache_key("searxng", "rust", 3));
+    }
+
+    #[test]
+    fn put_get_and_stats() {
+        let (_tmp, cache) = temp_cache(60, 100);
+        let key = SearchCache::cache_key("searxng", "rust", 5);
+        assert!(cache.get(&key).unwrap().is_none());
+
+        cache.put(&key, "searxng", "rust", "[]").unwrap();
+        assert_eq!(cache.get(&key).unwrap().as_deref(), Some("[]"));
+
+        let (entries, hits) = cache.stats().unwrap();
+        assert_eq!(entries, 1);
+        assert_eq!(hits, 1);
+    }
+
+    #[test]
+    fn expired_entry_returns_none() {
+        let (_tmp, cache) = temp_cache(0, 100);
+        let key = SearchCache::cache_key("searxng", "rust", 5);
+        cache.put(&key, "searxng", "rust", "[]").unwrap();
+        assert!(cache.get(&key).unwrap().is_none());
+    }
+
+    #[test]
+    fn lru_eviction_and_clear() {
+        let (_tmp, cache) = temp_cache(60, 2);
+        for query in ["a", "b", "c"] {
+            let key = SearchCache::cache_key("searxng", query, 5);
+            cache.put(&key, "searxng", query, "[]").unwrap();
+        }
+        assert_eq!(cache.stats().unwrap().0, 2);
+        assert_eq!(cache.clear().unwrap(), 2);
+    }
+}
@@ -50,6 +50,41 @@ impl Observer for LogObserver {
             ObserverEvent::Error { component, message } => {
                 info!(component = %component, error = %message, "error");
             }
+            ObserverEvent::RobotSafety { event, detail } => {
+                info!(event = %event, detail = %detail, "robot.safety");
+            }
+            ObserverEvent::CronJobRun {
+                job_id,
+                success,
+                duration,
+            } => {
+                let ms = u64::try_from(duration.as_millis()).unwrap_or(u64::MAX);
+                info!(job_id = %job_id, success = success, duration_ms = ms, "cron.run");
+            }
+            ObserverEvent::SopRun { sop, status } => {
+                info!(sop = %sop, status = %status, "sop.run");
+            }
+            ObserverEvent::ApprovalResolved {
+                channel,
+                tool,
+                approved,
+                wait,
+            } => {
+                let ms = u64::try_from(wait.as_millis()).unwrap_or(u64::MAX);
+                info!(
+                    channel = %channel,
+                    tool = %tool,
+                    approved = approved,
+                    wait_ms = ms,
+                    "approval.resolved"
+                );
+            }
+            ObserverEvent::ChannelRestart { channel } => {
+                info!(channel = %channel, "channel.restart");
+            }
+            ObserverEvent::ResponseCacheLookup { hit } => {
+                info!(hit = hit, "response_cache.lookup");
+            }
             ObserverEvent::LlmRequest {
                 provider,
                 model,
@@ -101,6 +136,21 @@ impl Observer for LogObserver {
             ObserverMetric::QueueDepth(d) => {
                 info!(depth = d, "metric.queue_depth");
             }
+            ObserverMetric::CostBudget {
+                period,
+                spent_usd,
+                limit_usd,
+            } => {
+                info!(
+                    period = %period,
+                    spent_usd = spent_usd,
+                    limit_usd = limit_usd,
+                    "metric.cost_budget"
+                );
+            }
+            ObserverMetric::MemoryEntries { backend, count } => {
+                info!(backend = %backend, count = count, "metric.memory_entries");
+            }
         }
     }
 
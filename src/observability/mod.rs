@@ -5,6 +5,8 @@ pub mod noop;
 pub mod otel;
 pub mod prometheus;
 pub mod runtime_trace;
+pub mod spans;
+pub mod trace_query;
 pub mod traits;
 pub mod verbose;
 
@@ -16,6 +18,8 @@ pub use noop::NoopObserver;
 #[cfg(feature = "observability-otel")]
 pub use otel::OtelObserver;
 pub use prometheus::PrometheusObserver;
+#[allow(unused_imports)]
+pub use spans::{SpanKind, TraceSpan};
 pub use traits::{Observer, ObserverEvent};
 #[allow(unused_imports)]
 pub use verbose::VerboseObserver;
@@ -26,7 +30,7 @@ use crate::config::ObservabilityConfig;
 pub fn create_observer(config: &ObservabilityConfig) -> Box<dyn Observer> {
     match config.backend.as_str() {
         "log" => Box::new(LogObserver::new()),
-        "prometheus" => Box::new(PrometheusObserver::new()),
+        "prometheus" => Box::new(PrometheusObserver::global()),
         "otel" | "opentelemetry" | "otlp" => {
             #[cfg(feature = "observability-otel")]
             match OtelObserver::new(
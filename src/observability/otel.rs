@@ -188,7 +188,33 @@ impl Observer for OtelObserver {
             }
             ObserverEvent::LlmRequest { .. }
             | ObserverEvent::ToolCallStart { .. }
-            | ObserverEvent::TurnComplete => {}
+            | ObserverEvent::TurnComplete
+            | ObserverEvent::SopRun { .. }
+            | ObserverEvent::ApprovalResolved { .. }
+            | ObserverEvent::ChannelRestart { .. }
+            | ObserverEvent::ResponseCacheLookup { .. } => {}
+            ObserverEvent::CronJobRun {
+                job_id,
+                success,
+                duration,
+            } => {
+                let start_time = SystemTime::now()
+                    .checked_sub(*duration)
+                    .unwrap_or(SystemTime::now());
+                let mut span = tracer.build(
+                    opentelemetry::trace::SpanBuilder::from_name("cron.run")
+                        .with_kind(SpanKind::Internal)
+                        .with_start_time(start_time)
+                        .with_attributes(vec![
+                            KeyValue::new("job_id", job_id.clone()),
+                            KeyValue::new("success", success.to_string()),
+                        ]),
+                );
+                if !success {
+                    span.set_status(Status::error("cron job failed"));
+                }
+                span.end();
+            }
             ObserverEvent::LlmResponse {
                 provider,
                 model,
@@ -206,28 +232,6 @@ impl Observer for OtelObserver {
                 ];
                 self.llm_calls.add(1, &attrs);
                 self.llm_duration.record(secs, &attrs);
-
-                // Create a completed span for visibility in trace backends.
-                let start_time = SystemTime::now()
-                    .checked_sub(*duration)
-                    .unwrap_or(SystemTime::now());
-                let mut span = tracer.build(
-                    opentelemetry::trace::SpanBuilder::from_name("llm.call")
-                        .with_kind(SpanKind::Internal)
-                        .with_start_time(start_time)
-                        .with_attributes(vec![
-                            KeyValue::new("provider", provider.clone()),
-                            KeyValue::new("model", model.clone()),
-                            KeyValue::new("success", *success),
-                            KeyValue::new("duration_s", secs),
-                        ]),
-                );
-                if *success {
-                    span.set_status(Status::Ok);
-                } else {
-                    span.set_status(Status::error(""));
-                }
-                span.end();
             }
             ObserverEvent::AgentEnd {
                 provider,
@@ -276,29 +280,6 @@ impl Observer for OtelObserver {
                 success,
             } => {
                 let secs = duration.as_secs_f64();
-                let start_time = SystemTime::now()
-                    .checked_sub(*duration)
-                    .unwrap_or(SystemTime::now());
-
-                let status = if *success {
-                    Status::Ok
-                } else {
-                    Status::error("")
-                };
-
-                let mut span = tracer.build(
-                    opentelemetry::trace::SpanBuilder::from_name("tool.call")
-                        .with_kind(SpanKind::Internal)
-                        .with_start_time(start_time)
-                        .with_attributes(vec![
-                            KeyValue::new("tool.name", tool.clone()),
-                            KeyValue::new("tool.success", *success),
-                            KeyValue::new("duration_s", secs),
-                        ]),
-                );
-                span.set_status(status);
-                span.end();
-
                 let attrs = [
                     KeyValue::new("tool", tool.clone()),
                     KeyValue::new("success", success.to_string()),
@@ -335,6 +316,17 @@ impl Observer for OtelObserver {
                 self.errors
                     .add(1, &[KeyValue::new("component", component.clone())]);
             }
+            ObserverEvent::RobotSafety { event, detail } => {
+                let mut span = tracer.build(
+                    opentelemetry::trace::SpanBuilder::from_name("robot.safety")
+                        .with_kind(SpanKind::Internal)
+                        .with_attributes(vec![
+                            KeyValue::new("robot.safety.event", event.clone()),
+                            KeyValue::new("robot.safety.detail", detail.clone()),
+                        ]),
+                );
+                span.end();
+            }
         }
     }
 
@@ -352,6 +344,7 @@ impl Observer for OtelObserver {
             ObserverMetric::QueueDepth(d) => {
                 self.queue_depth.record(*d as u64, &[]);
             }
+            ObserverMetric::CostBudget { .. } | ObserverMetric::MemoryEntries { .. } => {}
         }
     }
 
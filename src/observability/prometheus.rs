@@ -1,9 +1,17 @@
 use super::traits::{Observer, ObserverEvent, ObserverMetric};
 use prometheus::{
-    Encoder, GaugeVec, Histogram, HistogramOpts, HistogramVec, IntCounterVec, Registry, TextEncoder,
+    CounterVec, Encoder, GaugeVec, Histogram, HistogramOpts, HistogramVec, IntCounterVec, Registry,
+    TextEncoder,
 };
+use std::sync::LazyLock;
+
+/// Process-wide observer shared by every subsystem so `/metrics` sees all of them.
+static GLOBAL: LazyLock<PrometheusObserver> = LazyLock::new(PrometheusObserver::new);
 
 /// Prometheus-backed observer — exposes metrics for scraping via `/metrics`.
+///
+/// Clones share the same registry and metric handles.
+#[derive(Clone)]
 pub struct PrometheusObserver {
     registry: Registry,
 
@@ -16,19 +24,36 @@ pub struct PrometheusObserver {
     channel_messages: IntCounterVec,
     heartbeat_ticks: prometheus::IntCounter,
     errors: IntCounterVec,
+    robot_safety_events: IntCounterVec,
+    cost_usd_total: CounterVec,
+    response_cache_lookups: IntCounterVec,
+    cron_job_runs: IntCounterVec,
+    sop_runs: IntCounterVec,
+    channel_restarts: IntCounterVec,
 
     // Histograms
     agent_duration: HistogramVec,
     tool_duration: HistogramVec,
     request_latency: Histogram,
+    cron_job_duration: HistogramVec,
+    approval_wait: HistogramVec,
 
     // Gauges
     tokens_used: prometheus::IntGauge,
     active_sessions: GaugeVec,
     queue_depth: GaugeVec,
+    cost_spend: GaugeVec,
+    cost_limit: GaugeVec,
+    memory_entries: GaugeVec,
 }
 
 impl PrometheusObserver {
+    /// Shared process-wide instance used by [`create_observer`](super::create_observer).
+    pub fn global() -> Self {
+        GLOBAL.clone()
+    }
+
+    /// Create an observer with its own private registry.
     pub fn new() -> Self {
         let registry = Registry::new();
 
@@ -81,6 +106,51 @@ impl PrometheusObserver {
         )
         .expect("valid metric");
 
+        let robot_safety_events = IntCounterVec::new(
+            prometheus::Opts::new(
+                "zeroclaw_robot_safety_events_total",
+                "Total robot safety monitor events",
+            ),
+            &["event"],
+        )
+        .expect("valid metric");
+
+        let cost_usd_total = CounterVec::new(
+            prometheus::Opts::new("zeroclaw_cost_usd_total", "Total LLM spend in USD"),
+            &["provider", "model"],
+        )
+        .expect("valid metric");
+
+        let response_cache_lookups = IntCounterVec::new(
+            prometheus::Opts::new(
+                "zeroclaw_response_cache_lookups_total",
+                "Total response cache lookups by result",
+            ),
+            &["result"],
+        )
+        .expect("valid metric");
+
+        let cron_job_runs = IntCounterVec::new(
+            prometheus::Opts::new("zeroclaw_cron_job_runs_total", "Total cron job runs"),
+            &["job_id", "status"],
+        )
+        .expect("valid metric");
+
+        let sop_runs = IntCounterVec::new(
+            prometheus::Opts::new("zeroclaw_sop_runs_total", "Total SOP runs by final status"),
+            &["sop", "status"],
+        )
+        .expect("valid metric");
+
+        let channel_restarts = IntCounterVec::new(
+            prometheus::Opts::new(
+                "zeroclaw_channel_restarts_total",
+                "Total channel listener restarts by the supervisor",
+            ),
+            &["channel"],
+        )
+        .expect("valid metric");
+
         let agent_duration = HistogramVec::new(
             HistogramOpts::new(
                 "zeroclaw_agent_duration_seconds",
@@ -110,6 +180,26 @@ impl PrometheusObserver {
         )
         .expect("valid metric");
 
+        let cron_job_duration = HistogramVec::new(
+            HistogramOpts::new(
+                "zeroclaw_cron_job_duration_seconds",
+                "Cron job run duration in seconds",
+            )
+            .buckets(vec![0.1, 0.5, 1.0, 5.0, 15.0, 60.0, 300.0, 900.0]),
+            &["job_id"],
+        )
+        .expect("valid metric");
+
+        let approval_wait = HistogramVec::new(
+            HistogramOpts::new(
+                "zeroclaw_approval_wait_seconds",
+                "Time spent waiting for a tool approval decision",
+            )
+            .buckets(vec![1.0, 5.0, 15.0, 30.0, 60.0, 120.0, 300.0, 900.0]),
+            &["channel", "decision"],
+        )
+        .expect("valid metric");
+
         let tokens_used = prometheus::IntGauge::new(
             "zeroclaw_tokens_used_last",
             "Tokens used in the last request",
@@ -128,6 +218,33 @@ impl PrometheusObserver {
         )
         .expect("valid metric");
 
+        let cost_spend = GaugeVec::new(
+            prometheus::Opts::new(
+                "zeroclaw_cost_spend_usd",
+                "Tracked spend in USD for the current budget period",
+            ),
+            &["period"],
+        )
+        .expect("valid metric");
+
+        let cost_limit = GaugeVec::new(
+            prometheus::Opts::new(
+                "zeroclaw_cost_limit_usd",
+                "Configured budget limit in USD for the period",
+            ),
+            &["period"],
+        )
+        .expect("valid metric");
+
+        let memory_entries = GaugeVec::new(
+            prometheus::Opts::new(
+                "zeroclaw_memory_entries",
+                "Entries held by the memory backend",
+            ),
+            &["backend"],
+        )
+        .expect("valid metric");
+
         // Register all metrics
         registry.register(Box::new(agent_starts.clone())).ok();
         registry.register(Box::new(llm_requests.clone())).ok();
@@ -139,12 +256,27 @@ impl PrometheusObserver {
         registry.register(Box::new(channel_messages.clone())).ok();
         registry.register(Box::new(heartbeat_ticks.clone())).ok();
         registry.register(Box::new(errors.clone())).ok();
+        registry
+            .register(Box::new(robot_safety_events.clone()))
+            .ok();
+        registry.register(Box::new(cost_usd_total.clone())).ok();
+        registry
+            .register(Box::new(response_cache_lookups.clone()))
+            .ok();
+        registry.register(Box::new(cron_job_runs.clone())).ok();
+        registry.register(Box::new(sop_runs.clone())).ok();
+        registry.register(Box::new(channel_restarts.clone())).ok();
         registry.register(Box::new(agent_duration.clone())).ok();
         registry.register(Box::new(tool_duration.clone())).ok();
         registry.register(Box::new(request_latency.clone())).ok();
         registry.register(Box::new(tokens_used.clone())).ok();
         registry.register(Box::new(active_sessions.clone())).ok();
         registry.register(Box::new(queue_depth.clone())).ok();
+        registry.register(Box::new(cron_job_duration.clone())).ok();
+        registry.register(Box::new(approval_wait.clone())).ok();
+        registry.register(Box::new(cost_spend.clone())).ok();
+        registry.register(Box::new(cost_limit.clone())).ok();
+        registry.register(Box::new(memory_entries.clone())).ok();
 
         Self {
             registry,
@@ -156,12 +288,23 @@ impl PrometheusObserver {
             channel_messages,
             heartbeat_ticks,
             errors,
+            robot_safety_events,
+            cost_usd_total,
+            response_cache_lookups,
+            cron_job_runs,
+            sop_runs,
+            channel_restarts,
             agent_duration,
             tool_duration,
             request_latency,
+            cron_job_duration,
+            approval_wait,
             tokens_used,
             active_sessions,
             queue_depth,
+            cost_spend,
+            cost_limit,
+            memory_entries,
         }
     }
 
@@ -188,7 +331,7 @@ impl Observer for PrometheusObserver {
                 model,
                 duration,
                 tokens_used,
-                cost_usd: _,
+                cost_usd,
             } => {
                 // Agent duration is recorded via the histogram with provider/model labels
                 self.agent_duration
@@ -197,6 +340,11 @@ impl Observer for PrometheusObserver {
                 if let Some(t) = tokens_used {
                     self.tokens_used.set(i64::try_from(*t).unwrap_or(i64::MAX));
                 }
+                if let Some(cost) = cost_usd.filter(|c| c.is_finite() && *c > 0.0) {
+                    self.cost_usd_total
+                        .with_label_values(&[provider, model])
+                        .inc_by(cost);
+                }
             }
             ObserverEvent::LlmResponse {
                 provider,
@@ -251,6 +399,45 @@ impl Observer for PrometheusObserver {
             } => {
                 self.errors.with_label_values(&[component]).inc();
             }
+            ObserverEvent::RobotSafety { event, detail: _ } => {
+                self.robot_safety_events.with_label_values(&[event]).inc();
+            }
+            ObserverEvent::CronJobRun {
+                job_id,
+                success,
+                duration,
+            } => {
+                let status = if *success { "ok" } else { "error" };
+                self.cron_job_runs
+                    .with_label_values(&[job_id.as_str(), status])
+                    .inc();
+                self.cron_job_duration
+                    .with_label_values(&[job_id.as_str()])
+                    .observe(duration.as_secs_f64());
+            }
+            ObserverEvent::SopRun { sop, status } => {
+                self.sop_runs.with_label_values(&[sop, status]).inc();
+            }
+            ObserverEvent::ApprovalResolved {
+                channel,
+                tool: _,
+                approved,
+                wait,
+            } => {
+                let decision = if *approved { "approved" } else { "denied" };
+                self.approval_wait
+                    .with_label_values(&[channel.as_str(), decision])
+                    .observe(wait.as_secs_f64());
+            }
+            ObserverEvent::ChannelRestart { channel } => {
+                self.channel_restarts.with_label_values(&[channel]).inc();
+            }
+            ObserverEvent::ResponseCacheLookup { hit } => {
+                let result = if *hit { "hit" } else { "miss" };
+                self.response_cache_lookups
+                    .with_label_values(&[result])
+                    .inc();
+            }
         }
     }
 
@@ -272,6 +459,19 @@ impl Observer for PrometheusObserver {
                     .with_label_values(&[] as &[&str])
                     .set(*d as f64);
             }
+            ObserverMetric::CostBudget {
+                period,
+                spent_usd,
+                limit_usd,
+            } => {
+                self.cost_spend.with_label_values(&[period]).set(*spent_usd);
+                self.cost_limit.with_label_values(&[period]).set(*limit_usd);
+            }
+            ObserverMetric::MemoryEntries { backend, count } => {
+                self.memory_entries
+                    .with_label_values(&[backend])
+                    .set(*count as f64);
+            }
         }
     }
 
@@ -287,8 +487,105 @@ impl Observer for PrometheusObserver {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::collections::BTreeSet;
     use std::time::Duration;
 
+    const ALERT_RULES: &str =
+        include_str!("../../docs/operations/monitoring/prometheus-alerts.yml");
+    const GRAFANA_DASHBOARD: &str =
+        include_str!("../../docs/operations/monitoring/grafana-dashboard.json");
+
+    /// Record one sample for every labelled series so each family shows up in `encode()`.
+    fn observer_with_every_series() -> PrometheusObserver {
+        let obs = PrometheusObserver::new();
+        obs.record_event(&ObserverEvent::AgentStart {
+            provider: "openrouter".into(),
+            model: "claude-sonnet".into(),
+        });
+        obs.record_event(&ObserverEvent::AgentEnd {
+            provider: "openrouter".into(),
+            model: "claude-sonnet".into(),
+            duration: Duration::from_secs(1),
+            tokens_used: Some(10),
+            cost_usd: Some(0.01),
+        });
+        obs.record_event(&ObserverEvent::LlmResponse {
+            provider: "openrouter".into(),
+            model: "claude-sonnet".into(),
+            duration: Duration::from_millis(200),
+            success: true,
+            error_message: None,
+            input_tokens: Some(1),
+            output_tokens: Some(1),
+        });
+        obs.record_event(&ObserverEvent::ToolCall {
+            tool: "shell".into(),
+            duration: Duration::from_millis(10),
+            success: true,
+        });
+        obs.record_event(&ObserverEvent::ChannelMessage {
+            channel: "telegram".into(),
+            direction: "inbound".into(),
+        });
+        obs.record_event(&ObserverEvent::HeartbeatTick);
+        obs.record_event(&ObserverEvent::Error {
+            component: "provider".into(),
+            message: "timeout".into(),
+        });
+        obs.record_event(&ObserverEvent::RobotSafety {
+            event: "emergency_stop".into(),
+            detail: String::new(),
+        });
+        obs.record_event(&ObserverEvent::CronJobRun {
+            job_id: "job-1".into(),
+            success: true,
+            duration: Duration::from_secs(2),
+        });
+        obs.record_event(&ObserverEvent::SopRun {
+            sop: "deploy".into(),
+            status: "completed".into(),
+        });
+        obs.record_event(&ObserverEvent::ApprovalResolved {
+            channel: "cli".into(),
+            tool: "shell".into(),
+            approved: true,
+            wait: Duration::from_secs(3),
+        });
+        obs.record_event(&ObserverEvent::ChannelRestart {
+            channel: "telegram".into(),
+        });
+        obs.record_event(&ObserverEvent::ResponseCacheLookup { hit: true });
+        obs.record_metric(&ObserverMetric::RequestLatency(Duration::from_millis(5)));
+        obs.record_metric(&ObserverMetric::ActiveSessions(1));
+        obs.record_metric(&ObserverMetric::QueueDepth(1));
+        obs.record_metric(&ObserverMetric::CostBudget {
+            period: "daily".into(),
+            spent_usd: 1.0,
+            limit_usd: 10.0,
+        });
+        obs.record_metric(&ObserverMetric::MemoryEntries {
+            backend: "sqlite".into(),
+            count: 3,
+        });
+        obs
+    }
+
+    /// Series names (including histogram `_bucket`/`_sum`/`_count`) present in the exposition.
+    fn exported_series_names(obs: &PrometheusObserver) -> BTreeSet<String> {
+        obs.encode()
+            .lines()
+            .filter(|line| !line.starts_with('#'))
+            .filter_map(|line| line.split(['{', ' ']).next())
+            .map(str::to_string)
+            .collect()
+    }
+
+    fn referenced_metric_names(text: &str) -> BTreeSet<&str> {
+        text.split(|c: char| !(c.is_ascii_alphanumeric() || c == '_'))
+            .filter(|token| token.starts_with("zeroclaw_"))
+            .collect()
+    }
+
     #[test]
     fn prometheus_observer_name() {
         assert_eq!(PrometheusObserver::new().name(), "prometheus");
@@ -426,6 +723,117 @@ mod tests {
         assert!(output.contains(r#"zeroclaw_errors_total{component="channels"} 1"#));
     }
 
+    #[test]
+    fn robot_safety_events_track_by_event() {
+        let obs = PrometheusObserver::new();
+        obs.record_event(&ObserverEvent::RobotSafety {
+            event: "emergency_stop".into(),
+            detail: "kill-all".into(),
+        });
+
+        let output = obs.encode();
+        assert!(output.contains(r#"zeroclaw_robot_safety_events_total{event="emergency_stop"} 1"#));
+    }
+
+    #[test]
+    fn shipped_alerts_and_dashboard_reference_existing_metrics() {
+        let exported = exported_series_names(&observer_with_every_series());
+
+        for (file, text) in [
+            ("prometheus-alerts.yml", ALERT_RULES),
+            ("grafana-dashboard.json", GRAFANA_DASHBOARD),
+        ] {
+            let referenced = referenced_metric_names(text);
+            assert!(!referenced.is_empty(), "{file} references no metrics");
+            let missing: Vec<_> = referenced
+                .iter()
+                .filter(|name| !exported.contains(**name))
+                .collect();
+            assert!(
+                missing.is_empty(),
+                "{file} references unknown metrics: {missing:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn grafana_dashboard_is_valid_json() {
+        let dashboard: serde_json::Value = serde_json::from_str(GRAFANA_DASHBOARD).unwrap();
+        assert!(dashboard["panels"]
+            .as_array()
+            .is_some_and(|p| !p.is_empty()));
+    }
+
+    #[test]
+    fn cron_sop_and_channel_events_use_consistent_labels() {
+        let output = observer_with_every_series().encode();
+        assert!(output.contains(r#"zeroclaw_cron_job_runs_total{job_id="job-1",status="ok"} 1"#));
+        assert!(output.contains(r#"zeroclaw_cron_job_duration_seconds_count{job_id="job-1"} 1"#));
+        assert!(output.contains(r#"zeroclaw_sop_runs_total{sop="deploy",status="completed"} 1"#));
+        assert!(output.contains(r#"zeroclaw_channel_restarts_total{channel="telegram"} 1"#));
+        assert!(output.contains(
+            r#"zeroclaw_approval_wait_seconds_count{channel="cli",decision="approved"} 1"#
+        ));
+        assert!(output.contains(r#"zeroclaw_response_cache_lookups_total{result="hit"} 1"#));
+    }
+
+    #[test]
+    fn cost_is_tracked_per_provider_and_model() {
+        let obs = PrometheusObserver::new();
+        for cost in [Some(0.25), Some(0.5), None, Some(f64::NAN)] {
+            obs.record_event(&ObserverEvent::AgentEnd {
+                provider: "anthropic".into(),
+                model: "claude-sonnet".into(),
+                duration: Duration::from_millis(10),
+                tokens_used: None,
+                cost_usd: cost,
+            });
+        }
+        obs.record_metric(&ObserverMetric::CostBudget {
+            period: "daily".into(),
+            spent_usd: 1.5,
+            limit_usd: 10.0,
+        });
+
+        let output = obs.encode();
+        assert!(output.contains(
+            r#"zeroclaw_cost_usd_total{model="claude-sonnet",provider="anthropic"} 0.75"#
+        ));
+        assert!(output.contains(r#"zeroclaw_cost_spend_usd{period="daily"} 1.5"#));
+        assert!(output.contains(r#"zeroclaw_cost_limit_usd{period="daily"} 10"#));
+    }
+
+    #[test]
+    fn memory_entries_gauge_tracks_backend() {
+        let obs = PrometheusObserver::new();
+        obs.record_metric(&ObserverMetric::MemoryEntries {
+            backend: "sqlite".into(),
+            count: 12,
+        });
+        obs.record_metric(&ObserverMetric::MemoryEntries {
+            backend: "sqlite".into(),
+            count: 7,
+        });
+
+        assert!(obs
+            .encode()
+            .contains(r#"zeroclaw_memory_entries{backend="sqlite"} 7"#));
+    }
+
+    #[test]
+    fn global_observer_shares_one_registry() {
+        let a = PrometheusObserver::global();
+        let b = PrometheusObserver::global();
+        let channel = format!("global-test-{}", uuid::Uuid::new_v4());
+        a.record_event(&ObserverEvent::ChannelRestart {
+            channel: channel.clone(),
+        });
+
+        assert!(b.encode().contains(&format!(
+            r#"zeroclaw_channel_restarts_total{{channel="{channel}"}} 1"#
+        )));
+    }
+
     #[test]
     fn gauge_reflects_latest_value() {
         let obs = PrometheusObserver::new();
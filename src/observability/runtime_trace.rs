@@ -192,6 +192,12 @@ pub fn init_from_config(config: &ObservabilityConfig, workspace_dir: &Path) {
     *guard = logger;
 }
 
+/// Stop recording runtime trace events for the rest of the process.
+pub fn disable() {
+    let mut guard = TRACE_LOGGER.write().unwrap_or_else(|e| e.into_inner());
+    *guard = None;
+}
+
 /// Record a runtime trace event.
 pub fn record_event(
     event_type: &str,
@@ -229,13 +235,10 @@ pub fn record_event(
     }
 }
 
-/// Load recent runtime trace events from storage.
-pub fn load_events(
-    path: &Path,
-    limit: usize,
-    event_filter: Option<&str>,
-    contains: Option<&str>,
-) -> Result<Vec<RuntimeTraceEvent>> {
+/// Read every runtime trace event from storage, oldest first.
+///
+/// Malformed lines are skipped with a warning.
+pub fn read_events(path: &Path) -> Result<Vec<RuntimeTraceEvent>> {
     if !path.exists() {
         return Ok(Vec::new());
     }
@@ -255,6 +258,40 @@ pub fn load_events(
         }
     }
 
+    Ok(events)
+}
+
+/// Case-insensitive text match across event type, message, payload and routing fields.
+///
+/// `needle` must already be lowercase.
+pub(crate) fn event_contains_text(event: &RuntimeTraceEvent, needle: &str) -> bool {
+    let mut haystack = format!(
+        "{} {} {}",
+        event.event_type,
+        event.message.as_deref().unwrap_or_default(),
+        event.payload
+    );
+    if let Some(channel) = &event.channel {
+        haystack.push_str(channel);
+    }
+    if let Some(provider) = &event.provider {
+        haystack.push_str(provider);
+    }
+    if let Some(model) = &event.model {
+        haystack.push_str(model);
+    }
+    haystack.to_ascii_lowercase().contains(needle)
+}
+
+/// Load recent runtime trace events from storage.
+pub fn load_events(
+    path: &Path,
+    limit: usize,
+    event_filter: Option<&str>,
+    contains: Option<&str>,
+) -> Result<Vec<RuntimeTraceEvent>> {
+    let mut events = read_events(path)?;
+
     if let Some(filter) = event_filter.map(str::trim).filter(|f| !f.is_empty()) {
         let normalized = filter.to_ascii_lowercase();
         events.retain(|event| event.event_type.to_ascii_lowercase() == normalized);
@@ -262,24 +299,7 @@ pub fn load_events(
 
     if let Some(needle) = contains.map(str::trim).filter(|s| !s.is_empty()) {
         let needle = needle.to_ascii_lowercase();
-        events.retain(|event| {
-            let mut haystack = format!(
-                "{} {} {}",
-                event.event_type,
-                event.message.as_deref().unwrap_or_default(),
-                event.payload
-            );
-            if let Some(channel) = &event.channel {
-                haystack.push_str(channel);
-            }
-            if let Some(provider) = &event.provider {
-                haystack.push_str(provider);
-            }
-            if let Some(model) = &event.model {
-                haystack.push_str(model);
-            }
-            haystack.to_ascii_lowercase().contains(&needle)
-        });
+        events.retain(|event| event_contains_text(event, &needle));
     }
 
     if events.len() > limit {
@@ -313,6 +333,22 @@ pub fn find_event_by_id(path: &Path, id: &str) -> Result<Option<RuntimeTraceEven
     Ok(None)
 }
 
+/// Load every event recorded for one agent turn, oldest first.
+pub fn load_turn_events(path: &Path, turn_id: &str) -> Result<Vec<RuntimeTraceEvent>> {
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+
+    let raw = fs::read_to_string(path)?;
+    Ok(raw
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .filter_map(|line| serde_json::from_str::<RuntimeTraceEvent>(line).ok())
+        .filter(|event| event.turn_id.as_deref() == Some(turn_id))
+        .collect())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -411,4 +447,31 @@ mod tests {
         assert!(found.is_some());
         assert_eq!(found.unwrap().id, target_id);
     }
+
+    #[test]
+    fn load_turn_events_keeps_recording_order() {
+        let tmp = tempfile::tempdir().unwrap();
+        let path = tmp.path().join("trace.jsonl");
+        let logger = RuntimeTraceLogger::new(RuntimeTraceStorageMode::Full, 100, path.clone());
+
+        for (i, turn) in ["turn-a", "turn-b", "turn-a"].iter().enumerate() {
+            let event = RuntimeTraceEvent {
+                id: format!("id-{i}"),
+                timestamp: Utc::now().to_rfc3339(),
+                event_type: "llm_response".into(),
+                channel: None,
+                provider: None,
+                model: None,
+                turn_id: Some((*turn).into()),
+                success: Some(true),
+                message: None,
+                payload: serde_json::json!({}),
+            };
+            logger.append(&event).unwrap();
+        }
+
+        let events = load_turn_events(&path, "turn-a").unwrap();
+        let ids: Vec<_> = events.iter().map(|event| event.id.as_str()).collect();
+        assert_eq!(ids, ["id-0", "id-2"]);
+    }
 }
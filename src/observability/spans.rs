@@ -0,0 +1,388 @@
+//! Hierarchical trace spans for channel messages, agent turns, LLM calls and
+//! tool executions.
+//!
+//! A [`TraceSpan`] starts as a child of the span that is current for the
+//! calling task, and [`TraceSpan::instrument`] keeps it current across
+//! `.await` points, so a channel message, the agent turn it triggers, every
+//! provider attempt (including retries and fallbacks) and every tool call
+//! end up in one trace. W3C `traceparent` headers are extracted from inbound
+//! gateway requests and injected into outbound HTTP calls.
+//!
+//! Spans are exported through the global OpenTelemetry tracer installed by
+//! [`OtelObserver`](super::OtelObserver). Without the `observability-otel`
+//! feature every function here is a no-op.
+
+use reqwest::header::HeaderMap;
+use std::borrow::Cow;
+use std::future::Future;
+
+/// Role of a span in a distributed trace.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SpanKind {
+    /// In-process work (agent turn, tool execution).
+    Internal,
+    /// Handling an inbound request (gateway endpoint).
+    Server,
+    /// Outbound request to another service (LLM provider).
+    Client,
+    /// Processing a message received from a channel.
+    Consumer,
+}
+
+/// Attribute value attached to a span.
+#[derive(Debug, Clone, PartialEq)]
+pub enum AttrValue {
+    Str(String),
+    Int(i64),
+    Float(f64),
+    Bool(bool),
+}
+
+impl From<&str> for AttrValue {
+    fn from(value: &str) -> Self {
+        Self::Str(value.to_string())
+    }
+}
+
+impl From<String> for AttrValue {
+    fn from(value: String) -> Self {
+        Self::Str(value)
+    }
+}
+
+impl From<i64> for AttrValue {
+    fn from(value: i64) -> Self {
+        Self::Int(value)
+    }
+}
+
+impl From<u64> for AttrValue {
+    fn from(value: u64) -> Self {
+        Self::Int(i64::try_from(value).unwrap_or(i64::MAX))
+    }
+}
+
+impl From<u32> for AttrValue {
+    fn from(value: u32) -> Self {
+        Self::Int(i64::from(value))
+    }
+}
+
+impl From<usize> for AttrValue {
+    fn from(value: usize) -> Self {
+        Self::Int(i64::try_from(value).unwrap_or(i64::MAX))
+    }
+}
+
+impl From<f64> for AttrValue {
+    fn from(value: f64) -> Self {
+        Self::Float(value)
+    }
+}
+
+impl From<bool> for AttrValue {
+    fn from(value: bool) -> Self {
+        Self::Bool(value)
+    }
+}
+
+/// An in-flight span. It ends when dropped.
+pub struct TraceSpan {
+    #[cfg(feature = "observability-otel")]
+    cx: opentelemetry::Context,
+}
+
+#[cfg(feature = "observability-otel")]
+mod otel_impl {
+    use super::{AttrValue, HeaderMap, SpanKind};
+    use opentelemetry::propagation::TextMapPropagator;
+    use opentelemetry::trace::{SpanKind as OtelSpanKind, TraceContextExt, Tracer};
+    use opentelemetry::{global, Context, KeyValue, Value};
+    use opentelemetry_sdk::propagation::TraceContextPropagator;
+    use std::borrow::Cow;
+    use std::collections::HashMap;
+    use std::future::Future;
+    use std::pin::Pin;
+    use std::task::Poll;
+
+    pub(super) fn start(name: Cow<'static, str>, kind: SpanKind, parent: &Context) -> Context {
+        let tracer = global::tracer("zeroclaw");
+        let kind = match kind {
+            SpanKind::Internal => OtelSpanKind::Internal,
+            SpanKind::Server => OtelSpanKind::Server,
+            SpanKind::Client => OtelSpanKind::Client,
+            SpanKind::Consumer => OtelSpanKind::Consumer,
+        };
+        let span = tracer
+            .span_builder(name)
+            .with_kind(kind)
+            .start_with_context(&tracer, parent);
+        parent.with_span(span)
+    }
+
+    pub(super) fn key_value(key: &'static str, value: AttrValue) -> KeyValue {
+        let value = match value {
+            AttrValue::Str(s) => Value::from(s),
+            AttrValue::Int(i) => Value::from(i),
+            AttrValue::Float(f) => Value::from(f),
+            AttrValue::Bool(b) => Value::from(b),
+        };
+        KeyValue::new(key, value)
+    }
+
+    pub(super) fn extract(headers: &HeaderMap) -> Context {
+        let carrier: HashMap<String, String> = headers
+            .iter()
+            .filter_map(|(name, value)| {
+                value
+                    .to_str()
+                    .ok()
+                    .map(|value| (name.as_str().to_string(), value.to_string()))
+            })
+            .collect();
+        TraceContextPropagator::new().extract_with_context(&Context::current(), &carrier)
+    }
+
+    pub(super) fn inject() -> Vec<(String, String)> {
+        let mut carrier: HashMap<String, String> = HashMap::new();
+        TraceContextPropagator::new().inject_context(&Context::current(), &mut carrier);
+        let mut headers: Vec<_> = carrier.into_iter().collect();
+        headers.sort();
+        headers
+    }
+
+    /// Future that makes `cx` the current context while `inner` is polled.
+    pub(super) struct WithContext<F> {
+        pub(super) inner: Pin<Box<F>>,
+        pub(super) cx: Context,
+    }
+
+    impl<F: Future> Future for WithContext<F> {
+        type Output = F::Output;
+
+        fn poll(mut self: Pin<&mut Self>, task: &mut std::task::Context<'_>) -> Poll<F::Output> {
+            let _guard = self.cx.clone().attach();
+            self.inner.as_mut().poll(task)
+        }
+    }
+}
+
+impl TraceSpan {
+    /// Start a span as a child of the current span.
+    pub fn start(name: impl Into<Cow<'static, str>>, kind: SpanKind) -> Self {
+        #[cfg(feature = "observability-otel")]
+        {
+            Self {
+                cx: otel_impl::start(name.into(), kind, &opentelemetry::Context::current()),
+            }
+        }
+        #[cfg(not(feature = "observability-otel"))]
+        {
+            let _ = (name, kind);
+            Self {}
+        }
+    }
+
+    /// Start a span under the remote parent carried by a `traceparent` header,
+    /// or under the current span when `headers` carry none.
+    pub fn start_from_headers(
+        name: impl Into<Cow<'static, str>>,
+        kind: SpanKind,
+        headers: &HeaderMap,
+    ) -> Self {
+        #[cfg(feature = "observability-otel")]
+        {
+            Self {
+                cx: otel_impl::start(name.into(), kind, &otel_impl::extract(headers)),
+            }
+        }
+        #[cfg(not(feature = "observability-otel"))]
+        {
+            let _ = (name, kind, headers);
+            Self {}
+        }
+    }
+
+    /// `invoke_agent` span covering one agent turn.
+    pub fn agent_turn(provider: &str, model: &str, channel: &str) -> Self {
+        Self::start("invoke_agent", SpanKind::Internal)
+            .with_attr("gen_ai.operation.name", "invoke_agent")
+            .with_attr("gen_ai.provider.name", provider)
+            .with_attr("gen_ai.request.model", model)
+            .with_attr("zeroclaw.channel", channel)
+    }
+
+    /// `chat {model}` span covering one LLM request, including retries.
+    pub fn llm_call(provider: &str, model: &str, temperature: f64) -> Self {
+        Self::start(format!("chat {model}"), SpanKind::Client)
+            .with_attr("gen_ai.operation.name", "chat")
+            .with_attr("gen_ai.provider.name", provider)
+            .with_attr("gen_ai.request.model", model)
+            .with_attr("gen_ai.request.temperature", temperature)
+    }
+
+    /// `execute_tool {name}` span covering one tool execution.
+    pub fn tool_call(tool: &str) -> Self {
+        Self::start(format!("execute_tool {tool}"), SpanKind::Internal)
+            .with_attr("gen_ai.operation.name", "execute_tool")
+            .with_attr("gen_ai.tool.name", tool)
+    }
+
+    /// Record provider-reported token usage.
+    pub fn record_usage(&self, input_tokens: Option<u64>, output_tokens: Option<u64>) {
+        if let Some(tokens) = input_tokens {
+            self.set_attr("gen_ai.usage.input_tokens", tokens);
+        }
+        if let Some(tokens) = output_tokens {
+            self.set_attr("gen_ai.usage.output_tokens", tokens);
+        }
+    }
+
+    /// Builder-style [`set_attr`](Self::set_attr).
+    #[must_use]
+    pub fn with_attr(self, key: &'static str, value: impl Into<AttrValue>) -> Self {
+        self.set_attr(key, value);
+        self
+    }
+
+    /// Attach an attribute to the span.
+    pub fn set_attr(&self, key: &'static str, value: impl Into<AttrValue>) {
+        #[cfg(feature = "observability-otel")]
+        {
+            use opentelemetry::trace::TraceContextExt;
+            self.cx
+                .span()
+                .set_attribute(otel_impl::key_value(key, value.into()));
+        }
+        #[cfg(not(feature = "observability-otel"))]
+        {
+            let _ = (key, value);
+        }
+    }
+
+    /// Mark the span as failed.
+    pub fn set_error(&self, message: &str) {
+        #[cfg(feature = "observability-otel")]
+        {
+            use opentelemetry::trace::{Status, TraceContextExt};
+            self.cx
+                .span()
+                .set_status(Status::error(message.to_string()));
+        }
+        #[cfg(not(feature = "observability-otel"))]
+        {
+            let _ = message;
+        }
+    }
+
+    /// Mark the span as failed and end it.
+    pub fn fail(self, message: &str) {
+        self.set_error(message);
+    }
+
+    /// Run `fut` with this span as the current span, so spans started inside
+    /// become its children. The span itself stays open until dropped.
+    pub fn instrument<F: Future>(&self, fut: F) -> impl Future<Output = F::Output> {
+        #[cfg(feature = "observability-otel")]
+        {
+            otel_impl::WithContext {
+                inner: Box::pin(fut),
+                cx: self.cx.clone(),
+            }
+        }
+        #[cfg(not(feature = "observability-otel"))]
+        {
+            fut
+        }
+    }
+}
+
+impl Drop for TraceSpan {
+    fn drop(&mut self) {
+        #[cfg(feature = "observability-otel")]
+        {
+            use opentelemetry::trace::TraceContextExt;
+            self.cx.span().end();
+        }
+    }
+}
+
+/// W3C trace-context headers (`traceparent`, `tracestate`) for the current
+/// span, to forward on outbound HTTP requests. Empty when no trace is active.
+pub fn propagation_headers() -> Vec<(String, String)> {
+    #[cfg(feature = "observability-otel")]
+    {
+        otel_impl::inject()
+    }
+    #[cfg(not(feature = "observability-otel"))]
+    {
+        Vec::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TRACE_ID: &str = "4bf92f3577b34da6a3ce929d0e0e4736";
+
+    fn inbound_headers() -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            "traceparent",
+            format!("00-{TRACE_ID}-00f067aa0ba902b7-01")
+                .parse()
+                .unwrap(),
+        );
+        headers
+    }
+
+    #[test]
+    fn attr_value_conversions() {
+        assert_eq!(AttrValue::from("x"), AttrValue::Str("x".into()));
+        assert_eq!(AttrValue::from(7_u32), AttrValue::Int(7));
+        assert_eq!(AttrValue::from(u64::MAX), AttrValue::Int(i64::MAX));
+        assert_eq!(AttrValue::from(true), AttrValue::Bool(true));
+    }
+
+    #[test]
+    fn no_headers_outside_a_trace() {
+        assert!(propagation_headers().is_empty());
+    }
+
+    #[tokio::test]
+    async fn instrument_returns_inner_output() {
+        let span = TraceSpan::start("test", SpanKind::Internal).with_attr("k", 1_u64);
+        let value = span.instrument(async { 41 + 1 }).await;
+        span.set_error("boom");
+        assert_eq!(value, 42);
+    }
+
+    #[tokio::test]
+    async fn inbound_traceparent_propagates_through_nested_spans() {
+        let request =
+            TraceSpan::start_from_headers("POST /webhook", SpanKind::Server, &inbound_headers());
+        let outbound = request
+            .instrument(async {
+                let turn = TraceSpan::start("invoke_agent", SpanKind::Internal);
+                turn.instrument(async {
+                    tokio::task::yield_now().await;
+                    propagation_headers()
+                })
+                .await
+            })
+            .await;
+
+        if cfg!(feature = "observability-otel") {
+            let traceparent = outbound
+                .iter()
+                .find(|(name, _)| name == "traceparent")
+                .map(|(_, value)| value.clone())
+                .expect("traceparent header");
+            assert!(traceparent.contains(TRACE_ID), "{traceparent}");
+        } else {
+            assert!(outbound.is_empty());
+        }
+        assert!(propagation_headers().is_empty());
+    }
+}
@@ -0,0 +1,805 @@
+//! Query, aggregate and export recorded runtime trace events.
+//!
+//! Backs `zeroclaw doctor traces`: filter expressions over the trace JSONL,
+//! aggregate views (slowest and most-failing tools, tokens per turn,
+//! tool-call parse failures) and export to CSV or OTLP-JSON, so a misbehaving
+//! deployment can be diagnosed from its trace file alone.
+
+use super::runtime_trace::{event_contains_text, RuntimeTraceEvent};
+use anyhow::{bail, Result};
+use chrono::{DateTime, Duration as ChronoDuration, NaiveDate, Utc};
+use serde_json::{json, Value};
+use sha2::{Digest, Sha256};
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::fmt::Write;
+
+/// Filter over runtime trace events. Unset fields match everything.
+#[derive(Debug, Clone, Default)]
+pub struct TraceFilter {
+    /// Event type (e.g. `tool_call_result`).
+    pub event: Option<String>,
+    pub channel: Option<String>,
+    pub provider: Option<String>,
+    pub model: Option<String>,
+    /// Tool name from the event payload.
+    pub tool: Option<String>,
+    pub turn: Option<String>,
+    pub success: Option<bool>,
+    /// Inclusive lower time bound.
+    pub since: Option<DateTime<Utc>>,
+    /// Exclusive upper time bound.
+    pub until: Option<DateTime<Utc>>,
+    /// Case-insensitive text match across message, payload and routing fields.
+    pub contains: Option<String>,
+}
+
+impl TraceFilter {
+    pub fn matches(&self, event: &RuntimeTraceEvent) -> bool {
+        fn field_matches(wanted: Option<&String>, actual: Option<&str>) -> bool {
+            match wanted.map(|w| w.trim()).filter(|w| !w.is_empty()) {
+                Some(wanted) => actual.is_some_and(|actual| actual.eq_ignore_ascii_case(wanted)),
+                None => true,
+            }
+        }
+
+        if !field_matches(self.event.as_ref(), Some(&event.event_type))
+            || !field_matches(self.channel.as_ref(), event.channel.as_deref())
+            || !field_matches(self.provider.as_ref(), event.provider.as_deref())
+            || !field_matches(self.model.as_ref(), event.model.as_deref())
+            || !field_matches(self.tool.as_ref(), event_tool(event))
+            || !field_matches(self.turn.as_ref(), event.turn_id.as_deref())
+        {
+            return false;
+        }
+
+        if self.success.is_some() && event.success != self.success {
+            return false;
+        }
+
+        if self.since.is_some() || self.until.is_some() {
+            let Some(at) = event_time(event) else {
+                return false;
+            };
+            if self.since.is_some_and(|since| at < since)
+                || self.until.is_some_and(|until| at >= until)
+            {
+                return false;
+            }
+        }
+
+        match self.contains.as_deref().map(str::trim) {
+            Some(needle) if !needle.is_empty() => {
+                event_contains_text(event, &needle.to_ascii_lowercase())
+            }
+            _ => true,
+        }
+    }
+
+    /// Short human-readable description of the active filters.
+    pub fn summary(&self) -> String {
+        let mut parts = Vec::new();
+        for (name, value) in [
+            ("event", &self.event),
+            ("channel", &self.channel),
+            ("provider", &self.provider),
+            ("model", &self.model),
+            ("tool", &self.tool),
+            ("turn", &self.turn),
+            ("contains", &self.contains),
+        ] {
+            if let Some(value) = value {
+                parts.push(format!("{name}={value}"));
+            }
+        }
+        if let Some(success) = self.success {
+            parts.push(format!("success={success}"));
+        }
+        if let Some(since) = self.since {
+            parts.push(format!("since={}", since.to_rfc3339()));
+        }
+        if let Some(until) = self.until {
+            parts.push(format!("until={}", until.to_rfc3339()));
+        }
+        if parts.is_empty() {
+            "none".into()
+        } else {
+            parts.join(" ")
+        }
+    }
+}
+
+/// Parse a time bound: RFC 3339, a `YYYY-MM-DD` date (UTC midnight), or a
+/// relative age such as `30s`, `15m`, `2h`, `7d` or `1w` before `now`.
+pub fn parse_time_bound(raw: &str, now: DateTime<Utc>) -> Result<DateTime<Utc>> {
+    let raw = raw.trim();
+    if let Ok(at) = DateTime::parse_from_rfc3339(raw) {
+        return Ok(at.with_timezone(&Utc));
+    }
+    if let Ok(date) = NaiveDate::parse_from_str(raw, "%Y-%m-%d") {
+        if let Some(midnight) = date.and_hms_opt(0, 0, 0) {
+            return Ok(midnight.and_utc());
+        }
+    }
+
+    let split = raw.find(|c: char| !c.is_ascii_digit()).unwrap_or(raw.len());
+    let (amount, unit) = raw.split_at(split);
+    let Ok(amount) = amount.parse::<i64>() else {
+        bail!("Invalid time '{raw}': use RFC 3339, YYYY-MM-DD, or a relative age like 15m, 2h, 7d");
+    };
+    let age = match unit {
+        "s" => ChronoDuration::try_seconds(amount),
+        "m" => ChronoDuration::try_minutes(amount),
+        "h" => ChronoDuration::try_hours(amount),
+        "d" => ChronoDuration::try_days(amount),
+        "w" => ChronoDuration::try_weeks(amount),
+        _ => bail!("Invalid time unit in '{raw}': expected s, m, h, d or w"),
+    };
+    match age.and_then(|age| now.checked_sub_signed(age)) {
+        Some(at) => Ok(at),
+        None => bail!("Time '{raw}' is out of range"),
+    }
+}
+
+/// Timestamp of an event, if it parses.
+pub fn event_time(event: &RuntimeTraceEvent) -> Option<DateTime<Utc>> {
+    DateTime::parse_from_rfc3339(&event.timestamp)
+        .ok()
+        .map(|at| at.with_timezone(&Utc))
+}
+
+/// Tool name carried in the payload of tool-call events.
+pub fn event_tool(event: &RuntimeTraceEvent) -> Option<&str> {
+    event.payload.get("tool").and_then(Value::as_str)
+}
+
+/// Duration recorded in the payload (`duration_ms` for LLM/tool events,
+/// `elapsed_ms` for channel events).
+pub fn event_duration_ms(event: &RuntimeTraceEvent) -> Option<u64> {
+    payload_u64(event, "duration_ms").or_else(|| payload_u64(event, "elapsed_ms"))
+}
+
+fn payload_u64(event: &RuntimeTraceEvent, key: &str) -> Option<u64> {
+    event.payload.get(key).and_then(Value::as_u64)
+}
+
+/// Events recorded after `last_seen_id`, for `--follow`.
+///
+/// When the id is no longer in the file (rolling trim or a replaced file),
+/// every event is treated as new.
+pub fn events_after<'a>(
+    events: &'a [RuntimeTraceEvent],
+    last_seen_id: Option<&str>,
+) -> &'a [RuntimeTraceEvent] {
+    let Some(last_seen_id) = last_seen_id else {
+        return events;
+    };
+    match events.iter().rposition(|event| event.id == last_seen_id) {
+        Some(index) => &events[index + 1..],
+        None => events,
+    }
+}
+
+// ── Aggregations ────────────────────────────────────────────────
+
+/// Aggregate outcome and timing of one tool across `tool_call_result` events.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ToolStats {
+    pub tool: String,
+    pub calls: u64,
+    pub failures: u64,
+    /// Timing over calls that recorded a duration (denied or deduplicated calls do not).
+    pub avg_ms: u64,
+    pub p95_ms: u64,
+    pub max_ms: u64,
+}
+
+impl ToolStats {
+    pub fn failure_rate(&self) -> f64 {
+        if self.calls == 0 {
+            0.0
+        } else {
+            self.failures as f64 / self.calls as f64
+        }
+    }
+}
+
+/// Per-tool statistics, sorted by tool name.
+pub fn tool_stats(events: &[RuntimeTraceEvent]) -> Vec<ToolStats> {
+    let mut by_tool: HashMap<&str, (u64, u64, Vec<u64>)> = HashMap::new();
+    for event in events
+        .iter()
+        .filter(|event| event.event_type == "tool_call_result")
+    {
+        let Some(tool) = event_tool(event) else {
+            continue;
+        };
+        let entry = by_tool.entry(tool).or_default();
+        entry.0 += 1;
+        if event.success == Some(false) {
+            entry.1 += 1;
+        }
+        if let Some(ms) = event_duration_ms(event) {
+            entry.2.push(ms);
+        }
+    }
+
+    let mut stats: Vec<ToolStats> = by_tool
+        .into_iter()
+        .map(|(tool, (calls, failures, mut durations))| {
+            durations.sort_unstable();
+            let avg_ms = if durations.is_empty() {
+                0
+            } else {
+                durations.iter().sum::<u64>() / durations.len() as u64
+            };
+            let p95_ms = percentile(&durations, 95);
+            ToolStats {
+                tool: tool.to_string(),
+                calls,
+                failures,
+                avg_ms,
+                p95_ms,
+                max_ms: durations.last().copied().unwrap_or(0),
+            }
+        })
+        .collect();
+    stats.sort_by(|a, b| a.tool.cmp(&b.tool));
+    stats
+}
+
+/// Nearest-rank percentile over sorted samples; 0 when empty.
+fn percentile(sorted: &[u64], pct: usize) -> u64 {
+    if sorted.is_empty() {
+        return 0;
+    }
+    let rank = (sorted.len() * pct).div_ceil(100).max(1);
+    sorted[rank - 1]
+}
+
+/// Tools ordered by p95 duration, slowest first.
+pub fn slowest_tools(events: &[RuntimeTraceEvent]) -> Vec<ToolStats> {
+    let mut stats: Vec<_> = tool_stats(events)
+        .into_iter()
+        .filter(|stats| stats.max_ms > 0)
+        .collect();
+    stats.sort_by(|a, b| {
+        b.p95_ms
+            .cmp(&a.p95_ms)
+            .then(b.max_ms.cmp(&a.max_ms))
+            .then(a.tool.cmp(&b.tool))
+    });
+    stats
+}
+
+/// Tools with at least one failure, most failures first.
+pub fn failing_tools(events: &[RuntimeTraceEvent]) -> Vec<ToolStats> {
+    let mut stats: Vec<_> = tool_stats(events)
+        .into_iter()
+        .filter(|stats| stats.failures > 0)
+        .collect();
+    stats.sort_by(|a, b| {
+        b.failures
+            .cmp(&a.failures)
+            .then(b.failure_rate().total_cmp(&a.failure_rate()))
+            .then(a.tool.cmp(&b.tool))
+    });
+    stats
+}
+
+/// Token usage of one agent turn, summed over its `llm_response` events.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TurnUsage {
+    pub turn_id: String,
+    /// Timestamp of the first response in the turn.
+    pub started: String,
+    pub channel: Option<String>,
+    pub model: Option<String>,
+    pub llm_calls: u64,
+    pub input_tokens: u64,
+    pub output_tokens: u64,
+}
+
+impl TurnUsage {
+    pub fn total_tokens(&self) -> u64 {
+        self.input_tokens + self.output_tokens
+    }
+}
+
+/// Per-turn token usage, heaviest turns first.
+pub fn tokens_per_turn(events: &[RuntimeTraceEvent]) -> Vec<TurnUsage> {
+    let mut order: Vec<&str> = Vec::new();
+    let mut by_turn: HashMap<&str, TurnUsage> = HashMap::new();
+    for event in events
+        .iter()
+        .filter(|event| event.event_type == "llm_response")
+    {
+        let Some(turn_id) = event.turn_id.as_deref() else {
+            continue;
+        };
+        let usage = by_turn.entry(turn_id).or_insert_with(|| {
+            order.push(turn_id);
+            TurnUsage {
+                turn_id: turn_id.to_string(),
+                started: event.timestamp.clone(),
+                channel: event.channel.clone(),
+                model: event.model.clone(),
+                llm_calls: 0,
+                input_tokens: 0,
+                output_tokens: 0,
+            }
+        });
+        usage.llm_calls += 1;
+        usage.input_tokens += payload_u64(event, "input_tokens").unwrap_or(0);
+        usage.output_tokens += payload_u64(event, "output_tokens").unwrap_or(0);
+    }
+
+    let mut turns: Vec<TurnUsage> = order
+        .into_iter()
+        .filter_map(|turn_id| by_turn.remove(turn_id))
+        .collect();
+    // Stable sort keeps recording order among equal totals.
+    turns.sort_by_key(|turn| std::cmp::Reverse(turn.total_tokens()));
+    turns
+}
+
+/// How often a provider/model produced tool calls that could not be parsed.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParseFailureStats {
+    pub provider: String,
+    pub model: String,
+    pub responses: u64,
+    pub parse_issues: u64,
+}
+
+impl ParseFailureStats {
+    /// Parse issues per model response.
+    pub fn rate(&self) -> f64 {
+        if self.responses == 0 {
+            0.0
+        } else {
+            self.parse_issues as f64 / self.responses as f64
+        }
+    }
+}
+
+/// Tool-call parse issues per provider/model, most frequent first.
+pub fn parse_failures(events: &[RuntimeTraceEvent]) -> Vec<ParseFailureStats> {
+    let mut by_model: HashMap<(&str, &str), (u64, u64)> = HashMap::new();
+    for event in events {
+        let is_issue = match event.event_type.as_str() {
+            "llm_response" => false,
+            "tool_call_parse_issue" => true,
+            _ => continue,
+        };
+        let key = (
+            event.provider.as_deref().unwrap_or("-"),
+            event.model.as_deref().unwrap_or("-"),
+        );
+        let entry = by_model.entry(key).or_default();
+        if is_issue {
+            entry.1 += 1;
+        } else {
+            entry.0 += 1;
+        }
+    }
+
+    let mut stats: Vec<ParseFailureStats> = by_model
+        .into_iter()
+        .map(
+            |((provider, model), (responses, parse_issues))| ParseFailureStats {
+                provider: provider.to_string(),
+                model: model.to_string(),
+                responses,
+                parse_issues,
+            },
+        )
+        .collect();
+    stats.sort_by(|a, b| {
+        b.parse_issues
+            .cmp(&a.parse_issues)
+            .then(b.rate().total_cmp(&a.rate()))
+            .then(a.provider.cmp(&b.provider))
+            .then(a.model.cmp(&b.model))
+    });
+    stats
+}
+
+// ── Export ──────────────────────────────────────────────────────
+
+const CSV_HEADER: &str = "id,timestamp,event_type,channel,provider,model,turn_id,success,tool,duration_ms,input_tokens,output_tokens,message";
+
+/// Render events as CSV (RFC 4180 quoting), one row per event.
+pub fn to_csv(events: &[RuntimeTraceEvent]) -> String {
+    fn field(value: &str) -> Cow<'_, str> {
+        if value.contains([',', '"', '\n', '\r']) {
+            Cow::Owned(format!("\"{}\"", value.replace('"', "\"\"")))
+        } else {
+            Cow::Borrowed(value)
+        }
+    }
+    fn number(value: Option<u64>) -> String {
+        value.map(|v| v.to_string()).unwrap_or_default()
+    }
+
+    let mut out = String::from(CSV_HEADER);
+    out.push('\n');
+    for event in events {
+        let success = match event.success {
+            Some(true) => "true",
+            Some(false) => "false",
+            None => "",
+        };
+        let _ = writeln!(
+            out,
+            "{},{},{},{},{},{},{},{},{},{},{},{},{}",
+            field(&event.id),
+            field(&event.timestamp),
+            field(&event.event_type),
+            field(event.channel.as_deref().unwrap_or_default()),
+            field(event.provider.as_deref().unwrap_or_default()),
+            field(event.model.as_deref().unwrap_or_default()),
+            field(event.turn_id.as_deref().unwrap_or_default()),
+            success,
+            field(event_tool(event).unwrap_or_default()),
+            number(event_duration_ms(event)),
+            number(payload_u64(event, "input_tokens")),
+            number(payload_u64(event, "output_tokens")),
+            field(event.message.as_deref().unwrap_or_default()),
+        );
+    }
+    out
+}
+
+/// Render events as an OTLP/JSON `ExportTraceServiceRequest`.
+///
+/// Each event becomes one span; events of the same turn share a trace id.
+/// The result can be POSTed to an OTLP/HTTP collector at `/v1/traces`.
+pub fn to_otlp_json(events: &[RuntimeTraceEvent], service_name: &str) -> Value {
+    fn string_attr(key: &str, value: &str) -> Value {
+        json!({ "key": key, "value": { "stringValue": value } })
+    }
+    fn int_attr(key: &str, value: u64) -> Value {
+        // OTLP/JSON encodes 64-bit integers as strings.
+        json!({ "key": key, "value": { "intValue": value.to_string() } })
+    }
+
+    let spans: Vec<Value> = events
+        .iter()
+        .map(|event| {
+            let trace_seed = event.turn_id.as_deref().unwrap_or(&event.id);
+            let end_nanos = event_time(event)
+                .and_then(|at| at.timestamp_nanos_opt())
+                .unwrap_or_default();
+            let duration_nanos = event_duration_ms(event)
+                .and_then(|ms| i64::try_from(ms).ok())
+                .map_or(0, |ms| ms.saturating_mul(1_000_000));
+            let start_nanos = end_nanos.saturating_sub(duration_nanos).max(0);
+
+            let mut attributes = vec![string_attr("zeroclaw.event_type", &event.event_type)];
+            for (key, value) in [
+                ("zeroclaw.channel", event.channel.as_deref()),
+                ("gen_ai.provider.name", event.provider.as_deref()),
+                ("gen_ai.request.model", event.model.as_deref()),
+                ("zeroclaw.turn_id", event.turn_id.as_deref()),
+                ("gen_ai.tool.name", event_tool(event)),
+                ("zeroclaw.message", event.message.as_deref()),
+            ] {
+                if let Some(value) = value {
+                    attributes.push(string_attr(key, value));
+                }
+            }
+            for (key, payload_key) in [
+                ("gen_ai.usage.input_tokens", "input_tokens"),
+                ("gen_ai.usage.output_tokens", "output_tokens"),
+            ] {
+                if let Some(value) = payload_u64(event, payload_key) {
+                    attributes.push(int_attr(key, value));
+                }
+            }
+
+            let status = match event.success {
+                Some(true) => json!({ "code": 1 }),
+                Some(false) => json!({
+                    "code": 2,
+                    "message": event.message.as_deref().unwrap_or_default(),
+                }),
+                None => json!({}),
+            };
+
+            json!({
+                "traceId": hex_digest(trace_seed, 16),
+                "spanId": hex_digest(&event.id, 8),
+                "name": event.event_type,
+                "kind": 1,
+                "startTimeUnixNano": start_nanos.to_string(),
+                "endTimeUnixNano": end_nanos.to_string(),
+                "attributes": attributes,
+                "status": status,
+            })
+        })
+        .collect();
+
+    json!({
+        "resourceSpans": [{
+            "resource": { "attributes": [string_attr("service.name", service_name)] },
+            "scopeSpans": [{
+                "scope": { "name": "zeroclaw.runtime_trace" },
+                "spans": spans,
+            }],
+        }],
+    })
+}
+
+/// First `bytes` bytes of SHA-256(`seed`) as lowercase hex (deterministic OTLP ids).
+fn hex_digest(seed: &str, bytes: usize) -> String {
+    hex::encode(&Sha256::digest(seed.as_bytes())[..bytes])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn event(id: &str, event_type: &str, payload: Value) -> RuntimeTraceEvent {
+        RuntimeTraceEvent {
+            id: id.into(),
+            timestamp: "2026-03-01T12:00:00+00:00".into(),
+            event_type: event_type.into(),
+            channel: Some("telegram".into()),
+            provider: Some("openrouter".into()),
+            model: Some("claude-sonnet".into()),
+            turn_id: Some("turn-1".into()),
+            success: Some(true),
+            message: None,
+            payload,
+        }
+    }
+
+    fn tool_result(
+        id: &str,
+        tool: &str,
+        success: bool,
+        duration_ms: Option<u64>,
+    ) -> RuntimeTraceEvent {
+        let mut payload = json!({ "tool": tool });
+        if let Some(ms) = duration_ms {
+            payload["duration_ms"] = json!(ms);
+        }
+        RuntimeTraceEvent {
+            success: Some(success),
+            ..event(id, "tool_call_result", payload)
+        }
+    }
+
+    #[test]
+    fn filter_matches_routing_fields_and_payload_tool() {
+        let shell = tool_result("a", "shell", false, Some(10));
+        let filter = TraceFilter {
+            channel: Some("Telegram".into()),
+            tool: Some("shell".into()),
+            success: Some(false),
+            ..TraceFilter::default()
+        };
+        assert!(filter.matches(&shell));
+
+        let other_tool = TraceFilter {
+            tool: Some("file_read".into()),
+            ..TraceFilter::default()
+        };
+        assert!(!other_tool.matches(&shell));
+
+        let succeeded = TraceFilter {
+            success: Some(true),
+            ..TraceFilter::default()
+        };
+        assert!(!succeeded.matches(&shell));
+    }
+
+    #[test]
+    fn filter_applies_time_range_and_text() {
+        let mut llm = event("a", "llm_response", json!({ "text": "Rate LIMIT hit" }));
+        let at = event_time(&llm).unwrap();
+
+        let window = TraceFilter {
+            since: Some(at),
+            until: Some(at + ChronoDuration::seconds(1)),
+            contains: Some("rate limit".into()),
+            ..TraceFilter::default()
+        };
+        assert!(window.matches(&llm));
+
+        let later = TraceFilter {
+            since: Some(at + ChronoDuration::seconds(1)),
+            ..TraceFilter::default()
+        };
+        assert!(!later.matches(&llm));
+
+        llm.timestamp = "not-a-time".into();
+        assert!(!window.matches(&llm));
+    }
+
+    #[test]
+    fn parse_time_bound_accepts_absolute_and_relative_values() {
+        let now = DateTime::parse_from_rfc3339("2026-03-01T12:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+
+        assert_eq!(
+            parse_time_bound("2h", now).unwrap(),
+            now - ChronoDuration::hours(2)
+        );
+        assert_eq!(
+            parse_time_bound("7d", now).unwrap(),
+            now - ChronoDuration::days(7)
+        );
+        assert_eq!(
+            parse_time_bound("2026-02-28", now).unwrap().to_rfc3339(),
+            "2026-02-28T00:00:00+00:00"
+        );
+        assert_eq!(
+            parse_time_bound("2026-03-01T10:00:00+02:00", now)
+                .unwrap()
+                .to_rfc3339(),
+            "2026-03-01T08:00:00+00:00"
+        );
+        assert!(parse_time_bound("5y", now).is_err());
+        assert!(parse_time_bound("yesterday", now).is_err());
+    }
+
+    #[test]
+    fn events_after_resumes_from_last_seen_id() {
+        let events = vec![
+            event("a", "turn_start", json!({})),
+            event("b", "llm_response", json!({})),
+            event("c", "turn_final_response", json!({})),
+        ];
+        let ids =
+            |slice: &[RuntimeTraceEvent]| slice.iter().map(|e| e.id.clone()).collect::<Vec<_>>();
+
+        assert_eq!(ids(events_after(&events, Some("b"))), ["c"]);
+        assert!(events_after(&events, Some("c")).is_empty());
+        assert_eq!(ids(events_after(&events, Some("trimmed"))), ["a", "b", "c"]);
+        assert_eq!(events_after(&events, None).len(), 3);
+    }
+
+    #[test]
+    fn tool_views_rank_by_latency_and_failures() {
+        let events = vec![
+            tool_result("1", "shell", true, Some(100)),
+            tool_result("2", "shell", false, Some(900)),
+            tool_result("3", "shell", false, Some(200)),
+            tool_result("4", "file_read", true, Some(5)),
+            tool_result("5", "web_fetch", false, None),
+            tool_result("6", "web_fetch", true, Some(1500)),
+        ];
+
+        let slowest = slowest_tools(&events);
+        let order: Vec<_> = slowest.iter().map(|s| s.tool.as_str()).collect();
+        assert_eq!(order, ["web_fetch", "shell", "file_read"]);
+        let shell = &slowest[1];
+        assert_eq!(
+            (shell.calls, shell.avg_ms, shell.p95_ms, shell.max_ms),
+            (3, 400, 900, 900)
+        );
+
+        let failing = failing_tools(&events);
+        let order: Vec<_> = failing
+            .iter()
+            .map(|s| (s.tool.as_str(), s.failures))
+            .collect();
+        assert_eq!(order, [("shell", 2), ("web_fetch", 1)]);
+        assert!((failing[0].failure_rate() - 2.0 / 3.0).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn tokens_per_turn_sums_llm_responses() {
+        let mut second_turn = event(
+            "3",
+            "llm_response",
+            json!({ "input_tokens": 5000, "output_tokens": 10 }),
+        );
+        second_turn.turn_id = Some("turn-2".into());
+        let events = vec![
+            event(
+                "1",
+                "llm_response",
+                json!({ "input_tokens": 100, "output_tokens": 20 }),
+            ),
+            event(
+                "2",
+                "llm_response",
+                json!({ "input_tokens": 150, "output_tokens": null }),
+            ),
+            second_turn,
+            event("4", "tool_call_result", json!({ "tool": "shell" })),
+        ];
+
+        let turns = tokens_per_turn(&events);
+        assert_eq!(turns.len(), 2);
+        assert_eq!(turns[0].turn_id, "turn-2");
+        assert_eq!(turns[1].turn_id, "turn-1");
+        assert_eq!(turns[1].llm_calls, 2);
+        assert_eq!(turns[1].input_tokens, 250);
+        assert_eq!(turns[1].total_tokens(), 270);
+    }
+
+    #[test]
+    fn parse_failures_count_per_model() {
+        let mut other = event("4", "llm_response", json!({}));
+        other.model = Some("llama3".into());
+        let events = vec![
+            event("1", "llm_response", json!({})),
+            event("2", "llm_response", json!({})),
+            event("3", "tool_call_parse_issue", json!({})),
+            other,
+        ];
+
+        let stats = parse_failures(&events);
+        assert_eq!(stats[0].model, "claude-sonnet");
+        assert_eq!((stats[0].responses, stats[0].parse_issues), (2, 1));
+        assert!((stats[0].rate() - 0.5).abs() < f64::EPSILON);
+        assert_eq!(
+            (stats[1].model.as_str(), stats[1].parse_issues),
+            ("llama3", 0)
+        );
+    }
+
+    #[test]
+    fn csv_export_quotes_fields_and_flattens_payload() {
+        let mut failed = tool_result("1", "shell", false, Some(42));
+        failed.message = Some("exit 1, \"denied\"\nretry".into());
+
+        let csv = to_csv(&[failed]);
+        let mut lines = csv.lines();
+        assert_eq!(lines.next(), Some(CSV_HEADER));
+        assert!(csv.contains(
+            "1,2026-03-01T12:00:00+00:00,tool_call_result,telegram,openrouter,claude-sonnet,turn-1,false,shell,42,,,\"exit 1, \"\"denied\"\"\nretry\"\n"
+        ));
+    }
+
+    #[test]
+    fn otlp_export_groups_turn_into_one_trace() {
+        let events = vec![
+            event(
+                "1",
+                "llm_response",
+                json!({ "duration_ms": 250, "input_tokens": 12 }),
+            ),
+            tool_result("2", "shell", false, Some(10)),
+        ];
+
+        let otlp = to_otlp_json(&events, "zeroclaw");
+        let resource = &otlp["resourceSpans"][0];
+        assert_eq!(
+            resource["resource"]["attributes"][0]["value"]["stringValue"],
+            "zeroclaw"
+        );
+        let spans = resource["scopeSpans"][0]["spans"].as_array().unwrap();
+        assert_eq!(spans.len(), 2);
+        assert_eq!(spans[0]["traceId"], spans[1]["traceId"]);
+        assert_eq!(spans[0]["traceId"].as_str().unwrap().len(), 32);
+        assert_eq!(spans[0]["spanId"].as_str().unwrap().len(), 16);
+        assert_ne!(spans[0]["spanId"], spans[1]["spanId"]);
+
+        let end: i64 = spans[0]["endTimeUnixNano"]
+            .as_str()
+            .unwrap()
+            .parse()
+            .unwrap();
+        let start: i64 = spans[0]["startTimeUnixNano"]
+            .as_str()
+            .unwrap()
+            .parse()
+            .unwrap();
+        assert_eq!(end - start, 250_000_000);
+        assert!(spans[0]["attributes"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .any(|attr| attr["key"] == "gen_ai.usage.input_tokens"
+                && attr["value"]["intValue"] == "12"));
+
+        assert_eq!(spans[1]["status"]["code"], 2);
+        assert_eq!(spans[1]["name"], "tool_call_result");
+    }
+}
@@ -65,6 +65,39 @@ pub enum ObserverEvent {
         /// Human-readable error description. Must not contain secrets or tokens.
         message: String,
     },
+    /// The robot safety monitor reported an event (obstacle, e-stop, bump, watchdog).
+    RobotSafety {
+        /// Event kind (e.g., `"emergency_stop"`, `"obstacle_detected"`).
+        event: String,
+        /// Human-readable detail; empty when the event carries none.
+        detail: String,
+    },
+    /// A scheduled cron job finished one run.
+    CronJobRun {
+        job_id: String,
+        success: bool,
+        duration: Duration,
+    },
+    /// A standard operating procedure run reached a terminal status.
+    SopRun {
+        /// SOP name.
+        sop: String,
+        /// Terminal status (e.g., `"completed"`, `"failed"`, `"cancelled"`).
+        status: String,
+    },
+    /// A pending tool approval was answered (or timed out).
+    ApprovalResolved {
+        /// Channel the approval was requested on (`"cli"` for interactive runs).
+        channel: String,
+        tool: String,
+        approved: bool,
+        /// Time spent waiting for the decision.
+        wait: Duration,
+    },
+    /// The channel supervisor restarted a listener after it exited or failed.
+    ChannelRestart { channel: String },
+    /// A response cache lookup completed.
+    ResponseCacheLookup { hit: bool },
 }
 
 /// Numeric metrics emitted by the agent runtime.
@@ -81,6 +114,14 @@ pub enum ObserverMetric {
     ActiveSessions(u64),
     /// Current depth of the inbound message queue.
     QueueDepth(u64),
+    /// Spend and configured limit for a budget period (`"daily"` or `"monthly"`).
+    CostBudget {
+        period: String,
+        spent_usd: f64,
+        limit_usd: f64,
+    },
+    /// Number of entries held by a memory backend.
+    MemoryEntries { backend: String, count: u64 },
 }
 
 /// Core observability trait for recording agent runtime telemetry.
@@ -14,7 +14,7 @@ use crate::memory::{
 use crate::providers::{
     canonical_china_provider_name, is_glm_alias, is_glm_cn_alias, is_minimax_alias,
     is_moonshot_alias, is_qianfan_alias, is_qwen_alias, is_qwen_oauth_alias, is_zai_alias,
-    is_zai_cn_alias,
+    is_zai_cn_alias, ModelInfo,
 };
 use anyhow::{bail, Context, Result};
 use console::style;
@@ -147,6 +147,8 @@ pub async fn run_wizard(force: bool) -> Result<Config> {
         model_routes: Vec::new(),
         embedding_routes: Vec::new(),
         heartbeat: HeartbeatConfig::default(),
+        goal_loop: crate::config::GoalLoopConfig::default(),
+        sop: crate::config::SopConfig::default(),
         cron: crate::config::CronConfig::default(),
         channels_config,
         memory: memory_config, // User-selected memory backend
@@ -164,11 +166,13 @@ pub async fn run_wizard(force: bool) -> Result<Config> {
         identity: crate::config::IdentityConfig::default(),
         cost: crate::config::CostConfig::default(),
         peripherals: crate::config::PeripheralsConfig::default(),
+        robot: crate::config::RobotConfig::default(),
         agents: std::collections::HashMap::new(),
         hooks: crate::config::HooksConfig::default(),
         hardware: hardware_config,
         query_classification: crate::config::QueryClassificationConfig::default(),
         transcription: crate::config::TranscriptionConfig::default(),
+        layers: None,
     };
 
     println!(
@@ -498,6 +502,8 @@ async fn run_quick_setup_with_home(
         model_routes: Vec::new(),
         embedding_routes: Vec::new(),
         heartbeat: HeartbeatConfig::default(),
+        goal_loop: crate::config::GoalLoopConfig::default(),
+        sop: crate::config::SopConfig::default(),
         cron: crate::config::CronConfig::default(),
         channels_config: ChannelsConfig::default(),
         memory: memory_config,
@@ -515,11 +521,13 @@ async fn run_quick_setup_with_home(
         identity: crate::config::IdentityConfig::default(),
         cost: crate::config::CostConfig::default(),
         peripherals: crate::config::PeripheralsConfig::default(),
+        robot: crate::config::RobotConfig::default(),
         agents: std::collections::HashMap::new(),
         hooks: crate::config::HooksConfig::default(),
         hardware: crate::config::HardwareConfig::default(),
         query_classification: crate::config::QueryClassificationConfig::default(),
         transcription: crate::config::TranscriptionConfig::default(),
+        layers: None,
     };
 
     config.save().await?;
@@ -1281,11 +1289,28 @@ fn parse_ollama_model_ids(payload: &Value) -> Vec<String> {
     normalize_model_ids(ids)
 }
 
+/// Model ids from a live `/models` call, plus any catalog metadata it carried.
+#[derive(Debug, Default)]
+struct LiveModels {
+    ids: Vec<String>,
+    metadata: Vec<ModelInfo>,
+}
+
+impl LiveModels {
+    fn ids_only(ids: Vec<String>) -> Self {
+        Self {
+            ids,
+            metadata: Vec::new(),
+        }
+    }
+}
+
 fn fetch_openai_compatible_models(
+    provider_name: &str,
     endpoint: &str,
     api_key: Option<&str>,
     allow_unauthenticated: bool,
-) -> Result<Vec<String>> {
+) -> Result<LiveModels> {
     let client = build_model_fetch_client()?;
     let mut request = client.get(endpoint);
 
@@ -1302,10 +1327,13 @@ fn fetch_openai_compatible_models(
         .json()
         .context("failed to parse model list response")?;
 
-    Ok(parse_openai_compatible_model_ids(&payload))
+    Ok(LiveModels {
+        ids: parse_openai_compatible_model_ids(&payload),
+        metadata: crate::providers::catalog::parse_models_payload(provider_name, &payload),
+    })
 }
 
-fn fetch_openrouter_models(api_key: Option<&str>) -> Result<Vec<String>> {
+fn fetch_openrouter_models(api_key: Option<&str>) -> Result<LiveModels> {
     let client = build_model_fetch_client()?;
     let mut request = client.get("https://openrouter.ai/api/v1/models");
     if let Some(api_key) = api_key {
@@ -1319,10 +1347,13 @@ fn fetch_openrouter_models(api_key: Option<&str>) -> Result<Vec<String>> {
         .json()
         .context("failed to parse OpenRouter model list response")?;
 
-    Ok(parse_openai_compatible_model_ids(&payload))
+    Ok(LiveModels {
+        ids: parse_openai_compatible_model_ids(&payload),
+        metadata: crate::providers::catalog::parse_models_payload("openrouter", &payload),
+    })
 }
 
-fn fetch_anthropic_models(api_key: Option<&str>) -> Result<Vec<String>> {
+fn fetch_anthropic_models(api_key: Option<&str>) -> Result<LiveModels> {
     let Some(api_key) = api_key else {
         bail!("Anthropic model fetch requires API key or OAuth token");
     };
@@ -1354,10 +1385,13 @@ fn fetch_anthropic_models(api_key: Option<&str>) -> Result<Vec<String>> {
         .json()
         .context("failed to parse Anthropic model list response")?;
 
-    Ok(parse_openai_compatible_model_ids(&payload))
+    Ok(LiveModels {
+        ids: parse_openai_compatible_model_ids(&payload),
+        metadata: crate::providers::catalog::parse_models_payload("anthropic", &payload),
+    })
 }
 
-fn fetch_gemini_models(api_key: Option<&str>) -> Result<Vec<String>> {
+fn fetch_gemini_models(api_key: Option<&str>) -> Result<LiveModels> {
     let Some(api_key) = api_key else {
         bail!("Gemini model fetch requires API key");
     };
@@ -1372,7 +1406,10 @@ fn fetch_gemini_models(api_key: Option<&str>) -> Result<Vec<String>> {
         .json()
         .context("failed to parse Gemini model list response")?;
 
-    Ok(parse_gemini_model_ids(&payload))
+    Ok(LiveModels {
+        ids: parse_gemini_model_ids(&payload),
+        metadata: crate::providers::catalog::parse_models_payload("gemini", &payload),
+    })
 }
 
 fn fetch_ollama_models() -> Result<Vec<String>> {
@@ -1471,7 +1508,7 @@ fn fetch_live_models_for_provider(
     provider_name: &str,
     api_key: &str,
     provider_api_url: Option<&str>,
-) -> Result<Vec<String>> {
+) -> Result<LiveModels> {
     let requested_provider_name = provider_name;
     let provider_name = canonical_provider_name(provider_name);
     let ollama_remote = provider_name == "ollama" && ollama_uses_remote_endpoint(provider_api_url);
@@ -1506,7 +1543,7 @@ fn fetch_live_models_for_provider(
             if ollama_remote {
                 // Remote Ollama endpoints can serve cloud-routed models.
                 // Keep this curated list aligned with current Ollama cloud catalog.
-                vec![
+                LiveModels::ids_only(vec![
                     "glm-5:cloud".to_string(),
                     "glm-4.7:cloud".to_string(),
                     "gpt-oss:20b:cloud".to_string(),
@@ -1517,13 +1554,15 @@ fn fetch_live_models_for_provider(
                     "kimi-k2.5:cloud".to_string(),
                     "minimax-m2.5:cloud".to_string(),
                     "deepseek-v3.1:671b:cloud".to_string(),
-                ]
+                ])
             } else {
                 // Local endpoints should not surface cloud-only suffixes.
-                fetch_ollama_models()?
-                    .into_iter()
-                    .filter(|model_id| !model_id.ends_with(":cloud"))
-                    .collect()
+                LiveModels::ids_only(
+                    fetch_ollama_models()?
+                        .into_iter()
+                        .filter(|model_id| !model_id.ends_with(":cloud"))
+                        .collect(),
+                )
             }
         }
         _ => {
@@ -1533,12 +1572,13 @@ fn fetch_live_models_for_provider(
                 let allow_unauthenticated =
                     allows_unauthenticated_model_fetch(requested_provider_name);
                 fetch_openai_compatible_models(
+                    provider_name,
                     &endpoint,
                     api_key.as_deref(),
                     allow_unauthenticated,
                 )?
             } else {
-                Vec::new()
+                LiveModels::default()
             }
         }
     };
@@ -1640,6 +1680,23 @@ async fn cache_live_models_for_provider(
     save_model_cache_state(workspace_dir, &state).await
 }
 
+/// Cache fetched model ids and record their metadata in the model catalog.
+async fn record_live_models(
+    workspace_dir: &Path,
+    provider_name: &str,
+    live: &LiveModels,
+) -> Result<()> {
+    cache_live_models_for_provider(workspace_dir, provider_name, &live.ids).await?;
+    if !live.metadata.is_empty() {
+        crate::providers::catalog::record_refresh(
+            workspace_dir,
+            canonical_provider_name(provider_name),
+            live.metadata.clone(),
+        )?;
+    }
+    Ok(())
+}
+
 async fn load_cached_models_for_provider_internal(
     workspace_dir: &Path,
     provider_name: &str,
@@ -1765,14 +1822,20 @@ pub async fn run_models_refresh(
     let api_key = config.api_key.clone().unwrap_or_default();
 
     match fetch_live_models_for_provider(&provider_name, &api_key, config.api_url.as_deref()) {
-        Ok(models) if !models.is_empty() => {
-            cache_live_models_for_provider(&config.workspace_dir, &provider_name, &models).await?;
+        Ok(live) if !live.ids.is_empty() => {
+            record_live_models(&config.workspace_dir, &provider_name, &live).await?;
             println!(
                 "Refreshed '{}' model cache with {} models.",
                 provider_name,
-                models.len()
+                live.ids.len()
             );
-            print_model_preview(&models);
+            if !live.metadata.is_empty() {
+                println!(
+                    "Recorded catalog metadata (context window, pricing) for {} models.",
+                    live.metadata.len()
+                );
+            }
+            print_model_preview(&live.ids);
             Ok(())
         }
         Ok(_) => {
@@ -1814,31 +1877,51 @@ pub async fn run_models_list(config: &Config, provider_override: Option<&str>) -
         .unwrap_or("openrouter");
 
     let cached = load_any_cached_models_for_provider(&config.workspace_dir, provider_name).await?;
+    let catalog = crate::providers::ModelCatalog::load(&config.workspace_dir);
 
-    let Some(cached) = cached else {
-        println!();
-        println!(
-            "  No cached models for '{provider_name}'. Run: zeroclaw models refresh --provider {provider_name}"
-        );
-        println!();
-        return Ok(());
+    let models = match cached {
+        Some(cached) => {
+            println!();
+            println!(
+                "  {} models for '{}' (cached {} ago):",
+                cached.models.len(),
+                provider_name,
+                humanize_age(cached.age_secs)
+            );
+            cached.models
+        }
+        None => {
+            let known: Vec<String> = catalog
+                .for_provider(provider_name)
+                .map(|model| model.id.clone())
+                .collect();
+            println!();
+            if known.is_empty() {
+                println!(
+                    "  No cached models for '{provider_name}'. Run: zeroclaw models refresh --provider {provider_name}"
+                );
+                println!();
+                return Ok(());
+            }
+            println!(
+                "  {} catalog models for '{provider_name}' (run `zeroclaw models refresh --provider {provider_name}` for the live list):",
+                known.len()
+            );
+            known
+        }
     };
 
     println!();
-    println!(
-        "  {} models for '{}' (cached {} ago):",
-        cached.models.len(),
-        provider_name,
-        humanize_age(cached.age_secs)
-    );
-    println!();
-    for model in &cached.models {
+    for model in &models {
         let marker = if config.default_model.as_deref() == Some(model.as_str()) {
             "* "
         } else {
             "  "
         };
-        println!("  {marker}{model}");
+        match catalog.find(provider_name, model) {
+            Some(info) => println!("  {marker}{model}  {}", style(info.summary()).dim()),
+            None => println!("  {marker}{model}"),
+        }
     }
     println!();
     Ok(())
@@ -1871,6 +1954,19 @@ pub async fn run_models_status(config: &Config) -> Result<()> {
         "  Temp:      {}",
         style(format!("{:.1}", config.default_temperature)).cyan()
     );
+    match crate::providers::ModelCatalog::load(&config.workspace_dir).find(provider, model) {
+        Some(info) => {
+            println!("  Catalog:   {}", info.summary());
+            if info.is_deprecated(chrono::Utc::now().date_naive()) {
+                println!(
+                    "  Warning:   {}",
+                    style("model is retired; pick a replacement with `zeroclaw models set`")
+                        .yellow()
+                );
+            }
+        }
+        None => println!("  Catalog:   {}", style("unknown model").yellow()),
+    }
 
     match load_any_cached_models_for_provider(&config.workspace_dir, provider).await? {
         Some(cached) => {
@@ -2701,13 +2797,9 @@ async fn setup_provider(workspace_dir: &Path) -> Result<(String, String, String,
                     &api_key,
                     provider_api_url.as_deref(),
                 ) {
-                    Ok(live_model_ids) if !live_model_ids.is_empty() => {
-                        cache_live_models_for_provider(
-                            workspace_dir,
-                            provider_name,
-                            &live_model_ids,
-                        )
-                        .await?;
+                    Ok(live) if !live.ids.is_empty() => {
+                        record_live_models(workspace_dir, provider_name, &live).await?;
+                        let live_model_ids = live.ids;
 
                         let fetched_count = live_model_ids.len();
                         let shown_count = fetched_count.min(LIVE_MODEL_MAX_OPTIONS);
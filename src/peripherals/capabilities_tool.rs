@@ -1,6 +1,6 @@
 //! Hardware capabilities tool — Phase C: query device for reported GPIO pins.
 
-use super::serial::SerialTransport;
+use super::traits::PeripheralTransport;
 use crate::tools::traits::{Tool, ToolResult};
 use async_trait::async_trait;
 use serde_json::json;
@@ -8,12 +8,12 @@ use std::sync::Arc;
 
 /// Tool: query device capabilities (GPIO pins, LED pin) from firmware.
 pub struct HardwareCapabilitiesTool {
-    /// (board_name, transport) for each serial board.
-    boards: Vec<(String, Arc<SerialTransport>)>,
+    /// (board_name, transport) for each serial or simulated board.
+    boards: Vec<(String, Arc<dyn PeripheralTransport>)>,
 }
 
 impl HardwareCapabilitiesTool {
-    pub(crate) fn new(boards: Vec<(String, Arc<SerialTransport>)>) -> Self {
+    pub(crate) fn new(boards: Vec<(String, Arc<dyn PeripheralTransport>)>) -> Self {
         Self { boards }
     }
 }
@@ -50,7 +50,7 @@ impl Tool for HardwareCapabilitiesTool {
                     continue;
                 }
             }
-            match transport.capabilities().await {
+            match transport.request("capabilities", json!({})).await {
                 Ok(result) => {
                     let output = if result.success {
                         if let Ok(parsed) =
@@ -84,7 +84,8 @@ impl Tool for HardwareCapabilitiesTool {
             if filter.is_some() {
                 "No matching board or capabilities not supported.".to_string()
             } else {
-                "No serial boards configured or capabilities not supported.".to_string()
+                "No serial or simulated boards configured or capabilities not supported."
+                    .to_string()
             }
         } else {
             outputs.join("\n")
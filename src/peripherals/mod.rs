@@ -7,6 +7,8 @@ pub mod traits;
 
 #[cfg(feature = "hardware")]
 pub mod serial;
+#[cfg(feature = "hardware")]
+pub mod simulated;
 
 #[cfg(feature = "hardware")]
 pub mod arduino_flash;
@@ -26,11 +28,13 @@ pub mod rpi;
 
 use crate::config::{Config, PeripheralBoardConfig, PeripheralsConfig};
 #[cfg(feature = "hardware")]
-use crate::peripherals::traits::Peripheral;
+use crate::peripherals::traits::{Peripheral, PeripheralTransport};
 #[cfg(feature = "hardware")]
 use crate::tools::HardwareMemoryMapTool;
 use crate::tools::Tool;
 use anyhow::Result;
+#[cfg(feature = "hardware")]
+use std::sync::Arc;
 
 /// List configured boards from config (no connection yet).
 pub fn list_configured_boards(config: &PeripheralsConfig) -> Vec<&PeripheralBoardConfig> {
@@ -63,7 +67,11 @@ pub async fn handle_command(cmd: crate::PeripheralCommands, config: &Config) ->
             } else {
                 println!("Configured peripherals:");
                 for b in boards {
-                    let path = b.path.as_deref().unwrap_or("(native)");
+                    let path = match b.path.as_deref() {
+                        Some(path) => path,
+                        None if b.transport == "simulated" => "(simulated)",
+                        None => "(native)",
+                    };
                     println!("  {}  {}  {}", b.board, b.transport, path);
                 }
             }
@@ -94,6 +102,7 @@ pub async fn handle_command(cmd: crate::PeripheralCommands, config: &Config) ->
                 transport: transport.to_string(),
                 path: path_opt,
                 baud: 115_200,
+                simulation: None,
             });
             cfg.save().await?;
             println!("Added {} at {}. Restart daemon to apply.", board, path);
@@ -143,7 +152,8 @@ pub async fn create_peripheral_tools(config: &PeripheralsConfig) -> Result<Vec<B
     }
 
     let mut tools: Vec<Box<dyn Tool>> = Vec::new();
-    let mut serial_transports: Vec<(String, std::sync::Arc<serial::SerialTransport>)> = Vec::new();
+    let mut transports: Vec<(String, Arc<dyn PeripheralTransport>)> = Vec::new();
+    let mut memory_transports: Vec<(String, Arc<dyn PeripheralTransport>)> = Vec::new();
 
     for board in &config.boards {
         // Arduino Uno Q: Bridge transport (socket to local Bridge app)
@@ -172,6 +182,28 @@ pub async fn create_peripheral_tools(config: &PeripheralsConfig) -> Result<Vec<B
             continue;
         }
 
+        // Simulated board (no hardware; in-process or PTY)
+        if board.transport == "simulated" {
+            match simulated::SimulatedPeripheral::from_config(board).await {
+                Ok(mut peripheral) => {
+                    if peripheral.connect().await.is_err() {
+                        tracing::warn!(
+                            "Peripheral {} connect warning (continuing)",
+                            peripheral.name()
+                        );
+                    }
+                    transports.push((board.board.clone(), peripheral.transport()));
+                    memory_transports.push((board.board.clone(), peripheral.transport()));
+                    tools.extend(peripheral.tools());
+                    tracing::info!(board = %board.board, "Simulated peripheral started");
+                }
+                Err(e) => {
+                    tracing::warn!("Failed to start simulated board {}: {}", board.board, e);
+                }
+            }
+            continue;
+        }
+
         // Serial transport (STM32, ESP32, Arduino, etc.)
         if board.transport != "serial" {
             continue;
@@ -187,7 +219,7 @@ pub async fn create_peripheral_tools(config: &PeripheralsConfig) -> Result<Vec<B
                 if p.connect().await.is_err() {
                     tracing::warn!("Peripheral {} connect warning (continuing)", p.name());
                 }
-                serial_transports.push((board.board.clone(), p.transport()));
+                transports.push((board.board.clone(), p.transport()));
                 tools.extend(p.tools());
                 if board.board == "arduino-uno" {
                     if let Some(ref path) = board.path {
@@ -212,15 +244,16 @@ pub async fn create_peripheral_tools(config: &PeripheralsConfig) -> Result<Vec<B
         tools.push(Box::new(crate::tools::HardwareBoardInfoTool::new(
             board_names.clone(),
         )));
-        tools.push(Box::new(crate::tools::HardwareMemoryReadTool::new(
-            board_names,
-        )));
+        tools.push(Box::new(
+            crate::tools::HardwareMemoryReadTool::new(board_names)
+                .with_transports(memory_transports),
+        ));
     }
 
-    // Phase C: Add hardware_capabilities tool when any serial boards
-    if !serial_transports.is_empty() {
+    // Phase C: Add hardware_capabilities tool when any serial or simulated boards
+    if !transports.is_empty() {
         tools.push(Box::new(capabilities_tool::HardwareCapabilitiesTool::new(
-            serial_transports,
+            transports,
         )));
     }
 
@@ -247,6 +280,7 @@ mod tests {
                 transport: "serial".into(),
                 path: Some("/dev/ttyACM0".into()),
                 baud: 115_200,
+                simulation: None,
             }],
             datasheet_dir: None,
         };
@@ -267,12 +301,14 @@ mod tests {
                     transport: "serial".into(),
                     path: Some("/dev/ttyACM0".into()),
                     baud: 115_200,
+                    simulation: None,
                 },
                 PeripheralBoardConfig {
                     board: "rpi-gpio".into(),
                     transport: "native".into(),
                     path: None,
                     baud: 115_200,
+                    simulation: None,
                 },
             ],
             datasheet_dir: None,
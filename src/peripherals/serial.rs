@@ -4,7 +4,7 @@
 //! Request:  {"id":"1","cmd":"gpio_write","args":{"pin":13,"value":1}}
 //! Response: {"id":"1","ok":true,"result":"done"}
 
-use super::traits::Peripheral;
+use super::traits::{Peripheral, PeripheralTransport};
 use crate::config::PeripheralBoardConfig;
 use crate::tools::traits::{Tool, ToolResult};
 use async_trait::async_trait;
@@ -28,6 +28,7 @@ const ALLOWED_PATH_PREFIXES: &[&str] = &[
 
 fn is_path_allowed(path: &str) -> bool {
     ALLOWED_PATH_PREFIXES.iter().any(|p| path.starts_with(p))
+        || super::simulated::is_simulated_pty(path)
 }
 
 /// JSON request/response over serial.
@@ -69,9 +70,10 @@ pub(crate) struct SerialTransport {
 }
 
 /// Timeout for serial request/response (seconds).
-const SERIAL_TIMEOUT_SECS: u64 = 5;
+pub(crate) const SERIAL_TIMEOUT_SECS: u64 = 5;
 
-impl SerialTransport {
+#[async_trait]
+impl PeripheralTransport for SerialTransport {
     async fn request(&self, cmd: &str, args: Value) -> anyhow::Result<ToolResult> {
         let mut port = self.port.lock().await;
         let resp = tokio::time::timeout(
@@ -96,11 +98,6 @@ impl SerialTransport {
             error,
         })
     }
-
-    /// Phase C: fetch capabilities from device (gpio pins, led_pin).
-    pub async fn capabilities(&self) -> anyhow::Result<ToolResult> {
-        self.request("capabilities", json!({})).await
-    }
 }
 
 /// Serial peripheral for STM32, Arduino, etc. over USB CDC.
@@ -170,27 +167,30 @@ impl Peripheral for SerialPeripheral {
     }
 
     fn tools(&self) -> Vec<Box<dyn Tool>> {
-        vec![
-            Box::new(GpioReadTool {
-                transport: self.transport.clone(),
-            }),
-            Box::new(GpioWriteTool {
-                transport: self.transport.clone(),
-            }),
-        ]
+        gpio_tools(self.transport.clone())
     }
 }
 
 impl SerialPeripheral {
     /// Expose transport for capabilities tool (Phase C).
-    pub(crate) fn transport(&self) -> Arc<SerialTransport> {
+    pub(crate) fn transport(&self) -> Arc<dyn PeripheralTransport> {
         self.transport.clone()
     }
 }
 
+/// `gpio_read` and `gpio_write` tools backed by `transport`.
+pub(crate) fn gpio_tools(transport: Arc<dyn PeripheralTransport>) -> Vec<Box<dyn Tool>> {
+    vec![
+        Box::new(GpioReadTool {
+            transport: transport.clone(),
+        }),
+        Box::new(GpioWriteTool { transport }),
+    ]
+}
+
 /// Tool: read GPIO pin value.
 struct GpioReadTool {
-    transport: Arc<SerialTransport>,
+    transport: Arc<dyn PeripheralTransport>,
 }
 
 #[async_trait]
@@ -229,7 +229,7 @@ impl Tool for GpioReadTool {
 
 /// Tool: write GPIO pin value.
 struct GpioWriteTool {
-    transport: Arc<SerialTransport>,
+    transport: Arc<dyn PeripheralTransport>,
 }
 
 #[async_trait]
@@ -0,0 +1,786 @@
+//! Simulated peripheral board — hardware-free development and CI.
+//!
+//! Answers the same newline-delimited JSON protocol as serial firmware
+//! (`ping`, `capabilities`, `gpio_read`, `gpio_write`, `memory_read`), either
+//! in-process or on a pseudo-terminal that the regular serial transport opens
+//! like a USB CDC device. Pin state is scriptable (initial levels, timed
+//! changes, [`SimulatedBoard::set_pin`]) and faults can be injected (failed
+//! or dropped commands, latency, stuck pins).
+//!
+//! Every level change is published as a [`PinEvent`] whose
+//! [`signal`](PinEvent::signal) matches the `{board}/{signal}` topics used by
+//! peripheral-triggered SOPs; [`spawn_sop_bridge`] dispatches them.
+
+use super::serial::{gpio_tools, SERIAL_TIMEOUT_SECS};
+use super::traits::{Peripheral, PeripheralTransport};
+use crate::config::{
+    PeripheralBoardConfig, SimulatedBoardConfig, SimulatedFaultConfig, SimulatedPinStep,
+};
+use crate::sop::dispatch::{dispatch_peripheral_signal, process_headless_results};
+use crate::sop::{SopAuditLogger, SopEngine};
+use crate::tools::hardware_memory_read::format_hex_dump;
+use crate::tools::traits::{Tool, ToolResult};
+use anyhow::Context;
+use async_trait::async_trait;
+use serde_json::{json, Value};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::sync::{Arc, LazyLock, Mutex};
+use std::time::Duration;
+use tokio::sync::broadcast;
+use tokio::task::JoinHandle;
+
+/// Pins every simulated board reports, in addition to configured ones.
+const DEFAULT_GPIO_PINS: std::ops::RangeInclusive<u32> = 0..=31;
+
+/// Pin wired to the simulated user LED.
+const LED_PIN: u32 = 13;
+
+/// Largest `memory_read` the simulator serves, matching `hardware_memory_read`.
+const MAX_MEMORY_READ: u64 = 256;
+
+/// PTY paths served by simulated boards in this process. The serial
+/// transport accepts these in addition to its `/dev/tty*` allowlist.
+static SIMULATED_PTYS: LazyLock<Mutex<HashSet<String>>> =
+    LazyLock::new(|| Mutex::new(HashSet::new()));
+
+/// Running simulated boards by name, so tests and other subsystems can drive
+/// pin state or subscribe to pin events.
+static BOARDS: LazyLock<Mutex<HashMap<String, Arc<SimulatedBoard>>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+
+/// True when `path` is a PTY created by a simulated board in this process.
+pub fn is_simulated_pty(path: &str) -> bool {
+    SIMULATED_PTYS
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .contains(path)
+}
+
+/// Look up a running simulated board by its configured name.
+pub fn find_board(name: &str) -> Option<Arc<SimulatedBoard>> {
+    BOARDS
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .get(name)
+        .cloned()
+}
+
+/// A simulated pin changed level.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PinEvent {
+    pub board: String,
+    pub pin: u32,
+    pub value: u8,
+}
+
+impl PinEvent {
+    /// Signal name for peripheral SOP triggers (`pin_<n>`).
+    pub fn signal(&self) -> String {
+        format!("pin_{}", self.pin)
+    }
+}
+
+/// In-memory board state shared by every transport that serves it.
+pub struct SimulatedBoard {
+    name: String,
+    pins: Mutex<BTreeMap<u32, u8>>,
+    faults: Mutex<SimulatedFaultConfig>,
+    memory: Vec<(u64, Vec<u8>)>,
+    script: Vec<SimulatedPinStep>,
+    events: broadcast::Sender<PinEvent>,
+}
+
+impl SimulatedBoard {
+    /// Build a board from its `[peripherals.boards.simulation]` settings.
+    pub fn from_config(name: &str, config: &SimulatedBoardConfig) -> anyhow::Result<Self> {
+        let mut pins: BTreeMap<u32, u8> = DEFAULT_GPIO_PINS.map(|pin| (pin, 0)).collect();
+        for pin in &config.pins {
+            pins.insert(pin.pin, pin.value.min(1));
+        }
+        for step in &config.script {
+            pins.entry(step.pin).or_insert(0);
+        }
+
+        let memory = config
+            .memory
+            .iter()
+            .map(|block| {
+                parse_hex_bytes(&block.hex)
+                    .map(|bytes| (block.address, bytes))
+                    .with_context(|| format!("invalid simulated memory at 0x{:08X}", block.address))
+            })
+            .collect::<anyhow::Result<Vec<_>>>()?;
+
+        let mut script = config.script.clone();
+        script.sort_by_key(|step| step.after_ms);
+
+        Ok(Self {
+            name: name.to_string(),
+            pins: Mutex::new(pins),
+            faults: Mutex::new(config.faults.clone()),
+            memory,
+            script,
+            events: broadcast::channel(64).0,
+        })
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Receive every subsequent pin level change.
+    pub fn subscribe(&self) -> broadcast::Receiver<PinEvent> {
+        self.events.subscribe()
+    }
+
+    /// Current level of `pin` (unknown pins read low).
+    pub fn pin(&self, pin: u32) -> u8 {
+        self.pins
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .get(&pin)
+            .copied()
+            .unwrap_or(0)
+    }
+
+    /// Drive `pin` from the outside (a button press, a sensor edge).
+    pub fn set_pin(&self, pin: u32, value: u8) {
+        let value = value.min(1);
+        let previous = self
+            .pins
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .insert(pin, value);
+        if previous != Some(value) {
+            let _ = self.events.send(PinEvent {
+                board: self.name.clone(),
+                pin,
+                value,
+            });
+        }
+    }
+
+    /// Replace the active fault configuration.
+    pub fn inject_faults(&self, faults: SimulatedFaultConfig) {
+        *self.faults.lock().unwrap_or_else(|e| e.into_inner()) = faults;
+    }
+
+    fn latency(&self) -> Duration {
+        Duration::from_millis(
+            self.faults
+                .lock()
+                .unwrap_or_else(|e| e.into_inner())
+                .latency_ms,
+        )
+    }
+
+    /// Execute one command. `None` means the board never answers.
+    pub fn handle(&self, cmd: &str, args: &Value) -> Option<Result<String, String>> {
+        {
+            let faults = self.faults.lock().unwrap_or_else(|e| e.into_inner());
+            if faults.drop_commands.iter().any(|c| c == cmd) {
+                return None;
+            }
+            if faults.fail_commands.iter().any(|c| c == cmd) {
+                return Some(Err(format!("Simulated fault: {cmd} failed")));
+            }
+        }
+
+        let pin_arg = || {
+            args.get("pin")
+                .and_then(Value::as_u64)
+                .and_then(|pin| u32::try_from(pin).ok())
+                .ok_or_else(|| "Missing 'pin' parameter".to_string())
+        };
+
+        Some(match cmd {
+            "ping" => Ok("pong".into()),
+            "capabilities" => {
+                let gpio: Vec<u32> = self
+                    .pins
+                    .lock()
+                    .unwrap_or_else(|e| e.into_inner())
+                    .keys()
+                    .copied()
+                    .collect();
+                Ok(json!({ "gpio": gpio, "led_pin": LED_PIN, "simulated": true }).to_string())
+            }
+            "gpio_read" => pin_arg().and_then(|pin| {
+                self.pins
+                    .lock()
+                    .unwrap_or_else(|e| e.into_inner())
+                    .get(&pin)
+                    .map(u8::to_string)
+                    .ok_or_else(|| format!("Invalid pin: {pin}"))
+            }),
+            "gpio_write" => pin_arg().and_then(|pin| {
+                let value = match args.get("value").and_then(Value::as_u64) {
+                    Some(0) => 0,
+                    Some(1) => 1,
+                    _ => return Err("'value' must be 0 or 1".into()),
+                };
+                if !self
+                    .pins
+                    .lock()
+                    .unwrap_or_else(|e| e.into_inner())
+                    .contains_key(&pin)
+                {
+                    return Err(format!("Invalid pin: {pin}"));
+                }
+                let stuck = self
+                    .faults
+                    .lock()
+                    .unwrap_or_else(|e| e.into_inner())
+                    .stuck_pins
+                    .contains(&pin);
+                if !stuck {
+                    self.set_pin(pin, value);
+                }
+                Ok("done".into())
+            }),
+            "memory_read" => {
+                let address = args.get("address").and_then(Value::as_u64).unwrap_or(0);
+                let length = args
+                    .get("length")
+                    .and_then(Value::as_u64)
+                    .unwrap_or(128)
+                    .clamp(1, MAX_MEMORY_READ);
+                let bytes: Vec<u8> = (0..length)
+                    .map(|offset| self.read_byte(address.saturating_add(offset)))
+                    .collect();
+                Ok(format_hex_dump(address, &bytes))
+            }
+            other => Err(format!("Unknown command: {other}")),
+        })
+    }
+
+    fn read_byte(&self, address: u64) -> u8 {
+        self.memory
+            .iter()
+            .find_map(|(start, bytes)| {
+                let offset = usize::try_from(address.checked_sub(*start)?).ok()?;
+                bytes.get(offset).copied()
+            })
+            .unwrap_or(0)
+    }
+
+    /// Answer one protocol line. `None` when the command is dropped.
+    pub fn handle_line(&self, line: &str) -> Option<String> {
+        let request: Value = match serde_json::from_str(line.trim()) {
+            Ok(request) => request,
+            Err(e) => {
+                return Some(
+                    json!({ "id": "", "ok": false, "result": "", "error": e.to_string() })
+                        .to_string(),
+                )
+            }
+        };
+        let id = request["id"].as_str().unwrap_or_default();
+        let cmd = request["cmd"].as_str().unwrap_or_default();
+        let response = match self.handle(cmd, &request["args"])? {
+            Ok(result) => json!({ "id": id, "ok": true, "result": result }),
+            Err(error) => json!({ "id": id, "ok": false, "result": "", "error": error }),
+        };
+        Some(response.to_string())
+    }
+
+    /// Apply the configured script, relative to now.
+    fn start_script(self: &Arc<Self>) -> Option<JoinHandle<()>> {
+        if self.script.is_empty() {
+            return None;
+        }
+        let board = Arc::clone(self);
+        Some(tokio::spawn(async move {
+            let start = tokio::time::Instant::now();
+            for step in &board.script {
+                tokio::time::sleep_until(start + Duration::from_millis(step.after_ms)).await;
+                board.set_pin(step.pin, step.value);
+            }
+        }))
+    }
+}
+
+/// Dispatch every pin change on `board` to peripheral-triggered SOPs.
+///
+/// Each [`PinEvent`] becomes the `{board}/pin_<n>` signal with the new level
+/// as payload. Started runs are handled like MQTT-triggered ones.
+pub fn spawn_sop_bridge(
+    board: &SimulatedBoard,
+    engine: Arc<Mutex<SopEngine>>,
+    audit: Arc<SopAuditLogger>,
+) -> JoinHandle<()> {
+    let mut events = board.subscribe();
+    tokio::spawn(async move {
+        loop {
+            match events.recv().await {
+                Ok(event) => {
+                    let payload = event.value.to_string();
+                    let results = dispatch_peripheral_signal(
+                        &engine,
+                        &audit,
+                        &event.board,
+                        &event.signal(),
+                        Some(&payload),
+                    )
+                    .await;
+                    process_headless_results(&results);
+                }
+                Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                    tracing::warn!("Simulated board SOP bridge dropped {skipped} pin events");
+                }
+                Err(broadcast::error::RecvError::Closed) => break,
+            }
+        }
+    })
+}
+
+fn parse_hex_bytes(raw: &str) -> Option<Vec<u8>> {
+    let digits: String = raw.split_whitespace().collect();
+    if digits.is_empty() || !digits.len().is_multiple_of(2) {
+        return None;
+    }
+    (0..digits.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(digits.get(i..i + 2)?, 16).ok())
+        .collect()
+}
+
+/// In-process transport: requests go straight to the board.
+struct InProcessTransport {
+    board: Arc<SimulatedBoard>,
+    /// How long a dropped command waits before failing, like a silent serial port.
+    timeout: Duration,
+}
+
+#[async_trait]
+impl PeripheralTransport for InProcessTransport {
+    async fn request(&self, cmd: &str, args: Value) -> anyhow::Result<ToolResult> {
+        tokio::time::sleep(self.board.latency()).await;
+        let Some(reply) = self.board.handle(cmd, &args) else {
+            tokio::time::sleep(self.timeout).await;
+            anyhow::bail!("Serial request timed out after {}s", self.timeout.as_secs());
+        };
+        Ok(match reply {
+            Ok(output) => ToolResult {
+                success: true,
+                output,
+                error: None,
+            },
+            Err(error) => ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some(error),
+            },
+        })
+    }
+}
+
+/// Serve `board` on a new PTY and return the path of its device end.
+#[cfg(unix)]
+fn serve_pty(board: Arc<SimulatedBoard>) -> anyhow::Result<String> {
+    use portable_pty::{native_pty_system, PtySize};
+    use std::io::{BufRead, BufReader, Write};
+
+    let pair = native_pty_system()
+        .openpty(PtySize::default())
+        .context("failed to open PTY for simulated board")?;
+    let path = pair
+        .master
+        .tty_name()
+        .context("simulated board PTY has no device path")?
+        .to_string_lossy()
+        .into_owned();
+    let reader = pair.master.try_clone_reader()?;
+    let mut writer = pair.master.take_writer()?;
+
+    SIMULATED_PTYS
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .insert(path.clone());
+
+    std::thread::Builder::new()
+        .name(format!("sim-{}", board.name()))
+        .spawn(move || {
+            // Keep the device end open so reads do not hit EOF before the
+            // serial transport connects.
+            let _pair = pair;
+            let mut reader = BufReader::new(reader);
+            let mut line = String::new();
+            loop {
+                line.clear();
+                match reader.read_line(&mut line) {
+                    Ok(0) | Err(_) => break,
+                    Ok(_) => {}
+                }
+                if line.trim().is_empty() {
+                    continue;
+                }
+                std::thread::sleep(board.latency());
+                if let Some(reply) = board.handle_line(&line) {
+                    if writeln!(writer, "{reply}").is_err() || writer.flush().is_err() {
+                        break;
+                    }
+                }
+            }
+        })?;
+
+    Ok(path)
+}
+
+#[cfg(not(unix))]
+fn serve_pty(_board: Arc<SimulatedBoard>) -> anyhow::Result<String> {
+    anyhow::bail!("PTY simulation is only supported on Unix")
+}
+
+/// Simulated board exposed as a [`Peripheral`].
+pub struct SimulatedPeripheral {
+    name: String,
+    board_type: String,
+    board: Arc<SimulatedBoard>,
+    transport: Arc<dyn PeripheralTransport>,
+    script: Option<JoinHandle<()>>,
+}
+
+impl SimulatedPeripheral {
+    /// Create the board and its transport (`simulation.pty` selects a PTY
+    /// served through the serial transport, otherwise in-process).
+    pub async fn from_config(config: &PeripheralBoardConfig) -> anyhow::Result<Self> {
+        let simulation = config.simulation.clone().unwrap_or_default();
+        let board = Arc::new(SimulatedBoard::from_config(&config.board, &simulation)?);
+
+        let (name, transport): (String, Arc<dyn PeripheralTransport>) = if simulation.pty {
+            let path = serve_pty(Arc::clone(&board))?;
+            let serial_config = PeripheralBoardConfig {
+                transport: "serial".into(),
+                path: Some(path.clone()),
+                simulation: None,
+                ..config.clone()
+            };
+            let serial = super::serial::SerialPeripheral::connect(&serial_config).await?;
+            (
+                format!("{}-{}", config.board, path.replace('/', "_")),
+                serial.transport(),
+            )
+        } else {
+            (
+                format!("{}-sim", config.board),
+                Arc::new(InProcessTransport {
+                    board: Arc::clone(&board),
+                    timeout: Duration::from_secs(SERIAL_TIMEOUT_SECS),
+                }),
+            )
+        };
+
+        BOARDS
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .insert(config.board.clone(), Arc::clone(&board));
+
+        Ok(Self {
+            name,
+            board_type: config.board.clone(),
+            board,
+            transport,
+            script: None,
+        })
+    }
+
+    /// Board state, for driving pins and subscribing to pin events.
+    pub fn board(&self) -> Arc<SimulatedBoard> {
+        Arc::clone(&self.board)
+    }
+
+    /// Transport for the capabilities and memory-read tools.
+    pub(crate) fn transport(&self) -> Arc<dyn PeripheralTransport> {
+        Arc::clone(&self.transport)
+    }
+}
+
+#[async_trait]
+impl Peripheral for SimulatedPeripheral {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn board_type(&self) -> &str {
+        &self.board_type
+    }
+
+    async fn connect(&mut self) -> anyhow::Result<()> {
+        if self.script.is_none() {
+            self.script = self.board.start_script();
+        }
+        Ok(())
+    }
+
+    async fn disconnect(&mut self) -> anyhow::Result<()> {
+        if let Some(script) = self.script.take() {
+            script.abort();
+        }
+        Ok(())
+    }
+
+    async fn health_check(&self) -> bool {
+        self.transport
+            .request("ping", json!({}))
+            .await
+            .is_ok_and(|r| r.success)
+    }
+
+    fn tools(&self) -> Vec<Box<dyn Tool>> {
+        gpio_tools(Arc::clone(&self.transport))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{SimulatedMemoryConfig, SimulatedPinConfig};
+
+    fn board_config(simulation: SimulatedBoardConfig) -> PeripheralBoardConfig {
+        PeripheralBoardConfig {
+            board: "simulated".into(),
+            transport: "simulated".into(),
+            path: None,
+            baud: 115_200,
+            simulation: Some(simulation),
+        }
+    }
+
+    fn find_tool<'a>(tools: &'a [Box<dyn Tool>], name: &str) -> &'a dyn Tool {
+        tools.iter().find(|t| t.name() == name).unwrap().as_ref()
+    }
+
+    #[tokio::test]
+    async fn gpio_tools_read_and_write_simulated_pins() {
+        let peripheral = SimulatedPeripheral::from_config(&board_config(SimulatedBoardConfig {
+            pins: vec![SimulatedPinConfig { pin: 7, value: 1 }],
+            ..SimulatedBoardConfig::default()
+        }))
+        .await
+        .unwrap();
+        let tools = peripheral.tools();
+
+        let read = find_tool(&tools, "gpio_read");
+        assert_eq!(read.execute(json!({ "pin": 7 })).await.unwrap().output, "1");
+
+        let write = find_tool(&tools, "gpio_write");
+        let result = write
+            .execute(json!({ "pin": 7, "value": 0 }))
+            .await
+            .unwrap();
+        assert!(result.success);
+        assert_eq!(peripheral.board().pin(7), 0);
+
+        let invalid = read.execute(json!({ "pin": 99 })).await.unwrap();
+        assert!(!invalid.success);
+        assert_eq!(invalid.error.as_deref(), Some("Invalid pin: 99"));
+        assert!(peripheral.health_check().await);
+    }
+
+    #[test]
+    fn capabilities_and_memory_read_follow_firmware_protocol() {
+        let board = SimulatedBoard::from_config(
+            "simulated",
+            &SimulatedBoardConfig {
+                memory: vec![SimulatedMemoryConfig {
+                    address: 0x2000_0000,
+                    hex: "DE AD BE EF".into(),
+                }],
+                ..SimulatedBoardConfig::default()
+            },
+        )
+        .unwrap();
+
+        let caps: Value =
+            serde_json::from_str(&board.handle("capabilities", &json!({})).unwrap().unwrap())
+                .unwrap();
+        assert_eq!(caps["led_pin"], 13);
+        assert_eq!(caps["gpio"].as_array().unwrap().len(), 32);
+
+        let dump = board
+            .handle(
+                "memory_read",
+                &json!({ "address": 0x2000_0000_u64, "length": 6 }),
+            )
+            .unwrap()
+            .unwrap();
+        assert!(dump.contains("0x20000000  DE AD BE EF 00 00"), "{dump}");
+
+        let reply: Value = serde_json::from_str(
+            &board
+                .handle_line(r#"{"id":"7","cmd":"gpio_read","args":{"pin":13}}"#)
+                .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(reply, json!({ "id": "7", "ok": true, "result": "0" }));
+    }
+
+    #[tokio::test]
+    async fn injected_faults_fail_drop_and_stick() {
+        let board = Arc::new(
+            SimulatedBoard::from_config("simulated", &SimulatedBoardConfig::default()).unwrap(),
+        );
+        board.inject_faults(SimulatedFaultConfig {
+            fail_commands: vec!["capabilities".into()],
+            drop_commands: vec!["ping".into()],
+            stuck_pins: vec![4],
+            ..SimulatedFaultConfig::default()
+        });
+
+        assert!(board.handle("capabilities", &json!({})).unwrap().is_err());
+        assert!(board.handle("ping", &json!({})).is_none());
+        assert!(board.handle_line(r#"{"id":"1","cmd":"ping"}"#).is_none());
+
+        board
+            .handle("gpio_write", &json!({ "pin": 4, "value": 1 }))
+            .unwrap()
+            .unwrap();
+        assert_eq!(board.pin(4), 0);
+
+        let transport = InProcessTransport {
+            board: Arc::clone(&board),
+            timeout: Duration::from_millis(10),
+        };
+        let err = transport.request("ping", json!({})).await.unwrap_err();
+        assert!(err.to_string().contains("timed out"), "{err}");
+    }
+
+    #[tokio::test]
+    async fn script_changes_pins_and_emits_signals() {
+        let mut peripheral =
+            SimulatedPeripheral::from_config(&board_config(SimulatedBoardConfig {
+                script: vec![
+                    SimulatedPinStep {
+                        after_ms: 50,
+                        pin: 5,
+                        value: 1,
+                    },
+                    SimulatedPinStep {
+                        after_ms: 10,
+                        pin: 40,
+                        value: 1,
+                    },
+                ],
+                ..SimulatedBoardConfig::default()
+            }))
+            .await
+            .unwrap();
+        let mut events = peripheral.board().subscribe();
+        peripheral.connect().await.unwrap();
+
+        let first = events.recv().await.unwrap();
+        assert_eq!((first.pin, first.signal().as_str()), (40, "pin_40"));
+        let second = events.recv().await.unwrap();
+        assert_eq!(
+            second,
+            PinEvent {
+                board: "simulated".into(),
+                pin: 5,
+                value: 1,
+            }
+        );
+        assert!(find_board("simulated").is_some());
+        peripheral.disconnect().await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn pin_change_starts_peripheral_sop_run() {
+        use crate::config::{MemoryConfig, SopConfig};
+        use crate::memory::traits::Memory;
+        use crate::sop::types::{
+            Sop, SopExecutionMode, SopPriority, SopRunStatus, SopStep, SopTrigger,
+        };
+
+        let mut engine = SopEngine::new(SopConfig::default());
+        engine.set_sops_for_test(vec![Sop {
+            name: "door-open".into(),
+            description: "Check the door sensor".into(),
+            version: "1.0.0".into(),
+            priority: SopPriority::High,
+            execution_mode: SopExecutionMode::Supervised,
+            triggers: vec![SopTrigger::Peripheral {
+                board: "door-board".into(),
+                signal: "pin_5".into(),
+                condition: None,
+            }],
+            steps: vec![SopStep {
+                number: 1,
+                title: "Inspect".into(),
+                body: "Inspect the door".into(),
+                suggested_tools: vec![],
+                requires_confirmation: false,
+            }],
+            cooldown_secs: 0,
+            max_concurrent: 1,
+            generation: Default::default(),
+            location: None,
+        }]);
+        let engine = Arc::new(Mutex::new(engine));
+
+        let tmp = tempfile::tempdir().unwrap();
+        let memory: Arc<dyn Memory> = Arc::from(
+            crate::memory::create_memory(
+                &MemoryConfig {
+                    backend: "sqlite".into(),
+                    ..MemoryConfig::default()
+                },
+                tmp.path(),
+                None,
+            )
+            .unwrap(),
+        );
+        let audit = Arc::new(SopAuditLogger::new(memory));
+
+        let board =
+            SimulatedBoard::from_config("door-board", &SimulatedBoardConfig::default()).unwrap();
+        let bridge = spawn_sop_bridge(&board, Arc::clone(&engine), audit);
+        board.set_pin(4, 1);
+        board.set_pin(5, 1);
+
+        let run = tokio::time::timeout(Duration::from_secs(5), async {
+            loop {
+                let started = engine
+                    .lock()
+                    .unwrap()
+                    .active_runs()
+                    .values()
+                    .next()
+                    .cloned();
+                if let Some(run) = started {
+                    break run;
+                }
+                tokio::time::sleep(Duration::from_millis(10)).await;
+            }
+        })
+        .await
+        .expect("pin change should start an SOP run");
+        bridge.abort();
+
+        assert_eq!(run.sop_name, "door-open");
+        assert_eq!(run.status, SopRunStatus::WaitingApproval);
+        assert_eq!(run.trigger_event.topic.as_deref(), Some("door-board/pin_5"));
+        assert_eq!(run.trigger_event.payload.as_deref(), Some("1"));
+        assert_eq!(engine.lock().unwrap().active_runs().len(), 1);
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn pty_board_is_reachable_through_serial_transport() {
+        let peripheral = SimulatedPeripheral::from_config(&board_config(SimulatedBoardConfig {
+            pty: true,
+            pins: vec![SimulatedPinConfig { pin: 2, value: 1 }],
+            ..SimulatedBoardConfig::default()
+        }))
+        .await
+        .unwrap();
+
+        let result = peripheral
+            .transport()
+            .request("gpio_read", json!({ "pin": 2 }))
+            .await
+            .unwrap();
+        assert!(result.success, "{result:?}");
+        assert_eq!(result.output, "1");
+    }
+}
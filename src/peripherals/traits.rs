@@ -6,8 +6,26 @@
 //! and firmware integration guide.
 
 use async_trait::async_trait;
+use serde_json::Value;
 
-use crate::tools::Tool;
+use crate::tools::{Tool, ToolResult};
+
+/// Request/response channel to board firmware.
+///
+/// Requests follow the newline-delimited JSON protocol described in
+/// `peripherals::serial` (`{"id","cmd","args"}` → `{"id","ok","result"}`).
+/// Serial ports and simulated boards implement this so the same GPIO,
+/// capabilities and memory-read tools work over either.
+#[async_trait]
+pub trait PeripheralTransport: Send + Sync {
+    /// Send `cmd` with `args` and map the firmware reply to a tool result.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the request could not be delivered or the reply
+    /// did not arrive in time.
+    async fn request(&self, cmd: &str, args: Value) -> anyhow::Result<ToolResult>;
+}
 
 /// A hardware peripheral that exposes capabilities as agent tools.
 ///
@@ -1,11 +1,19 @@
+use crate::providers::batch::{
+    parse_jsonl, BatchHandle, BatchProvider, BatchRequest, BatchResult, BatchStatus,
+};
+use crate::providers::streaming::{self, sse_data, StreamParser};
 use crate::providers::traits::{
     ChatMessage, ChatRequest as ProviderChatRequest, ChatResponse as ProviderChatResponse,
-    Provider, ProviderCapabilities, TokenUsage, ToolCall as ProviderToolCall,
+    GenerationParams, Provider, ProviderCapabilities, ResponseFormat, StreamError, StreamEvent,
+    StreamResult, TokenUsage, ToolCall as ProviderToolCall, COMPACTION_SUMMARY_PREFIX,
+    VOLATILE_PROMPT_HEADING,
 };
 use crate::tools::ToolSpec;
 use async_trait::async_trait;
+use futures_util::stream;
 use reqwest::Client;
 use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
 
 pub struct AnthropicProvider {
     credential: Option<String>,
@@ -51,8 +59,65 @@ struct NativeChatRequest<'a> {
     temperature: f64,
     #[serde(skip_serializing_if = "Option::is_none")]
     tools: Option<Vec<NativeToolSpec<'a>>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    tool_choice: Option<serde_json::Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    stream: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    top_p: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    stop_sequences: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    thinking: Option<serde_json::Value>,
+}
+
+impl NativeChatRequest<'_> {
+    /// Map generation parameters onto the request. Seed and penalties have
+    /// no Anthropic equivalent and are ignored.
+    ///
+    /// Extended thinking is only enabled for requests without tools: turns
+    /// that follow a tool call must echo the signed thinking blocks, which
+    /// conversation history does not keep.
+    fn apply_generation(&mut self, params: Option<&GenerationParams>) {
+        let Some(params) = params else {
+            return;
+        };
+        if let Some(max_tokens) = params.max_tokens {
+            self.max_tokens = max_tokens;
+        }
+        self.top_p = params.top_p;
+        if !params.stop.is_empty() {
+            self.stop_sequences = Some(params.stop.clone());
+        }
+
+        let Some(budget) = params.thinking_budget_tokens() else {
+            return;
+        };
+        if self.tools.is_some() {
+            tracing::debug!("Anthropic extended thinking skipped for a request with tools");
+            return;
+        }
+        let budget = budget.max(MIN_THINKING_BUDGET);
+        self.thinking = Some(serde_json::json!({"type": "enabled", "budget_tokens": budget}));
+        // The budget counts toward max_tokens, and thinking requires the
+        // default temperature.
+        if self.max_tokens <= budget {
+            self.max_tokens = budget + DEFAULT_MAX_TOKENS;
+        }
+        self.temperature = 1.0;
+    }
 }
 
+/// Output token limit when the caller sets none (the API requires one).
+const DEFAULT_MAX_TOKENS: u32 = 4096;
+
+/// Smallest thinking budget the API accepts.
+const MIN_THINKING_BUDGET: u32 = 1024;
+
+/// Description of the tool that carries a requested response format.
+const STRUCTURED_OUTPUT_TOOL_DESCRIPTION: &str =
+    "Return the final answer. The input must satisfy the schema exactly.";
+
 #[derive(Debug, Serialize)]
 struct NativeMessage {
     role: String,
@@ -148,6 +213,21 @@ struct AnthropicUsage {
     input_tokens: Option<u64>,
     #[serde(default)]
     output_tokens: Option<u64>,
+    #[serde(default)]
+    cache_read_input_tokens: Option<u64>,
+    #[serde(default)]
+    cache_creation_input_tokens: Option<u64>,
+}
+
+impl From<AnthropicUsage> for TokenUsage {
+    fn from(usage: AnthropicUsage) -> Self {
+        Self {
+            input_tokens: usage.input_tokens,
+            output_tokens: usage.output_tokens,
+            cache_read_tokens: usage.cache_read_input_tokens,
+            cache_write_tokens: usage.cache_creation_input_tokens,
+        }
+    }
 }
 
 #[derive(Debug, Deserialize)]
@@ -164,6 +244,138 @@ struct NativeContentIn {
     input: Option<serde_json::Value>,
 }
 
+/// Server-sent event from the streaming Messages API.
+#[derive(Debug, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum NativeStreamEvent {
+    MessageStart {
+        message: NativeStreamMessage,
+    },
+    ContentBlockStart {
+        index: usize,
+        content_block: NativeContentIn,
+    },
+    ContentBlockDelta {
+        index: usize,
+        delta: NativeBlockDelta,
+    },
+    ContentBlockStop {
+        index: usize,
+    },
+    MessageDelta {
+        #[serde(default)]
+        usage: Option<AnthropicUsage>,
+    },
+    Error {
+        error: NativeStreamError,
+    },
+    #[serde(other)]
+    Other,
+}
+
+#[derive(Debug, Deserialize)]
+struct NativeStreamMessage {
+    #[serde(default)]
+    usage: Option<AnthropicUsage>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum NativeBlockDelta {
+    TextDelta {
+        text: String,
+    },
+    InputJsonDelta {
+        partial_json: String,
+    },
+    ThinkingDelta {
+        thinking: String,
+    },
+    #[serde(other)]
+    Other,
+}
+
+#[derive(Debug, Deserialize)]
+struct NativeStreamError {
+    message: String,
+}
+
+/// Translates Messages API stream events into typed stream events.
+///
+/// Tool calls are indexed by their content block index.
+#[derive(Debug, Default)]
+struct NativeStreamParser {
+    tool_blocks: HashSet<usize>,
+}
+
+impl StreamParser for NativeStreamParser {
+    fn parse_line(&mut self, line: &str) -> StreamResult<Vec<StreamEvent>> {
+        let Some(data) = sse_data(line.trim()) else {
+            return Ok(Vec::new());
+        };
+        let event: NativeStreamEvent = serde_json::from_str(data).map_err(StreamError::Json)?;
+
+        let usage_event = |usage: AnthropicUsage| StreamEvent::Usage(usage.into());
+        let events = match event {
+            NativeStreamEvent::MessageStart { message } => {
+                message.usage.map(usage_event).into_iter().collect()
+            }
+            NativeStreamEvent::ContentBlockStart {
+                index,
+                content_block,
+            } if content_block.kind == "tool_use" => {
+                self.tool_blocks.insert(index);
+                vec![StreamEvent::ToolCallStart {
+                    index,
+                    id: content_block
+                        .id
+                        .unwrap_or_else(|| uuid::Uuid::new_v4().to_string()),
+                    name: content_block.name.unwrap_or_default(),
+                }]
+            }
+            NativeStreamEvent::ContentBlockDelta { index, delta } => match delta {
+                NativeBlockDelta::TextDelta { text } if !text.is_empty() => {
+                    vec![StreamEvent::TextDelta(text)]
+                }
+                NativeBlockDelta::InputJsonDelta { partial_json }
+                    if !partial_json.is_empty() && self.tool_blocks.contains(&index) =>
+                {
+                    vec![StreamEvent::ToolCallDelta {
+                        index,
+                        arguments: partial_json,
+                    }]
+                }
+                NativeBlockDelta::ThinkingDelta { thinking } if !thinking.is_empty() => {
+                    vec![StreamEvent::ReasoningDelta(thinking)]
+                }
+                _ => Vec::new(),
+            },
+            NativeStreamEvent::ContentBlockStop { index } if self.tool_blocks.remove(&index) => {
+                vec![StreamEvent::ToolCallEnd { index }]
+            }
+            NativeStreamEvent::MessageDelta { usage } => {
+                usage.map(usage_event).into_iter().collect()
+            }
+            NativeStreamEvent::Error { error } => {
+                return Err(StreamError::Provider(format!(
+                    "Anthropic stream error: {}",
+                    super::sanitize_api_error(&error.message)
+                )));
+            }
+            _ => Vec::new(),
+        };
+        Ok(events)
+    }
+
+    fn finish(&mut self) -> Vec<StreamEvent> {
+        let mut open: Vec<usize> = self.tool_blocks.drain().collect();
+        open.sort_unstable();
+        open.into_iter()
+            .map(|index| StreamEvent::ToolCallEnd { index })
+            .collect()
+    }
+}
+
 impl AnthropicProvider {
     pub fn new(credential: Option<&str>) -> Self {
         Self::with_base_url(credential, None)
@@ -226,6 +438,35 @@ impl AnthropicProvider {
         }
     }
 
+    /// Split a system prompt into its stable prefix and the per-turn section
+    /// starting at [`VOLATILE_PROMPT_HEADING`].
+    fn split_volatile_system(text: &str) -> (&str, &str) {
+        text.rfind(VOLATILE_PROMPT_HEADING)
+            .map_or((text, ""), |index| text.split_at(index))
+    }
+
+    /// Cache history up to the compaction summary, which only changes when
+    /// history is compacted again. The last message is left to
+    /// `apply_cache_to_last_message`.
+    fn apply_cache_to_compaction_summary(messages: &mut [NativeMessage]) {
+        let Some((_, earlier)) = messages.split_last_mut() else {
+            return;
+        };
+        let summary = earlier.iter_mut().rev().find(|msg| {
+            msg.role == "assistant"
+                && matches!(
+                    msg.content.first(),
+                    Some(NativeContentOut::Text { text, .. })
+                        if text.starts_with(COMPACTION_SUMMARY_PREFIX)
+                )
+        });
+        if let Some(NativeContentOut::Text { cache_control, .. }) =
+            summary.and_then(|msg| msg.content.last_mut())
+        {
+            *cache_control = Some(CacheControl::ephemeral());
+        }
+    }
+
     fn convert_tools<'a>(tools: Option<&'a [ToolSpec]>) -> Option<Vec<NativeToolSpec<'a>>> {
         let items = tools?;
         if items.is_empty() {
@@ -249,6 +490,46 @@ impl AnthropicProvider {
         Some(native_tools)
     }
 
+    /// Input schema for the forced structured-output tool.
+    ///
+    /// Anthropic has no response-format parameter, so the requested schema
+    /// becomes a tool the model is required to call. Tool inputs must be
+    /// objects; other schemas are wrapped in a `value` property (second
+    /// element `true`).
+    fn structured_output_schema(format: &ResponseFormat) -> (serde_json::Value, bool) {
+        if format
+            .schema
+            .get("type")
+            .and_then(serde_json::Value::as_str)
+            == Some("object")
+        {
+            return (format.schema.clone(), false);
+        }
+        let wrapped = serde_json::json!({
+            "type": "object",
+            "properties": { "value": format.schema },
+            "required": ["value"],
+        });
+        (wrapped, true)
+    }
+
+    /// Move the structured-output tool call into the response text.
+    fn take_structured_output(response: &mut ProviderChatResponse, name: &str, wrapped: bool) {
+        let Some(index) = response.tool_calls.iter().position(|tc| tc.name == name) else {
+            return;
+        };
+        let call = response.tool_calls.remove(index);
+        let text = if wrapped {
+            serde_json::from_str::<serde_json::Value>(&call.arguments)
+                .ok()
+                .and_then(|mut input| input.get_mut("value").map(serde_json::Value::take))
+                .map_or(call.arguments, |value| value.to_string())
+        } else {
+            call.arguments
+        };
+        response.text = Some(text);
+    }
+
     fn parse_assistant_tool_call_message(content: &str) -> Option<Vec<NativeContentOut>> {
         let value = serde_json::from_str::<serde_json::Value>(content).ok()?;
         let tool_calls = value
@@ -388,14 +669,25 @@ impl AnthropicProvider {
             }
         }
 
-        // Convert system text to SystemPrompt with cache control if large
+        // Convert system text to SystemPrompt with cache control if large.
+        // The per-turn tail stays after the breakpoint so it does not
+        // invalidate the cached prefix.
         let system_prompt = system_text.map(|text| {
-            if Self::should_cache_system(&text) {
-                SystemPrompt::Blocks(vec![SystemBlock {
+            let (stable, volatile) = Self::split_volatile_system(&text);
+            if Self::should_cache_system(stable) {
+                let mut blocks = vec![SystemBlock {
                     block_type: "text".to_string(),
-                    text,
+                    text: stable.to_string(),
                     cache_control: Some(CacheControl::ephemeral()),
-                }])
+                }];
+                if !volatile.trim().is_empty() {
+                    blocks.push(SystemBlock {
+                        block_type: "text".to_string(),
+                        text: volatile.to_string(),
+                        cache_control: None,
+                    });
+                }
+                SystemPrompt::Blocks(blocks)
             } else {
                 SystemPrompt::String(text)
             }
@@ -404,6 +696,18 @@ impl AnthropicProvider {
         (system_prompt, native_messages)
     }
 
+    /// Convert messages and auto-cache the last one if the conversation is long.
+    fn convert_messages_with_cache(
+        messages: &[ChatMessage],
+    ) -> (Option<SystemPrompt>, Vec<NativeMessage>) {
+        let (system_prompt, mut native_messages) = Self::convert_messages(messages);
+        Self::apply_cache_to_compaction_summary(&mut native_messages);
+        if Self::should_cache_conversation(messages) {
+            Self::apply_cache_to_last_message(&mut native_messages);
+        }
+        (system_prompt, native_messages)
+    }
+
     fn parse_text_response(response: ChatResponse) -> anyhow::Result<String> {
         response
             .content
@@ -417,10 +721,7 @@ impl AnthropicProvider {
         let mut text_parts = Vec::new();
         let mut tool_calls = Vec::new();
 
-        let usage = response.usage.map(|u| TokenUsage {
-            input_tokens: u.input_tokens,
-            output_tokens: u.output_tokens,
-        });
+        let usage = response.usage.map(TokenUsage::from);
 
         for block in response.content {
             match block.kind.as_str() {
@@ -464,6 +765,89 @@ impl AnthropicProvider {
     fn http_client(&self) -> Client {
         crate::config::build_runtime_proxy_client_with_timeouts("provider.anthropic", 120, 10)
     }
+
+    fn require_credential(&self) -> anyhow::Result<&str> {
+        self.credential.as_deref().ok_or_else(|| {
+            anyhow::anyhow!(
+                "Anthropic credentials not set. Set ANTHROPIC_API_KEY or ANTHROPIC_OAUTH_TOKEN (setup-token)."
+            )
+        })
+    }
+
+    /// Message parameters for one batch entry.
+    fn batch_params(
+        request: &ProviderChatRequest<'_>,
+        model: &str,
+        temperature: f64,
+    ) -> anyhow::Result<serde_json::Value> {
+        if request.response_format.is_some() {
+            // Structured output rides on a forced tool call that `chat`
+            // unwraps; batch results are read back without the format.
+            anyhow::bail!("Anthropic structured output is not available in batch mode");
+        }
+        let (system_prompt, messages) = Self::convert_messages_with_cache(request.messages);
+        let mut native_request = NativeChatRequest {
+            model: model.to_string(),
+            max_tokens: DEFAULT_MAX_TOKENS,
+            system: system_prompt,
+            messages,
+            temperature,
+            tools: Self::convert_tools(request.tools),
+            tool_choice: None,
+            stream: None,
+            top_p: None,
+            stop_sequences: None,
+            thinking: None,
+        };
+        native_request.apply_generation(request.params);
+        Ok(serde_json::to_value(native_request)?)
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct MessageBatch {
+    id: String,
+    processing_status: String,
+    #[serde(default)]
+    results_url: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct MessageBatchResultLine {
+    custom_id: String,
+    result: MessageBatchResult,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum MessageBatchResult {
+    Succeeded { message: NativeChatResponse },
+    Errored { error: serde_json::Value },
+    Canceled,
+    Expired,
+}
+
+impl MessageBatchResultLine {
+    fn into_result(self) -> BatchResult {
+        let outcome = match self.result {
+            MessageBatchResult::Succeeded { message } => {
+                Ok(AnthropicProvider::parse_native_response(message))
+            }
+            MessageBatchResult::Errored { error } => Err(error
+                .pointer("/error/message")
+                .and_then(serde_json::Value::as_str)
+                .map_or_else(
+                    || super::sanitize_api_error(&error.to_string()),
+                    ToString::to_string,
+                )),
+            MessageBatchResult::Canceled => Err("request was canceled".to_string()),
+            MessageBatchResult::Expired => Err("request expired before processing".to_string()),
+        };
+        BatchResult {
+            custom_id: self.custom_id,
+            outcome,
+        }
+    }
 }
 
 #[async_trait]
@@ -483,7 +867,7 @@ impl Provider for AnthropicProvider {
 
         let request = ChatRequest {
             model: model.to_string(),
-            max_tokens: 4096,
+            max_tokens: DEFAULT_MAX_TOKENS,
             system: system_prompt.map(ToString::to_string),
             messages: vec![Message {
                 role: "user".to_string(),
@@ -523,21 +907,43 @@ impl Provider for AnthropicProvider {
             )
         })?;
 
-        let (system_prompt, mut messages) = Self::convert_messages(request.messages);
-
-        // Auto-cache last message if conversation is long
-        if Self::should_cache_conversation(request.messages) {
-            Self::apply_cache_to_last_message(&mut messages);
+        let (system_prompt, messages) = Self::convert_messages_with_cache(request.messages);
+
+        let mut tools = Self::convert_tools(request.tools);
+        let structured = request
+            .response_format
+            .map(|format| (format, Self::structured_output_schema(format)));
+        let mut tool_choice = None;
+        if let Some((format, (schema, _))) = &structured {
+            // With other tools present the model may still call those first;
+            // "any" keeps it from answering in free text.
+            tool_choice = Some(if tools.is_some() {
+                serde_json::json!({"type": "any"})
+            } else {
+                serde_json::json!({"type": "tool", "name": format.name})
+            });
+            tools.get_or_insert_with(Vec::new).push(NativeToolSpec {
+                name: &format.name,
+                description: STRUCTURED_OUTPUT_TOOL_DESCRIPTION,
+                input_schema: schema,
+                cache_control: None,
+            });
         }
 
-        let native_request = NativeChatRequest {
+        let mut native_request = NativeChatRequest {
             model: model.to_string(),
-            max_tokens: 4096,
+            max_tokens: DEFAULT_MAX_TOKENS,
             system: system_prompt,
             messages,
             temperature,
-            tools: Self::convert_tools(request.tools),
+            tools,
+            tool_choice,
+            stream: None,
+            top_p: None,
+            stop_sequences: None,
+            thinking: None,
         };
+        native_request.apply_generation(request.params);
 
         let req = self
             .http_client()
@@ -552,13 +958,70 @@ impl Provider for AnthropicProvider {
         }
 
         let native_response: NativeChatResponse = response.json().await?;
-        Ok(Self::parse_native_response(native_response))
+        let mut result = Self::parse_native_response(native_response);
+        if let Some((format, (_, wrapped))) = &structured {
+            Self::take_structured_output(&mut result, &format.name, *wrapped);
+        }
+        Ok(result)
     }
 
     fn supports_native_tools(&self) -> bool {
         true
     }
 
+    fn supports_stream_events(&self) -> bool {
+        true
+    }
+
+    fn stream_chat(
+        &self,
+        request: ProviderChatRequest<'_>,
+        model: &str,
+        temperature: f64,
+    ) -> stream::BoxStream<'static, StreamResult<StreamEvent>> {
+        let Some(credential) = self.credential.as_ref() else {
+            return streaming::error_stream(StreamError::Provider(
+                "Anthropic credentials not set. Set ANTHROPIC_API_KEY or ANTHROPIC_OAUTH_TOKEN (setup-token)."
+                    .to_string(),
+            ));
+        };
+        if request.response_format.is_some() {
+            // Structured output rides on a forced tool call that `chat`
+            // unwraps; streaming would surface it as a regular tool call.
+            return streaming::error_stream(StreamError::Provider(
+                "Anthropic structured output is not available while streaming".to_string(),
+            ));
+        }
+
+        let (system_prompt, messages) = Self::convert_messages_with_cache(request.messages);
+        let mut native_request = NativeChatRequest {
+            model: model.to_string(),
+            max_tokens: DEFAULT_MAX_TOKENS,
+            system: system_prompt,
+            messages,
+            temperature,
+            tools: Self::convert_tools(request.tools),
+            tool_choice: None,
+            stream: Some(true),
+            top_p: None,
+            stop_sequences: None,
+            thinking: None,
+        };
+        native_request.apply_generation(request.params);
+
+        let req = self
+            .http_client()
+            .post(format!("{}/v1/messages", self.base_url))
+            .header("anthropic-version", "2023-06-01")
+            .header("content-type", "application/json")
+            .json(&native_request);
+        streaming::event_stream(
+            "Anthropic",
+            self.apply_auth(req, credential),
+            NativeStreamParser::default(),
+        )
+    }
+
     fn capabilities(&self) -> ProviderCapabilities {
         ProviderCapabilities {
             native_tool_calling: true,
@@ -609,6 +1072,8 @@ impl Provider for AnthropicProvider {
             } else {
                 Some(&tool_specs)
             },
+            response_format: None,
+            params: None,
         };
         self.chat(request, model, temperature).await
     }
@@ -628,6 +1093,91 @@ impl Provider for AnthropicProvider {
     }
 }
 
+#[async_trait]
+impl BatchProvider for AnthropicProvider {
+    async fn submit_batch(
+        &self,
+        requests: &[BatchRequest<'_>],
+        model: &str,
+        temperature: f64,
+    ) -> anyhow::Result<BatchHandle> {
+        let credential = self.require_credential()?;
+        if requests.is_empty() {
+            anyhow::bail!("Cannot submit an empty batch");
+        }
+
+        let entries = requests
+            .iter()
+            .map(|item| {
+                Ok(serde_json::json!({
+                    "custom_id": item.custom_id,
+                    "params": Self::batch_params(&item.request, model, temperature)?,
+                }))
+            })
+            .collect::<anyhow::Result<Vec<_>>>()?;
+
+        let req = self
+            .http_client()
+            .post(format!("{}/v1/messages/batches", self.base_url))
+            .header("anthropic-version", "2023-06-01")
+            .header("content-type", "application/json")
+            .json(&serde_json::json!({ "requests": entries }));
+        let response = self.apply_auth(req, credential).send().await?;
+        if !response.status().is_success() {
+            return Err(super::api_error("Anthropic", response).await);
+        }
+        let batch: MessageBatch = response.json().await?;
+
+        Ok(BatchHandle {
+            provider: "anthropic".to_string(),
+            id: batch.id,
+            model: model.to_string(),
+            submitted_at: chrono::Utc::now(),
+        })
+    }
+
+    async fn poll_batch(&self, handle: &BatchHandle) -> anyhow::Result<BatchStatus> {
+        let credential = self.require_credential()?;
+        let req = self
+            .http_client()
+            .get(format!(
+                "{}/v1/messages/batches/{}",
+                self.base_url, handle.id
+            ))
+            .header("anthropic-version", "2023-06-01");
+        let response = self.apply_auth(req, credential).send().await?;
+        if !response.status().is_success() {
+            return Err(super::api_error("Anthropic", response).await);
+        }
+        let batch: MessageBatch = response.json().await?;
+        if batch.processing_status != "ended" {
+            return Ok(BatchStatus::InProgress);
+        }
+        let Some(results_url) = batch.results_url else {
+            return Ok(BatchStatus::Failed(format!(
+                "Anthropic batch {} ended without results",
+                batch.id
+            )));
+        };
+
+        let req = self
+            .http_client()
+            .get(results_url)
+            .header("anthropic-version", "2023-06-01");
+        let response = self.apply_auth(req, credential).send().await?;
+        if !response.status().is_success() {
+            return Err(super::api_error("Anthropic", response).await);
+        }
+        let lines: Vec<MessageBatchResultLine> = parse_jsonl(&response.text().await?)?;
+        Ok(BatchStatus::Completed(
+            lines
+                .into_iter()
+                .map(MessageBatchResultLine::into_result)
+                .collect(),
+        ))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1192,6 +1742,46 @@ mod tests {
         }
     }
 
+    #[test]
+    fn convert_messages_keeps_volatile_system_tail_outside_cache() {
+        let stable = format!("## Tools\n\n{}\n\n", "a".repeat(3073));
+        let volatile = format!("{VOLATILE_PROMPT_HEADING}\n\n2026-01-01 09:00:00 (UTC)\n");
+        let messages = vec![ChatMessage::system(format!("{stable}{volatile}"))];
+
+        let (system_prompt, _) = AnthropicProvider::convert_messages(&messages);
+
+        match system_prompt.unwrap() {
+            SystemPrompt::Blocks(blocks) => {
+                assert_eq!(blocks.len(), 2);
+                assert_eq!(blocks[0].text, stable);
+                assert!(blocks[0].cache_control.is_some());
+                assert_eq!(blocks[1].text, volatile);
+                assert!(blocks[1].cache_control.is_none());
+            }
+            SystemPrompt::String(_) => panic!("Expected Blocks variant for large prompt"),
+        }
+    }
+
+    #[test]
+    fn convert_messages_with_cache_marks_compaction_summary() {
+        let messages = vec![
+            ChatMessage::system("System"),
+            ChatMessage::assistant(format!("{COMPACTION_SUMMARY_PREFIX}\n- user prefers Rust")),
+            ChatMessage::user("next question"),
+        ];
+
+        let (_, native) = AnthropicProvider::convert_messages_with_cache(&messages);
+
+        match &native[0].content[0] {
+            NativeContentOut::Text { cache_control, .. } => assert!(cache_control.is_some()),
+            other => panic!("Expected text block, got {other:?}"),
+        }
+        match &native[1].content[0] {
+            NativeContentOut::Text { cache_control, .. } => assert!(cache_control.is_none()),
+            other => panic!("Expected text block, got {other:?}"),
+        }
+    }
+
     #[test]
     fn backward_compatibility_native_chat_request() {
         // Test that requests without cache_control serialize identically to old format
@@ -1208,13 +1798,254 @@ mod tests {
             }],
             temperature: 0.7,
             tools: None,
+            tool_choice: None,
+            stream: None,
+            top_p: None,
+            stop_sequences: None,
+            thinking: None,
         };
 
         let json = serde_json::to_string(&req).unwrap();
         assert!(!json.contains("cache_control"));
+        assert!(!json.contains("tool_choice"));
+        assert!(!json.contains("stream"));
         assert!(json.contains(r#""system":"System""#));
     }
 
+    fn empty_native_request<'a>() -> NativeChatRequest<'a> {
+        NativeChatRequest {
+            model: "claude-sonnet-4".to_string(),
+            max_tokens: DEFAULT_MAX_TOKENS,
+            system: None,
+            messages: vec![],
+            temperature: 0.2,
+            tools: None,
+            tool_choice: None,
+            stream: None,
+            top_p: None,
+            stop_sequences: None,
+            thinking: None,
+        }
+    }
+
+    #[test]
+    fn apply_generation_maps_limits_and_thinking_budget() {
+        let params = GenerationParams {
+            max_tokens: Some(2000),
+            top_p: Some(0.95),
+            stop: vec!["###".into()],
+            seed: Some(1),
+            reasoning_effort: Some(crate::providers::ReasoningEffort::Medium),
+            ..GenerationParams::default()
+        };
+        let mut req = empty_native_request();
+        req.apply_generation(Some(&params));
+
+        let json = serde_json::to_value(&req).unwrap();
+        assert_eq!(json["stop_sequences"], serde_json::json!(["###"]));
+        assert_eq!(json["top_p"], 0.95);
+        assert_eq!(json["thinking"]["budget_tokens"], 4096);
+        // max_tokens must exceed the budget; thinking needs temperature 1.
+        assert_eq!(json["max_tokens"], 4096 + DEFAULT_MAX_TOKENS);
+        assert_eq!(json["temperature"], 1.0);
+        assert!(json.get("seed").is_none());
+    }
+
+    #[test]
+    fn apply_generation_skips_thinking_when_tools_are_present() {
+        let schema = serde_json::json!({"type": "object"});
+        let mut req = empty_native_request();
+        req.tools = Some(vec![NativeToolSpec {
+            name: "shell",
+            description: "Run a command",
+            input_schema: &schema,
+            cache_control: None,
+        }]);
+        req.apply_generation(Some(&GenerationParams {
+            max_tokens: Some(256),
+            thinking_budget: Some(8000),
+            ..GenerationParams::default()
+        }));
+
+        assert!(req.thinking.is_none());
+        assert_eq!(req.max_tokens, 256);
+        assert!((req.temperature - 0.2).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn structured_output_forces_schema_tool() {
+        let format = ResponseFormat::new(
+            "verdict",
+            serde_json::json!({"type": "object", "properties": {"ok": {"type": "boolean"}}}),
+        );
+        let (schema, wrapped) = AnthropicProvider::structured_output_schema(&format);
+        assert!(!wrapped);
+
+        let req = NativeChatRequest {
+            model: "claude-3-opus".to_string(),
+            max_tokens: 4096,
+            system: None,
+            messages: vec![],
+            temperature: 0.0,
+            tools: Some(vec![NativeToolSpec {
+                name: &format.name,
+                description: STRUCTURED_OUTPUT_TOOL_DESCRIPTION,
+                input_schema: &schema,
+                cache_control: None,
+            }]),
+            tool_choice: Some(serde_json::json!({"type": "tool", "name": format.name})),
+            stream: None,
+            top_p: None,
+            stop_sequences: None,
+            thinking: None,
+        };
+        let json = serde_json::to_value(&req).unwrap();
+        assert_eq!(json["tool_choice"]["name"], "verdict");
+        assert_eq!(
+            json["tools"][0]["input_schema"]["properties"]["ok"]["type"],
+            "boolean"
+        );
+
+        let mut response = ProviderChatResponse {
+            text: Some("Sure, here it is".to_string()),
+            tool_calls: vec![ProviderToolCall {
+                id: "toolu_1".to_string(),
+                name: "verdict".to_string(),
+                arguments: r#"{"ok":true}"#.to_string(),
+            }],
+            usage: None,
+            reasoning_content: None,
+        };
+        AnthropicProvider::take_structured_output(&mut response, "verdict", false);
+        assert_eq!(response.text.as_deref(), Some(r#"{"ok":true}"#));
+        assert!(response.tool_calls.is_empty());
+    }
+
+    #[test]
+    fn structured_output_wraps_non_object_schema() {
+        let format = ResponseFormat::new(
+            "labels",
+            serde_json::json!({"type": "array", "items": {"type": "string"}}),
+        );
+        let (schema, wrapped) = AnthropicProvider::structured_output_schema(&format);
+        assert!(wrapped);
+        assert_eq!(schema["properties"]["value"]["type"], "array");
+
+        let mut response = ProviderChatResponse {
+            text: None,
+            tool_calls: vec![
+                ProviderToolCall {
+                    id: "toolu_1".to_string(),
+                    name: "shell".to_string(),
+                    arguments: "{}".to_string(),
+                },
+                ProviderToolCall {
+                    id: "toolu_2".to_string(),
+                    name: "labels".to_string(),
+                    arguments: r#"{"value":["a","b"]}"#.to_string(),
+                },
+            ],
+            usage: None,
+            reasoning_content: None,
+        };
+        AnthropicProvider::take_structured_output(&mut response, "labels", true);
+        assert_eq!(response.text.as_deref(), Some(r#"["a","b"]"#));
+        assert_eq!(response.tool_calls.len(), 1);
+        assert_eq!(response.tool_calls[0].name, "shell");
+    }
+
+    #[test]
+    fn stream_parser_translates_message_events() {
+        let mut parser = NativeStreamParser::default();
+        let mut events = Vec::new();
+        for line in [
+            "event: message_start",
+            r#"data: {"type":"message_start","message":{"id":"msg_1","usage":{"input_tokens":25,"output_tokens":1,"cache_read_input_tokens":2048,"cache_creation_input_tokens":0}}}"#,
+            r#"data: {"type":"content_block_start","index":0,"content_block":{"type":"thinking","thinking":""}}"#,
+            r#"data: {"type":"content_block_delta","index":0,"delta":{"type":"thinking_delta","thinking":"User wants the date."}}"#,
+            r#"data: {"type":"content_block_delta","index":0,"delta":{"type":"signature_delta","signature":"abc"}}"#,
+            r#"data: {"type":"content_block_stop","index":0}"#,
+            r#"data: {"type":"content_block_start","index":1,"content_block":{"type":"text","text":""}}"#,
+            r#"data: {"type":"content_block_delta","index":1,"delta":{"type":"text_delta","text":"Let me check."}}"#,
+            r#"data: {"type":"content_block_stop","index":1}"#,
+            r#"data: {"type":"content_block_start","index":2,"content_block":{"type":"tool_use","id":"toolu_1","name":"shell","input":{}}}"#,
+            r#"data: {"type":"content_block_delta","index":2,"delta":{"type":"input_json_delta","partial_json":"{\"command\": "}}"#,
+            r#"data: {"type":"content_block_delta","index":2,"delta":{"type":"input_json_delta","partial_json":"\"date\"}"}}"#,
+            r#"data: {"type":"content_block_stop","index":2}"#,
+            r#"data: {"type":"message_delta","delta":{"stop_reason":"tool_use"},"usage":{"output_tokens":42}}"#,
+            r#"data: {"type":"ping"}"#,
+            r#"data: {"type":"message_stop"}"#,
+        ] {
+            events.extend(parser.parse_line(line).unwrap());
+        }
+        assert!(parser.finish().is_empty());
+
+        assert_eq!(
+            events,
+            vec![
+                StreamEvent::Usage(TokenUsage {
+                    input_tokens: Some(25),
+                    output_tokens: Some(1),
+                    cache_read_tokens: Some(2048),
+                    cache_write_tokens: Some(0),
+                }),
+                StreamEvent::ReasoningDelta("User wants the date.".into()),
+                StreamEvent::TextDelta("Let me check.".into()),
+                StreamEvent::ToolCallStart {
+                    index: 2,
+                    id: "toolu_1".into(),
+                    name: "shell".into(),
+                },
+                StreamEvent::ToolCallDelta {
+                    index: 2,
+                    arguments: "{\"command\": ".into(),
+                },
+                StreamEvent::ToolCallDelta {
+                    index: 2,
+                    arguments: "\"date\"}".into(),
+                },
+                StreamEvent::ToolCallEnd { index: 2 },
+                StreamEvent::Usage(TokenUsage {
+                    input_tokens: None,
+                    output_tokens: Some(42),
+                    cache_read_tokens: None,
+                    cache_write_tokens: None,
+                }),
+            ]
+        );
+    }
+
+    #[test]
+    fn stream_parser_surfaces_error_events() {
+        let mut parser = NativeStreamParser::default();
+        let err = parser
+            .parse_line(
+                r#"data: {"type":"error","error":{"type":"overloaded_error","message":"Overloaded"}}"#,
+            )
+            .unwrap_err();
+        assert!(err.to_string().contains("Overloaded"));
+    }
+
+    #[tokio::test]
+    async fn stream_chat_rejects_response_format() {
+        use futures_util::StreamExt;
+
+        let provider = AnthropicProvider::new(Some("anthropic-test-credential"));
+        let format = ResponseFormat::new("verdict", serde_json::json!({"type": "object"}));
+        let request = ProviderChatRequest {
+            messages: &[ChatMessage::user("hi")],
+            tools: None,
+            response_format: Some(&format),
+            params: None,
+        };
+        let first = provider
+            .stream_chat(request, "claude-3-opus", 0.0)
+            .next()
+            .await
+            .unwrap();
+        assert!(first.is_err());
+    }
+
     #[tokio::test]
     async fn warmup_without_key_is_noop() {
         let provider = AnthropicProvider::new(None);
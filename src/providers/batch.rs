@@ -0,0 +1,76 @@
+//! Batch submission for bulk, non-interactive workloads.
+//!
+//! Providers with a batch API accept many requests at once, answer within
+//! hours instead of seconds and bill the work at a discount. A caller submits
+//! requests with [`BatchProvider::submit_batch`], persists the returned
+//! [`BatchHandle`] and polls it with [`BatchProvider::poll_batch`] until the
+//! batch ends, so pending work survives restarts.
+
+use super::traits::{ChatRequest, ChatResponse};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// One request in a batch, keyed by a caller-chosen id.
+pub struct BatchRequest<'a> {
+    /// Unique within the batch; results carry it back.
+    pub custom_id: &'a str,
+    pub request: ChatRequest<'a>,
+}
+
+/// Reference to a submitted batch, safe to persist.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BatchHandle {
+    /// Provider that accepted the batch (`openai`, `anthropic`).
+    pub provider: String,
+    /// Provider-assigned batch id.
+    pub id: String,
+    pub model: String,
+    pub submitted_at: DateTime<Utc>,
+}
+
+/// Result of one request in an ended batch.
+#[derive(Debug)]
+pub struct BatchResult {
+    pub custom_id: String,
+    /// The response, or the provider's error message for this request.
+    pub outcome: Result<ChatResponse, String>,
+}
+
+/// State of a submitted batch.
+#[derive(Debug)]
+pub enum BatchStatus {
+    /// Still queued or running.
+    InProgress,
+    /// Ended; one result per request that produced output.
+    Completed(Vec<BatchResult>),
+    /// Ended without results (expired, cancelled or rejected).
+    Failed(String),
+}
+
+/// Providers that can run requests through a discounted batch API.
+#[async_trait]
+pub trait BatchProvider: Send + Sync {
+    /// Submit requests as one batch.
+    async fn submit_batch(
+        &self,
+        requests: &[BatchRequest<'_>],
+        model: &str,
+        temperature: f64,
+    ) -> anyhow::Result<BatchHandle>;
+
+    /// Check a batch, returning its results once it has ended.
+    async fn poll_batch(&self, handle: &BatchHandle) -> anyhow::Result<BatchStatus>;
+}
+
+/// Parse a JSONL body line by line, skipping blank lines.
+pub(crate) fn parse_jsonl<T: serde::de::DeserializeOwned>(body: &str) -> anyhow::Result<Vec<T>> {
+    body.lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .map(|line| {
+            serde_json::from_str(line)
+                .map_err(|e| anyhow::anyhow!("Invalid batch result line: {e}"))
+        })
+        .collect()
+}
@@ -6,7 +6,8 @@
 
 use crate::providers::traits::{
     ChatMessage, ChatRequest as ProviderChatRequest, ChatResponse as ProviderChatResponse,
-    Provider, ProviderCapabilities, TokenUsage, ToolCall as ProviderToolCall, ToolsPayload,
+    GenerationParams, Provider, ProviderCapabilities, TokenUsage, ToolCall as ProviderToolCall,
+    ToolsPayload,
 };
 use crate::tools::ToolSpec;
 use async_trait::async_trait;
@@ -366,6 +367,25 @@ enum SystemBlock {
 struct InferenceConfig {
     max_tokens: u32,
     temperature: f64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    top_p: Option<f64>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    stop_sequences: Vec<String>,
+}
+
+impl InferenceConfig {
+    /// Converse inference settings. Only `max_tokens`, `top_p` and `stop`
+    /// of the generation parameters have Converse equivalents.
+    fn new(temperature: f64, params: Option<&GenerationParams>) -> Self {
+        Self {
+            max_tokens: params
+                .and_then(|p| p.max_tokens)
+                .unwrap_or(DEFAULT_MAX_TOKENS),
+            temperature,
+            top_p: params.and_then(|p| p.top_p),
+            stop_sequences: params.map(|p| p.stop.clone()).unwrap_or_default(),
+        }
+    }
 }
 
 #[derive(Debug, Serialize)]
@@ -833,6 +853,8 @@ impl BedrockProvider {
         let usage = response.usage.map(|u| TokenUsage {
             input_tokens: u.input_tokens,
             output_tokens: u.output_tokens,
+            cache_read_tokens: None,
+            cache_write_tokens: None,
         });
 
         if let Some(output) = response.output {
@@ -1018,10 +1040,7 @@ impl Provider for BedrockProvider {
                 role: "user".to_string(),
                 content: Self::parse_user_content_blocks(message),
             }],
-            inference_config: Some(InferenceConfig {
-                max_tokens: DEFAULT_MAX_TOKENS,
-                temperature,
-            }),
+            inference_config: Some(InferenceConfig::new(temperature, None)),
             tool_config: None,
         };
 
@@ -1042,7 +1061,17 @@ impl Provider for BedrockProvider {
     ) -> anyhow::Result<ProviderChatResponse> {
         let credentials = self.resolve_credentials().await?;
 
-        let (system_blocks, mut converse_messages) = Self::convert_messages(request.messages);
+        // Converse has no response-format parameter; the schema travels as
+        // system prompt instructions.
+        let formatted_messages = request.response_format.map(|format| {
+            crate::providers::traits::with_system_instructions(
+                request.messages,
+                &format.instructions(),
+            )
+        });
+        let messages = formatted_messages.as_deref().unwrap_or(request.messages);
+
+        let (system_blocks, mut converse_messages) = Self::convert_messages(messages);
 
         // Apply cachePoint to system if large.
         let system = system_blocks.map(|mut blocks| {
@@ -1058,7 +1087,7 @@ impl Provider for BedrockProvider {
         });
 
         // Apply cachePoint to last message if conversation is long.
-        if Self::should_cache_conversation(request.messages) {
+        if Self::should_cache_conversation(messages) {
             if let Some(last_msg) = converse_messages.last_mut() {
                 last_msg
                     .content
@@ -1073,10 +1102,7 @@ impl Provider for BedrockProvider {
         let converse_request = ConverseRequest {
             system,
             messages: converse_messages,
-            inference_config: Some(InferenceConfig {
-                max_tokens: DEFAULT_MAX_TOKENS,
-                temperature,
-            }),
+            inference_config: Some(InferenceConfig::new(temperature, request.params)),
             tool_config,
         };
 
@@ -1440,6 +1466,8 @@ mod tests {
             inference_config: Some(InferenceConfig {
                 max_tokens: 4096,
                 temperature: 0.7,
+                top_p: None,
+                stop_sequences: Vec::new(),
             }),
             tool_config: None,
         };
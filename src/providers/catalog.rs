@@ -0,0 +1,535 @@
+//! Model catalog: context windows, pricing and capabilities per model.
+//!
+//! The catalog is layered. A snapshot bundled with the binary comes first,
+//! then metadata recorded by `zeroclaw models refresh` in
+//! `<workspace>/state/model_catalog.json`, then a hand-maintained
+//! `<workspace>/model_catalog.json`. Later layers win field by field, so an
+//! override only needs the fields it changes.
+
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::path::{Path, PathBuf};
+
+const BUNDLED_CATALOG: &str = include_str!("model_catalog.json");
+
+/// File name of the local override, relative to the workspace root.
+pub const CATALOG_OVERRIDE_FILE: &str = "model_catalog.json";
+
+/// File name of refreshed metadata, relative to `<workspace>/state`.
+pub const CATALOG_REFRESH_FILE: &str = "model_catalog.json";
+
+/// What is known about one model. Unknown fields stay `None`.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ModelInfo {
+    /// Provider the id belongs to (`anthropic`, `openai`, `openrouter`, ...).
+    pub provider: String,
+    pub id: String,
+    /// Other ids that resolve to this model.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub aliases: Vec<String>,
+    /// Maximum input tokens.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub context_window: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_output_tokens: Option<u64>,
+    /// USD per 1M input tokens.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub input_price: Option<f64>,
+    /// USD per 1M output tokens.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub output_price: Option<f64>,
+    /// USD per 1M input tokens read from the prompt cache.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cache_read_price: Option<f64>,
+    /// USD per 1M input tokens written to the prompt cache.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cache_write_price: Option<f64>,
+    /// Accepted input modalities (`text`, `image`, `audio`, `video`).
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub modalities: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tool_calling: Option<bool>,
+    /// Structured output / JSON mode support.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub json_mode: Option<bool>,
+    /// Date the provider retires (or retired) the model.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub deprecated_on: Option<NaiveDate>,
+}
+
+impl ModelInfo {
+    fn matches_id(&self, model: &str) -> bool {
+        self.id.eq_ignore_ascii_case(model)
+            || self
+                .aliases
+                .iter()
+                .any(|alias| alias.eq_ignore_ascii_case(model))
+    }
+
+    /// Input and output price per 1M tokens, when both are known.
+    pub fn pricing(&self) -> Option<(f64, f64)> {
+        Some((self.input_price?, self.output_price?))
+    }
+
+    pub fn supports_vision(&self) -> bool {
+        self.modalities.iter().any(|m| m == "image")
+    }
+
+    /// Whether the model is retired as of `today`.
+    pub fn is_deprecated(&self, today: NaiveDate) -> bool {
+        self.deprecated_on.is_some_and(|date| date <= today)
+    }
+
+    /// Short human-readable summary, e.g. `200k ctx, 64k out, $3.00/$15.00 per 1M, tools`.
+    pub fn summary(&self) -> String {
+        let mut parts = Vec::new();
+        if let Some(window) = self.context_window {
+            parts.push(format!("{} ctx", format_tokens(window)));
+        }
+        if let Some(max_output) = self.max_output_tokens {
+            parts.push(format!("{} out", format_tokens(max_output)));
+        }
+        if let Some((input, output)) = self.pricing() {
+            parts.push(format!("${input:.2}/${output:.2} per 1M"));
+        }
+        if self.tool_calling == Some(true) {
+            parts.push("tools".into());
+        }
+        if self.supports_vision() {
+            parts.push("vision".into());
+        }
+        if let Some(date) = self.deprecated_on {
+            parts.push(format!("retires {date}"));
+        }
+        parts.join(", ")
+    }
+
+    /// Overlay the fields `other` knows about onto this entry.
+    fn merge(&mut self, other: ModelInfo) {
+        fn take<T>(slot: &mut Option<T>, value: Option<T>) {
+            if value.is_some() {
+                *slot = value;
+            }
+        }
+        for alias in other.aliases {
+            if !self.matches_id(&alias) {
+                self.aliases.push(alias);
+            }
+        }
+        take(&mut self.context_window, other.context_window);
+        take(&mut self.max_output_tokens, other.max_output_tokens);
+        take(&mut self.input_price, other.input_price);
+        take(&mut self.output_price, other.output_price);
+        take(&mut self.cache_read_price, other.cache_read_price);
+        take(&mut self.cache_write_price, other.cache_write_price);
+        if !other.modalities.is_empty() {
+            self.modalities = other.modalities;
+        }
+        take(&mut self.tool_calling, other.tool_calling);
+        take(&mut self.json_mode, other.json_mode);
+        take(&mut self.deprecated_on, other.deprecated_on);
+    }
+
+    fn has_metadata(&self) -> bool {
+        self.context_window.is_some()
+            || self.max_output_tokens.is_some()
+            || self.input_price.is_some()
+            || self.output_price.is_some()
+            || !self.modalities.is_empty()
+            || self.tool_calling.is_some()
+            || self.json_mode.is_some()
+    }
+}
+
+/// Known models across providers.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ModelCatalog {
+    #[serde(default)]
+    pub models: Vec<ModelInfo>,
+}
+
+impl ModelCatalog {
+    /// The snapshot shipped with this build.
+    pub fn bundled() -> Self {
+        serde_json::from_str(BUNDLED_CATALOG).expect("bundled model catalog is valid JSON")
+    }
+
+    /// Bundled catalog overlaid with refreshed metadata and the local override.
+    ///
+    /// Unreadable or malformed local files are skipped with a warning so a bad
+    /// edit never takes the agent down.
+    pub fn load(workspace_dir: &Path) -> Self {
+        let mut catalog = Self::bundled();
+        for path in [
+            refresh_path(workspace_dir),
+            workspace_dir.join(CATALOG_OVERRIDE_FILE),
+        ] {
+            if let Some(layer) = read_layer(&path) {
+                catalog.extend(layer.models);
+            }
+        }
+        catalog
+    }
+
+    /// Merge entries in, field by field for models already present.
+    pub fn extend(&mut self, models: impl IntoIterator<Item = ModelInfo>) {
+        for model in models {
+            match self.models.iter_mut().find(|existing| {
+                existing.provider.eq_ignore_ascii_case(&model.provider)
+                    && existing.matches_id(&model.id)
+            }) {
+                Some(existing) => existing.merge(model),
+                None => self.models.push(model),
+            }
+        }
+    }
+
+    /// Find a model for a provider.
+    ///
+    /// Prefers an entry under the same provider, then the same id under any
+    /// provider, then splits `vendor/model` ids (as used by OpenRouter) into
+    /// provider and model.
+    pub fn find(&self, provider: &str, model: &str) -> Option<&ModelInfo> {
+        let model = model.trim();
+        let provider = provider_key(provider);
+        self.models
+            .iter()
+            .find(|m| m.provider.eq_ignore_ascii_case(provider) && m.matches_id(model))
+            .or_else(|| self.models.iter().find(|m| m.matches_id(model)))
+            .or_else(|| {
+                let (vendor, rest) = model.split_once('/')?;
+                let vendor = if vendor == "google" { "gemini" } else { vendor };
+                self.models
+                    .iter()
+                    .find(|m| m.provider.eq_ignore_ascii_case(vendor) && m.matches_id(rest))
+            })
+    }
+
+    /// Models listed for one provider.
+    pub fn for_provider<'a>(&'a self, provider: &'a str) -> impl Iterator<Item = &'a ModelInfo> {
+        let provider = provider_key(provider);
+        self.models
+            .iter()
+            .filter(move |m| m.provider.eq_ignore_ascii_case(provider))
+    }
+}
+
+fn format_tokens(tokens: u64) -> String {
+    if tokens >= 1_000_000 {
+        format!("{:.1}M", tokens as f64 / 1_000_000.0)
+    } else if tokens >= 1_000 {
+        format!("{}k", tokens / 1_000)
+    } else {
+        tokens.to_string()
+    }
+}
+
+/// Strip `:<url>` suffixes and `-custom` variants down to the catalog key.
+fn provider_key(provider: &str) -> &str {
+    let name = provider.split(':').next().unwrap_or(provider).trim();
+    name.strip_suffix("-custom").unwrap_or(name)
+}
+
+fn refresh_path(workspace_dir: &Path) -> PathBuf {
+    workspace_dir.join("state").join(CATALOG_REFRESH_FILE)
+}
+
+fn read_layer(path: &Path) -> Option<ModelCatalog> {
+    let raw = std::fs::read_to_string(path).ok()?;
+    match serde_json::from_str(&raw) {
+        Ok(layer) => Some(layer),
+        Err(e) => {
+            tracing::warn!("Ignoring malformed model catalog {}: {e}", path.display());
+            None
+        }
+    }
+}
+
+/// Replace the refreshed metadata for `provider` with `models`.
+pub fn record_refresh(
+    workspace_dir: &Path,
+    provider: &str,
+    models: Vec<ModelInfo>,
+) -> anyhow::Result<()> {
+    let path = refresh_path(workspace_dir);
+    let provider = provider_key(provider);
+    let mut layer = read_layer(&path).unwrap_or_default();
+    layer
+        .models
+        .retain(|m| !m.provider.eq_ignore_ascii_case(provider));
+    layer.models.extend(models);
+
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    std::fs::write(&path, serde_json::to_vec_pretty(&layer)?)?;
+    Ok(())
+}
+
+/// Extract catalog metadata from a provider's `/models` response.
+///
+/// Understands OpenAI-style `data` arrays (including OpenRouter's pricing,
+/// context length and supported parameters) and Gemini's `models` array.
+/// Entries that carry nothing beyond an id are dropped.
+pub fn parse_models_payload(provider: &str, payload: &Value) -> Vec<ModelInfo> {
+    let items = payload
+        .get("data")
+        .or_else(|| payload.get("models"))
+        .unwrap_or(payload)
+        .as_array();
+    let Some(items) = items else {
+        return Vec::new();
+    };
+
+    items
+        .iter()
+        .filter_map(|item| {
+            let id = item
+                .get("id")
+                .or_else(|| item.get("name"))
+                .and_then(Value::as_str)?
+                .trim_start_matches("models/")
+                .to_string();
+            let first_u64 = |keys: &[&str]| {
+                keys.iter()
+                    .find_map(|key| item.pointer(key).and_then(Value::as_u64))
+            };
+            // OpenRouter quotes prices per token as decimal strings.
+            let per_million = |key: &str| {
+                let value = item.pointer(key)?;
+                let per_token = value
+                    .as_f64()
+                    .or_else(|| value.as_str()?.parse::<f64>().ok())?;
+                (per_token >= 0.0).then_some(per_token * 1_000_000.0)
+            };
+            let supported: Vec<&str> = item
+                .get("supported_parameters")
+                .and_then(Value::as_array)
+                .map(|params| params.iter().filter_map(Value::as_str).collect())
+                .unwrap_or_default();
+
+            let info = ModelInfo {
+                provider: provider_key(provider).to_string(),
+                id,
+                context_window: first_u64(&[
+                    "/context_length",
+                    "/context_window",
+                    "/max_input_tokens",
+                    "/inputTokenLimit",
+                    "/top_provider/context_length",
+                ]),
+                max_output_tokens: first_u64(&[
+                    "/max_output_tokens",
+                    "/outputTokenLimit",
+                    "/top_provider/max_completion_tokens",
+                ]),
+                input_price: per_million("/pricing/prompt"),
+                output_price: per_million("/pricing/completion"),
+                cache_read_price: per_million("/pricing/input_cache_read"),
+                cache_write_price: per_million("/pricing/input_cache_write"),
+                modalities: item
+                    .pointer("/architecture/input_modalities")
+                    .and_then(Value::as_array)
+                    .map(|m| {
+                        m.iter()
+                            .filter_map(Value::as_str)
+                            .map(str::to_string)
+                            .collect()
+                    })
+                    .unwrap_or_default(),
+                tool_calling: (!supported.is_empty()).then(|| supported.contains(&"tools")),
+                json_mode: (!supported.is_empty()).then(|| {
+                    supported.contains(&"response_format")
+                        || supported.contains(&"structured_outputs")
+                }),
+                ..ModelInfo::default()
+            };
+            info.has_metadata().then_some(info)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(s: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(s, "%Y-%m-%d").unwrap()
+    }
+
+    #[test]
+    fn bundled_catalog_parses_and_has_core_fields() {
+        let catalog = ModelCatalog::bundled();
+        assert!(!catalog.models.is_empty());
+        for model in &catalog.models {
+            assert!(model.context_window.is_some(), "{} lacks context", model.id);
+            assert!(model.pricing().is_some(), "{} lacks pricing", model.id);
+        }
+    }
+
+    #[test]
+    fn find_prefers_provider_then_id_then_vendor_prefix() {
+        let catalog = ModelCatalog::bundled();
+        let sonnet = catalog
+            .find("anthropic", "claude-sonnet-4-20250514")
+            .unwrap();
+        assert_eq!(sonnet.context_window, Some(200_000));
+        assert_eq!(
+            catalog
+                .find(
+                    "anthropic-custom:https://proxy.example",
+                    "claude-sonnet-4-0"
+                )
+                .unwrap()
+                .id,
+            "claude-sonnet-4-20250514"
+        );
+        assert_eq!(catalog.find("azure", "gpt-4o").unwrap().provider, "openai");
+        assert_eq!(
+            catalog.find("openrouter", "openai/gpt-4o-mini").unwrap().id,
+            "gpt-4o-mini"
+        );
+        assert_eq!(
+            catalog
+                .find("openrouter", "google/gemini-2.5-flash")
+                .unwrap()
+                .provider,
+            "gemini"
+        );
+        assert!(catalog.find("openai", "no-such-model").is_none());
+    }
+
+    #[test]
+    fn load_overlays_refresh_then_override_field_by_field() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        record_refresh(
+            tmp.path(),
+            "openai",
+            vec![ModelInfo {
+                provider: "openai".into(),
+                id: "gpt-4o".into(),
+                context_window: Some(111_000),
+                input_price: Some(2.0),
+                ..ModelInfo::default()
+            }],
+        )
+        .unwrap();
+        std::fs::write(
+            tmp.path().join(CATALOG_OVERRIDE_FILE),
+            r#"{"models": [
+                {"provider": "openai", "id": "gpt-4o", "input_price": 1.0},
+                {"provider": "local", "id": "my-model", "context_window": 8192}
+            ]}"#,
+        )
+        .unwrap();
+
+        let catalog = ModelCatalog::load(tmp.path());
+        let gpt = catalog.find("openai", "gpt-4o").unwrap();
+        assert_eq!(gpt.context_window, Some(111_000));
+        assert_eq!(gpt.input_price, Some(1.0));
+        assert_eq!(gpt.output_price, Some(10.0));
+        assert_eq!(
+            catalog.find("local", "my-model").unwrap().context_window,
+            Some(8192)
+        );
+    }
+
+    #[test]
+    fn malformed_override_falls_back_to_bundled() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        std::fs::write(tmp.path().join(CATALOG_OVERRIDE_FILE), "{not json").unwrap();
+        let catalog = ModelCatalog::load(tmp.path());
+        assert_eq!(catalog.models.len(), ModelCatalog::bundled().models.len());
+    }
+
+    #[test]
+    fn record_refresh_replaces_only_that_provider() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let entry = |provider: &str, id: &str| ModelInfo {
+            provider: provider.into(),
+            id: id.into(),
+            context_window: Some(1000),
+            ..ModelInfo::default()
+        };
+        record_refresh(tmp.path(), "openrouter", vec![entry("openrouter", "a")]).unwrap();
+        record_refresh(tmp.path(), "gemini", vec![entry("gemini", "b")]).unwrap();
+        record_refresh(tmp.path(), "openrouter", vec![entry("openrouter", "c")]).unwrap();
+
+        let layer = read_layer(&refresh_path(tmp.path())).unwrap();
+        let ids: Vec<&str> = layer.models.iter().map(|m| m.id.as_str()).collect();
+        assert_eq!(ids, vec!["b", "c"]);
+    }
+
+    #[test]
+    fn parse_openrouter_models_payload() {
+        let payload = serde_json::json!({"data": [
+            {
+                "id": "anthropic/claude-sonnet-4",
+                "context_length": 200000,
+                "pricing": {
+                    "prompt": "0.000003",
+                    "completion": "0.000015",
+                    "input_cache_read": "0.0000003",
+                    "input_cache_write": "0.00000375"
+                },
+                "top_provider": {"max_completion_tokens": 64000},
+                "architecture": {"input_modalities": ["text", "image"]},
+                "supported_parameters": ["tools", "response_format", "temperature"]
+            },
+            {"id": "openrouter/auto", "pricing": {"prompt": "-1", "completion": "-1"}},
+            {"id": "bare-model"}
+        ]});
+        let models = parse_models_payload("openrouter", &payload);
+        assert_eq!(models.len(), 1);
+        let sonnet = &models[0];
+        assert_eq!(sonnet.context_window, Some(200_000));
+        assert_eq!(sonnet.max_output_tokens, Some(64_000));
+        let (input, output) = sonnet.pricing().unwrap();
+        assert!((input - 3.0).abs() < 1e-9 && (output - 15.0).abs() < 1e-9);
+        assert!((sonnet.cache_write_price.unwrap() - 3.75).abs() < 1e-9);
+        assert!(sonnet.supports_vision());
+        assert_eq!(sonnet.tool_calling, Some(true));
+        assert_eq!(sonnet.json_mode, Some(true));
+    }
+
+    #[test]
+    fn parse_gemini_models_payload() {
+        let payload = serde_json::json!({"models": [{
+            "name": "models/gemini-2.5-pro",
+            "inputTokenLimit": 1048576,
+            "outputTokenLimit": 65536
+        }]});
+        let models = parse_models_payload("gemini", &payload);
+        assert_eq!(models[0].id, "gemini-2.5-pro");
+        assert_eq!(models[0].context_window, Some(1_048_576));
+        assert_eq!(models[0].max_output_tokens, Some(65_536));
+        assert_eq!(models[0].tool_calling, None);
+    }
+
+    #[test]
+    fn summary_lists_known_fields() {
+        let catalog = ModelCatalog::bundled();
+        assert_eq!(
+            catalog
+                .find("anthropic", "claude-sonnet-4-20250514")
+                .unwrap()
+                .summary(),
+            "200k ctx, 64k out, $3.00/$15.00 per 1M, tools, vision"
+        );
+        assert_eq!(
+            catalog.find("openai", "gpt-4.1").unwrap().summary(),
+            "1.0M ctx, 32k out, $2.00/$8.00 per 1M, tools, vision"
+        );
+    }
+
+    #[test]
+    fn deprecation_is_inclusive_of_the_retirement_date() {
+        let catalog = ModelCatalog::bundled();
+        let o1 = catalog.find("openai", "o1-preview").unwrap();
+        assert!(!o1.is_deprecated(date("2025-07-27")));
+        assert!(o1.is_deprecated(date("2025-07-28")));
+        let gpt = catalog.find("openai", "gpt-4o").unwrap();
+        assert!(!gpt.is_deprecated(date("2030-01-01")));
+    }
+}
@@ -3,10 +3,11 @@
 //! This module provides a single implementation that works for all of them.
 
 use crate::multimodal;
+use crate::providers::streaming::{self, sse_data, LineBuffer, StreamParser};
 use crate::providers::traits::{
     ChatMessage, ChatRequest as ProviderChatRequest, ChatResponse as ProviderChatResponse,
-    Provider, StreamChunk, StreamError, StreamOptions, StreamResult, TokenUsage,
-    ToolCall as ProviderToolCall,
+    GenerationParams, Provider, ResponseFormat, StreamChunk, StreamError, StreamEvent,
+    StreamOptions, StreamResult, TokenUsage, ToolCall as ProviderToolCall,
 };
 use async_trait::async_trait;
 use futures_util::{stream, StreamExt};
@@ -555,6 +556,11 @@ struct NativeChatRequest {
     tools: Option<Vec<serde_json::Value>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     tool_choice: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    response_format: Option<serde_json::Value>,
+    /// Generation parameters (`max_tokens`, `top_p`, `stop`, ...).
+    #[serde(flatten)]
+    generation: serde_json::Map<String, serde_json::Value>,
 }
 
 #[derive(Debug, Serialize)]
@@ -616,7 +622,11 @@ struct ResponsesContent {
 /// Server-Sent Event stream chunk for OpenAI-compatible streaming.
 #[derive(Debug, Deserialize)]
 struct StreamChunkResponse {
+    #[serde(default)]
     choices: Vec<StreamChoice>,
+    /// Sent on the last chunk by providers that report streaming usage.
+    #[serde(default)]
+    usage: Option<UsageInfo>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -632,6 +642,128 @@ struct StreamDelta {
     /// Reasoning/thinking models may stream output via `reasoning_content`.
     #[serde(default)]
     reasoning_content: Option<String>,
+    #[serde(default)]
+    tool_calls: Option<Vec<StreamToolCallDelta>>,
+}
+
+/// Incremental tool call: the first fragment carries `id` and the function
+/// name, later fragments append to `arguments`.
+#[derive(Debug, Deserialize)]
+struct StreamToolCallDelta {
+    #[serde(default)]
+    index: Option<usize>,
+    #[serde(default)]
+    id: Option<String>,
+    #[serde(default)]
+    function: Option<StreamFunctionDelta>,
+}
+
+#[derive(Debug, Deserialize)]
+struct StreamFunctionDelta {
+    #[serde(default)]
+    name: Option<String>,
+    #[serde(default)]
+    arguments: Option<String>,
+}
+
+#[derive(Debug, Default)]
+struct PendingStreamToolCall {
+    id: Option<String>,
+    started: bool,
+    ended: bool,
+    /// Argument fragments received before the function name.
+    buffered_arguments: String,
+}
+
+/// Translates OpenAI-compatible SSE chunks into typed stream events.
+#[derive(Debug, Default)]
+struct NativeStreamParser {
+    tool_calls: std::collections::BTreeMap<usize, PendingStreamToolCall>,
+}
+
+impl StreamParser for NativeStreamParser {
+    fn parse_line(&mut self, line: &str) -> StreamResult<Vec<StreamEvent>> {
+        let Some(data) = sse_data(line.trim()) else {
+            return Ok(Vec::new());
+        };
+        let chunk: StreamChunkResponse = serde_json::from_str(data).map_err(StreamError::Json)?;
+
+        let mut events = Vec::new();
+        if let Some(choice) = chunk.choices.into_iter().next() {
+            let delta = choice.delta;
+            if let Some(reasoning) = delta.reasoning_content.filter(|r| !r.is_empty()) {
+                events.push(StreamEvent::ReasoningDelta(reasoning));
+            }
+            if let Some(content) = delta.content.filter(|c| !c.is_empty()) {
+                events.push(StreamEvent::TextDelta(content));
+            }
+
+            for (position, call) in delta.tool_calls.unwrap_or_default().into_iter().enumerate() {
+                // Some providers omit `index` when each chunk carries whole calls.
+                let index = call.index.unwrap_or(position);
+                let pending = self.tool_calls.entry(index).or_default();
+                if pending.id.is_none() {
+                    pending.id = call.id.filter(|id| !id.is_empty());
+                }
+                let (name, arguments) = call
+                    .function
+                    .map(|function| (function.name, function.arguments))
+                    .unwrap_or_default();
+
+                if !pending.started {
+                    if let Some(name) = name.filter(|n| !n.is_empty()) {
+                        pending.started = true;
+                        events.push(StreamEvent::ToolCallStart {
+                            index,
+                            id: pending
+                                .id
+                                .clone()
+                                .unwrap_or_else(|| uuid::Uuid::new_v4().to_string()),
+                            name,
+                        });
+                        if !pending.buffered_arguments.is_empty() {
+                            events.push(StreamEvent::ToolCallDelta {
+                                index,
+                                arguments: std::mem::take(&mut pending.buffered_arguments),
+                            });
+                        }
+                    }
+                }
+                if let Some(arguments) = arguments.filter(|a| !a.is_empty()) {
+                    if pending.started {
+                        events.push(StreamEvent::ToolCallDelta { index, arguments });
+                    } else {
+                        pending.buffered_arguments.push_str(&arguments);
+                    }
+                }
+            }
+
+            if choice.finish_reason.is_some() {
+                events.extend(self.finish());
+            }
+        }
+
+        if let Some(usage) = chunk.usage {
+            events.push(StreamEvent::Usage(TokenUsage {
+                input_tokens: usage.prompt_tokens,
+                output_tokens: usage.completion_tokens,
+                cache_read_tokens: None,
+                cache_write_tokens: None,
+            }));
+        }
+        Ok(events)
+    }
+
+    fn finish(&mut self) -> Vec<StreamEvent> {
+        self.tool_calls
+            .iter_mut()
+            .filter(|(_, call)| call.started && !call.ended)
+            .map(|(index, call)| {
+                call.ended = true;
+                StreamEvent::ToolCallEnd { index: *index }
+            })
+            .collect()
+    }
 }
 
 /// Parse SSE (Server-Sent Events) stream from OpenAI-compatible providers.
@@ -683,7 +815,7 @@ fn sse_bytes_to_chunks(
 
     tokio::spawn(async move {
         // Buffer for incomplete lines
-        let mut buffer = String::new();
+        let mut buffer = LineBuffer::default();
 
         // Get response body as bytes stream
         match response.error_for_status_ref() {
@@ -699,27 +831,8 @@ fn sse_bytes_to_chunks(
         while let Some(item) = bytes_stream.next().await {
             match item {
                 Ok(bytes) => {
-                    // Convert bytes to string and process line by line
-                    let text = match String::from_utf8(bytes.to_vec()) {
-                        Ok(t) => t,
-                        Err(e) => {
-                            let _ = tx
-                                .send(Err(StreamError::InvalidSse(format!(
-                                    "Invalid UTF-8: {}",
-                                    e
-                                ))))
-                                .await;
-                            break;
-                        }
-                    };
-
-                    buffer.push_str(&text);
-
                     // Process complete lines
-                    while let Some(pos) = buffer.find('\n') {
-                        let line = buffer.drain(..=pos).collect::<String>();
-                        buffer = buffer[pos + 1..].to_string();
-
+                    for line in buffer.push(&bytes) {
                         match parse_sse_line(&line) {
                             Ok(Some(content)) => {
                                 let mut chunk = StreamChunk::delta(content);
@@ -1055,31 +1168,25 @@ impl OpenAiCompatibleProvider {
             .collect()
     }
 
-    fn with_prompt_guided_tool_instructions(
+    fn with_prompt_guided_instructions(
         messages: &[ChatMessage],
         tools: Option<&[crate::tools::ToolSpec]>,
+        response_format: Option<&ResponseFormat>,
     ) -> Vec<ChatMessage> {
-        let Some(tools) = tools else {
-            return messages.to_vec();
-        };
-
-        if tools.is_empty() {
-            return messages.to_vec();
+        let mut instructions = Vec::new();
+        if let Some(tools) = tools.filter(|tools| !tools.is_empty()) {
+            instructions.push(crate::providers::traits::build_tool_instructions_text(
+                tools,
+            ));
         }
-
-        let instructions = crate::providers::traits::build_tool_instructions_text(tools);
-        let mut modified_messages = messages.to_vec();
-
-        if let Some(system_message) = modified_messages.iter_mut().find(|m| m.role == "system") {
-            if !system_message.content.is_empty() {
-                system_message.content.push_str("\n\n");
-            }
-            system_message.content.push_str(&instructions);
-        } else {
-            modified_messages.insert(0, ChatMessage::system(instructions));
+        if let Some(format) = response_format {
+            instructions.push(format.instructions());
         }
 
-        modified_messages
+        if instructions.is_empty() {
+            return messages.to_vec();
+        }
+        crate::providers::traits::with_system_instructions(messages, &instructions.join("\n\n"))
     }
 
     fn parse_native_response(message: ResponseMessage) -> ProviderChatResponse {
@@ -1139,6 +1246,18 @@ impl OpenAiCompatibleProvider {
         .iter()
         .any(|hint| lower.contains(hint))
     }
+
+    fn is_response_format_unsupported(status: reqwest::StatusCode, error: &str) -> bool {
+        if !matches!(
+            status,
+            reqwest::StatusCode::BAD_REQUEST | reqwest::StatusCode::UNPROCESSABLE_ENTITY
+        ) {
+            return false;
+        }
+
+        let lower = error.to_lowercase();
+        lower.contains("response_format") || lower.contains("json_schema")
+    }
 }
 
 #[async_trait]
@@ -1468,6 +1587,8 @@ impl Provider for OpenAiCompatibleProvider {
         let usage = chat_response.usage.map(|u| TokenUsage {
             input_tokens: u.prompt_tokens,
             output_tokens: u.completion_tokens,
+            cache_read_tokens: None,
+            cache_write_tokens: None,
         });
         let choice = chat_response
             .choices
@@ -1531,7 +1652,19 @@ impl Provider for OpenAiCompatibleProvider {
             stream: Some(false),
             tool_choice: tools.as_ref().map(|_| "auto".to_string()),
             tools,
+            response_format: request.response_format.map(ResponseFormat::openai_payload),
+            generation: request
+                .params
+                .map(GenerationParams::openai_payload)
+                .unwrap_or_default(),
         };
+        // The Responses API fallback has no tool support; a requested
+        // response format travels as prompt instructions instead.
+        let responses_messages = Self::with_prompt_guided_instructions(
+            &effective_messages,
+            None,
+            request.response_format,
+        );
 
         let url = self.chat_completions_url();
         let response = match self
@@ -1547,7 +1680,7 @@ impl Provider for OpenAiCompatibleProvider {
                 if self.supports_responses_fallback {
                     let sanitized = super::sanitize_api_error(&chat_error.to_string());
                     return self
-                        .chat_via_responses(credential, &effective_messages, model)
+                        .chat_via_responses(credential, &responses_messages, model)
                         .await
                         .map(|text| ProviderChatResponse {
                             text: Some(text),
@@ -1572,9 +1705,15 @@ impl Provider for OpenAiCompatibleProvider {
             let error = response.text().await?;
             let sanitized = super::sanitize_api_error(&error);
 
-            if Self::is_native_tool_schema_unsupported(status, &sanitized) {
-                let fallback_messages =
-                    Self::with_prompt_guided_tool_instructions(request.messages, request.tools);
+            if Self::is_native_tool_schema_unsupported(status, &sanitized)
+                || (request.response_format.is_some()
+                    && Self::is_response_format_unsupported(status, &sanitized))
+            {
+                let fallback_messages = Self::with_prompt_guided_instructions(
+                    request.messages,
+                    request.tools,
+                    request.response_format,
+                );
                 let text = self
                     .chat_with_history(&fallback_messages, model, temperature)
                     .await?;
@@ -1588,7 +1727,7 @@ impl Provider for OpenAiCompatibleProvider {
 
             if status == reqwest::StatusCode::NOT_FOUND && self.supports_responses_fallback {
                 return self
-                    .chat_via_responses(credential, &effective_messages, model)
+                    .chat_via_responses(credential, &responses_messages, model)
                     .await
                     .map(|text| ProviderChatResponse {
                         text: Some(text),
@@ -1611,6 +1750,8 @@ impl Provider for OpenAiCompatibleProvider {
         let usage = native_response.usage.map(|u| TokenUsage {
             input_tokens: u.prompt_tokens,
             output_tokens: u.completion_tokens,
+            cache_read_tokens: None,
+            cache_write_tokens: None,
         });
         let message = native_response
             .choices
@@ -1736,6 +1877,56 @@ impl Provider for OpenAiCompatibleProvider {
         .boxed()
     }
 
+    fn supports_stream_events(&self) -> bool {
+        true
+    }
+
+    fn stream_chat(
+        &self,
+        request: ProviderChatRequest<'_>,
+        model: &str,
+        temperature: f64,
+    ) -> stream::BoxStream<'static, StreamResult<StreamEvent>> {
+        let Some(credential) = self.credential.as_ref() else {
+            return streaming::error_stream(StreamError::Provider(format!(
+                "{} API key not set",
+                self.name
+            )));
+        };
+
+        let tools = Self::convert_tool_specs(request.tools);
+        let effective_messages = if self.merge_system_into_user {
+            Self::flatten_system_messages(request.messages)
+        } else {
+            request.messages.to_vec()
+        };
+        let native_request = NativeChatRequest {
+            model: model.to_string(),
+            messages: Self::convert_messages_for_native(
+                &effective_messages,
+                !self.merge_system_into_user,
+            ),
+            temperature,
+            stream: Some(true),
+            tool_choice: tools.as_ref().map(|_| "auto".to_string()),
+            tools,
+            response_format: request.response_format.map(ResponseFormat::openai_payload),
+            generation: request
+                .params
+                .map(GenerationParams::openai_payload)
+                .unwrap_or_default(),
+        };
+
+        let url = self.chat_completions_url();
+        let req = self
+            .apply_auth_header(
+                self.http_client().post(&url).json(&native_request),
+                credential,
+            )
+            .header("Accept", "text/event-stream");
+        streaming::event_stream(&self.name, req, NativeStreamParser::default())
+    }
+
     async fn warmup(&self) -> anyhow::Result<()> {
         if let Some(credential) = self.credential.as_ref() {
             // Hit the chat completions URL with a GET to establish the connection pool.
@@ -2342,11 +2533,96 @@ mod tests {
         }];
 
         let output =
-            OpenAiCompatibleProvider::with_prompt_guided_tool_instructions(&input, Some(&tools));
+            OpenAiCompatibleProvider::with_prompt_guided_instructions(&input, Some(&tools), None);
         assert!(!output.is_empty());
         assert_eq!(output[0].role, "system");
         assert!(output[0].content.contains("Available Tools"));
         assert!(output[0].content.contains("shell_exec"));
+        assert!(!output[0].content.contains("Response Format"));
+    }
+
+    #[test]
+    fn prompt_guided_fallback_includes_response_format() {
+        let input = vec![ChatMessage::system("base"), ChatMessage::user("classify")];
+        let format = ResponseFormat::new(
+            "verdict",
+            serde_json::json!({"type": "object", "properties": {"ok": {"type": "boolean"}}}),
+        );
+
+        let output =
+            OpenAiCompatibleProvider::with_prompt_guided_instructions(&input, None, Some(&format));
+        assert_eq!(output.len(), 2);
+        assert!(output[0].content.starts_with("base\n\n## Response Format"));
+        assert!(output[0].content.contains("\"ok\""));
+
+        assert!(OpenAiCompatibleProvider::is_response_format_unsupported(
+            reqwest::StatusCode::BAD_REQUEST,
+            "Unknown parameter: 'response_format'"
+        ));
+        assert!(!OpenAiCompatibleProvider::is_response_format_unsupported(
+            reqwest::StatusCode::BAD_REQUEST,
+            "invalid api key"
+        ));
+    }
+
+    #[test]
+    fn native_request_serializes_response_format() {
+        let format =
+            ResponseFormat::new("verdict", serde_json::json!({"type": "object"})).with_strict(true);
+        let req = NativeChatRequest {
+            model: "m".to_string(),
+            messages: vec![],
+            temperature: 0.0,
+            stream: Some(false),
+            tools: None,
+            tool_choice: None,
+            response_format: Some(format.openai_payload()),
+            generation: serde_json::Map::new(),
+        };
+
+        let json = serde_json::to_value(&req).unwrap();
+        assert_eq!(json["response_format"]["type"], "json_schema");
+        assert_eq!(json["response_format"]["json_schema"]["name"], "verdict");
+        assert_eq!(json["response_format"]["json_schema"]["strict"], true);
+
+        let plain = NativeChatRequest {
+            response_format: None,
+            ..req
+        };
+        assert!(serde_json::to_value(&plain)
+            .unwrap()
+            .get("response_format")
+            .is_none());
+    }
+
+    #[test]
+    fn native_request_flattens_generation_params() {
+        let params = GenerationParams {
+            max_tokens: Some(64),
+            stop: vec!["END".into()],
+            seed: Some(7),
+            reasoning_effort: Some(crate::providers::ReasoningEffort::High),
+            thinking_budget: Some(2048),
+            ..GenerationParams::default()
+        };
+        let req = NativeChatRequest {
+            model: "m".to_string(),
+            messages: vec![],
+            temperature: 0.0,
+            stream: Some(false),
+            tools: None,
+            tool_choice: None,
+            response_format: None,
+            generation: params.openai_payload(),
+        };
+
+        let json = serde_json::to_value(&req).unwrap();
+        assert_eq!(json["max_tokens"], 64);
+        assert_eq!(json["stop"], serde_json::json!(["END"]));
+        assert_eq!(json["seed"], 7);
+        assert_eq!(json["reasoning_effort"], "high");
+        assert!(json.get("top_p").is_none());
+        assert!(json.get("thinking_budget").is_none());
     }
 
     #[tokio::test]
@@ -2807,6 +3083,69 @@ mod tests {
         assert_eq!(result, None);
     }
 
+    #[test]
+    fn native_stream_parser_emits_text_reasoning_and_tool_call_events() {
+        let mut parser = NativeStreamParser::default();
+        let mut events = Vec::new();
+        for line in [
+            r#"data: {"choices":[{"delta":{"reasoning_content":"Need a shell."}}]}"#,
+            r#"data: {"choices":[{"delta":{"content":"Checking."}}]}"#,
+            r#"data: {"choices":[{"delta":{"tool_calls":[{"index":0,"id":"call_1","type":"function","function":{"name":"shell","arguments":""}}]}}]}"#,
+            r#"data: {"choices":[{"delta":{"tool_calls":[{"index":0,"function":{"arguments":"{\"command\":"}}]}}]}"#,
+            r#"data: {"choices":[{"delta":{"tool_calls":[{"index":0,"function":{"arguments":"\"date\"}"}}]}}]}"#,
+            r#"data: {"choices":[{"delta":{},"finish_reason":"tool_calls"}]}"#,
+            r#"data: {"choices":[],"usage":{"prompt_tokens":20,"completion_tokens":9}}"#,
+            "data: [DONE]",
+        ] {
+            events.extend(parser.parse_line(line).unwrap());
+        }
+        events.extend(parser.finish());
+
+        assert_eq!(
+            events,
+            vec![
+                StreamEvent::ReasoningDelta("Need a shell.".into()),
+                StreamEvent::TextDelta("Checking.".into()),
+                StreamEvent::ToolCallStart {
+                    index: 0,
+                    id: "call_1".into(),
+                    name: "shell".into(),
+                },
+                StreamEvent::ToolCallDelta {
+                    index: 0,
+                    arguments: "{\"command\":".into(),
+                },
+                StreamEvent::ToolCallDelta {
+                    index: 0,
+                    arguments: "\"date\"}".into(),
+                },
+                StreamEvent::ToolCallEnd { index: 0 },
+                StreamEvent::Usage(TokenUsage {
+                    input_tokens: Some(20),
+                    output_tokens: Some(9),
+                    cache_read_tokens: None,
+                    cache_write_tokens: None,
+                }),
+            ]
+        );
+    }
+
+    #[test]
+    fn native_stream_parser_closes_open_tool_calls_at_end_of_body() {
+        let mut parser = NativeStreamParser::default();
+        let events = parser
+            .parse_line(
+                r#"data: {"choices":[{"delta":{"tool_calls":[{"function":{"name":"file_read","arguments":"{}"}}]}}]}"#,
+            )
+            .unwrap();
+        assert!(matches!(
+            &events[0],
+            StreamEvent::ToolCallStart { index: 0, name, .. } if name == "file_read"
+        ));
+        assert_eq!(parser.finish(), vec![StreamEvent::ToolCallEnd { index: 0 }]);
+        assert!(parser.finish().is_empty());
+    }
+
     #[test]
     fn api_response_parses_usage() {
         let json = r#"{
@@ -353,6 +353,8 @@ impl CopilotProvider {
         let usage = api_response.usage.map(|u| TokenUsage {
             input_tokens: u.prompt_tokens,
             output_tokens: u.completion_tokens,
+            cache_read_tokens: None,
+            cache_write_tokens: None,
         });
         let choice = api_response
             .choices
@@ -645,8 +647,17 @@ impl Provider for CopilotProvider {
         model: &str,
         temperature: f64,
     ) -> anyhow::Result<ProviderChatResponse> {
+        // The Copilot API has no structured output; the schema travels as
+        // system prompt instructions.
+        let messages = match request.response_format {
+            Some(format) => crate::providers::traits::with_system_instructions(
+                request.messages,
+                &format.instructions(),
+            ),
+            None => request.messages.to_vec(),
+        };
         self.send_chat_request(
-            Self::convert_messages(request.messages),
+            Self::convert_messages(&messages),
             request.tools,
             model,
             temperature,
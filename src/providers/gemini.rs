@@ -5,10 +5,15 @@
 //! - Google Cloud ADC (`GOOGLE_APPLICATION_CREDENTIALS`)
 
 use crate::auth::AuthService;
-use crate::providers::traits::{ChatMessage, ChatResponse, Provider, TokenUsage};
+use crate::providers::streaming::{self, sse_data, StreamParser};
+use crate::providers::traits::{
+    ChatMessage, ChatResponse, GenerationParams, Provider, ResponseFormat, StreamError,
+    StreamEvent, StreamResult, TokenUsage,
+};
 use async_trait::async_trait;
 use base64::Engine;
 use directories::UserDirs;
+use futures_util::stream;
 use reqwest::Client;
 use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
@@ -144,6 +149,196 @@ struct GenerationConfig {
     temperature: f64,
     #[serde(rename = "maxOutputTokens")]
     max_output_tokens: u32,
+    #[serde(rename = "responseMimeType", skip_serializing_if = "Option::is_none")]
+    response_mime_type: Option<String>,
+    #[serde(rename = "responseSchema", skip_serializing_if = "Option::is_none")]
+    response_schema: Option<serde_json::Value>,
+    #[serde(rename = "topP", skip_serializing_if = "Option::is_none")]
+    top_p: Option<f64>,
+    #[serde(rename = "stopSequences", skip_serializing_if = "Vec::is_empty")]
+    stop_sequences: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    seed: Option<u64>,
+    #[serde(rename = "presencePenalty", skip_serializing_if = "Option::is_none")]
+    presence_penalty: Option<f64>,
+    #[serde(rename = "frequencyPenalty", skip_serializing_if = "Option::is_none")]
+    frequency_penalty: Option<f64>,
+    #[serde(rename = "thinkingConfig", skip_serializing_if = "Option::is_none")]
+    thinking_config: Option<ThinkingConfig>,
+}
+
+#[derive(Debug, Serialize, Clone)]
+struct ThinkingConfig {
+    #[serde(rename = "thinkingBudget")]
+    thinking_budget: u32,
+}
+
+/// Output token limit when the caller sets none.
+const DEFAULT_MAX_OUTPUT_TOKENS: u32 = 8192;
+
+impl GenerationConfig {
+    fn new(
+        temperature: f64,
+        response_format: Option<&ResponseFormat>,
+        params: Option<&GenerationParams>,
+    ) -> Self {
+        let params = params.cloned().unwrap_or_default();
+        Self {
+            temperature,
+            max_output_tokens: params.max_tokens.unwrap_or(DEFAULT_MAX_OUTPUT_TOKENS),
+            response_mime_type: response_format.map(|_| "application/json".to_string()),
+            response_schema: response_format.map(|format| gemini_response_schema(&format.schema)),
+            top_p: params.top_p,
+            stop_sequences: params.stop.clone(),
+            seed: params.seed,
+            presence_penalty: params.presence_penalty,
+            frequency_penalty: params.frequency_penalty,
+            thinking_config: params
+                .thinking_budget_tokens()
+                .map(|thinking_budget| ThinkingConfig { thinking_budget }),
+        }
+    }
+}
+
+/// Schema keywords Gemini's `responseSchema` (an OpenAPI subset) accepts
+/// verbatim.
+const GEMINI_SCHEMA_KEYWORDS: &[&str] = &[
+    "format",
+    "description",
+    "nullable",
+    "required",
+    "minItems",
+    "maxItems",
+    "minimum",
+    "maximum",
+    "minLength",
+    "maxLength",
+    "pattern",
+    "propertyOrdering",
+];
+
+/// Maximum `$ref` nesting inlined into a Gemini response schema.
+const MAX_SCHEMA_REF_DEPTH: usize = 16;
+
+/// Convert a JSON Schema into Gemini's `responseSchema` dialect.
+///
+/// `$ref`s are inlined from `$defs`/`definitions`, `["T", "null"]` type
+/// unions and null variants become `nullable`, `oneOf` becomes `anyOf`,
+/// `const` becomes a one-value `enum`, and unsupported keywords are dropped.
+fn gemini_response_schema(schema: &serde_json::Value) -> serde_json::Value {
+    let defs = schema
+        .get("$defs")
+        .or_else(|| schema.get("definitions"))
+        .cloned()
+        .unwrap_or(serde_json::Value::Null);
+    sanitize_gemini_schema(schema, &defs, 0)
+}
+
+fn sanitize_gemini_schema(
+    node: &serde_json::Value,
+    defs: &serde_json::Value,
+    depth: usize,
+) -> serde_json::Value {
+    use serde_json::{json, Map, Value};
+
+    let Value::Object(map) = node else {
+        return node.clone();
+    };
+    if let Some(reference) = map.get("$ref").and_then(Value::as_str) {
+        let name = reference.rsplit('/').next().unwrap_or_default();
+        return match defs.get(name) {
+            Some(target) if depth < MAX_SCHEMA_REF_DEPTH => {
+                sanitize_gemini_schema(target, defs, depth + 1)
+            }
+            _ => json!({"type": "object"}),
+        };
+    }
+
+    let mut out = Map::new();
+    for (key, value) in map {
+        match key.as_str() {
+            "type" => match value {
+                Value::Array(types) => {
+                    if types.iter().any(|t| t == "null") {
+                        out.insert("nullable".into(), Value::Bool(true));
+                    }
+                    if let Some(first) = types.iter().find(|t| *t != "null") {
+                        out.insert("type".into(), first.clone());
+                    }
+                }
+                other => {
+                    out.insert("type".into(), other.clone());
+                }
+            },
+            "properties" => {
+                if let Value::Object(properties) = value {
+                    let properties = properties
+                        .iter()
+                        .map(|(name, prop)| {
+                            (name.clone(), sanitize_gemini_schema(prop, defs, depth))
+                        })
+                        .collect();
+                    out.insert("properties".into(), Value::Object(properties));
+                }
+            }
+            "items" => {
+                out.insert("items".into(), sanitize_gemini_schema(value, defs, depth));
+            }
+            "anyOf" | "oneOf" => {
+                let Value::Array(variants) = value else {
+                    continue;
+                };
+                let mut kept = Vec::new();
+                for variant in variants {
+                    if variant.get("type").and_then(Value::as_str) == Some("null") {
+                        out.insert("nullable".into(), Value::Bool(true));
+                    } else {
+                        kept.push(sanitize_gemini_schema(variant, defs, depth));
+                    }
+                }
+                // Unit enums with documented variants arrive as `oneOf` of consts.
+                let consts: Option<Vec<Value>> = kept
+                    .iter()
+                    .map(|v| match v.get("enum").and_then(Value::as_array) {
+                        Some(values) if values.len() == 1 => Some(values[0].clone()),
+                        _ => None,
+                    })
+                    .collect();
+                if let Some(values) = consts.filter(|values| values.len() > 1) {
+                    out.insert("type".into(), Value::String("string".into()));
+                    out.insert("enum".into(), Value::Array(values));
+                } else if kept.len() == 1 {
+                    if let Some(Value::Object(only)) = kept.pop() {
+                        for (k, v) in only {
+                            out.entry(k).or_insert(v);
+                        }
+                    }
+                } else if !kept.is_empty() {
+                    out.insert("anyOf".into(), Value::Array(kept));
+                }
+            }
+            "const" => {
+                out.insert("enum".into(), Value::Array(vec![value.clone()]));
+            }
+            "enum" => {
+                if let Value::Array(values) = value {
+                    if values.iter().any(Value::is_null) {
+                        out.insert("nullable".into(), Value::Bool(true));
+                    }
+                    let values = values.iter().filter(|v| !v.is_null()).cloned().collect();
+                    out.insert("enum".into(), Value::Array(values));
+                }
+            }
+            other if GEMINI_SCHEMA_KEYWORDS.contains(&other) => {
+                out.insert(key.clone(), value.clone());
+            }
+            _ => {}
+        }
+    }
+    if out.contains_key("enum") && !out.contains_key("type") {
+        out.insert("type".into(), Value::String("string".into()));
+    }
+    Value::Object(out)
 }
 
 #[derive(Debug, Deserialize)]
@@ -244,6 +439,57 @@ impl GenerateContentResponse {
     }
 }
 
+/// Translates `streamGenerateContent?alt=sse` chunks into typed stream events.
+///
+/// Each chunk is a partial `GenerateContentResponse`; thought parts stream as
+/// reasoning and usage metadata is cumulative.
+#[derive(Debug, Default)]
+struct GeminiStreamParser;
+
+impl StreamParser for GeminiStreamParser {
+    fn parse_line(&mut self, line: &str) -> StreamResult<Vec<StreamEvent>> {
+        let Some(data) = sse_data(line.trim()) else {
+            return Ok(Vec::new());
+        };
+        let chunk: GenerateContentResponse =
+            serde_json::from_str(data).map_err(StreamError::Json)?;
+        let chunk = chunk.into_effective_response();
+        if let Some(err) = chunk.error {
+            return Err(StreamError::Provider(format!(
+                "Gemini API error: {}",
+                err.message
+            )));
+        }
+
+        let parts = chunk
+            .candidates
+            .and_then(|c| c.into_iter().next())
+            .and_then(|c| c.content)
+            .map(|c| c.parts)
+            .unwrap_or_default();
+        let mut events: Vec<StreamEvent> = parts
+            .into_iter()
+            .filter_map(|part| {
+                let text = part.text.filter(|t| !t.is_empty())?;
+                Some(if part.thought {
+                    StreamEvent::ReasoningDelta(text)
+                } else {
+                    StreamEvent::TextDelta(text)
+                })
+            })
+            .collect();
+        if let Some(usage) = chunk.usage_metadata {
+            events.push(StreamEvent::Usage(TokenUsage {
+                input_tokens: usage.prompt_token_count,
+                output_tokens: usage.candidates_token_count,
+                cache_read_tokens: None,
+                cache_write_tokens: None,
+            }));
+        }
+        Ok(events)
+    }
+}
+
 // ══════════════════════════════════════════════════════════════════════════════
 // GEMINI CLI TOKEN STRUCTURES
 // ══════════════════════════════════════════════════════════════════════════════
@@ -795,10 +1041,57 @@ impl GeminiProvider {
         }
     }
 
+    /// Build the streaming URL for API-key auth; `alt=sse` selects
+    /// server-sent events.
+    fn build_stream_generate_content_url(model: &str, api_key: &str) -> String {
+        format!(
+            "{PUBLIC_API_ENDPOINT}/{}:streamGenerateContent?alt=sse&key={api_key}",
+            Self::format_model_name(model)
+        )
+    }
+
     fn http_client(&self) -> Client {
         crate::config::build_runtime_proxy_client_with_timeouts("provider.gemini", 120, 10)
     }
 
+    /// Split a conversation into Gemini contents and the system instruction.
+    fn build_contents(messages: &[ChatMessage]) -> (Vec<Content>, Option<Content>) {
+        let mut system_parts: Vec<&str> = Vec::new();
+        let mut contents: Vec<Content> = Vec::new();
+
+        for msg in messages {
+            match msg.role.as_str() {
+                "system" => system_parts.push(&msg.content),
+                "user" => contents.push(Content {
+                    role: Some("user".to_string()),
+                    parts: vec![Part {
+                        text: msg.content.clone(),
+                    }],
+                }),
+                "assistant" => contents.push(Content {
+                    role: Some("model".to_string()),
+                    parts: vec![Part {
+                        text: msg.content.clone(),
+                    }],
+                }),
+                _ => {}
+            }
+        }
+
+        let system_instruction = if system_parts.is_empty() {
+            None
+        } else {
+            Some(Content {
+                role: None,
+                parts: vec![Part {
+                    text: system_parts.join("\n\n"),
+                }],
+            })
+        };
+
+        (contents, system_instruction)
+    }
+
     /// Resolve the GCP project ID for OAuth by calling the loadCodeAssist endpoint.
     /// Caches the result for subsequent calls.
     async fn resolve_oauth_project(&self, token: &str) -> anyhow::Result<String> {
@@ -936,7 +1229,7 @@ impl GeminiProvider {
         contents: Vec<Content>,
         system_instruction: Option<Content>,
         model: &str,
-        temperature: f64,
+        generation_config: GenerationConfig,
     ) -> anyhow::Result<(String, Option<TokenUsage>)> {
         let auth = self.auth.as_ref().ok_or_else(|| {
             anyhow::anyhow!(
@@ -983,10 +1276,7 @@ impl GeminiProvider {
         let request = GenerateContentRequest {
             contents,
             system_instruction,
-            generation_config: GenerationConfig {
-                temperature,
-                max_output_tokens: 8192,
-            },
+            generation_config,
         };
 
         let url = Self::build_generate_content_url(model, auth);
@@ -1128,6 +1418,8 @@ impl GeminiProvider {
         let usage = result.usage_metadata.map(|u| TokenUsage {
             input_tokens: u.prompt_token_count,
             output_tokens: u.candidates_token_count,
+            cache_read_tokens: None,
+            cache_write_tokens: None,
         });
 
         let text = result
@@ -1165,7 +1457,12 @@ impl Provider for GeminiProvider {
         }];
 
         let (text, _usage) = self
-            .send_generate_content(contents, system_instruction, model, temperature)
+            .send_generate_content(
+                contents,
+                system_instruction,
+                model,
+                GenerationConfig::new(temperature, None, None),
+            )
             .await?;
         Ok(text)
     }
@@ -1217,7 +1514,12 @@ impl Provider for GeminiProvider {
         };
 
         let (text, _usage) = self
-            .send_generate_content(contents, system_instruction, model, temperature)
+            .send_generate_content(
+                contents,
+                system_instruction,
+                model,
+                GenerationConfig::new(temperature, None, None),
+            )
             .await?;
         Ok(text)
     }
@@ -1228,41 +1530,15 @@ impl Provider for GeminiProvider {
         model: &str,
         temperature: f64,
     ) -> anyhow::Result<ChatResponse> {
-        let mut system_parts: Vec<&str> = Vec::new();
-        let mut contents: Vec<Content> = Vec::new();
-
-        for msg in request.messages {
-            match msg.role.as_str() {
-                "system" => system_parts.push(&msg.content),
-                "user" => contents.push(Content {
-                    role: Some("user".to_string()),
-                    parts: vec![Part {
-                        text: msg.content.clone(),
-                    }],
-                }),
-                "assistant" => contents.push(Content {
-                    role: Some("model".to_string()),
-                    parts: vec![Part {
-                        text: msg.content.clone(),
-                    }],
-                }),
-                _ => {}
-            }
-        }
-
-        let system_instruction = if system_parts.is_empty() {
-            None
-        } else {
-            Some(Content {
-                role: None,
-                parts: vec![Part {
-                    text: system_parts.join("\n\n"),
-                }],
-            })
-        };
+        let (contents, system_instruction) = Self::build_contents(request.messages);
 
         let (text, usage) = self
-            .send_generate_content(contents, system_instruction, model, temperature)
+            .send_generate_content(
+                contents,
+                system_instruction,
+                model,
+                GenerationConfig::new(temperature, request.response_format, request.params),
+            )
             .await?;
 
         Ok(ChatResponse {
@@ -1273,6 +1549,40 @@ impl Provider for GeminiProvider {
         })
     }
 
+    fn supports_stream_events(&self) -> bool {
+        // OAuth requests need async token and project resolution per call;
+        // only API-key auth streams.
+        self.auth.as_ref().is_some_and(GeminiAuth::is_api_key)
+    }
+
+    fn stream_chat(
+        &self,
+        request: crate::providers::traits::ChatRequest<'_>,
+        model: &str,
+        temperature: f64,
+    ) -> stream::BoxStream<'static, StreamResult<StreamEvent>> {
+        let Some(auth) = self.auth.as_ref().filter(|auth| auth.is_api_key()) else {
+            return streaming::error_stream(StreamError::Provider(
+                "Gemini streaming requires API key auth".to_string(),
+            ));
+        };
+
+        let (contents, system_instruction) = Self::build_contents(request.messages);
+        let body = GenerateContentRequest {
+            contents,
+            system_instruction,
+            generation_config: GenerationConfig::new(
+                temperature,
+                request.response_format,
+                request.params,
+            ),
+        };
+
+        let url = Self::build_stream_generate_content_url(model, auth.api_key_credential());
+        let req = self.http_client().post(url).json(&body);
+        streaming::event_stream("Gemini", req, GeminiStreamParser)
+    }
+
     async fn warmup(&self) -> anyhow::Result<()> {
         if let Some(auth) = self.auth.as_ref() {
             match auth {
@@ -1534,6 +1844,54 @@ mod tests {
         assert!(url.contains("models/gemini-2.0-flash"));
     }
 
+    #[test]
+    fn stream_url_requests_sse_on_public_endpoint() {
+        let url = GeminiProvider::build_stream_generate_content_url("gemini-2.0-flash", "k-123");
+        assert_eq!(
+            url,
+            format!(
+                "{PUBLIC_API_ENDPOINT}/models/gemini-2.0-flash:streamGenerateContent?alt=sse&key=k-123"
+            )
+        );
+    }
+
+    #[test]
+    fn streaming_is_limited_to_api_key_auth() {
+        let api_key = test_provider(Some(GeminiAuth::ExplicitKey("k".into())));
+        assert!(api_key.supports_stream_events());
+
+        let oauth = test_provider(Some(test_oauth_auth("ya29.token")));
+        assert!(!oauth.supports_stream_events());
+    }
+
+    #[test]
+    fn stream_parser_splits_thoughts_text_and_usage() {
+        let mut parser = GeminiStreamParser;
+        let events = parser
+            .parse_line(
+                r#"data: {"candidates":[{"content":{"role":"model","parts":[{"text":"Plan the answer.","thought":true},{"text":"Hello"}]}}],"usageMetadata":{"promptTokenCount":8,"candidatesTokenCount":3}}"#,
+            )
+            .unwrap();
+        assert_eq!(
+            events,
+            vec![
+                StreamEvent::ReasoningDelta("Plan the answer.".into()),
+                StreamEvent::TextDelta("Hello".into()),
+                StreamEvent::Usage(TokenUsage {
+                    input_tokens: Some(8),
+                    output_tokens: Some(3),
+                    cache_read_tokens: None,
+                    cache_write_tokens: None,
+                }),
+            ]
+        );
+
+        let err = parser
+            .parse_line(r#"data: {"error":{"message":"quota exceeded"}}"#)
+            .unwrap_err();
+        assert!(err.to_string().contains("quota exceeded"));
+    }
+
     #[test]
     fn oauth_request_uses_bearer_auth_header() {
         let provider = test_provider(Some(test_oauth_auth("ya29.mock-token")));
@@ -1547,10 +1905,7 @@ mod tests {
                 }],
             }],
             system_instruction: None,
-            generation_config: GenerationConfig {
-                temperature: 0.7,
-                max_output_tokens: 8192,
-            },
+            generation_config: GenerationConfig::new(0.7, None, None),
         };
 
         let request = provider
@@ -1588,10 +1943,7 @@ mod tests {
                 }],
             }],
             system_instruction: None,
-            generation_config: GenerationConfig {
-                temperature: 0.7,
-                max_output_tokens: 8192,
-            },
+            generation_config: GenerationConfig::new(0.7, None, None),
         };
 
         let request = provider
@@ -1632,10 +1984,7 @@ mod tests {
                 }],
             }],
             system_instruction: None,
-            generation_config: GenerationConfig {
-                temperature: 0.7,
-                max_output_tokens: 8192,
-            },
+            generation_config: GenerationConfig::new(0.7, None, None),
         };
 
         let request = provider
@@ -1654,6 +2003,82 @@ mod tests {
         assert!(request.headers().get(AUTHORIZATION).is_none());
     }
 
+    #[test]
+    fn response_schema_converts_json_schema_to_gemini_dialect() {
+        let schema = serde_json::json!({
+            "$schema": "https://json-schema.org/draft/2020-12/schema",
+            "title": "Outcome",
+            "type": "object",
+            "additionalProperties": false,
+            "properties": {
+                "status": { "$ref": "#/$defs/Status" },
+                "note": { "type": ["string", "null"], "description": "Optional note" },
+                "tags": { "type": "array", "items": { "type": "string" } },
+                "detail": { "anyOf": [{ "$ref": "#/$defs/Detail" }, { "type": "null" }] }
+            },
+            "required": ["status"],
+            "$defs": {
+                "Status": { "oneOf": [
+                    { "const": "completed", "type": "string" },
+                    { "const": "failed", "type": "string" }
+                ]},
+                "Detail": { "type": "object", "properties": { "code": { "type": "integer" } } }
+            }
+        });
+
+        let converted = gemini_response_schema(&schema);
+        assert!(converted.get("$schema").is_none());
+        assert!(converted.get("$defs").is_none());
+        assert!(converted.get("title").is_none());
+        assert!(converted.get("additionalProperties").is_none());
+        assert_eq!(converted["required"], serde_json::json!(["status"]));
+
+        let props = &converted["properties"];
+        assert_eq!(props["status"]["type"], "string");
+        assert_eq!(
+            props["status"]["enum"],
+            serde_json::json!(["completed", "failed"])
+        );
+        assert_eq!(props["note"]["type"], "string");
+        assert_eq!(props["note"]["nullable"], true);
+        assert_eq!(props["tags"]["items"]["type"], "string");
+        assert_eq!(props["detail"]["type"], "object");
+        assert_eq!(props["detail"]["nullable"], true);
+        assert_eq!(props["detail"]["properties"]["code"]["type"], "integer");
+    }
+
+    #[test]
+    fn generation_config_serializes_response_schema() {
+        let format = ResponseFormat::new("verdict", serde_json::json!({"type": "object"}));
+        let config = GenerationConfig::new(0.0, Some(&format), None);
+        let json = serde_json::to_value(&config).unwrap();
+        assert_eq!(json["responseMimeType"], "application/json");
+        assert_eq!(json["responseSchema"]["type"], "object");
+        assert_eq!(json["maxOutputTokens"], DEFAULT_MAX_OUTPUT_TOKENS);
+        assert!(json.get("stopSequences").is_none());
+        assert!(json.get("thinkingConfig").is_none());
+    }
+
+    #[test]
+    fn generation_config_maps_generation_params() {
+        let params = GenerationParams {
+            max_tokens: Some(512),
+            top_p: Some(0.8),
+            stop: vec!["END".into()],
+            seed: Some(42),
+            frequency_penalty: Some(0.5),
+            reasoning_effort: Some(crate::providers::ReasoningEffort::Low),
+            ..GenerationParams::default()
+        };
+        let json = serde_json::to_value(GenerationConfig::new(0.3, None, Some(&params))).unwrap();
+        assert_eq!(json["maxOutputTokens"], 512);
+        assert_eq!(json["topP"], 0.8);
+        assert_eq!(json["stopSequences"], serde_json::json!(["END"]));
+        assert_eq!(json["seed"], 42);
+        assert_eq!(json["frequencyPenalty"], 0.5);
+        assert_eq!(json["thinkingConfig"]["thinkingBudget"], 1024);
+    }
+
     #[test]
     fn request_serialization() {
         let request = GenerateContentRequest {
@@ -1669,10 +2094,7 @@ mod tests {
                     text: "You are helpful".to_string(),
                 }],
             }),
-            generation_config: GenerationConfig {
-                temperature: 0.7,
-                max_output_tokens: 8192,
-            },
+            generation_config: GenerationConfig::new(0.7, None, None),
         };
 
         let json = serde_json::to_string(&request).unwrap();
@@ -1698,10 +2120,7 @@ mod tests {
                     }],
                 }],
                 system_instruction: None,
-                generation_config: Some(GenerationConfig {
-                    temperature: 0.7,
-                    max_output_tokens: 8192,
-                }),
+                generation_config: Some(GenerationConfig::new(0.7, None, None)),
             },
         };
 
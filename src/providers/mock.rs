@@ -0,0 +1,894 @@
+//! Scripted provider for offline end-to-end runs.
+//!
+//! Select it with `default_provider = "mock:<script path>"` (or `"mock"` with
+//! `api_url` pointing at the script). The script is a JSON or TOML file (or
+//! YAML with the `mock-yaml` feature) with an ordered list of steps; each
+//! request consumes the next step, checks the step's expectations and answers
+//! with its canned response. Requests can
+//! be appended to a JSONL transcript so CI can assert on the tool calls made.
+
+use super::traits::{
+    ChatMessage, ChatRequest, ChatResponse, Provider, ProviderCapabilities, StreamChunk,
+    StreamError, StreamEvent, StreamOptions, StreamResult, TokenUsage, ToolCall,
+};
+use anyhow::Context;
+use async_trait::async_trait;
+use futures_util::{stream, StreamExt};
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue};
+use reqwest::StatusCode;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::io::Write as _;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::Duration;
+
+/// A mock script: ordered steps plus provider capabilities.
+#[derive(Debug, Clone, Deserialize)]
+pub struct MockScript {
+    /// Answer tool calls natively. When false the agent uses XML tool calling.
+    #[serde(default = "default_true")]
+    pub native_tools: bool,
+    /// Accept image markers in user messages.
+    #[serde(default)]
+    pub vision: bool,
+    /// Support streaming (text chunks and typed events).
+    #[serde(default = "default_true")]
+    pub streaming: bool,
+    /// JSONL file that receives one record per request.
+    #[serde(default)]
+    pub transcript: Option<PathBuf>,
+    /// Steps answered in order.
+    pub steps: Vec<MockStep>,
+}
+
+fn default_true() -> bool {
+    true
+}
+
+/// One expected request and its canned response.
+#[derive(Debug, Clone, Deserialize)]
+pub struct MockStep {
+    #[serde(default)]
+    pub expect: MockExpectation,
+    /// Wait this long before answering.
+    #[serde(default)]
+    pub delay_ms: u64,
+    pub respond: MockResponse,
+}
+
+/// Checks applied to the request a step answers. Unset fields match anything.
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct MockExpectation {
+    /// Exact model name.
+    #[serde(default)]
+    pub model: Option<String>,
+    /// Role of the last message (`user`, `tool`, `assistant`).
+    #[serde(default)]
+    pub last_role: Option<String>,
+    /// Substring of the last message.
+    #[serde(default)]
+    pub last_message_contains: Option<String>,
+    /// Substring of the system prompt.
+    #[serde(default)]
+    pub system_contains: Option<String>,
+    /// Tool names that must be offered natively.
+    #[serde(default)]
+    pub tools_include: Vec<String>,
+}
+
+/// Canned response for a step.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum MockResponse {
+    /// Plain assistant text, optionally with reasoning.
+    Text {
+        text: String,
+        #[serde(default)]
+        reasoning: Option<String>,
+        #[serde(default)]
+        usage: Option<MockUsage>,
+    },
+    /// Native tool calls.
+    ToolCalls {
+        #[serde(default)]
+        text: Option<String>,
+        calls: Vec<MockToolCall>,
+        #[serde(default)]
+        usage: Option<MockUsage>,
+    },
+    /// Tool calls rendered as `<tool_call>` tags in the reply text.
+    XmlToolCalls {
+        #[serde(default)]
+        text: Option<String>,
+        calls: Vec<MockToolCall>,
+    },
+    /// Provider error with an HTTP status (default 500).
+    Error {
+        message: String,
+        #[serde(default)]
+        status: Option<u16>,
+    },
+    /// HTTP 429 with rate-limit headers.
+    RateLimit {
+        #[serde(default = "default_rate_limit_message")]
+        message: String,
+        #[serde(default)]
+        retry_after_secs: Option<u64>,
+        #[serde(default)]
+        headers: BTreeMap<String, String>,
+    },
+}
+
+fn default_rate_limit_message() -> String {
+    "rate limit exceeded".to_string()
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MockToolCall {
+    #[serde(default)]
+    pub id: Option<String>,
+    pub name: String,
+    #[serde(default)]
+    pub arguments: serde_json::Value,
+}
+
+#[derive(Debug, Clone, Copy, Deserialize)]
+pub struct MockUsage {
+    #[serde(default)]
+    pub input_tokens: Option<u64>,
+    #[serde(default)]
+    pub output_tokens: Option<u64>,
+}
+
+impl From<MockUsage> for TokenUsage {
+    fn from(usage: MockUsage) -> Self {
+        Self {
+            input_tokens: usage.input_tokens,
+            output_tokens: usage.output_tokens,
+            cache_read_tokens: None,
+            cache_write_tokens: None,
+        }
+    }
+}
+
+/// HTTP-style error returned by scripted `error` and `rate_limit` steps.
+///
+/// Callers can downcast to read the status and headers, e.g. to feed rate
+/// limit headers to a quota extractor.
+#[derive(Debug, thiserror::Error)]
+#[error("Mock API error ({status}): {message}{}", retry_after_suffix(.headers))]
+pub struct MockApiError {
+    pub status: StatusCode,
+    pub headers: HeaderMap,
+    pub message: String,
+}
+
+fn retry_after_suffix(headers: &HeaderMap) -> String {
+    headers
+        .get("retry-after")
+        .and_then(|value| value.to_str().ok())
+        .map(|value| format!(" (retry-after: {value})"))
+        .unwrap_or_default()
+}
+
+/// A request the mock provider received.
+#[derive(Debug, Clone, Serialize)]
+pub struct MockRequest {
+    /// Index of the step that answered it.
+    pub step: usize,
+    pub model: String,
+    pub temperature: f64,
+    pub stream: bool,
+    pub messages: Vec<ChatMessage>,
+    /// Names of tools offered natively.
+    pub tools: Vec<String>,
+    /// Names of tool calls in the scripted response.
+    pub tool_calls: Vec<String>,
+}
+
+#[derive(Debug, Default)]
+struct MockState {
+    cursor: usize,
+    requests: Vec<MockRequest>,
+}
+
+pub struct MockProvider {
+    script: MockScript,
+    state: Mutex<MockState>,
+}
+
+impl MockScript {
+    /// Load a script, parsed as TOML for `.toml` files, YAML for `.yaml` and
+    /// `.yml` files (with the `mock-yaml` feature), and JSON otherwise.
+    pub fn load(path: &Path) -> anyhow::Result<Self> {
+        let raw = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read mock script {}", path.display()))?;
+        let extension = path
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .map(str::to_ascii_lowercase);
+        let parsed = match extension.as_deref() {
+            Some("toml") => toml::from_str(&raw).map_err(anyhow::Error::from),
+            #[cfg(feature = "mock-yaml")]
+            Some("yaml" | "yml") => serde_yaml::from_str(&raw).map_err(anyhow::Error::from),
+            #[cfg(not(feature = "mock-yaml"))]
+            Some("yaml" | "yml") => Err(anyhow::anyhow!(
+                "YAML mock scripts need the `mock-yaml` feature; use JSON or TOML"
+            )),
+            _ => serde_json::from_str(&raw).map_err(anyhow::Error::from),
+        };
+        parsed.with_context(|| format!("Invalid mock script {}", path.display()))
+    }
+}
+
+impl MockProvider {
+    pub fn new(script: MockScript) -> Self {
+        Self {
+            script,
+            state: Mutex::new(MockState::default()),
+        }
+    }
+
+    pub fn from_path(path: &Path) -> anyhow::Result<Self> {
+        MockScript::load(path).map(Self::new)
+    }
+
+    /// Requests received so far.
+    pub fn requests(&self) -> Vec<MockRequest> {
+        self.lock_state().requests.clone()
+    }
+
+    /// Steps not yet consumed.
+    pub fn remaining_steps(&self) -> usize {
+        self.script
+            .steps
+            .len()
+            .saturating_sub(self.lock_state().cursor)
+    }
+
+    fn lock_state(&self) -> std::sync::MutexGuard<'_, MockState> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Consume the next step for a request and return its delay and response.
+    fn next_step(
+        &self,
+        messages: &[ChatMessage],
+        tools: Vec<String>,
+        model: &str,
+        temperature: f64,
+        stream: bool,
+    ) -> anyhow::Result<(Duration, MockResponse)> {
+        let mut state = self.lock_state();
+        let index = state.cursor;
+        let Some(step) = self.script.steps.get(index) else {
+            anyhow::bail!(
+                "Mock script exhausted: request #{} has no step ({} scripted)",
+                index + 1,
+                self.script.steps.len()
+            );
+        };
+        check_expectation(&step.expect, messages, &tools, model)
+            .map_err(|reason| anyhow::anyhow!("Mock script step {index}: {reason}"))?;
+
+        let tool_calls = match &step.respond {
+            MockResponse::ToolCalls { calls, .. } | MockResponse::XmlToolCalls { calls, .. } => {
+                calls.iter().map(|call| call.name.clone()).collect()
+            }
+            _ => Vec::new(),
+        };
+        let request = MockRequest {
+            step: index,
+            model: model.to_string(),
+            temperature,
+            stream,
+            messages: messages.to_vec(),
+            tools,
+            tool_calls,
+        };
+        if let Some(path) = &self.script.transcript {
+            append_transcript(path, &request)?;
+        }
+        state.requests.push(request);
+        state.cursor += 1;
+
+        Ok((Duration::from_millis(step.delay_ms), step.respond.clone()))
+    }
+}
+
+fn check_expectation(
+    expect: &MockExpectation,
+    messages: &[ChatMessage],
+    tools: &[String],
+    model: &str,
+) -> Result<(), String> {
+    if let Some(expected) = &expect.model {
+        if expected != model {
+            return Err(format!("expected model `{expected}`, got `{model}`"));
+        }
+    }
+    let last = messages.last();
+    if let Some(role) = &expect.last_role {
+        let actual = last.map_or("<none>", |m| m.role.as_str());
+        if role != actual {
+            return Err(format!(
+                "expected last message role `{role}`, got `{actual}`"
+            ));
+        }
+    }
+    if let Some(needle) = &expect.last_message_contains {
+        if !last.is_some_and(|m| m.content.contains(needle.as_str())) {
+            return Err(format!("last message does not contain `{needle}`"));
+        }
+    }
+    if let Some(needle) = &expect.system_contains {
+        let system = messages.iter().find(|m| m.role == "system");
+        if !system.is_some_and(|m| m.content.contains(needle.as_str())) {
+            return Err(format!("system prompt does not contain `{needle}`"));
+        }
+    }
+    if let Some(missing) = expect
+        .tools_include
+        .iter()
+        .find(|name| !tools.contains(name))
+    {
+        return Err(format!("tool `{missing}` was not offered"));
+    }
+    Ok(())
+}
+
+fn append_transcript(path: &Path, request: &MockRequest) -> anyhow::Result<()> {
+    let mut file = std::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .with_context(|| format!("Failed to open mock transcript {}", path.display()))?;
+    writeln!(file, "{}", serde_json::to_string(request)?)?;
+    Ok(())
+}
+
+fn tool_call_id(step_call: &MockToolCall, index: usize) -> String {
+    step_call
+        .id
+        .clone()
+        .unwrap_or_else(|| format!("call_mock_{index}"))
+}
+
+fn arguments_json(arguments: &serde_json::Value) -> String {
+    if arguments.is_null() {
+        "{}".to_string()
+    } else {
+        arguments.to_string()
+    }
+}
+
+fn render_xml_tool_calls(text: Option<&str>, calls: &[MockToolCall]) -> String {
+    let mut out = text.map(str::to_string).unwrap_or_default();
+    for call in calls {
+        if !out.is_empty() {
+            out.push('\n');
+        }
+        let payload = serde_json::json!({
+            "name": call.name,
+            "arguments": if call.arguments.is_null() {
+                serde_json::json!({})
+            } else {
+                call.arguments.clone()
+            },
+        });
+        out.push_str("<tool_call>\n");
+        out.push_str(&payload.to_string());
+        out.push_str("\n</tool_call>");
+    }
+    out
+}
+
+fn api_error(status: StatusCode, message: String, headers: HeaderMap) -> anyhow::Error {
+    MockApiError {
+        status,
+        headers,
+        message,
+    }
+    .into()
+}
+
+/// Turn a scripted response into a chat response or error.
+fn into_chat_response(response: MockResponse) -> anyhow::Result<ChatResponse> {
+    match response {
+        MockResponse::Text {
+            text,
+            reasoning,
+            usage,
+        } => Ok(ChatResponse {
+            text: Some(text),
+            tool_calls: Vec::new(),
+            usage: usage.map(Into::into),
+            reasoning_content: reasoning,
+        }),
+        MockResponse::ToolCalls { text, calls, usage } => Ok(ChatResponse {
+            text,
+            tool_calls: calls
+                .iter()
+                .enumerate()
+                .map(|(index, call)| ToolCall {
+                    id: tool_call_id(call, index),
+                    name: call.name.clone(),
+                    arguments: arguments_json(&call.arguments),
+                })
+                .collect(),
+            usage: usage.map(Into::into),
+            reasoning_content: None,
+        }),
+        MockResponse::XmlToolCalls { text, calls } => Ok(ChatResponse {
+            text: Some(render_xml_tool_calls(text.as_deref(), &calls)),
+            tool_calls: Vec::new(),
+            usage: None,
+            reasoning_content: None,
+        }),
+        MockResponse::Error { message, status } => {
+            let status = status
+                .and_then(|code| StatusCode::from_u16(code).ok())
+                .unwrap_or(StatusCode::INTERNAL_SERVER_ERROR);
+            Err(api_error(status, message, HeaderMap::new()))
+        }
+        MockResponse::RateLimit {
+            message,
+            retry_after_secs,
+            headers,
+        } => {
+            let mut map = HeaderMap::new();
+            for (name, value) in &headers {
+                let name = HeaderName::from_bytes(name.as_bytes())
+                    .with_context(|| format!("Invalid mock header name `{name}`"))?;
+                let value = HeaderValue::from_str(value)
+                    .with_context(|| format!("Invalid mock header value `{value}`"))?;
+                map.insert(name, value);
+            }
+            if let Some(secs) = retry_after_secs {
+                map.insert("retry-after", HeaderValue::from(secs));
+            }
+            Err(api_error(StatusCode::TOO_MANY_REQUESTS, message, map))
+        }
+    }
+}
+
+/// Stream events for a response: text split at word boundaries, then
+/// tool calls, usage and `Done`.
+fn response_events(response: ChatResponse) -> Vec<StreamResult<StreamEvent>> {
+    let mut events = Vec::new();
+    if let Some(reasoning) = response.reasoning_content {
+        events.push(Ok(StreamEvent::ReasoningDelta(reasoning)));
+    }
+    if let Some(text) = response.text {
+        events.extend(
+            text.split_inclusive(' ')
+                .map(|piece| Ok(StreamEvent::TextDelta(piece.to_string()))),
+        );
+    }
+    for (index, call) in response.tool_calls.into_iter().enumerate() {
+        events.push(Ok(StreamEvent::ToolCallStart {
+            index,
+            id: call.id,
+            name: call.name,
+        }));
+        events.push(Ok(StreamEvent::ToolCallDelta {
+            index,
+            arguments: call.arguments,
+        }));
+        events.push(Ok(StreamEvent::ToolCallEnd { index }));
+    }
+    if let Some(usage) = response.usage {
+        events.push(Ok(StreamEvent::Usage(usage)));
+    }
+    events.push(Ok(StreamEvent::Done));
+    events
+}
+
+fn delayed<T: Send + 'static>(delay: Duration, items: Vec<T>) -> stream::BoxStream<'static, T> {
+    stream::once(async move {
+        if !delay.is_zero() {
+            tokio::time::sleep(delay).await;
+        }
+        stream::iter(items)
+    })
+    .flatten()
+    .boxed()
+}
+
+#[async_trait]
+impl Provider for MockProvider {
+    fn capabilities(&self) -> ProviderCapabilities {
+        ProviderCapabilities {
+            native_tool_calling: self.script.native_tools,
+            vision: self.script.vision,
+        }
+    }
+
+    async fn chat_with_system(
+        &self,
+        system_prompt: Option<&str>,
+        message: &str,
+        model: &str,
+        temperature: f64,
+    ) -> anyhow::Result<String> {
+        let mut messages = Vec::new();
+        if let Some(system) = system_prompt {
+            messages.push(ChatMessage::system(system));
+        }
+        messages.push(ChatMessage::user(message));
+        self.chat_with_history(&messages, model, temperature).await
+    }
+
+    async fn chat_with_history(
+        &self,
+        messages: &[ChatMessage],
+        model: &str,
+        temperature: f64,
+    ) -> anyhow::Result<String> {
+        let response = self
+            .chat(
+                ChatRequest {
+                    messages,
+                    tools: None,
+                    response_format: None,
+                    params: None,
+                },
+                model,
+                temperature,
+            )
+            .await?;
+        Ok(response.text.unwrap_or_default())
+    }
+
+    async fn chat(
+        &self,
+        request: ChatRequest<'_>,
+        model: &str,
+        temperature: f64,
+    ) -> anyhow::Result<ChatResponse> {
+        let tools = request
+            .tools
+            .unwrap_or_default()
+            .iter()
+            .map(|tool| tool.name.clone())
+            .collect();
+        let (delay, response) =
+            self.next_step(request.messages, tools, model, temperature, false)?;
+        if !delay.is_zero() {
+            tokio::time::sleep(delay).await;
+        }
+        into_chat_response(response)
+    }
+
+    async fn chat_with_tools(
+        &self,
+        messages: &[ChatMessage],
+        tools: &[serde_json::Value],
+        model: &str,
+        temperature: f64,
+    ) -> anyhow::Result<ChatResponse> {
+        let tools = tools
+            .iter()
+            .filter_map(|tool| {
+                tool.pointer("/function/name")
+                    .or_else(|| tool.get("name"))
+                    .and_then(serde_json::Value::as_str)
+                    .map(str::to_string)
+            })
+            .collect();
+        let (delay, response) = self.next_step(messages, tools, model, temperature, false)?;
+        if !delay.is_zero() {
+            tokio::time::sleep(delay).await;
+        }
+        into_chat_response(response)
+    }
+
+    fn supports_streaming(&self) -> bool {
+        self.script.streaming
+    }
+
+    fn stream_chat_with_history(
+        &self,
+        messages: &[ChatMessage],
+        model: &str,
+        temperature: f64,
+        _options: StreamOptions,
+    ) -> stream::BoxStream<'static, StreamResult<StreamChunk>> {
+        let (delay, chunks) = match self.next_step(messages, Vec::new(), model, temperature, true) {
+            Ok((delay, response)) => {
+                let chunks = match into_chat_response(response) {
+                    Ok(response) => {
+                        let text = response.text.unwrap_or_default();
+                        let mut chunks: Vec<_> = text
+                            .split_inclusive(' ')
+                            .map(|piece| Ok(StreamChunk::delta(piece)))
+                            .collect();
+                        chunks.push(Ok(StreamChunk::final_chunk()));
+                        chunks
+                    }
+                    Err(e) => vec![Err(StreamError::Provider(e.to_string()))],
+                };
+                (delay, chunks)
+            }
+            Err(e) => (
+                Duration::ZERO,
+                vec![Err(StreamError::Provider(e.to_string()))],
+            ),
+        };
+        delayed(delay, chunks)
+    }
+
+    fn supports_stream_events(&self) -> bool {
+        self.script.streaming
+    }
+
+    fn stream_chat(
+        &self,
+        request: ChatRequest<'_>,
+        model: &str,
+        temperature: f64,
+    ) -> stream::BoxStream<'static, StreamResult<StreamEvent>> {
+        let tools = request
+            .tools
+            .unwrap_or_default()
+            .iter()
+            .map(|tool| tool.name.clone())
+            .collect();
+        let (delay, events) =
+            match self.next_step(request.messages, tools, model, temperature, true) {
+                Ok((delay, response)) => {
+                    let events = match into_chat_response(response) {
+                        Ok(response) => response_events(response),
+                        Err(e) => vec![Err(StreamError::Provider(e.to_string()))],
+                    };
+                    (delay, events)
+                }
+                Err(e) => (
+                    Duration::ZERO,
+                    vec![Err(StreamError::Provider(e.to_string()))],
+                ),
+            };
+        delayed(delay, events)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::providers::streaming::StreamAccumulator;
+    use crate::tools::ToolSpec;
+
+    fn script(json: serde_json::Value) -> MockScript {
+        serde_json::from_value(json).unwrap()
+    }
+
+    fn shell_spec() -> ToolSpec {
+        ToolSpec {
+            name: "shell".into(),
+            description: "Run a command".into(),
+            parameters: serde_json::json!({"type": "object"}),
+        }
+    }
+
+    #[tokio::test]
+    async fn answers_steps_in_order_and_records_requests() {
+        let provider = MockProvider::new(script(serde_json::json!({
+            "steps": [
+                {
+                    "expect": { "last_message_contains": "list files", "tools_include": ["shell"] },
+                    "respond": {
+                        "type": "tool_calls",
+                        "calls": [{ "name": "shell", "arguments": { "command": "ls" } }]
+                    }
+                },
+                {
+                    "expect": { "last_role": "tool" },
+                    "respond": { "type": "text", "text": "Done." }
+                }
+            ]
+        })));
+        let tools = [shell_spec()];
+
+        let first = provider
+            .chat(
+                ChatRequest {
+                    messages: &[ChatMessage::user("please list files")],
+                    tools: Some(&tools),
+                    response_format: None,
+                    params: None,
+                },
+                "mock-model",
+                0.0,
+            )
+            .await
+            .unwrap();
+        assert_eq!(first.tool_calls.len(), 1);
+        assert_eq!(first.tool_calls[0].id, "call_mock_0");
+        assert_eq!(first.tool_calls[0].arguments, r#"{"command":"ls"}"#);
+
+        let reply = provider
+            .chat_with_history(
+                &[
+                    ChatMessage::user("please list files"),
+                    ChatMessage::tool("a.txt"),
+                ],
+                "mock-model",
+                0.0,
+            )
+            .await
+            .unwrap();
+        assert_eq!(reply, "Done.");
+
+        let requests = provider.requests();
+        assert_eq!(requests.len(), 2);
+        assert_eq!(requests[0].tools, vec!["shell".to_string()]);
+        assert_eq!(requests[0].tool_calls, vec!["shell".to_string()]);
+        assert_eq!(provider.remaining_steps(), 0);
+
+        let err = provider.simple_chat("again", "mock-model", 0.0).await;
+        assert!(err.unwrap_err().to_string().contains("exhausted"));
+    }
+
+    #[tokio::test]
+    async fn unmet_expectation_is_an_error() {
+        let provider = MockProvider::new(script(serde_json::json!({
+            "steps": [{
+                "expect": { "model": "expected-model" },
+                "respond": { "type": "text", "text": "hi" }
+            }]
+        })));
+
+        let err = provider
+            .simple_chat("hello", "other-model", 0.0)
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("step 0"));
+        assert!(err.to_string().contains("expected model `expected-model`"));
+        assert_eq!(provider.remaining_steps(), 1);
+    }
+
+    #[tokio::test]
+    async fn xml_tool_calls_are_rendered_as_tags() {
+        let provider = MockProvider::new(script(serde_json::json!({
+            "native_tools": false,
+            "steps": [{
+                "respond": {
+                    "type": "xml_tool_calls",
+                    "text": "Checking.",
+                    "calls": [{ "name": "file_read", "arguments": { "path": "a.txt" } }]
+                }
+            }]
+        })));
+        assert!(!provider.supports_native_tools());
+
+        let text = provider.simple_chat("read a.txt", "m", 0.0).await.unwrap();
+        assert!(text.starts_with("Checking.\n<tool_call>\n"));
+        assert!(text.contains(r#""name":"file_read""#));
+        assert!(text.ends_with("</tool_call>"));
+    }
+
+    #[tokio::test]
+    async fn rate_limit_step_carries_status_and_headers() {
+        let provider = MockProvider::new(script(serde_json::json!({
+            "steps": [{
+                "respond": {
+                    "type": "rate_limit",
+                    "retry_after_secs": 3,
+                    "headers": { "x-ratelimit-remaining": "0" }
+                }
+            }]
+        })));
+
+        let err = provider.simple_chat("hi", "m", 0.0).await.unwrap_err();
+        let api_err = err.downcast_ref::<MockApiError>().unwrap();
+        assert_eq!(api_err.status, StatusCode::TOO_MANY_REQUESTS);
+        assert_eq!(api_err.headers["x-ratelimit-remaining"], "0");
+        let message = err.to_string();
+        assert!(message.contains("429 Too Many Requests"));
+        assert!(message.contains("retry-after: 3"));
+    }
+
+    #[tokio::test]
+    async fn stream_chat_emits_text_tool_calls_and_usage() {
+        let provider = MockProvider::new(script(serde_json::json!({
+            "steps": [{
+                "respond": {
+                    "type": "tool_calls",
+                    "text": "Let me check",
+                    "calls": [{ "id": "call_1", "name": "shell", "arguments": { "command": "date" } }],
+                    "usage": { "input_tokens": 10, "output_tokens": 4 }
+                }
+            }]
+        })));
+
+        let mut acc = StreamAccumulator::default();
+        let mut events = provider.stream_chat(
+            ChatRequest {
+                messages: &[ChatMessage::user("time?")],
+                tools: None,
+                response_format: None,
+                params: None,
+            },
+            "m",
+            0.0,
+        );
+        let mut deltas = 0;
+        while let Some(event) = events.next().await {
+            let event = event.unwrap();
+            if matches!(event, StreamEvent::TextDelta(_)) {
+                deltas += 1;
+            }
+            acc.push(&event);
+        }
+        assert_eq!(deltas, 3);
+
+        let response = acc.finish();
+        assert_eq!(response.text.as_deref(), Some("Let me check"));
+        assert_eq!(response.tool_calls[0].id, "call_1");
+        assert_eq!(response.tool_calls[0].arguments, r#"{"command":"date"}"#);
+        assert_eq!(response.usage.unwrap().output_tokens, Some(4));
+        assert!(provider.requests()[0].stream);
+    }
+
+    #[test]
+    fn load_reads_toml_scripts_and_writes_transcript() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let transcript = tmp.path().join("transcript.jsonl");
+        let path = tmp.path().join("script.toml");
+        std::fs::write(
+            &path,
+            format!(
+                r#"
+transcript = "{}"
+
+[[steps]]
+respond = {{ type = "text", text = "hello" }}
+"#,
+                transcript.display()
+            ),
+        )
+        .unwrap();
+
+        let provider = MockProvider::from_path(&path).unwrap();
+        let reply = tokio::runtime::Runtime::new()
+            .unwrap()
+            .block_on(provider.simple_chat("hi", "m", 0.0))
+            .unwrap();
+        assert_eq!(reply, "hello");
+
+        let line = std::fs::read_to_string(&transcript).unwrap();
+        let record: serde_json::Value = serde_json::from_str(line.trim()).unwrap();
+        assert_eq!(record["step"], 0);
+        assert_eq!(record["messages"][0]["content"], "hi");
+    }
+
+    #[cfg(feature = "mock-yaml")]
+    #[test]
+    fn load_reads_yaml_scripts() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let path = tmp.path().join("script.yml");
+        std::fs::write(
+            &path,
+            r#"
+native_tools: true
+steps:
+  - respond:
+      type: tool_calls
+      calls:
+        - name: shell
+          arguments: { command: date }
+  - respond: { type: text, text: done }
+"#,
+        )
+        .unwrap();
+
+        let script = MockScript::load(&path).unwrap();
+        assert!(script.native_tools);
+        assert_eq!(script.steps.len(), 2);
+        assert!(matches!(
+            &script.steps[1].respond,
+            MockResponse::Text { text, .. } if text == "done"
+        ));
+    }
+}
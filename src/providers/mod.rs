@@ -17,24 +17,38 @@
 //! in [`create_provider_with_url`]. See `AGENTS.md` §7.1 for the full change playbook.
 
 pub mod anthropic;
+pub mod batch;
 pub mod bedrock;
+pub mod catalog;
 pub mod compatible;
 pub mod copilot;
 pub mod gemini;
+pub mod mock;
 pub mod ollama;
 pub mod openai;
 pub mod openai_codex;
 pub mod openrouter;
+pub mod racing;
 pub mod reliable;
 pub mod router;
+pub mod streaming;
+pub mod structured;
 pub mod telnyx;
+pub mod tool_mode;
 pub mod traits;
 
 #[allow(unused_imports)]
+pub use batch::{BatchHandle, BatchProvider, BatchRequest, BatchStatus};
+pub use catalog::{ModelCatalog, ModelInfo};
+pub use racing::{RaceContext, Racer, RacingProvider};
+pub use tool_mode::{ToolMode, ToolModes};
 pub use traits::{
-    ChatMessage, ChatRequest, ChatResponse, ConversationMessage, Provider, ProviderCapabilityError,
-    ToolCall, ToolResultMessage,
+    ChatMessage, ChatRequest, ChatResponse, ConversationMessage, GenerationParams, Provider,
+    ProviderCapabilityError, StreamEvent, ToolCall, ToolResultMessage, COMPACTION_SUMMARY_PREFIX,
+    VOLATILE_PROMPT_HEADING,
 };
+#[allow(unused_imports)]
+pub use traits::{ReasoningEffort, ResponseFormat};
 
 use crate::auth::AuthService;
 use compatible::{AuthStyle, OpenAiCompatibleProvider};
@@ -1212,6 +1226,24 @@ fn create_provider_with_url_and_options(
             key,
         ))),
 
+        // ── Scripted mock for offline tests ────────────────
+        // Format: "mock:/path/to/script.json" or "mock" with api_url as the path
+        name if name == "mock" || name.starts_with("mock:") => {
+            let path = name
+                .strip_prefix("mock:")
+                .or(api_url)
+                .map(str::trim)
+                .filter(|value| !value.is_empty())
+                .ok_or_else(|| {
+                    anyhow::anyhow!(
+                        "Mock provider requires a script path: use \"mock:/path/to/script.json\" or set api_url"
+                    )
+                })?;
+            Ok(Box::new(mock::MockProvider::from_path(std::path::Path::new(
+                path,
+            ))?))
+        }
+
         // ── Bring Your Own Provider (custom URL) ───────────
         // Format: "custom:https://your-api.com" or "custom:http://localhost:1234"
         name if name.starts_with("custom:") => {
@@ -1274,14 +1306,47 @@ fn create_provider_with_url_and_options(
     }
 }
 
+/// Create the batch API client for a provider.
+///
+/// Only providers with a native batch API qualify: `openai` (honouring
+/// `api_url`), `anthropic` and `anthropic-custom:<url>`.
+pub fn create_batch_provider(
+    name: &str,
+    api_key: Option<&str>,
+    api_url: Option<&str>,
+) -> anyhow::Result<Box<dyn BatchProvider>> {
+    let credential = resolve_provider_credential(name, api_key);
+    let key = credential.as_deref();
+    match name {
+        "openai" => Ok(Box::new(openai::OpenAiProvider::with_base_url(
+            api_url, key,
+        ))),
+        "anthropic" => Ok(Box::new(anthropic::AnthropicProvider::new(key))),
+        name if name.starts_with("anthropic-custom:") => {
+            let base_url = parse_custom_provider_url(
+                name.strip_prefix("anthropic-custom:").unwrap_or(""),
+                "Anthropic-custom provider",
+                "anthropic-custom:https://your-api.com",
+            )?;
+            Ok(Box::new(anthropic::AnthropicProvider::with_base_url(
+                key,
+                Some(&base_url),
+            )))
+        }
+        _ => anyhow::bail!(
+            "Provider '{name}' has no batch API; batch mode needs `openai` or `anthropic`"
+        ),
+    }
+}
+
 /// Parse `"provider:profile"` syntax for fallback entries.
 ///
 /// Returns `(provider_name, Some(profile))` when the entry contains a colon-
 /// delimited profile, or `(original_str, None)` otherwise.  Entries starting
 /// with `custom:` or `anthropic-custom:` are left untouched because the colon
-/// is part of the URL scheme.
+/// is part of the URL scheme, and `mock:` entries carry a script path.
 fn parse_provider_profile(s: &str) -> (&str, Option<&str>) {
-    if s.starts_with("custom:") || s.starts_with("anthropic-custom:") {
+    if s.starts_with("custom:") || s.starts_with("anthropic-custom:") || s.starts_with("mock:") {
         return (s, None);
     }
     match s.split_once(':') {
@@ -1480,6 +1545,7 @@ pub fn create_routed_provider_with_options(
                 router::Route {
                     provider_name: r.provider.clone(),
                     model: r.model.clone(),
+                    params: r.generation.clone(),
                 },
             )
         })
@@ -2410,6 +2476,25 @@ mod tests {
         }
     }
 
+    #[test]
+    fn factory_mock_loads_script_from_name_or_api_url() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let script = tmp.path().join("script.json");
+        std::fs::write(
+            &script,
+            r#"{"steps": [{"respond": {"type": "text", "text": "hi"}}]}"#,
+        )
+        .unwrap();
+        let path = script.to_str().unwrap();
+
+        assert!(create_provider(&format!("mock:{path}"), None).is_ok());
+        assert!(create_provider_with_url("mock", None, Some(path)).is_ok());
+        match create_provider("mock", None) {
+            Err(e) => assert!(e.to_string().contains("requires a script path")),
+            Ok(_) => panic!("Expected error for mock provider without a script"),
+        }
+    }
+
     #[test]
     fn factory_custom_trims_whitespace() {
         let p = create_provider("custom:  https://my-llm.example.com  ", Some("key"));
@@ -2502,6 +2587,7 @@ mod tests {
             channel_max_backoff_secs: 60,
             scheduler_poll_secs: 15,
             scheduler_retries: 2,
+            racing: crate::config::RacingConfig::default(),
         };
 
         let provider = create_resilient_provider(
@@ -2541,6 +2627,7 @@ mod tests {
             channel_max_backoff_secs: 60,
             scheduler_poll_secs: 15,
             scheduler_retries: 2,
+            racing: crate::config::RacingConfig::default(),
         };
 
         // Primary uses a ZAI key; fallbacks (lmstudio, ollama) should NOT
@@ -2563,6 +2650,7 @@ mod tests {
             channel_max_backoff_secs: 60,
             scheduler_poll_secs: 15,
             scheduler_retries: 2,
+            racing: crate::config::RacingConfig::default(),
         };
 
         let provider =
@@ -2589,6 +2677,7 @@ mod tests {
             channel_max_backoff_secs: 60,
             scheduler_poll_secs: 15,
             scheduler_retries: 2,
+            racing: crate::config::RacingConfig::default(),
         };
 
         let provider = create_resilient_provider("zai", Some("zai-test-key"), None, &reliability);
@@ -2621,6 +2710,7 @@ mod tests {
             channel_max_backoff_secs: 60,
             scheduler_poll_secs: 15,
             scheduler_retries: 2,
+            racing: crate::config::RacingConfig::default(),
         };
 
         let provider = create_resilient_provider("zai", Some("zai-test-key"), None, &reliability);
@@ -2884,6 +2974,14 @@ mod tests {
         assert_eq!(profile, None);
     }
 
+    #[test]
+    fn parse_provider_profile_mock_script_not_split() {
+        let input = "mock:/tmp/scripts/agent.json";
+        let (name, profile) = parse_provider_profile(input);
+        assert_eq!(name, input);
+        assert_eq!(profile, None);
+    }
+
     #[test]
     fn parse_provider_profile_empty_profile_ignored() {
         let (name, profile) = parse_provider_profile("openai-codex:");
@@ -2914,6 +3012,7 @@ mod tests {
             channel_max_backoff_secs: 60,
             scheduler_poll_secs: 15,
             scheduler_retries: 2,
+            racing: crate::config::RacingConfig::default(),
         };
 
         // openai-codex resolves its own OAuth credential; it should not
@@ -2943,6 +3042,7 @@ mod tests {
             channel_max_backoff_secs: 60,
             scheduler_poll_secs: 15,
             scheduler_retries: 2,
+            racing: crate::config::RacingConfig::default(),
         };
 
         let provider = create_resilient_provider("ollama", None, None, &reliability);
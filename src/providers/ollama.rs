@@ -1,8 +1,11 @@
 use crate::multimodal;
+use crate::providers::streaming::{self, StreamParser};
 use crate::providers::traits::{
-    ChatMessage, ChatResponse, Provider, ProviderCapabilities, TokenUsage, ToolCall,
+    ChatMessage, ChatResponse, GenerationParams, Provider, ProviderCapabilities, StreamError,
+    StreamEvent, StreamResult, TokenUsage, ToolCall,
 };
 use async_trait::async_trait;
+use futures_util::stream;
 use reqwest::Client;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
@@ -25,6 +28,9 @@ struct ChatRequest {
     think: Option<bool>,
     #[serde(skip_serializing_if = "Option::is_none")]
     tools: Option<Vec<serde_json::Value>>,
+    /// JSON Schema for structured output.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    format: Option<serde_json::Value>,
 }
 
 #[derive(Debug, Serialize)]
@@ -56,6 +62,33 @@ struct OutgoingFunction {
 #[derive(Debug, Serialize)]
 struct Options {
     temperature: f64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    num_predict: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    top_p: Option<f64>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    stop: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    seed: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    presence_penalty: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    frequency_penalty: Option<f64>,
+}
+
+impl Options {
+    fn new(temperature: f64, params: Option<&GenerationParams>) -> Self {
+        let params = params.cloned().unwrap_or_default();
+        Self {
+            temperature,
+            num_predict: params.max_tokens,
+            top_p: params.top_p,
+            stop: params.stop,
+            seed: params.seed,
+            presence_penalty: params.presence_penalty,
+            frequency_penalty: params.frequency_penalty,
+        }
+    }
 }
 
 // ─── Response Structures ──────────────────────────────────────────────────────
@@ -93,6 +126,83 @@ struct OllamaFunction {
     arguments: serde_json::Value,
 }
 
+/// One line of a streaming `/api/chat` response.
+#[derive(Debug, Deserialize)]
+struct ApiStreamChunk {
+    #[serde(default)]
+    message: Option<ResponseMessage>,
+    #[serde(default)]
+    done: bool,
+    #[serde(default)]
+    prompt_eval_count: Option<u64>,
+    #[serde(default)]
+    eval_count: Option<u64>,
+    #[serde(default)]
+    error: Option<String>,
+}
+
+/// Translates `/api/chat` NDJSON chunks into typed stream events.
+///
+/// Ollama sends each tool call whole, so one call becomes a start, a single
+/// arguments delta and an end.
+#[derive(Debug, Default)]
+struct OllamaStreamParser {
+    next_tool_index: usize,
+}
+
+impl StreamParser for OllamaStreamParser {
+    fn parse_line(&mut self, line: &str) -> StreamResult<Vec<StreamEvent>> {
+        let line = line.trim();
+        if line.is_empty() {
+            return Ok(Vec::new());
+        }
+        let chunk: ApiStreamChunk = serde_json::from_str(line).map_err(StreamError::Json)?;
+        if let Some(error) = chunk.error {
+            return Err(StreamError::Provider(format!(
+                "Ollama API error: {}",
+                super::sanitize_api_error(&error)
+            )));
+        }
+
+        let mut events = Vec::new();
+        if let Some(message) = chunk.message {
+            if let Some(thinking) = message.thinking.filter(|t| !t.is_empty()) {
+                events.push(StreamEvent::ReasoningDelta(thinking));
+            }
+            if !message.content.is_empty() {
+                events.push(StreamEvent::TextDelta(message.content));
+            }
+            for tc in &message.tool_calls {
+                let index = self.next_tool_index;
+                self.next_tool_index += 1;
+                let (name, args) = OllamaProvider::extract_tool_name_and_args(tc);
+                events.push(StreamEvent::ToolCallStart {
+                    index,
+                    id: tc
+                        .id
+                        .clone()
+                        .unwrap_or_else(|| uuid::Uuid::new_v4().to_string()),
+                    name,
+                });
+                events.push(StreamEvent::ToolCallDelta {
+                    index,
+                    arguments: serde_json::to_string(&args).unwrap_or_else(|_| "{}".to_string()),
+                });
+                events.push(StreamEvent::ToolCallEnd { index });
+            }
+        }
+        if chunk.done && (chunk.prompt_eval_count.is_some() || chunk.eval_count.is_some()) {
+            events.push(StreamEvent::Usage(TokenUsage {
+                input_tokens: chunk.prompt_eval_count,
+                output_tokens: chunk.eval_count,
+                cache_read_tokens: None,
+                cache_write_tokens: None,
+            }));
+        }
+        Ok(events)
+    }
+}
+
 // ─── Implementation ───────────────────────────────────────────────────────────
 
 impl OllamaProvider {
@@ -205,14 +315,24 @@ impl OllamaProvider {
         model: &str,
         temperature: f64,
         tools: Option<&[serde_json::Value]>,
+        format: Option<&serde_json::Value>,
+        params: Option<&GenerationParams>,
     ) -> ChatRequest {
+        // A requested reasoning effort turns thinking on unless the provider
+        // config already decides it.
+        let think = self.reasoning_enabled.or_else(|| {
+            params
+                .and_then(GenerationParams::thinking_budget_tokens)
+                .map(|_| true)
+        });
         ChatRequest {
             model: model.to_string(),
             messages,
             stream: false,
-            options: Options { temperature },
-            think: self.reasoning_enabled,
+            options: Options::new(temperature, params),
+            think,
             tools: tools.map(|t| t.to_vec()),
+            format: format.cloned(),
         }
     }
 
@@ -343,26 +463,21 @@ impl OllamaProvider {
             .collect()
     }
 
-    /// Send a request to Ollama and get the parsed response.
-    /// Pass `tools` to enable native function-calling for models that support it.
+    /// Send a request built by [`Self::build_chat_request`] to Ollama and get
+    /// the parsed response.
     async fn send_request(
         &self,
-        messages: Vec<Message>,
-        model: &str,
-        temperature: f64,
+        request: ChatRequest,
         should_auth: bool,
-        tools: Option<&[serde_json::Value]>,
     ) -> anyhow::Result<ApiChatResponse> {
-        let request = self.build_chat_request(messages, model, temperature, tools);
-
         let url = format!("{}/api/chat", self.base_url);
 
         tracing::debug!(
             "Ollama request: url={} model={} message_count={} temperature={} think={:?} tool_count={}",
             url,
-            model,
+            request.model,
             request.messages.len(),
-            temperature,
+            request.options.temperature,
             request.think,
             request.tools.as_ref().map_or(0, |t| t.len()),
         );
@@ -413,6 +528,99 @@ impl OllamaProvider {
         Ok(chat_response)
     }
 
+    /// Chat through `/api/chat` returning structured tool calls, with optional
+    /// native tools, JSON Schema `format` and generation parameters.
+    async fn chat_native(
+        &self,
+        messages: &[ChatMessage],
+        tools: Option<&[serde_json::Value]>,
+        format: Option<&serde_json::Value>,
+        params: Option<&GenerationParams>,
+        model: &str,
+        temperature: f64,
+    ) -> anyhow::Result<ChatResponse> {
+        let (normalized_model, should_auth) = self.resolve_request_details(model)?;
+
+        let api_messages = self.convert_messages(messages);
+        let request = self.build_chat_request(
+            api_messages,
+            &normalized_model,
+            temperature,
+            tools,
+            format,
+            params,
+        );
+        let response = self.send_request(request, should_auth).await?;
+
+        let usage = if response.prompt_eval_count.is_some() || response.eval_count.is_some() {
+            Some(TokenUsage {
+                input_tokens: response.prompt_eval_count,
+                output_tokens: response.eval_count,
+                cache_read_tokens: None,
+                cache_write_tokens: None,
+            })
+        } else {
+            None
+        };
+
+        // Native tool calls returned by the model.
+        if !response.message.tool_calls.is_empty() {
+            let tool_calls: Vec<ToolCall> = response
+                .message
+                .tool_calls
+                .iter()
+                .map(|tc| {
+                    let (name, args) = Self::extract_tool_name_and_args(tc);
+                    ToolCall {
+                        id: tc
+                            .id
+                            .clone()
+                            .unwrap_or_else(|| uuid::Uuid::new_v4().to_string()),
+                        name,
+                        arguments: serde_json::to_string(&args)
+                            .unwrap_or_else(|_| "{}".to_string()),
+                    }
+                })
+                .collect();
+            let text = Self::normalize_response_text(response.message.content);
+            return Ok(ChatResponse {
+                text,
+                tool_calls,
+                usage,
+                reasoning_content: None,
+            });
+        }
+
+        // Plain text response.
+        let content = response.message.content;
+        let text = if let Some(content) = Self::normalize_response_text(content) {
+            content
+        } else {
+            Self::fallback_text_for_empty_content(
+                &normalized_model,
+                response.message.thinking.as_deref(),
+            )
+        };
+        Ok(ChatResponse {
+            text: Some(text),
+            tool_calls: vec![],
+            usage,
+            reasoning_content: None,
+        })
+    }
+
+    /// Convert a tool spec to the OpenAI-compatible JSON `/api/chat` accepts.
+    fn tool_spec_to_json(spec: &crate::tools::ToolSpec) -> serde_json::Value {
+        serde_json::json!({
+            "type": "function",
+            "function": {
+                "name": spec.name,
+                "description": spec.description,
+                "parameters": spec.parameters
+            }
+        })
+    }
+
     /// Convert Ollama tool calls to the JSON format expected by parse_tool_calls in loop_.rs
     ///
     /// Handles quirky model behavior where tool calls are wrapped:
@@ -422,7 +630,7 @@ impl OllamaProvider {
         let formatted_calls: Vec<serde_json::Value> = tool_calls
             .iter()
             .map(|tc| {
-                let (tool_name, tool_args) = self.extract_tool_name_and_args(tc);
+                let (tool_name, tool_args) = Self::extract_tool_name_and_args(tc);
 
                 // Arguments must be a JSON string for parse_tool_calls compatibility
                 let args_str =
@@ -447,7 +655,7 @@ impl OllamaProvider {
     }
 
     /// Extract the actual tool name and arguments from potentially nested structures
-    fn extract_tool_name_and_args(&self, tc: &OllamaToolCall) -> (String, serde_json::Value) {
+    fn extract_tool_name_and_args(tc: &OllamaToolCall) -> (String, serde_json::Value) {
         let name = &tc.function.name;
         let args = &tc.function.arguments;
 
@@ -524,9 +732,9 @@ impl Provider for OllamaProvider {
             tool_name: None,
         });
 
-        let response = self
-            .send_request(messages, &normalized_model, temperature, should_auth, None)
-            .await?;
+        let request =
+            self.build_chat_request(messages, &normalized_model, temperature, None, None, None);
+        let response = self.send_request(request, should_auth).await?;
 
         // If model returned tool calls, format them for loop_.rs's parse_tool_calls
         if !response.message.tool_calls.is_empty() {
@@ -559,15 +767,15 @@ impl Provider for OllamaProvider {
 
         let api_messages = self.convert_messages(messages);
 
-        let response = self
-            .send_request(
-                api_messages,
-                &normalized_model,
-                temperature,
-                should_auth,
-                None,
-            )
-            .await?;
+        let request = self.build_chat_request(
+            api_messages,
+            &normalized_model,
+            temperature,
+            None,
+            None,
+            None,
+        );
+        let response = self.send_request(request, should_auth).await?;
 
         // If model returned tool calls, format them for loop_.rs's parse_tool_calls
         if !response.message.tool_calls.is_empty() {
@@ -597,77 +805,11 @@ impl Provider for OllamaProvider {
         model: &str,
         temperature: f64,
     ) -> anyhow::Result<ChatResponse> {
-        let (normalized_model, should_auth) = self.resolve_request_details(model)?;
-
-        let api_messages = self.convert_messages(messages);
-
         // Tools arrive pre-formatted in OpenAI/Ollama-compatible JSON from
         // tools_to_openai_format() in loop_.rs — pass them through directly.
         let tools_opt = if tools.is_empty() { None } else { Some(tools) };
-
-        let response = self
-            .send_request(
-                api_messages,
-                &normalized_model,
-                temperature,
-                should_auth,
-                tools_opt,
-            )
-            .await?;
-
-        let usage = if response.prompt_eval_count.is_some() || response.eval_count.is_some() {
-            Some(TokenUsage {
-                input_tokens: response.prompt_eval_count,
-                output_tokens: response.eval_count,
-            })
-        } else {
-            None
-        };
-
-        // Native tool calls returned by the model.
-        if !response.message.tool_calls.is_empty() {
-            let tool_calls: Vec<ToolCall> = response
-                .message
-                .tool_calls
-                .iter()
-                .map(|tc| {
-                    let (name, args) = self.extract_tool_name_and_args(tc);
-                    ToolCall {
-                        id: tc
-                            .id
-                            .clone()
-                            .unwrap_or_else(|| uuid::Uuid::new_v4().to_string()),
-                        name,
-                        arguments: serde_json::to_string(&args)
-                            .unwrap_or_else(|_| "{}".to_string()),
-                    }
-                })
-                .collect();
-            let text = Self::normalize_response_text(response.message.content);
-            return Ok(ChatResponse {
-                text,
-                tool_calls,
-                usage,
-                reasoning_content: None,
-            });
-        }
-
-        // Plain text response.
-        let content = response.message.content;
-        let text = if let Some(content) = Self::normalize_response_text(content) {
-            content
-        } else {
-            Self::fallback_text_for_empty_content(
-                &normalized_model,
-                response.message.thinking.as_deref(),
-            )
-        };
-        Ok(ChatResponse {
-            text: Some(text),
-            tool_calls: vec![],
-            usage,
-            reasoning_content: None,
-        })
+        self.chat_native(messages, tools_opt, None, None, model, temperature)
+            .await
     }
 
     fn supports_native_tools(&self) -> bool {
@@ -677,32 +819,79 @@ impl Provider for OllamaProvider {
         true
     }
 
+    fn supports_stream_events(&self) -> bool {
+        true
+    }
+
+    fn stream_chat(
+        &self,
+        request: crate::providers::traits::ChatRequest<'_>,
+        model: &str,
+        temperature: f64,
+    ) -> stream::BoxStream<'static, StreamResult<StreamEvent>> {
+        let (normalized_model, should_auth) = match self.resolve_request_details(model) {
+            Ok(details) => details,
+            Err(e) => return streaming::error_stream(StreamError::Provider(e.to_string())),
+        };
+
+        let tools: Vec<serde_json::Value> = request
+            .tools
+            .unwrap_or_default()
+            .iter()
+            .map(Self::tool_spec_to_json)
+            .collect();
+        let mut body = self.build_chat_request(
+            self.convert_messages(request.messages),
+            &normalized_model,
+            temperature,
+            (!tools.is_empty()).then_some(&tools[..]),
+            request.response_format.map(|format| &format.schema),
+            request.params,
+        );
+        body.stream = true;
+
+        let mut req = self
+            .http_client()
+            .post(format!("{}/api/chat", self.base_url))
+            .json(&body);
+        if should_auth {
+            if let Some(key) = self.api_key.as_ref() {
+                req = req.bearer_auth(key);
+            }
+        }
+        streaming::event_stream("Ollama", req, OllamaStreamParser::default())
+    }
+
     async fn chat(
         &self,
         request: crate::providers::traits::ChatRequest<'_>,
         model: &str,
         temperature: f64,
     ) -> anyhow::Result<ChatResponse> {
-        // Convert ToolSpec to OpenAI-compatible JSON and delegate to chat_with_tools.
-        if let Some(specs) = request.tools {
-            if !specs.is_empty() {
-                let tools: Vec<serde_json::Value> = specs
-                    .iter()
-                    .map(|s| {
-                        serde_json::json!({
-                            "type": "function",
-                            "function": {
-                                "name": s.name,
-                                "description": s.description,
-                                "parameters": s.parameters
-                            }
-                        })
-                    })
-                    .collect();
-                return self
-                    .chat_with_tools(request.messages, &tools, model, temperature)
-                    .await;
-            }
+        // Convert ToolSpec to OpenAI-compatible JSON and chat natively when
+        // tools, a response format or generation parameters are requested.
+        let tools: Vec<serde_json::Value> = request
+            .tools
+            .unwrap_or_default()
+            .iter()
+            .map(Self::tool_spec_to_json)
+            .collect();
+        if !tools.is_empty() || request.response_format.is_some() || request.params.is_some() {
+            let tools_opt = if tools.is_empty() {
+                None
+            } else {
+                Some(&tools[..])
+            };
+            return self
+                .chat_native(
+                    request.messages,
+                    tools_opt,
+                    request.response_format.map(|format| &format.schema),
+                    request.params,
+                    model,
+                    temperature,
+                )
+                .await;
         }
 
         // No tools — fall back to plain text chat.
@@ -820,6 +1009,8 @@ mod tests {
             "llama3",
             0.7,
             None,
+            None,
+            None,
         );
 
         let json = serde_json::to_value(request).unwrap();
@@ -840,12 +1031,112 @@ mod tests {
             "llama3",
             0.7,
             None,
+            None,
+            None,
         );
 
         let json = serde_json::to_value(request).unwrap();
         assert_eq!(json.get("think"), Some(&serde_json::json!(false)));
     }
 
+    #[test]
+    fn request_includes_format_schema_when_requested() {
+        let provider = OllamaProvider::new(None, None);
+        let schema = serde_json::json!({
+            "type": "object",
+            "properties": { "ok": { "type": "boolean" } },
+            "required": ["ok"]
+        });
+        let request =
+            provider.build_chat_request(Vec::new(), "llama3", 0.0, None, Some(&schema), None);
+
+        let json = serde_json::to_value(request).unwrap();
+        assert_eq!(json["format"], schema);
+
+        let plain = provider.build_chat_request(Vec::new(), "llama3", 0.0, None, None, None);
+        assert!(serde_json::to_value(plain).unwrap().get("format").is_none());
+    }
+
+    #[test]
+    fn request_maps_generation_params_to_options() {
+        let provider = OllamaProvider::new(None, None);
+        let params = GenerationParams {
+            max_tokens: Some(128),
+            top_p: Some(0.9),
+            stop: vec!["</answer>".into()],
+            seed: Some(3),
+            reasoning_effort: Some(crate::providers::ReasoningEffort::High),
+            ..GenerationParams::default()
+        };
+        let request =
+            provider.build_chat_request(Vec::new(), "llama3", 0.2, None, None, Some(&params));
+
+        let json = serde_json::to_value(request).unwrap();
+        assert_eq!(json["options"]["num_predict"], 128);
+        assert_eq!(json["options"]["top_p"], 0.9);
+        assert_eq!(json["options"]["stop"], serde_json::json!(["</answer>"]));
+        assert_eq!(json["options"]["seed"], 3);
+        assert!(json["options"].get("presence_penalty").is_none());
+        assert_eq!(json["think"], true);
+
+        let plain = provider.build_chat_request(Vec::new(), "llama3", 0.2, None, None, None);
+        assert_eq!(
+            serde_json::to_value(plain).unwrap()["options"],
+            serde_json::json!({"temperature": 0.2})
+        );
+    }
+
+    #[test]
+    fn stream_parser_translates_ndjson_chunks() {
+        let mut parser = OllamaStreamParser::default();
+        let mut events = Vec::new();
+        for line in [
+            r#"{"model":"qwen3","message":{"role":"assistant","content":"","thinking":"Needs a tool."},"done":false}"#,
+            r#"{"model":"qwen3","message":{"role":"assistant","content":"On it."},"done":false}"#,
+            r#"{"model":"qwen3","message":{"role":"assistant","content":"","tool_calls":[{"function":{"name":"tool.shell","arguments":{"command":"date"}}}]},"done":false}"#,
+            r#"{"model":"qwen3","message":{"role":"assistant","content":""},"done":true,"prompt_eval_count":30,"eval_count":12}"#,
+        ] {
+            events.extend(parser.parse_line(line).unwrap());
+        }
+
+        assert_eq!(
+            events[0],
+            StreamEvent::ReasoningDelta("Needs a tool.".into())
+        );
+        assert_eq!(events[1], StreamEvent::TextDelta("On it.".into()));
+        assert!(matches!(
+            &events[2],
+            StreamEvent::ToolCallStart { index: 0, name, .. } if name == "shell"
+        ));
+        assert_eq!(
+            events[3],
+            StreamEvent::ToolCallDelta {
+                index: 0,
+                arguments: r#"{"command":"date"}"#.into(),
+            }
+        );
+        assert_eq!(events[4], StreamEvent::ToolCallEnd { index: 0 });
+        assert_eq!(
+            events[5],
+            StreamEvent::Usage(TokenUsage {
+                input_tokens: Some(30),
+                output_tokens: Some(12),
+                cache_read_tokens: None,
+                cache_write_tokens: None,
+            })
+        );
+        assert_eq!(events.len(), 6);
+    }
+
+    #[test]
+    fn stream_parser_surfaces_error_lines() {
+        let mut parser = OllamaStreamParser::default();
+        let err = parser
+            .parse_line(r#"{"error":"model 'nope' not found"}"#)
+            .unwrap_err();
+        assert!(err.to_string().contains("not found"));
+    }
+
     #[test]
     fn response_deserializes() {
         let json = r#"{"message":{"role":"assistant","content":"Hello from Ollama!"}}"#;
@@ -904,7 +1195,6 @@ mod tests {
 
     #[test]
     fn extract_tool_name_handles_nested_tool_call() {
-        let provider = OllamaProvider::new(None, None);
         let tc = OllamaToolCall {
             id: Some("call_123".into()),
             function: OllamaFunction {
@@ -915,14 +1205,13 @@ mod tests {
                 }),
             },
         };
-        let (name, args) = provider.extract_tool_name_and_args(&tc);
+        let (name, args) = OllamaProvider::extract_tool_name_and_args(&tc);
         assert_eq!(name, "shell");
         assert_eq!(args.get("command").unwrap(), "date");
     }
 
     #[test]
     fn extract_tool_name_handles_prefixed_name() {
-        let provider = OllamaProvider::new(None, None);
         let tc = OllamaToolCall {
             id: Some("call_123".into()),
             function: OllamaFunction {
@@ -930,14 +1219,13 @@ mod tests {
                 arguments: serde_json::json!({"command": "ls"}),
             },
         };
-        let (name, args) = provider.extract_tool_name_and_args(&tc);
+        let (name, args) = OllamaProvider::extract_tool_name_and_args(&tc);
         assert_eq!(name, "shell");
         assert_eq!(args.get("command").unwrap(), "ls");
     }
 
     #[test]
     fn extract_tool_name_handles_normal_call() {
-        let provider = OllamaProvider::new(None, None);
         let tc = OllamaToolCall {
             id: Some("call_123".into()),
             function: OllamaFunction {
@@ -945,7 +1233,7 @@ mod tests {
                 arguments: serde_json::json!({"path": "/tmp/test"}),
             },
         };
-        let (name, args) = provider.extract_tool_name_and_args(&tc);
+        let (name, args) = OllamaProvider::extract_tool_name_and_args(&tc);
         assert_eq!(name, "file_read");
         assert_eq!(args.get("path").unwrap(), "/tmp/test");
     }
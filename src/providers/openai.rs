@@ -1,6 +1,9 @@
+use crate::providers::batch::{
+    parse_jsonl, BatchHandle, BatchProvider, BatchRequest, BatchResult, BatchStatus,
+};
 use crate::providers::traits::{
     ChatMessage, ChatRequest as ProviderChatRequest, ChatResponse as ProviderChatResponse,
-    Provider, TokenUsage, ToolCall as ProviderToolCall,
+    GenerationParams, Provider, ResponseFormat, TokenUsage, ToolCall as ProviderToolCall,
 };
 use crate::tools::ToolSpec;
 use async_trait::async_trait;
@@ -67,6 +70,11 @@ struct NativeChatRequest {
     tools: Option<Vec<NativeToolSpec>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     tool_choice: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    response_format: Option<serde_json::Value>,
+    /// Generation parameters (`top_p`, `stop`, `seed`, ...).
+    #[serde(flatten)]
+    generation: serde_json::Map<String, serde_json::Value>,
 }
 
 #[derive(Debug, Serialize)]
@@ -304,9 +312,274 @@ impl OpenAiProvider {
         }
     }
 
+    fn native_chat_request(
+        &self,
+        request: &ProviderChatRequest<'_>,
+        model: &str,
+        temperature: f64,
+    ) -> NativeChatRequest {
+        let tools = Self::convert_tools(request.tools);
+        let generation = request
+            .params
+            .map(GenerationParams::openai_payload)
+            .unwrap_or_default();
+        NativeChatRequest {
+            model: model.to_string(),
+            messages: Self::convert_messages(request.messages),
+            temperature,
+            // A per-request limit arrives through `generation` and wins over
+            // the configured override.
+            max_tokens: self
+                .max_tokens_override
+                .filter(|_| !generation.contains_key("max_tokens")),
+            tool_choice: tools.as_ref().map(|_| "auto".to_string()),
+            tools,
+            response_format: request.response_format.map(ResponseFormat::openai_payload),
+            generation,
+        }
+    }
+
+    fn parse_chat_response(
+        native_response: NativeChatResponse,
+    ) -> anyhow::Result<ProviderChatResponse> {
+        let usage = native_response.usage.map(|u| TokenUsage {
+            input_tokens: u.prompt_tokens,
+            output_tokens: u.completion_tokens,
+            cache_read_tokens: None,
+            cache_write_tokens: None,
+        });
+        let message = native_response
+            .choices
+            .into_iter()
+            .next()
+            .map(|c| c.message)
+            .ok_or_else(|| anyhow::anyhow!("No response from OpenAI"))?;
+        let mut result = Self::parse_native_response(message);
+        result.usage = usage;
+        Ok(result)
+    }
+
     fn http_client(&self) -> Client {
         crate::config::build_runtime_proxy_client_with_timeouts("provider.openai", 120, 10)
     }
+
+    fn require_credential(&self) -> anyhow::Result<&str> {
+        self.credential.as_deref().ok_or_else(|| {
+            anyhow::anyhow!("OpenAI API key not set. Set OPENAI_API_KEY or edit config.toml.")
+        })
+    }
+
+    async fn fetch_batch_file(&self, file_id: &str) -> anyhow::Result<Vec<BatchOutputLine>> {
+        let response = self
+            .http_client()
+            .get(format!("{}/files/{file_id}/content", self.base_url))
+            .header(
+                "Authorization",
+                format!("Bearer {}", self.require_credential()?),
+            )
+            .send()
+            .await?;
+        if !response.status().is_success() {
+            return Err(super::api_error("OpenAI", response).await);
+        }
+        parse_jsonl(&response.text().await?)
+    }
+}
+
+/// Endpoint every batch line targets.
+const BATCH_ENDPOINT: &str = "/v1/chat/completions";
+
+#[derive(Debug, Serialize)]
+struct BatchInputLine<'a> {
+    custom_id: &'a str,
+    method: &'static str,
+    url: &'static str,
+    body: NativeChatRequest,
+}
+
+#[derive(Debug, Deserialize)]
+struct FileObject {
+    id: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct BatchObject {
+    id: String,
+    status: String,
+    #[serde(default)]
+    output_file_id: Option<String>,
+    #[serde(default)]
+    error_file_id: Option<String>,
+    #[serde(default)]
+    errors: Option<BatchErrors>,
+}
+
+#[derive(Debug, Deserialize)]
+struct BatchErrors {
+    #[serde(default)]
+    data: Vec<BatchErrorDetail>,
+}
+
+#[derive(Debug, Deserialize)]
+struct BatchErrorDetail {
+    #[serde(default)]
+    message: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct BatchOutputLine {
+    custom_id: String,
+    #[serde(default)]
+    response: Option<BatchOutputResponse>,
+    #[serde(default)]
+    error: Option<BatchErrorDetail>,
+}
+
+#[derive(Debug, Deserialize)]
+struct BatchOutputResponse {
+    status_code: u16,
+    body: serde_json::Value,
+}
+
+impl BatchOutputLine {
+    fn into_result(self) -> BatchResult {
+        let outcome = match (self.response, self.error) {
+            (_, Some(error)) => Err(error.message),
+            (Some(response), None) if response.status_code == 200 => {
+                serde_json::from_value::<NativeChatResponse>(response.body)
+                    .map_err(anyhow::Error::from)
+                    .and_then(OpenAiProvider::parse_chat_response)
+                    .map_err(|e| e.to_string())
+            }
+            (Some(response), None) => Err(format!(
+                "request failed with status {}: {}",
+                response.status_code,
+                super::sanitize_api_error(&response.body.to_string())
+            )),
+            (None, None) => Err("batch line has neither response nor error".to_string()),
+        };
+        BatchResult {
+            custom_id: self.custom_id,
+            outcome,
+        }
+    }
+}
+
+#[async_trait]
+impl BatchProvider for OpenAiProvider {
+    async fn submit_batch(
+        &self,
+        requests: &[BatchRequest<'_>],
+        model: &str,
+        temperature: f64,
+    ) -> anyhow::Result<BatchHandle> {
+        let credential = self.require_credential()?;
+        if requests.is_empty() {
+            anyhow::bail!("Cannot submit an empty batch");
+        }
+
+        let mut jsonl = String::new();
+        for item in requests {
+            let line = BatchInputLine {
+                custom_id: item.custom_id,
+                method: "POST",
+                url: BATCH_ENDPOINT,
+                body: self.native_chat_request(&item.request, model, temperature),
+            };
+            jsonl.push_str(&serde_json::to_string(&line)?);
+            jsonl.push('\n');
+        }
+
+        let form = reqwest::multipart::Form::new()
+            .text("purpose", "batch")
+            .part(
+                "file",
+                reqwest::multipart::Part::bytes(jsonl.into_bytes())
+                    .file_name("batch.jsonl")
+                    .mime_str("application/jsonl")?,
+            );
+        let response = self
+            .http_client()
+            .post(format!("{}/files", self.base_url))
+            .header("Authorization", format!("Bearer {credential}"))
+            .multipart(form)
+            .send()
+            .await?;
+        if !response.status().is_success() {
+            return Err(super::api_error("OpenAI", response).await);
+        }
+        let file: FileObject = response.json().await?;
+
+        let response = self
+            .http_client()
+            .post(format!("{}/batches", self.base_url))
+            .header("Authorization", format!("Bearer {credential}"))
+            .json(&serde_json::json!({
+                "input_file_id": file.id,
+                "endpoint": BATCH_ENDPOINT,
+                "completion_window": "24h",
+            }))
+            .send()
+            .await?;
+        if !response.status().is_success() {
+            return Err(super::api_error("OpenAI", response).await);
+        }
+        let batch: BatchObject = response.json().await?;
+
+        Ok(BatchHandle {
+            provider: "openai".to_string(),
+            id: batch.id,
+            model: model.to_string(),
+            submitted_at: chrono::Utc::now(),
+        })
+    }
+
+    async fn poll_batch(&self, handle: &BatchHandle) -> anyhow::Result<BatchStatus> {
+        let credential = self.require_credential()?;
+        let response = self
+            .http_client()
+            .get(format!("{}/batches/{}", self.base_url, handle.id))
+            .header("Authorization", format!("Bearer {credential}"))
+            .send()
+            .await?;
+        if !response.status().is_success() {
+            return Err(super::api_error("OpenAI", response).await);
+        }
+        let batch: BatchObject = response.json().await?;
+
+        match batch.status.as_str() {
+            "completed" => {
+                let mut results = Vec::new();
+                for file_id in [&batch.output_file_id, &batch.error_file_id]
+                    .into_iter()
+                    .flatten()
+                {
+                    let lines = self.fetch_batch_file(file_id).await?;
+                    results.extend(lines.into_iter().map(BatchOutputLine::into_result));
+                }
+                Ok(BatchStatus::Completed(results))
+            }
+            "failed" | "expired" | "cancelled" => {
+                let detail = batch
+                    .errors
+                    .map(|errors| {
+                        errors
+                            .data
+                            .into_iter()
+                            .map(|e| e.message)
+                            .collect::<Vec<_>>()
+                            .join("; ")
+                    })
+                    .filter(|detail| !detail.is_empty())
+                    .unwrap_or_else(|| "no details".to_string());
+                Ok(BatchStatus::Failed(format!(
+                    "OpenAI batch {} {}: {detail}",
+                    batch.id, batch.status
+                )))
+            }
+            _ => Ok(BatchStatus::InProgress),
+        }
+    }
 }
 
 #[async_trait]
@@ -375,15 +648,7 @@ impl Provider for OpenAiProvider {
             anyhow::anyhow!("OpenAI API key not set. Set OPENAI_API_KEY or edit config.toml.")
         })?;
 
-        let tools = Self::convert_tools(request.tools);
-        let native_request = NativeChatRequest {
-            model: model.to_string(),
-            messages: Self::convert_messages(request.messages),
-            temperature,
-            max_tokens: self.max_tokens_override,
-            tool_choice: tools.as_ref().map(|_| "auto".to_string()),
-            tools,
-        };
+        let native_request = self.native_chat_request(&request, model, temperature);
 
         let response = self
             .http_client()
@@ -397,20 +662,7 @@ impl Provider for OpenAiProvider {
             return Err(super::api_error("OpenAI", response).await);
         }
 
-        let native_response: NativeChatResponse = response.json().await?;
-        let usage = native_response.usage.map(|u| TokenUsage {
-            input_tokens: u.prompt_tokens,
-            output_tokens: u.completion_tokens,
-        });
-        let message = native_response
-            .choices
-            .into_iter()
-            .next()
-            .map(|c| c.message)
-            .ok_or_else(|| anyhow::anyhow!("No response from OpenAI"))?;
-        let mut result = Self::parse_native_response(message);
-        result.usage = usage;
-        Ok(result)
+        Self::parse_chat_response(response.json().await?)
     }
 
     fn supports_native_tools(&self) -> bool {
@@ -447,6 +699,8 @@ impl Provider for OpenAiProvider {
             max_tokens: self.max_tokens_override,
             tool_choice: native_tools.as_ref().map(|_| "auto".to_string()),
             tools: native_tools,
+            response_format: None,
+            generation: serde_json::Map::new(),
         };
 
         let response = self
@@ -465,6 +719,8 @@ impl Provider for OpenAiProvider {
         let usage = native_response.usage.map(|u| TokenUsage {
             input_tokens: u.prompt_tokens,
             output_tokens: u.completion_tokens,
+            cache_read_tokens: None,
+            cache_write_tokens: None,
         });
         let message = native_response
             .choices
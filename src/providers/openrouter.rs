@@ -1,7 +1,8 @@
 use crate::multimodal;
 use crate::providers::traits::{
     ChatMessage, ChatRequest as ProviderChatRequest, ChatResponse as ProviderChatResponse,
-    Provider, ProviderCapabilities, TokenUsage, ToolCall as ProviderToolCall,
+    GenerationParams, Provider, ProviderCapabilities, ResponseFormat, TokenUsage,
+    ToolCall as ProviderToolCall,
 };
 use crate::tools::ToolSpec;
 use async_trait::async_trait;
@@ -73,6 +74,11 @@ struct NativeChatRequest {
     tools: Option<Vec<NativeToolSpec>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     tool_choice: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    response_format: Option<serde_json::Value>,
+    /// Generation parameters (`top_p`, `stop`, `seed`, ...).
+    #[serde(flatten)]
+    generation: serde_json::Map<String, serde_json::Value>,
 }
 
 #[derive(Debug, Serialize)]
@@ -455,13 +461,23 @@ impl Provider for OpenRouterProvider {
         })?;
 
         let tools = Self::convert_tools(request.tools);
+        let generation = request
+            .params
+            .map(GenerationParams::openai_payload)
+            .unwrap_or_default();
         let native_request = NativeChatRequest {
             model: model.to_string(),
             messages: Self::convert_messages(request.messages),
             temperature,
-            max_tokens: self.max_tokens_override,
+            // A per-request limit arrives through `generation` and wins over
+            // the configured override.
+            max_tokens: self
+                .max_tokens_override
+                .filter(|_| !generation.contains_key("max_tokens")),
             tool_choice: tools.as_ref().map(|_| "auto".to_string()),
             tools,
+            response_format: request.response_format.map(ResponseFormat::openai_payload),
+            generation,
         };
 
         let response = self
@@ -485,6 +501,8 @@ impl Provider for OpenRouterProvider {
         let usage = native_response.usage.map(|u| TokenUsage {
             input_tokens: u.prompt_tokens,
             output_tokens: u.completion_tokens,
+            cache_read_tokens: None,
+            cache_write_tokens: None,
         });
         let message = native_response
             .choices
@@ -557,6 +575,8 @@ impl Provider for OpenRouterProvider {
             max_tokens: self.max_tokens_override,
             tool_choice: native_tools.as_ref().map(|_| "auto".to_string()),
             tools: native_tools,
+            response_format: None,
+            generation: serde_json::Map::new(),
         };
 
         let response = self
@@ -580,6 +600,8 @@ impl Provider for OpenRouterProvider {
         let usage = native_response.usage.map(|u| TokenUsage {
             input_tokens: u.prompt_tokens,
             output_tokens: u.completion_tokens,
+            cache_read_tokens: None,
+            cache_write_tokens: None,
         });
         let message = native_response
             .choices
@@ -0,0 +1,627 @@
+//! Speculative provider racing for latency-critical channels.
+//!
+//! [`RacingProvider`] sends the same request to several providers and returns
+//! the first successful response. The lead racer starts immediately; each
+//! following racer is launched once the previous one has been running for
+//! its observed p95 latency (or the configured hedge delay until enough
+//! latencies are known), or straight away when every running attempt has
+//! failed. Attempts still running when a winner arrives are cancelled.
+
+use super::traits::{ChatMessage, ChatRequest, ChatResponse, TokenUsage};
+use super::Provider;
+use crate::cost::UsageRecorder;
+use crate::observability::{SpanKind, TraceSpan};
+use async_trait::async_trait;
+use futures_util::future::BoxFuture;
+use futures_util::stream::FuturesUnordered;
+use futures_util::{FutureExt, StreamExt};
+use parking_lot::Mutex;
+use std::collections::{HashMap, VecDeque};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::time::Instant;
+
+/// Latencies kept per provider for the p95 estimate.
+const LATENCY_WINDOW: usize = 100;
+
+/// Latencies needed before the p95 replaces the configured hedge delay.
+const MIN_LATENCY_SAMPLES: usize = 10;
+
+/// One provider taking part in a race.
+pub struct Racer {
+    pub name: String,
+    pub provider: Arc<dyn Provider>,
+    /// Model sent to this racer; `None` uses the model the caller asked for.
+    pub model: Option<String>,
+}
+
+impl Racer {
+    pub fn new(name: impl Into<String>, provider: Arc<dyn Provider>) -> Self {
+        Self {
+            name: name.into(),
+            provider,
+            model: None,
+        }
+    }
+
+    pub fn with_model(mut self, model: impl Into<String>) -> Self {
+        self.model = Some(model.into());
+        self
+    }
+}
+
+/// Latency history and cost attribution shared by every race of a runtime.
+#[derive(Default)]
+pub struct RaceContext {
+    latencies: Mutex<HashMap<String, VecDeque<Duration>>>,
+    costs: Option<Arc<UsageRecorder>>,
+}
+
+impl RaceContext {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record the cost of every attempt with `recorder`.
+    pub fn with_usage_recorder(mut self, recorder: Arc<UsageRecorder>) -> Self {
+        self.costs = Some(recorder);
+        self
+    }
+
+    /// p95 of the latest successful response times, once enough are known.
+    pub fn latency_p95(&self, provider: &str) -> Option<Duration> {
+        let latencies = self.latencies.lock();
+        let samples = latencies.get(provider)?;
+        if samples.len() < MIN_LATENCY_SAMPLES {
+            return None;
+        }
+        let mut sorted: Vec<Duration> = samples.iter().copied().collect();
+        sorted.sort_unstable();
+        let rank = (sorted.len() * 95).div_ceil(100);
+        sorted.get(rank.saturating_sub(1)).copied()
+    }
+
+    fn record_latency(&self, provider: &str, latency: Duration) {
+        let mut latencies = self.latencies.lock();
+        let samples = latencies.entry(provider.to_string()).or_default();
+        if samples.len() == LATENCY_WINDOW {
+            samples.pop_front();
+        }
+        samples.push_back(latency);
+    }
+
+    fn record_cost(&self, provider: &str, model: &str, usage: &TokenUsage) {
+        if let Some(costs) = &self.costs {
+            costs.record(provider, model, usage);
+        }
+    }
+}
+
+/// Rough token count for attempts without reported usage (~4 characters per token).
+fn estimate_tokens(chars: usize) -> u64 {
+    chars as u64 / 4
+}
+
+fn estimate_history_tokens(messages: &[ChatMessage]) -> u64 {
+    messages
+        .iter()
+        .map(|msg| estimate_tokens(msg.content.len()) + 4)
+        .sum()
+}
+
+/// Responses a race can return, with the usage needed to bill the winner.
+trait RaceOutput: Send {
+    fn reported_usage(&self) -> Option<&TokenUsage>;
+
+    fn output_chars(&self) -> usize;
+}
+
+impl RaceOutput for String {
+    fn reported_usage(&self) -> Option<&TokenUsage> {
+        None
+    }
+
+    fn output_chars(&self) -> usize {
+        self.len()
+    }
+}
+
+impl RaceOutput for ChatResponse {
+    fn reported_usage(&self) -> Option<&TokenUsage> {
+        self.usage.as_ref()
+    }
+
+    fn output_chars(&self) -> usize {
+        self.text.as_ref().map_or(0, String::len)
+            + self
+                .tool_calls
+                .iter()
+                .map(|call| call.name.len() + call.arguments.len())
+                .sum::<usize>()
+    }
+}
+
+/// Provider wrapper that races several providers and keeps the fastest answer.
+pub struct RacingProvider {
+    racers: Vec<Racer>,
+    hedge_delay: Duration,
+    context: Arc<RaceContext>,
+}
+
+impl RacingProvider {
+    /// Race `racers` in order. `hedge_delay_ms = 0` launches all at once.
+    pub fn new(racers: Vec<Racer>, hedge_delay_ms: u64, context: Arc<RaceContext>) -> Self {
+        Self {
+            racers,
+            hedge_delay: Duration::from_millis(hedge_delay_ms),
+            context,
+        }
+    }
+
+    fn lead(&self) -> Option<&Racer> {
+        self.racers.first()
+    }
+
+    /// How long to give `racer` before launching the next one.
+    fn hedge_delay_after(&self, racer: &Racer) -> Duration {
+        if self.hedge_delay.is_zero() {
+            return Duration::ZERO;
+        }
+        self.context
+            .latency_p95(&racer.name)
+            .unwrap_or(self.hedge_delay)
+    }
+
+    async fn race<'a, T, F>(
+        &'a self,
+        model: &'a str,
+        prompt_tokens: u64,
+        call: F,
+    ) -> anyhow::Result<T>
+    where
+        T: RaceOutput + 'a,
+        F: Fn(&'a dyn Provider, &'a str) -> BoxFuture<'a, anyhow::Result<T>>,
+    {
+        let mut in_flight = FuturesUnordered::new();
+        let mut running: Vec<usize> = Vec::new();
+        let mut failures = Vec::new();
+        let mut launched = 0;
+        let mut next_launch = Instant::now();
+
+        loop {
+            if launched < self.racers.len()
+                && (in_flight.is_empty() || Instant::now() >= next_launch)
+            {
+                let index = launched;
+                let racer = &self.racers[index];
+                let sent_model = racer.model.as_deref().unwrap_or(model);
+                let span =
+                    TraceSpan::start(format!("race attempt {}", racer.name), SpanKind::Client)
+                        .with_attr("gen_ai.provider.name", racer.name.as_str())
+                        .with_attr("gen_ai.request.model", sent_model)
+                        .with_attr("zeroclaw.race_position", index + 1);
+                let attempt = call(racer.provider.as_ref(), sent_model);
+                let started = Instant::now();
+                in_flight.push(
+                    async move {
+                        let result = span.instrument(attempt).await;
+                        if let Err(e) = &result {
+                            span.fail(&e.to_string());
+                        }
+                        (index, started.elapsed(), result)
+                    }
+                    .boxed(),
+                );
+                running.push(index);
+                next_launch = started + self.hedge_delay_after(racer);
+                launched += 1;
+                continue;
+            }
+
+            let finished = if launched < self.racers.len() {
+                tokio::select! {
+                    finished = in_flight.next() => finished,
+                    () = tokio::time::sleep_until(next_launch) => continue,
+                }
+            } else {
+                in_flight.next().await
+            };
+            let Some((index, elapsed, result)) = finished else {
+                break;
+            };
+            running.retain(|&i| i != index);
+            let racer = &self.racers[index];
+            let sent_model = racer.model.as_deref().unwrap_or(model);
+
+            match result {
+                Ok(response) => {
+                    self.context.record_latency(&racer.name, elapsed);
+                    let usage = response.reported_usage().cloned().unwrap_or(TokenUsage {
+                        input_tokens: Some(prompt_tokens),
+                        output_tokens: Some(estimate_tokens(response.output_chars())),
+                        ..TokenUsage::default()
+                    });
+                    self.context.record_cost(&racer.name, sent_model, &usage);
+
+                    // Cancelled requests have already been sent, so bill
+                    // their prompt even though no answer is read.
+                    for &loser in &running {
+                        let loser = &self.racers[loser];
+                        let loser_model = loser.model.as_deref().unwrap_or(model);
+                        let prompt_only = TokenUsage {
+                            input_tokens: Some(prompt_tokens),
+                            ..TokenUsage::default()
+                        };
+                        self.context
+                            .record_cost(&loser.name, loser_model, &prompt_only);
+                    }
+                    if index > 0 {
+                        tracing::info!(
+                            provider = racer.name.as_str(),
+                            model = sent_model,
+                            latency_ms = u64::try_from(elapsed.as_millis()).unwrap_or(u64::MAX),
+                            cancelled = running.len(),
+                            "Race won by hedged provider"
+                        );
+                    }
+                    return Ok(response);
+                }
+                Err(e) => {
+                    let error_detail = super::sanitize_api_error(&e.to_string());
+                    tracing::warn!(
+                        provider = racer.name.as_str(),
+                        model = sent_model,
+                        error = %error_detail,
+                        "Race attempt failed"
+                    );
+                    failures.push(format!(
+                        "provider={} model={sent_model}: {error_detail}",
+                        racer.name
+                    ));
+                }
+            }
+        }
+
+        anyhow::bail!(
+            "All raced providers failed. Attempts:\n{}",
+            failures.join("\n")
+        )
+    }
+}
+
+#[async_trait]
+impl Provider for RacingProvider {
+    fn capabilities(&self) -> super::traits::ProviderCapabilities {
+        self.lead()
+            .map(|racer| racer.provider.capabilities())
+            .unwrap_or_default()
+    }
+
+    fn supports_native_tools(&self) -> bool {
+        self.lead()
+            .is_some_and(|racer| racer.provider.supports_native_tools())
+    }
+
+    fn supports_vision(&self) -> bool {
+        self.lead()
+            .is_some_and(|racer| racer.provider.supports_vision())
+    }
+
+    async fn warmup(&self) -> anyhow::Result<()> {
+        for racer in &self.racers {
+            if racer.provider.warmup().await.is_err() {
+                tracing::warn!(provider = racer.name.as_str(), "Warmup failed (non-fatal)");
+            }
+        }
+        Ok(())
+    }
+
+    async fn chat_with_system(
+        &self,
+        system_prompt: Option<&str>,
+        message: &str,
+        model: &str,
+        temperature: f64,
+    ) -> anyhow::Result<String> {
+        let prompt_tokens = estimate_tokens(system_prompt.map_or(0, str::len) + message.len());
+        self.race(model, prompt_tokens, |provider, sent_model| {
+            provider.chat_with_system(system_prompt, message, sent_model, temperature)
+        })
+        .await
+    }
+
+    async fn chat_with_history(
+        &self,
+        messages: &[ChatMessage],
+        model: &str,
+        temperature: f64,
+    ) -> anyhow::Result<String> {
+        self.race(
+            model,
+            estimate_history_tokens(messages),
+            |provider, sent_model| provider.chat_with_history(messages, sent_model, temperature),
+        )
+        .await
+    }
+
+    async fn chat(
+        &self,
+        request: ChatRequest<'_>,
+        model: &str,
+        temperature: f64,
+    ) -> anyhow::Result<ChatResponse> {
+        self.race(
+            model,
+            estimate_history_tokens(request.messages),
+            |provider, sent_model| provider.chat(request, sent_model, temperature),
+        )
+        .await
+    }
+
+    async fn chat_with_tools(
+        &self,
+        messages: &[ChatMessage],
+        tools: &[serde_json::Value],
+        model: &str,
+        temperature: f64,
+    ) -> anyhow::Result<ChatResponse> {
+        self.race(
+            model,
+            estimate_history_tokens(messages),
+            |provider, sent_model| {
+                provider.chat_with_tools(messages, tools, sent_model, temperature)
+            },
+        )
+        .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::schema::{CostConfig, ModelPricing};
+    use crate::cost::CostTracker;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    struct DelayedProvider {
+        delay: Duration,
+        reply: Result<&'static str, &'static str>,
+        calls: Arc<AtomicUsize>,
+        models_seen: Arc<Mutex<Vec<String>>>,
+    }
+
+    impl DelayedProvider {
+        fn new(delay_ms: u64, reply: Result<&'static str, &'static str>) -> Self {
+            Self {
+                delay: Duration::from_millis(delay_ms),
+                reply,
+                calls: Arc::new(AtomicUsize::new(0)),
+                models_seen: Arc::new(Mutex::new(Vec::new())),
+            }
+        }
+    }
+
+    #[async_trait]
+    impl Provider for DelayedProvider {
+        async fn chat_with_system(
+            &self,
+            _system_prompt: Option<&str>,
+            _message: &str,
+            model: &str,
+            _temperature: f64,
+        ) -> anyhow::Result<String> {
+            self.calls.fetch_add(1, Ordering::SeqCst);
+            self.models_seen.lock().push(model.to_string());
+            tokio::time::sleep(self.delay).await;
+            match self.reply {
+                Ok(text) => Ok(text.to_string()),
+                Err(error) => anyhow::bail!(error),
+            }
+        }
+
+        async fn chat(
+            &self,
+            request: ChatRequest<'_>,
+            model: &str,
+            temperature: f64,
+        ) -> anyhow::Result<ChatResponse> {
+            let text = self
+                .chat_with_system(None, &request.messages[0].content, model, temperature)
+                .await?;
+            Ok(ChatResponse {
+                text: Some(text),
+                tool_calls: Vec::new(),
+                usage: Some(TokenUsage {
+                    input_tokens: Some(1_000_000),
+                    output_tokens: Some(1_000_000),
+                    ..TokenUsage::default()
+                }),
+                reasoning_content: None,
+            })
+        }
+    }
+
+    fn racer(name: &str, provider: DelayedProvider) -> (Racer, Arc<AtomicUsize>) {
+        let calls = Arc::clone(&provider.calls);
+        (Racer::new(name, Arc::new(provider)), calls)
+    }
+
+    #[tokio::test]
+    async fn parallel_race_returns_fastest_response() {
+        let (slow, slow_calls) = racer("slow", DelayedProvider::new(2_000, Ok("slow")));
+        let (fast, fast_calls) = racer("fast", DelayedProvider::new(10, Ok("fast")));
+        let provider = RacingProvider::new(vec![slow, fast], 0, Arc::new(RaceContext::new()));
+
+        let started = Instant::now();
+        let reply = provider.simple_chat("hi", "m", 0.0).await.unwrap();
+
+        assert_eq!(reply, "fast");
+        assert!(started.elapsed() < Duration::from_secs(1));
+        assert_eq!(slow_calls.load(Ordering::SeqCst), 1);
+        assert_eq!(fast_calls.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn hedged_racer_is_not_launched_when_lead_answers_in_time() {
+        let (lead, _) = racer("lead", DelayedProvider::new(10, Ok("lead")));
+        let (backup, backup_calls) = racer("backup", DelayedProvider::new(10, Ok("backup")));
+        let provider = RacingProvider::new(vec![lead, backup], 500, Arc::new(RaceContext::new()));
+
+        let reply = provider.simple_chat("hi", "m", 0.0).await.unwrap();
+
+        assert_eq!(reply, "lead");
+        assert_eq!(backup_calls.load(Ordering::SeqCst), 0);
+    }
+
+    #[tokio::test]
+    async fn hedged_racer_wins_when_lead_is_slow() {
+        let (lead, _) = racer("lead", DelayedProvider::new(2_000, Ok("lead")));
+        let backup = DelayedProvider::new(10, Ok("backup"));
+        let models_seen = Arc::clone(&backup.models_seen);
+        let backup = Racer::new("backup", Arc::new(backup)).with_model("backup-model");
+        let provider = RacingProvider::new(vec![lead, backup], 50, Arc::new(RaceContext::new()));
+
+        let started = Instant::now();
+        let reply = provider.simple_chat("hi", "lead-model", 0.0).await.unwrap();
+
+        assert_eq!(reply, "backup");
+        assert!(started.elapsed() >= Duration::from_millis(50));
+        assert!(started.elapsed() < Duration::from_secs(1));
+        assert_eq!(*models_seen.lock(), vec!["backup-model".to_string()]);
+    }
+
+    #[tokio::test]
+    async fn failed_lead_launches_next_racer_without_waiting() {
+        let (lead, _) = racer("lead", DelayedProvider::new(0, Err("500 unavailable")));
+        let (backup, _) = racer("backup", DelayedProvider::new(0, Ok("backup")));
+        let provider =
+            RacingProvider::new(vec![lead, backup], 10_000, Arc::new(RaceContext::new()));
+
+        let started = Instant::now();
+        let reply = provider.simple_chat("hi", "m", 0.0).await.unwrap();
+
+        assert_eq!(reply, "backup");
+        assert!(started.elapsed() < Duration::from_secs(1));
+    }
+
+    #[tokio::test]
+    async fn all_failures_are_reported() {
+        let (lead, _) = racer("lead", DelayedProvider::new(0, Err("lead down")));
+        let (backup, _) = racer("backup", DelayedProvider::new(0, Err("backup down")));
+        let provider = RacingProvider::new(vec![lead, backup], 0, Arc::new(RaceContext::new()));
+
+        let err = provider
+            .simple_chat("hi", "m", 0.0)
+            .await
+            .unwrap_err()
+            .to_string();
+
+        assert!(err.contains("All raced providers failed"));
+        assert!(err.contains("provider=lead model=m: lead down"));
+        assert!(err.contains("provider=backup model=m: backup down"));
+    }
+
+    #[test]
+    fn hedge_delay_adapts_to_observed_p95() {
+        let context = Arc::new(RaceContext::new());
+        let (lead, _) = racer("lead", DelayedProvider::new(0, Ok("lead")));
+        let provider = RacingProvider::new(vec![lead], 400, Arc::clone(&context));
+
+        for ms in 1..MIN_LATENCY_SAMPLES as u64 {
+            context.record_latency("lead", Duration::from_millis(ms * 10));
+        }
+        assert_eq!(context.latency_p95("lead"), None);
+        assert_eq!(
+            provider.hedge_delay_after(&provider.racers[0]),
+            Duration::from_millis(400)
+        );
+
+        for ms in 1..=100 {
+            context.record_latency("lead", Duration::from_millis(ms));
+        }
+        assert_eq!(context.latency_p95("lead"), Some(Duration::from_millis(95)));
+        assert_eq!(
+            provider.hedge_delay_after(&provider.racers[0]),
+            Duration::from_millis(95)
+        );
+    }
+
+    #[test]
+    fn zero_hedge_delay_ignores_latency_history() {
+        let context = Arc::new(RaceContext::new());
+        for _ in 0..MIN_LATENCY_SAMPLES {
+            context.record_latency("lead", Duration::from_millis(300));
+        }
+        let (lead, _) = racer("lead", DelayedProvider::new(0, Ok("lead")));
+        let provider = RacingProvider::new(vec![lead], 0, context);
+
+        assert_eq!(
+            provider.hedge_delay_after(&provider.racers[0]),
+            Duration::ZERO
+        );
+    }
+
+    #[tokio::test]
+    async fn every_attempt_is_billed() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let config = CostConfig {
+            enabled: true,
+            ..CostConfig::default()
+        };
+        let tracker = Arc::new(CostTracker::new(config, tmp.path()).unwrap());
+        let prices = HashMap::from([
+            (
+                "slow/slow-model".to_string(),
+                ModelPricing {
+                    input: 2.0,
+                    output: 8.0,
+                },
+            ),
+            (
+                "fast-model".to_string(),
+                ModelPricing {
+                    input: 1.0,
+                    output: 4.0,
+                },
+            ),
+        ]);
+        let context = Arc::new(RaceContext::new().with_usage_recorder(Arc::new(
+            UsageRecorder::new(Arc::clone(&tracker), prices, tmp.path()),
+        )));
+        let (slow, _) = racer("slow", DelayedProvider::new(2_000, Ok("slow")));
+        let (fast, _) = racer("fast", DelayedProvider::new(10, Ok("fast")));
+        let provider = RacingProvider::new(
+            vec![slow.with_model("slow-model"), fast.with_model("fast-model")],
+            0,
+            context,
+        );
+
+        let messages = vec![ChatMessage::user("x".repeat(4_000))];
+        let request = ChatRequest {
+            messages: &messages,
+            tools: None,
+            response_format: None,
+            params: None,
+        };
+        let response = provider.chat(request, "m", 0.0).await.unwrap();
+        assert_eq!(response.text.as_deref(), Some("fast"));
+
+        let summary = tracker.get_summary().unwrap();
+        assert_eq!(summary.request_count, 2);
+        // Winner: reported 1M input + 1M output tokens at $1/$4.
+        let fast = &summary.by_model["fast/fast-model"];
+        assert!(
+            (fast.cost_usd - 5.0).abs() < 1e-9,
+            "fast cost {}",
+            fast.cost_usd
+        );
+        // Cancelled: the ~1,004-token prompt at $2 per 1M input tokens.
+        let slow = &summary.by_model["slow/slow-model"];
+        assert_eq!(slow.total_tokens, 1_004);
+        assert!(
+            (slow.cost_usd - 0.002_008).abs() < 1e-9,
+            "slow cost {}",
+            slow.cost_usd
+        );
+    }
+}
@@ -1,7 +1,8 @@
 use super::traits::{
-    ChatMessage, ChatRequest, ChatResponse, StreamChunk, StreamOptions, StreamResult,
+    ChatMessage, ChatRequest, ChatResponse, StreamChunk, StreamEvent, StreamOptions, StreamResult,
 };
 use super::Provider;
+use crate::observability::{SpanKind, TraceSpan};
 use async_trait::async_trait;
 use futures_util::{stream, StreamExt};
 use std::collections::{HashMap, HashSet};
@@ -211,6 +212,16 @@ fn push_failure(
     ));
 }
 
+/// Trace span for a single (provider, model) attempt. Retries and fallbacks
+/// show up as sibling spans under the caller's `chat` span.
+fn attempt_span(provider_name: &str, model: &str, attempt: u32, fallback: bool) -> TraceSpan {
+    TraceSpan::start(format!("chat attempt {provider_name}"), SpanKind::Client)
+        .with_attr("gen_ai.provider.name", provider_name)
+        .with_attr("gen_ai.request.model", model)
+        .with_attr("zeroclaw.attempt", attempt + 1)
+        .with_attr("zeroclaw.fallback", fallback)
+}
+
 // ── Resilient Provider Wrapper ────────────────────────────────────────────
 // Three-level failover strategy: model chain → provider chain → retry loop.
 //   Outer loop:  iterate model fallback chain (original model first, then
@@ -383,8 +394,19 @@ impl Provider for ReliableProvider {
                     let mut backoff_ms = self.base_backoff_ms;
 
                     for attempt in 0..=self.max_retries {
-                        match provider
-                            .chat_with_system(system_prompt, message, sent_model, temperature)
+                        let span = attempt_span(
+                            provider_name,
+                            sent_model,
+                            attempt,
+                            provider_index > 0 || sent_model != model,
+                        );
+                        match span
+                            .instrument(provider.chat_with_system(
+                                system_prompt,
+                                message,
+                                sent_model,
+                                temperature,
+                            ))
                             .await
                         {
                             Ok(resp) => {
@@ -406,6 +428,7 @@ impl Provider for ReliableProvider {
                                 let rate_limited = is_rate_limited(&e);
                                 let failure_reason = failure_reason(rate_limited, non_retryable);
                                 let error_detail = compact_error_detail(&e);
+                                span.fail(&error_detail);
 
                                 push_failure(
                                     &mut failures,
@@ -508,8 +531,18 @@ impl Provider for ReliableProvider {
                     let mut backoff_ms = self.base_backoff_ms;
 
                     for attempt in 0..=self.max_retries {
-                        match provider
-                            .chat_with_history(messages, sent_model, temperature)
+                        let span = attempt_span(
+                            provider_name,
+                            sent_model,
+                            attempt,
+                            provider_index > 0 || sent_model != model,
+                        );
+                        match span
+                            .instrument(provider.chat_with_history(
+                                messages,
+                                sent_model,
+                                temperature,
+                            ))
                             .await
                         {
                             Ok(resp) => {
@@ -531,6 +564,7 @@ impl Provider for ReliableProvider {
                                 let rate_limited = is_rate_limited(&e);
                                 let failure_reason = failure_reason(rate_limited, non_retryable);
                                 let error_detail = compact_error_detail(&e);
+                                span.fail(&error_detail);
 
                                 push_failure(
                                     &mut failures,
@@ -639,8 +673,19 @@ impl Provider for ReliableProvider {
                     let mut backoff_ms = self.base_backoff_ms;
 
                     for attempt in 0..=self.max_retries {
-                        match provider
-                            .chat_with_tools(messages, tools, sent_model, temperature)
+                        let span = attempt_span(
+                            provider_name,
+                            sent_model,
+                            attempt,
+                            provider_index > 0 || sent_model != model,
+                        );
+                        match span
+                            .instrument(provider.chat_with_tools(
+                                messages,
+                                tools,
+                                sent_model,
+                                temperature,
+                            ))
                             .await
                         {
                             Ok(resp) => {
@@ -662,6 +707,7 @@ impl Provider for ReliableProvider {
                                 let rate_limited = is_rate_limited(&e);
                                 let failure_reason = failure_reason(rate_limited, non_retryable);
                                 let error_detail = compact_error_detail(&e);
+                                span.fail(&error_detail);
 
                                 push_failure(
                                     &mut failures,
@@ -757,8 +803,19 @@ impl Provider for ReliableProvider {
                         let req = ChatRequest {
                             messages: request.messages,
                             tools: request.tools,
+                            response_format: request.response_format,
+                            params: None,
                         };
-                        match provider.chat(req, sent_model, temperature).await {
+                        let span = attempt_span(
+                            provider_name,
+                            sent_model,
+                            attempt,
+                            provider_index > 0 || sent_model != model,
+                        );
+                        match span
+                            .instrument(provider.chat(req, sent_model, temperature))
+                            .await
+                        {
                             Ok(resp) => {
                                 if attempt > 0 || sent_model != model {
                                     tracing::info!(
@@ -778,6 +835,7 @@ impl Provider for ReliableProvider {
                                 let rate_limited = is_rate_limited(&e);
                                 let failure_reason = failure_reason(rate_limited, non_retryable);
                                 let error_detail = compact_error_detail(&e);
+                                span.fail(&error_detail);
 
                                 push_failure(
                                     &mut failures,
@@ -939,6 +997,47 @@ impl Provider for ReliableProvider {
         })
         .boxed()
     }
+
+    fn supports_stream_events(&self) -> bool {
+        self.providers
+            .first()
+            .is_some_and(|(_, p)| p.supports_stream_events())
+    }
+
+    fn stream_chat(
+        &self,
+        request: ChatRequest<'_>,
+        model: &str,
+        temperature: f64,
+    ) -> stream::BoxStream<'static, StreamResult<StreamEvent>> {
+        // Event streams go to the primary provider only, attempted once: a
+        // fallback provider may not know the requested model. Callers retry
+        // through `chat`, which applies the full retry/fallback chain.
+        let Some((provider_name, provider)) = self
+            .providers
+            .first()
+            .filter(|(_, p)| p.supports_stream_events())
+        else {
+            return super::streaming::error_stream(super::traits::StreamError::Provider(
+                "Primary provider does not support stream events".to_string(),
+            ));
+        };
+
+        let provider_name = provider_name.clone();
+        let model_name = model.to_string();
+        provider
+            .stream_chat(request, model, temperature)
+            .inspect(move |event| {
+                if let Err(e) = event {
+                    tracing::warn!(
+                        provider = provider_name,
+                        model = model_name,
+                        "Streaming error: {e}"
+                    );
+                }
+            })
+            .boxed()
+    }
 }
 
 #[cfg(test)]
@@ -1838,6 +1937,8 @@ mod tests {
         let request = ChatRequest {
             messages: &messages,
             tools: None,
+            response_format: None,
+            params: None,
         };
         let result = provider.chat(request, "test-model", 0.0).await.unwrap();
 
@@ -1874,6 +1975,8 @@ mod tests {
         let request = ChatRequest {
             messages: &messages,
             tools: None,
+            response_format: None,
+            params: None,
         };
         let result = provider.chat(request, "test-model", 0.0).await.unwrap();
 
@@ -1945,6 +2048,8 @@ mod tests {
         let request = ChatRequest {
             messages: &messages,
             tools: None,
+            response_format: None,
+            params: None,
         };
         let err = provider
             .chat(request, "test", 0.0)
@@ -2061,6 +2166,8 @@ mod tests {
         let request = ChatRequest {
             messages: &messages,
             tools: None,
+            response_format: None,
+            params: None,
         };
         let result = provider.chat(request, "claude-opus", 0.0).await.unwrap();
         assert_eq!(result.text.as_deref(), Some("ok from sonnet"));
@@ -2109,6 +2216,8 @@ mod tests {
         let request = ChatRequest {
             messages: &messages,
             tools: None,
+            response_format: None,
+            params: None,
         };
         let result = provider.chat(request, "test", 0.0).await.unwrap();
         assert_eq!(result.text.as_deref(), Some("from fallback"));
@@ -1,6 +1,9 @@
-use super::traits::{ChatMessage, ChatRequest, ChatResponse};
+use super::traits::{
+    ChatMessage, ChatRequest, ChatResponse, GenerationParams, StreamEvent, StreamResult,
+};
 use super::Provider;
 use async_trait::async_trait;
+use futures_util::stream;
 use std::collections::HashMap;
 
 /// A single route: maps a task hint to a provider + model combo.
@@ -8,6 +11,16 @@ use std::collections::HashMap;
 pub struct Route {
     pub provider_name: String,
     pub model: String,
+    /// Generation defaults for requests sent through this route. Parameters
+    /// set on the request itself take precedence.
+    pub params: GenerationParams,
+}
+
+/// A route resolved against the provider list.
+struct ResolvedRoute {
+    provider_index: usize,
+    model: String,
+    params: GenerationParams,
 }
 
 /// Multi-model router — routes requests to different provider+model combos
@@ -19,7 +32,7 @@ pub struct Route {
 ///
 /// This wraps multiple pre-created providers and selects the right one per request.
 pub struct RouterProvider {
-    routes: HashMap<String, (usize, String)>, // hint → (provider_index, model)
+    routes: HashMap<String, ResolvedRoute>,
     providers: Vec<(String, Box<dyn Provider>)>,
     default_index: usize,
     default_model: String,
@@ -45,12 +58,19 @@ impl RouterProvider {
             .collect();
 
         // Resolve routes to provider indices
-        let resolved_routes: HashMap<String, (usize, String)> = routes
+        let resolved_routes: HashMap<String, ResolvedRoute> = routes
             .into_iter()
             .filter_map(|(hint, route)| {
                 let index = name_to_index.get(route.provider_name.as_str()).copied();
                 match index {
-                    Some(i) => Some((hint, (i, route.model))),
+                    Some(provider_index) => Some((
+                        hint,
+                        ResolvedRoute {
+                            provider_index,
+                            model: route.model,
+                            params: route.params,
+                        },
+                    )),
                     None => {
                         tracing::warn!(
                             hint = hint,
@@ -85,8 +105,8 @@ impl RouterProvider {
     /// Resolve a model parameter to a (provider_index, actual_model) pair.
     fn resolve(&self, model: &str) -> (usize, String) {
         if let Some(hint) = model.strip_prefix("hint:") {
-            if let Some((idx, resolved_model)) = self.routes.get(hint) {
-                return (*idx, resolved_model.clone());
+            if let Some(route) = self.routes.get(hint) {
+                return (route.provider_index, route.model.clone());
             }
             tracing::warn!(
                 hint = hint,
@@ -97,6 +117,24 @@ impl RouterProvider {
         // Not a hint or hint not found — use default provider with the model as-is
         (self.default_index, model.to_string())
     }
+
+    /// Generation parameters for a request sent with `model`: the request's
+    /// own parameters over the route defaults. `None` keeps the request's
+    /// parameters unchanged.
+    fn route_params(
+        &self,
+        model: &str,
+        request_params: Option<&GenerationParams>,
+    ) -> Option<GenerationParams> {
+        let route = self.routes.get(model.strip_prefix("hint:")?)?;
+        if route.params.is_empty() {
+            return None;
+        }
+        Some(request_params.map_or_else(
+            || route.params.clone(),
+            |params| params.with_defaults(&route.params),
+        ))
+    }
 }
 
 #[async_trait]
@@ -143,6 +181,11 @@ impl Provider for RouterProvider {
     ) -> anyhow::Result<ChatResponse> {
         let (provider_idx, resolved_model) = self.resolve(model);
         let (_, provider) = &self.providers[provider_idx];
+        let params = self.route_params(model, request.params);
+        let request = ChatRequest {
+            params: params.as_ref().or(request.params),
+            ..request
+        };
         provider.chat(request, &resolved_model, temperature).await
     }
 
@@ -167,6 +210,29 @@ impl Provider for RouterProvider {
             .unwrap_or(false)
     }
 
+    fn supports_stream_events(&self) -> bool {
+        self.providers
+            .get(self.default_index)
+            .map(|(_, p)| p.supports_stream_events())
+            .unwrap_or(false)
+    }
+
+    fn stream_chat(
+        &self,
+        request: ChatRequest<'_>,
+        model: &str,
+        temperature: f64,
+    ) -> stream::BoxStream<'static, StreamResult<StreamEvent>> {
+        let (provider_idx, resolved_model) = self.resolve(model);
+        let (_, provider) = &self.providers[provider_idx];
+        let params = self.route_params(model, request.params);
+        let request = ChatRequest {
+            params: params.as_ref().or(request.params),
+            ..request
+        };
+        provider.stream_chat(request, &resolved_model, temperature)
+    }
+
     fn supports_vision(&self) -> bool {
         self.vision_override.unwrap_or_else(|| {
             self.providers
@@ -259,6 +325,7 @@ mod tests {
                     Route {
                         provider_name: provider_name.to_string(),
                         model: model.to_string(),
+                        params: GenerationParams::default(),
                     },
                 )
             })
@@ -375,6 +442,44 @@ mod tests {
         assert_eq!(model, "claude-opus");
     }
 
+    #[test]
+    fn route_params_fill_in_request_params() {
+        let providers: Vec<(String, Box<dyn Provider>)> =
+            vec![("default".into(), Box::new(MockProvider::new("ok")))];
+        let router = RouterProvider::new(
+            providers,
+            vec![(
+                "classify".into(),
+                Route {
+                    provider_name: "default".into(),
+                    model: "small-model".into(),
+                    params: GenerationParams {
+                        max_tokens: Some(16),
+                        seed: Some(1),
+                        ..GenerationParams::default()
+                    },
+                },
+            )],
+            "default-model".into(),
+        );
+
+        let route_only = router.route_params("hint:classify", None).unwrap();
+        assert_eq!(route_only.max_tokens, Some(16));
+
+        let request = GenerationParams {
+            max_tokens: Some(64),
+            ..GenerationParams::default()
+        };
+        let merged = router
+            .route_params("hint:classify", Some(&request))
+            .unwrap();
+        assert_eq!(merged.max_tokens, Some(64));
+        assert_eq!(merged.seed, Some(1));
+
+        assert!(router.route_params("small-model", Some(&request)).is_none());
+        assert!(router.route_params("hint:unknown", None).is_none());
+    }
+
     #[test]
     fn skips_routes_with_unknown_provider() {
         let (router, _) = make_router(
@@ -0,0 +1,356 @@
+//! Typed streaming support shared by provider adapters.
+//!
+//! Adapters build their HTTP request up front and hand it to
+//! [`event_stream`] together with a [`StreamParser`] that translates body
+//! lines (SSE `data:` lines or NDJSON) into [`StreamEvent`]s. Consumers fold
+//! the events back into a [`ChatResponse`] with [`StreamAccumulator`].
+
+use super::traits::{ChatResponse, StreamError, StreamEvent, StreamResult, TokenUsage, ToolCall};
+use futures_util::{stream, StreamExt};
+use std::collections::BTreeMap;
+use tokio::sync::mpsc;
+
+/// Translates response body lines into stream events.
+pub(crate) trait StreamParser: Send + 'static {
+    /// Handle one complete line of the response body.
+    fn parse_line(&mut self, line: &str) -> StreamResult<Vec<StreamEvent>>;
+
+    /// Events owed when the body ends, such as ends of still-open tool calls.
+    fn finish(&mut self) -> Vec<StreamEvent> {
+        Vec::new()
+    }
+}
+
+/// Splits a byte stream into lines, holding partial lines (and partial UTF-8
+/// sequences) until the rest arrives.
+#[derive(Debug, Default)]
+pub(crate) struct LineBuffer {
+    pending: Vec<u8>,
+}
+
+impl LineBuffer {
+    /// Append bytes and return the lines they complete, without terminators.
+    pub(crate) fn push(&mut self, bytes: &[u8]) -> Vec<String> {
+        self.pending.extend_from_slice(bytes);
+        let mut lines = Vec::new();
+        while let Some(pos) = self.pending.iter().position(|b| *b == b'\n') {
+            let line: Vec<u8> = self.pending.drain(..=pos).collect();
+            lines.push(
+                String::from_utf8_lossy(&line)
+                    .trim_end_matches(['\r', '\n'])
+                    .to_string(),
+            );
+        }
+        lines
+    }
+
+    /// Take the trailing line that was never terminated, if any.
+    pub(crate) fn finish(&mut self) -> Option<String> {
+        let rest = std::mem::take(&mut self.pending);
+        let line = String::from_utf8_lossy(&rest).trim_end().to_string();
+        (!line.is_empty()).then_some(line)
+    }
+}
+
+/// Payload of an SSE `data:` line. Other fields, comments and the `[DONE]`
+/// sentinel yield `None`.
+pub(crate) fn sse_data(line: &str) -> Option<&str> {
+    let data = line.strip_prefix("data:")?.trim();
+    (!data.is_empty() && data != "[DONE]").then_some(data)
+}
+
+/// A stream that yields a single error.
+pub(crate) fn error_stream(
+    error: StreamError,
+) -> stream::BoxStream<'static, StreamResult<StreamEvent>> {
+    stream::once(async move { Err(error) }).boxed()
+}
+
+/// Send `request` and stream the events `parser` produces from its body.
+///
+/// Non-success statuses become [`StreamError::Provider`] with a sanitized
+/// body. A stream that completes without error ends with [`StreamEvent::Done`].
+pub(crate) fn event_stream<P: StreamParser>(
+    provider: &str,
+    request: reqwest::RequestBuilder,
+    mut parser: P,
+) -> stream::BoxStream<'static, StreamResult<StreamEvent>> {
+    let provider = provider.to_string();
+    let (tx, rx) = mpsc::channel::<StreamResult<StreamEvent>>(100);
+
+    tokio::spawn(async move {
+        let response = match request.send().await {
+            Ok(response) => response,
+            Err(e) => {
+                let _ = tx.send(Err(StreamError::Http(e))).await;
+                return;
+            }
+        };
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let body = response.text().await.unwrap_or_default();
+            let _ = tx
+                .send(Err(StreamError::Provider(format!(
+                    "{provider} API error ({status}): {}",
+                    super::sanitize_api_error(&body)
+                ))))
+                .await;
+            return;
+        }
+
+        let mut lines = LineBuffer::default();
+        let mut body = response.bytes_stream();
+        while let Some(item) = body.next().await {
+            let bytes = match item {
+                Ok(bytes) => bytes,
+                Err(e) => {
+                    let _ = tx.send(Err(StreamError::Http(e))).await;
+                    return;
+                }
+            };
+            for line in lines.push(&bytes) {
+                if !forward(&tx, parser.parse_line(&line)).await {
+                    return;
+                }
+            }
+        }
+        if let Some(line) = lines.finish() {
+            if !forward(&tx, parser.parse_line(&line)).await {
+                return;
+            }
+        }
+
+        let mut tail = parser.finish();
+        tail.push(StreamEvent::Done);
+        forward(&tx, Ok(tail)).await;
+    });
+
+    stream::unfold(rx, |mut rx| async move {
+        rx.recv().await.map(|event| (event, rx))
+    })
+    .boxed()
+}
+
+/// Forward parsed events. Returns `false` once the stream should stop.
+async fn forward(
+    tx: &mpsc::Sender<StreamResult<StreamEvent>>,
+    events: StreamResult<Vec<StreamEvent>>,
+) -> bool {
+    match events {
+        Ok(events) => {
+            for event in events {
+                if tx.send(Ok(event)).await.is_err() {
+                    return false; // Receiver dropped
+                }
+            }
+            true
+        }
+        Err(e) => {
+            let _ = tx.send(Err(e)).await;
+            false
+        }
+    }
+}
+
+/// Folds stream events into a [`ChatResponse`].
+#[derive(Debug, Default)]
+pub struct StreamAccumulator {
+    text: String,
+    reasoning: String,
+    tool_calls: BTreeMap<usize, ToolCall>,
+    usage: Option<TokenUsage>,
+}
+
+impl StreamAccumulator {
+    pub fn push(&mut self, event: &StreamEvent) {
+        match event {
+            StreamEvent::TextDelta(text) => self.text.push_str(text),
+            StreamEvent::ReasoningDelta(text) => self.reasoning.push_str(text),
+            StreamEvent::ToolCallStart { index, id, name } => {
+                self.tool_calls.insert(
+                    *index,
+                    ToolCall {
+                        id: id.clone(),
+                        name: name.clone(),
+                        arguments: String::new(),
+                    },
+                );
+            }
+            StreamEvent::ToolCallDelta { index, arguments } => {
+                if let Some(call) = self.tool_calls.get_mut(index) {
+                    call.arguments.push_str(arguments);
+                }
+            }
+            StreamEvent::Usage(usage) => {
+                let merged = self.usage.get_or_insert_with(TokenUsage::default);
+                if usage.input_tokens.is_some() {
+                    merged.input_tokens = usage.input_tokens;
+                }
+                if usage.output_tokens.is_some() {
+                    merged.output_tokens = usage.output_tokens;
+                }
+                if usage.cache_read_tokens.is_some() {
+                    merged.cache_read_tokens = usage.cache_read_tokens;
+                }
+                if usage.cache_write_tokens.is_some() {
+                    merged.cache_write_tokens = usage.cache_write_tokens;
+                }
+            }
+            StreamEvent::ToolCallEnd { .. } | StreamEvent::Done => {}
+        }
+    }
+
+    /// Text received so far.
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    /// Build the final response. Calls that streamed no arguments get `{}`;
+    /// invalid or truncated argument JSON is kept as received so the caller
+    /// can reject or retry the call instead of running it with no arguments.
+    pub fn finish(self) -> ChatResponse {
+        let tool_calls = self
+            .tool_calls
+            .into_values()
+            .map(|mut call| {
+                if call.arguments.trim().is_empty() {
+                    call.arguments = "{}".to_string();
+                } else if serde_json::from_str::<serde::de::IgnoredAny>(&call.arguments).is_err() {
+                    tracing::warn!(
+                        function = %call.name,
+                        arguments = %call.arguments,
+                        "Invalid JSON in streamed tool-call arguments"
+                    );
+                }
+                call
+            })
+            .collect();
+
+        ChatResponse {
+            text: (!self.text.is_empty()).then_some(self.text),
+            tool_calls,
+            usage: self.usage,
+            reasoning_content: (!self.reasoning.is_empty()).then_some(self.reasoning),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn line_buffer_joins_lines_split_across_chunks() {
+        let mut buffer = LineBuffer::default();
+        assert!(buffer.push(b"data: {\"a\"").is_empty());
+        assert_eq!(
+            buffer.push(b": 1}\r\n\ndata: x"),
+            vec!["data: {\"a\": 1}", ""]
+        );
+        assert_eq!(buffer.finish(), Some("data: x".to_string()));
+        assert_eq!(buffer.finish(), None);
+    }
+
+    #[test]
+    fn line_buffer_keeps_multibyte_characters_split_across_chunks() {
+        let bytes = "héllo\n".as_bytes();
+        let mut buffer = LineBuffer::default();
+        assert!(buffer.push(&bytes[..2]).is_empty());
+        assert_eq!(buffer.push(&bytes[2..]), vec!["héllo"]);
+    }
+
+    #[test]
+    fn sse_data_skips_non_data_lines_and_done_sentinel() {
+        assert_eq!(sse_data("data: {\"x\":1}"), Some("{\"x\":1}"));
+        assert_eq!(sse_data("data:[DONE]"), None);
+        assert_eq!(sse_data("event: message_start"), None);
+        assert_eq!(sse_data(": keep-alive"), None);
+        assert_eq!(sse_data(""), None);
+    }
+
+    #[test]
+    fn accumulator_assembles_text_reasoning_tool_calls_and_usage() {
+        let mut acc = StreamAccumulator::default();
+        for event in [
+            StreamEvent::ReasoningDelta("Need the ".into()),
+            StreamEvent::ReasoningDelta("date.".into()),
+            StreamEvent::TextDelta("Checking".into()),
+            StreamEvent::Usage(TokenUsage {
+                input_tokens: Some(12),
+                output_tokens: None,
+                cache_read_tokens: None,
+                cache_write_tokens: None,
+            }),
+            StreamEvent::ToolCallStart {
+                index: 1,
+                id: "call_b".into(),
+                name: "file_read".into(),
+            },
+            StreamEvent::ToolCallStart {
+                index: 0,
+                id: "call_a".into(),
+                name: "shell".into(),
+            },
+            StreamEvent::ToolCallDelta {
+                index: 0,
+                arguments: "{\"command\":".into(),
+            },
+            StreamEvent::ToolCallDelta {
+                index: 0,
+                arguments: "\"date\"}".into(),
+            },
+            StreamEvent::ToolCallEnd { index: 0 },
+            StreamEvent::ToolCallEnd { index: 1 },
+            StreamEvent::Usage(TokenUsage {
+                input_tokens: None,
+                output_tokens: Some(7),
+                cache_read_tokens: None,
+                cache_write_tokens: None,
+            }),
+            StreamEvent::Done,
+        ] {
+            acc.push(&event);
+        }
+        assert_eq!(acc.text(), "Checking");
+
+        let response = acc.finish();
+        assert_eq!(response.text.as_deref(), Some("Checking"));
+        assert_eq!(
+            response.reasoning_content.as_deref(),
+            Some("Need the date.")
+        );
+        assert_eq!(response.tool_calls.len(), 2);
+        assert_eq!(response.tool_calls[0].id, "call_a");
+        assert_eq!(response.tool_calls[0].arguments, r#"{"command":"date"}"#);
+        assert_eq!(response.tool_calls[1].name, "file_read");
+        assert_eq!(response.tool_calls[1].arguments, "{}");
+        assert_eq!(
+            response.usage,
+            Some(TokenUsage {
+                input_tokens: Some(12),
+                output_tokens: Some(7),
+                cache_read_tokens: None,
+                cache_write_tokens: None,
+            })
+        );
+    }
+
+    #[test]
+    fn accumulator_keeps_truncated_arguments_as_received() {
+        let mut acc = StreamAccumulator::default();
+        acc.push(&StreamEvent::ToolCallStart {
+            index: 0,
+            id: "call_a".into(),
+            name: "shell".into(),
+        });
+        acc.push(&StreamEvent::ToolCallDelta {
+            index: 0,
+            arguments: "{\"command\": \"da".into(),
+        });
+
+        let response = acc.finish();
+        assert!(response.text.is_none());
+        assert_eq!(response.tool_calls[0].arguments, "{\"command\": \"da");
+    }
+}
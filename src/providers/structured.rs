@@ -0,0 +1,278 @@
+//! Schema-constrained chat helpers.
+//!
+//! [`chat_structured`] sends a [`ResponseFormat`] derived from a `schemars`
+//! type, then validates the reply by deserializing it. Replies that do not
+//! match are sent back to the model with the validation error so it can
+//! repair them. Native structured output makes repairs rare; providers that
+//! only receive prompt instructions rely on them.
+
+use super::traits::{ChatMessage, ChatRequest, Provider, ResponseFormat};
+use anyhow::Result;
+use schemars::JsonSchema;
+use serde::de::DeserializeOwned;
+
+/// Repair rounds [`chat_structured`] callers use unless they need otherwise.
+pub const DEFAULT_MAX_REPAIRS: usize = 2;
+
+/// Maximum characters of an invalid reply quoted in the final error.
+const MAX_ERROR_REPLY_CHARS: usize = 200;
+
+/// Build the response format for `T`, named after its schema.
+pub fn response_format_for<T: JsonSchema>() -> ResponseFormat {
+    let name: String = T::schema_name()
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '_' || c == '-' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    ResponseFormat::for_type::<T>(name)
+}
+
+/// Locate the JSON value in a model reply.
+///
+/// Accepts bare JSON, JSON inside a markdown code fence, and JSON surrounded
+/// by prose (first opening brace/bracket to the last matching closer).
+pub fn extract_json(text: &str) -> Option<&str> {
+    let trimmed = text.trim();
+    if trimmed.is_empty() {
+        return None;
+    }
+    if serde_json::from_str::<serde::de::IgnoredAny>(trimmed).is_ok() {
+        return Some(trimmed);
+    }
+
+    if let Some(start) = trimmed.find("```") {
+        let after = &trimmed[start + 3..];
+        let body_start = after.find('\n').map_or(0, |i| i + 1);
+        let body = &after[body_start..];
+        if let Some(end) = body.find("```") {
+            let inner = body[..end].trim();
+            if !inner.is_empty() {
+                return Some(inner);
+            }
+        }
+    }
+
+    let start = trimmed.find(['{', '['])?;
+    let closer = if trimmed[start..].starts_with('{') {
+        '}'
+    } else {
+        ']'
+    };
+    let end = trimmed.rfind(closer)?;
+    (end > start).then(|| &trimmed[start..=end])
+}
+
+/// Parse a model reply into `T`.
+pub fn parse_structured<T: DeserializeOwned>(text: &str) -> Result<T> {
+    let json = extract_json(text).ok_or_else(|| anyhow::anyhow!("reply contains no JSON"))?;
+    serde_json::from_str(json).map_err(|e| anyhow::anyhow!("reply does not match schema: {e}"))
+}
+
+/// Chat with a JSON Schema response format and return the validated value.
+///
+/// Invalid replies are retried up to `max_repairs` times with the validation
+/// error appended to the conversation.
+pub async fn chat_structured<T: JsonSchema + DeserializeOwned>(
+    provider: &dyn Provider,
+    messages: &[ChatMessage],
+    model: &str,
+    temperature: f64,
+    max_repairs: usize,
+) -> Result<T> {
+    let format = response_format_for::<T>();
+    let mut conversation = messages.to_vec();
+
+    for attempt in 0..=max_repairs {
+        let response = provider
+            .chat(
+                ChatRequest {
+                    messages: &conversation,
+                    tools: None,
+                    response_format: Some(&format),
+                    params: None,
+                },
+                model,
+                temperature,
+            )
+            .await?;
+        let reply = response.text.unwrap_or_default();
+
+        let error = match parse_structured::<T>(&reply) {
+            Ok(value) => return Ok(value),
+            Err(e) => e,
+        };
+        if attempt == max_repairs {
+            anyhow::bail!(
+                "structured response `{}` still invalid after {max_repairs} repair(s): {error}; last reply: {}",
+                format.name,
+                crate::util::truncate_with_ellipsis(reply.trim(), MAX_ERROR_REPLY_CHARS)
+            );
+        }
+
+        tracing::debug!(
+            schema = %format.name,
+            attempt,
+            "structured response invalid, requesting repair: {error}"
+        );
+        conversation.push(ChatMessage::assistant(reply));
+        conversation.push(ChatMessage::user(format!(
+            "That reply is invalid for the `{}` schema: {error}. \
+             Reply again with only the corrected JSON value.",
+            format.name
+        )));
+    }
+
+    unreachable!("loop returns or bails on the last attempt")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::providers::ChatResponse;
+    use async_trait::async_trait;
+    use serde::Deserialize;
+    use std::sync::Mutex;
+
+    #[derive(Debug, Deserialize, JsonSchema, PartialEq)]
+    struct Verdict {
+        ok: bool,
+        reason: String,
+    }
+
+    /// Replays canned replies and records every request it receives.
+    struct ScriptedProvider {
+        replies: Mutex<Vec<String>>,
+        requests: Mutex<Vec<(Vec<ChatMessage>, Option<ResponseFormat>)>>,
+    }
+
+    impl ScriptedProvider {
+        fn new(replies: &[&str]) -> Self {
+            Self {
+                replies: Mutex::new(replies.iter().rev().map(|r| (*r).to_string()).collect()),
+                requests: Mutex::new(Vec::new()),
+            }
+        }
+    }
+
+    #[async_trait]
+    impl Provider for ScriptedProvider {
+        async fn chat_with_system(
+            &self,
+            _system_prompt: Option<&str>,
+            _message: &str,
+            _model: &str,
+            _temperature: f64,
+        ) -> Result<String> {
+            unreachable!("chat() is overridden")
+        }
+
+        async fn chat(
+            &self,
+            request: ChatRequest<'_>,
+            _model: &str,
+            _temperature: f64,
+        ) -> Result<ChatResponse> {
+            self.requests
+                .lock()
+                .unwrap()
+                .push((request.messages.to_vec(), request.response_format.cloned()));
+            let text = self.replies.lock().unwrap().pop().unwrap_or_default();
+            Ok(ChatResponse {
+                text: Some(text),
+                tool_calls: Vec::new(),
+                usage: None,
+                reasoning_content: None,
+            })
+        }
+    }
+
+    #[test]
+    fn extract_json_handles_bare_fenced_and_embedded_replies() {
+        assert_eq!(extract_json(r#" {"a":1} "#), Some(r#"{"a":1}"#));
+        assert_eq!(extract_json("```json\n{\"a\": 1}\n```"), Some("{\"a\": 1}"));
+        assert_eq!(
+            extract_json("Here you go: {\"a\": {\"b\": 2}} hope it helps"),
+            Some("{\"a\": {\"b\": 2}}")
+        );
+        assert_eq!(extract_json("[1, 2] done"), Some("[1, 2]"));
+        assert_eq!(extract_json("no json here"), None);
+        assert_eq!(extract_json("   "), None);
+    }
+
+    #[test]
+    fn response_format_for_uses_schemars_schema() {
+        let format = response_format_for::<Verdict>();
+        assert_eq!(format.name, "Verdict");
+        assert_eq!(format.schema["type"], "object");
+        assert!(format.schema["properties"]["ok"].is_object());
+        assert_eq!(
+            format.openai_payload()["json_schema"]["name"],
+            serde_json::json!("Verdict")
+        );
+    }
+
+    #[tokio::test]
+    async fn chat_structured_returns_first_valid_reply() {
+        let provider = ScriptedProvider::new(&[r#"{"ok": true, "reason": "fine"}"#]);
+        let verdict: Verdict =
+            chat_structured(&provider, &[ChatMessage::user("judge")], "m", 0.0, 2)
+                .await
+                .unwrap();
+
+        assert_eq!(
+            verdict,
+            Verdict {
+                ok: true,
+                reason: "fine".into()
+            }
+        );
+        let requests = provider.requests.lock().unwrap();
+        assert_eq!(requests.len(), 1);
+        assert_eq!(requests[0].1.as_ref().unwrap().name, "Verdict");
+    }
+
+    #[tokio::test]
+    async fn chat_structured_repairs_invalid_reply() {
+        let provider = ScriptedProvider::new(&[
+            "Sure! The answer is yes.",
+            r#"{"ok": "yes"}"#,
+            r#"```json
+{"ok": false, "reason": "missing file"}
+```"#,
+        ]);
+        let verdict: Verdict =
+            chat_structured(&provider, &[ChatMessage::user("judge")], "m", 0.0, 2)
+                .await
+                .unwrap();
+
+        assert!(!verdict.ok);
+        assert_eq!(verdict.reason, "missing file");
+
+        let requests = provider.requests.lock().unwrap();
+        assert_eq!(requests.len(), 3);
+        let repair = &requests[1].0;
+        assert_eq!(repair.len(), 3);
+        assert_eq!(repair[1].role, "assistant");
+        assert_eq!(repair[1].content, "Sure! The answer is yes.");
+        assert!(repair[2].content.contains("no JSON"));
+        assert!(requests[2].0[4].content.contains("does not match schema"));
+    }
+
+    #[tokio::test]
+    async fn chat_structured_gives_up_after_max_repairs() {
+        let provider = ScriptedProvider::new(&["nope", "still nope"]);
+        let err = chat_structured::<Verdict>(&provider, &[ChatMessage::user("judge")], "m", 0.0, 1)
+            .await
+            .unwrap_err();
+
+        let message = err.to_string();
+        assert!(message.contains("after 1 repair(s)"));
+        assert!(message.contains("still nope"));
+        assert_eq!(provider.requests.lock().unwrap().len(), 2);
+    }
+}
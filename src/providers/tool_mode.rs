@@ -0,0 +1,255 @@
+//! Per-model choice between native and prompt-guided (XML) tool calling.
+//!
+//! `Provider::supports_native_tools()` is fixed per provider, yet many
+//! OpenAI-compatible and Ollama models reject the native `tools` field or
+//! return malformed calls. When that happens the tool loop retries the turn
+//! with prompt-guided tools and records the model here, in
+//! `<workspace>/state/tool_modes.json`. `agent.tool_modes` overrides both.
+
+use super::Provider;
+use chrono::{DateTime, Utc};
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+const TOOL_MODES_FILE: &str = "tool_modes.json";
+
+/// How tool definitions reach a model.
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, schemars::JsonSchema,
+)]
+#[serde(rename_all = "lowercase")]
+pub enum ToolMode {
+    /// Native when the provider supports it, falling back to XML on failure.
+    #[default]
+    Auto,
+    /// Always use the provider's native tool calling; never fall back.
+    Native,
+    /// Always describe tools in the system prompt and parse `<tool_call>` tags.
+    Xml,
+}
+
+impl ToolMode {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Auto => "auto",
+            Self::Native => "native",
+            Self::Xml => "xml",
+        }
+    }
+}
+
+/// A tool mode the runtime switched a model to.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LearnedToolMode {
+    pub provider: String,
+    pub model: String,
+    pub mode: ToolMode,
+    /// Why native tool calling was abandoned.
+    pub reason: String,
+    pub learned_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct ToolModesFile {
+    #[serde(default)]
+    models: Vec<LearnedToolMode>,
+}
+
+/// Configured overrides plus learned tool modes for one workspace.
+#[derive(Default)]
+pub struct ToolModes {
+    overrides: HashMap<String, ToolMode>,
+    /// `None` keeps learned modes in memory only.
+    path: Option<PathBuf>,
+    learned: Mutex<Vec<LearnedToolMode>>,
+}
+
+impl ToolModes {
+    /// Load learned modes from the workspace; `overrides` come from
+    /// `agent.tool_modes` and are keyed by `provider/model` or model id.
+    pub fn load(workspace_dir: &Path, overrides: HashMap<String, ToolMode>) -> Self {
+        let path = state_path(workspace_dir);
+        Self {
+            overrides,
+            learned: Mutex::new(read_learned(&path)),
+            path: Some(path),
+        }
+    }
+
+    /// The mode to use for `model` on `provider`.
+    pub fn mode_for(&self, provider: &str, model: &str) -> ToolMode {
+        let configured = self
+            .overrides
+            .get(&format!("{provider}/{model}"))
+            .or_else(|| self.overrides.get(model))
+            .copied()
+            .unwrap_or_default();
+        if configured != ToolMode::Auto {
+            return configured;
+        }
+        self.learned
+            .lock()
+            .iter()
+            .find(|entry| entry.provider == provider && entry.model == model)
+            .map_or(ToolMode::Auto, |entry| entry.mode)
+    }
+
+    /// Whether turns for `model` should send native tool definitions.
+    pub fn native_tools(&self, provider: &dyn Provider, provider_name: &str, model: &str) -> bool {
+        self.mode_for(provider_name, model) != ToolMode::Xml && provider.supports_native_tools()
+    }
+
+    /// Remember that `model` only works with prompt-guided tools.
+    pub fn record_xml_fallback(&self, provider: &str, model: &str, reason: &str) {
+        let mut learned = self.learned.lock();
+        learned.retain(|entry| !(entry.provider == provider && entry.model == model));
+        learned.push(LearnedToolMode {
+            provider: provider.to_string(),
+            model: model.to_string(),
+            mode: ToolMode::Xml,
+            reason: reason.to_string(),
+            learned_at: Utc::now(),
+        });
+
+        let Some(path) = &self.path else {
+            return;
+        };
+        let file = ToolModesFile {
+            models: learned.clone(),
+        };
+        let written = path
+            .parent()
+            .map_or(Ok(()), std::fs::create_dir_all)
+            .and_then(|()| {
+                let json = serde_json::to_vec_pretty(&file).map_err(std::io::Error::other)?;
+                std::fs::write(path, json)
+            });
+        if let Err(e) = written {
+            tracing::warn!("Failed to persist tool mode for {provider}/{model}: {e}");
+        }
+    }
+}
+
+/// Tool modes learned in `workspace_dir`, for diagnostics.
+pub fn learned_tool_modes(workspace_dir: &Path) -> Vec<LearnedToolMode> {
+    read_learned(&state_path(workspace_dir))
+}
+
+fn state_path(workspace_dir: &Path) -> PathBuf {
+    workspace_dir.join("state").join(TOOL_MODES_FILE)
+}
+
+fn read_learned(path: &Path) -> Vec<LearnedToolMode> {
+    let Ok(raw) = std::fs::read_to_string(path) else {
+        return Vec::new();
+    };
+    match serde_json::from_str::<ToolModesFile>(&raw) {
+        Ok(file) => file.models,
+        Err(e) => {
+            tracing::warn!("Ignoring malformed tool modes {}: {e}", path.display());
+            Vec::new()
+        }
+    }
+}
+
+/// Whether a provider error means the model rejected native tool definitions.
+pub fn is_tool_rejection(err: &anyhow::Error) -> bool {
+    let lower = format!("{err:#}").to_lowercase();
+    let mentions_tools = ["tool", "function call", "function_call", "functions"]
+        .iter()
+        .any(|hint| lower.contains(hint));
+    let rejected = [
+        "400",
+        "bad request",
+        "not support",
+        "unsupported",
+        "unrecognized",
+        "not permitted",
+        "not allowed",
+    ]
+    .iter()
+    .any(|hint| lower.contains(hint));
+    mentions_tools && rejected
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use async_trait::async_trait;
+
+    struct NativeProvider;
+
+    #[async_trait]
+    impl Provider for NativeProvider {
+        fn capabilities(&self) -> super::super::traits::ProviderCapabilities {
+            super::super::traits::ProviderCapabilities {
+                native_tool_calling: true,
+                vision: false,
+            }
+        }
+
+        async fn chat_with_system(
+            &self,
+            _system_prompt: Option<&str>,
+            _message: &str,
+            _model: &str,
+            _temperature: f64,
+        ) -> anyhow::Result<String> {
+            Ok(String::new())
+        }
+    }
+
+    #[test]
+    fn overrides_take_precedence_over_learned_modes() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let overrides = HashMap::from([
+            ("ollama/qwen3".to_string(), ToolMode::Native),
+            ("llama3.1".to_string(), ToolMode::Xml),
+        ]);
+        let modes = ToolModes::load(tmp.path(), overrides);
+        modes.record_xml_fallback("ollama", "qwen3", "400 does not support tools");
+
+        assert_eq!(modes.mode_for("ollama", "qwen3"), ToolMode::Native);
+        assert_eq!(modes.mode_for("compatible", "llama3.1"), ToolMode::Xml);
+        assert_eq!(modes.mode_for("ollama", "mistral"), ToolMode::Auto);
+        assert!(modes.native_tools(&NativeProvider, "ollama", "qwen3"));
+        assert!(!modes.native_tools(&NativeProvider, "compatible", "llama3.1"));
+    }
+
+    #[test]
+    fn learned_modes_persist_in_workspace() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let modes = ToolModes::load(tmp.path(), HashMap::new());
+        modes.record_xml_fallback("ollama", "gemma2", "first");
+        modes.record_xml_fallback("ollama", "gemma2", "400 gemma2 does not support tools");
+
+        let reloaded = ToolModes::load(tmp.path(), HashMap::new());
+        assert_eq!(reloaded.mode_for("ollama", "gemma2"), ToolMode::Xml);
+        assert_eq!(reloaded.mode_for("openai", "gemma2"), ToolMode::Auto);
+
+        let learned = learned_tool_modes(tmp.path());
+        assert_eq!(learned.len(), 1);
+        assert_eq!(learned[0].reason, "400 gemma2 does not support tools");
+    }
+
+    #[test]
+    fn tool_rejection_matches_provider_errors() {
+        for message in [
+            "Ollama API error (400 Bad Request): registry.ollama.ai/library/gemma2:latest does not support tools",
+            "Unrecognized request argument supplied: tools",
+            "\"auto\" tool choice requires --enable-auto-tool-choice (400)",
+            "this model does not support function calling",
+        ] {
+            assert!(is_tool_rejection(&anyhow::anyhow!(message)), "{message}");
+        }
+        for message in [
+            "500 Internal Server Error",
+            "401 Unauthorized: invalid api key",
+            "tool execution timed out",
+        ] {
+            assert!(!is_tool_rejection(&anyhow::anyhow!(message)), "{message}");
+        }
+    }
+}
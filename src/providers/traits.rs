@@ -41,6 +41,15 @@ impl ChatMessage {
     }
 }
 
+/// Heading of the system prompt section that changes every turn.
+///
+/// Prompt builders put it after everything stable so providers with prompt
+/// caching can cache the text before it.
+pub const VOLATILE_PROMPT_HEADING: &str = "## Current Date & Time";
+
+/// Prefix of the assistant message that replaces compacted history.
+pub const COMPACTION_SUMMARY_PREFIX: &str = "[Compaction summary]";
+
 /// A tool call requested by the LLM.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ToolCall {
@@ -50,10 +59,14 @@ pub struct ToolCall {
 }
 
 /// Raw token counts from a single LLM API response.
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, PartialEq)]
 pub struct TokenUsage {
     pub input_tokens: Option<u64>,
     pub output_tokens: Option<u64>,
+    /// Input tokens served from the provider's prompt cache.
+    pub cache_read_tokens: Option<u64>,
+    /// Input tokens written to the provider's prompt cache.
+    pub cache_write_tokens: Option<u64>,
 }
 
 /// An LLM response that may contain text, tool calls, or both.
@@ -89,6 +102,248 @@ impl ChatResponse {
 pub struct ChatRequest<'a> {
     pub messages: &'a [ChatMessage],
     pub tools: Option<&'a [ToolSpec]>,
+    /// Constrain the final answer to JSON matching a schema.
+    pub response_format: Option<&'a ResponseFormat>,
+    /// Sampling limits and reasoning controls beyond temperature.
+    pub params: Option<&'a GenerationParams>,
+}
+
+/// Reasoning effort requested from models that think before answering.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, schemars::JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum ReasoningEffort {
+    Low,
+    Medium,
+    High,
+}
+
+impl ReasoningEffort {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Low => "low",
+            Self::Medium => "medium",
+            Self::High => "high",
+        }
+    }
+
+    /// Thinking budget used for providers that take a token budget instead
+    /// of an effort level.
+    pub fn thinking_budget(self) -> u32 {
+        match self {
+            Self::Low => 1_024,
+            Self::Medium => 4_096,
+            Self::High => 16_384,
+        }
+    }
+}
+
+/// Per-request generation parameters.
+///
+/// Every field is optional; unset fields keep the provider default. Each
+/// provider maps what it supports onto its native request and ignores the
+/// rest. Field names follow the OpenAI Chat Completions API, so the same
+/// table works in `config.toml` and in gateway requests.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct GenerationParams {
+    /// Maximum tokens to generate.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_tokens: Option<u32>,
+    /// Nucleus sampling probability mass (0.0-1.0).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub top_p: Option<f64>,
+    /// Sequences that end generation. Accepts a string or a list.
+    #[serde(
+        default,
+        deserialize_with = "deserialize_stop",
+        skip_serializing_if = "Vec::is_empty"
+    )]
+    pub stop: Vec<String>,
+    /// Seed for best-effort deterministic sampling.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub seed: Option<u64>,
+    /// Penalty for tokens already present (-2.0-2.0).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub presence_penalty: Option<f64>,
+    /// Penalty proportional to token frequency (-2.0-2.0).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub frequency_penalty: Option<f64>,
+    /// Reasoning effort for reasoning models.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reasoning_effort: Option<ReasoningEffort>,
+    /// Thinking token budget (Anthropic extended thinking, Gemini thinking).
+    /// Defaults to a budget derived from `reasoning_effort`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub thinking_budget: Option<u32>,
+}
+
+fn deserialize_stop<'de, D>(deserializer: D) -> Result<Vec<String>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Stop {
+        One(String),
+        Many(Vec<String>),
+    }
+
+    Ok(match Option::<Stop>::deserialize(deserializer)? {
+        None => Vec::new(),
+        Some(Stop::One(stop)) => vec![stop],
+        Some(Stop::Many(stops)) => stops,
+    })
+}
+
+impl GenerationParams {
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// `Some(self)` unless every field is unset.
+    pub fn non_empty(&self) -> Option<&Self> {
+        (!self.is_empty()).then_some(self)
+    }
+
+    /// Fill fields left unset here from `defaults`.
+    pub fn with_defaults(&self, defaults: &Self) -> Self {
+        Self {
+            max_tokens: self.max_tokens.or(defaults.max_tokens),
+            top_p: self.top_p.or(defaults.top_p),
+            stop: if self.stop.is_empty() {
+                defaults.stop.clone()
+            } else {
+                self.stop.clone()
+            },
+            seed: self.seed.or(defaults.seed),
+            presence_penalty: self.presence_penalty.or(defaults.presence_penalty),
+            frequency_penalty: self.frequency_penalty.or(defaults.frequency_penalty),
+            reasoning_effort: self.reasoning_effort.or(defaults.reasoning_effort),
+            thinking_budget: self.thinking_budget.or(defaults.thinking_budget),
+        }
+    }
+
+    /// Explicit thinking budget, or one derived from the reasoning effort.
+    pub fn thinking_budget_tokens(&self) -> Option<u32> {
+        self.thinking_budget
+            .or_else(|| self.reasoning_effort.map(ReasoningEffort::thinking_budget))
+    }
+
+    /// Reject values outside the ranges providers accept.
+    pub fn validate(&self) -> anyhow::Result<()> {
+        if self.max_tokens == Some(0) {
+            anyhow::bail!("max_tokens must be greater than 0");
+        }
+        if let Some(top_p) = self.top_p {
+            if !(0.0..=1.0).contains(&top_p) {
+                anyhow::bail!("top_p must be between 0.0 and 1.0");
+            }
+        }
+        for (name, penalty) in [
+            ("presence_penalty", self.presence_penalty),
+            ("frequency_penalty", self.frequency_penalty),
+        ] {
+            if penalty.is_some_and(|p| !(-2.0..=2.0).contains(&p)) {
+                anyhow::bail!("{name} must be between -2.0 and 2.0");
+            }
+        }
+        if self.stop.iter().any(String::is_empty) {
+            anyhow::bail!("stop sequences must not be empty");
+        }
+        Ok(())
+    }
+
+    /// OpenAI Chat Completions fields, for flattening into a request body.
+    pub fn openai_payload(&self) -> serde_json::Map<String, serde_json::Value> {
+        let mut payload = serde_json::Map::new();
+        if let Some(max_tokens) = self.max_tokens {
+            payload.insert("max_tokens".into(), max_tokens.into());
+        }
+        if let Some(top_p) = self.top_p {
+            payload.insert("top_p".into(), top_p.into());
+        }
+        if !self.stop.is_empty() {
+            payload.insert("stop".into(), self.stop.clone().into());
+        }
+        if let Some(seed) = self.seed {
+            payload.insert("seed".into(), seed.into());
+        }
+        if let Some(penalty) = self.presence_penalty {
+            payload.insert("presence_penalty".into(), penalty.into());
+        }
+        if let Some(penalty) = self.frequency_penalty {
+            payload.insert("frequency_penalty".into(), penalty.into());
+        }
+        if let Some(effort) = self.reasoning_effort {
+            payload.insert("reasoning_effort".into(), effort.as_str().into());
+        }
+        payload
+    }
+}
+
+/// JSON Schema constraint for a structured chat response.
+///
+/// Providers map this onto their native feature (OpenAI `json_schema`,
+/// Anthropic forced tool use, Gemini `responseSchema`, Ollama `format`).
+/// Providers without one receive the schema as system prompt instructions;
+/// callers that need a guaranteed shape should go through
+/// [`chat_structured`](super::structured::chat_structured), which validates
+/// and repairs the reply.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ResponseFormat {
+    /// Identifier for the schema (`[a-zA-Z0-9_-]`, used as OpenAI schema
+    /// name and Anthropic tool name).
+    pub name: String,
+    /// JSON Schema the response must satisfy.
+    pub schema: serde_json::Value,
+    /// Request strict schema adherence where the provider supports it.
+    pub strict: bool,
+}
+
+impl ResponseFormat {
+    pub fn new(name: impl Into<String>, schema: serde_json::Value) -> Self {
+        Self {
+            name: name.into(),
+            schema,
+            strict: false,
+        }
+    }
+
+    /// Build a format from a type's `schemars` schema.
+    pub fn for_type<T: schemars::JsonSchema>(name: impl Into<String>) -> Self {
+        let schema = serde_json::to_value(schemars::schema_for!(T))
+            .unwrap_or_else(|_| serde_json::json!({"type": "object"}));
+        Self::new(name, schema)
+    }
+
+    pub fn with_strict(mut self, strict: bool) -> Self {
+        self.strict = strict;
+        self
+    }
+
+    /// OpenAI Chat Completions `response_format` payload.
+    pub fn openai_payload(&self) -> serde_json::Value {
+        serde_json::json!({
+            "type": "json_schema",
+            "json_schema": {
+                "name": self.name,
+                "schema": self.schema,
+                "strict": self.strict,
+            }
+        })
+    }
+
+    /// Prompt text for providers without native structured output.
+    pub fn instructions(&self) -> String {
+        let schema =
+            serde_json::to_string_pretty(&self.schema).unwrap_or_else(|_| "{}".to_string());
+        format!(
+            "## Response Format\n\n\
+             Reply with a single JSON value that validates against the JSON Schema below. \
+             Do not wrap it in markdown code fences and do not add any text before or after it.\n\n\
+             Schema `{}`:\n{schema}\n",
+            self.name
+        )
+    }
 }
 
 /// A tool result to feed back to the LLM.
@@ -187,6 +442,33 @@ impl StreamOptions {
     }
 }
 
+/// A typed event from a streaming chat response.
+///
+/// Tool calls are identified by `index`, which is unique within one response.
+/// Arguments arrive as JSON fragments that concatenate to the full argument
+/// object.
+#[derive(Debug, Clone, PartialEq)]
+pub enum StreamEvent {
+    /// Answer text.
+    TextDelta(String),
+    /// Reasoning/thinking text.
+    ReasoningDelta(String),
+    /// A tool call begins.
+    ToolCallStart {
+        index: usize,
+        id: String,
+        name: String,
+    },
+    /// A fragment of a tool call's JSON arguments.
+    ToolCallDelta { index: usize, arguments: String },
+    /// A tool call's arguments are complete.
+    ToolCallEnd { index: usize },
+    /// Token usage so far; later events supersede earlier counts.
+    Usage(TokenUsage),
+    /// The response is complete.
+    Done,
+}
+
 /// Result type for streaming operations.
 pub type StreamResult<T> = std::result::Result<T, StreamError>;
 
@@ -328,48 +610,37 @@ pub trait Provider: Send + Sync {
         model: &str,
         temperature: f64,
     ) -> anyhow::Result<ChatResponse> {
+        let mut instructions = Vec::new();
+
         // If tools are provided but provider doesn't support native tools,
         // inject tool instructions into system prompt as fallback.
         if let Some(tools) = request.tools {
             if !tools.is_empty() && !self.supports_native_tools() {
-                let tool_instructions = match self.convert_tools(tools) {
-                    ToolsPayload::PromptGuided { instructions } => instructions,
+                match self.convert_tools(tools) {
+                    ToolsPayload::PromptGuided {
+                        instructions: tool_instructions,
+                    } => instructions.push(tool_instructions),
                     payload => {
                         anyhow::bail!(
                             "Provider returned non-prompt-guided tools payload ({payload:?}) while supports_native_tools() is false"
                         )
                     }
-                };
-                let mut modified_messages = request.messages.to_vec();
-
-                // Inject tool instructions into an existing system message.
-                // If none exists, prepend one to the conversation.
-                if let Some(system_message) =
-                    modified_messages.iter_mut().find(|m| m.role == "system")
-                {
-                    if !system_message.content.is_empty() {
-                        system_message.content.push_str("\n\n");
-                    }
-                    system_message.content.push_str(&tool_instructions);
-                } else {
-                    modified_messages.insert(0, ChatMessage::system(tool_instructions));
                 }
-
-                let text = self
-                    .chat_with_history(&modified_messages, model, temperature)
-                    .await?;
-                return Ok(ChatResponse {
-                    text: Some(text),
-                    tool_calls: Vec::new(),
-                    usage: None,
-                    reasoning_content: None,
-                });
             }
         }
+        if let Some(format) = request.response_format {
+            instructions.push(format.instructions());
+        }
 
-        let text = self
-            .chat_with_history(request.messages, model, temperature)
-            .await?;
+        let text = if instructions.is_empty() {
+            self.chat_with_history(request.messages, model, temperature)
+                .await?
+        } else {
+            let modified_messages =
+                with_system_instructions(request.messages, &instructions.join("\n\n"));
+            self.chat_with_history(&modified_messages, model, temperature)
+                .await?
+        };
         Ok(ChatResponse {
             text: Some(text),
             tool_calls: Vec::new(),
@@ -455,6 +726,73 @@ pub trait Provider: Send + Sync {
             .unwrap_or("");
         self.stream_chat_with_system(system, last_user, model, temperature, options)
     }
+
+    /// Whether `stream_chat` streams tool calls and reasoning natively.
+    /// Default implementation returns false.
+    fn supports_stream_events(&self) -> bool {
+        false
+    }
+
+    /// Streaming chat returning typed events.
+    /// Default implementation streams text through `stream_chat_with_history`;
+    /// requests with tools or a response format yield an error.
+    fn stream_chat(
+        &self,
+        request: ChatRequest<'_>,
+        model: &str,
+        temperature: f64,
+    ) -> stream::BoxStream<'static, StreamResult<StreamEvent>> {
+        if request.tools.is_some_and(|tools| !tools.is_empty()) || request.response_format.is_some()
+        {
+            return stream::once(async {
+                Err(StreamError::Provider(
+                    "provider does not stream tool calls or structured output".to_string(),
+                ))
+            })
+            .boxed();
+        }
+
+        self.stream_chat_with_history(
+            request.messages,
+            model,
+            temperature,
+            StreamOptions::new(true),
+        )
+        .flat_map(|chunk| {
+            let events = match chunk {
+                Ok(chunk) => {
+                    let mut events = Vec::new();
+                    if !chunk.is_final && !chunk.delta.is_empty() {
+                        events.push(Ok(StreamEvent::TextDelta(chunk.delta)));
+                    }
+                    if chunk.is_final {
+                        events.push(Ok(StreamEvent::Done));
+                    }
+                    events
+                }
+                Err(e) => vec![Err(e)],
+            };
+            stream::iter(events)
+        })
+        .boxed()
+    }
+}
+
+/// Append prompt-level instructions to the conversation's system message.
+///
+/// Instructions go into an existing system message; if none exists, one is
+/// prepended to the conversation.
+pub fn with_system_instructions(messages: &[ChatMessage], instructions: &str) -> Vec<ChatMessage> {
+    let mut modified_messages = messages.to_vec();
+    if let Some(system_message) = modified_messages.iter_mut().find(|m| m.role == "system") {
+        if !system_message.content.is_empty() {
+            system_message.content.push_str("\n\n");
+        }
+        system_message.content.push_str(instructions);
+    } else {
+        modified_messages.insert(0, ChatMessage::system(instructions));
+    }
+    modified_messages
 }
 
 /// Build tool instructions text for prompt-guided tool calling.
@@ -516,6 +854,90 @@ mod tests {
         }
     }
 
+    /// Streams the message back word by word through the legacy text API.
+    struct TextStreamProvider;
+
+    #[async_trait]
+    impl Provider for TextStreamProvider {
+        async fn chat_with_system(
+            &self,
+            _system_prompt: Option<&str>,
+            message: &str,
+            _model: &str,
+            _temperature: f64,
+        ) -> anyhow::Result<String> {
+            Ok(message.to_string())
+        }
+
+        fn supports_streaming(&self) -> bool {
+            true
+        }
+
+        fn stream_chat_with_system(
+            &self,
+            _system_prompt: Option<&str>,
+            message: &str,
+            _model: &str,
+            _temperature: f64,
+            _options: StreamOptions,
+        ) -> stream::BoxStream<'static, StreamResult<StreamChunk>> {
+            let mut chunks: Vec<_> = message
+                .split_inclusive(' ')
+                .map(|word| Ok(StreamChunk::delta(word)))
+                .collect();
+            chunks.push(Ok(StreamChunk::final_chunk()));
+            stream::iter(chunks).boxed()
+        }
+    }
+
+    #[tokio::test]
+    async fn default_stream_chat_maps_text_chunks_to_events() {
+        let provider = TextStreamProvider;
+        let request = ChatRequest {
+            messages: &[ChatMessage::user("hello streaming world")],
+            tools: None,
+            response_format: None,
+            params: None,
+        };
+
+        let events: Vec<StreamEvent> = provider
+            .stream_chat(request, "model", 0.0)
+            .map(Result::unwrap)
+            .collect()
+            .await;
+
+        assert!(!provider.supports_stream_events());
+        assert_eq!(
+            events,
+            vec![
+                StreamEvent::TextDelta("hello ".into()),
+                StreamEvent::TextDelta("streaming ".into()),
+                StreamEvent::TextDelta("world".into()),
+                StreamEvent::Done,
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn default_stream_chat_rejects_tools() {
+        let provider = TextStreamProvider;
+        let tools = vec![ToolSpec {
+            name: "shell".into(),
+            description: "Run commands".into(),
+            parameters: serde_json::json!({"type": "object"}),
+        }];
+        let request = ChatRequest {
+            messages: &[ChatMessage::user("hi")],
+            tools: Some(&tools),
+            response_format: None,
+            params: None,
+        };
+
+        let events: Vec<_> = provider.stream_chat(request, "model", 0.0).collect().await;
+        assert_eq!(events.len(), 1);
+        assert!(matches!(events[0], Err(StreamError::Provider(_))));
+    }
+
     #[test]
     fn chat_message_constructors() {
         let sys = ChatMessage::system("Be helpful");
@@ -572,6 +994,8 @@ mod tests {
             usage: Some(TokenUsage {
                 input_tokens: Some(100),
                 output_tokens: Some(50),
+                cache_read_tokens: None,
+                cache_write_tokens: None,
             }),
             reasoning_content: None,
         };
@@ -784,6 +1208,8 @@ mod tests {
         let request = ChatRequest {
             messages: &[ChatMessage::user("Hello")],
             tools: Some(&tools),
+            response_format: None,
+            params: None,
         };
 
         let response = provider.chat(request, "model", 0.7).await.unwrap();
@@ -801,6 +1227,8 @@ mod tests {
         let request = ChatRequest {
             messages: &[ChatMessage::user("Hello")],
             tools: None,
+            response_format: None,
+            params: None,
         };
 
         let response = provider.chat(request, "model", 0.7).await.unwrap();
@@ -901,6 +1329,8 @@ mod tests {
                 ChatMessage::system("BASE_SYSTEM_PROMPT"),
             ],
             tools: Some(&tools),
+            response_format: None,
+            params: None,
         };
 
         let response = provider.chat(request, "model", 0.7).await.unwrap();
@@ -910,6 +1340,31 @@ mod tests {
         assert!(text.contains("Tool Use Protocol"));
     }
 
+    #[tokio::test]
+    async fn provider_chat_injects_response_format_instructions() {
+        let provider = EchoSystemProvider {
+            supports_native: true,
+        };
+        let format = ResponseFormat::new(
+            "verdict",
+            serde_json::json!({"type": "object", "properties": {"ok": {"type": "boolean"}}}),
+        );
+
+        let request = ChatRequest {
+            messages: &[ChatMessage::system("BASE"), ChatMessage::user("Hello")],
+            tools: None,
+            response_format: Some(&format),
+            params: None,
+        };
+
+        let response = provider.chat(request, "model", 0.7).await.unwrap();
+        let text = response.text.unwrap_or_default();
+
+        assert!(text.starts_with("BASE\n\n## Response Format"));
+        assert!(text.contains("Schema `verdict`"));
+        assert!(text.contains("\"ok\""));
+    }
+
     #[tokio::test]
     async fn provider_chat_prompt_guided_uses_convert_tools_override() {
         let provider = CustomConvertProvider;
@@ -923,6 +1378,8 @@ mod tests {
         let request = ChatRequest {
             messages: &[ChatMessage::system("BASE"), ChatMessage::user("Hello")],
             tools: Some(&tools),
+            response_format: None,
+            params: None,
         };
 
         let response = provider.chat(request, "model", 0.7).await.unwrap();
@@ -945,6 +1402,8 @@ mod tests {
         let request = ChatRequest {
             messages: &[ChatMessage::user("Hello")],
             tools: Some(&tools),
+            response_format: None,
+            params: None,
         };
 
         let err = provider.chat(request, "model", 0.7).await.unwrap_err();
@@ -0,0 +1,420 @@
+//! Robot kit bridge — `zeroclaw-robot-kit` tools as agent tools.
+//!
+//! Wraps the robot kit's drive, look, listen, speak, sense and emote tools in
+//! the agent `Tool` trait, with drive going through the kit's `SafeDrive`.
+//! One `SafetyMonitor` is shared by all of them:
+//!
+//! - safety events are forwarded to the observer as
+//!   [`ObserverEvent::RobotSafety`](crate::observability::ObserverEvent::RobotSafety);
+//! - the e-stop state (`zeroclaw estop`) is polled, and kill-all or freezing
+//!   any robot tool triggers an emergency stop and `SafetyMonitor::shutdown`.
+//!   The robot stays halted until the daemon restarts.
+//!
+//! Requires the `robot` build feature.
+
+use crate::config::Config;
+use crate::observability::Observer;
+use crate::tools::Tool;
+use anyhow::Result;
+use std::sync::Arc;
+
+#[cfg(feature = "robot")]
+use crate::config::EstopConfig;
+#[cfg(feature = "robot")]
+use crate::observability::ObserverEvent;
+#[cfg(feature = "robot")]
+use crate::security::{EstopManager, EstopState};
+#[cfg(feature = "robot")]
+use crate::tools::ToolResult;
+#[cfg(feature = "robot")]
+use async_trait::async_trait;
+#[cfg(feature = "robot")]
+use std::path::{Path, PathBuf};
+#[cfg(feature = "robot")]
+use std::sync::atomic::Ordering;
+#[cfg(feature = "robot")]
+use std::time::Duration;
+#[cfg(feature = "robot")]
+use zeroclaw_robot_kit::{self as kit, SafetyEvent, SafetyMonitor};
+
+/// A robot kit tool exposed through the agent `Tool` trait.
+#[cfg(feature = "robot")]
+pub struct RobotTool {
+    inner: Box<dyn kit::Tool>,
+    safety: Arc<SafetyMonitor>,
+}
+
+#[cfg(feature = "robot")]
+#[async_trait]
+impl Tool for RobotTool {
+    fn name(&self) -> &str {
+        self.inner.name()
+    }
+
+    fn description(&self) -> &str {
+        self.inner.description()
+    }
+
+    fn parameters_schema(&self) -> serde_json::Value {
+        self.inner.parameters_schema()
+    }
+
+    async fn execute(&self, args: serde_json::Value) -> Result<ToolResult> {
+        if self.safety.state().estop_active.load(Ordering::SeqCst) {
+            return Ok(ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some("Robot halted by emergency stop".into()),
+            });
+        }
+
+        let result = self.inner.execute(args).await?;
+        Ok(ToolResult {
+            success: result.success,
+            output: result.output,
+            error: result.error,
+        })
+    }
+}
+
+/// Wrap robot kit tools so they can join the agent tool registry.
+#[cfg(feature = "robot")]
+pub fn bridge_tools(
+    tools: Vec<Box<dyn kit::Tool>>,
+    safety: &Arc<SafetyMonitor>,
+) -> Vec<Box<dyn Tool>> {
+    tools
+        .into_iter()
+        .map(|inner| {
+            Box::new(RobotTool {
+                inner,
+                safety: Arc::clone(safety),
+            }) as Box<dyn Tool>
+        })
+        .collect()
+}
+
+/// Map a safety monitor event to an observer event.
+#[cfg(feature = "robot")]
+pub fn observer_event(event: &SafetyEvent) -> ObserverEvent {
+    let (event, detail) = match event {
+        SafetyEvent::ObstacleDetected { distance, angle } => (
+            "obstacle_detected",
+            format!("obstacle at {distance:.2}m ({angle}°)"),
+        ),
+        SafetyEvent::EmergencyStop { reason } => ("emergency_stop", reason.clone()),
+        SafetyEvent::WatchdogTimeout => ("watchdog_timeout", String::new()),
+        SafetyEvent::MovementApproved => ("movement_approved", String::new()),
+        SafetyEvent::MovementDenied { reason } => ("movement_denied", reason.clone()),
+        SafetyEvent::BumpDetected { sensor } => ("bump_detected", sensor.clone()),
+        SafetyEvent::Recovered => ("recovered", String::new()),
+    };
+    ObserverEvent::RobotSafety {
+        event: event.to_string(),
+        detail,
+    }
+}
+
+/// Why `state` halts the robot, if it does: kill-all, or a frozen robot tool.
+#[cfg(feature = "robot")]
+pub fn estop_halt_reason(state: &EstopState, robot_tools: &[String]) -> Option<String> {
+    if state.kill_all {
+        return Some("E-stop kill-all engaged".into());
+    }
+    state
+        .frozen_tools
+        .iter()
+        .find(|tool| robot_tools.contains(tool))
+        .map(|tool| format!("E-stop tool-freeze engaged for '{tool}'"))
+}
+
+#[cfg(feature = "robot")]
+fn forward_safety_events(safety: &SafetyMonitor, observer: Arc<dyn Observer>) {
+    let mut events = safety.subscribe();
+    tokio::spawn(async move {
+        loop {
+            match events.recv().await {
+                Ok(event) => observer.record_event(&observer_event(&event)),
+                Err(tokio::sync::broadcast::error::RecvError::Lagged(skipped)) => {
+                    tracing::warn!(skipped, "Robot safety events dropped");
+                }
+                Err(tokio::sync::broadcast::error::RecvError::Closed) => break,
+            }
+        }
+    });
+}
+
+#[cfg(feature = "robot")]
+fn spawn_estop_watch(
+    estop: EstopConfig,
+    config_dir: PathBuf,
+    poll: Duration,
+    safety: &Arc<SafetyMonitor>,
+    robot_tools: Vec<String>,
+) {
+    let safety = Arc::downgrade(safety);
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(poll);
+        loop {
+            interval.tick().await;
+            let Some(safety) = safety.upgrade() else {
+                break;
+            };
+            let state = match EstopManager::load(&estop, &config_dir) {
+                Ok(manager) => manager.status(),
+                Err(e) => {
+                    tracing::warn!("Robot e-stop check failed: {e}");
+                    continue;
+                }
+            };
+            if let Some(reason) = estop_halt_reason(&state, &robot_tools) {
+                safety.emergency_stop(&reason).await;
+                safety.shutdown();
+                break;
+            }
+        }
+    });
+}
+
+#[cfg(feature = "robot")]
+fn load_kit_config(config: &Config) -> Result<kit::RobotConfig> {
+    let Some(file) = config.robot.config_file.as_deref() else {
+        return Ok(kit::RobotConfig::default());
+    };
+    let path = PathBuf::from(shellexpand::tilde(file).into_owned());
+    let path = if path.is_absolute() {
+        path
+    } else {
+        config
+            .config_path
+            .parent()
+            .unwrap_or_else(|| Path::new("."))
+            .join(path)
+    };
+    kit::RobotConfig::load(&path)
+        .map_err(|e| anyhow::anyhow!("Failed to load robot config {}: {e}", path.display()))
+}
+
+/// Create robot tools from `[robot]`, returning an empty vec when disabled.
+#[cfg(feature = "robot")]
+#[allow(clippy::unused_async)]
+pub async fn create_robot_tools(
+    config: &Config,
+    observer: Arc<dyn Observer>,
+) -> Result<Vec<Box<dyn Tool>>> {
+    if !config.robot.enabled {
+        return Ok(Vec::new());
+    }
+
+    let kit_config = load_kit_config(config)?;
+    let (monitor, _events) = SafetyMonitor::new(kit_config.safety.clone());
+    let safety = Arc::new(monitor);
+    forward_safety_events(&safety, observer);
+
+    let tools = bridge_tools(kit::create_safe_tools(&kit_config, safety.clone()), &safety);
+
+    if config.security.estop.enabled {
+        let config_dir = config
+            .config_path
+            .parent()
+            .map_or_else(|| PathBuf::from("."), Path::to_path_buf);
+        spawn_estop_watch(
+            config.security.estop.clone(),
+            config_dir,
+            Duration::from_millis(config.robot.estop_poll_ms),
+            &safety,
+            tools.iter().map(|t| t.name().to_string()).collect(),
+        );
+    }
+
+    tracing::info!(
+        count = tools.len(),
+        drive = %kit_config.drive.backend,
+        "Robot tools added"
+    );
+    Ok(tools)
+}
+
+#[cfg(not(feature = "robot"))]
+#[allow(clippy::unused_async)]
+pub async fn create_robot_tools(
+    config: &Config,
+    _observer: Arc<dyn Observer>,
+) -> Result<Vec<Box<dyn Tool>>> {
+    if config.robot.enabled {
+        tracing::warn!("[robot] is enabled but this build lacks the 'robot' feature");
+    }
+    Ok(Vec::new())
+}
+
+#[cfg(all(test, feature = "robot"))]
+mod tests {
+    use super::*;
+    use crate::observability::traits::ObserverMetric;
+    use crate::security::EstopLevel;
+    use serde_json::json;
+    use std::sync::Mutex;
+    use zeroclaw_robot_kit::{
+        DriveTool, EmoteTool, MockDrive, MotorCommand, RobotConfig, SafeDrive,
+    };
+
+    #[derive(Default)]
+    struct RecordingObserver {
+        events: Mutex<Vec<(String, String)>>,
+    }
+
+    impl Observer for RecordingObserver {
+        fn record_event(&self, event: &ObserverEvent) {
+            if let ObserverEvent::RobotSafety { event, detail } = event {
+                self.events
+                    .lock()
+                    .unwrap()
+                    .push((event.clone(), detail.clone()));
+            }
+        }
+
+        fn record_metric(&self, _metric: &ObserverMetric) {}
+
+        fn name(&self) -> &str {
+            "recording"
+        }
+
+        fn as_any(&self) -> &dyn std::any::Any {
+            self
+        }
+    }
+
+    fn test_estop_config() -> EstopConfig {
+        EstopConfig {
+            enabled: true,
+            state_file: "estop-state.json".into(),
+            require_otp_to_resume: false,
+        }
+    }
+
+    fn mock_robot() -> (Arc<MockDrive>, Arc<SafetyMonitor>, Vec<Box<dyn Tool>>) {
+        let config = RobotConfig::default();
+        let motors = Arc::new(MockDrive::new());
+        let (monitor, _events) = SafetyMonitor::new(config.safety.clone());
+        let safety = Arc::new(monitor);
+        let drive = Arc::new(DriveTool::with_backend(config.clone(), motors.clone()));
+        let tools = bridge_tools(
+            vec![
+                Box::new(SafeDrive::new(drive, safety.clone())),
+                Box::new(EmoteTool::new(config)),
+            ],
+            &safety,
+        );
+        (motors, safety, tools)
+    }
+
+    #[tokio::test]
+    async fn bridged_drive_reaches_mock_motors() {
+        let (motors, _safety, tools) = mock_robot();
+        let drive = tools.iter().find(|t| t.name() == "drive").unwrap();
+        assert!(drive.parameters_schema()["properties"]["action"].is_object());
+
+        let result = drive
+            .execute(json!({ "action": "forward", "distance": 0.1 }))
+            .await
+            .unwrap();
+
+        assert!(result.success, "{result:?}");
+        assert!(matches!(
+            motors.commands().as_slice(),
+            [MotorCommand::Move { linear_x, .. }] if *linear_x > 0.0
+        ));
+    }
+
+    #[test]
+    fn kill_all_and_robot_tool_freeze_halt_the_robot() {
+        let tools = vec!["drive".to_string(), "emote".to_string()];
+        let dir = tempfile::tempdir().unwrap();
+        let estop = test_estop_config();
+        let mut manager = EstopManager::load(&estop, dir.path()).unwrap();
+        assert_eq!(estop_halt_reason(&manager.status(), &tools), None);
+
+        manager
+            .engage(EstopLevel::ToolFreeze(vec!["shell".into()]))
+            .unwrap();
+        assert_eq!(estop_halt_reason(&manager.status(), &tools), None);
+
+        manager
+            .engage(EstopLevel::ToolFreeze(vec!["Drive".into()]))
+            .unwrap();
+        assert!(estop_halt_reason(&manager.status(), &tools)
+            .unwrap()
+            .contains("'drive'"));
+
+        let state = EstopState {
+            kill_all: true,
+            ..EstopState::default()
+        };
+        assert!(estop_halt_reason(&state, &tools).is_some());
+    }
+
+    #[tokio::test]
+    async fn estop_stops_robot_and_reports_safety_events() {
+        let (motors, safety, tools) = mock_robot();
+        let observer = Arc::new(RecordingObserver::default());
+        forward_safety_events(&safety, observer.clone());
+
+        let dir = tempfile::tempdir().unwrap();
+        let estop = test_estop_config();
+        EstopManager::load(&estop, dir.path())
+            .unwrap()
+            .engage(EstopLevel::KillAll)
+            .unwrap();
+        spawn_estop_watch(
+            estop,
+            dir.path().to_path_buf(),
+            Duration::from_millis(10),
+            &safety,
+            vec!["drive".into(), "emote".into()],
+        );
+
+        for _ in 0..100 {
+            if safety.state().estop_active.load(Ordering::SeqCst) {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        assert!(!safety.can_move().await);
+
+        for tool in &tools {
+            let result = tool
+                .execute(json!({ "action": "forward", "expression": "happy" }))
+                .await
+                .unwrap();
+            assert_eq!(
+                result.error.as_deref(),
+                Some("Robot halted by emergency stop")
+            );
+        }
+        assert!(motors.commands().is_empty());
+
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        let events = observer.events.lock().unwrap().clone();
+        assert_eq!(
+            events,
+            vec![(
+                "emergency_stop".to_string(),
+                "E-stop kill-all engaged".to_string()
+            )]
+        );
+    }
+
+    #[test]
+    fn safety_events_map_to_observer_events() {
+        let ObserverEvent::RobotSafety { event, detail } =
+            observer_event(&SafetyEvent::ObstacleDetected {
+                distance: 0.2,
+                angle: 90,
+            })
+        else {
+            panic!("expected RobotSafety");
+        };
+        assert_eq!(event, "obstacle_detected");
+        assert_eq!(detail, "obstacle at 0.20m (90°)");
+    }
+}
@@ -28,7 +28,7 @@ pub struct WasmRuntime {
 /// Result of executing a WASM module.
 #[derive(Debug, Clone)]
 pub struct WasmExecutionResult {
-    /// Standard output captured from the module (if WASI is used)
+    /// Output the module emitted through `zeroclaw.output_write`
     pub stdout: String,
     /// Standard error captured from the module
     pub stderr: String,
@@ -40,6 +40,13 @@ pub struct WasmExecutionResult {
     pub module_sha256: String,
 }
 
+/// Per-execution host state shared with the `zeroclaw` host imports.
+#[cfg(feature = "runtime-wasm")]
+struct WasmHostState {
+    input: Vec<u8>,
+    output: Vec<u8>,
+}
+
 /// Capabilities granted to a WASM tool module.
 #[derive(Debug, Clone, Default)]
 pub struct WasmCapabilities {
@@ -58,6 +65,8 @@ pub struct WasmCapabilities {
 impl WasmRuntime {
     const MAX_MEMORY_MB: u64 = 4096;
     const MAX_FUEL_LIMIT: u64 = 10_000_000_000;
+    /// Upper bound on bytes a module may emit through `zeroclaw.output_write`.
+    pub const MAX_OUTPUT_BYTES: usize = 1_048_576;
 
     /// Create a new WASM runtime with the given configuration.
     pub fn new(config: WasmRuntimeConfig) -> Self {
@@ -445,14 +454,33 @@ impl WasmRuntime {
     /// This is the primary entry point for running sandboxed tool code.
     /// The module must export a `_start` function (WASI convention) or
     /// a custom `run` function that takes no arguments and returns i32.
-    #[cfg(feature = "runtime-wasm")]
     pub fn execute_module(
         &self,
         module_name: &str,
         workspace_dir: &Path,
         caps: &WasmCapabilities,
     ) -> Result<WasmExecutionResult> {
-        use wasmi::{Engine, Linker, Module, Store};
+        self.execute_module_with_input(module_name, workspace_dir, caps, &[])
+    }
+
+    /// Execute a WASM module with an input payload.
+    ///
+    /// The payload is exposed through two host imports in the `zeroclaw`
+    /// namespace: `input_len() -> i32` and `input_read(ptr: i32, len: i32) -> i32`,
+    /// which copies up to `len` bytes into the module's exported `memory`
+    /// and returns the number of bytes written (or `-1` on failure).
+    /// Modules can return data with `output_write(ptr: i32, len: i32) -> i32`,
+    /// which appends `len` bytes from `memory` to the captured `stdout`
+    /// (capped at [`Self::MAX_OUTPUT_BYTES`]).
+    #[cfg(feature = "runtime-wasm")]
+    pub fn execute_module_with_input(
+        &self,
+        module_name: &str,
+        workspace_dir: &Path,
+        caps: &WasmCapabilities,
+        input: &[u8],
+    ) -> Result<WasmExecutionResult> {
+        use wasmi::{Caller, Engine, Extern, Linker, Module, Store};
 
         self.validate_config()?;
         Self::validate_module_name(module_name)?;
@@ -582,7 +610,13 @@ impl WasmRuntime {
             .with_context(|| format!("Failed to parse WASM module: {module_name}"))?;
 
         // Create store with fuel budget
-        let mut store = Store::new(&engine, ());
+        let mut store = Store::new(
+            &engine,
+            WasmHostState {
+                input: input.to_vec(),
+                output: Vec::new(),
+            },
+        );
         let fuel = self.effective_fuel(&effective_caps);
         if fuel > 0 {
             store.set_fuel(fuel).with_context(|| {
@@ -590,8 +624,61 @@ impl WasmRuntime {
             })?;
         }
 
-        // Link host functions (minimal — pure sandboxing)
-        let linker = Linker::new(&engine);
+        // Link host functions (minimal — input payload and output buffer only)
+        let mut linker = Linker::<WasmHostState>::new(&engine);
+        linker
+            .func_wrap(
+                "zeroclaw",
+                "input_len",
+                |caller: Caller<'_, WasmHostState>| -> i32 {
+                    i32::try_from(caller.data().input.len()).unwrap_or(i32::MAX)
+                },
+            )
+            .context("Failed to link zeroclaw.input_len")?;
+        linker
+            .func_wrap(
+                "zeroclaw",
+                "input_read",
+                |mut caller: Caller<'_, WasmHostState>, ptr: i32, len: i32| -> i32 {
+                    let (Ok(offset), Ok(len)) = (usize::try_from(ptr), usize::try_from(len)) else {
+                        return -1;
+                    };
+                    let Some(memory) = caller.get_export("memory").and_then(Extern::into_memory)
+                    else {
+                        return -1;
+                    };
+                    let payload = caller.data().input.clone();
+                    let count = len.min(payload.len());
+                    match memory.write(&mut caller, offset, &payload[..count]) {
+                        Ok(()) => i32::try_from(count).unwrap_or(-1),
+                        Err(_) => -1,
+                    }
+                },
+            )
+            .context("Failed to link zeroclaw.input_read")?;
+        linker
+            .func_wrap(
+                "zeroclaw",
+                "output_write",
+                |mut caller: Caller<'_, WasmHostState>, ptr: i32, len: i32| -> i32 {
+                    let (Ok(offset), Ok(len)) = (usize::try_from(ptr), usize::try_from(len)) else {
+                        return -1;
+                    };
+                    let Some(memory) = caller.get_export("memory").and_then(Extern::into_memory)
+                    else {
+                        return -1;
+                    };
+                    let remaining =
+                        Self::MAX_OUTPUT_BYTES.saturating_sub(caller.data().output.len());
+                    let mut chunk = vec![0u8; len.min(remaining)];
+                    if memory.read(&caller, offset, &mut chunk).is_err() {
+                        return -1;
+                    }
+                    caller.data_mut().output.extend_from_slice(&chunk);
+                    i32::try_from(chunk.len()).unwrap_or(-1)
+                },
+            )
+            .context("Failed to link zeroclaw.output_write")?;
 
         // Instantiate module
         let instance = linker
@@ -633,7 +720,7 @@ impl WasmRuntime {
         let fuel_consumed = fuel_before.saturating_sub(fuel_after);
 
         Ok(WasmExecutionResult {
-            stdout: String::new(), // No WASI stdout yet — pure computation
+            stdout: String::from_utf8_lossy(&store.data().output).into_owned(),
             stderr: String::new(),
             exit_code,
             fuel_consumed,
@@ -643,11 +730,12 @@ impl WasmRuntime {
 
     /// Stub for when the `runtime-wasm` feature is not enabled.
     #[cfg(not(feature = "runtime-wasm"))]
-    pub fn execute_module(
+    pub fn execute_module_with_input(
         &self,
         module_name: &str,
         _workspace_dir: &Path,
         _caps: &WasmCapabilities,
+        _input: &[u8],
     ) -> Result<WasmExecutionResult> {
         bail!(
             "WASM runtime is not available in this build. \
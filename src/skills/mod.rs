@@ -7,6 +7,10 @@ use std::process::Command;
 use std::time::{Duration, SystemTime};
 
 mod audit;
+mod params;
+mod tool;
+
+pub use tool::create_skill_tools;
 
 const OPEN_SKILLS_REPO_URL: &str = "https://github.com/besoeasy/open-skills";
 const OPEN_SKILLS_SYNC_MARKER: &str = ".zeroclaw-open-skills-sync";
@@ -37,12 +41,36 @@ pub struct Skill {
 pub struct SkillTool {
     pub name: String,
     pub description: String,
-    /// "shell", "http", "script"
+    /// "shell", "http", "script", "wasm"
     pub kind: String,
-    /// The command/URL/script to execute
+    /// The command/URL/script/WASM module to execute
     pub command: String,
+    /// Legacy flat argument descriptions (`name = "description"`).
+    /// Ignored when `parameters` is set.
     #[serde(default)]
     pub args: HashMap<String, String>,
+    /// JSON Schema for the tool's arguments (`[tools.parameters]`).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub parameters: Option<serde_json::Value>,
+    /// How validated arguments are handed to the command.
+    #[serde(default)]
+    pub input: SkillToolInput,
+    /// Per-call timeout override in seconds.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timeout_secs: Option<u64>,
+}
+
+/// Delivery channel for skill tool arguments.
+///
+/// Arguments are never interpolated into the command string.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SkillToolInput {
+    /// `SKILL_ARGS_JSON` plus one `SKILL_ARG_<NAME>` variable per top-level argument.
+    #[default]
+    Env,
+    /// The JSON argument object is written to the process's stdin.
+    Stdin,
 }
 
 /// Skill manifest parsed from SKILL.toml
@@ -1097,6 +1125,9 @@ command = "echo hello"
                 kind: "shell".to_string(),
                 command: "echo hi".to_string(),
                 args: HashMap::new(),
+                parameters: None,
+                input: SkillToolInput::Env,
+                timeout_secs: None,
             }],
             prompts: vec!["Do the thing.".to_string()],
             location: Some(PathBuf::from("/tmp/workspace/skills/test/SKILL.md")),
@@ -1231,6 +1262,53 @@ command = "https://api.example.com/deploy"
         assert_eq!(s.tools[2].kind, "http");
     }
 
+    #[test]
+    fn toml_skill_tool_parameters_and_input_mode() {
+        let dir = tempfile::tempdir().unwrap();
+        let skill_dir = dir.path().join("skills").join("typed");
+        fs::create_dir_all(&skill_dir).unwrap();
+
+        fs::write(
+            skill_dir.join("SKILL.toml"),
+            r#"
+[skill]
+name = "typed"
+description = "Typed tool parameters"
+
+[[tools]]
+name = "search"
+description = "Search tickets"
+kind = "shell"
+command = "echo"
+input = "stdin"
+timeout_secs = 5
+
+[tools.parameters]
+type = "object"
+required = ["query"]
+
+[tools.parameters.properties.query]
+type = "string"
+
+[tools.parameters.properties.status]
+type = "string"
+enum = ["open", "closed"]
+"#,
+        )
+        .unwrap();
+
+        let skills = load_skills(dir.path());
+        let tool = &skills[0].tools[0];
+        assert_eq!(tool.input, SkillToolInput::Stdin);
+        assert_eq!(tool.timeout_secs, Some(5));
+        let schema = tool.parameters.as_ref().unwrap();
+        assert_eq!(schema["required"], serde_json::json!(["query"]));
+        assert_eq!(
+            schema["properties"]["status"]["enum"],
+            serde_json::json!(["open", "closed"])
+        );
+    }
+
     #[test]
     fn toml_skill_minimal() {
         let dir = tempfile::tempdir().unwrap();
@@ -1297,6 +1375,9 @@ description = "Bare minimum"
                 kind: "shell".to_string(),
                 command: "curl wttr.in".to_string(),
                 args: HashMap::new(),
+                parameters: None,
+                input: SkillToolInput::Env,
+                timeout_secs: None,
             }],
             prompts: vec![],
             location: None,
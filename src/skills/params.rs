@@ -0,0 +1,376 @@
+//! Parameter schemas for skill-defined tools.
+//!
+//! A `[[tools]]` entry in `SKILL.toml` may declare a JSON Schema under
+//! `[tools.parameters]`. The schema is advertised to the model verbatim and
+//! every call is validated against it before the tool runs. Tools that only
+//! use the legacy flat `args` table get a derived schema in which each entry
+//! becomes an optional string parameter.
+//!
+//! Only the subset of JSON Schema that matters for tool calls is enforced:
+//! `type`, `enum`, `const`, `properties`, `required`, `additionalProperties`,
+//! `items`, numeric bounds, string length, `pattern` and array length.
+
+use super::SkillTool;
+use anyhow::{bail, Result};
+use serde_json::{json, Map, Value};
+
+const SUPPORTED_TYPES: &[&str] = &[
+    "string", "integer", "number", "boolean", "array", "object", "null",
+];
+
+/// Build the JSON Schema advertised to the model for a skill tool.
+pub fn tool_parameters_schema(tool: &SkillTool) -> Value {
+    if let Some(schema) = &tool.parameters {
+        let mut schema = schema.clone();
+        if let Value::Object(map) = &mut schema {
+            map.entry("type").or_insert_with(|| json!("object"));
+            map.entry("properties")
+                .or_insert_with(|| Value::Object(Map::new()));
+        }
+        return schema;
+    }
+
+    let mut names: Vec<&String> = tool.args.keys().collect();
+    names.sort();
+    let properties: Map<String, Value> = names
+        .into_iter()
+        .map(|name| {
+            (
+                name.clone(),
+                json!({ "type": "string", "description": tool.args[name] }),
+            )
+        })
+        .collect();
+
+    json!({ "type": "object", "properties": properties })
+}
+
+/// Reject schemas this validator cannot honour, so a typo in `SKILL.toml`
+/// fails at load time instead of silently accepting any input.
+pub fn check_schema(schema: &Value) -> Result<()> {
+    let Some(map) = schema.as_object() else {
+        bail!("parameters must be a table");
+    };
+    if map.get("type").and_then(Value::as_str) != Some("object") {
+        bail!("parameters.type must be \"object\"");
+    }
+    check_schema_node(schema, "parameters")
+}
+
+fn check_schema_node(schema: &Value, path: &str) -> Result<()> {
+    let Some(map) = schema.as_object() else {
+        bail!("{path} must be a table");
+    };
+
+    match map.get("type") {
+        None => {}
+        Some(Value::String(ty)) => check_type_name(ty, path)?,
+        Some(Value::Array(types)) => {
+            for ty in types {
+                let Some(ty) = ty.as_str() else {
+                    bail!("{path}.type entries must be strings");
+                };
+                check_type_name(ty, path)?;
+            }
+        }
+        Some(_) => bail!("{path}.type must be a string or an array of strings"),
+    }
+
+    if let Some(values) = map.get("enum") {
+        if values.as_array().is_none_or(Vec::is_empty) {
+            bail!("{path}.enum must be a non-empty array");
+        }
+    }
+
+    if let Some(required) = map.get("required") {
+        let Some(required) = required.as_array() else {
+            bail!("{path}.required must be an array of property names");
+        };
+        if required.iter().any(|name| !name.is_string()) {
+            bail!("{path}.required must be an array of property names");
+        }
+    }
+
+    if let Some(pattern) = map.get("pattern") {
+        let Some(pattern) = pattern.as_str() else {
+            bail!("{path}.pattern must be a string");
+        };
+        if let Err(err) = regex::Regex::new(pattern) {
+            bail!("{path}.pattern is not a valid regex: {err}");
+        }
+    }
+
+    if let Some(properties) = map.get("properties") {
+        let Some(properties) = properties.as_object() else {
+            bail!("{path}.properties must be a table");
+        };
+        for (name, property) in properties {
+            check_schema_node(property, &format!("{path}.properties.{name}"))?;
+        }
+    }
+
+    if let Some(items) = map.get("items") {
+        check_schema_node(items, &format!("{path}.items"))?;
+    }
+
+    Ok(())
+}
+
+fn check_type_name(ty: &str, path: &str) -> Result<()> {
+    if SUPPORTED_TYPES.contains(&ty) {
+        Ok(())
+    } else {
+        bail!(
+            "{path}.type '{ty}' is not supported (expected one of: {})",
+            SUPPORTED_TYPES.join(", ")
+        )
+    }
+}
+
+/// Validate call arguments against a parameter schema.
+///
+/// Returns a human-readable description of the first violation, prefixed
+/// with the JSON path of the offending value (e.g. `$.limit`).
+pub fn validate_arguments(schema: &Value, args: &Value) -> Result<(), String> {
+    validate_node(schema, args, "$")
+}
+
+fn validate_node(schema: &Value, value: &Value, path: &str) -> Result<(), String> {
+    let Some(schema) = schema.as_object() else {
+        return Ok(());
+    };
+
+    match schema.get("type") {
+        Some(Value::String(ty)) if !matches_type(ty, value) => {
+            return Err(format!("{path}: expected {ty}, got {}", type_name(value)));
+        }
+        Some(Value::Array(types)) => {
+            let allowed: Vec<&str> = types.iter().filter_map(Value::as_str).collect();
+            if !allowed.iter().any(|ty| matches_type(ty, value)) {
+                return Err(format!(
+                    "{path}: expected one of [{}], got {}",
+                    allowed.join(", "),
+                    type_name(value)
+                ));
+            }
+        }
+        _ => {}
+    }
+
+    if let Some(expected) = schema.get("const") {
+        if expected != value {
+            return Err(format!("{path}: must equal {expected}"));
+        }
+    }
+
+    if let Some(Value::Array(options)) = schema.get("enum") {
+        if !options.contains(value) {
+            let rendered: Vec<String> = options.iter().map(Value::to_string).collect();
+            return Err(format!(
+                "{path}: must be one of [{}], got {value}",
+                rendered.join(", ")
+            ));
+        }
+    }
+
+    match value {
+        Value::Number(number) => {
+            let Some(number) = number.as_f64() else {
+                return Ok(());
+            };
+            if let Some(minimum) = schema.get("minimum").and_then(Value::as_f64) {
+                if number < minimum {
+                    return Err(format!("{path}: must be >= {minimum}"));
+                }
+            }
+            if let Some(maximum) = schema.get("maximum").and_then(Value::as_f64) {
+                if number > maximum {
+                    return Err(format!("{path}: must be <= {maximum}"));
+                }
+            }
+        }
+        Value::String(text) => {
+            let length = text.chars().count() as u64;
+            if let Some(min) = schema.get("minLength").and_then(Value::as_u64) {
+                if length < min {
+                    return Err(format!("{path}: must be at least {min} characters"));
+                }
+            }
+            if let Some(max) = schema.get("maxLength").and_then(Value::as_u64) {
+                if length > max {
+                    return Err(format!("{path}: must be at most {max} characters"));
+                }
+            }
+            if let Some(pattern) = schema.get("pattern").and_then(Value::as_str) {
+                let re = regex::Regex::new(pattern)
+                    .map_err(|err| format!("{path}: invalid pattern in schema: {err}"))?;
+                if !re.is_match(text) {
+                    return Err(format!("{path}: must match pattern {pattern}"));
+                }
+            }
+        }
+        Value::Array(items) => {
+            let length = items.len() as u64;
+            if let Some(min) = schema.get("minItems").and_then(Value::as_u64) {
+                if length < min {
+                    return Err(format!("{path}: must contain at least {min} items"));
+                }
+            }
+            if let Some(max) = schema.get("maxItems").and_then(Value::as_u64) {
+                if length > max {
+                    return Err(format!("{path}: must contain at most {max} items"));
+                }
+            }
+            if let Some(item_schema) = schema.get("items") {
+                for (index, item) in items.iter().enumerate() {
+                    validate_node(item_schema, item, &format!("{path}[{index}]"))?;
+                }
+            }
+        }
+        Value::Object(fields) => {
+            if let Some(Value::Array(required)) = schema.get("required") {
+                for name in required.iter().filter_map(Value::as_str) {
+                    if !fields.contains_key(name) {
+                        return Err(format!("{path}: missing required property '{name}'"));
+                    }
+                }
+            }
+            let properties = schema.get("properties").and_then(Value::as_object);
+            let allow_additional = schema
+                .get("additionalProperties")
+                .and_then(Value::as_bool)
+                .unwrap_or(true);
+            for (name, field) in fields {
+                match properties.and_then(|props| props.get(name)) {
+                    Some(property) => {
+                        validate_node(property, field, &format!("{path}.{name}"))?;
+                    }
+                    None if !allow_additional => {
+                        return Err(format!("{path}: unexpected property '{name}'"));
+                    }
+                    None => {}
+                }
+            }
+        }
+        Value::Bool(_) | Value::Null => {}
+    }
+
+    Ok(())
+}
+
+fn matches_type(ty: &str, value: &Value) -> bool {
+    match ty {
+        "string" => value.is_string(),
+        "integer" => value.is_i64() || value.is_u64(),
+        "number" => value.is_number(),
+        "boolean" => value.is_boolean(),
+        "array" => value.is_array(),
+        "object" => value.is_object(),
+        "null" => value.is_null(),
+        _ => false,
+    }
+}
+
+fn type_name(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "null",
+        Value::Bool(_) => "boolean",
+        Value::Number(n) if n.is_i64() || n.is_u64() => "integer",
+        Value::Number(_) => "number",
+        Value::String(_) => "string",
+        Value::Array(_) => "array",
+        Value::Object(_) => "object",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn schema() -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "query": { "type": "string", "minLength": 1 },
+                "limit": { "type": "integer", "minimum": 1, "maximum": 50 },
+                "mode": { "type": "string", "enum": ["fast", "full"] },
+                "tags": { "type": "array", "items": { "type": "string" } }
+            },
+            "required": ["query"],
+            "additionalProperties": false
+        })
+    }
+
+    #[test]
+    fn accepts_valid_arguments() {
+        let args = json!({"query": "rust", "limit": 5, "mode": "fast", "tags": ["a"]});
+        assert!(validate_arguments(&schema(), &args).is_ok());
+    }
+
+    #[test]
+    fn rejects_missing_required_property() {
+        let err = validate_arguments(&schema(), &json!({"limit": 5})).unwrap_err();
+        assert!(err.contains("missing required property 'query'"), "{err}");
+    }
+
+    #[test]
+    fn rejects_wrong_type_and_out_of_range_values() {
+        let err = validate_arguments(&schema(), &json!({"query": "x", "limit": "5"})).unwrap_err();
+        assert_eq!(err, "$.limit: expected integer, got string");
+
+        let err = validate_arguments(&schema(), &json!({"query": "x", "limit": 99})).unwrap_err();
+        assert!(err.starts_with("$.limit: must be <="), "{err}");
+    }
+
+    #[test]
+    fn rejects_enum_mismatch_and_bad_array_items() {
+        let err =
+            validate_arguments(&schema(), &json!({"query": "x", "mode": "slow"})).unwrap_err();
+        assert!(err.starts_with("$.mode: must be one of"), "{err}");
+
+        let err =
+            validate_arguments(&schema(), &json!({"query": "x", "tags": ["a", 1]})).unwrap_err();
+        assert_eq!(err, "$.tags[1]: expected string, got integer");
+    }
+
+    #[test]
+    fn rejects_unknown_property_when_additional_properties_disabled() {
+        let err = validate_arguments(&schema(), &json!({"query": "x", "shell": "rm"})).unwrap_err();
+        assert!(err.contains("unexpected property 'shell'"), "{err}");
+    }
+
+    #[test]
+    fn check_schema_rejects_unsupported_types_and_bad_patterns() {
+        assert!(check_schema(&schema()).is_ok());
+        assert!(check_schema(&json!({"type": "string"})).is_err());
+        assert!(check_schema(&json!({
+            "type": "object",
+            "properties": { "n": { "type": "float" } }
+        }))
+        .is_err());
+        assert!(check_schema(&json!({
+            "type": "object",
+            "properties": { "n": { "type": "string", "pattern": "(" } }
+        }))
+        .is_err());
+    }
+
+    #[test]
+    fn legacy_args_become_optional_string_properties() {
+        let tool = SkillTool {
+            name: "greet".into(),
+            description: "Greets".into(),
+            kind: "shell".into(),
+            command: "echo hi".into(),
+            args: HashMap::from([("who".to_string(), "Person to greet".to_string())]),
+            parameters: None,
+            input: crate::skills::SkillToolInput::Env,
+            timeout_secs: None,
+        };
+        let schema = tool_parameters_schema(&tool);
+        assert_eq!(schema["properties"]["who"]["type"], "string");
+        assert!(schema.get("required").is_none());
+        assert!(validate_arguments(&schema, &json!({"who": "Ana"})).is_ok());
+    }
+}
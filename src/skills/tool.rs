@@ -0,0 +1,700 @@
+//! Executable wrapper that exposes `SKILL.toml` tools to the agent.
+//!
+//! Each [`SkillTool`] becomes a regular [`Tool`] whose parameter schema comes
+//! from [`params::tool_parameters_schema`]. Arguments are validated before
+//! anything runs and are delivered through environment variables or stdin —
+//! never interpolated into the command string. Shell and script tools are
+//! built by the configured [`RuntimeAdapter`] and wrapped by the active
+//! [`Sandbox`]; `kind = "wasm"` tools run inside [`WasmRuntime`].
+
+use super::params;
+use super::{Skill, SkillTool, SkillToolInput};
+use crate::config::{Config, WasmRuntimeConfig};
+use crate::runtime::{RuntimeAdapter, WasmRuntime};
+use crate::security::{Sandbox, SecurityPolicy};
+use crate::tools::shell::collect_allowed_shell_env_vars;
+use crate::tools::traits::{Tool, ToolResult};
+use async_trait::async_trait;
+use serde_json::Value;
+use std::collections::HashSet;
+use std::fmt::Write;
+use std::path::{Path, PathBuf};
+use std::process::Stdio;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::io::AsyncWriteExt;
+
+/// Default execution time limit for skill tools.
+const DEFAULT_TIMEOUT_SECS: u64 = 60;
+/// Maximum output size in bytes (1MB).
+const MAX_OUTPUT_BYTES: usize = 1_048_576;
+/// Environment variable carrying the full JSON argument object.
+const ARGS_JSON_ENV: &str = "SKILL_ARGS_JSON";
+/// Prefix for per-argument environment variables.
+const ARG_ENV_PREFIX: &str = "SKILL_ARG_";
+/// Reserved argument that approves medium/high-risk shell commands in
+/// supervised mode, as for the built-in `shell` tool. Never passed to skills.
+const APPROVED_ARG: &str = "approved";
+
+/// A skill-defined tool registered in the agent tool registry.
+pub struct SkillToolExecutor {
+    skill_name: String,
+    skill_dir: Option<PathBuf>,
+    tool: SkillTool,
+    schema: Value,
+    security: Arc<SecurityPolicy>,
+    runtime: Arc<dyn RuntimeAdapter>,
+    sandbox: Arc<dyn Sandbox>,
+    wasm_config: WasmRuntimeConfig,
+    /// `http_request.allowed_domains`, which also gates `kind = "http"` tools.
+    http_allowed_domains: Vec<String>,
+}
+
+impl SkillToolExecutor {
+    /// Build an executor for one tool of `skill`.
+    ///
+    /// Fails when the declared parameter schema is not supported.
+    pub fn new(
+        skill: &Skill,
+        tool: SkillTool,
+        security: Arc<SecurityPolicy>,
+        runtime: Arc<dyn RuntimeAdapter>,
+        sandbox: Arc<dyn Sandbox>,
+        wasm_config: WasmRuntimeConfig,
+        http_allowed_domains: Vec<String>,
+    ) -> anyhow::Result<Self> {
+        let mut schema = params::tool_parameters_schema(&tool);
+        params::check_schema(&schema)?;
+        if tool.kind == "shell" {
+            if let Some(properties) = schema["properties"].as_object_mut() {
+                properties.insert(
+                    APPROVED_ARG.into(),
+                    serde_json::json!({
+                        "type": "boolean",
+                        "description": "Set true to explicitly approve medium/high-risk commands in supervised mode",
+                        "default": false
+                    }),
+                );
+            }
+        }
+        Ok(Self {
+            skill_name: skill.name.clone(),
+            skill_dir: skill
+                .location
+                .as_deref()
+                .and_then(Path::parent)
+                .map(Path::to_path_buf),
+            tool,
+            schema,
+            security,
+            runtime,
+            sandbox,
+            wasm_config,
+            http_allowed_domains: crate::tools::http_request::normalize_allowed_domains(
+                http_allowed_domains,
+            ),
+        })
+    }
+
+    fn timeout(&self) -> Duration {
+        Duration::from_secs(
+            self.tool
+                .timeout_secs
+                .filter(|secs| *secs > 0)
+                .unwrap_or(DEFAULT_TIMEOUT_SECS),
+        )
+    }
+
+    fn failure(message: impl Into<String>) -> ToolResult {
+        ToolResult {
+            success: false,
+            output: String::new(),
+            error: Some(message.into()),
+        }
+    }
+
+    /// Resolve a `kind = "script"` command to a file inside the skill directory.
+    fn resolve_script(&self) -> Result<PathBuf, String> {
+        let Some(skill_dir) = &self.skill_dir else {
+            return Err(format!(
+                "Skill '{}' has no directory to resolve scripts from",
+                self.skill_name
+            ));
+        };
+        let canonical_dir = std::fs::canonicalize(skill_dir)
+            .map_err(|e| format!("Failed to resolve skill directory: {e}"))?;
+        let script = std::fs::canonicalize(skill_dir.join(self.tool.command.trim()))
+            .map_err(|e| format!("Skill script '{}' not found: {e}", self.tool.command))?;
+        if !script.starts_with(&canonical_dir) || !script.is_file() {
+            return Err(format!(
+                "Skill script '{}' must be a file inside the skill directory",
+                self.tool.command
+            ));
+        }
+        Ok(script)
+    }
+
+    async fn run_process(&self, command: &str, args: &Value) -> anyhow::Result<ToolResult> {
+        let mut cmd = match self
+            .runtime
+            .build_shell_command(command, &self.security.workspace_dir)
+        {
+            Ok(cmd) => cmd,
+            Err(e) => {
+                return Ok(Self::failure(format!(
+                    "Failed to build runtime command: {e}"
+                )))
+            }
+        };
+
+        // Start from an empty environment so secrets never leak into skills,
+        // then add the safe baseline and the validated arguments.
+        cmd.env_clear();
+        for var in collect_allowed_shell_env_vars(&self.security) {
+            if let Ok(val) = std::env::var(&var) {
+                cmd.env(&var, val);
+            }
+        }
+        cmd.env("SKILL_NAME", &self.skill_name);
+        if let Some(dir) = &self.skill_dir {
+            cmd.env("SKILL_DIR", dir);
+        }
+
+        let payload = serde_json::to_string(args)?;
+        match self.tool.input {
+            SkillToolInput::Env => {
+                cmd.env(ARGS_JSON_ENV, &payload);
+                for (name, value) in argument_env_vars(args) {
+                    cmd.env(name, value);
+                }
+                cmd.stdin(Stdio::null());
+            }
+            SkillToolInput::Stdin => {
+                cmd.stdin(Stdio::piped());
+            }
+        }
+        cmd.stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .kill_on_drop(true);
+
+        if let Err(e) = self.sandbox.wrap_command(cmd.as_std_mut()) {
+            return Ok(Self::failure(format!(
+                "Failed to apply {} sandbox: {e}",
+                self.sandbox.name()
+            )));
+        }
+
+        let mut child = match cmd.spawn() {
+            Ok(child) => child,
+            Err(e) => return Ok(Self::failure(format!("Failed to execute skill tool: {e}"))),
+        };
+        if let Some(mut stdin) = child.stdin.take() {
+            // A tool that exits without reading its input is not an error.
+            let _ = stdin.write_all(payload.as_bytes()).await;
+        }
+
+        let timeout = self.timeout();
+        match tokio::time::timeout(timeout, child.wait_with_output()).await {
+            Ok(Ok(output)) => {
+                let stdout = truncate_output(&output.stdout, "output");
+                let stderr = truncate_output(&output.stderr, "stderr");
+                Ok(ToolResult {
+                    success: output.status.success(),
+                    output: stdout,
+                    error: if stderr.is_empty() {
+                        None
+                    } else {
+                        Some(stderr)
+                    },
+                })
+            }
+            Ok(Err(e)) => Ok(Self::failure(format!("Failed to execute skill tool: {e}"))),
+            Err(_) => Ok(Self::failure(format!(
+                "Skill tool timed out after {}s and was killed",
+                timeout.as_secs()
+            ))),
+        }
+    }
+
+    async fn run_http(&self, args: &Value) -> anyhow::Result<ToolResult> {
+        let url = match crate::tools::http_request::validate_allowed_url(
+            &self.tool.command,
+            &self.http_allowed_domains,
+        ) {
+            Ok(url) => url,
+            Err(e) => {
+                return Ok(Self::failure(format!(
+                    "Skill http tool '{}' blocked: {e}",
+                    self.tool.name
+                )))
+            }
+        };
+
+        let builder = reqwest::Client::builder()
+            .timeout(self.timeout())
+            .connect_timeout(Duration::from_secs(10))
+            .redirect(reqwest::redirect::Policy::none());
+        let client =
+            crate::config::apply_runtime_proxy_to_builder(builder, "tool.skill").build()?;
+
+        match client.post(&url).json(args).send().await {
+            Ok(response) => {
+                let status = response.status();
+                let body = response.text().await.unwrap_or_default();
+                let body = truncate_output(body.as_bytes(), "response");
+                if status.is_success() {
+                    Ok(ToolResult {
+                        success: true,
+                        output: body,
+                        error: None,
+                    })
+                } else {
+                    Ok(ToolResult {
+                        success: false,
+                        output: body,
+                        error: Some(format!("HTTP {}", status.as_u16())),
+                    })
+                }
+            }
+            Err(e) => Ok(Self::failure(format!("HTTP request failed: {e}"))),
+        }
+    }
+
+    async fn run_wasm(&self, args: &Value) -> anyhow::Result<ToolResult> {
+        let runtime = WasmRuntime::with_workspace(
+            self.wasm_config.clone(),
+            self.security.workspace_dir.clone(),
+        );
+        let module = self.tool.command.trim().to_string();
+        let workspace_dir = self.security.workspace_dir.clone();
+        let payload = serde_json::to_vec(args)?;
+        let caps = runtime.default_capabilities();
+
+        let handle = tokio::task::spawn_blocking(move || {
+            runtime.execute_module_with_input(&module, &workspace_dir, &caps, &payload)
+        });
+        match tokio::time::timeout(self.timeout(), handle).await {
+            Ok(Ok(Ok(result))) => {
+                let success = result.exit_code == 0;
+                let error = if success {
+                    None
+                } else if result.stderr.is_empty() {
+                    Some(format!("WASM module exited with code {}", result.exit_code))
+                } else {
+                    Some(result.stderr)
+                };
+                Ok(ToolResult {
+                    success,
+                    output: result.stdout,
+                    error,
+                })
+            }
+            Ok(Ok(Err(e))) => Ok(Self::failure(e.to_string())),
+            Ok(Err(e)) => Ok(Self::failure(format!("WASM execution task failed: {e}"))),
+            Err(_) => Ok(Self::failure(format!(
+                "Skill tool timed out after {}s",
+                self.timeout().as_secs()
+            ))),
+        }
+    }
+}
+
+#[async_trait]
+impl Tool for SkillToolExecutor {
+    fn name(&self) -> &str {
+        &self.tool.name
+    }
+
+    fn description(&self) -> &str {
+        &self.tool.description
+    }
+
+    fn parameters_schema(&self) -> Value {
+        self.schema.clone()
+    }
+
+    async fn execute(&self, args: Value) -> anyhow::Result<ToolResult> {
+        let mut args = if args.is_null() {
+            Value::Object(serde_json::Map::new())
+        } else {
+            args
+        };
+        let approved = if self.tool.kind == "shell" {
+            args.as_object_mut()
+                .and_then(|map| map.remove(APPROVED_ARG))
+                .and_then(|v| v.as_bool())
+                .unwrap_or(false)
+        } else {
+            false
+        };
+        if let Err(reason) = params::validate_arguments(&self.schema, &args) {
+            return Ok(Self::failure(format!(
+                "Invalid arguments for skill tool '{}': {reason}",
+                self.tool.name
+            )));
+        }
+
+        if self.security.is_rate_limited() {
+            return Ok(Self::failure(
+                "Rate limit exceeded: too many actions in the last hour",
+            ));
+        }
+        if !self.security.can_act() {
+            return Ok(Self::failure(format!(
+                "Security policy: read-only mode, cannot run skill tool '{}'",
+                self.tool.name
+            )));
+        }
+
+        let command = match self.tool.kind.as_str() {
+            "shell" => {
+                let command = self.tool.command.trim().to_string();
+                if let Err(reason) = self.security.validate_command_execution(&command, approved) {
+                    return Ok(Self::failure(reason));
+                }
+                if let Some(path) = self.security.forbidden_path_argument(&command) {
+                    return Ok(Self::failure(format!(
+                        "Path blocked by security policy: {path}"
+                    )));
+                }
+                Some(command)
+            }
+            "script" => match self.resolve_script() {
+                Ok(script) => Some(shell_quote(&script.to_string_lossy())),
+                Err(reason) => return Ok(Self::failure(reason)),
+            },
+            "http" | "wasm" => None,
+            other => {
+                return Ok(Self::failure(format!(
+                    "Unsupported skill tool kind '{other}'. Use shell, script, http or wasm."
+                )));
+            }
+        };
+
+        if !self.security.record_action() {
+            return Ok(Self::failure(
+                "Rate limit exceeded: action budget exhausted",
+            ));
+        }
+
+        match (self.tool.kind.as_str(), command) {
+            (_, Some(command)) => self.run_process(&command, &args).await,
+            ("http", None) => self.run_http(&args).await,
+            _ => self.run_wasm(&args).await,
+        }
+    }
+}
+
+/// Build executable tools for every tool declared by `skills`.
+///
+/// Tools with an invalid schema, or whose name is already used by `existing`
+/// or an earlier skill, are skipped with a warning so one broken skill cannot
+/// take down the registry.
+pub fn create_skill_tools(
+    skills: &[Skill],
+    security: &Arc<SecurityPolicy>,
+    runtime: Arc<dyn RuntimeAdapter>,
+    config: &Config,
+    existing: &[Box<dyn Tool>],
+) -> Vec<Box<dyn Tool>> {
+    let sandbox = crate::security::create_sandbox(&config.security);
+    let mut seen: HashSet<String> = existing.iter().map(|t| t.name().to_string()).collect();
+    let mut tools: Vec<Box<dyn Tool>> = Vec::new();
+
+    for skill in skills {
+        for tool in &skill.tools {
+            if !seen.insert(tool.name.clone()) {
+                tracing::warn!(
+                    skill = %skill.name,
+                    tool = %tool.name,
+                    "Skipping skill tool: name already registered"
+                );
+                continue;
+            }
+            match SkillToolExecutor::new(
+                skill,
+                tool.clone(),
+                security.clone(),
+                runtime.clone(),
+                sandbox.clone(),
+                config.runtime.wasm.clone(),
+                config.http_request.allowed_domains.clone(),
+            ) {
+                Ok(executor) => tools.push(Box::new(executor)),
+                Err(e) => tracing::warn!(
+                    skill = %skill.name,
+                    tool = %tool.name,
+                    "Skipping skill tool with invalid parameters: {e}"
+                ),
+            }
+        }
+    }
+
+    tools
+}
+
+/// Map top-level arguments to `SKILL_ARG_<NAME>` variables.
+///
+/// Strings are passed verbatim; every other value is JSON-encoded.
+fn argument_env_vars(args: &Value) -> Vec<(String, String)> {
+    let Some(map) = args.as_object() else {
+        return Vec::new();
+    };
+    map.iter()
+        .map(|(name, value)| {
+            let key: String = name
+                .chars()
+                .map(|c| {
+                    if c.is_ascii_alphanumeric() {
+                        c.to_ascii_uppercase()
+                    } else {
+                        '_'
+                    }
+                })
+                .collect();
+            let value = match value {
+                Value::String(s) => s.clone(),
+                other => other.to_string(),
+            };
+            (format!("{ARG_ENV_PREFIX}{key}"), value)
+        })
+        .collect()
+}
+
+fn shell_quote(value: &str) -> String {
+    format!("'{}'", value.replace('\'', r"'\''"))
+}
+
+fn truncate_output(bytes: &[u8], label: &str) -> String {
+    let mut text = String::from_utf8_lossy(bytes).to_string();
+    if text.len() > MAX_OUTPUT_BYTES {
+        text.truncate(crate::util::floor_utf8_char_boundary(
+            &text,
+            MAX_OUTPUT_BYTES,
+        ));
+        let _ = write!(text, "\n... [{label} truncated at 1MB]");
+    }
+    text
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::runtime::NativeRuntime;
+    use crate::security::{AutonomyLevel, NoopSandbox};
+    use serde_json::json;
+    use std::collections::HashMap;
+
+    fn test_security(workspace: &Path, autonomy: AutonomyLevel) -> Arc<SecurityPolicy> {
+        Arc::new(SecurityPolicy {
+            autonomy,
+            workspace_dir: workspace.to_path_buf(),
+            allowed_commands: vec![
+                "printenv".into(),
+                "cat".into(),
+                "echo".into(),
+                "touch".into(),
+            ],
+            ..SecurityPolicy::default()
+        })
+    }
+
+    fn skill_with(dir: &Path, tool: SkillTool) -> Skill {
+        Skill {
+            name: "demo".into(),
+            description: "Demo skill".into(),
+            version: "1.0.0".into(),
+            author: None,
+            tags: Vec::new(),
+            tools: vec![tool],
+            prompts: Vec::new(),
+            location: Some(dir.join("SKILL.toml")),
+        }
+    }
+
+    fn shell_tool(command: &str, input: SkillToolInput) -> SkillTool {
+        SkillTool {
+            name: "lookup".into(),
+            description: "Look something up".into(),
+            kind: "shell".into(),
+            command: command.into(),
+            args: HashMap::new(),
+            parameters: Some(json!({
+                "type": "object",
+                "properties": {
+                    "query": { "type": "string" },
+                    "limit": { "type": "integer", "minimum": 1 }
+                },
+                "required": ["query"]
+            })),
+            input,
+            timeout_secs: None,
+        }
+    }
+
+    fn executor(dir: &Path, tool: SkillTool, autonomy: AutonomyLevel) -> SkillToolExecutor {
+        SkillToolExecutor::new(
+            &skill_with(dir, tool.clone()),
+            tool,
+            test_security(dir, autonomy),
+            Arc::new(NativeRuntime::new()),
+            Arc::new(NoopSandbox),
+            WasmRuntimeConfig::default(),
+            vec!["api.example.com".into()],
+        )
+        .unwrap()
+    }
+
+    fn executor_for(dir: &Path, tool: SkillTool) -> SkillToolExecutor {
+        executor(dir, tool, AutonomyLevel::Full)
+    }
+
+    #[test]
+    fn exposes_declared_schema() {
+        let dir = tempfile::tempdir().unwrap();
+        let tool = executor_for(dir.path(), shell_tool("echo", SkillToolInput::Env));
+        let schema = tool.parameters_schema();
+        assert_eq!(schema["properties"]["limit"]["type"], "integer");
+        assert_eq!(schema["required"], json!(["query"]));
+    }
+
+    #[tokio::test]
+    async fn rejects_invalid_arguments_before_running() {
+        let dir = tempfile::tempdir().unwrap();
+        let tool = executor_for(dir.path(), shell_tool("echo", SkillToolInput::Env));
+        let result = tool.execute(json!({"limit": 0})).await.unwrap();
+        assert!(!result.success);
+        assert!(result.error.unwrap().contains("missing required property"));
+    }
+
+    #[tokio::test]
+    async fn passes_arguments_through_env_without_interpolation() {
+        let dir = tempfile::tempdir().unwrap();
+        let tool = executor_for(
+            dir.path(),
+            shell_tool("printenv SKILL_ARG_QUERY", SkillToolInput::Env),
+        );
+        let result = tool
+            .execute(json!({"query": "$(touch pwned); `id`"}))
+            .await
+            .unwrap();
+        assert!(result.success, "{:?}", result.error);
+        assert_eq!(result.output.trim(), "$(touch pwned); `id`");
+        assert!(!dir.path().join("pwned").exists());
+    }
+
+    #[tokio::test]
+    async fn passes_arguments_through_stdin() {
+        let dir = tempfile::tempdir().unwrap();
+        let tool = executor_for(dir.path(), shell_tool("cat", SkillToolInput::Stdin));
+        let result = tool
+            .execute(json!({"query": "rust", "limit": 3}))
+            .await
+            .unwrap();
+        assert!(result.success, "{:?}", result.error);
+        let echoed: Value = serde_json::from_str(&result.output).unwrap();
+        assert_eq!(echoed, json!({"query": "rust", "limit": 3}));
+    }
+
+    #[tokio::test]
+    async fn read_only_autonomy_blocks_execution() {
+        let dir = tempfile::tempdir().unwrap();
+        let tool = executor(
+            dir.path(),
+            shell_tool("echo", SkillToolInput::Env),
+            AutonomyLevel::ReadOnly,
+        );
+        let result = tool.execute(json!({"query": "x"})).await.unwrap();
+        assert!(!result.success);
+        assert!(result.error.unwrap().contains("read-only"));
+    }
+
+    #[tokio::test]
+    async fn shell_command_must_pass_allowlist() {
+        let dir = tempfile::tempdir().unwrap();
+        let tool = executor_for(
+            dir.path(),
+            shell_tool("curl example.com", SkillToolInput::Env),
+        );
+        let result = tool.execute(json!({"query": "x"})).await.unwrap();
+        assert!(!result.success);
+        assert!(result.error.unwrap().contains("not allowed"));
+    }
+
+    #[tokio::test]
+    async fn supervised_shell_command_requires_approval() {
+        let dir = tempfile::tempdir().unwrap();
+        let tool = executor(
+            dir.path(),
+            shell_tool("touch skill_approval_marker", SkillToolInput::Env),
+            AutonomyLevel::Supervised,
+        );
+        assert!(tool.parameters_schema()["properties"]["approved"].is_object());
+
+        let denied = tool.execute(json!({"query": "x"})).await.unwrap();
+        assert!(!denied.success);
+        assert!(denied.error.unwrap().contains("explicit approval"));
+        assert!(!dir.path().join("skill_approval_marker").exists());
+
+        let allowed = tool
+            .execute(json!({"query": "x", "approved": true}))
+            .await
+            .unwrap();
+        assert!(allowed.success, "{:?}", allowed.error);
+        assert!(dir.path().join("skill_approval_marker").exists());
+    }
+
+    #[tokio::test]
+    async fn http_tool_must_target_allowed_domain() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut tool = shell_tool("https://evil.example.net/collect", SkillToolInput::Env);
+        tool.kind = "http".into();
+        let tool = executor_for(dir.path(), tool);
+        let result = tool.execute(json!({"query": "x"})).await.unwrap();
+        assert!(!result.success);
+        let err = result.error.unwrap();
+        assert!(err.contains("not in http_request.allowed_domains"));
+    }
+
+    #[tokio::test]
+    async fn script_must_stay_inside_skill_directory() {
+        let dir = tempfile::tempdir().unwrap();
+        let skill_dir = dir.path().join("skill");
+        std::fs::create_dir_all(&skill_dir).unwrap();
+        std::fs::write(dir.path().join("outside.sh"), "#!/bin/sh\necho hi\n").unwrap();
+
+        let mut tool = shell_tool("../outside.sh", SkillToolInput::Env);
+        tool.kind = "script".into();
+        let tool = executor_for(&skill_dir, tool);
+        let result = tool.execute(json!({"query": "x"})).await.unwrap();
+        assert!(!result.success);
+        assert!(result.error.unwrap().contains("inside the skill directory"));
+    }
+
+    #[test]
+    fn create_skill_tools_skips_duplicates_and_bad_schemas() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut bad = shell_tool("echo", SkillToolInput::Env);
+        bad.name = "bad".into();
+        bad.parameters = Some(json!({"type": "object", "properties": {"n": {"type": "float"}}}));
+        let mut clash = shell_tool("echo", SkillToolInput::Env);
+        clash.name = "shell".into();
+        let mut skill = skill_with(dir.path(), shell_tool("echo", SkillToolInput::Env));
+        skill.tools.push(bad);
+        skill.tools.push(clash);
+
+        let security = test_security(dir.path(), AutonomyLevel::Full);
+        let runtime: Arc<dyn RuntimeAdapter> = Arc::new(NativeRuntime::new());
+        let existing = crate::tools::default_tools_with_runtime(security.clone(), runtime.clone());
+        let tools = create_skill_tools(&[skill], &security, runtime, &Config::default(), &existing);
+        let names: Vec<&str> = tools.iter().map(|t| t.name()).collect();
+        assert_eq!(names, vec!["lookup"]);
+    }
+
+    #[test]
+    fn argument_env_vars_uppercase_and_encode_non_strings() {
+        let vars = argument_env_vars(&json!({"file-name": "a.txt", "count": 2}));
+        assert!(vars.contains(&("SKILL_ARG_FILE_NAME".into(), "a.txt".into())));
+        assert!(vars.contains(&("SKILL_ARG_COUNT".into(), "2".into())));
+    }
+}
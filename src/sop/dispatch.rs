@@ -62,7 +62,6 @@ fn action_label(action: &SopRunAction) -> &'static str {
 /// 1. Lock → `match_trigger` → collect SOP names → drop lock
 /// 2. Lock → for each name: `start_run` → collect results → drop lock
 /// 3. Async (no lock): audit each started run
-#[tracing::instrument(skip(engine, audit), fields(source = %event.source, topic = ?event.topic))]
 pub async fn dispatch_sop_event(
     engine: &Arc<Mutex<SopEngine>>,
     audit: &SopAuditLogger,
@@ -83,7 +82,10 @@ pub async fn dispatch_sop_event(
     };
 
     if matched_names.is_empty() {
-        debug!("SOP dispatch: no match for event");
+        debug!(
+            "SOP dispatch: no match for {} event (topic: {:?})",
+            event.source, event.topic
+        );
         return vec![DispatchResult::NoMatch];
     }
 
@@ -158,7 +160,7 @@ pub async fn dispatch_sop_event(
 /// approval timeout polling in the scheduler handles progression.
 /// For `ExecuteStep` actions, the run is started in the engine but steps
 /// cannot be executed without an agent loop — this is logged as a warning.
-pub async fn process_headless_results(results: &[DispatchResult]) {
+pub fn process_headless_results(results: &[DispatchResult]) {
     for result in results {
         match result {
             DispatchResult::Started {
@@ -250,7 +252,7 @@ impl SopCronCache {
             for trigger in &sop.triggers {
                 if let super::types::SopTrigger::Cron { expression } = trigger {
                     // Normalize 5-field crontab to 6-field (prepend seconds)
-                    let normalized = match crate::cron::schedule::normalize_expression(expression) {
+                    let normalized = match crate::cron::normalize_expression(expression) {
                         Ok(n) => n,
                         Err(e) => {
                             warn!(
@@ -352,6 +354,7 @@ mod tests {
             }],
             cooldown_secs: 0,
             max_concurrent: 2,
+            generation: Default::default(),
             location: None,
         }
     }
@@ -12,6 +12,7 @@ use super::types::{
     SopStepStatus, SopTrigger, SopTriggerSource,
 };
 use crate::config::SopConfig;
+use crate::providers::GenerationParams;
 
 /// Central SOP orchestrator: loads SOPs, matches triggers, manages run lifecycle.
 pub struct SopEngine {
@@ -67,6 +68,12 @@ impl SopEngine {
         self.sops.iter().find(|s| s.name == name)
     }
 
+    /// Generation parameters of the SOP behind an active run, if it sets any.
+    pub fn run_generation(&self, run_id: &str) -> Option<GenerationParams> {
+        let run = self.active_runs.get(run_id)?;
+        self.get_sop(&run.sop_name)?.generation.non_empty().cloned()
+    }
+
     // ── Trigger matching ────────────────────────────────────────
 
     /// Match an incoming event against all loaded SOPs and return the names of
@@ -691,6 +698,7 @@ mod tests {
             ],
             cooldown_secs: 0,
             max_concurrent: 1,
+            generation: Default::default(),
             location: None,
         }
     }
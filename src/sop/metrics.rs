@@ -1,5 +1,5 @@
 use std::collections::{HashMap, VecDeque};
-use std::sync::RwLock;
+use std::sync::{Arc, RwLock};
 use std::time::Instant;
 
 use chrono::{DateTime, NaiveDateTime, Utc};
@@ -8,6 +8,7 @@ use tracing::warn;
 
 use super::types::{SopRun, SopRunStatus, SopStepStatus};
 use crate::memory::traits::{Memory, MemoryCategory};
+use crate::observability::{Observer, ObserverEvent};
 
 /// Maximum recent runs kept in each ring buffer (global + per-SOP).
 /// Covers ~90-day window at ~11 runs/day. If throughput exceeds this,
@@ -82,6 +83,7 @@ struct CollectorState {
 /// health endpoints, and diagnostics.
 pub struct SopMetricsCollector {
     inner: RwLock<CollectorState>,
+    observer: Option<Arc<dyn Observer>>,
 }
 
 impl SopMetricsCollector {
@@ -89,15 +91,29 @@ impl SopMetricsCollector {
     pub fn new() -> Self {
         Self {
             inner: RwLock::new(CollectorState::default()),
+            observer: None,
         }
     }
 
+    /// Also report terminal runs to `observer` (e.g. Prometheus `zeroclaw_sop_runs_total`).
+    pub fn with_observer(mut self, observer: Arc<dyn Observer>) -> Self {
+        self.observer = Some(observer);
+        self
+    }
+
     // ── Push methods (sync, write lock) ────────────────────────
 
     /// Record a terminal run (Completed/Failed/Cancelled).
     ///
     /// Call after `audit.log_run_complete()`.
     pub fn record_run_complete(&self, run: &SopRun) {
+        if let Some(observer) = &self.observer {
+            observer.record_event(&ObserverEvent::SopRun {
+                sop: run.sop_name.clone(),
+                status: run.status.to_string(),
+            });
+        }
+
         let Ok(mut state) = self.inner.write() else {
             warn!("SOP metrics collector lock poisoned in record_run_complete");
             return;
@@ -278,6 +294,7 @@ impl SopMetricsCollector {
 
         Ok(Self {
             inner: RwLock::new(state),
+            observer: None,
         })
     }
 
@@ -645,7 +662,8 @@ mod tests {
             current_step: total_steps,
             total_steps,
             started_at: "2026-02-19T12:00:00Z".into(),
-            completed_at: Some("2026-02-19T12:05:00Z".into()),
+            // Recent enough for the 7-day windows whenever the tests run.
+            completed_at: Some(crate::sop::engine::now_iso8601()),
             step_results,
             waiting_since: None,
         }
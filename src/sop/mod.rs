@@ -112,7 +112,11 @@ fn load_sop(sop_dir: &Path, default_execution_mode: SopExecutionMode) -> Result<
         execution_mode,
         cooldown_secs,
         max_concurrent,
+        generation,
     } = manifest.sop;
+    if let Err(e) = generation.validate() {
+        anyhow::bail!("sop.generation.{e}");
+    }
 
     Ok(Sop {
         name,
@@ -124,6 +128,7 @@ fn load_sop(sop_dir: &Path, default_execution_mode: SopExecutionMode) -> Result<
         steps,
         cooldown_secs,
         max_concurrent,
+        generation,
         location: Some(sop_dir.to_path_buf()),
     })
 }
@@ -461,6 +466,12 @@ pub fn handle_command(command: crate::SopCommands, config: &crate::config::Confi
             println!("Execution mode: {}", sop.execution_mode);
             println!("Cooldown:       {}s", sop.cooldown_secs);
             println!("Max concurrent: {}", sop.max_concurrent);
+            if let Some(generation) = sop.generation.non_empty() {
+                println!(
+                    "Generation:     {}",
+                    serde_json::to_string(generation).unwrap_or_default()
+                );
+            }
             println!();
 
             if !sop.triggers.is_empty() {
@@ -692,6 +703,42 @@ type = "manual"
         assert_eq!(sops[0].execution_mode, SopExecutionMode::Auto);
     }
 
+    #[test]
+    fn load_sop_reads_generation_and_rejects_invalid_values() {
+        let dir = tempfile::tempdir().unwrap();
+        let sop_dir = dir.path().join("short-replies");
+        fs::create_dir_all(&sop_dir).unwrap();
+        let write_manifest = |max_tokens: u32| {
+            fs::write(
+                sop_dir.join("SOP.toml"),
+                format!(
+                    r#"
+[sop]
+name = "short-replies"
+description = "SOP with its own generation limits"
+
+[sop.generation]
+max_tokens = {max_tokens}
+reasoning_effort = "low"
+
+[[triggers]]
+type = "manual"
+"#
+                ),
+            )
+            .unwrap();
+        };
+
+        write_manifest(256);
+        let sops = load_sops_from_directory(dir.path(), SopExecutionMode::Auto);
+        assert_eq!(sops.len(), 1);
+        assert_eq!(sops[0].generation.max_tokens, Some(256));
+        assert!(sops[0].generation.reasoning_effort.is_some());
+
+        write_manifest(0);
+        assert!(load_sops_from_directory(dir.path(), SopExecutionMode::Auto).is_empty());
+    }
+
     #[test]
     fn validate_sop_warnings() {
         let sop = Sop {
@@ -704,6 +751,7 @@ type = "manual"
             steps: Vec::new(),
             cooldown_secs: 0,
             max_concurrent: 1,
+            generation: Default::default(),
             location: None,
         };
 
@@ -732,6 +780,7 @@ type = "manual"
             }],
             cooldown_secs: 0,
             max_concurrent: 1,
+            generation: Default::default(),
             location: None,
         };
 
@@ -1,3 +1,4 @@
+use crate::providers::GenerationParams;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use std::fmt;
@@ -123,6 +124,10 @@ pub struct Sop {
     pub cooldown_secs: u64,
     #[serde(default = "default_max_concurrent")]
     pub max_concurrent: u32,
+    /// Generation parameters for agent turns while this SOP runs, layered
+    /// over the turn's own (`[sop.generation]` in SOP.toml).
+    #[serde(default, skip_serializing_if = "GenerationParams::is_empty")]
+    pub generation: GenerationParams,
     #[serde(skip)]
     pub location: Option<PathBuf>,
 }
@@ -160,6 +165,8 @@ pub(crate) struct SopMeta {
     pub cooldown_secs: u64,
     #[serde(default = "default_max_concurrent")]
     pub max_concurrent: u32,
+    #[serde(default)]
+    pub generation: GenerationParams,
 }
 
 fn default_sop_version() -> String {
@@ -233,8 +240,9 @@ impl fmt::Display for SopRunStatus {
 }
 
 /// Result status of a single step execution.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "snake_case")]
+#[schemars(inline)]
 pub enum SopStepStatus {
     Completed,
     Failed,
@@ -1,6 +1,7 @@
 use super::traits::{Tool, ToolResult};
 use crate::config::Config;
-use crate::cron::{self, DeliveryConfig, JobType, Schedule, SessionTarget};
+use crate::cron::{self, CronJobPatch, DeliveryConfig, JobType, Schedule, SessionTarget};
+use crate::providers::GenerationParams;
 use crate::security::SecurityPolicy;
 use async_trait::async_trait;
 use serde_json::json;
@@ -71,6 +72,14 @@ impl Tool for CronAddTool {
                 "prompt": { "type": "string" },
                 "session_target": { "type": "string", "enum": ["isolated", "main"] },
                 "model": { "type": "string" },
+                "generation": {
+                    "type": "object",
+                    "description": "Sampling overrides for agent jobs, layered over [agent] generation. Example: {\"max_tokens\":512,\"top_p\":0.9,\"reasoning_effort\":\"low\"}"
+                },
+                "batch": {
+                    "type": "boolean",
+                    "description": "Run an agent job as a single tool-free request through the provider's discounted batch API (openai/anthropic). Results arrive on a later scheduler tick."
+                },
                 "delivery": {
                     "type": "object",
                     "description": "Delivery config to send job output to a channel. Example: {\"mode\":\"announce\",\"channel\":\"discord\",\"to\":\"<channel_id>\"}",
@@ -227,6 +236,28 @@ impl Tool for CronAddTool {
                     None => None,
                 };
 
+                let generation = match args.get("generation") {
+                    Some(v) => match serde_json::from_value::<GenerationParams>(v.clone())
+                        .map_err(anyhow::Error::from)
+                        .and_then(|params| params.validate().map(|()| params))
+                    {
+                        Ok(params) => params.non_empty().cloned(),
+                        Err(e) => {
+                            return Ok(ToolResult {
+                                success: false,
+                                output: String::new(),
+                                error: Some(format!("Invalid generation params: {e}")),
+                            });
+                        }
+                    },
+                    None => None,
+                };
+
+                let batch = args
+                    .get("batch")
+                    .and_then(serde_json::Value::as_bool)
+                    .unwrap_or(false);
+
                 if let Some(blocked) = self.enforce_mutation_allowed("cron_add") {
                     return Ok(blocked);
                 }
@@ -241,6 +272,20 @@ impl Tool for CronAddTool {
                     delivery,
                     delete_after_run,
                 )
+                .and_then(|job| {
+                    if generation.is_none() && !batch {
+                        return Ok(job);
+                    }
+                    cron::update_job(
+                        &self.config,
+                        &job.id,
+                        CronJobPatch {
+                            generation,
+                            batch: batch.then_some(true),
+                            ..CronJobPatch::default()
+                        },
+                    )
+                })
             }
         };
 
@@ -252,6 +297,7 @@ impl Tool for CronAddTool {
                     "name": job.name,
                     "job_type": job.job_type,
                     "schedule": job.schedule,
+                    "batch": job.batch,
                     "next_run": job.next_run,
                     "enabled": job.enabled
                 }))?,
@@ -478,4 +524,72 @@ mod tests {
             .unwrap_or_default()
             .contains("Missing 'prompt'"));
     }
+
+    #[tokio::test]
+    async fn agent_job_persists_generation_params() {
+        let tmp = TempDir::new().unwrap();
+        let cfg = test_config(&tmp).await;
+        let tool = CronAddTool::new(cfg.clone(), test_security(&cfg));
+
+        let result = tool
+            .execute(json!({
+                "schedule": { "kind": "cron", "expr": "*/5 * * * *" },
+                "job_type": "agent",
+                "prompt": "summarize inbox",
+                "generation": { "max_tokens": 256, "stop": "END" }
+            }))
+            .await
+            .unwrap();
+        assert!(result.success, "{:?}", result.error);
+
+        let jobs = cron::list_jobs(&cfg).unwrap();
+        let generation = jobs[0].generation.as_ref().unwrap();
+        assert_eq!(generation.max_tokens, Some(256));
+        assert_eq!(generation.stop, vec!["END".to_string()]);
+    }
+
+    #[tokio::test]
+    async fn agent_job_can_opt_into_batch_mode() {
+        let tmp = TempDir::new().unwrap();
+        let cfg = test_config(&tmp).await;
+        let tool = CronAddTool::new(cfg.clone(), test_security(&cfg));
+
+        let result = tool
+            .execute(json!({
+                "schedule": { "kind": "cron", "expr": "0 2 * * *" },
+                "job_type": "agent",
+                "prompt": "summarize yesterday's logs",
+                "batch": true
+            }))
+            .await
+            .unwrap();
+        assert!(result.success, "{:?}", result.error);
+
+        let jobs = cron::list_jobs(&cfg).unwrap();
+        assert!(jobs[0].batch);
+        assert!(jobs[0].generation.is_none());
+    }
+
+    #[tokio::test]
+    async fn agent_job_rejects_invalid_generation_params() {
+        let tmp = TempDir::new().unwrap();
+        let cfg = test_config(&tmp).await;
+        let tool = CronAddTool::new(cfg.clone(), test_security(&cfg));
+
+        let result = tool
+            .execute(json!({
+                "schedule": { "kind": "cron", "expr": "*/5 * * * *" },
+                "job_type": "agent",
+                "prompt": "summarize inbox",
+                "generation": { "top_p": 1.5 }
+            }))
+            .await
+            .unwrap();
+        assert!(!result.success);
+        assert!(result
+            .error
+            .unwrap_or_default()
+            .contains("Invalid generation params"));
+        assert!(cron::list_jobs(&cfg).unwrap().is_empty());
+    }
 }
@@ -54,7 +54,7 @@ impl Tool for CronUpdateTool {
     }
 
     fn description(&self) -> &str {
-        "修补现有的 cron 作业（计划、命令、提示、启用、交付、模型等）"
+        "修补现有的 cron 作业（计划、命令、提示、启用、交付、模型、生成参数、批处理模式等）"
     }
 
     fn parameters_schema(&self) -> serde_json::Value {
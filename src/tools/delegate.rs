@@ -597,6 +597,9 @@ impl DelegateTool {
                 None,
                 None,
                 &[],
+                None,
+                None,
+                None,
             ),
         )
         .await;
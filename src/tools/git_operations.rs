@@ -53,7 +53,17 @@ impl GitOperationsTool {
     fn requires_write_access(&self, operation: &str) -> bool {
         matches!(
             operation,
-            "commit" | "add" | "checkout" | "stash" | "reset" | "revert"
+            "commit"
+                | "add"
+                | "checkout"
+                | "stash"
+                | "reset"
+                | "revert"
+                | "cherry-pick"
+                | "merge"
+                | "rebase"
+                | "tag"
+                | "worktree"
         )
     }
 
@@ -61,10 +71,50 @@ impl GitOperationsTool {
     fn is_read_only(&self, operation: &str) -> bool {
         matches!(
             operation,
-            "status" | "diff" | "log" | "show" | "branch" | "rev-parse"
+            "status" | "diff" | "log" | "show" | "branch" | "rev-parse" | "blame"
         )
     }
 
+    /// Listing sub-actions of otherwise mutating operations (`tag`, `worktree`)
+    /// only read repository state.
+    fn is_listing_action(operation: &str, args: &serde_json::Value) -> bool {
+        matches!(operation, "tag" | "worktree") && Self::action_arg(args, "list") == "list"
+    }
+
+    /// Check if an operation rewrites history or discards work, which needs
+    /// explicit approval in supervised mode.
+    fn rewrites_history(operation: &str, args: &serde_json::Value) -> bool {
+        match operation {
+            "reset" => true,
+            "rebase" => Self::action_arg(args, "start") != "abort",
+            "tag" => Self::action_arg(args, "list") == "delete",
+            "worktree" => {
+                Self::action_arg(args, "list") == "remove"
+                    && args.get("force").and_then(|v| v.as_bool()).unwrap_or(false)
+            }
+            _ => false,
+        }
+    }
+
+    fn action_arg<'a>(args: &'a serde_json::Value, default: &'a str) -> &'a str {
+        args.get("action")
+            .and_then(|v| v.as_str())
+            .unwrap_or(default)
+    }
+
+    /// Validate a single revision, branch or tag argument.
+    fn sanitize_revision(&self, rev: &str) -> anyhow::Result<String> {
+        let sanitized = self.sanitize_git_args(rev)?;
+        if sanitized.len() != 1 {
+            anyhow::bail!("Invalid revision specification: {rev}");
+        }
+        let rev = sanitized.into_iter().next().unwrap_or_default();
+        if rev.starts_with('-') {
+            anyhow::bail!("Revision must not start with '-': {rev}");
+        }
+        Ok(rev)
+    }
+
     async fn run_git_command(&self, args: &[&str]) -> anyhow::Result<String> {
         let output = tokio::process::Command::new("git")
             .args(args)
@@ -80,6 +130,18 @@ impl GitOperationsTool {
         Ok(String::from_utf8_lossy(&output.stdout).to_string())
     }
 
+    /// Run git without failing on a non-zero exit, for operations that can
+    /// stop midway (merge, rebase, cherry-pick). `GIT_EDITOR=true` keeps
+    /// `--continue` from waiting on an interactive editor.
+    async fn run_git_command_raw(&self, args: &[&str]) -> anyhow::Result<std::process::Output> {
+        Ok(tokio::process::Command::new("git")
+            .args(args)
+            .current_dir(&self.workspace_dir)
+            .env("GIT_EDITOR", "true")
+            .output()
+            .await?)
+    }
+
     async fn git_status(&self, _args: serde_json::Value) -> anyhow::Result<ToolResult> {
         let output = self
             .run_git_command(&["status", "--porcelain=2", "--branch"])
@@ -150,8 +212,20 @@ impl GitOperationsTool {
 
         let output = self.run_git_command(&git_args).await?;
 
-        // Parse diff into structured hunks
+        let hunks = Self::parse_diff_hunks(&output);
         let mut result = serde_json::Map::new();
+        result.insert("file_count".to_string(), json!(hunks.len()));
+        result.insert("hunks".to_string(), json!(hunks));
+
+        Ok(ToolResult {
+            success: true,
+            output: serde_json::to_string_pretty(&result).unwrap_or_default(),
+            error: None,
+        })
+    }
+
+    /// Parse unified diff output into structured hunks
+    fn parse_diff_hunks(output: &str) -> Vec<serde_json::Value> {
         let mut hunks = Vec::new();
         let mut current_file = String::new();
         let mut current_hunk = serde_json::Map::new();
@@ -200,14 +274,7 @@ impl GitOperationsTool {
             }
         }
 
-        result.insert("hunks".to_string(), json!(hunks));
-        result.insert("file_count".to_string(), json!(hunks.len()));
-
-        Ok(ToolResult {
-            success: true,
-            output: serde_json::to_string_pretty(&result).unwrap_or_default(),
-            error: None,
-        })
+        hunks
     }
 
     async fn git_log(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
@@ -421,6 +488,510 @@ impl GitOperationsTool {
             }),
         }
     }
+
+    async fn git_blame(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
+        let path = args
+            .get("path")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| anyhow::anyhow!("Missing 'path' parameter"))?;
+        let path = self.sanitize_revision(path)?;
+
+        let start_line = args.get("start_line").and_then(|v| v.as_u64());
+        let end_line = args.get("end_line").and_then(|v| v.as_u64());
+        let range = match (start_line, end_line) {
+            (Some(0), _) | (_, Some(0)) => anyhow::bail!("Line numbers start at 1"),
+            (Some(start), Some(end)) if end < start => {
+                anyhow::bail!("'end_line' must not be before 'start_line'")
+            }
+            (Some(start), Some(end)) => Some(format!("{start},{end}")),
+            (Some(start), None) => Some(format!("{start},")),
+            (None, Some(end)) => Some(format!("1,{end}")),
+            (None, None) => None,
+        };
+
+        let mut git_args = vec!["blame".to_string(), "--porcelain".to_string()];
+        if let Some(range) = range {
+            git_args.push("-L".to_string());
+            git_args.push(range);
+        }
+        if let Some(rev) = args.get("rev").and_then(|v| v.as_str()) {
+            git_args.push(self.sanitize_revision(rev)?);
+        }
+        git_args.push("--".to_string());
+        git_args.push(path.clone());
+
+        let git_args: Vec<&str> = git_args.iter().map(String::as_str).collect();
+        let output = self.run_git_command(&git_args).await?;
+        let lines = Self::parse_blame_porcelain(&output);
+
+        Ok(ToolResult {
+            success: true,
+            output: serde_json::to_string_pretty(&json!({
+                "path": path,
+                "lines": lines
+            }))
+            .unwrap_or_default(),
+            error: None,
+        })
+    }
+
+    /// Parse `git blame --porcelain` output into one entry per line
+    fn parse_blame_porcelain(output: &str) -> Vec<serde_json::Value> {
+        let mut commits: std::collections::HashMap<String, (String, String, String)> =
+            std::collections::HashMap::new();
+        let mut lines = Vec::new();
+        let mut current_commit = String::new();
+        let mut current_line = 0u64;
+        let mut author = String::new();
+        let mut author_time = String::new();
+        let mut summary = String::new();
+
+        for line in output.lines() {
+            if let Some(text) = line.strip_prefix('\t') {
+                let (author, date, summary) = commits
+                    .entry(current_commit.clone())
+                    .or_insert_with(|| (author.clone(), author_time.clone(), summary.clone()))
+                    .clone();
+                lines.push(json!({
+                    "line": current_line,
+                    "commit": current_commit,
+                    "author": author,
+                    "author_time": date,
+                    "summary": summary,
+                    "text": text
+                }));
+            } else if let Some(value) = line.strip_prefix("author ") {
+                author = value.to_string();
+            } else if let Some(value) = line.strip_prefix("author-time ") {
+                author_time = value.to_string();
+            } else if let Some(value) = line.strip_prefix("summary ") {
+                summary = value.to_string();
+            } else {
+                let mut parts = line.split_whitespace();
+                if let (Some(sha), Some(_), Some(final_line)) =
+                    (parts.next(), parts.next(), parts.next())
+                {
+                    if sha.len() == 40 && sha.chars().all(|c| c.is_ascii_hexdigit()) {
+                        current_commit = sha.to_string();
+                        current_line = final_line.parse().unwrap_or(0);
+                        author.clear();
+                        author_time.clear();
+                        summary.clear();
+                    }
+                }
+            }
+        }
+
+        lines
+    }
+
+    async fn git_show(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
+        let rev = args.get("rev").and_then(|v| v.as_str()).unwrap_or("HEAD");
+        let rev = self.sanitize_revision(rev)?;
+
+        let header = self
+            .run_git_command(&[
+                "show",
+                "-s",
+                "--date=iso",
+                "--format=%H%x1f%an%x1f%ae%x1f%ad%x1f%P%x1f%B",
+                &rev,
+            ])
+            .await?;
+        let mut fields = header.splitn(6, '\u{1f}');
+        let hash = fields.next().unwrap_or_default().trim();
+        let author = fields.next().unwrap_or_default();
+        let email = fields.next().unwrap_or_default();
+        let date = fields.next().unwrap_or_default();
+        let parents: Vec<&str> = fields
+            .next()
+            .unwrap_or_default()
+            .split_whitespace()
+            .collect();
+        let message = fields.next().unwrap_or_default().trim();
+
+        let patch = self
+            .run_git_command(&["show", "--format=", "--unified=3", &rev])
+            .await?;
+        let hunks = Self::parse_diff_hunks(&patch);
+
+        Ok(ToolResult {
+            success: true,
+            output: serde_json::to_string_pretty(&json!({
+                "hash": hash,
+                "author": author,
+                "email": email,
+                "date": date,
+                "parents": parents,
+                "message": message,
+                "hunks": hunks
+            }))
+            .unwrap_or_default(),
+            error: None,
+        })
+    }
+
+    async fn git_worktree(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
+        let action = Self::action_arg(&args, "list");
+
+        if action == "list" {
+            let output = self
+                .run_git_command(&["worktree", "list", "--porcelain"])
+                .await?;
+            return Ok(ToolResult {
+                success: true,
+                output: serde_json::to_string_pretty(&json!({
+                    "worktrees": Self::parse_worktree_list(&output)
+                }))
+                .unwrap_or_default(),
+                error: None,
+            });
+        }
+
+        let path = args
+            .get("path")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| anyhow::anyhow!("Missing 'path' parameter"))?;
+        let path = self.sanitize_revision(path)?;
+        if !self.security.is_path_allowed(&path) {
+            anyhow::bail!("Path not allowed by security policy: {path}");
+        }
+
+        let output = match action {
+            "add" => {
+                let mut git_args = vec!["worktree".to_string(), "add".to_string()];
+                if let Some(branch) = args.get("branch").and_then(|v| v.as_str()) {
+                    git_args.push("-b".to_string());
+                    git_args.push(self.sanitize_revision(branch)?);
+                }
+                git_args.push(path.clone());
+                if let Some(rev) = args.get("rev").and_then(|v| v.as_str()) {
+                    git_args.push(self.sanitize_revision(rev)?);
+                }
+                let git_args: Vec<&str> = git_args.iter().map(String::as_str).collect();
+                self.run_git_command(&git_args).await
+            }
+            "remove" => {
+                let force = args.get("force").and_then(|v| v.as_bool()).unwrap_or(false);
+                let mut git_args = vec!["worktree", "remove"];
+                if force {
+                    git_args.push("--force");
+                }
+                git_args.push(&path);
+                self.run_git_command(&git_args).await
+            }
+            _ => anyhow::bail!("Unknown worktree action: {action}. Use: list, add, remove"),
+        };
+
+        match output {
+            Ok(_) => Ok(ToolResult {
+                success: true,
+                output: format!("Worktree {action}: {path}"),
+                error: None,
+            }),
+            Err(e) => Ok(ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some(format!("Worktree {action} failed: {e}")),
+            }),
+        }
+    }
+
+    /// Parse `git worktree list --porcelain` output
+    fn parse_worktree_list(output: &str) -> Vec<serde_json::Value> {
+        let mut worktrees = Vec::new();
+        for block in output.split("\n\n").filter(|b| !b.trim().is_empty()) {
+            let mut entry = serde_json::Map::new();
+            for line in block.lines() {
+                let (key, value) = line.split_once(' ').unwrap_or((line, ""));
+                match key {
+                    "worktree" => {
+                        entry.insert("path".to_string(), json!(value));
+                    }
+                    "HEAD" => {
+                        entry.insert("head".to_string(), json!(value));
+                    }
+                    "branch" => {
+                        let branch = value.trim_start_matches("refs/heads/");
+                        entry.insert("branch".to_string(), json!(branch));
+                    }
+                    "bare" | "detached" | "locked" | "prunable" => {
+                        entry.insert(key.to_string(), json!(true));
+                    }
+                    _ => {}
+                }
+            }
+            worktrees.push(serde_json::Value::Object(entry));
+        }
+        worktrees
+    }
+
+    /// Run merge, rebase or cherry-pick. When git stops on conflicts the
+    /// result lists every conflicted hunk so the agent can resolve them and
+    /// continue (or abort) with a follow-up call.
+    async fn git_integrate(
+        &self,
+        operation: &str,
+        args: serde_json::Value,
+    ) -> anyhow::Result<ToolResult> {
+        let action = Self::action_arg(&args, "start");
+
+        let mut git_args = vec![operation.to_string()];
+        match action {
+            "continue" | "abort" => git_args.push(format!("--{action}")),
+            "start" => {
+                let target_key = if operation == "cherry-pick" {
+                    "rev"
+                } else {
+                    "branch"
+                };
+                let target = args
+                    .get(target_key)
+                    .and_then(|v| v.as_str())
+                    .ok_or_else(|| anyhow::anyhow!("Missing '{target_key}' parameter"))?;
+                if operation == "merge" {
+                    git_args.push("--no-edit".to_string());
+                }
+                git_args.push(self.sanitize_revision(target)?);
+            }
+            _ => anyhow::bail!("Unknown {operation} action: {action}. Use: start, continue, abort"),
+        }
+
+        let git_args: Vec<&str> = git_args.iter().map(String::as_str).collect();
+        let output = self.run_git_command_raw(&git_args).await?;
+        let stdout = String::from_utf8_lossy(&output.stdout).to_string();
+
+        if output.status.success() {
+            return Ok(ToolResult {
+                success: true,
+                output: serde_json::to_string_pretty(&json!({
+                    "status": "completed",
+                    "operation": operation,
+                    "output": stdout.trim()
+                }))
+                .unwrap_or_default(),
+                error: None,
+            });
+        }
+
+        let conflicts = self.collect_conflicts().await?;
+        if conflicts.is_empty() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            return Ok(ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some(format!("{operation} {action} failed: {stderr}")),
+            });
+        }
+
+        let files: Vec<&str> = conflicts
+            .iter()
+            .filter_map(|hunk| hunk.get("file").and_then(|v| v.as_str()))
+            .fold(Vec::new(), |mut files, file| {
+                if !files.contains(&file) {
+                    files.push(file);
+                }
+                files
+            });
+
+        // Leave `error` empty so the structured report reaches the agent.
+        Ok(ToolResult {
+            success: false,
+            output: serde_json::to_string_pretty(&json!({
+                "status": "conflicts",
+                "operation": operation,
+                "files": files,
+                "conflicts": conflicts,
+                "next_steps": format!(
+                    "Resolve the conflicts, stage the files with 'add', then run '{operation}' \
+                     with action='continue', or use action='abort' to give up"
+                )
+            }))
+            .unwrap_or_default(),
+            error: None,
+        })
+    }
+
+    /// Collect conflict hunks from every unmerged file in the working tree
+    async fn collect_conflicts(&self) -> anyhow::Result<Vec<serde_json::Value>> {
+        let unmerged = self
+            .run_git_command(&["diff", "--name-only", "--diff-filter=U"])
+            .await?;
+        let toplevel = self
+            .run_git_command(&["rev-parse", "--show-toplevel"])
+            .await?;
+        let toplevel = std::path::PathBuf::from(toplevel.trim());
+
+        let mut hunks = Vec::new();
+        for file in unmerged.lines().filter(|l| !l.trim().is_empty()) {
+            let content = tokio::fs::read(toplevel.join(file))
+                .await
+                .unwrap_or_default();
+            let content = String::from_utf8_lossy(&content);
+            let parsed = Self::parse_conflict_hunks(file, &content);
+            if parsed.is_empty() {
+                // Binary, deleted or otherwise marker-less conflict
+                hunks.push(json!({ "file": file }));
+            } else {
+                hunks.extend(parsed);
+            }
+        }
+        Ok(hunks)
+    }
+
+    /// Parse `<<<<<<<` / `|||||||` / `=======` / `>>>>>>>` conflict markers
+    fn parse_conflict_hunks(file: &str, content: &str) -> Vec<serde_json::Value> {
+        #[derive(PartialEq)]
+        enum Section {
+            Outside,
+            Ours,
+            Base,
+            Theirs,
+        }
+
+        let mut hunks = Vec::new();
+        let mut section = Section::Outside;
+        let mut start_line = 0;
+        let mut ours_label = "";
+        let mut ours: Vec<&str> = Vec::new();
+        let mut base: Vec<&str> = Vec::new();
+        let mut theirs: Vec<&str> = Vec::new();
+
+        for (index, line) in content.lines().enumerate() {
+            let line_no = index + 1;
+            match section {
+                Section::Outside => {
+                    if let Some(label) = line.strip_prefix("<<<<<<<") {
+                        section = Section::Ours;
+                        start_line = line_no;
+                        ours_label = label.trim();
+                        ours.clear();
+                        base.clear();
+                        theirs.clear();
+                    }
+                }
+                Section::Ours | Section::Base if line.starts_with("=======") => {
+                    section = Section::Theirs;
+                }
+                Section::Ours if line.starts_with("|||||||") => section = Section::Base,
+                Section::Ours => ours.push(line),
+                Section::Base => base.push(line),
+                Section::Theirs => {
+                    if let Some(label) = line.strip_prefix(">>>>>>>") {
+                        let mut hunk = json!({
+                            "file": file,
+                            "start_line": start_line,
+                            "end_line": line_no,
+                            "ours_label": ours_label,
+                            "theirs_label": label.trim(),
+                            "ours": ours.join("\n"),
+                            "theirs": theirs.join("\n"),
+                        });
+                        if !base.is_empty() {
+                            hunk["base"] = json!(base.join("\n"));
+                        }
+                        hunks.push(hunk);
+                        section = Section::Outside;
+                    } else {
+                        theirs.push(line);
+                    }
+                }
+            }
+        }
+
+        hunks
+    }
+
+    async fn git_tag(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
+        let action = Self::action_arg(&args, "list");
+
+        if action == "list" {
+            let output = self
+                .run_git_command(&[
+                    "tag",
+                    "--list",
+                    "--sort=-creatordate",
+                    "--format=%(refname:short)|%(objectname:short)|%(subject)",
+                ])
+                .await?;
+            let tags: Vec<serde_json::Value> = output
+                .lines()
+                .filter_map(|line| {
+                    let mut parts = line.splitn(3, '|');
+                    Some(json!({
+                        "name": parts.next()?,
+                        "target": parts.next().unwrap_or_default(),
+                        "subject": parts.next().unwrap_or_default()
+                    }))
+                })
+                .collect();
+            return Ok(ToolResult {
+                success: true,
+                output: serde_json::to_string_pretty(&json!({ "tags": tags })).unwrap_or_default(),
+                error: None,
+            });
+        }
+
+        let name = args
+            .get("name")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| anyhow::anyhow!("Missing 'name' parameter"))?;
+        let name = self.sanitize_revision(name)?;
+
+        let output = match action {
+            "create" => {
+                let mut git_args = vec!["tag".to_string()];
+                if let Some(message) = args.get("message").and_then(|v| v.as_str()) {
+                    git_args.push("-a".to_string());
+                    git_args.push("-m".to_string());
+                    git_args.push(Self::truncate_commit_message(message.trim()));
+                }
+                git_args.push(name.clone());
+                if let Some(rev) = args.get("rev").and_then(|v| v.as_str()) {
+                    git_args.push(self.sanitize_revision(rev)?);
+                }
+                let git_args: Vec<&str> = git_args.iter().map(String::as_str).collect();
+                self.run_git_command(&git_args).await
+            }
+            "delete" => self.run_git_command(&["tag", "-d", &name]).await,
+            _ => anyhow::bail!("Unknown tag action: {action}. Use: list, create, delete"),
+        };
+
+        match output {
+            Ok(_) => Ok(ToolResult {
+                success: true,
+                output: format!("Tag {action}: {name}"),
+                error: None,
+            }),
+            Err(e) => Ok(ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some(format!("Tag {action} failed: {e}")),
+            }),
+        }
+    }
+
+    async fn git_reset(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
+        let rev = args
+            .get("rev")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| anyhow::anyhow!("Missing 'rev' parameter"))?;
+        let rev = self.sanitize_revision(rev)?;
+
+        // Only soft resets are exposed: HEAD moves, index and files are kept.
+        match self.run_git_command(&["reset", "--soft", &rev]).await {
+            Ok(_) => Ok(ToolResult {
+                success: true,
+                output: format!("Soft reset to: {rev}"),
+                error: None,
+            }),
+            Err(e) => Ok(ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some(format!("Reset failed: {e}")),
+            }),
+        }
+    }
 }
 
 #[async_trait]
@@ -430,7 +1001,7 @@ impl Tool for GitOperationsTool {
     }
 
     fn description(&self) -> &str {
-        "执行结构化的 Git 操作（status、diff、log、branch、commit、add、checkout、stash）。提供解析的 JSON 输出，并与安全策略集成以实现自主控制。"
+        "执行结构化的 Git 操作（status、diff、log、branch、blame、show、commit、add、checkout、stash、worktree、cherry-pick、merge、rebase、tag、reset --soft）。提供解析的 JSON 输出，merge/rebase/cherry-pick 遇到冲突时返回结构化的冲突块，并与安全策略集成以实现自主控制；改写历史的操作需要 approved=true。"
     }
 
     fn parameters_schema(&self) -> serde_json::Value {
@@ -439,12 +1010,16 @@ impl Tool for GitOperationsTool {
             "properties": {
                 "operation": {
                     "type": "string",
-                    "enum": ["status", "diff", "log", "branch", "commit", "add", "checkout", "stash"],
-                    "description": "Git operation to perform"
+                    "enum": [
+                        "status", "diff", "log", "branch", "blame", "show", "commit", "add",
+                        "checkout", "stash", "worktree", "cherry-pick", "merge", "rebase", "tag",
+                        "reset"
+                    ],
+                    "description": "Git operation to perform ('reset' is always a soft reset)"
                 },
                 "message": {
                     "type": "string",
-                    "description": "Commit message (for 'commit' operation)"
+                    "description": "Commit message (for 'commit'), or annotation (for 'tag' create)"
                 },
                 "paths": {
                     "type": "string",
@@ -452,7 +1027,36 @@ impl Tool for GitOperationsTool {
                 },
                 "branch": {
                     "type": "string",
-                    "description": "Branch name (for 'checkout' operation)"
+                    "description": "Branch name (for 'checkout', 'merge', 'rebase'; new branch for 'worktree' add)"
+                },
+                "rev": {
+                    "type": "string",
+                    "description": "Commit-ish (for 'show' default HEAD, 'blame', 'cherry-pick', 'reset', 'tag' create, 'worktree' add base)"
+                },
+                "path": {
+                    "type": "string",
+                    "description": "File to blame (for 'blame') or worktree directory inside the workspace (for 'worktree')"
+                },
+                "start_line": {
+                    "type": "integer",
+                    "description": "First line to blame, 1-based (for 'blame')"
+                },
+                "end_line": {
+                    "type": "integer",
+                    "description": "Last line to blame, inclusive (for 'blame')"
+                },
+                "name": {
+                    "type": "string",
+                    "description": "Tag name (for 'tag' create/delete)"
+                },
+                "force": {
+                    "type": "boolean",
+                    "description": "Discard local changes when removing a worktree (for 'worktree' remove)"
+                },
+                "approved": {
+                    "type": "boolean",
+                    "description": "Explicitly approve history-rewriting operations (reset, rebase, tag delete, forced worktree remove) in supervised mode",
+                    "default": false
                 },
                 "files": {
                     "type": "string",
@@ -468,8 +1072,11 @@ impl Tool for GitOperationsTool {
                 },
                 "action": {
                     "type": "string",
-                    "enum": ["push", "pop", "list", "drop"],
-                    "description": "Stash action (for 'stash' operation)"
+                    "enum": [
+                        "push", "pop", "list", "drop", "add", "remove", "create", "delete",
+                        "start", "continue", "abort"
+                    ],
+                    "description": "Sub-action: 'stash' push/pop/list/drop; 'worktree' list/add/remove; 'tag' list/create/delete; 'merge'/'rebase'/'cherry-pick' start/continue/abort"
                 },
                 "index": {
                     "type": "integer",
@@ -515,7 +1122,7 @@ impl Tool for GitOperationsTool {
         }
 
         // Check autonomy level for write operations
-        if self.requires_write_access(operation) {
+        if self.requires_write_access(operation) && !Self::is_listing_action(operation, &args) {
             if !self.security.can_act() {
                 return Ok(ToolResult {
                     success: false,
@@ -538,6 +1145,23 @@ impl Tool for GitOperationsTool {
             }
         }
 
+        // History rewrites need explicit approval unless fully autonomous
+        if Self::rewrites_history(operation, &args)
+            && self.security.autonomy == AutonomyLevel::Supervised
+            && !args
+                .get("approved")
+                .and_then(|v| v.as_bool())
+                .unwrap_or(false)
+        {
+            return Ok(ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some(format!(
+                    "Action blocked: '{operation}' rewrites history and requires explicit approval (approved=true)"
+                )),
+            });
+        }
+
         // Record action for rate limiting
         if !self.security.record_action() {
             return Ok(ToolResult {
@@ -557,6 +1181,15 @@ impl Tool for GitOperationsTool {
             "add" => self.git_add(args).await,
             "checkout" => self.git_checkout(args).await,
             "stash" => self.git_stash(args).await,
+            "blame" => self.git_blame(args).await,
+            "show" => self.git_show(args).await,
+            "worktree" => self.git_worktree(args).await,
+            "cherry-pick" | "merge" | "rebase" => {
+                let operation = operation.to_string();
+                self.git_integrate(&operation, args).await
+            }
+            "tag" => self.git_tag(args).await,
+            "reset" => self.git_reset(args).await,
             _ => Ok(ToolResult {
                 success: false,
                 output: String::new(),
@@ -803,6 +1436,199 @@ mod tests {
             .contains("Unknown operation"));
     }
 
+    fn git(dir: &std::path::Path, args: &[&str]) {
+        let output = std::process::Command::new("git")
+            .args(args)
+            .current_dir(dir)
+            .output()
+            .unwrap();
+        assert!(
+            output.status.success(),
+            "git {args:?} failed: {}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+    }
+
+    /// Repository with one commit on `main`, and diverging edits of the same
+    /// line on `main` and `feature`.
+    fn init_conflicting_repo(dir: &std::path::Path) {
+        git(dir, &["init", "-q", "-b", "main"]);
+        git(dir, &["config", "user.name", "Test"]);
+        git(dir, &["config", "user.email", "test@example.com"]);
+        std::fs::write(dir.join("file.txt"), "one\ntwo\nthree\n").unwrap();
+        git(dir, &["add", "file.txt"]);
+        git(dir, &["commit", "-q", "-m", "initial"]);
+        git(dir, &["checkout", "-q", "-b", "feature"]);
+        std::fs::write(dir.join("file.txt"), "one\nfeature\nthree\n").unwrap();
+        git(dir, &["commit", "-q", "-am", "feature edit"]);
+        git(dir, &["checkout", "-q", "main"]);
+        std::fs::write(dir.join("file.txt"), "one\nmain\nthree\n").unwrap();
+        git(dir, &["commit", "-q", "-am", "main edit"]);
+    }
+
+    #[test]
+    fn history_rewrite_detection() {
+        assert!(GitOperationsTool::rewrites_history("reset", &json!({})));
+        assert!(GitOperationsTool::rewrites_history("rebase", &json!({})));
+        assert!(!GitOperationsTool::rewrites_history(
+            "rebase",
+            &json!({"action": "abort"})
+        ));
+        assert!(GitOperationsTool::rewrites_history(
+            "tag",
+            &json!({"action": "delete"})
+        ));
+        assert!(!GitOperationsTool::rewrites_history(
+            "tag",
+            &json!({"action": "create"})
+        ));
+        assert!(GitOperationsTool::rewrites_history(
+            "worktree",
+            &json!({"action": "remove", "force": true})
+        ));
+        assert!(!GitOperationsTool::rewrites_history("merge", &json!({})));
+        assert!(!GitOperationsTool::rewrites_history(
+            "cherry-pick",
+            &json!({})
+        ));
+    }
+
+    #[test]
+    fn parses_conflict_markers_with_base_section() {
+        let content =
+            "a\n<<<<<<< HEAD\nours\n||||||| base\norig\n=======\ntheirs\n>>>>>>> feature\nz\n";
+        let hunks = GitOperationsTool::parse_conflict_hunks("f.txt", content);
+        assert_eq!(hunks.len(), 1);
+        assert_eq!(hunks[0]["start_line"], 2);
+        assert_eq!(hunks[0]["end_line"], 8);
+        assert_eq!(hunks[0]["ours"], "ours");
+        assert_eq!(hunks[0]["base"], "orig");
+        assert_eq!(hunks[0]["theirs"], "theirs");
+        assert_eq!(hunks[0]["theirs_label"], "feature");
+    }
+
+    #[test]
+    fn parses_blame_porcelain_with_repeated_commits() {
+        let sha = "a".repeat(40);
+        let output = format!(
+            "{sha} 1 1 2\nauthor Alice\nauthor-time 1700000000\nsummary first\n\tline one\n{sha} 2 2\n\tline two\n"
+        );
+        let lines = GitOperationsTool::parse_blame_porcelain(&output);
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[1]["line"], 2);
+        assert_eq!(lines[1]["author"], "Alice");
+        assert_eq!(lines[1]["summary"], "first");
+        assert_eq!(lines[1]["text"], "line two");
+    }
+
+    #[tokio::test]
+    async fn merge_stops_on_conflicts_with_structured_hunks() {
+        let tmp = TempDir::new().unwrap();
+        init_conflicting_repo(tmp.path());
+        let tool = test_tool(tmp.path());
+
+        let result = tool
+            .execute(json!({"operation": "merge", "branch": "feature"}))
+            .await
+            .unwrap();
+        assert!(!result.success);
+        assert!(result.error.is_none());
+        let report: serde_json::Value = serde_json::from_str(&result.output).unwrap();
+        assert_eq!(report["status"], "conflicts");
+        assert_eq!(report["files"], json!(["file.txt"]));
+        assert_eq!(report["conflicts"][0]["ours"], "main");
+        assert_eq!(report["conflicts"][0]["theirs"], "feature");
+
+        let aborted = tool
+            .execute(json!({"operation": "merge", "action": "abort"}))
+            .await
+            .unwrap();
+        assert!(aborted.success, "{:?}", aborted.error);
+    }
+
+    #[tokio::test]
+    async fn reset_requires_approval_in_supervised_mode() {
+        let tmp = TempDir::new().unwrap();
+        init_conflicting_repo(tmp.path());
+        let tool = test_tool(tmp.path());
+
+        let blocked = tool
+            .execute(json!({"operation": "reset", "rev": "HEAD~1"}))
+            .await
+            .unwrap();
+        assert!(!blocked.success);
+        assert!(blocked.error.unwrap().contains("approved=true"));
+
+        let approved = tool
+            .execute(json!({"operation": "reset", "rev": "HEAD~1", "approved": true}))
+            .await
+            .unwrap();
+        assert!(approved.success, "{:?}", approved.error);
+        // Soft reset keeps the change staged.
+        let staged = tool
+            .execute(json!({"operation": "diff", "cached": true}))
+            .await
+            .unwrap();
+        assert!(staged.output.contains("+main"));
+    }
+
+    #[tokio::test]
+    async fn blame_show_and_listings_allowed_in_readonly_mode() {
+        let tmp = TempDir::new().unwrap();
+        init_conflicting_repo(tmp.path());
+        let security = Arc::new(SecurityPolicy {
+            autonomy: AutonomyLevel::ReadOnly,
+            ..SecurityPolicy::default()
+        });
+        let tool = GitOperationsTool::new(security, tmp.path().to_path_buf());
+
+        let blame = tool
+            .execute(json!({
+                "operation": "blame",
+                "path": "file.txt",
+                "start_line": 2,
+                "end_line": 2
+            }))
+            .await
+            .unwrap();
+        assert!(blame.success, "{:?}", blame.error);
+        let blame: serde_json::Value = serde_json::from_str(&blame.output).unwrap();
+        assert_eq!(blame["lines"].as_array().unwrap().len(), 1);
+        assert_eq!(blame["lines"][0]["summary"], "main edit");
+
+        let show = tool.execute(json!({"operation": "show"})).await.unwrap();
+        assert!(show.success, "{:?}", show.error);
+        assert!(show.output.contains("main edit"));
+
+        for operation in ["tag", "worktree"] {
+            let listed = tool.execute(json!({"operation": operation})).await.unwrap();
+            assert!(listed.success, "{operation}: {:?}", listed.error);
+        }
+
+        let create = tool
+            .execute(json!({"operation": "tag", "action": "create", "name": "v1"}))
+            .await
+            .unwrap();
+        assert!(!create.success);
+    }
+
+    #[tokio::test]
+    async fn worktree_add_rejects_paths_outside_workspace() {
+        let tmp = TempDir::new().unwrap();
+        init_conflicting_repo(tmp.path());
+        let tool = test_tool(tmp.path());
+
+        let result = tool
+            .execute(json!({
+                "operation": "worktree",
+                "action": "add",
+                "path": "../escape",
+                "branch": "wt"
+            }))
+            .await;
+        assert!(result.is_err());
+    }
+
     #[test]
     fn truncates_multibyte_commit_message_without_panicking() {
         let long = "🦀".repeat(2500);
@@ -39,6 +39,11 @@ const BOARD_INFO: &[(&str, &str, &str)] = &[
         "Raspberry Pi",
         "ARM Linux. Native GPIO via sysfs/rppal. No fixed LED pin.",
     ),
+    (
+        "simulated",
+        "ZeroClaw simulator",
+        "Software board, no hardware. GPIO 0-31, LED on pin 13. Memory from [peripherals.boards.simulation].",
+    ),
 ];
 
 /// Tool: return full board info (chip, architecture, memory map) for agent/Telegram.
@@ -30,6 +30,10 @@ const MEMORY_MAPS: &[(&str, &str)] = &[
         "esp32",
         "Flash: 0x3F40_0000 - 0x3F7F_FFFF (4 MB typical)\nIRAM: 0x4000_0000 - 0x4005_FFFF\nDRAM: 0x3FFB_0000 - 0x3FFF_FFFF",
     ),
+    (
+        "simulated",
+        "Sparse: blocks from [[peripherals.boards.simulation.memory]]; unmapped addresses read 0x00\nZeroClaw simulator",
+    ),
 ];
 
 /// Tool: report hardware memory map for connected boards.
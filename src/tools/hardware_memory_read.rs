@@ -4,8 +4,11 @@
 //! Requires probe feature and Nucleo connected via USB.
 
 use super::traits::{Tool, ToolResult};
+use crate::peripherals::traits::PeripheralTransport;
 use async_trait::async_trait;
 use serde_json::json;
+use std::fmt::Write;
+use std::sync::Arc;
 
 /// RAM base for Nucleo-F401RE (STM32F401)
 const NUCLEO_RAM_BASE: u64 = 0x2000_0000;
@@ -13,11 +16,27 @@ const NUCLEO_RAM_BASE: u64 = 0x2000_0000;
 /// Tool: read memory at address from connected Nucleo via probe-rs.
 pub struct HardwareMemoryReadTool {
     boards: Vec<String>,
+    /// Boards that answer `memory_read` over the firmware protocol
+    /// (simulated boards), keyed by board name.
+    transports: Vec<(String, Arc<dyn PeripheralTransport>)>,
 }
 
 impl HardwareMemoryReadTool {
     pub fn new(boards: Vec<String>) -> Self {
-        Self { boards }
+        Self {
+            boards,
+            transports: Vec::new(),
+        }
+    }
+
+    /// Serve reads for these boards through their transport instead of probe-rs.
+    #[must_use]
+    pub fn with_transports(
+        mut self,
+        transports: Vec<(String, Arc<dyn PeripheralTransport>)>,
+    ) -> Self {
+        self.transports = transports;
+        self
     }
 
     fn chip_for_board(board: &str) -> Option<&'static str> {
@@ -78,6 +97,26 @@ impl Tool for HardwareMemoryReadTool {
             .or_else(|| self.boards.first().cloned())
             .unwrap_or_else(|| "nucleo-f401re".into());
 
+        let address_str = args
+            .get("address")
+            .and_then(|v| v.as_str())
+            .unwrap_or("0x20000000");
+        let address = parse_hex_address(address_str).unwrap_or(NUCLEO_RAM_BASE);
+
+        let requested_length = args.get("length").and_then(|v| v.as_u64()).unwrap_or(128);
+        let length = usize::try_from(requested_length)
+            .unwrap_or(256)
+            .clamp(1, 256);
+
+        if let Some((_, transport)) = self.transports.iter().find(|(name, _)| *name == board) {
+            return transport
+                .request(
+                    "memory_read",
+                    json!({ "address": address, "length": length }),
+                )
+                .await;
+        }
+
         let chip = Self::chip_for_board(&board);
         if chip.is_none() {
             return Ok(ToolResult {
@@ -90,20 +129,9 @@ impl Tool for HardwareMemoryReadTool {
             });
         }
 
-        let address_str = args
-            .get("address")
-            .and_then(|v| v.as_str())
-            .unwrap_or("0x20000000");
-        let _address = parse_hex_address(address_str).unwrap_or(NUCLEO_RAM_BASE);
-
-        let requested_length = args.get("length").and_then(|v| v.as_u64()).unwrap_or(128);
-        let _length = usize::try_from(requested_length)
-            .unwrap_or(256)
-            .clamp(1, 256);
-
         #[cfg(feature = "probe")]
         {
-            match probe_read_memory(chip.unwrap(), _address, _length) {
+            match probe_read_memory(chip.unwrap(), address, length) {
                 Ok(output) => {
                     return Ok(ToolResult {
                         success: true,
@@ -157,8 +185,16 @@ fn probe_read_memory(chip: &str, address: u64, length: usize) -> anyhow::Result<
     core.read_8(address, &mut buf)
         .map_err(|e| anyhow::anyhow!("{}", e))?;
 
-    // Format as hex dump: address | bytes (16 per line)
-    let mut out = format!("Memory read from 0x{:08X} ({} bytes):\n\n", address, length);
+    Ok(format_hex_dump(address, &buf))
+}
+
+/// Format bytes read from `address` as a hex dump (16 bytes per line).
+pub(crate) fn format_hex_dump(address: u64, buf: &[u8]) -> String {
+    let mut out = format!(
+        "Memory read from 0x{:08X} ({} bytes):\n\n",
+        address,
+        buf.len()
+    );
     const COLS: usize = 16;
     for (i, chunk) in buf.chunks(COLS).enumerate() {
         let addr = address + (i * COLS) as u64;
@@ -177,7 +213,7 @@ fn probe_read_memory(chip: &str, address: u64, length: usize) -> anyhow::Result<
                 }
             })
             .collect();
-        out.push_str(&format!("0x{:08X}  {:48}  {}\n", addr, hex, ascii));
+        let _ = writeln!(out, "0x{:08X}  {:48}  {}", addr, hex, ascii);
     }
-    Ok(out)
+    out
 }
@@ -46,12 +46,19 @@ pub mod memory_recall;
 pub mod memory_store;
 pub mod model_routing_config;
 pub mod pdf_read;
+pub mod process;
 pub mod proxy_config;
 pub mod pushover;
 pub mod schedule;
 pub mod schema;
 pub mod screenshot;
+pub mod search_backends;
 pub mod shell;
+pub mod sop_advance;
+pub mod sop_approve;
+pub mod sop_execute;
+pub mod sop_list;
+pub mod sop_status;
 pub mod traits;
 pub mod task_plan;
 pub mod web_fetch;
@@ -87,6 +94,7 @@ pub use memory_recall::MemoryRecallTool;
 pub use memory_store::MemoryStoreTool;
 pub use model_routing_config::ModelRoutingConfigTool;
 pub use pdf_read::PdfReadTool;
+pub use process::ProcessTool;
 pub use proxy_config::ProxyConfigTool;
 pub use pushover::PushoverTool;
 pub use schedule::ScheduleTool;
@@ -94,10 +102,15 @@ pub use schedule::ScheduleTool;
 pub use schema::{CleaningStrategy, SchemaCleanr};
 pub use screenshot::ScreenshotTool;
 pub use shell::ShellTool;
+pub use sop_advance::SopAdvanceTool;
+pub use sop_approve::SopApproveTool;
+pub use sop_execute::SopExecuteTool;
+pub use sop_list::SopListTool;
+pub use sop_status::SopStatusTool;
 pub use task_plan::TaskPlanTool;
 pub use traits::Tool;
 #[allow(unused_imports)]
-pub use traits::{ToolResult, ToolSpec};
+pub use traits::{GenerationOverride, ToolResult, ToolSpec};
 pub use web_fetch::WebFetchTool;
 pub use web_search_tool::WebSearchTool;
 
@@ -184,6 +197,7 @@ pub fn all_tools(
         security,
         Arc::new(NativeRuntime::new()),
         memory,
+        Arc::new(crate::observability::NoopObserver),
         composio_key,
         composio_entity_id,
         browser_config,
@@ -203,6 +217,7 @@ pub fn all_tools_with_runtime(
     security: &Arc<SecurityPolicy>,
     runtime: Arc<dyn RuntimeAdapter>,
     memory: Arc<dyn Memory>,
+    observer: Arc<dyn crate::observability::Observer>,
     composio_key: Option<&str>,
     composio_entity_id: Option<&str>,
     browser_config: &crate::config::BrowserConfig,
@@ -214,7 +229,8 @@ pub fn all_tools_with_runtime(
     root_config: &crate::config::Config,
 ) -> Vec<Box<dyn Tool>> {
     let mut tool_arcs: Vec<Arc<dyn Tool>> = vec![
-        Arc::new(ShellTool::new(security.clone(), runtime)),
+        Arc::new(ShellTool::new(security.clone(), runtime.clone())),
+        Arc::new(ProcessTool::new(security.clone(), runtime)),
         Arc::new(FileReadTool::new(security.clone())),
         Arc::new(FileWriteTool::new(security.clone())),
         Arc::new(FileEditTool::new(security.clone())),
@@ -228,9 +244,9 @@ pub fn all_tools_with_runtime(
         Arc::new(CronRunsTool::new(config.clone())),
         Arc::new(MemoryStoreTool::new(memory.clone(), security.clone())),
         Arc::new(MemoryRecallTool::new(memory.clone())),
-        Arc::new(MemoryForgetTool::new(memory, security.clone())),
+        Arc::new(MemoryForgetTool::new(memory.clone(), security.clone())),
         Arc::new(ScheduleTool::new(security.clone(), root_config.clone())),
-        Arc::new(TaskPlanTool::new(security.clone())),
+        Arc::new(TaskPlanTool::new(security.clone()).with_workspace(workspace_dir)),
         Arc::new(ModelRoutingConfigTool::new(
             config.clone(),
             security.clone(),
@@ -294,17 +310,40 @@ pub fn all_tools_with_runtime(
 
     // Web search tool (enabled by default for GLM and other models)
     if root_config.web_search.enabled {
-        tool_arcs.push(Arc::new(WebSearchTool::new(
+        tool_arcs.push(Arc::new(WebSearchTool::from_config(
             security.clone(),
-            root_config.web_search.provider.clone(),
-            root_config.web_search.brave_api_key.clone(),
-            None,
-            root_config.web_search.max_results,
-            root_config.web_search.timeout_secs,
+            &root_config.web_search,
+            workspace_dir,
             "Mozilla/5.0 (Macintosh; Intel Mac OS X 10_15_7) AppleWebKit/537.36".to_string(),
         )));
     }
 
+    // SOP tools share one engine, audit log and metrics collector
+    let mut sop_engine = crate::sop::SopEngine::new(root_config.sop.clone());
+    sop_engine.reload(workspace_dir);
+    if !sop_engine.sops().is_empty() {
+        let engine = Arc::new(std::sync::Mutex::new(sop_engine));
+        let audit = Arc::new(crate::sop::SopAuditLogger::new(memory));
+        let collector = Arc::new(crate::sop::SopMetricsCollector::new().with_observer(observer));
+        tool_arcs.push(Arc::new(SopListTool::new(engine.clone())));
+        tool_arcs.push(Arc::new(
+            SopExecuteTool::new(engine.clone()).with_audit(audit.clone()),
+        ));
+        tool_arcs.push(Arc::new(
+            SopAdvanceTool::new(engine.clone())
+                .with_audit(audit.clone())
+                .with_collector(collector.clone()),
+        ));
+        tool_arcs.push(Arc::new(
+            SopApproveTool::new(engine.clone())
+                .with_audit(audit)
+                .with_collector(collector.clone()),
+        ));
+        tool_arcs.push(Arc::new(
+            SopStatusTool::new(engine).with_collector(collector),
+        ));
+    }
+
     // PDF extraction (feature-gated at compile time via rag-pdf)
     tool_arcs.push(Arc::new(PdfReadTool::new(security.clone())));
 
@@ -366,7 +405,9 @@ pub fn all_tools_with_runtime(
             tracing::info!("Adding Gitee TTS tool for Gitee AI provider");
             tool_arcs.push(Arc::new(GiteeTtsTool::new(api_key.clone())));
         } else {
-            tracing::warn!("Gitee AI provider detected but no API key found in reliability.api_keys");
+            tracing::warn!(
+                "Gitee AI provider detected but no API key found in reliability.api_keys"
+            );
         }
     }
 
@@ -434,6 +475,7 @@ mod tests {
         assert!(names.contains(&"model_routing_config"));
         assert!(names.contains(&"pushover"));
         assert!(names.contains(&"proxy_config"));
+        assert!(names.contains(&"process"));
     }
 
     #[test]
@@ -656,4 +698,75 @@ mod tests {
         let names: Vec<&str> = tools.iter().map(|t| t.name()).collect();
         assert!(!names.contains(&"delegate"));
     }
+
+    #[tokio::test]
+    async fn all_tools_registers_sop_tools_reporting_run_metrics() {
+        let tmp = TempDir::new().unwrap();
+        let sop_dir = tmp.path().join("sops").join("tools-metrics-sop");
+        std::fs::create_dir_all(&sop_dir).unwrap();
+        std::fs::write(
+            sop_dir.join("SOP.toml"),
+            "[sop]\nname = \"tools-metrics-sop\"\ndescription = \"Metrics check\"\n\
+             execution_mode = \"auto\"\n\n[[triggers]]\ntype = \"manual\"\n",
+        )
+        .unwrap();
+        std::fs::write(sop_dir.join("SOP.md"), "## Steps\n\n1. **Check** — Look.\n").unwrap();
+
+        let security = Arc::new(SecurityPolicy::default());
+        let mem_cfg = MemoryConfig {
+            backend: "sqlite".into(),
+            ..MemoryConfig::default()
+        };
+        let mem: Arc<dyn Memory> =
+            Arc::from(crate::memory::create_memory(&mem_cfg, tmp.path(), None).unwrap());
+        let cfg = test_config(&tmp);
+
+        let tools = all_tools_with_runtime(
+            Arc::new(Config::default()),
+            &security,
+            Arc::new(NativeRuntime::new()),
+            mem,
+            Arc::new(crate::observability::PrometheusObserver::global()),
+            None,
+            None,
+            &BrowserConfig::default(),
+            &crate::config::HttpRequestConfig::default(),
+            &crate::config::WebFetchConfig::default(),
+            tmp.path(),
+            &HashMap::new(),
+            None,
+            &cfg,
+        );
+        let tool = |name: &str| tools.iter().find(|t| t.name() == name).unwrap();
+
+        let started = tool("sop_execute")
+            .execute(serde_json::json!({ "name": "tools-metrics-sop" }))
+            .await
+            .unwrap();
+        assert!(started.success, "{started:?}");
+        let run_id = started
+            .output
+            .lines()
+            .next()
+            .and_then(|line| line.strip_prefix("SOP run started: "))
+            .unwrap()
+            .to_string();
+        let advanced = tool("sop_advance")
+            .execute(serde_json::json!({
+                "run_id": run_id,
+                "status": "completed",
+                "output": "looked"
+            }))
+            .await
+            .unwrap();
+        assert!(advanced.success, "{advanced:?}");
+
+        let metrics = crate::observability::PrometheusObserver::global().encode();
+        assert!(
+            metrics.contains(
+                r#"zeroclaw_sop_runs_total{sop="tools-metrics-sop",status="completed"} 1"#
+            ),
+            "{metrics}"
+        );
+    }
 }
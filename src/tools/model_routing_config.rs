@@ -1,5 +1,6 @@
 use super::traits::{Tool, ToolResult};
 use crate::config::{ClassificationRule, Config, DelegateAgentConfig, ModelRouteConfig};
+use crate::providers::GenerationParams;
 use crate::security::SecurityPolicy;
 use crate::util::MaybeSet;
 use async_trait::async_trait;
@@ -466,8 +467,8 @@ impl ModelRoutingConfigTool {
             hint: hint.clone(),
             provider: provider.clone(),
             model: model.clone(),
-            max_tokens: None,
             api_key: None,
+            generation: GenerationParams::default(),
         });
 
         next_route.hint = hint.clone();
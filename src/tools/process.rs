@@ -7,17 +7,32 @@ use crate::security::SyscallAnomalyDetector;
 use async_trait::async_trait;
 use serde_json::json;
 use std::collections::HashMap;
+use std::io::{Read, Write};
 use std::process::Stdio;
 use std::sync::{Arc, Mutex, RwLock};
-use std::time::Instant;
+use std::time::{Duration, Instant};
 use tokio::io::AsyncReadExt;
 
 /// Maximum output bytes kept per stream (stdout/stderr): 512KB.
+/// For PTY sessions this is the scrollback size.
 const MAX_OUTPUT_BYTES: usize = 524_288;
 
 /// Maximum concurrent background processes.
 const MAX_PROCESSES: usize = 8;
 
+/// Default and maximum `wait_for` timeouts.
+const DEFAULT_WAIT_TIMEOUT_SECS: u64 = 30;
+const MAX_WAIT_TIMEOUT_SECS: u64 = 300;
+
+/// Poll interval while `wait_for` watches the scrollback.
+const WAIT_POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+/// Maximum bytes accepted by a single `write`/`send_keys` call.
+const MAX_INPUT_BYTES: usize = 4096;
+
+/// Shells whose submitted input lines are validated as commands.
+const INTERACTIVE_SHELLS: &[&str] = &["sh", "bash", "zsh", "dash", "ash", "fish", "ksh"];
+
 #[derive(Debug, Default, Clone)]
 struct OutputBuffer {
     data: String,
@@ -29,10 +44,78 @@ struct ProcessEntry {
     command: String,
     pid: u32,
     started_at: Instant,
-    child: Mutex<tokio::process::Child>,
+    child: Mutex<ProcessChild>,
     stdout_buf: Arc<Mutex<OutputBuffer>>,
     stderr_buf: Arc<Mutex<OutputBuffer>>,
     analyzed_offsets: Mutex<(u64, u64)>,
+    /// Writer for the PTY master; `None` for plain background processes.
+    pty_writer: Option<Mutex<Box<dyn Write + Send>>>,
+    /// Absolute scrollback offset consumed by the last successful `wait_for`.
+    wait_cursor: Mutex<u64>,
+    /// Input written since the last submitted line.
+    pending_line: Mutex<PendingLine>,
+}
+
+/// Line being typed into a PTY session, validated as a whole on submit.
+#[derive(Debug, Default, Clone)]
+struct PendingLine {
+    text: String,
+    /// Control or navigation keys (history, completion, cursor movement)
+    /// were sent, so the program may submit something other than `text`.
+    edited: bool,
+}
+
+/// A line submitted to a PTY session with CR or LF.
+#[derive(Debug, PartialEq, Eq)]
+struct SubmittedLine {
+    text: String,
+    edited: bool,
+}
+
+/// A spawned child, either with piped stdio or attached to a PTY.
+enum ProcessChild {
+    Piped(tokio::process::Child),
+    Pty(Box<dyn portable_pty::Child + Send + Sync>),
+}
+
+impl ProcessChild {
+    fn id(&self) -> Option<u32> {
+        match self {
+            Self::Piped(child) => child.id(),
+            Self::Pty(child) => child.process_id(),
+        }
+    }
+
+    /// Exit code if the child has exited, `None` while it is running.
+    fn try_wait(&mut self) -> std::io::Result<Option<i32>> {
+        match self {
+            Self::Piped(child) => Ok(child.try_wait()?.map(|status| status.code().unwrap_or(-1))),
+            Self::Pty(child) => Ok(child
+                .try_wait()?
+                .map(|status| i32::try_from(status.exit_code()).unwrap_or(-1))),
+        }
+    }
+
+    fn start_kill(&mut self) -> std::io::Result<()> {
+        match self {
+            Self::Piped(child) => child.start_kill(),
+            Self::Pty(child) => child.kill(),
+        }
+    }
+}
+
+impl ProcessEntry {
+    fn is_running(&self) -> bool {
+        self.child
+            .lock()
+            .map(|mut c| matches!(c.try_wait(), Ok(None)))
+            .unwrap_or(false)
+    }
+
+    /// Whether input lines should be checked as shell commands.
+    fn is_interactive_shell(&self) -> bool {
+        session_program(&self.command).is_some_and(|program| INTERACTIVE_SHELLS.contains(&program))
+    }
 }
 
 /// Background process management tool.
@@ -40,6 +123,10 @@ struct ProcessEntry {
 /// Allows the agent to spawn long-running commands, check their output,
 /// and terminate them. Complements the synchronous `ShellTool` for commands
 /// that need to run beyond the 60-second shell timeout.
+///
+/// Processes spawned with `pty: true` run on a pseudo-terminal, so the agent
+/// can drive REPLs and interactive prompts with `write`, `send_keys` and
+/// `wait_for`. PTY output is ANSI-stripped into a bounded scrollback buffer.
 pub struct ProcessTool {
     security: Arc<SecurityPolicy>,
     runtime: Arc<dyn RuntimeAdapter>,
@@ -81,18 +168,12 @@ impl ProcessTool {
             .and_then(|v| v.as_str())
             .ok_or_else(|| anyhow::anyhow!("Missing 'command' parameter for spawn action"))?;
 
+        let use_pty = args.get("pty").and_then(|v| v.as_bool()).unwrap_or(false);
+
         // Check concurrent running process count.
         {
             let processes = self.processes.read().unwrap();
-            let running = processes
-                .values()
-                .filter(|e| {
-                    e.child
-                        .lock()
-                        .map(|mut c| matches!(c.try_wait(), Ok(None)))
-                        .unwrap_or(false)
-                })
-                .count();
+            let running = processes.values().filter(|e| e.is_running()).count();
             if running >= MAX_PROCESSES {
                 return Ok(ToolResult {
                     success: false,
@@ -157,9 +238,6 @@ impl ProcessTool {
             }
         };
 
-        cmd.stdin(Stdio::null());
-        cmd.stdout(Stdio::piped());
-        cmd.stderr(Stdio::piped());
         cmd.env_clear();
 
         for var in collect_allowed_shell_env_vars(&self.security) {
@@ -168,30 +246,48 @@ impl ProcessTool {
             }
         }
 
-        let mut child = match cmd.spawn() {
-            Ok(child) => child,
-            Err(e) => {
-                return Ok(ToolResult {
-                    success: false,
-                    output: String::new(),
-                    error: Some(format!("Failed to spawn process: {e}")),
-                });
+        let stdout_buf = Arc::new(Mutex::new(OutputBuffer::default()));
+        let stderr_buf = Arc::new(Mutex::new(OutputBuffer::default()));
+
+        let (child, pty_writer) = if use_pty {
+            match spawn_on_pty(&cmd, stdout_buf.clone()) {
+                Ok((child, writer)) => (child, Some(Mutex::new(writer))),
+                Err(e) => {
+                    return Ok(ToolResult {
+                        success: false,
+                        output: String::new(),
+                        error: Some(format!("Failed to spawn process on a PTY: {e}")),
+                    });
+                }
             }
+        } else {
+            cmd.stdin(Stdio::null());
+            cmd.stdout(Stdio::piped());
+            cmd.stderr(Stdio::piped());
+
+            let mut child = match cmd.spawn() {
+                Ok(child) => child,
+                Err(e) => {
+                    return Ok(ToolResult {
+                        success: false,
+                        output: String::new(),
+                        error: Some(format!("Failed to spawn process: {e}")),
+                    });
+                }
+            };
+
+            // Set up background output readers.
+            if let Some(stdout) = child.stdout.take() {
+                spawn_reader_task(stdout, stdout_buf.clone());
+            }
+            if let Some(stderr) = child.stderr.take() {
+                spawn_reader_task(stderr, stderr_buf.clone());
+            }
+            (ProcessChild::Piped(child), None)
         };
 
         let pid = child.id().unwrap_or(0);
 
-        // Set up background output readers.
-        let stdout_buf = Arc::new(Mutex::new(OutputBuffer::default()));
-        let stderr_buf = Arc::new(Mutex::new(OutputBuffer::default()));
-
-        if let Some(stdout) = child.stdout.take() {
-            spawn_reader_task(stdout, stdout_buf.clone());
-        }
-        if let Some(stderr) = child.stderr.take() {
-            spawn_reader_task(stderr, stderr_buf.clone());
-        }
-
         let id = {
             let mut next = self.next_id.lock().unwrap();
             let id = *next;
@@ -208,7 +304,11 @@ impl ProcessTool {
             stdout_buf,
             stderr_buf,
             analyzed_offsets: Mutex::new((0, 0)),
+            pty_writer,
+            wait_cursor: Mutex::new(0),
+            pending_line: Mutex::new(PendingLine::default()),
         };
+        let pty = entry.pty_writer.is_some();
 
         self.processes.write().unwrap().insert(id, entry);
 
@@ -217,6 +317,7 @@ impl ProcessTool {
             output: json!({
                 "id": id,
                 "pid": pid,
+                "pty": pty,
                 "message": format!("Process started: {command}")
             })
             .to_string(),
@@ -242,9 +343,7 @@ impl ProcessTool {
         for entry in processes.values() {
             let status = match entry.child.lock() {
                 Ok(mut child) => match child.try_wait() {
-                    Ok(Some(status)) => {
-                        format!("exited ({})", status.code().unwrap_or(-1))
-                    }
+                    Ok(Some(code)) => format!("exited ({code})"),
                     Ok(None) => "running".to_string(),
                     Err(e) => format!("error: {e}"),
                 },
@@ -255,6 +354,7 @@ impl ProcessTool {
                 "id": entry.id,
                 "command": entry.command,
                 "pid": entry.pid,
+                "pty": entry.pty_writer.is_some(),
                 "status": status,
                 "uptime_secs": entry.started_at.elapsed().as_secs(),
             }));
@@ -321,17 +421,300 @@ impl ProcessTool {
             }
         }
 
+        let tail_lines = args
+            .get("lines")
+            .and_then(|v| v.as_u64())
+            .and_then(|v| usize::try_from(v).ok());
+
         Ok(ToolResult {
             success: true,
             output: json!({
-                "stdout": stdout,
-                "stderr": stderr,
+                "stdout": tail_output(&stdout, tail_lines),
+                "stderr": tail_output(&stderr, tail_lines),
             })
             .to_string(),
             error: None,
         })
     }
 
+    /// Shared gate for `write` and `send_keys`: resolve the PTY session,
+    /// enforce autonomy and the action budget, then write `input`.
+    fn write_to_session(
+        &self,
+        id: usize,
+        input: &str,
+        approved: bool,
+    ) -> anyhow::Result<ToolResult> {
+        if input.len() > MAX_INPUT_BYTES {
+            return Ok(ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some(format!(
+                    "Input too large ({} bytes, max {MAX_INPUT_BYTES})",
+                    input.len()
+                )),
+            });
+        }
+
+        let processes = self.processes.read().unwrap();
+        let Some(entry) = processes.get(&id) else {
+            return Ok(ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some(format!("No process with id {id}")),
+            });
+        };
+        let Some(writer) = &entry.pty_writer else {
+            return Ok(ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some(format!(
+                    "Process {id} has no terminal; spawn it with pty=true to send input"
+                )),
+            });
+        };
+        if !entry.is_running() {
+            return Ok(ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some(format!("Process {id} has exited")),
+            });
+        }
+
+        if self.security.is_rate_limited() {
+            return Ok(ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some("Rate limit exceeded: too many actions in the last hour".into()),
+            });
+        }
+
+        // Lines submitted to an interactive shell are commands in their own
+        // right, so they go through the same validation as `spawn`. Input is
+        // buffered across calls and checked as a whole line when CR or LF
+        // submits it. Lines edited with control or navigation keys, and input
+        // to any other program (REPLs, database shells), cannot be validated
+        // as a command and need explicit approval instead.
+        let mut pending = entry.pending_line.lock().unwrap();
+        let (lines, next_pending) = submitted_lines(&pending, input);
+        if !entry.is_interactive_shell() && !lines.is_empty() && !approved {
+            return Ok(ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some(format!(
+                    "Input to non-shell session {id} ('{}') cannot be validated by the \
+                     command policy; resend with approved=true",
+                    entry.command
+                )),
+            });
+        }
+        if entry.is_interactive_shell() {
+            for line in &lines {
+                if line.edited && !approved {
+                    return Ok(ToolResult {
+                        success: false,
+                        output: String::new(),
+                        error: Some(format!(
+                            "Line submitted to shell session {id} was edited with control or \
+                             navigation keys (history, completion), so the command policy \
+                             cannot validate it; resend with approved=true"
+                        )),
+                    });
+                }
+                if line.text.is_empty() {
+                    continue;
+                }
+                if let Err(reason) = self
+                    .security
+                    .validate_command_execution(&line.text, approved)
+                {
+                    return Ok(ToolResult {
+                        success: false,
+                        output: String::new(),
+                        error: Some(reason),
+                    });
+                }
+                if let Some(path) = self.security.forbidden_path_argument(&line.text) {
+                    return Ok(ToolResult {
+                        success: false,
+                        output: String::new(),
+                        error: Some(format!("Path blocked by security policy: {path}")),
+                    });
+                }
+            }
+        }
+
+        if let Err(e) = self
+            .security
+            .enforce_tool_operation(ToolOperation::Act, "process")
+        {
+            return Ok(ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some(e),
+            });
+        }
+
+        let mut writer = writer.lock().unwrap();
+        if let Err(e) = writer
+            .write_all(input.as_bytes())
+            .and_then(|()| writer.flush())
+        {
+            return Ok(ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some(format!("Failed to write to process {id}: {e}")),
+            });
+        }
+        *pending = next_pending;
+
+        Ok(ToolResult {
+            success: true,
+            output: json!({ "id": id, "bytes_written": input.len() }).to_string(),
+            error: None,
+        })
+    }
+
+    fn handle_write(&self, args: &serde_json::Value) -> anyhow::Result<ToolResult> {
+        let id = parse_id(args, "write")?;
+        let input = args
+            .get("input")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| anyhow::anyhow!("Missing 'input' parameter for write action"))?;
+        self.write_to_session(id, input, parse_approved(args))
+    }
+
+    fn handle_send_keys(&self, args: &serde_json::Value) -> anyhow::Result<ToolResult> {
+        let id = parse_id(args, "send_keys")?;
+        let keys = args
+            .get("keys")
+            .and_then(|v| v.as_array())
+            .ok_or_else(|| anyhow::anyhow!("Missing 'keys' parameter for send_keys action"))?;
+
+        let mut input = String::new();
+        for key in keys {
+            let Some(key) = key.as_str() else {
+                anyhow::bail!("'keys' must be an array of strings");
+            };
+            match encode_key(key) {
+                Some(bytes) => input.push_str(&bytes),
+                None => {
+                    return Ok(ToolResult {
+                        success: false,
+                        output: String::new(),
+                        error: Some(format!(
+                            "Unknown key '{key}'. Use names like Enter, Tab, Escape, Up, \
+                             Backspace, C-c, or a single literal character"
+                        )),
+                    });
+                }
+            }
+        }
+        self.write_to_session(id, &input, parse_approved(args))
+    }
+
+    async fn handle_wait_for(&self, args: &serde_json::Value) -> anyhow::Result<ToolResult> {
+        if let Err(e) = self
+            .security
+            .enforce_tool_operation(ToolOperation::Read, "process")
+        {
+            return Ok(ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some(e),
+            });
+        }
+
+        let id = parse_id(args, "wait_for")?;
+        let pattern = args
+            .get("pattern")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| anyhow::anyhow!("Missing 'pattern' parameter for wait_for action"))?;
+        let regex = match regex::Regex::new(pattern) {
+            Ok(regex) => regex,
+            Err(e) => {
+                return Ok(ToolResult {
+                    success: false,
+                    output: String::new(),
+                    error: Some(format!("Invalid pattern: {e}")),
+                });
+            }
+        };
+        let timeout_secs = args
+            .get("timeout_secs")
+            .and_then(|v| v.as_u64())
+            .unwrap_or(DEFAULT_WAIT_TIMEOUT_SECS)
+            .min(MAX_WAIT_TIMEOUT_SECS);
+        let deadline = Instant::now() + Duration::from_secs(timeout_secs);
+
+        loop {
+            // Snapshot under the lock, then release it before sleeping.
+            let (snapshot, cursor, running) = {
+                let processes = self.processes.read().unwrap();
+                let Some(entry) = processes.get(&id) else {
+                    return Ok(ToolResult {
+                        success: false,
+                        output: String::new(),
+                        error: Some(format!("No process with id {id}")),
+                    });
+                };
+                let cursor = *entry.wait_cursor.lock().unwrap();
+                (
+                    snapshot_output_buffer(&entry.stdout_buf),
+                    cursor,
+                    entry.is_running(),
+                )
+            };
+
+            let mut unseen_start = cursor;
+            let unseen = slice_unseen_output(
+                &snapshot.data,
+                snapshot.dropped_prefix_bytes,
+                &mut unseen_start,
+            );
+            let unseen_offset = u64::try_from(snapshot.data.len() - unseen.len())
+                .unwrap_or(u64::MAX)
+                .saturating_add(snapshot.dropped_prefix_bytes);
+
+            if let Some(found) = regex.find(unseen) {
+                let consumed =
+                    unseen_offset.saturating_add(u64::try_from(found.end()).unwrap_or(u64::MAX));
+                if let Some(entry) = self.processes.read().unwrap().get(&id) {
+                    *entry.wait_cursor.lock().unwrap() = consumed;
+                }
+                return Ok(ToolResult {
+                    success: true,
+                    output: json!({
+                        "matched": found.as_str(),
+                        "output": &unseen[..found.end()],
+                    })
+                    .to_string(),
+                    error: None,
+                });
+            }
+
+            if !running {
+                return Ok(ToolResult {
+                    success: false,
+                    output: json!({ "output": unseen }).to_string(),
+                    error: Some(format!(
+                        "Process {id} exited before output matched '{pattern}'"
+                    )),
+                });
+            }
+            if Instant::now() >= deadline {
+                return Ok(ToolResult {
+                    success: false,
+                    output: json!({ "output": unseen }).to_string(),
+                    error: Some(format!(
+                        "Timed out after {timeout_secs}s waiting for '{pattern}'"
+                    )),
+                });
+            }
+            tokio::time::sleep(WAIT_POLL_INTERVAL).await;
+        }
+    }
+
     fn handle_kill(&self, args: &serde_json::Value) -> anyhow::Result<ToolResult> {
         if let Err(e) = self
             .security
@@ -396,6 +779,234 @@ fn parse_id(args: &serde_json::Value, action: &str) -> anyhow::Result<usize> {
         .ok_or_else(|| anyhow::anyhow!("Missing 'id' parameter for {action} action"))
 }
 
+fn parse_approved(args: &serde_json::Value) -> bool {
+    args.get("approved")
+        .and_then(|v| v.as_bool())
+        .unwrap_or(false)
+}
+
+/// Program a session runs, looking through `env` wrappers and their
+/// options and `NAME=value` assignments.
+fn session_program(command: &str) -> Option<&str> {
+    command
+        .split_whitespace()
+        .map(|word| (word, word.rsplit('/').next().unwrap_or(word)))
+        .find(|(word, program)| *program != "env" && !word.starts_with('-') && !word.contains('='))
+        .map(|(_, program)| program)
+}
+
+/// Lines that `input` submits (terminated by CR or LF) when typed after
+/// `pending`, plus the line left pending afterwards. Blank lines are skipped
+/// unless control or navigation keys were sent before them.
+fn submitted_lines(pending: &PendingLine, input: &str) -> (Vec<SubmittedLine>, PendingLine) {
+    let mut line = pending.clone();
+    let mut lines = Vec::new();
+    let mut chars = input.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '\r' | '\n' => {
+                let done = std::mem::take(&mut line);
+                let text = done.text.trim();
+                if !text.is_empty() || done.edited {
+                    lines.push(SubmittedLine {
+                        text: text.to_string(),
+                        edited: done.edited,
+                    });
+                }
+            }
+            '\x7f' | '\x08' => {
+                line.text.pop();
+            }
+            // Ctrl-C abandons the line.
+            '\x03' => line = PendingLine::default(),
+            '\x1b' => {
+                line.edited = true;
+                // Skip the rest of a CSI sequence such as `ESC [ A`.
+                if chars.next_if_eq(&'[').is_some() {
+                    for c in chars.by_ref() {
+                        if ('\x40'..='\x7e').contains(&c) {
+                            break;
+                        }
+                    }
+                }
+            }
+            c if c.is_control() => line.edited = true,
+            c => line.text.push(c),
+        }
+    }
+    (lines, line)
+}
+
+/// Translate a key name into the bytes a terminal would send.
+///
+/// Supports named keys (`Enter`, `Tab`, `Escape`, arrows, …), control
+/// chords written as `C-x` / `Ctrl-x`, and single literal characters.
+fn encode_key(key: &str) -> Option<String> {
+    let named = match key.to_ascii_lowercase().as_str() {
+        "enter" | "return" => Some("\r"),
+        "tab" => Some("\t"),
+        "escape" | "esc" => Some("\x1b"),
+        "backspace" => Some("\x7f"),
+        "space" => Some(" "),
+        "up" => Some("\x1b[A"),
+        "down" => Some("\x1b[B"),
+        "right" => Some("\x1b[C"),
+        "left" => Some("\x1b[D"),
+        "home" => Some("\x1b[H"),
+        "end" => Some("\x1b[F"),
+        "delete" => Some("\x1b[3~"),
+        "pageup" => Some("\x1b[5~"),
+        "pagedown" => Some("\x1b[6~"),
+        _ => None,
+    };
+    if let Some(bytes) = named {
+        return Some(bytes.to_string());
+    }
+
+    let lower = key.to_ascii_lowercase();
+    if let Some(chord) = lower
+        .strip_prefix("c-")
+        .or_else(|| lower.strip_prefix("ctrl-"))
+        .or_else(|| lower.strip_prefix("ctrl+"))
+    {
+        let mut chars = chord.chars();
+        return match (chars.next(), chars.next()) {
+            (Some(c), None) if c.is_ascii_lowercase() || "@[\\]^_".contains(c) => {
+                let code = (c.to_ascii_uppercase() as u8) & 0x1f;
+                Some(char::from(code).to_string())
+            }
+            _ => None,
+        };
+    }
+
+    let mut chars = key.chars();
+    match (chars.next(), chars.next()) {
+        (Some(c), None) => Some(c.to_string()),
+        _ => None,
+    }
+}
+
+/// Return only the last `lines` lines of `text` (all of it when `None`).
+fn tail_output(text: &str, lines: Option<usize>) -> &str {
+    let Some(lines) = lines else {
+        return text;
+    };
+    if lines == 0 {
+        return "";
+    }
+    let trimmed = text.strip_suffix('\n').unwrap_or(text);
+    match trimmed.rmatch_indices('\n').nth(lines - 1) {
+        Some((index, _)) => &text[index + 1..],
+        None => text,
+    }
+}
+
+/// Incremental ANSI escape stripper for terminal output.
+///
+/// Keeps state between chunks so sequences split across reads are still
+/// removed. CSI, OSC and two-byte escapes are dropped; `\r\n` becomes `\n`
+/// and bare carriage returns are discarded.
+#[derive(Debug, Default)]
+struct AnsiStripper {
+    state: AnsiState,
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+enum AnsiState {
+    #[default]
+    Text,
+    Escape,
+    Csi,
+    Osc,
+    OscEscape,
+}
+
+impl AnsiStripper {
+    fn strip(&mut self, input: &str) -> String {
+        let mut out = String::with_capacity(input.len());
+        for c in input.chars() {
+            self.state = match (self.state, c) {
+                (AnsiState::Text, '\x1b') => AnsiState::Escape,
+                (AnsiState::Text, c) => {
+                    // Control characters (including bare `\r`) are dropped.
+                    if !c.is_control() || c == '\n' || c == '\t' {
+                        out.push(c);
+                    }
+                    AnsiState::Text
+                }
+                (AnsiState::Csi, c) if ('\x40'..='\x7e').contains(&c) => AnsiState::Text,
+                (AnsiState::Escape, '[') | (AnsiState::Csi, _) => AnsiState::Csi,
+                (AnsiState::Escape, ']') => AnsiState::Osc,
+                (AnsiState::Osc, '\x1b') => AnsiState::OscEscape,
+                (AnsiState::Escape, _)
+                | (AnsiState::Osc, '\x07')
+                | (AnsiState::OscEscape, '\\') => AnsiState::Text,
+                (AnsiState::Osc | AnsiState::OscEscape, _) => AnsiState::Osc,
+            };
+        }
+        out
+    }
+}
+
+/// Spawn `cmd` attached to a fresh pseudo-terminal.
+///
+/// The program, arguments, environment and working directory prepared by the
+/// runtime adapter are carried over. A reader thread drains the master side
+/// into `output` (ANSI stripped); the returned writer feeds the child's
+/// terminal input.
+fn spawn_on_pty(
+    cmd: &tokio::process::Command,
+    output: Arc<Mutex<OutputBuffer>>,
+) -> anyhow::Result<(ProcessChild, Box<dyn Write + Send>)> {
+    use portable_pty::{native_pty_system, CommandBuilder, PtySize};
+
+    let std_cmd = cmd.as_std();
+    let mut builder = CommandBuilder::new(std_cmd.get_program());
+    builder.args(std_cmd.get_args());
+    builder.env_clear();
+    for (key, value) in std_cmd.get_envs() {
+        if let Some(value) = value {
+            builder.env(key, value);
+        }
+    }
+    builder.env("TERM", "dumb");
+    if let Some(dir) = std_cmd.get_current_dir() {
+        builder.cwd(dir);
+    }
+
+    let pair = native_pty_system().openpty(PtySize {
+        rows: 40,
+        cols: 120,
+        pixel_width: 0,
+        pixel_height: 0,
+    })?;
+    let child = pair.slave.spawn_command(builder)?;
+    // Drop our copy of the slave so the reader sees EOF once the child exits.
+    drop(pair.slave);
+
+    let mut reader = pair.master.try_clone_reader()?;
+    let writer = pair.master.take_writer()?;
+    let master = pair.master;
+    std::thread::spawn(move || {
+        // Keep the master open for as long as the session produces output.
+        let _master = master;
+        let mut stripper = AnsiStripper::default();
+        let mut chunk = vec![0u8; 8192];
+        loop {
+            match reader.read(&mut chunk) {
+                Ok(n) if n > 0 => {
+                    let text = String::from_utf8_lossy(&chunk[..n]);
+                    append_bounded(&output, &stripper.strip(&text));
+                }
+                Err(e) if e.kind() == std::io::ErrorKind::Interrupted => {}
+                _ => break,
+            }
+        }
+    });
+
+    Ok((ProcessChild::Pty(child), writer))
+}
+
 /// Append data to a bounded buffer, draining oldest bytes when over limit.
 fn append_bounded(buf: &Mutex<OutputBuffer>, new_data: &str) {
     let mut guard = buf.lock().unwrap();
@@ -469,7 +1080,7 @@ impl Tool for ProcessTool {
     }
 
     fn description(&self) -> &str {
-        "管理后台进程：启动长时间运行的命令，检查输出，并终止它们"
+        "管理后台进程：启动长时间运行的命令，检查输出，并终止它们。使用 pty=true 启动交互式会话后，可通过 write/send_keys 输入并用 wait_for 等待输出"
     }
 
     fn parameters_schema(&self) -> serde_json::Value {
@@ -478,20 +1089,46 @@ impl Tool for ProcessTool {
             "properties": {
                 "action": {
                     "type": "string",
-                    "enum": ["spawn", "list", "output", "kill"],
-                    "description": "Action to perform: spawn a process, list all, get output, or kill"
+                    "enum": ["spawn", "list", "output", "kill", "write", "send_keys", "wait_for"],
+                    "description": "Action to perform: spawn a process, list all, get output, kill, write to stdin, send keys, or wait for output matching a regex"
                 },
                 "command": {
                     "type": "string",
                     "description": "Shell command to run in background (required for 'spawn')"
                 },
+                "pty": {
+                    "type": "boolean",
+                    "description": "Run the process on a pseudo-terminal so it can be driven with write/send_keys/wait_for (for 'spawn')",
+                    "default": false
+                },
                 "id": {
                     "type": "integer",
-                    "description": "Process ID returned by spawn (required for 'output' and 'kill')"
+                    "description": "Process ID returned by spawn (required for every action except 'spawn' and 'list')"
+                },
+                "input": {
+                    "type": "string",
+                    "description": "Text to write to the terminal; include \\n to submit a line (required for 'write')"
+                },
+                "keys": {
+                    "type": "array",
+                    "items": { "type": "string" },
+                    "description": "Keys to send in order, e.g. [\"C-c\"], [\"Up\", \"Enter\"], [\"y\", \"Enter\"] (required for 'send_keys')"
+                },
+                "pattern": {
+                    "type": "string",
+                    "description": "Regex to wait for in output not yet matched by a previous wait_for (required for 'wait_for')"
+                },
+                "timeout_secs": {
+                    "type": "integer",
+                    "description": "Maximum seconds to wait for 'wait_for' (default 30, max 300)"
+                },
+                "lines": {
+                    "type": "integer",
+                    "description": "Only return the last N lines of scrollback (for 'output')"
                 },
                 "approved": {
                     "type": "boolean",
-                    "description": "Approve medium/high-risk commands (for 'spawn')",
+                    "description": "Approve medium/high-risk commands (for 'spawn', and for lines written to an interactive shell); required for input submitted to non-shell sessions such as REPLs, and for shell lines submitted after history, completion or cursor keys",
                     "default": false
                 }
             },
@@ -507,11 +1144,14 @@ impl Tool for ProcessTool {
             "list" => self.handle_list(),
             "output" => self.handle_output(&args),
             "kill" => self.handle_kill(&args),
+            "write" => self.handle_write(&args),
+            "send_keys" => self.handle_send_keys(&args),
+            "wait_for" => self.handle_wait_for(&args).await,
             other => Ok(ToolResult {
                 success: false,
                 output: String::new(),
                 error: Some(format!(
-                    "Unknown action '{other}'. Use: spawn, list, output, kill"
+                    "Unknown action '{other}'. Use: spawn, list, output, kill, write, send_keys, wait_for"
                 )),
             }),
         }
@@ -554,6 +1194,7 @@ mod tests {
     fn test_syscall_detector(tmp: &TempDir) -> Arc<SyscallAnomalyDetector> {
         let log_path = tmp.path().join("process-syscall-anomalies.log");
         let cfg = SyscallAnomalyConfig {
+            enabled: true,
             baseline_syscalls: vec!["read".into(), "write".into()],
             log_path: log_path.to_string_lossy().to_string(),
             alert_cooldown_secs: 1,
@@ -902,4 +1543,318 @@ mod tests {
             "incremental offsets should prevent duplicate detector emissions for unchanged output"
         );
     }
+
+    fn spawned_id(result: &ToolResult) -> u64 {
+        assert!(result.success, "spawn should succeed: {:?}", result.error);
+        let output: serde_json::Value = serde_json::from_str(&result.output).unwrap();
+        output["id"].as_u64().unwrap()
+    }
+
+    #[test]
+    fn encode_key_maps_named_keys_and_control_chords() {
+        assert_eq!(encode_key("Enter").as_deref(), Some("\r"));
+        assert_eq!(encode_key("esc").as_deref(), Some("\x1b"));
+        assert_eq!(encode_key("Up").as_deref(), Some("\x1b[A"));
+        assert_eq!(encode_key("C-c").as_deref(), Some("\x03"));
+        assert_eq!(encode_key("Ctrl-D").as_deref(), Some("\x04"));
+        assert_eq!(encode_key("y").as_deref(), Some("y"));
+        assert_eq!(encode_key("C-cc"), None);
+        assert_eq!(encode_key("Hyper"), None);
+    }
+
+    #[test]
+    fn ansi_stripper_handles_split_sequences() {
+        let mut stripper = AnsiStripper::default();
+        let mut out = stripper.strip("\x1b[1;3");
+        out.push_str(&stripper.strip("2mred\x1b[0m\r\n"));
+        out.push_str(&stripper.strip("\x1b]0;title\x07ok\x1bM"));
+        assert_eq!(out, "red\nok");
+    }
+
+    #[test]
+    fn session_program_looks_through_env_wrappers() {
+        assert_eq!(session_program("bash -i"), Some("bash"));
+        assert_eq!(session_program("/usr/bin/env bash"), Some("bash"));
+        assert_eq!(session_program("env -i TERM=dumb /bin/zsh"), Some("zsh"));
+        assert_eq!(session_program("python3 -q"), Some("python3"));
+        assert_eq!(session_program("env"), None);
+    }
+
+    fn submitted(pending: &PendingLine, input: &str) -> (Vec<(String, bool)>, PendingLine) {
+        let (lines, next) = submitted_lines(pending, input);
+        let lines = lines.into_iter().map(|l| (l.text, l.edited)).collect();
+        (lines, next)
+    }
+
+    #[test]
+    fn submitted_lines_ignores_unterminated_tail() {
+        let (lines, pending) = submitted(&PendingLine::default(), "ls\nrm -rf /\rpartial");
+        assert_eq!(
+            lines,
+            vec![("ls".into(), false), ("rm -rf /".into(), false)]
+        );
+        assert_eq!(pending.text, "partial");
+
+        let (lines, pending) = submitted(&PendingLine::default(), "no newline");
+        assert!(lines.is_empty());
+        assert_eq!(pending.text, "no newline");
+    }
+
+    #[test]
+    fn submitted_lines_joins_pending_input_and_flags_edits() {
+        let (_, pending) = submitted(&PendingLine::default(), "rm -rf ");
+        let (lines, pending) = submitted(&pending, "/tmp/x\r");
+        assert_eq!(lines, vec![("rm -rf /tmp/x".into(), false)]);
+        assert!(!pending.edited && pending.text.is_empty());
+
+        // History recall and completion submit text we never saw.
+        let (lines, _) = submitted(&PendingLine::default(), "\x1b[A\r");
+        assert_eq!(lines, vec![(String::new(), true)]);
+        let (lines, _) = submitted(&PendingLine::default(), "cat no\t\r");
+        assert_eq!(lines, vec![("cat no".into(), true)]);
+
+        // Backspace edits the buffer; Ctrl-C abandons it.
+        let (lines, _) = submitted(&PendingLine::default(), "lsx\x7f\r");
+        assert_eq!(lines, vec![("ls".into(), false)]);
+        let (lines, pending) = submitted(&PendingLine::default(), "\x1b[Arm\x03");
+        assert!(lines.is_empty());
+        assert!(!pending.edited && pending.text.is_empty());
+    }
+
+    #[test]
+    fn tail_output_returns_last_lines() {
+        assert_eq!(tail_output("a\nb\nc\n", Some(2)), "b\nc\n");
+        assert_eq!(tail_output("a\nb", Some(5)), "a\nb");
+        assert_eq!(tail_output("a\nb", None), "a\nb");
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn pty_session_round_trips_input_and_wait_for() {
+        let tool = make_tool();
+        let id = spawned_id(
+            &tool
+                .execute(json!({"action": "spawn", "command": "cat", "pty": true}))
+                .await
+                .unwrap(),
+        );
+
+        let write = tool
+            .execute(json!({
+                "action": "write",
+                "id": id,
+                "input": "ping_pty_session\n",
+                "approved": true
+            }))
+            .await
+            .unwrap();
+        assert!(write.success, "{:?}", write.error);
+
+        let waited = tool
+            .execute(json!({
+                "action": "wait_for",
+                "id": id,
+                "pattern": "ping_pty_\\w+",
+                "timeout_secs": 5
+            }))
+            .await
+            .unwrap();
+        assert!(waited.success, "{:?}", waited.error);
+        assert!(waited.output.contains("ping_pty_session"));
+
+        // Ctrl-D on an empty line closes cat's stdin.
+        let keys = tool
+            .execute(json!({"action": "send_keys", "id": id, "keys": ["C-d"]}))
+            .await
+            .unwrap();
+        assert!(keys.success, "{:?}", keys.error);
+
+        // Waiting for output that never arrives fails once cat exits.
+        let again = tool
+            .execute(json!({
+                "action": "wait_for",
+                "id": id,
+                "pattern": "never_printed",
+                "timeout_secs": 5
+            }))
+            .await
+            .unwrap();
+        assert!(!again.success);
+        assert!(again.error.unwrap().contains("exited"));
+    }
+
+    #[tokio::test]
+    async fn write_requires_pty_session() {
+        let tool = make_tool();
+        let id = spawned_id(
+            &tool
+                .execute(json!({"action": "spawn", "command": "sleep 5"}))
+                .await
+                .unwrap(),
+        );
+        let result = tool
+            .execute(json!({"action": "write", "id": id, "input": "x"}))
+            .await
+            .unwrap();
+        assert!(!result.success);
+        assert!(result.error.unwrap().contains("pty=true"));
+        tool.execute(json!({"action": "kill", "id": id}))
+            .await
+            .unwrap();
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn shell_session_lines_obey_command_policy() {
+        let mut policy = SecurityPolicy::default();
+        policy.autonomy = AutonomyLevel::Full;
+        policy.workspace_dir = std::env::temp_dir();
+        policy.allowed_commands.push("sh".into());
+        let tool = ProcessTool::new(Arc::new(policy), test_runtime());
+
+        let id = spawned_id(
+            &tool
+                .execute(json!({"action": "spawn", "command": "sh", "pty": true}))
+                .await
+                .unwrap(),
+        );
+
+        let blocked = tool
+            .execute(json!({"action": "write", "id": id, "input": "curl http://example.com\n"}))
+            .await
+            .unwrap();
+        assert!(!blocked.success);
+
+        // A command typed without a newline is checked when Enter submits it.
+        let typed = tool
+            .execute(json!({"action": "write", "id": id, "input": "curl http://example.com"}))
+            .await
+            .unwrap();
+        assert!(typed.success, "{:?}", typed.error);
+        let submitted = tool
+            .execute(json!({"action": "send_keys", "id": id, "keys": ["Enter"]}))
+            .await
+            .unwrap();
+        assert!(!submitted.success);
+        tool.execute(json!({"action": "send_keys", "id": id, "keys": ["C-c"]}))
+            .await
+            .unwrap();
+
+        // History recall runs a command we cannot see.
+        let recalled = tool
+            .execute(json!({"action": "send_keys", "id": id, "keys": ["Up", "Enter"]}))
+            .await
+            .unwrap();
+        assert!(!recalled.success);
+        assert!(recalled.error.unwrap().contains("approved=true"));
+        tool.execute(json!({"action": "send_keys", "id": id, "keys": ["C-c"]}))
+            .await
+            .unwrap();
+
+        let allowed = tool
+            .execute(json!({"action": "write", "id": id, "input": "echo shell_ok_42\n"}))
+            .await
+            .unwrap();
+        assert!(allowed.success, "{:?}", allowed.error);
+        let waited = tool
+            .execute(json!({
+                "action": "wait_for",
+                "id": id,
+                "pattern": "shell_ok_42",
+                "timeout_secs": 5
+            }))
+            .await
+            .unwrap();
+        assert!(waited.success, "{:?}", waited.error);
+
+        tool.execute(json!({"action": "kill", "id": id}))
+            .await
+            .unwrap();
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn env_wrapped_shell_lines_obey_command_policy() {
+        let mut policy = SecurityPolicy::default();
+        policy.autonomy = AutonomyLevel::Full;
+        policy.workspace_dir = std::env::temp_dir();
+        policy.allowed_commands.extend(["env".into(), "sh".into()]);
+        let tool = ProcessTool::new(Arc::new(policy), test_runtime());
+
+        let id = spawned_id(
+            &tool
+                .execute(json!({"action": "spawn", "command": "env sh", "pty": true}))
+                .await
+                .unwrap(),
+        );
+        let blocked = tool
+            .execute(json!({"action": "write", "id": id, "input": "curl http://example.com\n"}))
+            .await
+            .unwrap();
+        assert!(!blocked.success);
+        assert!(blocked.error.unwrap().contains("not allowed"));
+
+        tool.execute(json!({"action": "kill", "id": id}))
+            .await
+            .unwrap();
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn non_shell_session_input_requires_approval() {
+        let mut policy = SecurityPolicy::default();
+        policy.autonomy = AutonomyLevel::Full;
+        policy.workspace_dir = std::env::temp_dir();
+        policy.allowed_commands.push("cat".into());
+        let tool = ProcessTool::new(Arc::new(policy), test_runtime());
+
+        let id = spawned_id(
+            &tool
+                .execute(json!({"action": "spawn", "command": "cat", "pty": true}))
+                .await
+                .unwrap(),
+        );
+        let denied = tool
+            .execute(json!({"action": "write", "id": id, "input": "import os\n"}))
+            .await
+            .unwrap();
+        assert!(!denied.success);
+        assert!(denied.error.unwrap().contains("approved=true"));
+
+        let keys = tool
+            .execute(json!({"action": "send_keys", "id": id, "keys": ["y", "Enter"]}))
+            .await
+            .unwrap();
+        assert!(!keys.success);
+
+        let approved = tool
+            .execute(json!({
+                "action": "write",
+                "id": id,
+                "input": "approved_line_7\n",
+                "approved": true
+            }))
+            .await
+            .unwrap();
+        assert!(approved.success, "{:?}", approved.error);
+
+        tool.execute(json!({"action": "kill", "id": id}))
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn send_keys_rejected_in_read_only_mode() {
+        let security = Arc::new(SecurityPolicy {
+            autonomy: AutonomyLevel::ReadOnly,
+            workspace_dir: std::env::temp_dir(),
+            ..SecurityPolicy::default()
+        });
+        let tool = ProcessTool::new(security, test_runtime());
+        let result = tool
+            .execute(json!({"action": "send_keys", "id": 0, "keys": ["Enter"]}))
+            .await
+            .unwrap();
+        assert!(!result.success);
+    }
 }
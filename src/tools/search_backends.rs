@@ -0,0 +1,940 @@
+//! Search backends for `web_search_tool`.
+//!
+//! Each backend turns a query into a list of [`SearchResult`]s. Built-in
+//! backends cover DuckDuckGo, Brave, Firecrawl and self-hosted SearXNG;
+//! `[[web_search.custom]]` entries describe arbitrary JSON endpoints through a
+//! URL template and JSONPath mapping. [`FanOutBackend`] queries several
+//! backends in parallel and merges their results.
+
+use crate::config::{CustomSearchBackendConfig, WebSearchConfig};
+use async_trait::async_trait;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::time::Duration;
+
+/// Provider names handled by [`build_search_backend`] without custom config.
+pub const BUILTIN_SEARCH_BACKENDS: &[&str] =
+    &["duckduckgo", "ddg", "brave", "firecrawl", "searxng"];
+
+/// A single search hit.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SearchResult {
+    pub title: String,
+    pub url: String,
+    #[serde(default)]
+    pub snippet: String,
+}
+
+/// A source of web search results.
+#[async_trait]
+pub trait SearchBackend: Send + Sync {
+    /// Human-readable label shown in tool output (e.g. "SearXNG").
+    fn name(&self) -> &str;
+
+    /// Run `query`, returning at most `max_results` results.
+    async fn search(&self, query: &str, max_results: usize) -> anyhow::Result<Vec<SearchResult>>;
+}
+
+/// Shared HTTP settings for backends.
+#[derive(Debug, Clone)]
+pub struct SearchHttpOptions {
+    pub timeout_secs: u64,
+    pub user_agent: String,
+}
+
+impl SearchHttpOptions {
+    fn client(&self) -> anyhow::Result<reqwest::Client> {
+        let builder = reqwest::Client::builder()
+            .timeout(Duration::from_secs(self.timeout_secs))
+            .user_agent(self.user_agent.as_str());
+        let builder = crate::config::apply_runtime_proxy_to_builder(builder, "tool.web_search");
+        Ok(builder.build()?)
+    }
+}
+
+/// Build the backend for `config.fan_out` (when set) or `config.provider`.
+pub fn build_configured_backend(
+    config: &WebSearchConfig,
+    http: &SearchHttpOptions,
+) -> anyhow::Result<Box<dyn SearchBackend>> {
+    let names: Vec<&str> = config
+        .fan_out
+        .iter()
+        .map(|name| name.trim())
+        .filter(|name| !name.is_empty())
+        .collect();
+
+    if names.is_empty() {
+        return build_search_backend(&config.provider, config, None, http);
+    }
+
+    let backends = names
+        .iter()
+        .map(|name| build_search_backend(name, config, None, http))
+        .collect::<anyhow::Result<Vec<_>>>()?;
+    Ok(Box::new(FanOutBackend::new(backends)))
+}
+
+/// Build a single backend by provider name.
+///
+/// `api_url` overrides the Firecrawl endpoint.
+pub fn build_search_backend(
+    provider: &str,
+    config: &WebSearchConfig,
+    api_url: Option<String>,
+    http: &SearchHttpOptions,
+) -> anyhow::Result<Box<dyn SearchBackend>> {
+    let provider = provider.trim().to_lowercase();
+    match provider.as_str() {
+        "duckduckgo" | "ddg" => Ok(Box::new(DuckDuckGoBackend::new(http.clone()))),
+        "brave" => Ok(Box::new(BraveBackend::new(
+            http.clone(),
+            config.brave_api_key.clone(),
+        ))),
+        "firecrawl" => Ok(Box::new(FirecrawlBackend::new(
+            http.clone(),
+            config.brave_api_key.clone(),
+            api_url,
+        ))),
+        "searxng" => {
+            let base_url = config
+                .searxng_url
+                .as_deref()
+                .map(str::trim)
+                .filter(|url| !url.is_empty())
+                .ok_or_else(|| {
+                    anyhow::anyhow!(
+                        "web_search provider 'searxng' requires [web_search].searxng_url in config.toml"
+                    )
+                })?;
+            Ok(Box::new(SearxngBackend::new(http.clone(), base_url)))
+        }
+        _ => {
+            let custom = config
+                .custom
+                .iter()
+                .find(|backend| backend.name.trim().eq_ignore_ascii_case(&provider))
+                .ok_or_else(|| {
+                    anyhow::anyhow!(
+                        "Unknown search provider: '{provider}'. Set web_search.provider to 'duckduckgo', 'brave', 'firecrawl', 'searxng', or the name of a [[web_search.custom]] backend in config.toml"
+                    )
+                })?;
+            Ok(Box::new(JsonApiBackend::new(http.clone(), custom.clone())?))
+        }
+    }
+}
+
+// ── DuckDuckGo ─────────────────────────────────────────────────
+
+pub struct DuckDuckGoBackend {
+    http: SearchHttpOptions,
+}
+
+impl DuckDuckGoBackend {
+    pub fn new(http: SearchHttpOptions) -> Self {
+        Self { http }
+    }
+
+    pub(crate) fn parse_results(
+        html: &str,
+        max_results: usize,
+    ) -> anyhow::Result<Vec<SearchResult>> {
+        // Extract result links: <a class="result__a" href="...">Title</a>
+        let link_regex = Regex::new(
+            r#"<a[^>]*class="[^"]*result__a[^"]*"[^>]*href="([^"]+)"[^>]*>([\s\S]*?)</a>"#,
+        )?;
+
+        // Extract snippets: <a class="result__snippet">...</a>
+        let snippet_regex = Regex::new(r#"<a class="result__snippet[^"]*"[^>]*>([\s\S]*?)</a>"#)?;
+
+        let snippets: Vec<String> = snippet_regex
+            .captures_iter(html)
+            .take(max_results + 2)
+            .map(|caps| strip_tags(&caps[1]).trim().to_string())
+            .collect();
+
+        Ok(link_regex
+            .captures_iter(html)
+            .take(max_results)
+            .enumerate()
+            .map(|(i, caps)| SearchResult {
+                title: strip_tags(&caps[2]).trim().to_string(),
+                url: decode_ddg_redirect_url(&caps[1]).trim().to_string(),
+                snippet: snippets.get(i).cloned().unwrap_or_default(),
+            })
+            .collect())
+    }
+}
+
+#[async_trait]
+impl SearchBackend for DuckDuckGoBackend {
+    fn name(&self) -> &str {
+        "DuckDuckGo"
+    }
+
+    async fn search(&self, query: &str, max_results: usize) -> anyhow::Result<Vec<SearchResult>> {
+        let encoded_query = urlencoding::encode(query);
+        let search_url = format!("https://html.duckduckgo.com/html/?q={}", encoded_query);
+
+        let response = self.http.client()?.get(&search_url).send().await?;
+
+        if !response.status().is_success() {
+            anyhow::bail!(
+                "DuckDuckGo search failed with status: {}",
+                response.status()
+            );
+        }
+
+        let html = response.text().await?;
+        Self::parse_results(&html, max_results)
+    }
+}
+
+fn decode_ddg_redirect_url(raw_url: &str) -> String {
+    if let Some(index) = raw_url.find("uddg=") {
+        let encoded = &raw_url[index + 5..];
+        let encoded = encoded.split('&').next().unwrap_or(encoded);
+        if let Ok(decoded) = urlencoding::decode(encoded) {
+            return decoded.into_owned();
+        }
+    }
+
+    raw_url.to_string()
+}
+
+pub(crate) fn strip_tags(content: &str) -> String {
+    let re = Regex::new(r"<[^>]+>").unwrap();
+    re.replace_all(content, "").to_string()
+}
+
+// ── Brave ──────────────────────────────────────────────────────
+
+pub struct BraveBackend {
+    http: SearchHttpOptions,
+    api_key: Option<String>,
+}
+
+impl BraveBackend {
+    pub fn new(http: SearchHttpOptions, api_key: Option<String>) -> Self {
+        Self { http, api_key }
+    }
+
+    pub(crate) fn parse_results(
+        json: &serde_json::Value,
+        max_results: usize,
+    ) -> anyhow::Result<Vec<SearchResult>> {
+        let results = json
+            .get("web")
+            .and_then(|w| w.get("results"))
+            .and_then(|r| r.as_array())
+            .ok_or_else(|| anyhow::anyhow!("Invalid Brave API response"))?;
+
+        Ok(results
+            .iter()
+            .take(max_results)
+            .map(|result| result_from_fields(result, "title", "url", "description"))
+            .collect())
+    }
+}
+
+#[async_trait]
+impl SearchBackend for BraveBackend {
+    fn name(&self) -> &str {
+        "Brave"
+    }
+
+    async fn search(&self, query: &str, max_results: usize) -> anyhow::Result<Vec<SearchResult>> {
+        let auth_token = match self.api_key.as_ref() {
+            Some(raw) if !raw.trim().is_empty() => raw.trim(),
+            _ => anyhow::bail!("Brave API key not configured"),
+        };
+
+        let encoded_query = urlencoding::encode(query);
+        let search_url = format!(
+            "https://api.search.brave.com/res/v1/web/search?q={}&count={}",
+            encoded_query, max_results
+        );
+
+        let response = self
+            .http
+            .client()?
+            .get(&search_url)
+            .header("Accept", "application/json")
+            .header("X-Subscription-Token", auth_token)
+            .send()
+            .await?;
+
+        if !response.status().is_success() {
+            anyhow::bail!("Brave search failed with status: {}", response.status());
+        }
+
+        let json: serde_json::Value = response.json().await?;
+        Self::parse_results(&json, max_results)
+    }
+}
+
+// ── Firecrawl ──────────────────────────────────────────────────
+
+pub struct FirecrawlBackend {
+    #[cfg_attr(not(feature = "firecrawl"), allow(dead_code))]
+    http: SearchHttpOptions,
+    #[cfg_attr(not(feature = "firecrawl"), allow(dead_code))]
+    api_key: Option<String>,
+    #[cfg_attr(not(feature = "firecrawl"), allow(dead_code))]
+    api_url: Option<String>,
+}
+
+impl FirecrawlBackend {
+    pub fn new(http: SearchHttpOptions, api_key: Option<String>, api_url: Option<String>) -> Self {
+        Self {
+            http,
+            api_key,
+            api_url,
+        }
+    }
+}
+
+#[async_trait]
+impl SearchBackend for FirecrawlBackend {
+    fn name(&self) -> &str {
+        "Firecrawl"
+    }
+
+    #[cfg(feature = "firecrawl")]
+    async fn search(&self, query: &str, max_results: usize) -> anyhow::Result<Vec<SearchResult>> {
+        let auth_token = match self.api_key.as_ref() {
+            Some(raw) if !raw.trim().is_empty() => raw.trim(),
+            _ => {
+                anyhow::bail!(
+                    "web_search provider 'firecrawl' requires [web_search].api_key in config.toml"
+                );
+            }
+        };
+
+        let api_url = self
+            .api_url
+            .as_deref()
+            .map(str::trim)
+            .filter(|s| !s.is_empty())
+            .unwrap_or("https://api.firecrawl.dev");
+        let endpoint = format!("{}/v1/search", api_url.trim_end_matches('/'));
+
+        let response = self
+            .http
+            .client()?
+            .post(endpoint)
+            .header(
+                reqwest::header::AUTHORIZATION,
+                format!("Bearer {auth_token}"),
+            )
+            .json(&serde_json::json!({
+                "query": query,
+                "limit": max_results,
+                "timeout": (self.http.timeout_secs * 1000) as u64,
+            }))
+            .send()
+            .await
+            .map_err(|e| anyhow::anyhow!("Firecrawl search failed: {e}"))?;
+        let status = response.status();
+        let body = response.text().await?;
+
+        if !status.is_success() {
+            anyhow::bail!(
+                "Firecrawl search failed with status {}: {}",
+                status.as_u16(),
+                body
+            );
+        }
+
+        let parsed: serde_json::Value = serde_json::from_str(&body)
+            .map_err(|e| anyhow::anyhow!("Invalid Firecrawl response JSON: {e}"))?;
+        if !parsed
+            .get("success")
+            .and_then(serde_json::Value::as_bool)
+            .unwrap_or(false)
+        {
+            let error = parsed
+                .get("error")
+                .and_then(serde_json::Value::as_str)
+                .unwrap_or("unknown error");
+            anyhow::bail!("Firecrawl search failed: {error}");
+        }
+
+        let results = parsed
+            .get("data")
+            .and_then(serde_json::Value::as_array)
+            .ok_or_else(|| anyhow::anyhow!("Firecrawl response missing data array"))?;
+
+        Ok(results
+            .iter()
+            .take(max_results)
+            .map(|result| result_from_fields(result, "title", "url", "description"))
+            .collect())
+    }
+
+    #[cfg(not(feature = "firecrawl"))]
+    async fn search(&self, _query: &str, _max_results: usize) -> anyhow::Result<Vec<SearchResult>> {
+        anyhow::bail!("web_search provider 'firecrawl' requires Cargo feature 'firecrawl'")
+    }
+}
+
+// ── SearXNG ────────────────────────────────────────────────────
+
+/// Self-hosted SearXNG instance queried through its JSON API.
+pub struct SearxngBackend {
+    http: SearchHttpOptions,
+    base_url: String,
+}
+
+impl SearxngBackend {
+    pub fn new(http: SearchHttpOptions, base_url: &str) -> Self {
+        Self {
+            http,
+            base_url: base_url.trim_end_matches('/').to_string(),
+        }
+    }
+
+    pub(crate) fn parse_results(
+        json: &serde_json::Value,
+        max_results: usize,
+    ) -> anyhow::Result<Vec<SearchResult>> {
+        let results = json
+            .get("results")
+            .and_then(serde_json::Value::as_array)
+            .ok_or_else(|| anyhow::anyhow!("Invalid SearXNG response: missing results array"))?;
+
+        Ok(results
+            .iter()
+            .take(max_results)
+            .map(|result| result_from_fields(result, "title", "url", "content"))
+            .collect())
+    }
+}
+
+#[async_trait]
+impl SearchBackend for SearxngBackend {
+    fn name(&self) -> &str {
+        "SearXNG"
+    }
+
+    async fn search(&self, query: &str, max_results: usize) -> anyhow::Result<Vec<SearchResult>> {
+        let response = self
+            .http
+            .client()?
+            .get(format!("{}/search", self.base_url))
+            .query(&[("q", query), ("format", "json")])
+            .header("Accept", "application/json")
+            .send()
+            .await?;
+
+        let status = response.status();
+        if !status.is_success() {
+            if status == reqwest::StatusCode::FORBIDDEN {
+                anyhow::bail!(
+                    "SearXNG search failed with status 403; enable the 'json' format under search.formats in settings.yml"
+                );
+            }
+            anyhow::bail!("SearXNG search failed with status: {status}");
+        }
+
+        let json: serde_json::Value = response.json().await?;
+        Self::parse_results(&json, max_results)
+    }
+}
+
+// ── Generic JSON endpoint ──────────────────────────────────────
+
+/// Search endpoint described by `[[web_search.custom]]`.
+pub struct JsonApiBackend {
+    http: SearchHttpOptions,
+    config: CustomSearchBackendConfig,
+    results_path: JsonPath,
+    title_path: JsonPath,
+    url_path: JsonPath,
+    snippet_path: Option<JsonPath>,
+}
+
+impl JsonApiBackend {
+    pub fn new(http: SearchHttpOptions, config: CustomSearchBackendConfig) -> anyhow::Result<Self> {
+        Ok(Self {
+            http,
+            results_path: JsonPath::parse(&config.results_path)?,
+            title_path: JsonPath::parse(&config.title_path)?,
+            url_path: JsonPath::parse(&config.url_path)?,
+            snippet_path: config
+                .snippet_path
+                .as_deref()
+                .map(JsonPath::parse)
+                .transpose()?,
+            config,
+        })
+    }
+
+    fn request_url(&self, query: &str, max_results: usize) -> String {
+        self.config
+            .url_template
+            .replace("{query}", &urlencoding::encode(query))
+            .replace("{max_results}", &max_results.to_string())
+    }
+
+    pub(crate) fn parse_results(
+        &self,
+        json: &serde_json::Value,
+        max_results: usize,
+    ) -> Vec<SearchResult> {
+        let first_string = |path: &JsonPath, item: &serde_json::Value| {
+            path.select(item)
+                .into_iter()
+                .find_map(|value| match value {
+                    serde_json::Value::String(s) => Some(s.trim().to_string()),
+                    serde_json::Value::Null => None,
+                    other => Some(other.to_string()),
+                })
+                .unwrap_or_default()
+        };
+
+        self.results_path
+            .select(json)
+            .into_iter()
+            .flat_map(|value| match value {
+                // `$.items` selects the array itself; `$.items[*]` its elements.
+                serde_json::Value::Array(items) => items.iter().collect::<Vec<_>>(),
+                other => vec![other],
+            })
+            .map(|item| SearchResult {
+                title: first_string(&self.title_path, item),
+                url: first_string(&self.url_path, item),
+                snippet: self
+                    .snippet_path
+                    .as_ref()
+                    .map(|path| first_string(path, item))
+                    .unwrap_or_default(),
+            })
+            .filter(|result| !result.url.is_empty())
+            .take(max_results)
+            .collect()
+    }
+}
+
+#[async_trait]
+impl SearchBackend for JsonApiBackend {
+    fn name(&self) -> &str {
+        self.config.name.trim()
+    }
+
+    async fn search(&self, query: &str, max_results: usize) -> anyhow::Result<Vec<SearchResult>> {
+        let mut request = self
+            .http
+            .client()?
+            .get(self.request_url(query, max_results))
+            .header("Accept", "application/json");
+        let api_key = self.config.api_key.as_deref().unwrap_or_default();
+        for (name, value) in &self.config.headers {
+            request = request.header(name.as_str(), value.replace("{api_key}", api_key));
+        }
+
+        let response = request.send().await?;
+        let status = response.status();
+        if !status.is_success() {
+            anyhow::bail!("{} search failed with status: {status}", self.name());
+        }
+
+        let json: serde_json::Value = response.json().await?;
+        Ok(self.parse_results(&json, max_results))
+    }
+}
+
+// ── Fan-out ────────────────────────────────────────────────────
+
+/// Queries every wrapped backend concurrently and interleaves their results,
+/// dropping URLs already seen. Fails only when every backend fails.
+pub struct FanOutBackend {
+    label: String,
+    backends: Vec<Box<dyn SearchBackend>>,
+}
+
+impl FanOutBackend {
+    pub fn new(backends: Vec<Box<dyn SearchBackend>>) -> Self {
+        let label = backends
+            .iter()
+            .map(|backend| backend.name())
+            .collect::<Vec<_>>()
+            .join(" + ");
+        Self { label, backends }
+    }
+
+    /// Round-robin merge so every backend contributes its top hits first.
+    fn merge(per_backend: Vec<Vec<SearchResult>>, max_results: usize) -> Vec<SearchResult> {
+        let mut seen = HashSet::new();
+        let mut merged = Vec::new();
+        let longest = per_backend.iter().map(Vec::len).max().unwrap_or(0);
+
+        for rank in 0..longest {
+            for results in &per_backend {
+                if let Some(result) = results.get(rank) {
+                    if seen.insert(normalize_url(&result.url)) {
+                        merged.push(result.clone());
+                    }
+                }
+            }
+        }
+
+        merged.truncate(max_results);
+        merged
+    }
+}
+
+#[async_trait]
+impl SearchBackend for FanOutBackend {
+    fn name(&self) -> &str {
+        &self.label
+    }
+
+    async fn search(&self, query: &str, max_results: usize) -> anyhow::Result<Vec<SearchResult>> {
+        let outcomes = futures_util::future::join_all(
+            self.backends
+                .iter()
+                .map(|backend| backend.search(query, max_results)),
+        )
+        .await;
+
+        let mut per_backend = Vec::new();
+        let mut errors = Vec::new();
+        for (backend, outcome) in self.backends.iter().zip(outcomes) {
+            match outcome {
+                Ok(results) => per_backend.push(results),
+                Err(e) => {
+                    tracing::warn!(backend = backend.name(), "Search backend failed: {e}");
+                    errors.push(format!("{}: {e}", backend.name()));
+                }
+            }
+        }
+
+        if per_backend.is_empty() && !errors.is_empty() {
+            anyhow::bail!("All search backends failed: {}", errors.join("; "));
+        }
+
+        Ok(Self::merge(per_backend, max_results))
+    }
+}
+
+/// Key used to detect duplicate results across backends.
+fn normalize_url(url: &str) -> String {
+    let url = url.trim();
+    let url = url
+        .strip_prefix("https://")
+        .or_else(|| url.strip_prefix("http://"))
+        .unwrap_or(url);
+    let url = url.strip_prefix("www.").unwrap_or(url);
+    let url = url.split('#').next().unwrap_or(url);
+    url.trim_end_matches('/').to_lowercase()
+}
+
+fn result_from_fields(
+    result: &serde_json::Value,
+    title_key: &str,
+    url_key: &str,
+    snippet_key: &str,
+) -> SearchResult {
+    let field = |key: &str| {
+        result
+            .get(key)
+            .and_then(serde_json::Value::as_str)
+            .unwrap_or("")
+            .trim()
+            .to_string()
+    };
+    let title = field(title_key);
+    SearchResult {
+        title: if title.is_empty() {
+            "No title".to_string()
+        } else {
+            title
+        },
+        url: field(url_key),
+        snippet: field(snippet_key),
+    }
+}
+
+// ── JSONPath ───────────────────────────────────────────────────
+
+/// Minimal JSONPath subset for mapping custom search responses.
+///
+/// Supports `$`, `.key`, `['key']` / `["key"]`, `[n]`, `[*]` and `.*`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct JsonPath {
+    segments: Vec<JsonPathSegment>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum JsonPathSegment {
+    Key(String),
+    Index(usize),
+    Wildcard,
+}
+
+impl JsonPath {
+    pub fn parse(path: &str) -> anyhow::Result<Self> {
+        let path = path.trim();
+        let rest = path
+            .strip_prefix('$')
+            .ok_or_else(|| anyhow::anyhow!("JSONPath must start with '$': {path}"))?;
+        let chars: Vec<char> = rest.chars().collect();
+        let mut segments = Vec::new();
+        let mut i = 0;
+
+        while i < chars.len() {
+            match chars[i] {
+                '.' => {
+                    let start = i + 1;
+                    let mut end = start;
+                    while end < chars.len() && chars[end] != '.' && chars[end] != '[' {
+                        end += 1;
+                    }
+                    let key: String = chars[start..end].iter().collect();
+                    match key.as_str() {
+                        "" => anyhow::bail!("Empty key in JSONPath: {path}"),
+                        "*" => segments.push(JsonPathSegment::Wildcard),
+                        _ => segments.push(JsonPathSegment::Key(key)),
+                    }
+                    i = end;
+                }
+                '[' => {
+                    let close = chars[i..]
+                        .iter()
+                        .position(|&c| c == ']')
+                        .map(|offset| i + offset)
+                        .ok_or_else(|| anyhow::anyhow!("Unclosed '[' in JSONPath: {path}"))?;
+                    let inner: String = chars[i + 1..close].iter().collect();
+                    let inner = inner.trim();
+                    if inner == "*" {
+                        segments.push(JsonPathSegment::Wildcard);
+                    } else if let Some(key) = inner
+                        .strip_prefix('\'')
+                        .and_then(|s| s.strip_suffix('\''))
+                        .or_else(|| inner.strip_prefix('"').and_then(|s| s.strip_suffix('"')))
+                    {
+                        segments.push(JsonPathSegment::Key(key.to_string()));
+                    } else {
+                        let index = inner.parse().map_err(|_| {
+                            anyhow::anyhow!("Unsupported JSONPath selector '[{inner}]': {path}")
+                        })?;
+                        segments.push(JsonPathSegment::Index(index));
+                    }
+                    i = close + 1;
+                }
+                other => anyhow::bail!("Unexpected '{other}' in JSONPath: {path}"),
+            }
+        }
+
+        Ok(Self { segments })
+    }
+
+    /// Return every value matched by the path.
+    pub fn select<'a>(&self, root: &'a serde_json::Value) -> Vec<&'a serde_json::Value> {
+        let mut current = vec![root];
+        for segment in &self.segments {
+            current = current
+                .into_iter()
+                .flat_map(|value| match (segment, value) {
+                    (JsonPathSegment::Key(key), serde_json::Value::Object(map)) => {
+                        map.get(key).into_iter().collect::<Vec<_>>()
+                    }
+                    (JsonPathSegment::Index(index), serde_json::Value::Array(items)) => {
+                        items.get(*index).into_iter().collect()
+                    }
+                    (JsonPathSegment::Wildcard, serde_json::Value::Array(items)) => {
+                        items.iter().collect()
+                    }
+                    (JsonPathSegment::Wildcard, serde_json::Value::Object(map)) => {
+                        map.values().collect()
+                    }
+                    _ => Vec::new(),
+                })
+                .collect();
+        }
+        current
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    fn http() -> SearchHttpOptions {
+        SearchHttpOptions {
+            timeout_secs: 5,
+            user_agent: "test".into(),
+        }
+    }
+
+    fn result(url: &str) -> SearchResult {
+        SearchResult {
+            title: url.to_string(),
+            url: url.to_string(),
+            snippet: String::new(),
+        }
+    }
+
+    struct StaticBackend {
+        name: &'static str,
+        results: Result<Vec<SearchResult>, String>,
+        calls: Arc<AtomicUsize>,
+    }
+
+    #[async_trait]
+    impl SearchBackend for StaticBackend {
+        fn name(&self) -> &str {
+            self.name
+        }
+
+        async fn search(&self, _query: &str, _max: usize) -> anyhow::Result<Vec<SearchResult>> {
+            self.calls.fetch_add(1, Ordering::SeqCst);
+            self.results.clone().map_err(|e| anyhow::anyhow!(e))
+        }
+    }
+
+    #[test]
+    fn json_path_parses_and_selects() {
+        let doc = json!({
+            "data": { "items": [
+                { "meta": { "title": "A" }, "link": "https://a.example" },
+                { "meta": { "title": "B" }, "link": "https://b.example" }
+            ]}
+        });
+        let items = JsonPath::parse("$.data.items[*]").unwrap();
+        assert_eq!(items.select(&doc).len(), 2);
+        let title = JsonPath::parse("$['data'].items[1].meta.title").unwrap();
+        assert_eq!(title.select(&doc), vec![&json!("B")]);
+        assert!(JsonPath::parse("data.items").is_err());
+        assert!(JsonPath::parse("$.items[?(@.x)]").is_err());
+        assert!(JsonPath::parse("$.items[").is_err());
+    }
+
+    #[test]
+    fn json_api_backend_maps_results() {
+        let backend = JsonApiBackend::new(
+            http(),
+            CustomSearchBackendConfig {
+                name: "internal".into(),
+                url_template: "https://search.internal/api?q={query}&n={max_results}".into(),
+                headers: std::collections::HashMap::new(),
+                api_key: None,
+                results_path: "$.hits".into(),
+                title_path: "$.meta.title".into(),
+                url_path: "$.link".into(),
+                snippet_path: Some("$.summary".into()),
+            },
+        )
+        .unwrap();
+        assert_eq!(
+            backend.request_url("rust async", 3),
+            "https://search.internal/api?q=rust%20async&n=3"
+        );
+
+        let doc = json!({ "hits": [
+            { "meta": { "title": "A" }, "link": "https://a.example", "summary": "first" },
+            { "meta": { "title": "No link" } },
+            { "meta": { "title": "B" }, "link": "https://b.example" }
+        ]});
+        let results = backend.parse_results(&doc, 5);
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0].title, "A");
+        assert_eq!(results[0].snippet, "first");
+        assert_eq!(results[1].url, "https://b.example");
+    }
+
+    #[test]
+    fn searxng_parses_results() {
+        let doc = json!({ "results": [
+            { "title": "Rust", "url": "https://rust-lang.org", "content": "A language" }
+        ]});
+        let results = SearxngBackend::parse_results(&doc, 5).unwrap();
+        assert_eq!(results[0].snippet, "A language");
+        assert!(SearxngBackend::parse_results(&json!({}), 5).is_err());
+    }
+
+    #[tokio::test]
+    async fn fan_out_interleaves_and_dedupes() {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let fan_out = FanOutBackend::new(vec![
+            Box::new(StaticBackend {
+                name: "one",
+                results: Ok(vec![
+                    result("https://a.example/"),
+                    result("https://b.example"),
+                ]),
+                calls: calls.clone(),
+            }),
+            Box::new(StaticBackend {
+                name: "two",
+                results: Ok(vec![
+                    result("http://www.a.example"),
+                    result("https://c.example"),
+                ]),
+                calls: calls.clone(),
+            }),
+            Box::new(StaticBackend {
+                name: "broken",
+                results: Err("down".into()),
+                calls: calls.clone(),
+            }),
+        ]);
+
+        assert_eq!(fan_out.name(), "one + two + broken");
+        let results = fan_out.search("q", 10).await.unwrap();
+        let urls: Vec<&str> = results.iter().map(|r| r.url.as_str()).collect();
+        assert_eq!(
+            urls,
+            vec![
+                "https://a.example/",
+                "https://b.example",
+                "https://c.example"
+            ]
+        );
+        assert_eq!(calls.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn fan_out_fails_when_every_backend_fails() {
+        let fan_out = FanOutBackend::new(vec![Box::new(StaticBackend {
+            name: "broken",
+            results: Err("down".into()),
+            calls: Arc::new(AtomicUsize::new(0)),
+        })]);
+        let error = fan_out.search("q", 5).await.unwrap_err().to_string();
+        assert!(error.contains("broken: down"));
+    }
+
+    #[test]
+    fn build_backend_resolves_custom_and_rejects_unknown() {
+        let mut config = WebSearchConfig::default();
+        assert!(build_search_backend("searxng", &config, None, &http()).is_err());
+
+        config.searxng_url = Some("http://searxng.internal:8080/".into());
+        config.custom.push(CustomSearchBackendConfig {
+            name: "Internal".into(),
+            url_template: "https://search.internal/?q={query}".into(),
+            headers: std::collections::HashMap::new(),
+            api_key: None,
+            results_path: "$.results[*]".into(),
+            title_path: "$.title".into(),
+            url_path: "$.url".into(),
+            snippet_path: None,
+        });
+        config.fan_out = vec!["searxng".into(), "internal".into()];
+
+        let backend = build_configured_backend(&config, &http()).unwrap();
+        assert_eq!(backend.name(), "SearXNG + Internal");
+
+        let error = build_search_backend("bing", &config, None, &http())
+            .err()
+            .unwrap()
+            .to_string();
+        assert!(error.contains("Unknown search provider"));
+    }
+}
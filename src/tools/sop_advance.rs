@@ -1,13 +1,27 @@
 use std::sync::{Arc, Mutex};
 
 use async_trait::async_trait;
-use serde_json::json;
+use schemars::JsonSchema;
+use serde::Deserialize;
 use tracing::warn;
 
-use super::traits::{Tool, ToolResult};
+use super::traits::{GenerationOverride, Tool, ToolResult};
 use crate::sop::types::{SopRunAction, SopStepResult, SopStepStatus};
 use crate::sop::{SopAuditLogger, SopEngine, SopMetricsCollector};
 
+/// Step report submitted by the agent. The tool's parameter schema is
+/// generated from this type, so the reported status is always one of the
+/// engine's step states rather than free text.
+#[derive(Debug, Deserialize, JsonSchema)]
+struct StepReport {
+    /// The run ID to advance
+    run_id: String,
+    /// Result status of the current step
+    status: SopStepStatus,
+    /// Brief summary of what happened in this step
+    output: String,
+}
+
 /// Report a step result and advance an SOP run to the next step.
 pub struct SopAdvanceTool {
     engine: Arc<Mutex<SopEngine>>,
@@ -46,57 +60,49 @@ impl Tool for SopAdvanceTool {
     }
 
     fn parameters_schema(&self) -> serde_json::Value {
-        json!({
-            "type": "object",
-            "properties": {
-                "run_id": {
-                    "type": "string",
-                    "description": "The run ID to advance"
-                },
-                "status": {
-                    "type": "string",
-                    "enum": ["completed", "failed", "skipped"],
-                    "description": "Result status of the current step"
-                },
-                "output": {
-                    "type": "string",
-                    "description": "Brief summary of what happened in this step"
-                }
-            },
-            "required": ["run_id", "status", "output"]
-        })
+        let mut schema = serde_json::to_value(schemars::schema_for!(StepReport))
+            .unwrap_or_else(|_| serde_json::json!({"type": "object"}));
+        if let Some(root) = schema.as_object_mut() {
+            root.remove("$schema");
+            root.remove("title");
+        }
+        schema
+    }
+
+    fn generation_override(&self, args: &serde_json::Value) -> Option<GenerationOverride> {
+        let run_id = args.get("run_id")?.as_str()?;
+        let engine = self.engine.lock().ok()?;
+        // A finished run hands the turn back to its own parameters.
+        Some(
+            engine
+                .run_generation(run_id)
+                .map_or(GenerationOverride::Reset, GenerationOverride::Apply),
+        )
     }
 
     async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
-        let run_id = args
-            .get("run_id")
-            .and_then(|v| v.as_str())
-            .ok_or_else(|| anyhow::anyhow!("Missing 'run_id' parameter"))?;
-
-        let status_str = args
-            .get("status")
-            .and_then(|v| v.as_str())
-            .ok_or_else(|| anyhow::anyhow!("Missing 'status' parameter"))?;
-
-        let output = args
-            .get("output")
-            .and_then(|v| v.as_str())
-            .ok_or_else(|| anyhow::anyhow!("Missing 'output' parameter"))?;
-
-        let step_status = match status_str {
-            "completed" => SopStepStatus::Completed,
-            "failed" => SopStepStatus::Failed,
-            "skipped" => SopStepStatus::Skipped,
-            other => {
+        for field in ["run_id", "status", "output"] {
+            if args.get(field).and_then(|v| v.as_str()).is_none() {
+                anyhow::bail!("Missing '{field}' parameter");
+            }
+        }
+
+        let report: StepReport = match serde_json::from_value(args.clone()) {
+            Ok(report) => report,
+            Err(_) => {
+                let status = args["status"].as_str().unwrap_or_default();
                 return Ok(ToolResult {
                     success: false,
                     output: String::new(),
                     error: Some(format!(
-                        "Invalid status '{other}'. Must be: completed, failed, or skipped"
+                        "Invalid status '{status}'. Must be: completed, failed, or skipped"
                     )),
                 });
             }
         };
+        let run_id = report.run_id.as_str();
+        let output = report.output.as_str();
+        let step_status = report.status;
 
         // Lock engine, advance step, snapshot data for audit, then drop lock
         let (action, step_result_ok, finished_run) = {
@@ -206,6 +212,7 @@ mod tests {
     use crate::memory::Memory;
     use crate::sop::engine::SopEngine;
     use crate::sop::types::*;
+    use serde_json::json;
 
     fn test_sop() -> Sop {
         Sop {
@@ -233,13 +240,18 @@ mod tests {
             ],
             cooldown_secs: 0,
             max_concurrent: 1,
+            generation: Default::default(),
             location: None,
         }
     }
 
     fn engine_with_active_run() -> (Arc<Mutex<SopEngine>>, String) {
+        engine_with_active_run_of(test_sop())
+    }
+
+    fn engine_with_active_run_of(sop: Sop) -> (Arc<Mutex<SopEngine>>, String) {
         let mut engine = SopEngine::new(SopConfig::default());
-        engine.set_sops_for_test(vec![test_sop()]);
+        engine.set_sops_for_test(vec![sop]);
         let event = SopEvent {
             source: SopTriggerSource::Manual,
             topic: None,
@@ -300,6 +312,32 @@ mod tests {
         assert!(result.output.contains("completed successfully"));
     }
 
+    #[tokio::test]
+    async fn generation_override_ends_with_the_run() {
+        let mut sop = test_sop();
+        sop.generation.max_tokens = Some(128);
+        let (engine, run_id) = engine_with_active_run_of(sop);
+        let tool = SopAdvanceTool::new(engine);
+        let args = json!({
+            "run_id": run_id,
+            "status": "completed",
+            "output": "done"
+        });
+
+        assert!(tool.execute(args.clone()).await.unwrap().success);
+        assert!(matches!(
+            tool.generation_override(&args),
+            Some(GenerationOverride::Apply(params)) if params.max_tokens == Some(128)
+        ));
+
+        let result = tool.execute(args.clone()).await.unwrap();
+        assert!(result.output.contains("completed successfully"));
+        assert_eq!(
+            tool.generation_override(&args),
+            Some(GenerationOverride::Reset)
+        );
+    }
+
     #[tokio::test]
     async fn advance_with_failure() {
         let (engine, run_id) = engine_with_active_run();
@@ -355,6 +393,13 @@ mod tests {
         let schema = tool.parameters_schema();
         assert!(schema["properties"]["run_id"].is_object());
         assert!(schema["properties"]["status"]["enum"].is_array());
+        assert_eq!(
+            schema["properties"]["status"]["enum"],
+            json!(["completed", "failed", "skipped"])
+        );
+        assert_eq!(schema["required"], json!(["run_id", "status", "output"]));
+        assert!(schema.get("$schema").is_none());
+        assert!(schema.get("$defs").is_none());
     }
 
     #[tokio::test]
@@ -4,7 +4,7 @@ use async_trait::async_trait;
 use serde_json::json;
 use tracing::warn;
 
-use super::traits::{Tool, ToolResult};
+use super::traits::{GenerationOverride, Tool, ToolResult};
 use crate::sop::types::SopRunAction;
 use crate::sop::{SopAuditLogger, SopEngine, SopMetricsCollector};
 
@@ -58,6 +58,17 @@ impl Tool for SopApproveTool {
         })
     }
 
+    fn generation_override(&self, args: &serde_json::Value) -> Option<GenerationOverride> {
+        let run_id = args.get("run_id")?.as_str()?;
+        let engine = self.engine.lock().ok()?;
+        // A finished run hands the turn back to its own parameters.
+        Some(
+            engine
+                .run_generation(run_id)
+                .map_or(GenerationOverride::Reset, GenerationOverride::Apply),
+        )
+    }
+
     async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
         let run_id = args
             .get("run_id")
@@ -146,6 +157,7 @@ mod tests {
             }],
             cooldown_secs: 0,
             max_concurrent: 1,
+            generation: Default::default(),
             location: None,
         }
     }
@@ -4,7 +4,7 @@ use async_trait::async_trait;
 use serde_json::json;
 use tracing::warn;
 
-use super::traits::{Tool, ToolResult};
+use super::traits::{GenerationOverride, Tool, ToolResult};
 use crate::sop::types::{SopEvent, SopRunAction, SopTriggerSource};
 use crate::sop::{SopAuditLogger, SopEngine};
 
@@ -55,6 +55,19 @@ impl Tool for SopExecuteTool {
         })
     }
 
+    fn generation_override(&self, args: &serde_json::Value) -> Option<GenerationOverride> {
+        let name = args.get("name")?.as_str()?;
+        let engine = self.engine.lock().ok()?;
+        Some(
+            engine
+                .get_sop(name)?
+                .generation
+                .non_empty()
+                .cloned()
+                .map_or(GenerationOverride::Reset, GenerationOverride::Apply),
+        )
+    }
+
     async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
         let sop_name = args
             .get("name")
@@ -179,6 +192,7 @@ mod tests {
             ],
             cooldown_secs: 0,
             max_concurrent: 1,
+            generation: Default::default(),
             location: None,
         }
     }
@@ -217,6 +231,26 @@ mod tests {
         assert!(result.error.unwrap().contains("Failed to start SOP"));
     }
 
+    #[test]
+    fn generation_override_uses_sop_generation() {
+        let mut sop = test_sop("limited", SopExecutionMode::Auto);
+        sop.generation.max_tokens = Some(128);
+        let engine = engine_with_sops(vec![sop, test_sop("plain", SopExecutionMode::Auto)]);
+        let tool = SopExecuteTool::new(engine);
+
+        let Some(GenerationOverride::Apply(params)) =
+            tool.generation_override(&json!({"name": "limited"}))
+        else {
+            panic!("expected the SOP's generation parameters");
+        };
+        assert_eq!(params.max_tokens, Some(128));
+        assert_eq!(
+            tool.generation_override(&json!({"name": "plain"})),
+            Some(GenerationOverride::Reset)
+        );
+        assert!(tool.generation_override(&json!({})).is_none());
+    }
+
     #[tokio::test]
     async fn execute_missing_name() {
         let engine = engine_with_sops(vec![]);
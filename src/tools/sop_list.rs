@@ -140,6 +140,7 @@ mod tests {
             }],
             cooldown_secs: 0,
             max_concurrent: 1,
+            generation: Default::default(),
             location: None,
         }
     }
@@ -312,6 +312,7 @@ mod tests {
             }],
             cooldown_secs: 0,
             max_concurrent: 2,
+            generation: Default::default(),
             location: None,
         }
     }
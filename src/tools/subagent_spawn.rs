@@ -465,6 +465,9 @@ async fn run_agentic_background(
             None,
             None,
             &[],
+            None,
+            None,
+            None,
         ),
     )
     .await;
@@ -1,21 +1,31 @@
-//! Session-scoped task checklist for tracking multi-step work.
+//! Task checklist for tracking multi-step work.
 //!
 //! Provides a `task_plan` tool that lets the agent break complex work into
-//! steps and track progress within a single session. The task list lives in
-//! memory (`Arc<RwLock<Vec<TaskItem>>>`) and is discarded when the session
-//! ends — it is intentionally not persisted via the Memory trait.
+//! steps and track progress. By default the task list lives in memory
+//! (`Arc<RwLock<Vec<TaskItem>>>`) and is discarded when the session ends — it
+//! is intentionally not persisted via the Memory trait.
+//!
+//! A plan created with a `name` is saved to `{workspace}/state/task_plans.json`
+//! and can be resumed from a later CLI or channel session. Plans may be linked
+//! to a [`Goal`](crate::goals::engine::Goal): tasks carrying a `step_id` push
+//! their status to the matching goal step whenever they are updated.
 
+use crate::goals::engine::{GoalEngine, StepStatus};
 use crate::security::{policy::ToolOperation, SecurityPolicy};
 use crate::tools::traits::{Tool, ToolResult};
+use anyhow::Result;
 use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
 use serde_json::json;
-use std::fmt;
+use std::fmt::{self, Write as _};
+use std::path::{Path, PathBuf};
 use std::sync::{Arc, RwLock};
 
 // ── Data Structures ──────────────────────────────────────────────────────
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-enum TaskStatus {
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TaskStatus {
     Pending,
     InProgress,
     Completed,
@@ -40,13 +50,132 @@ impl TaskStatus {
             _ => None,
         }
     }
+
+    fn to_step_status(self) -> StepStatus {
+        match self {
+            TaskStatus::Pending => StepStatus::Pending,
+            TaskStatus::InProgress => StepStatus::InProgress,
+            TaskStatus::Completed => StepStatus::Completed,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TaskItem {
+    pub id: usize,
+    pub title: String,
+    pub status: TaskStatus,
+    /// Goal step advanced by this task (requires the plan's `goal_id`).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub step_id: Option<String>,
+}
+
+/// A named plan persisted across sessions.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TaskPlan {
+    pub name: String,
+    #[serde(default)]
+    pub goal_id: Option<String>,
+    #[serde(default)]
+    pub created_at: String,
+    #[serde(default)]
+    pub updated_at: String,
+    #[serde(default)]
+    pub tasks: Vec<TaskItem>,
+}
+
+impl TaskPlan {
+    /// Number of completed tasks.
+    pub fn completed(&self) -> usize {
+        self.tasks
+            .iter()
+            .filter(|t| t.status == TaskStatus::Completed)
+            .count()
+    }
 }
 
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct TaskPlanState {
+    #[serde(default)]
+    plans: Vec<TaskPlan>,
+}
+
+// ── Store ────────────────────────────────────────────────────────────────
+
+/// Named task plans persisted to `{workspace}/state/task_plans.json`.
 #[derive(Debug, Clone)]
-struct TaskItem {
-    id: usize,
-    title: String,
-    status: TaskStatus,
+pub struct TaskPlanStore {
+    state_path: PathBuf,
+}
+
+impl TaskPlanStore {
+    pub fn new(workspace_dir: &Path) -> Self {
+        Self {
+            state_path: workspace_dir.join("state").join("task_plans.json"),
+        }
+    }
+
+    /// All saved plans, most recently updated first.
+    pub fn list(&self) -> Result<Vec<TaskPlan>> {
+        let mut plans = self.load()?.plans;
+        plans.sort_by(|a, b| b.updated_at.cmp(&a.updated_at));
+        Ok(plans)
+    }
+
+    pub fn get(&self, name: &str) -> Result<Option<TaskPlan>> {
+        Ok(self.load()?.plans.into_iter().find(|p| p.name == name))
+    }
+
+    /// Insert or replace the plan with the same name.
+    pub fn save(&self, plan: &TaskPlan) -> Result<()> {
+        let mut state = self.load()?;
+        match state.plans.iter_mut().find(|p| p.name == plan.name) {
+            Some(existing) => *existing = plan.clone(),
+            None => state.plans.push(plan.clone()),
+        }
+        self.write(&state)
+    }
+
+    /// Remove a plan. Returns `false` when no plan had that name.
+    pub fn remove(&self, name: &str) -> Result<bool> {
+        let mut state = self.load()?;
+        let before = state.plans.len();
+        state.plans.retain(|p| p.name != name);
+        if state.plans.len() == before {
+            return Ok(false);
+        }
+        self.write(&state)?;
+        Ok(true)
+    }
+
+    fn load(&self) -> Result<TaskPlanState> {
+        if !self.state_path.exists() {
+            return Ok(TaskPlanState::default());
+        }
+        let bytes = std::fs::read(&self.state_path)?;
+        if bytes.is_empty() {
+            return Ok(TaskPlanState::default());
+        }
+        Ok(serde_json::from_slice(&bytes)?)
+    }
+
+    /// Atomic save: write to .tmp then rename.
+    fn write(&self, state: &TaskPlanState) -> Result<()> {
+        if let Some(parent) = self.state_path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let tmp = self.state_path.with_extension("json.tmp");
+        std::fs::write(&tmp, serde_json::to_vec_pretty(state)?)?;
+        std::fs::rename(&tmp, &self.state_path)?;
+        Ok(())
+    }
+}
+
+/// Named plan the current session is attached to.
+#[derive(Debug, Clone)]
+struct ActivePlan {
+    name: String,
+    created_at: String,
 }
 
 // ── Tool ─────────────────────────────────────────────────────────────────
@@ -55,6 +184,11 @@ pub struct TaskPlanTool {
     security: Arc<SecurityPolicy>,
     tasks: Arc<RwLock<Vec<TaskItem>>>,
     next_id: Arc<RwLock<usize>>,
+    active: Arc<RwLock<Option<ActivePlan>>>,
+    /// Goal whose steps the current tasks advance, named plan or not.
+    goal_id: Arc<RwLock<Option<String>>>,
+    store: Option<TaskPlanStore>,
+    goals: Option<Arc<GoalEngine>>,
 }
 
 impl TaskPlanTool {
@@ -63,9 +197,20 @@ impl TaskPlanTool {
             security,
             tasks: Arc::new(RwLock::new(Vec::new())),
             next_id: Arc::new(RwLock::new(1)),
+            active: Arc::new(RwLock::new(None)),
+            goal_id: Arc::new(RwLock::new(None)),
+            store: None,
+            goals: None,
         }
     }
 
+    /// Enable named plans and goal linking under `workspace_dir`.
+    pub fn with_workspace(mut self, workspace_dir: &Path) -> Self {
+        self.store = Some(TaskPlanStore::new(workspace_dir));
+        self.goals = Some(Arc::new(GoalEngine::new(workspace_dir)));
+        self
+    }
+
     /// Enforce mutation permission (autonomy + rate limit).
     fn enforce_mutation(&self) -> Result<(), ToolResult> {
         self.security
@@ -77,31 +222,95 @@ impl TaskPlanTool {
             })
     }
 
-    fn handle_create(&self, tasks_val: &serde_json::Value) -> ToolResult {
+    fn failure(message: impl Into<String>) -> ToolResult {
+        ToolResult {
+            success: false,
+            output: String::new(),
+            error: Some(message.into()),
+        }
+    }
+
+    fn require_store(&self) -> Result<&TaskPlanStore, ToolResult> {
+        self.store.as_ref().ok_or_else(|| {
+            Self::failure("Named task plans are not available: no workspace configured")
+        })
+    }
+
+    /// Save the active named plan, if any. Returns an error message on failure.
+    fn persist(&self) -> Result<(), String> {
+        let Some(active) = self.active.read().unwrap().clone() else {
+            return Ok(());
+        };
+        let Some(store) = self.store.as_ref() else {
+            return Ok(());
+        };
+        let plan = TaskPlan {
+            name: active.name.clone(),
+            goal_id: self.goal_id.read().unwrap().clone(),
+            created_at: active.created_at,
+            updated_at: chrono::Utc::now().to_rfc3339(),
+            tasks: self.tasks.read().unwrap().clone(),
+        };
+        store
+            .save(&plan)
+            .map_err(|e| format!("Failed to save task plan '{}': {e}", active.name))
+    }
+
+    /// Build tasks from the steps of a goal, linking each task to its step.
+    async fn tasks_from_goal(&self, goal_id: &str) -> Result<Vec<TaskItem>, ToolResult> {
+        let Some(goals) = self.goals.as_ref() else {
+            return Err(Self::failure(
+                "Goal linking is not available: no workspace configured",
+            ));
+        };
+        let state = goals
+            .load_state()
+            .await
+            .map_err(|e| Self::failure(format!("Failed to load goals: {e}")))?;
+        let goal = state
+            .goals
+            .iter()
+            .find(|g| g.id == goal_id)
+            .ok_or_else(|| Self::failure(format!("Goal '{goal_id}' not found")))?;
+        if goal.steps.is_empty() {
+            return Err(Self::failure(format!("Goal '{goal_id}' has no steps")));
+        }
+
+        Ok(goal
+            .steps
+            .iter()
+            .enumerate()
+            .map(|(i, step)| TaskItem {
+                id: i + 1,
+                title: step.description.clone(),
+                status: match step.status {
+                    StepStatus::Completed => TaskStatus::Completed,
+                    StepStatus::InProgress => TaskStatus::InProgress,
+                    _ => TaskStatus::Pending,
+                },
+                step_id: Some(step.id.clone()),
+            })
+            .collect())
+    }
+
+    fn parse_tasks(tasks_val: &serde_json::Value) -> Result<Vec<TaskItem>, ToolResult> {
         let arr = match tasks_val.as_array() {
             Some(a) if !a.is_empty() => a,
             _ => {
-                return ToolResult {
-                    success: false,
-                    output: String::new(),
-                    error: Some(
-                        "Parameter 'tasks' must be a non-empty array of {title, status?}".into(),
-                    ),
-                };
+                return Err(Self::failure(
+                    "Parameter 'tasks' must be a non-empty array of {title, status?}",
+                ));
             }
         };
 
         let mut items = Vec::with_capacity(arr.len());
-        let mut id = 1usize;
-        for entry in arr {
+        for (i, entry) in arr.iter().enumerate() {
             let title = match entry.get("title").and_then(|v| v.as_str()) {
                 Some(t) if !t.is_empty() => t.to_string(),
                 _ => {
-                    return ToolResult {
-                        success: false,
-                        output: String::new(),
-                        error: Some("Each task must have a non-empty 'title' string".into()),
-                    };
+                    return Err(Self::failure(
+                        "Each task must have a non-empty 'title' string",
+                    ));
                 }
             };
             let status = entry
@@ -109,40 +318,93 @@ impl TaskPlanTool {
                 .and_then(|v| v.as_str())
                 .and_then(TaskStatus::from_str)
                 .unwrap_or(TaskStatus::Pending);
-            items.push(TaskItem { id, title, status });
-            id += 1;
+            let step_id = entry
+                .get("step_id")
+                .and_then(|v| v.as_str())
+                .filter(|s| !s.is_empty())
+                .map(str::to_string);
+            items.push(TaskItem {
+                id: i + 1,
+                title,
+                status,
+                step_id,
+            });
         }
+        Ok(items)
+    }
+
+    async fn handle_create(
+        &self,
+        tasks_val: Option<&serde_json::Value>,
+        name: Option<&str>,
+        goal_id: Option<&str>,
+    ) -> ToolResult {
+        if name.is_some() || goal_id.is_some() {
+            if let Err(r) = self.require_store() {
+                return r;
+            }
+        }
+
+        let items = match (tasks_val, goal_id) {
+            (None, Some(goal_id)) => self.tasks_from_goal(goal_id).await,
+            (tasks_val, _) => Self::parse_tasks(tasks_val.unwrap_or(&json!([]))),
+        };
+        let items = match items {
+            Ok(items) => items,
+            Err(r) => return r,
+        };
 
         let count = items.len();
+        let next_id = count + 1;
         *self.tasks.write().unwrap() = items;
-        *self.next_id.write().unwrap() = id;
+        *self.next_id.write().unwrap() = next_id;
+        *self.active.write().unwrap() = name.map(|name| ActivePlan {
+            name: name.to_string(),
+            created_at: chrono::Utc::now().to_rfc3339(),
+        });
+        *self.goal_id.write().unwrap() = goal_id.map(str::to_string);
+
+        if let Err(e) = self.persist() {
+            return Self::failure(e);
+        }
 
+        let mut output = format!("Created {count} task(s).");
+        if let Some(name) = name {
+            let _ = write!(output, " Saved as plan '{name}'.");
+        }
+        if let Some(goal_id) = goal_id {
+            let _ = write!(output, " Linked to goal '{goal_id}'.");
+        }
         ToolResult {
             success: true,
-            output: format!("Created {count} task(s)."),
+            output,
             error: None,
         }
     }
 
-    fn handle_add(&self, title: &str) -> ToolResult {
+    fn handle_add(&self, title: &str, step_id: Option<&str>) -> ToolResult {
         if title.is_empty() {
-            return ToolResult {
-                success: false,
-                output: String::new(),
-                error: Some("Parameter 'title' must be a non-empty string".into()),
-            };
+            return Self::failure("Parameter 'title' must be a non-empty string");
         }
 
-        let mut next_id = self.next_id.write().unwrap();
-        let id = *next_id;
-        *next_id += 1;
+        let id = {
+            let mut next_id = self.next_id.write().unwrap();
+            let id = *next_id;
+            *next_id += 1;
+            id
+        };
 
         self.tasks.write().unwrap().push(TaskItem {
             id,
             title: title.to_string(),
             status: TaskStatus::Pending,
+            step_id: step_id.map(str::to_string),
         });
 
+        if let Err(e) = self.persist() {
+            return Self::failure(e);
+        }
+
         ToolResult {
             success: true,
             output: format!("Added task [{id}] \"{title}\"."),
@@ -150,35 +412,50 @@ impl TaskPlanTool {
         }
     }
 
-    fn handle_update(&self, id: usize, status_str: &str) -> ToolResult {
+    async fn handle_update(&self, id: usize, status_str: &str) -> ToolResult {
         let status = match TaskStatus::from_str(status_str) {
             Some(s) => s,
             None => {
-                return ToolResult {
-                    success: false,
-                    output: String::new(),
-                    error: Some(format!(
-                        "Invalid status '{status_str}'. Must be: pending, in_progress, completed"
-                    )),
-                };
+                return Self::failure(format!(
+                    "Invalid status '{status_str}'. Must be: pending, in_progress, completed"
+                ));
             }
         };
 
-        let mut tasks = self.tasks.write().unwrap();
-        match tasks.iter_mut().find(|t| t.id == id) {
-            Some(task) => {
-                task.status = status;
-                ToolResult {
-                    success: true,
-                    output: format!("Task [{id}] updated to {status}."),
-                    error: None,
+        let step_id = {
+            let mut tasks = self.tasks.write().unwrap();
+            match tasks.iter_mut().find(|t| t.id == id) {
+                Some(task) => {
+                    task.status = status;
+                    task.step_id.clone()
                 }
+                None => return Self::failure(format!("Task with id {id} not found")),
             }
-            None => ToolResult {
-                success: false,
-                output: String::new(),
-                error: Some(format!("Task with id {id} not found")),
-            },
+        };
+
+        if let Err(e) = self.persist() {
+            return Self::failure(e);
+        }
+
+        let mut output = format!("Task [{id}] updated to {status}.");
+        let goal_id = self.goal_id.read().unwrap().clone();
+        if let (Some(goal_id), Some(step_id), Some(goals)) = (goal_id, step_id, self.goals.as_ref())
+        {
+            let note = match goals
+                .set_step_status(&goal_id, &step_id, status.to_step_status())
+                .await
+            {
+                Ok(true) => format!(" Goal '{goal_id}' step '{step_id}' marked {status}."),
+                Ok(false) => format!(" Warning: goal '{goal_id}' has no step '{step_id}'."),
+                Err(e) => format!(" Warning: failed to update goal step: {e}"),
+            };
+            output.push_str(&note);
+        }
+
+        ToolResult {
+            success: true,
+            output,
+            error: None,
         }
     }
 
@@ -198,7 +475,14 @@ impl TaskPlanTool {
             .count();
         let total = tasks.len();
 
-        let mut lines = vec![format!("Tasks ({completed}/{total} completed):")];
+        let header = match self.active.read().unwrap().as_ref() {
+            Some(active) => format!(
+                "Plan '{}' tasks ({completed}/{total} completed):",
+                active.name
+            ),
+            None => format!("Tasks ({completed}/{total} completed):"),
+        };
+        let mut lines = vec![header];
         for t in tasks.iter() {
             lines.push(format!("- [{}] [{}] {}", t.id, t.status, t.title));
         }
@@ -214,9 +498,87 @@ impl TaskPlanTool {
         self.tasks.write().unwrap().clear();
         *self.next_id.write().unwrap() = 1;
 
+        let mut output = "Task list cleared.".to_string();
+        *self.goal_id.write().unwrap() = None;
+        let active = self.active.write().unwrap().take();
+        if let (Some(active), Some(store)) = (active, self.store.as_ref()) {
+            if let Err(e) = store.remove(&active.name) {
+                return Self::failure(format!("Failed to delete task plan '{}': {e}", active.name));
+            }
+            let _ = write!(output, " Saved plan '{}' deleted.", active.name);
+        }
+
         ToolResult {
             success: true,
-            output: "Task list cleared.".into(),
+            output,
+            error: None,
+        }
+    }
+
+    fn handle_resume(&self, name: &str) -> ToolResult {
+        let store = match self.require_store() {
+            Ok(store) => store,
+            Err(r) => return r,
+        };
+        if name.is_empty() {
+            return Self::failure("Parameter 'name' is required for resume");
+        }
+
+        let plan = match store.get(name) {
+            Ok(Some(plan)) => plan,
+            Ok(None) => return Self::failure(format!("Task plan '{name}' not found")),
+            Err(e) => return Self::failure(format!("Failed to load task plans: {e}")),
+        };
+
+        let next_id = plan.tasks.iter().map(|t| t.id).max().unwrap_or(0) + 1;
+        *self.tasks.write().unwrap() = plan.tasks;
+        *self.next_id.write().unwrap() = next_id;
+        *self.active.write().unwrap() = Some(ActivePlan {
+            name: plan.name,
+            created_at: plan.created_at,
+        });
+        *self.goal_id.write().unwrap() = plan.goal_id;
+
+        self.handle_list()
+    }
+
+    fn handle_plans(&self) -> ToolResult {
+        let store = match self.require_store() {
+            Ok(store) => store,
+            Err(r) => return r,
+        };
+        let plans = match store.list() {
+            Ok(plans) => plans,
+            Err(e) => return Self::failure(format!("Failed to load task plans: {e}")),
+        };
+        if plans.is_empty() {
+            return ToolResult {
+                success: true,
+                output: "No saved task plans.".into(),
+                error: None,
+            };
+        }
+
+        let mut lines = vec![format!("Saved task plans ({}):", plans.len())];
+        for plan in &plans {
+            let mut line = format!(
+                "- {} ({}/{} completed)",
+                plan.name,
+                plan.completed(),
+                plan.tasks.len()
+            );
+            if let Some(goal_id) = plan.goal_id.as_deref() {
+                let _ = write!(line, " goal={goal_id}");
+            }
+            if !plan.updated_at.is_empty() {
+                let _ = write!(line, " updated={}", plan.updated_at);
+            }
+            lines.push(line);
+        }
+
+        ToolResult {
+            success: true,
+            output: lines.join("\n"),
             error: None,
         }
     }
@@ -229,8 +591,10 @@ impl Tool for TaskPlanTool {
     }
 
     fn description(&self) -> &str {
-        "管理当前会话的任务清单。用于将复杂工作分解为步骤并跟踪进度。\n\
-         操作：创建（批量）、添加（单个）、更新（更改状态）、列出（查看所有）、删除（清除所有）。"
+        "管理任务清单。用于将复杂工作分解为步骤并跟踪进度。\n\
+         操作：创建（批量）、添加（单个）、更新（更改状态）、列出（查看所有）、删除（清除所有）、\
+         恢复（加载已保存的命名计划）、计划列表（查看所有已保存的计划）。\n\
+         创建时提供 name 可跨会话保存计划；提供 goal_id 可关联目标，任务完成时会同步推进对应的目标步骤。"
     }
 
     fn parameters_schema(&self) -> serde_json::Value {
@@ -239,7 +603,7 @@ impl Tool for TaskPlanTool {
             "properties": {
                 "action": {
                     "type": "string",
-                    "enum": ["create", "add", "update", "list", "delete"],
+                    "enum": ["create", "add", "update", "list", "delete", "resume", "plans"],
                     "description": "Operation to perform"
                 },
                 "tasks": {
@@ -251,16 +615,32 @@ impl Tool for TaskPlanTool {
                             "status": {
                                 "type": "string",
                                 "enum": ["pending", "in_progress", "completed"]
+                            },
+                            "step_id": {
+                                "type": "string",
+                                "description": "Goal step advanced by this task"
                             }
                         },
                         "required": ["title"]
                     },
-                    "description": "For 'create': list of tasks to create (replaces existing list)"
+                    "description": "For 'create': list of tasks to create (replaces existing list). When omitted with 'goal_id', tasks are created from the goal's steps"
+                },
+                "name": {
+                    "type": "string",
+                    "description": "For 'create': save the plan under this name so it can be resumed later. For 'resume': name of the saved plan"
+                },
+                "goal_id": {
+                    "type": "string",
+                    "description": "For 'create': link the plan to a goal in state/goals.json"
                 },
                 "title": {
                     "type": "string",
                     "description": "For 'add': title of the new task"
                 },
+                "step_id": {
+                    "type": "string",
+                    "description": "For 'add': goal step advanced by the new task"
+                },
                 "id": {
                     "type": "integer",
                     "description": "For 'update': ID of the task to update"
@@ -280,14 +660,21 @@ impl Tool for TaskPlanTool {
             .get("action")
             .and_then(|v| v.as_str())
             .unwrap_or_default();
+        let str_arg = |key: &str| {
+            args.get(key)
+                .and_then(|v| v.as_str())
+                .map(str::trim)
+                .filter(|s| !s.is_empty())
+        };
 
         match action {
             "create" => {
                 if let Err(r) = self.enforce_mutation() {
                     return Ok(r);
                 }
-                let tasks_val = args.get("tasks").cloned().unwrap_or(json!([]));
-                Ok(self.handle_create(&tasks_val))
+                Ok(self
+                    .handle_create(args.get("tasks"), str_arg("name"), str_arg("goal_id"))
+                    .await)
             }
             "add" => {
                 if let Err(r) = self.enforce_mutation() {
@@ -297,7 +684,7 @@ impl Tool for TaskPlanTool {
                     .get("title")
                     .and_then(|v| v.as_str())
                     .unwrap_or_default();
-                Ok(self.handle_add(title))
+                Ok(self.handle_add(title, str_arg("step_id")))
             }
             "update" => {
                 if let Err(r) = self.enforce_mutation() {
@@ -323,7 +710,7 @@ impl Tool for TaskPlanTool {
                         error: Some("Parameter 'status' is required for update".into()),
                     });
                 }
-                Ok(self.handle_update(id, status))
+                Ok(self.handle_update(id, status).await)
             }
             "list" => Ok(self.handle_list()),
             "delete" => {
@@ -332,11 +719,13 @@ impl Tool for TaskPlanTool {
                 }
                 Ok(self.handle_delete())
             }
+            "resume" => Ok(self.handle_resume(str_arg("name").unwrap_or_default())),
+            "plans" => Ok(self.handle_plans()),
             other => Ok(ToolResult {
                 success: false,
                 output: String::new(),
                 error: Some(format!(
-                    "Unknown action '{other}'. Valid: create, add, update, list, delete"
+                    "Unknown action '{other}'. Valid: create, add, update, list, delete, resume, plans"
                 )),
             }),
         }
@@ -605,4 +994,158 @@ mod tests {
         assert!(r.success);
         assert!(r.output.contains("No tasks"));
     }
+
+    fn workspace_tool(dir: &std::path::Path) -> TaskPlanTool {
+        TaskPlanTool::new(Arc::new(SecurityPolicy::default())).with_workspace(dir)
+    }
+
+    #[tokio::test]
+    async fn named_plan_resumes_in_new_session() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let tool = workspace_tool(tmp.path());
+        let r = tool
+            .execute(json!({
+                "action": "create",
+                "name": "migration",
+                "tasks": [{ "title": "dump db" }, { "title": "restore db" }]
+            }))
+            .await
+            .unwrap();
+        assert!(r.success, "{:?}", r.error);
+        assert!(r.output.contains("Saved as plan 'migration'"));
+        tool.execute(json!({ "action": "update", "id": 1, "status": "completed" }))
+            .await
+            .unwrap();
+        tool.execute(json!({ "action": "add", "title": "verify" }))
+            .await
+            .unwrap();
+
+        let later = workspace_tool(tmp.path());
+        let r = later.execute(json!({ "action": "plans" })).await.unwrap();
+        assert!(r.output.contains("- migration (1/3 completed)"));
+
+        let r = later
+            .execute(json!({ "action": "resume", "name": "migration" }))
+            .await
+            .unwrap();
+        assert!(r.success);
+        assert!(r.output.contains("Plan 'migration' tasks (1/3 completed)"));
+        assert!(r.output.contains("[3] [pending] verify"));
+
+        let r = later
+            .execute(json!({ "action": "add", "title": "announce" }))
+            .await
+            .unwrap();
+        assert!(r.output.contains("[4]"));
+    }
+
+    #[tokio::test]
+    async fn delete_removes_saved_plan() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let tool = workspace_tool(tmp.path());
+        tool.execute(json!({
+            "action": "create",
+            "name": "tmp-plan",
+            "tasks": [{ "title": "t" }]
+        }))
+        .await
+        .unwrap();
+
+        let r = tool.execute(json!({ "action": "delete" })).await.unwrap();
+        assert!(r.output.contains("Saved plan 'tmp-plan' deleted"));
+        assert!(TaskPlanStore::new(tmp.path()).list().unwrap().is_empty());
+
+        let r = tool
+            .execute(json!({ "action": "resume", "name": "tmp-plan" }))
+            .await
+            .unwrap();
+        assert!(!r.success);
+        assert!(r.error.unwrap().contains("not found"));
+    }
+
+    #[tokio::test]
+    async fn goal_linked_plan_advances_steps() {
+        use crate::goals::engine::{Goal, GoalState, GoalStatus, Step};
+
+        let tmp = tempfile::TempDir::new().unwrap();
+        let engine = GoalEngine::new(tmp.path());
+        let step = |id: &str, description: &str| Step {
+            id: id.into(),
+            description: description.into(),
+            status: StepStatus::Pending,
+            result: None,
+            attempts: 0,
+        };
+        engine
+            .save_state(&GoalState {
+                goals: vec![Goal {
+                    id: "g1".into(),
+                    description: "Ship release".into(),
+                    status: GoalStatus::Pending,
+                    priority: Default::default(),
+                    created_at: String::new(),
+                    updated_at: String::new(),
+                    steps: vec![step("s1", "Write changelog"), step("s2", "Tag release")],
+                    context: String::new(),
+                    last_error: None,
+                }],
+            })
+            .await
+            .unwrap();
+
+        let tool = workspace_tool(tmp.path());
+        let r = tool
+            .execute(json!({ "action": "create", "name": "release", "goal_id": "g1" }))
+            .await
+            .unwrap();
+        assert!(r.success, "{:?}", r.error);
+        assert!(r.output.contains("Created 2 task(s)"));
+
+        let r = tool
+            .execute(json!({ "action": "update", "id": 1, "status": "completed" }))
+            .await
+            .unwrap();
+        assert!(r.output.contains("Goal 'g1' step 's1' marked completed"));
+
+        let state = engine.load_state().await.unwrap();
+        assert_eq!(state.goals[0].status, GoalStatus::InProgress);
+        assert_eq!(state.goals[0].steps[0].status, StepStatus::Completed);
+        assert_eq!(state.goals[0].steps[1].status, StepStatus::Pending);
+
+        let saved = TaskPlanStore::new(tmp.path())
+            .get("release")
+            .unwrap()
+            .unwrap();
+        assert_eq!(saved.goal_id.as_deref(), Some("g1"));
+        assert_eq!(saved.tasks[1].step_id.as_deref(), Some("s2"));
+
+        // The goal link does not depend on saving the plan under a name.
+        let r = tool
+            .execute(json!({ "action": "create", "goal_id": "g1" }))
+            .await
+            .unwrap();
+        assert!(r.output.contains("Linked to goal 'g1'"));
+        let r = tool
+            .execute(json!({ "action": "update", "id": 2, "status": "completed" }))
+            .await
+            .unwrap();
+        assert!(r.output.contains("Goal 'g1' step 's2' marked completed"));
+        let state = engine.load_state().await.unwrap();
+        assert_eq!(state.goals[0].steps[1].status, StepStatus::Completed);
+    }
+
+    #[tokio::test]
+    async fn named_plan_without_workspace_fails() {
+        let tool = default_tool();
+        let r = tool
+            .execute(json!({
+                "action": "create",
+                "name": "p",
+                "tasks": [{ "title": "t" }]
+            }))
+            .await
+            .unwrap();
+        assert!(!r.success);
+        assert!(r.error.unwrap().contains("no workspace"));
+    }
 }
@@ -1,3 +1,4 @@
+use crate::providers::GenerationParams;
 use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
 
@@ -40,6 +41,22 @@ pub trait Tool: Send + Sync {
             parameters: self.parameters_schema(),
         }
     }
+
+    /// Change to the generation parameters of the following requests in the
+    /// agent turn after a successful call with `args` (e.g. the limits of an
+    /// SOP while its run is active).
+    fn generation_override(&self, _args: &serde_json::Value) -> Option<GenerationOverride> {
+        None
+    }
+}
+
+/// Generation parameters a tool call switches the rest of the turn to.
+#[derive(Debug, Clone, PartialEq)]
+pub enum GenerationOverride {
+    /// Use these parameters, layered over the turn's own.
+    Apply(GenerationParams),
+    /// Go back to the turn's own parameters.
+    Reset,
 }
 
 #[cfg(test)]
@@ -187,7 +187,12 @@ impl Tool for WebFetchTool {
             }
         };
 
-        let response = match client.get(&url).send().await {
+        let mut request = client.get(&url);
+        for (key, value) in crate::observability::spans::propagation_headers() {
+            request = request.header(key, value);
+        }
+
+        let response = match request.send().await {
             Ok(r) => r,
             Err(e) => {
                 return Ok(ToolResult {
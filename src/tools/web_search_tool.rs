@@ -1,21 +1,23 @@
+use super::search_backends::{
+    build_configured_backend, build_search_backend, SearchBackend, SearchHttpOptions, SearchResult,
+};
 use super::traits::{Tool, ToolResult};
+use crate::config::WebSearchConfig;
+use crate::memory::SearchCache;
 use crate::security::SecurityPolicy;
 use async_trait::async_trait;
-use regex::Regex;
 use serde_json::json;
+use std::path::Path;
 use std::sync::Arc;
-use std::time::Duration;
 
 /// Web search tool for searching the internet.
-/// Supports providers: DuckDuckGo (free), Brave, Firecrawl.
+/// Supports providers: DuckDuckGo (free), Brave, Firecrawl, SearXNG, and
+/// custom JSON endpoints, optionally fanned out across several backends.
 pub struct WebSearchTool {
     security: Arc<SecurityPolicy>,
-    provider: String,
-    api_key: Option<String>,
-    api_url: Option<String>,
+    backend: Box<dyn SearchBackend>,
     max_results: usize,
-    timeout_secs: u64,
-    user_agent: String,
+    cache: Option<Arc<SearchCache>>,
 }
 
 impl WebSearchTool {
@@ -28,272 +30,127 @@ impl WebSearchTool {
         timeout_secs: u64,
         user_agent: String,
     ) -> Self {
-        Self {
-            security,
-            provider: provider.trim().to_lowercase(),
-            api_key,
-            api_url,
-            max_results: max_results.clamp(1, 10),
+        let config = WebSearchConfig {
+            provider: provider.clone(),
+            brave_api_key: api_key,
+            ..WebSearchConfig::default()
+        };
+        let http = SearchHttpOptions {
             timeout_secs: timeout_secs.max(1),
             user_agent,
-        }
-    }
-
-    async fn search_duckduckgo(&self, query: &str) -> anyhow::Result<String> {
-        let encoded_query = urlencoding::encode(query);
-        let search_url = format!("https://html.duckduckgo.com/html/?q={}", encoded_query);
-
-        let client = reqwest::Client::builder()
-            .timeout(Duration::from_secs(self.timeout_secs))
-            .user_agent(self.user_agent.as_str())
-            .build()?;
-
-        let response = client.get(&search_url).send().await?;
-
-        if !response.status().is_success() {
-            anyhow::bail!(
-                "DuckDuckGo search failed with status: {}",
-                response.status()
-            );
-        }
-
-        let html = response.text().await?;
-        self.parse_duckduckgo_results(&html, query)
+        };
+        let backend = build_search_backend(&provider, &config, api_url, &http)
+            .unwrap_or_else(|e| Box::new(UnavailableBackend(e.to_string())));
+        Self::with_backend(security, backend, max_results)
     }
 
-    fn parse_duckduckgo_results(&self, html: &str, query: &str) -> anyhow::Result<String> {
-        // Extract result links: <a class="result__a" href="...">Title</a>
-        let link_regex = Regex::new(
-            r#"<a[^>]*class="[^"]*result__a[^"]*"[^>]*href="([^"]+)"[^>]*>([\s\S]*?)</a>"#,
-        )?;
-
-        // Extract snippets: <a class="result__snippet">...</a>
-        let snippet_regex = Regex::new(r#"<a class="result__snippet[^"]*"[^>]*>([\s\S]*?)</a>"#)?;
-
-        let link_matches: Vec<_> = link_regex
-            .captures_iter(html)
-            .take(self.max_results + 2)
-            .collect();
-
-        let snippet_matches: Vec<_> = snippet_regex
-            .captures_iter(html)
-            .take(self.max_results + 2)
-            .collect();
-
-        if link_matches.is_empty() {
-            return Ok(format!("No results found for: {}", query));
-        }
-
-        let mut lines = vec![format!("Search results for: {} (via DuckDuckGo)", query)];
-
-        let count = link_matches.len().min(self.max_results);
-
-        for i in 0..count {
-            let caps = &link_matches[i];
-            let url_str = decode_ddg_redirect_url(&caps[1]);
-            let title = strip_tags(&caps[2]);
-
-            lines.push(format!("{}. {}", i + 1, title.trim()));
-            lines.push(format!("   {}", url_str.trim()));
-
-            // Add snippet if available
-            if i < snippet_matches.len() {
-                let snippet = strip_tags(&snippet_matches[i][1]);
-                let snippet = snippet.trim();
-                if !snippet.is_empty() {
-                    lines.push(format!("   {}", snippet));
-                }
+    /// Build the tool from `[web_search]`, honouring `fan_out`, custom
+    /// backends and the optional result cache.
+    pub fn from_config(
+        security: Arc<SecurityPolicy>,
+        config: &WebSearchConfig,
+        workspace_dir: &Path,
+        user_agent: String,
+    ) -> Self {
+        let http = SearchHttpOptions {
+            timeout_secs: config.timeout_secs.max(1),
+            user_agent,
+        };
+        let backend = build_configured_backend(config, &http)
+            .unwrap_or_else(|e| Box::new(UnavailableBackend(e.to_string())));
+        let mut tool = Self::with_backend(security, backend, config.max_results);
+
+        if config.cache_ttl_minutes > 0 {
+            match SearchCache::new(
+                workspace_dir,
+                config.cache_ttl_minutes,
+                config.cache_max_entries,
+            ) {
+                Ok(cache) => tool.cache = Some(Arc::new(cache)),
+                Err(e) => tracing::warn!("web_search: result cache disabled: {e}"),
             }
         }
 
-        Ok(lines.join("\n"))
+        tool
     }
 
-    async fn search_brave(&self, query: &str) -> anyhow::Result<String> {
-        let auth_token = match self.api_key.as_ref() {
-            Some(raw) if !raw.trim().is_empty() => raw.trim(),
-            _ => anyhow::bail!("Brave API key not configured"),
-        };
-
-        let encoded_query = urlencoding::encode(query);
-        let search_url = format!(
-            "https://api.search.brave.com/res/v1/web/search?q={}&count={}",
-            encoded_query, self.max_results
-        );
-
-        let client = reqwest::Client::builder()
-            .timeout(Duration::from_secs(self.timeout_secs))
-            .user_agent(self.user_agent.as_str())
-            .build()?;
-
-        let response = client
-            .get(&search_url)
-            .header("Accept", "application/json")
-            .header("X-Subscription-Token", auth_token)
-            .send()
-            .await?;
-
-        if !response.status().is_success() {
-            anyhow::bail!("Brave search failed with status: {}", response.status());
+    pub fn with_backend(
+        security: Arc<SecurityPolicy>,
+        backend: Box<dyn SearchBackend>,
+        max_results: usize,
+    ) -> Self {
+        Self {
+            security,
+            backend,
+            max_results: max_results.clamp(1, 10),
+            cache: None,
         }
-
-        let json: serde_json::Value = response.json().await?;
-        self.parse_brave_results(&json, query)
     }
 
-    fn parse_brave_results(&self, json: &serde_json::Value, query: &str) -> anyhow::Result<String> {
-        let results = json
-            .get("web")
-            .and_then(|w| w.get("results"))
-            .and_then(|r| r.as_array())
-            .ok_or_else(|| anyhow::anyhow!("Invalid Brave API response"))?;
-
-        if results.is_empty() {
-            return Ok(format!("No results found for: {}", query));
-        }
-
-        let mut lines = vec![format!("Search results for: {} (via Brave)", query)];
-
-        for (i, result) in results.iter().take(self.max_results).enumerate() {
-            let title = result
-                .get("title")
-                .and_then(|t| t.as_str())
-                .unwrap_or("No title");
-            let url = result.get("url").and_then(|u| u.as_str()).unwrap_or("");
-            let description = result
-                .get("description")
-                .and_then(|d| d.as_str())
-                .unwrap_or("");
-
-            lines.push(format!("{}. {}", i + 1, title));
-            lines.push(format!("   {}", url));
-            if !description.is_empty() {
-                lines.push(format!("   {}", description));
-            }
-        }
-
-        Ok(lines.join("\n"))
+    pub fn with_cache(mut self, cache: Arc<SearchCache>) -> Self {
+        self.cache = Some(cache);
+        self
     }
 
-    #[cfg(feature = "firecrawl")]
-    async fn search_firecrawl(&self, query: &str) -> anyhow::Result<String> {
-        let auth_token = match self.api_key.as_ref() {
-            Some(raw) if !raw.trim().is_empty() => raw.trim(),
-            _ => {
-                anyhow::bail!(
-                    "web_search provider 'firecrawl' requires [web_search].api_key in config.toml"
-                );
-            }
+    async fn search(&self, query: &str) -> anyhow::Result<Vec<SearchResult>> {
+        let Some(cache) = self.cache.as_ref() else {
+            return self.backend.search(query, self.max_results).await;
         };
 
-        let api_url = self
-            .api_url
-            .as_deref()
-            .map(str::trim)
-            .filter(|s| !s.is_empty())
-            .unwrap_or("https://api.firecrawl.dev");
-        let endpoint = format!("{}/v1/search", api_url.trim_end_matches('/'));
-        let client = reqwest::Client::builder()
-            .timeout(Duration::from_secs(self.timeout_secs))
-            .user_agent(self.user_agent.as_str())
-            .build()?;
-
-        let response = client
-            .post(endpoint)
-            .header(
-                reqwest::header::AUTHORIZATION,
-                format!("Bearer {auth_token}"),
-            )
-            .json(&json!({
-                "query": query,
-                "limit": self.max_results,
-                "timeout": (self.timeout_secs * 1000) as u64,
-            }))
-            .send()
-            .await
-            .map_err(|e| anyhow::anyhow!("Firecrawl search failed: {e}"))?;
-        let status = response.status();
-        let body = response.text().await?;
-
-        if !status.is_success() {
-            anyhow::bail!(
-                "Firecrawl search failed with status {}: {}",
-                status.as_u16(),
-                body
-            );
-        }
-
-        let parsed: serde_json::Value = serde_json::from_str(&body)
-            .map_err(|e| anyhow::anyhow!("Invalid Firecrawl response JSON: {e}"))?;
-        if !parsed
-            .get("success")
-            .and_then(serde_json::Value::as_bool)
-            .unwrap_or(false)
-        {
-            let error = parsed
-                .get("error")
-                .and_then(serde_json::Value::as_str)
-                .unwrap_or("unknown error");
-            anyhow::bail!("Firecrawl search failed: {error}");
-        }
-
-        let results = parsed
-            .get("data")
-            .and_then(serde_json::Value::as_array)
-            .ok_or_else(|| anyhow::anyhow!("Firecrawl response missing data array"))?;
-
-        if results.is_empty() {
-            return Ok(format!("No results found for: {}", query));
+        let key = SearchCache::cache_key(self.backend.name(), query, self.max_results);
+        match cache.get(&key) {
+            Ok(Some(cached)) => {
+                if let Ok(results) = serde_json::from_str::<Vec<SearchResult>>(&cached) {
+                    tracing::debug!("web_search: cache hit for {query:?}");
+                    return Ok(results);
+                }
+            }
+            Ok(None) => {}
+            Err(e) => tracing::warn!("web_search: cache lookup failed: {e}"),
         }
 
-        let mut lines = vec![format!("Search results for: {} (via Firecrawl)", query)];
-
-        for (i, result) in results.iter().take(self.max_results).enumerate() {
-            let title = result
-                .get("title")
-                .and_then(serde_json::Value::as_str)
-                .unwrap_or("No title");
-            let url = result
-                .get("url")
-                .and_then(serde_json::Value::as_str)
-                .unwrap_or("");
-            let description = result
-                .get("description")
-                .and_then(serde_json::Value::as_str)
-                .unwrap_or("");
-
-            lines.push(format!("{}. {}", i + 1, title));
-            lines.push(format!("   {}", url));
-            if !description.trim().is_empty() {
-                lines.push(format!("   {}", description.trim()));
+        let results = self.backend.search(query, self.max_results).await?;
+        if !results.is_empty() {
+            let stored = serde_json::to_string(&results)
+                .map_err(anyhow::Error::from)
+                .and_then(|json| cache.put(&key, self.backend.name(), query, &json));
+            if let Err(e) = stored {
+                tracing::warn!("web_search: cache store failed: {e}");
             }
         }
+        Ok(results)
+    }
+}
+
+/// Placeholder for a misconfigured provider; reports the configuration
+/// error when the tool is invoked instead of failing tool registration.
+struct UnavailableBackend(String);
 
-        Ok(lines.join("\n"))
+#[async_trait]
+impl SearchBackend for UnavailableBackend {
+    fn name(&self) -> &str {
+        "unavailable"
     }
 
-    #[cfg(not(feature = "firecrawl"))]
-    #[allow(clippy::unused_async)]
-    async fn search_firecrawl(&self, _query: &str) -> anyhow::Result<String> {
-        anyhow::bail!("web_search provider 'firecrawl' requires Cargo feature 'firecrawl'")
+    async fn search(&self, _query: &str, _max_results: usize) -> anyhow::Result<Vec<SearchResult>> {
+        anyhow::bail!("{}", self.0)
     }
 }
 
-fn decode_ddg_redirect_url(raw_url: &str) -> String {
-    if let Some(index) = raw_url.find("uddg=") {
-        let encoded = &raw_url[index + 5..];
-        let encoded = encoded.split('&').next().unwrap_or(encoded);
-        if let Ok(decoded) = urlencoding::decode(encoded) {
-            return decoded.into_owned();
-        }
+fn format_results(query: &str, backend: &str, results: &[SearchResult]) -> String {
+    if results.is_empty() {
+        return format!("No results found for: {}", query);
     }
 
-    raw_url.to_string()
-}
-
-fn strip_tags(content: &str) -> String {
-    let re = Regex::new(r"<[^>]+>").unwrap();
-    re.replace_all(content, "").to_string()
+    let mut lines = vec![format!("Search results for: {} (via {})", query, backend)];
+    for (i, result) in results.iter().enumerate() {
+        lines.push(format!("{}. {}", i + 1, result.title));
+        lines.push(format!("   {}", result.url));
+        if !result.snippet.is_empty() {
+            lines.push(format!("   {}", result.snippet));
+        }
+    }
+    lines.join("\n")
 }
 
 #[async_trait]
@@ -303,7 +160,7 @@ impl Tool for WebSearchTool {
     }
 
     fn description(&self) -> &str {
-        "在网络上搜索信息。返回相关的搜索结果，包括标题、URL和描述。使用此工具查找当前信息、新闻或研究主题。"
+        "在网络上搜索信息。返回相关的搜索结果，包括标题、URL和描述。支持 DuckDuckGo、Brave、Firecrawl、自托管 SearXNG 以及自定义 JSON 搜索接口。使用此工具查找当前信息、新闻或研究主题。"
     }
 
     fn parameters_schema(&self) -> serde_json::Value {
@@ -347,15 +204,8 @@ impl Tool for WebSearchTool {
 
         tracing::info!("Searching web for: {}", query);
 
-        let result = match self.provider.as_str() {
-            "duckduckgo" | "ddg" => self.search_duckduckgo(query).await?,
-            "brave" => self.search_brave(query).await?,
-            "firecrawl" => self.search_firecrawl(query).await?,
-            _ => anyhow::bail!(
-                "Unknown search provider: '{}'. Set tools.web_search.provider to 'duckduckgo', 'brave', or 'firecrawl' in config.toml",
-                self.provider
-            ),
-        };
+        let results = self.search(query).await?;
+        let result = format_results(query, self.backend.name(), &results);
 
         Ok(ToolResult {
             success: true,
@@ -369,6 +219,8 @@ impl Tool for WebSearchTool {
 mod tests {
     use super::*;
     use crate::security::{AutonomyLevel, SecurityPolicy};
+    use crate::tools::search_backends::{strip_tags, DuckDuckGoBackend};
+    use std::sync::atomic::{AtomicUsize, Ordering};
 
     fn test_security() -> Arc<SecurityPolicy> {
         Arc::new(SecurityPolicy {
@@ -421,6 +273,12 @@ mod tests {
         assert!(schema["properties"]["query"].is_object());
     }
 
+    fn parse_ddg(html: &str, max_results: usize) -> String {
+        let max_results = max_results.clamp(1, 10);
+        let results = DuckDuckGoBackend::parse_results(html, max_results).unwrap();
+        format_results("test", "DuckDuckGo", &results)
+    }
+
     #[test]
     fn test_strip_tags() {
         let html = "<b>Hello</b> <i>World</i>";
@@ -429,57 +287,28 @@ mod tests {
 
     #[test]
     fn test_parse_duckduckgo_results_empty() {
-        let tool = WebSearchTool::new(
-            test_security(),
-            "duckduckgo".to_string(),
-            None,
-            None,
-            5,
-            15,
-            "test".to_string(),
-        );
-        let result = tool
-            .parse_duckduckgo_results("<html>No results here</html>", "test")
-            .unwrap();
+        let result = parse_ddg("<html>No results here</html>", 5);
         assert!(result.contains("No results found"));
     }
 
     #[test]
     fn test_parse_duckduckgo_results_with_data() {
-        let tool = WebSearchTool::new(
-            test_security(),
-            "duckduckgo".to_string(),
-            None,
-            None,
-            5,
-            15,
-            "test".to_string(),
-        );
         let html = r#"
             <a class="result__a" href="https://example.com">Example Title</a>
             <a class="result__snippet">This is a description</a>
         "#;
-        let result = tool.parse_duckduckgo_results(html, "test").unwrap();
+        let result = parse_ddg(html, 5);
         assert!(result.contains("Example Title"));
         assert!(result.contains("https://example.com"));
     }
 
     #[test]
     fn test_parse_duckduckgo_results_decodes_redirect_url() {
-        let tool = WebSearchTool::new(
-            test_security(),
-            "duckduckgo".to_string(),
-            None,
-            None,
-            5,
-            15,
-            "test".to_string(),
-        );
         let html = r#"
             <a class="result__a" href="https://duckduckgo.com/l/?uddg=https%3A%2F%2Fexample.com%2Fpath%3Fa%3D1&amp;rut=test">Example Title</a>
             <a class="result__snippet">This is a description</a>
         "#;
-        let result = tool.parse_duckduckgo_results(html, "test").unwrap();
+        let result = parse_ddg(html, 5);
         assert!(result.contains("https://example.com/path?a=1"));
         assert!(!result.contains("rut=test"));
     }
@@ -495,11 +324,12 @@ mod tests {
             0,
             "test".to_string(),
         );
+        assert_eq!(tool.max_results, 1);
         let html = r#"
             <a class="result__a" href="https://example.com">Example Title</a>
             <a class="result__snippet">This is a description</a>
         "#;
-        let result = tool.parse_duckduckgo_results(html, "test").unwrap();
+        let result = parse_ddg(html, 0);
         assert!(result.contains("Example Title"));
     }
 
@@ -589,4 +419,79 @@ mod tests {
         assert!(!result.success);
         assert!(result.error.unwrap().contains("read-only"));
     }
+
+    struct CountingBackend {
+        calls: Arc<AtomicUsize>,
+    }
+
+    #[async_trait]
+    impl SearchBackend for CountingBackend {
+        fn name(&self) -> &str {
+            "Counting"
+        }
+
+        async fn search(&self, query: &str, _max: usize) -> anyhow::Result<Vec<SearchResult>> {
+            self.calls.fetch_add(1, Ordering::SeqCst);
+            Ok(vec![SearchResult {
+                title: format!("About {query}"),
+                url: "https://example.com".into(),
+                snippet: "snippet".into(),
+            }])
+        }
+    }
+
+    #[tokio::test]
+    async fn test_execute_formats_backend_results() {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let tool = WebSearchTool::with_backend(
+            test_security(),
+            Box::new(CountingBackend {
+                calls: calls.clone(),
+            }),
+            5,
+        );
+        let result = tool.execute(json!({"query": "rust"})).await.unwrap();
+        assert!(result.success);
+        assert!(result.output.contains("(via Counting)"));
+        assert!(result.output.contains("1. About rust"));
+        assert!(result.output.contains("   snippet"));
+    }
+
+    #[tokio::test]
+    async fn test_execute_serves_repeat_queries_from_cache() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let cache = Arc::new(SearchCache::new(tmp.path(), 60, 100).unwrap());
+        let calls = Arc::new(AtomicUsize::new(0));
+        let tool = WebSearchTool::with_backend(
+            test_security(),
+            Box::new(CountingBackend {
+                calls: calls.clone(),
+            }),
+            5,
+        )
+        .with_cache(cache.clone());
+
+        let first = tool.execute(json!({"query": "Rust async"})).await.unwrap();
+        let second = tool.execute(json!({"query": "rust  async"})).await.unwrap();
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+        assert!(first.output.contains("About Rust async"));
+        assert!(second.output.contains("About Rust async"));
+        assert_eq!(cache.stats().unwrap(), (1, 1));
+    }
+
+    #[tokio::test]
+    async fn test_from_config_reports_missing_searxng_url_on_execute() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let config = WebSearchConfig {
+            provider: "searxng".into(),
+            ..WebSearchConfig::default()
+        };
+        let tool = WebSearchTool::from_config(test_security(), &config, tmp.path(), "test".into());
+        let error = tool
+            .execute(json!({"query": "rust"}))
+            .await
+            .unwrap_err()
+            .to_string();
+        assert!(error.contains("searxng_url"));
+    }
 }
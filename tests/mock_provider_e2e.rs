@@ -0,0 +1,126 @@
+//! End-to-end tests for the scripted `mock:` provider.
+//!
+//! These run `process_message` and the gateway webhook against a mock
+//! script, so the full provider factory, tool loop and transcript are
+//! exercised offline. Each script step checks the request it answers; a
+//! mismatch fails the turn, so a passing test also proves the tool ran and
+//! its output reached the provider.
+
+use serde_json::Value;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use tempfile::TempDir;
+use zeroclaw::config::Config;
+
+const READ_NOTE_SCRIPT: &str = r#"
+native_tools = true
+
+[[steps]]
+expect = { model = "mock-model", last_message_contains = "read the note", tools_include = ["file_read"] }
+respond = { type = "tool_calls", calls = [{ name = "file_read", arguments = { path = "note.txt" } }] }
+
+[[steps]]
+expect = { last_role = "tool", last_message_contains = "mock-note-contents" }
+respond = { type = "text", text = "The note says mock-note-contents." }
+"#;
+
+/// Workspace with `note.txt` and a TOML script that reads it, plus a config
+/// pointing `default_provider` at the script.
+fn setup(tmp: &TempDir) -> (Config, PathBuf) {
+    let workspace_dir = tmp.path().join("workspace");
+    std::fs::create_dir_all(&workspace_dir).unwrap();
+    std::fs::write(workspace_dir.join("note.txt"), "mock-note-contents").unwrap();
+
+    let transcript = tmp.path().join("transcript.jsonl");
+    let script = tmp.path().join("read-note.toml");
+    std::fs::write(
+        &script,
+        format!(
+            "transcript = {}\n{READ_NOTE_SCRIPT}",
+            serde_json::to_string(&transcript).unwrap()
+        ),
+    )
+    .unwrap();
+
+    let mut config = Config::default();
+    config.workspace_dir = workspace_dir;
+    config.config_path = tmp.path().join("config.toml");
+    config.default_provider = Some(format!("mock:{}", script.display()));
+    config.default_model = Some("mock-model".into());
+    config.memory.backend = "none".into();
+    (config, transcript)
+}
+
+fn read_transcript(path: &Path) -> Vec<Value> {
+    std::fs::read_to_string(path)
+        .unwrap()
+        .lines()
+        .map(|line| serde_json::from_str(line).unwrap())
+        .collect()
+}
+
+fn assert_read_note_transcript(path: &Path) {
+    let records = read_transcript(path);
+    assert_eq!(records.len(), 2);
+    assert_eq!(records[0]["tool_calls"], serde_json::json!(["file_read"]));
+    assert_eq!(records[1]["step"], 1);
+    assert_eq!(records[1]["tool_calls"], serde_json::json!([]));
+}
+
+#[tokio::test]
+async fn process_message_runs_scripted_tool_call() {
+    let tmp = TempDir::new().unwrap();
+    let (config, transcript) = setup(&tmp);
+
+    let response = zeroclaw::agent::process_message(config, "Please read the note")
+        .await
+        .unwrap();
+
+    assert_eq!(response, "The note says mock-note-contents.");
+    assert_read_note_transcript(&transcript);
+}
+
+#[tokio::test]
+async fn gateway_webhook_runs_scripted_tool_call() {
+    let tmp = TempDir::new().unwrap();
+    let (mut config, transcript) = setup(&tmp);
+    config.gateway.require_pairing = false;
+
+    let port = std::net::TcpListener::bind("127.0.0.1:0")
+        .unwrap()
+        .local_addr()
+        .unwrap()
+        .port();
+    let gateway = tokio::spawn(Box::pin(zeroclaw::gateway::run_gateway(
+        "127.0.0.1",
+        port,
+        config,
+    )));
+
+    let client = reqwest::Client::new();
+    let base = format!("http://127.0.0.1:{port}");
+    let mut ready = false;
+    for _ in 0..100 {
+        if client.get(format!("{base}/health")).send().await.is_ok() {
+            ready = true;
+            break;
+        }
+        assert!(!gateway.is_finished(), "gateway exited before serving");
+        tokio::time::sleep(Duration::from_millis(50)).await;
+    }
+    assert!(ready, "gateway did not start listening");
+
+    let response = client
+        .post(format!("{base}/webhook"))
+        .json(&serde_json::json!({ "message": "Please read the note" }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), reqwest::StatusCode::OK);
+    let body: Value = response.json().await.unwrap();
+    assert_eq!(body["response"], "The note says mock-note-contents.");
+    assert_eq!(body["model"], "mock-model");
+    assert_read_note_transcript(&transcript);
+
+    gateway.abort();
+}
@@ -86,6 +86,8 @@ async fn provider_vision_support() -> Result<()> {
     let request = ChatRequest {
         messages: &messages,
         tools: None,
+        response_format: None,
+        params: None,
     };
 
     // Send request to provider
@@ -208,6 +210,8 @@ async fn openai_codex_second_vision_support() -> Result<()> {
     let request = ChatRequest {
         messages: &messages,
         tools: None,
+        response_format: None,
+        params: None,
     };
 
     // Send request to provider
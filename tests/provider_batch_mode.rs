@@ -0,0 +1,271 @@
+//! Batch API clients exercised against a local HTTP stand-in.
+//!
+//! Each test walks a batch through submission, an in-progress poll and the
+//! final poll that downloads results, checking the wire format on the way.
+
+use wiremock::matchers::{body_partial_json, header, method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+use zeroclaw::providers::anthropic::AnthropicProvider;
+use zeroclaw::providers::openai::OpenAiProvider;
+use zeroclaw::providers::{
+    create_batch_provider, BatchProvider, BatchRequest, BatchStatus, ChatMessage, ChatRequest,
+};
+
+fn user_request(messages: &[ChatMessage]) -> ChatRequest<'_> {
+    ChatRequest {
+        messages,
+        tools: None,
+        response_format: None,
+        params: None,
+    }
+}
+
+#[tokio::test]
+async fn openai_batch_uploads_jsonl_and_collects_results() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("POST"))
+        .and(path("/files"))
+        .respond_with(
+            ResponseTemplate::new(200).set_body_json(serde_json::json!({"id": "file-in"})),
+        )
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/batches"))
+        .and(body_partial_json(serde_json::json!({
+            "input_file_id": "file-in",
+            "endpoint": "/v1/chat/completions",
+            "completion_window": "24h"
+        })))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_json(serde_json::json!({"id": "batch_1", "status": "validating"})),
+        )
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/batches/batch_1"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_json(serde_json::json!({"id": "batch_1", "status": "in_progress"})),
+        )
+        .up_to_n_times(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/batches/batch_1"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "id": "batch_1",
+            "status": "completed",
+            "output_file_id": "file-out"
+        })))
+        .mount(&server)
+        .await;
+    let output = [
+        serde_json::json!({
+            "custom_id": "job-1",
+            "response": {
+                "status_code": 200,
+                "body": {
+                    "choices": [{"message": {"content": "Nightly summary"}}],
+                    "usage": {"prompt_tokens": 120, "completion_tokens": 30}
+                }
+            },
+            "error": null
+        }),
+        serde_json::json!({
+            "custom_id": "job-2",
+            "response": {"status_code": 400, "body": {"error": {"message": "bad model"}}},
+            "error": null
+        }),
+    ]
+    .map(|line| line.to_string())
+    .join("\n");
+    Mock::given(method("GET"))
+        .and(path("/files/file-out/content"))
+        .and(header("Authorization", "Bearer test-key"))
+        .respond_with(ResponseTemplate::new(200).set_body_string(output))
+        .mount(&server)
+        .await;
+
+    let provider = OpenAiProvider::with_base_url(Some(&server.uri()), Some("test-key"));
+    let first = [ChatMessage::user("summarize the day")];
+    let second = [ChatMessage::user("summarize the week")];
+    let requests = [
+        BatchRequest {
+            custom_id: "job-1",
+            request: user_request(&first),
+        },
+        BatchRequest {
+            custom_id: "job-2",
+            request: user_request(&second),
+        },
+    ];
+
+    let handle = provider
+        .submit_batch(&requests, "gpt-4o-mini", 0.2)
+        .await
+        .unwrap();
+    assert_eq!(handle.provider, "openai");
+    assert_eq!(handle.id, "batch_1");
+    assert_eq!(handle.model, "gpt-4o-mini");
+
+    let upload = server
+        .received_requests()
+        .await
+        .unwrap()
+        .into_iter()
+        .find(|request| request.url.path() == "/files")
+        .unwrap();
+    let upload_body = String::from_utf8_lossy(&upload.body);
+    assert!(upload_body.contains(r#""custom_id":"job-1""#));
+    assert!(upload_body.contains(r#""url":"/v1/chat/completions""#));
+    assert!(upload_body.contains("summarize the week"));
+
+    assert!(matches!(
+        provider.poll_batch(&handle).await.unwrap(),
+        BatchStatus::InProgress
+    ));
+
+    let BatchStatus::Completed(results) = provider.poll_batch(&handle).await.unwrap() else {
+        panic!("expected completed batch");
+    };
+    assert_eq!(results.len(), 2);
+    let ok = results[0].outcome.as_ref().unwrap();
+    assert_eq!(results[0].custom_id, "job-1");
+    assert_eq!(ok.text.as_deref(), Some("Nightly summary"));
+    assert_eq!(ok.usage.as_ref().unwrap().input_tokens, Some(120));
+    let err = results[1].outcome.as_ref().unwrap_err();
+    assert!(err.contains("400"), "{err}");
+}
+
+#[tokio::test]
+async fn anthropic_batch_submits_params_and_reads_results_url() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("POST"))
+        .and(path("/v1/messages/batches"))
+        .and(header("x-api-key", "test-key"))
+        .and(body_partial_json(serde_json::json!({
+            "requests": [{
+                "custom_id": "job-1",
+                "params": {"model": "claude-sonnet-4", "max_tokens": 256}
+            }]
+        })))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "id": "msgbatch_1",
+            "processing_status": "in_progress",
+            "results_url": null
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/v1/messages/batches/msgbatch_1"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "id": "msgbatch_1",
+            "processing_status": "ended",
+            "results_url": format!("{}/v1/messages/batches/msgbatch_1/results", server.uri())
+        })))
+        .mount(&server)
+        .await;
+    let results = [
+        serde_json::json!({
+            "custom_id": "job-1",
+            "result": {
+                "type": "succeeded",
+                "message": {
+                    "content": [{"type": "text", "text": "Weekly digest"}],
+                    "usage": {"input_tokens": 80, "output_tokens": 12}
+                }
+            }
+        }),
+        serde_json::json!({
+            "custom_id": "job-2",
+            "result": {
+                "type": "errored",
+                "error": {"type": "error", "error": {"type": "invalid_request_error", "message": "prompt too long"}}
+            }
+        }),
+        serde_json::json!({"custom_id": "job-3", "result": {"type": "expired"}}),
+    ]
+    .map(|line| line.to_string())
+    .join("\n");
+    Mock::given(method("GET"))
+        .and(path("/v1/messages/batches/msgbatch_1/results"))
+        .and(header("x-api-key", "test-key"))
+        .respond_with(ResponseTemplate::new(200).set_body_string(results))
+        .mount(&server)
+        .await;
+
+    let provider = AnthropicProvider::with_base_url(Some("test-key"), Some(&server.uri()));
+    let messages = [
+        ChatMessage::system("You write digests."),
+        ChatMessage::user("digest the week"),
+    ];
+    let params = zeroclaw::providers::GenerationParams {
+        max_tokens: Some(256),
+        ..zeroclaw::providers::GenerationParams::default()
+    };
+    let requests = [BatchRequest {
+        custom_id: "job-1",
+        request: ChatRequest {
+            messages: &messages,
+            tools: None,
+            response_format: None,
+            params: Some(&params),
+        },
+    }];
+
+    let handle = provider
+        .submit_batch(&requests, "claude-sonnet-4", 0.7)
+        .await
+        .unwrap();
+    assert_eq!(handle.provider, "anthropic");
+    assert_eq!(handle.id, "msgbatch_1");
+
+    let BatchStatus::Completed(results) = provider.poll_batch(&handle).await.unwrap() else {
+        panic!("expected completed batch");
+    };
+    assert_eq!(results.len(), 3);
+    let ok = results[0].outcome.as_ref().unwrap();
+    assert_eq!(ok.text.as_deref(), Some("Weekly digest"));
+    assert_eq!(ok.usage.as_ref().unwrap().output_tokens, Some(12));
+    assert_eq!(results[1].outcome.as_ref().unwrap_err(), "prompt too long");
+    assert!(results[2].outcome.as_ref().unwrap_err().contains("expired"));
+}
+
+#[tokio::test]
+async fn batch_api_errors_surface_status() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/v1/messages/batches"))
+        .respond_with(ResponseTemplate::new(401).set_body_string("invalid x-api-key"))
+        .mount(&server)
+        .await;
+
+    let provider = AnthropicProvider::with_base_url(Some("bad-key"), Some(&server.uri()));
+    let messages = [ChatMessage::user("hi")];
+    let requests = [BatchRequest {
+        custom_id: "job-1",
+        request: user_request(&messages),
+    }];
+    let err = provider
+        .submit_batch(&requests, "claude-sonnet-4", 0.7)
+        .await
+        .unwrap_err();
+    assert!(err.to_string().contains("401"), "{err}");
+}
+
+#[test]
+fn batch_provider_factory_rejects_providers_without_batch_api() {
+    assert!(create_batch_provider("openai", Some("key"), None).is_ok());
+    assert!(create_batch_provider("anthropic", Some("key"), None).is_ok());
+    let err = create_batch_provider("openrouter", Some("key"), None)
+        .err()
+        .expect("openrouter has no batch API");
+    assert!(err.to_string().contains("no batch API"));
+}
@@ -64,6 +64,10 @@ export default function AgentChat() {
           pendingContentRef.current += msg.content ?? '';
           break;
 
+        case 'clear':
+          pendingContentRef.current = '';
+          break;
+
         case 'message':
         case 'done': {
           const content = (msg.full_response ?? msg.content ?? pendingContentRef.current ?? '').trim();